@@ -88,7 +88,11 @@ async fn async_simple() {
     "#;
 
     let vars = Default::default();
-    let (res, errs) = juniper::execute(doc, None, &schema, &vars, &())
+    let juniper::ExecutionOutput {
+        data: res,
+        errors: errs,
+        ..
+    } = juniper::execute(doc, None, &schema, &vars, &())
         .await
         .unwrap();
 
@@ -0,0 +1,6 @@
+#[derive(juniper::GraphQLEnum)]
+pub enum Test {
+    Test(i32),
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+trait Node {
+    fn id(&self) -> &str;
+}
+
+struct Human {
+    id: String,
+}
+
+impl Node for Human {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+juniper::graphql_interface!(<'a> &'a dyn Node: () as "Node" |&self| {
+    field id(short: bool) -> &str { self.id() }
+
+    instance_resolvers: |_| {
+        &Human => None::<&Human>,
+    }
+});
+
+#[juniper::graphql_object(interfaces = [&dyn Node])]
+impl Human {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+fn main() {}
@@ -2,7 +2,10 @@
 use fnv::FnvHashMap;
 
 #[cfg(test)]
-use juniper::{self, DefaultScalarValue, FromInputValue, GraphQLType, InputValue, ToInputValue};
+use juniper::{
+    self, meta::MetaType, DefaultScalarValue, EmptyMutation, EmptySubscription, FromInputValue,
+    GraphQLType, InputValue, RootNode, ToInputValue, Value, Variables,
+};
 
 pub struct CustomContext {}
 
@@ -49,6 +52,37 @@ enum ContextEnum {
     A,
 }
 
+#[derive(juniper::GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "camelCase")]
+enum MigratedEnum {
+    NewName,
+    #[graphql(name = "OTHER", alias = "LEGACY_OTHER", alias = "older")]
+    Other,
+    #[graphql(ignore)]
+    #[allow(dead_code)]
+    Internal(u8),
+}
+
+#[derive(juniper::GraphQLEnum, Debug, PartialEq)]
+enum DomainEnum {
+    Pending,
+    #[graphql(default)]
+    Failed {
+        reason: String,
+    },
+    #[graphql(default = "DomainEnum::Retrying(3)")]
+    Retrying(u8),
+}
+
+struct MigratedQuery;
+
+#[juniper::graphql_object]
+impl MigratedQuery {
+    fn echo(value: MigratedEnum) -> MigratedEnum {
+        value
+    }
+}
+
 #[test]
 fn test_derived_enum() {
     // Ensure that rename works.
@@ -83,6 +117,16 @@ fn test_derived_enum() {
         FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("FULL")),
         Some(SomeEnum::Full)
     );
+
+    // Test invalid values.
+    assert_eq!(
+        <SomeEnum as FromInputValue<DefaultScalarValue>>::try_from_input_value(
+            &InputValue::scalar("PARTIAL")
+        )
+        .unwrap_err()
+        .to_string(),
+        r#"Expected "Some": expected one of: REGULAR, FULL"#,
+    );
 }
 
 #[test]
@@ -121,3 +165,83 @@ fn test_doc_custom_context() {
     test_context(ContextEnum::A);
     // test_context(OverrideDocEnum::Foo); does not work
 }
+
+#[test]
+fn test_rename_all_alias_and_ignore() {
+    let mut registry: juniper::Registry = juniper::Registry::new(FnvHashMap::default());
+    let values = match MigratedEnum::meta(&(), &mut registry) {
+        MetaType::Enum(meta) => meta
+            .values
+            .iter()
+            .map(|v| v.name.clone())
+            .collect::<Vec<_>>(),
+        _ => panic!("Expected an enum"),
+    };
+    assert_eq!(values, vec!["newName", "OTHER"]);
+
+    for name in &["OTHER", "LEGACY_OTHER", "older"] {
+        assert_eq!(
+            FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar(*name)),
+            Some(MigratedEnum::Other),
+        );
+    }
+    assert_eq!(
+        <MigratedEnum as FromInputValue<DefaultScalarValue>>::from_input_value(
+            &InputValue::scalar("internal")
+        ),
+        None,
+    );
+}
+
+#[tokio::test]
+async fn test_alias_in_literal() {
+    let schema = RootNode::new(
+        MigratedQuery,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = juniper::execute(
+        "{ echo(value: LEGACY_OTHER) }",
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .await
+    .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result.as_object_value().unwrap().get_field_value("echo"),
+        Some(&Value::scalar("OTHER")),
+    );
+}
+
+#[test]
+fn test_variants_carrying_data() {
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DomainEnum::Failed {
+            reason: "timeout".into()
+        }),
+        InputValue::scalar("FAILED"),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DomainEnum::Retrying(1)),
+        InputValue::scalar("RETRYING"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("FAILED")),
+        Some(DomainEnum::Failed {
+            reason: String::new()
+        }),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("RETRYING")),
+        Some(DomainEnum::Retrying(3)),
+    );
+}
@@ -47,6 +47,15 @@ struct OverrideDocComment {
     regular_field: bool,
 }
 
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct WithSkippedFields {
+    regular_field: String,
+    #[graphql(skip)]
+    internal_tag: Vec<String>,
+    #[graphql(skip, default = "\"server\".to_owned()")]
+    source: String,
+}
+
 #[derive(Debug, PartialEq)]
 struct Fake;
 
@@ -172,3 +181,39 @@ fn test_doc_comment_override() {
     let meta = OverrideDocComment::meta(&(), &mut registry);
     assert_eq!(meta.description(), Some(&"obj override".to_string()));
 }
+
+#[test]
+fn test_skipped_fields() {
+    let mut registry: juniper::Registry = juniper::Registry::new(FnvHashMap::default());
+    let meta = WithSkippedFields::meta(&(), &mut registry);
+    let field_names = match meta {
+        juniper::meta::MetaType::InputObject(ref meta) => meta
+            .input_fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>(),
+        _ => panic!("Expected an input object"),
+    };
+    assert_eq!(field_names, vec!["regularField"]);
+
+    let input: InputValue = ::serde_json::from_value(serde_json::json!({
+        "regularField": "a",
+    }))
+    .unwrap();
+
+    let output: WithSkippedFields = FromInputValue::from_input_value(&input).unwrap();
+    assert_eq!(
+        output,
+        WithSkippedFields {
+            regular_field: "a".into(),
+            internal_tag: vec![],
+            source: "server".into(),
+        }
+    );
+
+    let expected: InputValue = ::serde_json::from_value(serde_json::json!({
+        "regularField": "a",
+    }))
+    .unwrap();
+    assert_eq!(output.to_input_value(), expected);
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 #[cfg(test)]
 use fnv::FnvHashMap;
 #[cfg(test)]
@@ -72,6 +74,33 @@ struct SkippedFieldObj {
     skipped: i32,
 }
 
+/// A user account.
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[graphql(input_object = "UserInput")]
+struct User {
+    #[graphql(skip_input)]
+    id: i32,
+    #[graphql(name = "login")]
+    name: String,
+    #[graphql(skip)]
+    password: String,
+}
+
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[graphql(scalar = DefaultScalarValue)]
+struct WithMaps {
+    scores: BTreeMap<String, i32>,
+    #[graphql(name = "nestedById")]
+    nested: HashMap<i32, Vec<Nested>>,
+}
+
+#[derive(GraphQLObject, Debug, PartialEq)]
+struct WithTuple {
+    #[graphql(tuple(name = "Point", fields("x", "y")))]
+    position: (i32, i32),
+    corners: [i32; 2],
+}
+
 struct Context;
 impl juniper::Context for Context {}
 
@@ -123,6 +152,41 @@ impl Query {
             skipped: 42,
         }
     }
+
+    fn with_maps() -> WithMaps {
+        WithMaps {
+            scores: vec![("b".to_string(), 2), ("a".to_string(), 1)]
+                .into_iter()
+                .collect(),
+            nested: vec![7, 3, 5]
+                .into_iter()
+                .map(|id| {
+                    let nested = Nested {
+                        obj: Obj {
+                            regular_field: true,
+                            c: 1,
+                        },
+                    };
+                    (id, vec![nested])
+                })
+                .collect(),
+        }
+    }
+
+    fn with_tuple() -> WithTuple {
+        WithTuple {
+            position: (1, 2),
+            corners: [3, 4],
+        }
+    }
+
+    fn register(user: UserInput) -> User {
+        User {
+            id: 1,
+            name: user.name,
+            password: user.password,
+        }
+    }
 }
 
 #[tokio::test]
@@ -201,26 +265,28 @@ async fn test_derived_object() {
         EmptySubscription::<()>::new(),
     );
 
+    let output = execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .unwrap();
+
+    assert_eq!(output.errors, []);
     assert_eq!(
-        execute(doc, None, &schema, &Variables::new(), &()).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "obj",
-                    Value::object(
-                        vec![
-                            ("regularField", Value::scalar(true)),
-                            ("renamedField", Value::scalar(22)),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        output.data,
+        Value::object(
+            vec![(
+                "obj",
+                Value::object(
+                    vec![
+                        ("regularField", Value::scalar(true)),
+                        ("renamedField", Value::scalar(22)),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )
     );
 }
 
@@ -262,50 +328,295 @@ async fn test_skipped_field_siblings_unaffected() {
 }
 
 #[tokio::test]
-async fn test_derived_object_nested() {
+async fn test_derived_object_with_input_object() {
     let doc = r#"
         {
-            nested {
-                obj {
-                    regularField
-                    renamedField
+            register(user: { login: "alice", password: "secret" }) {
+                id
+                login
+            }
+        }"#;
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()).await,
+        Ok(juniper::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "register",
+                Value::object(
+                    vec![("id", Value::scalar(1)), ("login", Value::scalar("alice"))]
+                        .into_iter()
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_input_object_introspection() {
+    let doc = r#"
+        {
+            __type(name: "UserInput") {
+                kind
+                description
+                inputFields {
+                    name
                 }
             }
         }"#;
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+    assert_eq!(errs, []);
 
+    let type_info = result
+        .as_object_value()
+        .unwrap()
+        .get_field_value("__type")
+        .unwrap()
+        .as_object_value()
+        .unwrap();
+    assert_eq!(
+        type_info.get_field_value("kind"),
+        Some(&Value::scalar("INPUT_OBJECT"))
+    );
+    assert_eq!(
+        type_info.get_field_value("description"),
+        Some(&Value::scalar("A user account."))
+    );
+    assert_eq!(
+        type_info.get_field_value("inputFields"),
+        Some(&Value::list(vec![
+            Value::object(vec![("name", Value::scalar("login"))].into_iter().collect()),
+            Value::object(
+                vec![("name", Value::scalar("password"))]
+                    .into_iter()
+                    .collect()
+            ),
+        ]))
+    );
+}
+
+#[tokio::test]
+async fn test_map_fields_as_entry_lists() {
+    let doc = r#"
+        {
+            withMaps {
+                scores { key value }
+                nestedById { key value { obj { regularField } } }
+            }
+        }"#;
     let schema = RootNode::new(
         Query,
         EmptyMutation::<()>::new(),
         EmptySubscription::<()>::new(),
     );
 
+    let entry = |key: Value, value: Value| {
+        Value::object(vec![("key", key), ("value", value)].into_iter().collect())
+    };
+    let nested_value = Value::list(vec![Value::object(
+        vec![(
+            "obj",
+            Value::object(
+                vec![("regularField", Value::scalar(true))]
+                    .into_iter()
+                    .collect(),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    )]);
+
     assert_eq!(
         execute(doc, None, &schema, &Variables::new(), &()).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "nested",
+        Ok(juniper::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "withMaps",
+                Value::object(
+                    vec![
+                        (
+                            "scores",
+                            Value::list(vec![
+                                entry(Value::scalar("a"), Value::scalar(1)),
+                                entry(Value::scalar("b"), Value::scalar(2)),
+                            ]),
+                        ),
+                        (
+                            "nestedById",
+                            Value::list(vec![
+                                entry(Value::scalar(3), nested_value.clone()),
+                                entry(Value::scalar(5), nested_value.clone()),
+                                entry(Value::scalar(7), nested_value),
+                            ]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_map_entry_introspection() {
+    let doc = r#"
+        {
+            __type(name: "WithMapsNestedByIdEntry") {
+                name
+                fields {
+                    name
+                    type {
+                        ofType {
+                            name
+                        }
+                    }
+                }
+            }
+        }"#;
+
+    run_type_info_query(doc, |(type_info, fields)| {
+        assert_eq!(
+            type_info.get_field_value("name"),
+            Some(&Value::scalar("WithMapsNestedByIdEntry"))
+        );
+        assert_eq!(fields.len(), 2);
+        assert!(fields.contains(&Value::object(
+            vec![
+                ("name", Value::scalar("key")),
+                (
+                    "type",
                     Value::object(
                         vec![(
-                            "obj",
+                            "ofType",
                             Value::object(
-                                vec![
-                                    ("regularField", Value::scalar(false)),
-                                    ("renamedField", Value::scalar(333)),
-                                ]
-                                .into_iter()
-                                .collect(),
+                                vec![("name", Value::scalar("Int"))].into_iter().collect()
                             ),
                         )]
                         .into_iter()
                         .collect(),
                     ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_tuple_and_array_fields() {
+    let doc = r#"
+        {
+            withTuple {
+                position { x y }
+                corners
+            }
+        }"#;
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()).await,
+        Ok(juniper::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "withTuple",
+                Value::object(
+                    vec![
+                        (
+                            "position",
+                            Value::object(
+                                vec![("x", Value::scalar(1)), ("y", Value::scalar(2))]
+                                    .into_iter()
+                                    .collect(),
+                            ),
+                        ),
+                        (
+                            "corners",
+                            Value::list(vec![Value::scalar(3), Value::scalar(4)]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_derived_object_nested() {
+    let doc = r#"
+        {
+            nested {
+                obj {
+                    regularField
+                    renamedField
+                }
+            }
+        }"#;
+
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let output = execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .unwrap();
+
+    assert_eq!(output.errors, []);
+    assert_eq!(
+        output.data,
+        Value::object(
+            vec![(
+                "nested",
+                Value::object(
+                    vec![(
+                        "obj",
+                        Value::object(
+                            vec![
+                                ("regularField", Value::scalar(false)),
+                                ("renamedField", Value::scalar(333)),
+                            ]
+                            .into_iter()
+                            .collect(),
+                        ),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )
     );
 }
 
@@ -363,7 +674,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = execute(doc, None, &schema, &Variables::new(), &())
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
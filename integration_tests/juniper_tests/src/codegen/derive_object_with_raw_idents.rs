@@ -1,7 +1,7 @@
 #[cfg(test)]
 use juniper::{
-    self, execute, graphql_value, EmptyMutation, EmptySubscription, GraphQLInputObject, RootNode,
-    Value, Variables,
+    self, execute, graphql_value, EmptyMutation, EmptySubscription, GraphQLEnum,
+    GraphQLInputObject, GraphQLObject, RootNode, Value, Variables,
 };
 
 pub struct Query;
@@ -12,6 +12,31 @@ impl Query {
         let _ = r#fn;
         unimplemented!()
     }
+
+    #[graphql(arguments(where(description = "Keyword argument")))]
+    fn r#match(r#where: i32, r#loop_count: i32) -> MyObjectType {
+        MyObjectType {
+            r#type: if r#where > r#loop_count {
+                MyEnum::r#struct
+            } else {
+                MyEnum::r#enum
+            },
+            r#async_field: true,
+        }
+    }
+}
+
+#[derive(GraphQLObject)]
+struct MyObjectType {
+    r#type: MyEnum,
+    r#async_field: bool,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(GraphQLEnum)]
+enum MyEnum {
+    r#struct,
+    r#enum,
 }
 
 #[derive(GraphQLInputObject, Debug, PartialEq)]
@@ -49,6 +74,17 @@ async fn supports_raw_idents_in_types_and_args() {
                                     "name": "fn"
                                 }
                             ]
+                        },
+                        {
+                            "name": "match",
+                            "args": [
+                                {
+                                    "name": "where"
+                                },
+                                {
+                                    "name": "loopCount"
+                                }
+                            ]
                         }
                     ]
                 }
@@ -87,6 +123,71 @@ async fn supports_raw_idents_in_fields_of_input_types() {
     );
 }
 
+#[tokio::test]
+async fn supports_raw_idents_in_object_fields_and_enum_values() {
+    let doc = r#"
+    {
+        match(where: 2, loopCount: 1) {
+            type
+            asyncField
+        }
+        __type(name: "MyEnum") {
+            enumValues {
+                name
+            }
+        }
+    }
+    "#;
+
+    let value = run_type_info_query(&doc).await;
+
+    assert_eq!(
+        value,
+        graphql_value!({
+            "match": {
+                "type": "STRUCT",
+                "asyncField": true,
+            },
+            "__type": {
+                "enumValues": [{"name": "STRUCT"}, {"name": "ENUM"}],
+            },
+        }),
+    );
+}
+
+#[tokio::test]
+async fn supports_descriptions_of_raw_ident_args() {
+    let doc = r#"
+    {
+        __type(name: "Query") {
+            fields {
+                args {
+                    name
+                    description
+                }
+            }
+        }
+    }
+    "#;
+
+    let value = run_type_info_query(&doc).await;
+
+    assert_eq!(
+        value,
+        graphql_value!({
+            "__type": {
+                "fields": [
+                    {"args": [{"name": "fn", "description": None}]},
+                    {"args": [
+                        {"name": "where", "description": "Keyword argument"},
+                        {"name": "loopCount", "description": None},
+                    ]},
+                ],
+            },
+        }),
+    );
+}
+
 #[cfg(test)]
 async fn run_type_info_query(doc: &str) -> Value {
     let schema = RootNode::new(
@@ -95,7 +196,11 @@ async fn run_type_info_query(doc: &str) -> Value {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = execute(doc, None, &schema, &Variables::new(), &())
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -60,7 +60,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = execute(doc, None, &schema, &Variables::new(), &())
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -97,3 +101,270 @@ mod fallible {
         }
     }
 }
+
+mod tuples_and_arrays {
+    use super::*;
+
+    struct Query;
+
+    #[juniper::graphql_object]
+    impl Query {
+        #[graphql(tuple(name = "MinMax", fields("min", "max")))]
+        fn bounds(values: Vec<i32>) -> (i32, i32) {
+            if values.is_empty() {
+                return (0, 0);
+            }
+            (*values.iter().min().unwrap(), *values.iter().max().unwrap())
+        }
+
+        #[graphql(tuple = "Pair")]
+        async fn pair() -> (String, bool) {
+            ("a".into(), true)
+        }
+
+        fn rgb() -> [i32; 3] {
+            [255, 128, 0]
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_tuples_as_objects_and_arrays_as_lists() {
+        let doc = r#"
+            {
+                bounds(values: [3, 1, 2]) { min max }
+                empty: bounds(values: []) { min max }
+                pair { _0 _1 }
+                rgb
+            }"#;
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let object =
+            |fields: Vec<(&'static str, Value)>| Value::object(fields.into_iter().collect());
+        assert_eq!(
+            execute(doc, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(object(vec![
+                (
+                    "bounds",
+                    object(vec![("min", Value::scalar(1)), ("max", Value::scalar(3))]),
+                ),
+                (
+                    "empty",
+                    object(vec![("min", Value::scalar(0)), ("max", Value::scalar(0))]),
+                ),
+                (
+                    "pair",
+                    object(vec![
+                        ("_0", Value::scalar("a")),
+                        ("_1", Value::scalar(true))
+                    ]),
+                ),
+                (
+                    "rgb",
+                    Value::list(vec![
+                        Value::scalar(255),
+                        Value::scalar(128),
+                        Value::scalar(0),
+                    ]),
+                ),
+            ])))
+        );
+    }
+}
+
+mod sequences {
+    use futures::stream;
+    use juniper::graphql_value;
+
+    use super::*;
+
+    struct Query {
+        values: Vec<i32>,
+    }
+
+    #[juniper::graphql_object]
+    impl Query {
+        fn evens(&self) -> impl Iterator<Item = i32> + '_ {
+            self.values.iter().copied().filter(|v| v % 2 == 0)
+        }
+
+        fn odds(&self, limit: i32) -> Box<dyn Iterator<Item = &i32> + '_> {
+            if limit == 0 {
+                return Box::new(std::iter::empty());
+            }
+            Box::new(
+                self.values
+                    .iter()
+                    .filter(|v| *v % 2 == 1)
+                    .take(limit as usize),
+            )
+        }
+
+        async fn doubled(&self) -> impl futures::Stream<Item = i32> + '_ {
+            stream::iter(self.values.iter().map(|v| v * 2))
+        }
+
+        async fn names() -> std::pin::Pin<Box<dyn futures::Stream<Item = String> + Send>> {
+            Box::pin(stream::iter(vec!["a".into(), "b".into()]))
+        }
+    }
+
+    #[tokio::test]
+    async fn collects_iterators_and_streams_into_lists() {
+        let doc = r#"{
+            evens
+            odds(limit: 1)
+            none: odds(limit: 0)
+            doubled
+            names
+        }"#;
+        let schema = RootNode::new(
+            Query {
+                values: vec![1, 2, 3, 4],
+            },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let list = |values: Vec<Value>| Value::list(values);
+        assert_eq!(
+            execute(doc, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(Value::object(
+                vec![
+                    ("evens", list(vec![Value::scalar(2), Value::scalar(4)])),
+                    ("odds", list(vec![Value::scalar(1)])),
+                    ("none", list(vec![])),
+                    (
+                        "doubled",
+                        list(vec![
+                            Value::scalar(2),
+                            Value::scalar(4),
+                            Value::scalar(6),
+                            Value::scalar(8),
+                        ]),
+                    ),
+                    ("names", list(vec![Value::scalar("a"), Value::scalar("b")])),
+                ]
+                .into_iter()
+                .collect(),
+            ))),
+        );
+    }
+
+    #[tokio::test]
+    async fn registers_sequences_as_lists() {
+        let doc = r#"{
+            __type(name: "Query") {
+                fields {
+                    name
+                    type {
+                        kind
+                        ofType {
+                            kind
+                            ofType {
+                                kind
+                                ofType { name }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#;
+        let schema = RootNode::new(
+            Query { values: vec![] },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let list_of = |name| {
+            graphql_value!({
+                "kind": "NON_NULL",
+                "ofType": {
+                    "kind": "LIST",
+                    "ofType": {"kind": "NON_NULL", "ofType": {"name": name}},
+                },
+            })
+        };
+        assert_eq!(
+            execute(doc, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"fields": [
+                    {"name": "evens", "type": (list_of("Int"))},
+                    {"name": "odds", "type": (list_of("Int"))},
+                    {"name": "doubled", "type": (list_of("Int"))},
+                    {"name": "names", "type": (list_of("String"))},
+                ]}})
+            )),
+        );
+    }
+}
+
+mod lifetimes {
+    use juniper::graphql_value;
+
+    use super::*;
+
+    struct Db {
+        names: Vec<String>,
+    }
+
+    struct User<'a> {
+        name: &'a str,
+    }
+
+    #[juniper::graphql_object]
+    impl<'a> User<'a> {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn shout(&self) -> String {
+            self.name.to_uppercase()
+        }
+    }
+
+    struct Query<'a> {
+        db: &'a Db,
+    }
+
+    #[juniper::graphql_object]
+    impl<'a> Query<'a> {
+        fn users(&self) -> Vec<User<'a>> {
+            self.db.names.iter().map(|name| User { name }).collect()
+        }
+
+        async fn first(&self) -> Option<User<'a>> {
+            self.db.names.first().map(|name| User { name })
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_objects_borrowing_from_root() {
+        let db = Db {
+            names: vec!["alice".into(), "bob".into()],
+        };
+        let schema = RootNode::new(
+            Query { db: &db },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute(
+                "{ users { name } first { name shout } }",
+                None,
+                &schema,
+                &Variables::new(),
+                &(),
+            )
+            .await,
+            Ok(juniper::ExecutionOutput::from_data(graphql_value!({
+                "users": [{"name": "alice"}, {"name": "bob"}],
+                "first": {"name": "alice", "shout": "ALICE"},
+            }))),
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use juniper::{
-    DefaultScalarValue, EmptyMutation, EmptySubscription, Object, ParseScalarResult,
-    ParseScalarValue, RootNode, Value, Variables,
+    DefaultScalarValue, EmptyMutation, EmptySubscription, FromInputError, GraphQLError, InputValue,
+    Object, ParseScalarResult, ParseScalarValue, RootNode, Value, Variables,
 };
 
 struct DefaultName(i32);
 struct OtherOrder(i32);
 struct Named(i32);
 struct ScalarDescription(i32);
+struct Percent(i32);
 
 struct Root;
 
@@ -84,6 +85,27 @@ impl GraphQLScalar for ScalarDescription {
     }
 }
 
+#[juniper::graphql_scalar(description = "An integer between 0 and 100")]
+impl GraphQLScalar for Percent {
+    fn resolve(&self) -> Value {
+        Value::scalar(self.0)
+    }
+
+    fn try_from_input_value(v: &juniper::InputValue) -> Result<Percent, FromInputError> {
+        let i = v
+            .as_scalar_value::<i32>()
+            .ok_or_else(|| FromInputError::new("not an integer"))?;
+        if *i < 0 || *i > 100 {
+            return Err(FromInputError::new("must be between 0 and 100"));
+        }
+        Ok(Percent(*i))
+    }
+
+    fn from_str<'a>(value: juniper::ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
+        <i32 as ParseScalarValue>::from_str(value)
+    }
+}
+
 #[juniper::graphql_object]
 impl Root {
     fn default_name() -> DefaultName {
@@ -98,6 +120,9 @@ impl Root {
     fn scalar_description() -> ScalarDescription {
         ScalarDescription(0)
     }
+    fn percent(value: Percent) -> Percent {
+        value
+    }
 }
 
 async fn run_type_info_query<F>(doc: &str, f: F)
@@ -110,7 +135,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = juniper::execute(doc, None, &schema, &Variables::new(), &())
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = juniper::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -246,3 +275,117 @@ async fn scalar_description_introspection() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn fallible_input_conversion() {
+    let schema = RootNode::new(
+        Root {},
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let doc = "query Q($value: Percent!) { percent(value: $value) }";
+
+    let vars = vec![("value".to_owned(), InputValue::scalar(42))]
+        .into_iter()
+        .collect();
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = juniper::execute(doc, None, &schema, &vars, &())
+        .await
+        .expect("Execution failed");
+    assert_eq!(errs, []);
+    assert_eq!(
+        result.as_object_value().unwrap().get_field_value("percent"),
+        Some(&Value::scalar(42)),
+    );
+
+    let vars = vec![("value".to_owned(), InputValue::scalar(142))]
+        .into_iter()
+        .collect();
+    let err = juniper::execute(doc, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+    match err {
+        GraphQLError::ValidationError(errs) => assert_eq!(
+            errs[0].message(),
+            r#"Variable "$value" got invalid value. Expected "Percent": must be between 0 and 100."#,
+        ),
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+mod generic {
+    use std::{fmt::Display, str::FromStr};
+
+    use juniper::{graphql_value, DefaultScalarValue, ParseScalarResult, Value};
+
+    use super::{EmptyMutation, EmptySubscription, ParseScalarValue, RootNode, Variables};
+
+    /// Value serialized as a string.
+    struct Text<T>(T);
+
+    #[juniper::graphql_scalar(
+        description = "A value serialized as a string",
+        instances(template = "{}Text", i32, bool = "Flag")
+    )]
+    impl<T> GraphQLScalar for Text<T>
+    where
+        T: Display + FromStr,
+    {
+        fn resolve(&self) -> Value {
+            Value::scalar(self.0.to_string())
+        }
+
+        fn from_input_value(v: &juniper::InputValue) -> Option<Text<T>> {
+            v.as_string_value()
+                .and_then(|s| s.parse::<T>().ok())
+                .map(Text)
+        }
+
+        fn from_str<'a>(
+            value: juniper::parser::ScalarToken<'a>,
+        ) -> ParseScalarResult<'a, DefaultScalarValue> {
+            <String as ParseScalarValue>::from_str(value)
+        }
+    }
+
+    struct Query;
+
+    #[juniper::graphql_object]
+    impl Query {
+        fn increment(value: Text<i32>) -> Text<i32> {
+            Text(value.0 + 1)
+        }
+
+        fn negate(value: Text<bool>) -> Text<bool> {
+            Text(!value.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn instantiates_each_type_argument() {
+        let doc = r#"{
+            increment(value: "41")
+            negate(value: "false")
+            int: __type(name: "i32Text") { name description }
+            flag: __type(name: "Flag") { name description }
+        }"#;
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            juniper::execute(doc, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(graphql_value!({
+                "increment": "42",
+                "negate": "true",
+                "int": {"name": "i32Text", "description": "A value serialized as a string"},
+                "flag": {"name": "Flag", "description": "A value serialized as a string"},
+            }))),
+        );
+    }
+}
@@ -131,9 +131,8 @@ mod trivial {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -144,9 +143,8 @@ mod trivial {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -163,7 +161,9 @@ mod trivial {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"kind": "UNION"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"kind": "UNION"}})
+            )),
         );
     }
 
@@ -179,7 +179,9 @@ mod trivial {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "Character"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "Character"}})
+            )),
         );
     }
 
@@ -195,7 +197,9 @@ mod trivial {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"description": None}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": None}})
+            )),
         );
     }
 }
@@ -268,9 +272,8 @@ mod generic {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -281,9 +284,8 @@ mod generic {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -300,7 +302,9 @@ mod generic {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "Character"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "Character"}})
+            )),
         );
     }
 }
@@ -351,9 +355,8 @@ mod description_from_doc_comments {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -370,9 +373,8 @@ mod description_from_doc_comments {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"description": "Rust docs."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "Rust docs."}})
             )),
         );
     }
@@ -424,9 +426,8 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -443,7 +444,9 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "MyChar"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "MyChar"}})
+            )),
         );
     }
 
@@ -459,9 +462,8 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"description": "My character."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "My character."}})
             )),
         );
     }
@@ -535,9 +537,8 @@ mod explicit_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -548,9 +549,8 @@ mod explicit_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -626,9 +626,8 @@ mod custom_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -639,9 +638,8 @@ mod custom_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -713,9 +711,8 @@ mod inferred_custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -726,9 +723,8 @@ mod inferred_custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -800,9 +796,8 @@ mod explicit_custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -813,9 +808,8 @@ mod explicit_custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -872,9 +866,8 @@ mod ignored_methods {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -893,9 +886,8 @@ mod ignored_methods {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"possibleTypes": [{"name": "Human"}]}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"possibleTypes": [{"name": "Human"}]}})
             )),
         );
     }
@@ -975,9 +967,8 @@ mod external_resolver {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -994,9 +985,8 @@ mod external_resolver {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -1098,9 +1088,8 @@ mod full_featured {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -1111,9 +1100,8 @@ mod full_featured {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -1124,9 +1112,8 @@ mod full_featured {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((
-                graphql_value!({"character": {"ewokId": "ewok-1", "funny": true}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"ewokId": "ewok-1", "funny": true}})
             )),
         );
     }
@@ -1143,7 +1130,9 @@ mod full_featured {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((graphql_value!({"__type": {"name": "MyChar"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "MyChar"}})
+            )),
         );
     }
 
@@ -1159,9 +1148,8 @@ mod full_featured {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((
-                graphql_value!({"__type": {"description": "My character."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "My character."}})
             )),
         );
     }
@@ -114,9 +114,8 @@ mod trivial_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -127,9 +126,8 @@ mod trivial_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -146,7 +144,9 @@ mod trivial_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"kind": "UNION"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"kind": "UNION"}})
+            )),
         );
     }
 
@@ -162,7 +162,9 @@ mod trivial_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "Character"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "Character"}})
+            )),
         );
     }
 
@@ -178,7 +180,9 @@ mod trivial_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"description": None}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": None}})
+            )),
         );
     }
 }
@@ -234,9 +238,8 @@ mod generic_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -247,9 +250,8 @@ mod generic_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -266,7 +268,9 @@ mod generic_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "Character"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "Character"}})
+            )),
         );
     }
 }
@@ -307,9 +311,8 @@ mod description_from_doc_comments {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -326,9 +329,8 @@ mod description_from_doc_comments {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"description": "Rust docs."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "Rust docs."}})
             )),
         );
     }
@@ -371,9 +373,8 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -390,7 +391,9 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((graphql_value!({"__type": {"name": "MyChar"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "MyChar"}})
+            )),
         );
     }
 
@@ -406,9 +409,8 @@ mod explicit_name_and_description {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"description": "My character."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "My character."}})
             )),
         );
     }
@@ -464,9 +466,8 @@ mod explicit_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -477,9 +478,8 @@ mod explicit_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -537,9 +537,8 @@ mod custom_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -550,9 +549,8 @@ mod custom_scalar {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -606,9 +604,8 @@ mod custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -619,9 +616,8 @@ mod custom_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -675,9 +671,8 @@ mod different_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -688,9 +683,8 @@ mod different_context {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -735,9 +729,8 @@ mod ignored_enum_variants {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -756,9 +749,8 @@ mod ignored_enum_variants {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &()).await,
-            Ok((
-                graphql_value!({"__type": {"possibleTypes": [{"name": "Human"}]}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"possibleTypes": [{"name": "Human"}]}})
             )),
         );
     }
@@ -829,9 +821,8 @@ mod external_resolver_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -848,9 +839,8 @@ mod external_resolver_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -923,9 +913,8 @@ mod external_resolver_enum_variant {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -942,9 +931,8 @@ mod external_resolver_enum_variant {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -1036,9 +1024,8 @@ mod full_featured_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Human).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -1049,9 +1036,8 @@ mod full_featured_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Droid).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -1062,9 +1048,8 @@ mod full_featured_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((
-                graphql_value!({"character": {"ewokId": "ewok-1", "funny": true}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"ewokId": "ewok-1", "funny": true}})
             )),
         );
     }
@@ -1081,7 +1066,9 @@ mod full_featured_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((graphql_value!({"__type": {"name": "MyChar"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "MyChar"}})
+            )),
         );
     }
 
@@ -1097,9 +1084,8 @@ mod full_featured_enum {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &CustomContext::Ewok).await,
-            Ok((
-                graphql_value!({"__type": {"description": "My character."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "My character."}})
             )),
         );
     }
@@ -1188,9 +1174,8 @@ mod trivial_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -1208,9 +1193,108 @@ mod trivial_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
+            )),
+        );
+    }
+}
+
+mod type_resolver_struct {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(GraphQLUnion)]
+    #[graphql(
+        resolve_type = Character::kind,
+        on Human = Character::to_human,
+        on Droid = Character::to_droid,
+    )]
+    struct Character {
+        fields: HashMap<&'static str, &'static str>,
+    }
+
+    impl Character {
+        fn kind(&self, _: &()) -> &str {
+            self.fields["kind"]
+        }
+
+        fn to_human(&self, _: &()) -> Human {
+            Human {
+                id: self.fields["id"].to_string(),
+                home_planet: self.fields["planet"].to_string(),
+            }
+        }
+
+        fn to_droid(&self, _: &()) -> Droid {
+            Droid {
+                id: self.fields["id"].to_string(),
+                primary_function: self.fields["function"].to_string(),
+            }
+        }
+    }
+
+    enum QueryRoot {
+        Human,
+        Droid,
+    }
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn character(&self) -> Character {
+            let fields = match self {
+                Self::Human => vec![("kind", "Human"), ("id", "human-32"), ("planet", "earth")],
+                Self::Droid => vec![("kind", "Droid"), ("id", "droid-99"), ("function", "run")],
+            };
+            Character {
+                fields: fields.into_iter().collect(),
+            }
+        }
+    }
+
+    const DOC: &str = r#"{
+        character {
+            __typename
+            ... on Human {
+                humanId: id
+                homePlanet
+            }
+            ... on Droid {
+                droidId: id
+                primaryFunction
+            }
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn resolves_human() {
+        let schema = schema(QueryRoot::Human);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {
+                    "__typename": "Human",
+                    "humanId": "human-32",
+                    "homePlanet": "earth",
+                }})
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_droid() {
+        let schema = schema(QueryRoot::Droid);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {
+                    "__typename": "Droid",
+                    "droidId": "droid-99",
+                    "primaryFunction": "run",
+                }})
             )),
         );
     }
@@ -1276,9 +1360,8 @@ mod generic_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -1296,7 +1379,9 @@ mod generic_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((graphql_value!({"__type": {"name": "Character"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "Character"}})
+            )),
         );
     }
 }
@@ -1389,9 +1474,8 @@ mod full_featured_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"humanId": "human-32", "homePlanet": "earth"}})
             )),
         );
     }
@@ -1409,9 +1493,8 @@ mod full_featured_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}})
             )),
         );
     }
@@ -1432,7 +1515,9 @@ mod full_featured_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((graphql_value!({"__type": {"name": "MyChar"}}), vec![])),
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"name": "MyChar"}})
+            )),
         );
     }
 
@@ -1452,9 +1537,108 @@ mod full_featured_struct {
 
         assert_eq!(
             execute(DOC, None, &schema, &Variables::new(), &db).await,
-            Ok((
-                graphql_value!({"__type": {"description": "My character."}}),
-                vec![],
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"__type": {"description": "My character."}})
+            )),
+        );
+    }
+}
+
+mod local_enum {
+    use std::rc::Rc;
+
+    use juniper::execute_local;
+
+    use super::*;
+
+    struct LocalHuman {
+        id: Rc<str>,
+    }
+
+    #[graphql_object(local)]
+    impl LocalHuman {
+        async fn id(&self) -> String {
+            let id = Rc::clone(&self.id);
+            futures::future::ready(()).await;
+            id.to_string()
+        }
+    }
+
+    struct LocalDroid {
+        id: Rc<str>,
+    }
+
+    #[graphql_object(local)]
+    impl LocalDroid {
+        fn id(&self) -> &str {
+            &self.id
+        }
+    }
+
+    #[derive(GraphQLUnion)]
+    #[graphql(scalar = DefaultScalarValue, local)]
+    enum Character {
+        A(LocalHuman),
+        B(LocalDroid),
+    }
+
+    enum QueryRoot {
+        Human,
+        Droid,
+    }
+
+    #[graphql_object(local)]
+    impl QueryRoot {
+        fn character(&self) -> Character {
+            match self {
+                Self::Human => Character::A(LocalHuman {
+                    id: "human-32".into(),
+                }),
+                Self::Droid => Character::B(LocalDroid {
+                    id: "droid-99".into(),
+                }),
+            }
+        }
+    }
+
+    const DOC: &str = r#"{
+        character {
+            __typename
+            ... on LocalHuman {
+                humanId: id
+            }
+            ... on LocalDroid {
+                droidId: id
+            }
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn resolves_human() {
+        let schema = schema(QueryRoot::Human);
+
+        assert_eq!(
+            execute_local(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {
+                    "__typename": "LocalHuman",
+                    "humanId": "human-32",
+                }})
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_droid() {
+        let schema = schema(QueryRoot::Droid);
+
+        assert_eq!(
+            execute_local(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok(juniper::ExecutionOutput::from_data(
+                graphql_value!({"character": {
+                    "__typename": "LocalDroid",
+                    "droidId": "droid-99",
+                }})
             )),
         );
     }
@@ -191,7 +191,11 @@ where
 {
     let schema = RootNode::new(TestType, EmptyMutation::<()>::new(), TestSubscriptionType);
 
-    let (result, errs) = execute(query, None, &schema, &vars, &())
+    let juniper::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = execute(query, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -54,7 +54,7 @@ async fn users() {
 
     let query = r#"{ users { id } }"#;
 
-    let (_, errors) = juniper::execute(
+    let juniper::ExecutionOutput { errors, .. } = juniper::execute(
         query,
         None,
         &Schema::new(
@@ -77,7 +77,7 @@ async fn countries() {
 
     let query = r#"{ countries { id } }"#;
 
-    let (_, errors) = juniper::execute(
+    let juniper::ExecutionOutput { errors, .. } = juniper::execute(
         query,
         None,
         &Schema::new(Query, EmptyMutation::new(), EmptySubscription::new()),
@@ -101,7 +101,7 @@ async fn both() {
     }
     "#;
 
-    let (_, errors) = juniper::execute(
+    let juniper::ExecutionOutput { errors, .. } = juniper::execute(
         query,
         None,
         &Schema::new(
@@ -129,7 +129,7 @@ async fn both_in_different_order() {
     }
     "#;
 
-    let (_, errors) = juniper::execute(
+    let juniper::ExecutionOutput { errors, .. } = juniper::execute(
         query,
         None,
         &Schema::new(
@@ -5,8 +5,8 @@ extern crate juniper;
 use bencher::Bencher;
 
 use juniper::{
-    execute_sync, tests::fixtures::starwars::model::Database, DefaultScalarValue, EmptyMutation,
-    EmptySubscription, RootNode, Variables,
+    execute_sync, graphql_object, tests::fixtures::starwars::model::Database, DefaultScalarValue,
+    EmptyMutation, EmptySubscription, RootNode, Variables,
 };
 
 fn query_type_name(b: &mut Bencher) {
@@ -144,5 +144,49 @@ fn introspection_query(b: &mut Bencher) {
     b.iter(|| execute_sync(doc, None, &schema, &Variables::new(), &database));
 }
 
-benchmark_group!(queries, query_type_name, introspection_query);
+struct Node(i32);
+
+#[graphql_object]
+impl Node {
+    fn depth(&self) -> i32 {
+        self.0
+    }
+
+    fn child(&self) -> Node {
+        Node(self.0 + 1)
+    }
+}
+
+struct NodeQuery;
+
+#[graphql_object]
+impl NodeQuery {
+    fn root() -> Node {
+        Node(0)
+    }
+}
+
+fn deeply_nested_query(b: &mut Bencher) {
+    let schema = RootNode::new(
+        NodeQuery,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let depth = 120;
+    let doc = format!(
+        "{{ root {{ {} depth {} }} }}",
+        "child { ".repeat(depth),
+        "}".repeat(depth),
+    );
+
+    b.iter(|| execute_sync(&doc, None, &schema, &Variables::new(), &()));
+}
+
+benchmark_group!(
+    queries,
+    query_type_name,
+    introspection_query,
+    deeply_nested_query
+);
 benchmark_main!(queries);
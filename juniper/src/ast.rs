@@ -1,11 +1,18 @@
+//! The syntax tree of parsed documents
+//!
+//! Documents are only handed out parsed and validated, e.g. to a
+//! [`QueryRewriter`](../rewrite/trait.QueryRewriter.html).
+
 use std::{borrow::Cow, fmt, hash::Hash, slice, vec};
 
 use indexmap::IndexMap;
+use serde::Deserialize;
 
 use crate::{
     executor::Variables,
+    integrations::serde::InputValueDeserializer,
     parser::Spanning,
-    value::{DefaultScalarValue, ScalarValue},
+    value::{DefaultScalarValue, IntoScalarValue, ScalarValue},
 };
 
 /// A type literal in the syntax tree
@@ -28,6 +35,15 @@ pub enum Type<'a> {
     NonNullList(Box<Type<'a>>),
 }
 
+/// A wrapper around a named type in a type literal, see [`Type::wrappers`]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TypeWrapper {
+    /// A list of the wrapped type, e.g. the outer `[...]` of `[String]`
+    List,
+    /// A non-null wrapped type, e.g. the `!` of `String!`
+    NonNull,
+}
+
 /// A JSON-like value that can be passed into the query execution, either
 /// out-of-band, or in-band as default variable values. These are _not_ constant
 /// and might contain variables.
@@ -45,41 +61,61 @@ pub enum InputValue<S = DefaultScalarValue> {
     Object(Vec<(Spanning<String>, Spanning<InputValue<S>>)>),
 }
 
+/// Declaration of an operation's variable
 #[derive(Clone, PartialEq, Debug)]
 pub struct VariableDefinition<'a, S> {
+    /// The type of the variable
     pub var_type: Spanning<Type<'a>>,
+    /// The value used when the variable isn't provided
     pub default_value: Option<Spanning<InputValue<S>>>,
 }
 
+/// Arguments passed to a field or a directive
 #[derive(Clone, PartialEq, Debug)]
 pub struct Arguments<'a, S> {
+    /// The arguments, by name, in the order of the document
     pub items: Vec<(Spanning<&'a str>, Spanning<InputValue<S>>)>,
 }
 
+/// Declarations of an operation's variables
 #[derive(Clone, PartialEq, Debug)]
 pub struct VariableDefinitions<'a, S> {
+    /// The variables, by name, in the order of the document
     pub items: Vec<(Spanning<&'a str>, VariableDefinition<'a, S>)>,
 }
 
+/// Selection of a field
 #[derive(Clone, PartialEq, Debug)]
 pub struct Field<'a, S> {
+    /// The name of the field in the response, if not the field's name
     pub alias: Option<Spanning<&'a str>>,
+    /// The name of the field
     pub name: Spanning<&'a str>,
+    /// The arguments of the field
     pub arguments: Option<Spanning<Arguments<'a, S>>>,
+    /// The directives applied to the field
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    /// The sub-fields, for fields of composite types
     pub selection_set: Option<Vec<Selection<'a, S>>>,
 }
 
+/// Selection of the fields of a named fragment, e.g. `...userFields`
 #[derive(Clone, PartialEq, Debug)]
 pub struct FragmentSpread<'a, S> {
+    /// The name of the fragment
     pub name: Spanning<&'a str>,
+    /// The directives applied to the spread
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
 }
 
+/// Selection of fields on a possibly narrower type, e.g. `...on User { name }`
 #[derive(Clone, PartialEq, Debug)]
 pub struct InlineFragment<'a, S> {
+    /// The type the fields are selected on, if narrower than the parent's
     pub type_condition: Option<Spanning<&'a str>>,
+    /// The directives applied to the fragment
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    /// The selected fields
     pub selection_set: Vec<Selection<'a, S>>,
 }
 
@@ -106,42 +142,61 @@ pub enum Selection<'a, S = DefaultScalarValue> {
     InlineFragment(Spanning<InlineFragment<'a, S>>),
 }
 
+/// Directive applied to a selection or an operation, e.g. `@skip(if: true)`
 #[derive(Clone, PartialEq, Debug)]
 pub struct Directive<'a, S> {
+    /// The name of the directive
     pub name: Spanning<&'a str>,
+    /// The arguments of the directive
     pub arguments: Option<Spanning<Arguments<'a, S>>>,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+/// The kind of an operation in a document
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub enum OperationType {
     Query,
     Mutation,
     Subscription,
 }
 
+/// Definition of an operation
 #[derive(Clone, PartialEq, Debug)]
 pub struct Operation<'a, S> {
+    /// Whether the operation is a query, a mutation or a subscription
     pub operation_type: OperationType,
+    /// The name of the operation, unless anonymous
     pub name: Option<Spanning<&'a str>>,
+    /// The variables of the operation
     pub variable_definitions: Option<Spanning<VariableDefinitions<'a, S>>>,
+    /// The directives applied to the operation
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    /// The fields selected on the root type
     pub selection_set: Vec<Selection<'a, S>>,
 }
 
+/// Definition of a named fragment
 #[derive(Clone, PartialEq, Debug)]
 pub struct Fragment<'a, S> {
+    /// The name of the fragment
     pub name: Spanning<&'a str>,
+    /// The type the fields are selected on
     pub type_condition: Spanning<&'a str>,
+    /// The directives applied to the fragment
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    /// The selected fields
     pub selection_set: Vec<Selection<'a, S>>,
 }
 
+/// Top-level definition of a document
 #[derive(Clone, PartialEq, Debug)]
+#[allow(missing_docs)]
 pub enum Definition<'a, S> {
     Operation(Spanning<Operation<'a, S>>),
     Fragment(Spanning<Fragment<'a, S>>),
 }
 
+/// A parsed document, holding operations and fragments
 pub type Document<'a, S> = Vec<Definition<'a, S>>;
 
 /// Parse an unstructured input value into a Rust data type.
@@ -153,8 +208,94 @@ pub type Document<'a, S> = Vec<Definition<'a, S>>;
 pub trait FromInputValue<S = DefaultScalarValue>: Sized {
     /// Performs the conversion.
     fn from_input_value(v: &InputValue<S>) -> Option<Self>;
+
+    /// Performs the conversion, describing why it failed.
+    ///
+    /// The reason is reported to clients when a variable value can't be
+    /// converted. Defaults to [`FromInputError::invalid`] whenever
+    /// `from_input_value` returns `None`.
+    fn try_from_input_value(v: &InputValue<S>) -> Result<Self, FromInputError> {
+        Self::from_input_value(v).ok_or_else(FromInputError::invalid)
+    }
+
+    /// Performs the conversion of a value which wasn't provided at all, e.g.
+    /// an omitted argument or input object field.
+    ///
+    /// Defaults to converting a null value, which is only told apart by
+    /// [`Nullable`](crate::Nullable).
+    fn from_implicit_null() -> Option<Self> {
+        Self::from_input_value(&InputValue::Null)
+    }
+}
+
+/// Error converting an [`InputValue`] into a Rust data type
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FromInputError {
+    expected: Option<String>,
+    reason: Option<String>,
+    path: Vec<String>,
+}
+
+impl FromInputError {
+    /// Constructs an error without any details
+    pub fn invalid() -> Self {
+        Self::default()
+    }
+
+    /// Constructs an error with the given reason, e.g. `"out of range"`
+    pub fn new<R: Into<String>>(reason: R) -> Self {
+        Self {
+            expected: None,
+            reason: Some(reason.into()),
+            path: vec![],
+        }
+    }
+
+    /// Sets the name of the type the value was expected to have
+    pub fn with_expected<E: Into<String>>(mut self, expected: E) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// The name of the type the value was expected to have, if known
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+
+    /// Why the value couldn't be converted, if known
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// The object fields and list indices leading to the part of the value
+    /// which couldn't be converted, empty if it's the value itself
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Locates the error in the field or at the index `segment` of the value
+    pub(crate) fn at<P: Into<String>>(mut self, segment: P) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+}
+
+impl fmt::Display for FromInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, r#"At "{}": "#, self.path.join("."))?;
+        }
+        match (&self.expected, &self.reason) {
+            (Some(expected), Some(reason)) => write!(f, r#"Expected "{}": {}"#, expected, reason),
+            (Some(expected), None) => write!(f, r#"Expected "{}""#, expected),
+            (None, Some(reason)) => write!(f, "{}", reason),
+            (None, None) => write!(f, "Invalid value"),
+        }
+    }
 }
 
+impl std::error::Error for FromInputError {}
+
 /// Losslessly clones a Rust data type into an InputValue.
 pub trait ToInputValue<S = DefaultScalarValue>: Sized {
     /// Performs the conversion.
@@ -189,6 +330,33 @@ impl<'a> Type<'a> {
             _ => false,
         }
     }
+
+    /// Determines if a type is a list, whether nullable or not.
+    pub fn is_list(&self) -> bool {
+        matches!(*self, Type::List(_) | Type::NonNullList(_))
+    }
+
+    /// The wrappers around the innermost named type, outermost first
+    ///
+    /// `[String!]!` is wrapped by `[NonNull, List, NonNull]`, in the order of
+    /// the `ofType` chain of the introspection, while named types have no
+    /// wrappers.
+    pub fn wrappers(&self) -> Vec<TypeWrapper> {
+        let mut wrappers = vec![];
+        let mut t = self;
+        loop {
+            if t.is_non_null() {
+                wrappers.push(TypeWrapper::NonNull);
+            }
+            match *t {
+                Type::List(ref inner) | Type::NonNullList(ref inner) => {
+                    wrappers.push(TypeWrapper::List);
+                    t = inner;
+                }
+                Type::Named(_) | Type::NonNullNamed(_) => return wrappers,
+            }
+        }
+    }
 }
 
 impl<'a> fmt::Display for Type<'a> {
@@ -238,9 +406,9 @@ where
     /// Construct a scalar value
     pub fn scalar<T>(v: T) -> Self
     where
-        T: Into<S>,
+        T: IntoScalarValue<S>,
     {
-        InputValue::Scalar(v.into())
+        InputValue::Scalar(v.into_scalar_value())
     }
 
     /// Construct an enum value.
@@ -293,20 +461,35 @@ where
     }
 
     /// Resolve all variables to their values.
+    ///
+    /// Object fields set to variables which aren't defined are left out, as if
+    /// they weren't provided at all.
     pub fn into_const(self, vars: &Variables<S>) -> Self {
+        self.into_defined_const(vars)
+            .unwrap_or_else(InputValue::null)
+    }
+
+    /// Resolve all variables to their values, returning `None` if the value
+    /// itself is a variable which isn't defined.
+    pub(crate) fn into_defined_const(self, vars: &Variables<S>) -> Option<Self> {
         match self {
-            InputValue::Variable(v) => vars.get(&v).map_or_else(InputValue::null, Clone::clone),
-            InputValue::List(l) => InputValue::List(
+            InputValue::Variable(v) => vars.get(&v).cloned(),
+            InputValue::List(l) => Some(InputValue::List(
                 l.into_iter()
                     .map(|s| s.map(|v| v.into_const(vars)))
                     .collect(),
-            ),
-            InputValue::Object(o) => InputValue::Object(
+            )),
+            InputValue::Object(o) => Some(InputValue::Object(
                 o.into_iter()
-                    .map(|(sk, sv)| (sk, sv.map(|v| v.into_const(vars))))
+                    .filter_map(|(sk, sv)| {
+                        let (start, end) = (sv.start, sv.end);
+                        sv.item
+                            .into_defined_const(vars)
+                            .map(|v| (sk, Spanning::start_end(&start, &end, v)))
+                    })
                     .collect(),
-            ),
-            v => v,
+            )),
+            v => Some(v),
         }
     }
 
@@ -357,6 +540,14 @@ where
         self.as_scalar_value().and_then(|s| s.as_str())
     }
 
+    /// View the source text of the underlying number literal, if preserved by
+    /// the scalar value type.
+    ///
+    /// See [`ScalarValue::from_number_literal`].
+    pub fn as_number_literal(&self) -> Option<&str> {
+        self.as_scalar().and_then(|s| s.as_number_literal())
+    }
+
     /// View the underlying scalar value, if present.
     pub fn as_scalar(&self) -> Option<&S> {
         match *self {
@@ -400,6 +591,38 @@ where
         }
     }
 
+    /// Deserialize the input value into a Rust data type with
+    /// [serde](https://serde.rs), e.g. to convert the loosely typed values of
+    /// custom scalars into structs
+    ///
+    /// Objects are deserialized like maps or structs, lists like sequences,
+    /// and enum values like unit variants. The value mustn't have unresolved
+    /// variables. The error tells where the value doesn't fit the type:
+    ///
+    /// ```
+    /// # use juniper::{graphql_value, InputValue, ToInputValue};
+    /// #[derive(Debug, PartialEq, serde::Deserialize)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// let value: InputValue = graphql_value!({ "x": 1, "y": 2 }).to_input_value();
+    /// assert_eq!(value.deserialize_into::<Point>(), Ok(Point { x: 1, y: 2 }));
+    ///
+    /// let value: InputValue = graphql_value!([{ "x": 1, "y": "2" }]).to_input_value();
+    /// assert_eq!(
+    ///     value.deserialize_into::<Vec<Point>>().unwrap_err().to_string(),
+    ///     r#"At "0.y": invalid type: string "2", expected i32"#,
+    /// );
+    /// ```
+    pub fn deserialize_into<'de, T>(&'de self) -> Result<T, FromInputError>
+    where
+        T: Deserialize<'de>,
+    {
+        T::deserialize(InputValueDeserializer(self))
+    }
+
     /// Recursively find all variables
     pub fn referenced_variables(&self) -> Vec<&str> {
         match *self {
@@ -450,7 +673,7 @@ where
             InputValue::Null => write!(f, "null"),
             InputValue::Scalar(ref s) => {
                 if let Some(s) = s.as_str() {
-                    write!(f, "\"{}\"", s)
+                    write_string_literal(f, s)
                 } else {
                     write!(f, "{}", s)
                 }
@@ -486,23 +709,46 @@ where
     }
 }
 
+/// Writes a GraphQL string literal, escaping the characters which can't appear
+/// in it as is.
+fn write_string_literal(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
 impl<'a, S> Arguments<'a, S> {
+    /// Returns the arguments, by name
     pub fn into_iter(self) -> vec::IntoIter<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.into_iter()
     }
 
+    /// Iterates over the arguments, by name
     pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.iter()
     }
 
+    /// Iterates mutably over the arguments, by name
     pub fn iter_mut(&mut self) -> slice::IterMut<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.iter_mut()
     }
 
+    /// The number of arguments
     pub fn len(&self) -> usize {
         self.items.len()
     }
 
+    /// Returns the value of the argument with the provided name
     pub fn get(&self, key: &str) -> Option<&Spanning<InputValue<S>>> {
         self.items
             .iter()
@@ -513,6 +759,7 @@ impl<'a, S> Arguments<'a, S> {
 }
 
 impl<'a, S> VariableDefinitions<'a, S> {
+    /// Iterates over the variables, by name
     pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, VariableDefinition<S>)> {
         self.items.iter()
     }
@@ -520,9 +767,30 @@ impl<'a, S> VariableDefinitions<'a, S> {
 
 #[cfg(test)]
 mod tests {
-    use super::InputValue;
+    use super::{InputValue, Type, TypeWrapper};
     use crate::parser::Spanning;
 
+    #[test]
+    fn test_type_wrappers() {
+        let named = Type::Named("String".into());
+        assert!(named.wrappers().is_empty());
+        assert!(!named.is_list());
+
+        let list = Type::NonNullList(Box::new(Type::List(Box::new(Type::NonNullNamed(
+            "String".into(),
+        )))));
+        assert!(list.is_list());
+        assert_eq!(
+            list.wrappers(),
+            vec![
+                TypeWrapper::NonNull,
+                TypeWrapper::List,
+                TypeWrapper::List,
+                TypeWrapper::NonNull,
+            ],
+        );
+    }
+
     #[test]
     fn test_input_value_fmt() {
         let value: InputValue = InputValue::null();
@@ -0,0 +1,178 @@
+//! Authorization of the types and fields marked with
+//! `#[graphql(require_auth)]`
+//!
+//! Objects and fields declare the scopes they require with
+//! `#[graphql(require_auth("scope", ...))]`, or only an authenticated context
+//! with `#[graphql(require_auth)]`. A [`PolicyEvaluator`](trait.PolicyEvaluator.html)
+//! set with
+//! [`RootNode::with_policy_evaluator`](../struct.RootNode.html#method.with_policy_evaluator)
+//! tells whether the context of an operation meets each requirement, and its
+//! decisions are used both by the execution and by the introspection:
+//!
+//! - a field is denied when it requires scopes the context lacks, when it
+//!   belongs to a denied object, or when it returns a denied object, and fails
+//!   with a `FORBIDDEN` error code instead of being resolved;
+//! - the denied fields and objects are hidden from the introspection.
+//!
+//! Requirements are denied to all operations until an evaluator is set, and
+//! each distinct requirement of the schema is evaluated once per operation.
+//!
+//! ```
+//! # use juniper::{EmptyMutation, EmptySubscription, RootNode};
+//! struct Context {
+//!     scopes: Option<Vec<String>>,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! struct User;
+//!
+//! #[juniper::graphql_object(Context = Context)]
+//! impl User {
+//!     fn name() -> &str {
+//!         "Ferris"
+//!     }
+//!
+//!     #[graphql(require_auth("users:email"))]
+//!     fn email() -> &str {
+//!         "ferris@example.com"
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object(Context = Context)]
+//! impl Query {
+//!     #[graphql(require_auth)]
+//!     fn me() -> User {
+//!         User
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(
+//!     Query,
+//!     EmptyMutation::<Context>::new(),
+//!     EmptySubscription::<Context>::new(),
+//! )
+//! .with_policy_evaluator(|context: &Context, required: &[String]| {
+//!     match context.scopes {
+//!         Some(ref scopes) => required.iter().all(|s| scopes.contains(s)),
+//!         None => false,
+//!     }
+//! });
+//! ```
+
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    executor::FieldError,
+    schema::{
+        meta::{Field, MetaType},
+        model::SchemaType,
+    },
+    value::ScalarValue,
+};
+
+/// Decides whether the operations executed with a context of type `CtxT` may
+/// access the types and fields requiring authorization
+pub trait PolicyEvaluator<CtxT>: Send + Sync {
+    /// Whether the `context` meets a requirement of the `scopes`
+    ///
+    /// The `scopes` are empty for `#[graphql(require_auth)]`, which only
+    /// requires the context to be authenticated.
+    fn is_authorized(&self, context: &CtxT, scopes: &[String]) -> bool;
+}
+
+impl<CtxT, F> PolicyEvaluator<CtxT> for F
+where
+    F: Fn(&CtxT, &[String]) -> bool + Send + Sync,
+{
+    fn is_authorized(&self, context: &CtxT, scopes: &[String]) -> bool {
+        self(context, scopes)
+    }
+}
+
+impl<CtxT> fmt::Debug for dyn PolicyEvaluator<CtxT> + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("PolicyEvaluator")
+    }
+}
+
+/// The requirements of a schema denied to an operation
+#[derive(Debug, Default)]
+pub(crate) struct Authorization {
+    denied: HashSet<Vec<String>>,
+}
+
+impl Authorization {
+    /// Evaluates the requirements of the `schema` for the `context`, all of
+    /// them being denied without an `evaluator`
+    pub(crate) fn evaluate<CtxT, S>(
+        schema: &SchemaType<S>,
+        evaluator: Option<&dyn PolicyEvaluator<CtxT>>,
+        context: &CtxT,
+    ) -> Self {
+        let denied = schema
+            .auth_requirements
+            .iter()
+            .filter(|scopes| match evaluator {
+                Some(evaluator) => !evaluator.is_authorized(context, scopes),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        Authorization { denied }
+    }
+
+    /// Encodes the denied requirements in a stable order, for the results
+    /// cached for an operation to only be served to the operations denied the
+    /// same fields
+    pub(crate) fn encode(&self, out: &mut Vec<u8>) {
+        let mut denied = self.denied.iter().collect::<Vec<_>>();
+        denied.sort();
+        for scopes in denied {
+            out.extend_from_slice(&(scopes.len() as u64).to_le_bytes());
+            for scope in scopes {
+                out.extend_from_slice(&(scope.len() as u64).to_le_bytes());
+                out.extend_from_slice(scope.as_bytes());
+            }
+        }
+    }
+
+    fn allows(&self, scopes: Option<&Vec<String>>) -> bool {
+        match scopes {
+            Some(scopes) => !self.denied.contains(scopes),
+            None => true,
+        }
+    }
+
+    /// Whether the type is visible, only objects requiring authorization
+    /// being denied
+    pub(crate) fn allows_type<S>(&self, meta_type: &MetaType<S>) -> bool {
+        match *meta_type {
+            MetaType::Object(ref object) => self.allows(object.required_scopes.as_ref()),
+            _ => true,
+        }
+    }
+
+    /// Whether the field of the `parent` type may be resolved and is visible
+    pub(crate) fn allows_field<S>(
+        &self,
+        schema: &SchemaType<S>,
+        parent: &MetaType<S>,
+        field: &Field<S>,
+    ) -> bool {
+        self.denied.is_empty()
+            || (self.allows_type(parent)
+                && self.allows(field.required_scopes.as_ref())
+                && schema
+                    .concrete_type_by_name(field.field_type.innermost_name())
+                    .into_iter()
+                    .all(|t| self.allows_type(t)))
+    }
+}
+
+/// Error of the fields denied to an operation
+pub(crate) fn forbidden<S: ScalarValue>() -> FieldError<S> {
+    FieldError::new("Not authorized", graphql_value!({ "code": "FORBIDDEN" }))
+}
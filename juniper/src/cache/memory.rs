@@ -0,0 +1,114 @@
+use std::{
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use fnv::FnvHashMap;
+
+use crate::{
+    cache::CacheBackend,
+    value::{DefaultScalarValue, Value},
+};
+
+/// Cache keeping the values in memory, shared by the requests executed by the
+/// process
+pub struct InMemoryCache<S = DefaultScalarValue> {
+    entries: Mutex<Entries<S>>,
+}
+
+struct Entries<S> {
+    values: FnvHashMap<String, (Instant, Value<S>)>,
+    /// Number of entries from which the expired ones are removed
+    purge_at: usize,
+}
+
+const MIN_PURGE_AT: usize = 64;
+
+impl<S> InMemoryCache<S> {
+    /// Constructs an empty cache
+    pub fn new() -> Self {
+        InMemoryCache {
+            entries: Mutex::new(Entries {
+                values: FnvHashMap::default(),
+                purge_at: MIN_PURGE_AT,
+            }),
+        }
+    }
+
+    /// Removes all the values
+    pub fn clear(&self) {
+        self.entries().values.clear();
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries<S>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S> Default for InMemoryCache<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> fmt::Debug for InMemoryCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InMemoryCache")
+            .field("len", &self.entries().values.len())
+            .finish()
+    }
+}
+
+impl<S: Clone> CacheBackend<S> for InMemoryCache<S> {
+    fn get(&self, key: &str) -> Option<Value<S>> {
+        let mut entries = self.entries();
+        match entries.values.get(key) {
+            Some((expires, value)) if *expires > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.values.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn set(&self, key: &str, value: &Value<S>, ttl: Duration) {
+        let now = Instant::now();
+        let mut entries = self.entries();
+        if entries.values.len() >= entries.purge_at {
+            entries.values.retain(|_, (expires, _)| *expires > now);
+            entries.purge_at = MIN_PURGE_AT.max(entries.values.len() * 2);
+        }
+        entries
+            .values
+            .insert(key.to_owned(), (now + ttl, value.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::InMemoryCache;
+    use crate::{
+        cache::CacheBackend,
+        value::{DefaultScalarValue, Value},
+    };
+
+    #[test]
+    fn in_memory_values_expire() {
+        let cache = InMemoryCache::<DefaultScalarValue>::new();
+        cache.set("kept", &Value::scalar(1), Duration::from_secs(3600));
+        cache.set("expired", &Value::scalar(2), Duration::from_secs(0));
+
+        assert_eq!(cache.get("kept"), Some(Value::scalar(1)));
+        assert_eq!(cache.get("expired"), None);
+        assert_eq!(cache.get("missing"), None);
+
+        cache.clear();
+        assert_eq!(cache.get("kept"), None);
+    }
+}
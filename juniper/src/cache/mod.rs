@@ -0,0 +1,185 @@
+//! Caching of field results and of responses
+//!
+//! # Field results
+//!
+//! Fields marked with `#[graphql(cache(ttl = "30s"))]` are cached by the server.
+//!
+//! A field's result is cached per parent value, arguments, selection and
+//! [authorization](../auth/index.html) requirements denied to the operation,
+//! as the `Value` returned to the client, so a cache hit skips resolving the
+//! field and all its sub-fields. Results having errors aren't cached.
+//!
+//! Fields are only cached once a backend is set with
+//! [`RootNode::with_field_cache`](../struct.RootNode.html#method.with_field_cache):
+//!
+//! ```
+//! # use juniper::{cache::InMemoryCache, EmptyMutation, EmptySubscription, RootNode};
+//! #[derive(Hash)]
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     #[graphql(cache(ttl = "30s"))]
+//!     fn exchange_rate(currency: String) -> f64 {
+//!         // An expensive lookup
+//!         # 1.0
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_field_cache(InMemoryCache::new());
+//! ```
+//!
+//! The parent type has to implement `Hash`, and hash the same in all the
+//! processes sharing an external backend, as derived implementations on
+//! integers and strings do on the same platform. Cached values are shared by
+//! all requests, so fields resolved differently depending on the context must
+//! not be cached.
+//!
+//! # Responses
+//!
+//! Objects and fields marked with `#[graphql(cache_control(max_age = 60))]`
+//! allow clients and proxies to cache the responses of queries selecting them,
+//! for the shortest `max_age` of the selected fields. Marking any selected
+//! field with `scope = "private"` restricts caching to the client:
+//!
+//! ```
+//! #[derive(juniper::GraphQLObject)]
+//! #[graphql(cache_control(max_age = 3600))]
+//! struct Country {
+//!     name: String,
+//!     #[graphql(cache_control(max_age = 60))]
+//!     population: i32,
+//! }
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     #[graphql(cache_control(max_age = 300, scope = "private"))]
+//!     fn home_country() -> Country {
+//!         // Depends on the user
+//!         # Country { name: "Iceland".into(), population: 364_000 }
+//!     }
+//! }
+//! ```
+//!
+//! The hints of the sub-fields of a field served from the field cache apply
+//! as if they were resolved.
+//!
+//! The resulting [`CachePolicy`](struct.CachePolicy.html) is returned by
+//! [`GraphQLResponse::cache_policy`](../http/struct.GraphQLResponse.html#method.cache_policy),
+//! which the HTTP integrations use to set the `Cache-Control` header.
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+    time::Duration,
+};
+
+use fnv::FnvHasher;
+use futures::future::{self, BoxFuture};
+
+use crate::{
+    auth::Authorization,
+    executor::LookAheadSelection,
+    value::{DefaultScalarValue, ScalarValue, Value},
+};
+
+// `std::time::Instant` panics on `wasm32-unknown-unknown`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod memory;
+mod policy;
+#[cfg(feature = "redis-cache")]
+mod redis;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use self::memory::InMemoryCache;
+pub(crate) use self::policy::CachePolicyCollector;
+pub use self::policy::{CacheHint, CachePolicy, CacheScope};
+
+#[cfg(feature = "redis-cache")]
+pub use self::redis::RedisCache;
+
+/// Storage of cached field results
+///
+/// Failing to read or write an entry isn't an error, the field is resolved
+/// instead. The methods are called from resolvers, synchronous ones included,
+/// so they shouldn't block for long. Backends doing I/O implement
+/// [`get_async`](#method.get_async) for async resolvers not to block the
+/// executor.
+pub trait CacheBackend<S = DefaultScalarValue> {
+    /// Returns the value stored with the provided key, unless it's expired
+    fn get(&self, key: &str) -> Option<Value<S>>;
+
+    /// Returns the value stored with the provided key to an async resolver,
+    /// with [`get`](#tymethod.get) by default
+    fn get_async<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Value<S>>>
+    where
+        S: Send + 'a,
+    {
+        Box::pin(future::ready(self.get(key)))
+    }
+
+    /// Stores the value with the provided key for the provided duration
+    fn set(&self, key: &str, value: &Value<S>, ttl: Duration);
+}
+
+/// The backend set on a schema
+pub(crate) struct FieldCache<S>(pub(crate) Arc<dyn CacheBackend<S> + Send + Sync>);
+
+impl<S> fmt::Debug for FieldCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("FieldCache(..)")
+    }
+}
+
+/// Builds the key of a field's result from the parent value and the selection
+/// of the field, including its arguments with the variables substituted
+///
+/// The requirements denied to the operation are part of the key, as the
+/// sub-fields requiring them aren't resolved on a cache hit.
+///
+/// Keys are shared by processes through external backends, so they're hashed
+/// with FNV, from an encoding of the selection stable across builds.
+pub(crate) fn field_key<T, S>(
+    type_name: &str,
+    field_name: &str,
+    parent: &T,
+    selection: &LookAheadSelection<S>,
+    authorization: &Authorization,
+) -> String
+where
+    T: Hash + ?Sized,
+    S: ScalarValue,
+{
+    let mut hasher = FnvHasher::default();
+    parent.hash(&mut hasher);
+    let mut encoded = Vec::new();
+    selection.encode(&mut encoded);
+    authorization.encode(&mut encoded);
+    hasher.write(&encoded);
+    format!("{}.{}:{:016x}", type_name, field_name, hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::field_key;
+    use crate::{auth::Authorization, executor::LookAheadSelection, value::DefaultScalarValue};
+
+    #[test]
+    fn field_keys_are_stable_across_builds() {
+        let selection = LookAheadSelection::<DefaultScalarValue>::default();
+        assert_eq!(
+            field_key(
+                "Query",
+                "rate",
+                "EUR",
+                &selection,
+                &Authorization::default(),
+            ),
+            "Query.rate:e64c37ee5a12c21f",
+        );
+    }
+}
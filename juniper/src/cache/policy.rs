@@ -0,0 +1,118 @@
+use std::{fmt, sync::Mutex};
+
+/// Who may cache a response
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheScope {
+    /// Shared caches, like CDNs, may store the response
+    Public,
+    /// Only the client may store the response, as it's specific to the user
+    Private,
+}
+
+/// Caching hint declared on a type or field with
+/// `#[graphql(cache_control(max_age = 60, scope = "private"))]`
+///
+/// A hint on a field takes precedence over the hint on the type it returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheHint {
+    /// Seconds the value may be cached for
+    pub max_age: Option<u32>,
+    /// Who may cache the value
+    pub scope: Option<CacheScope>,
+}
+
+impl CacheHint {
+    /// Constructs a hint allowing to cache values for the provided seconds
+    pub fn max_age(max_age: u32) -> Self {
+        CacheHint {
+            max_age: Some(max_age),
+            scope: None,
+        }
+    }
+
+    /// Sets who may cache the values
+    pub fn scope(mut self, scope: CacheScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+/// How long, and by whom, the response to a query may be cached
+///
+/// Computed while executing the query from the hints of the resolved fields:
+/// the shortest `max_age` applies, and the response is private if any hint is.
+/// Root fields, and fields returning objects, interfaces or unions, without a
+/// hint on either the field or the returned type prevent caching, while other
+/// fields are cached as long as their parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Seconds the response may be cached for, at least one
+    pub max_age: u32,
+    /// Who may cache the response
+    pub scope: CacheScope,
+}
+
+impl CachePolicy {
+    /// Combines the policies of responses sent together, e.g. in a batch
+    pub fn merge(self, other: CachePolicy) -> CachePolicy {
+        CachePolicy {
+            max_age: self.max_age.min(other.max_age),
+            scope: if self.scope == CacheScope::Private {
+                CacheScope::Private
+            } else {
+                other.scope
+            },
+        }
+    }
+}
+
+/// The value of the `Cache-Control` HTTP header, e.g. `max-age=60, public`
+impl fmt::Display for CachePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scope = match self.scope {
+            CacheScope::Public => "public",
+            CacheScope::Private => "private",
+        };
+        write!(f, "max-age={}, {}", self.max_age, scope)
+    }
+}
+
+/// The policy of the query being executed, restricted by each resolved field
+#[derive(Debug)]
+pub(crate) struct CachePolicyCollector {
+    state: Mutex<(Option<u32>, CacheScope)>,
+}
+
+impl CachePolicyCollector {
+    pub(crate) fn new() -> Self {
+        CachePolicyCollector {
+            state: Mutex::new((None, CacheScope::Public)),
+        }
+    }
+
+    pub(crate) fn restrict(&self, max_age: Option<u32>, scope: Option<CacheScope>) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(max_age) = max_age {
+            state.0 = Some(state.0.map_or(max_age, |m| m.min(max_age)));
+        }
+        if scope == Some(CacheScope::Private) {
+            state.1 = CacheScope::Private;
+        }
+    }
+
+    /// The policy of the response, the hints of the fields being overridden
+    /// by the `max_age` of the operation, if any
+    pub(crate) fn into_policy(self, max_age: Option<u32>) -> Option<CachePolicy> {
+        let (hinted_max_age, scope) = self
+            .state
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match max_age.or(hinted_max_age) {
+            Some(max_age) if max_age > 0 => Some(CachePolicy { max_age, scope }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use futures::{
+    channel::oneshot,
+    future::{self, BoxFuture},
+};
+use redis::{Client, Connection, IntoConnectionInfo, RedisResult};
+
+use crate::{
+    ast::InputValue,
+    cache::CacheBackend,
+    value::{DefaultScalarValue, Object, ScalarValue, Value},
+};
+
+/// Work run off the async executor, as a blocking task of the runtime
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Cache keeping the values in Redis, as JSON, shared by all the processes
+/// using the server
+///
+/// Values expire on the server. A single connection is used, established again
+/// after failing.
+///
+/// The Redis client is blocking, so async resolvers should get the values
+/// through the runtime's blocking tasks, set with
+/// [`spawn_blocking`](#method.spawn_blocking):
+///
+/// ```
+/// # use juniper::cache::RedisCache;
+/// let cache = RedisCache::<juniper::DefaultScalarValue>::open("redis://127.0.0.1/")?
+///     .prefix("cache:")
+///     .spawn_blocking(|job| {
+///         tokio::task::spawn_blocking(job);
+///     });
+/// # Ok::<(), redis::RedisError>(())
+/// ```
+pub struct RedisCache<S = DefaultScalarValue> {
+    connection: Arc<SharedConnection>,
+    prefix: String,
+    spawn_blocking: Option<Arc<dyn Fn(Job) + Send + Sync>>,
+    _scalar: PhantomData<fn() -> S>,
+}
+
+struct SharedConnection {
+    client: Client,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl SharedConnection {
+    fn query<T: redis::FromRedisValue>(&self, cmd: &redis::Cmd) -> Option<T> {
+        let mut connection = self.connection();
+        if connection.is_none() {
+            *connection = self.client.get_connection().ok();
+        }
+        let result = cmd.query(connection.as_mut()?);
+        if result.is_err() {
+            *connection = None;
+        }
+        result.ok()
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Option<Connection>> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S> RedisCache<S> {
+    /// Constructs a cache using the server with the provided URL, e.g.
+    /// `redis://127.0.0.1/`
+    ///
+    /// The connection is only established once the cache is used.
+    pub fn open<T: IntoConnectionInfo>(params: T) -> RedisResult<Self> {
+        Ok(RedisCache {
+            connection: Arc::new(SharedConnection {
+                client: Client::open(params)?,
+                connection: Mutex::new(None),
+            }),
+            prefix: String::new(),
+            spawn_blocking: None,
+            _scalar: PhantomData,
+        })
+    }
+
+    /// Prepends the provided prefix to the keys of the values, to share the
+    /// server with other data
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Runs the queries of async resolvers, and all the writes, with the
+    /// provided function spawning blocking tasks, e.g.
+    /// `tokio::task::spawn_blocking`
+    ///
+    /// Without it, the queries block the thread of the resolver.
+    pub fn spawn_blocking<F>(mut self, spawn_blocking: F) -> Self
+    where
+        F: Fn(Job) + Send + Sync + 'static,
+    {
+        self.spawn_blocking = Some(Arc::new(spawn_blocking));
+        self
+    }
+}
+
+impl<S> fmt::Debug for RedisCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RedisCache")
+            .field("prefix", &self.prefix)
+            .field("spawn_blocking", &self.spawn_blocking.is_some())
+            .finish()
+    }
+}
+
+impl<S: ScalarValue> CacheBackend<S> for RedisCache<S> {
+    fn get(&self, key: &str) -> Option<Value<S>> {
+        let json: Option<String> = self
+            .connection
+            .query(redis::cmd("GET").arg(self.prefix.clone() + key))?;
+        from_json(&json?)
+    }
+
+    fn get_async<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Value<S>>>
+    where
+        S: Send + 'a,
+    {
+        let spawn_blocking = match &self.spawn_blocking {
+            Some(spawn_blocking) => spawn_blocking,
+            None => return Box::pin(future::ready(self.get(key))),
+        };
+        let (sender, receiver) = oneshot::channel();
+        let connection = Arc::clone(&self.connection);
+        let key = self.prefix.clone() + key;
+        spawn_blocking(Box::new(move || {
+            let json: Option<Option<String>> = connection.query(redis::cmd("GET").arg(key));
+            // The resolver may have stopped waiting for the value.
+            let _ = sender.send(json.flatten());
+        }));
+        Box::pin(async move { from_json(&receiver.await.ok()??) })
+    }
+
+    fn set(&self, key: &str, value: &Value<S>, ttl: Duration) {
+        let json = match serde_json::to_string(value) {
+            Ok(json) => json,
+            Err(_) => return,
+        };
+        let millis = ttl.as_millis().max(1) as u64;
+        let connection = Arc::clone(&self.connection);
+        let key = self.prefix.clone() + key;
+        let set = move || {
+            connection.query::<()>(redis::cmd("SET").arg(key).arg(json).arg("PX").arg(millis));
+        };
+        match &self.spawn_blocking {
+            Some(spawn_blocking) => spawn_blocking(Box::new(set)),
+            None => set(),
+        }
+    }
+}
+
+fn from_json<S: ScalarValue>(json: &str) -> Option<Value<S>> {
+    let value = serde_json::from_str::<InputValue<S>>(json).ok()?;
+    Some(into_value(value))
+}
+
+/// Converts a value read from JSON, which has no enums nor variables
+fn into_value<S: ScalarValue>(value: InputValue<S>) -> Value<S> {
+    match value {
+        InputValue::Null | InputValue::Variable(_) => Value::null(),
+        InputValue::Scalar(s) => Value::Scalar(s),
+        InputValue::Enum(e) => Value::Scalar(S::from_string(e)),
+        InputValue::List(items) => {
+            Value::list(items.into_iter().map(|i| into_value(i.item)).collect())
+        }
+        InputValue::Object(fields) => {
+            let mut object = Object::with_capacity(fields.len());
+            for (name, value) in fields {
+                object.add_field(name.item, into_value(value.item));
+            }
+            Value::object(object)
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Reports of the deprecated fields selected by the executed operations
+//!
+//! A [`DeprecationTracker`](struct.DeprecationTracker.html) set with
+//! [`RootNode::with_deprecation_tracker`](../struct.RootNode.html#method.with_deprecation_tracker)
+//! sends a [`DeprecationReport`](struct.DeprecationReport.html) to its
+//! [`DeprecationReporter`](trait.DeprecationReporter.html) for each operation
+//! selecting `@deprecated` fields, naming the client which sent it, to know
+//! which clients still have to migrate before removing the fields.
+//!
+//! Clients are identified from the context of the operations, e.g. filled from
+//! a header by a [`ContextFactory`](../http/trait.ContextFactory.html):
+//!
+//! ```
+//! # use juniper::{
+//! #     deprecation::{DeprecationReport, DeprecationTracker},
+//! #     http::{HttpError, ContextFactory, RequestParts},
+//! #     EmptyMutation, EmptySubscription, RootNode,
+//! # };
+//! struct Context {
+//!     client_name: Option<String>,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! let context_factory = |parts: RequestParts| async move {
+//!     Ok::<_, HttpError>(Context {
+//!         client_name: parts.header("apollographql-client-name").map(Into::into),
+//!     })
+//! };
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object(Context = Context)]
+//! impl Query {
+//!     #[deprecated(note = "Use `greeting`")]
+//!     fn hello() -> &str {
+//!         "Hello"
+//!     }
+//!
+//!     fn greeting() -> &str {
+//!         "Hello"
+//!     }
+//! }
+//!
+//! let tracker = DeprecationTracker::new(|report: &DeprecationReport| {
+//!     println!(
+//!         "{} selected {:?}",
+//!         report.client.as_deref().unwrap_or("An unknown client"),
+//!         report.fields,
+//!     );
+//! })
+//! .client_identity(|context: &Context| context.client_name.clone());
+//! let schema = RootNode::new(
+//!     Query,
+//!     EmptyMutation::<Context>::new(),
+//!     EmptySubscription::<Context>::new(),
+//! )
+//! .with_deprecation_tracker(tracker);
+//! # fn assert_factory<F: ContextFactory<Context = Context>>(_: &F) {}
+//! # assert_factory(&context_factory);
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    ast::{Document, Operation},
+    executor::OperationInfo,
+    parser::Spanning,
+    schema::model::SchemaType,
+    value::ScalarValue,
+};
+
+/// Receiver of the reports sent by a
+/// [`DeprecationTracker`](struct.DeprecationTracker.html)
+///
+/// Reporting is done within the request executing the operation, so it
+/// shouldn't block for long, e.g. by sending the report from another thread.
+pub trait DeprecationReporter: Send + Sync {
+    /// Handles the report of an operation selecting deprecated fields
+    fn report(&self, report: &DeprecationReport);
+}
+
+impl<F> DeprecationReporter for F
+where
+    F: Fn(&DeprecationReport) + Send + Sync,
+{
+    fn report(&self, report: &DeprecationReport) {
+        self(report)
+    }
+}
+
+/// The deprecated fields selected by an executed operation
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecationReport {
+    /// Name of the operation, if it has one
+    pub operation_name: Option<String>,
+    /// The schema coordinates (e.g. `"User.login"`) of the selected deprecated
+    /// fields, sorted and free of duplicates
+    pub fields: Vec<String>,
+    /// The client which sent the operation, if identified
+    pub client: Option<String>,
+}
+
+type ClientIdentity<CtxT> = Box<dyn Fn(&CtxT) -> Option<String> + Send + Sync>;
+
+/// Tracker of the deprecated fields selected by the operations executed with
+/// a context of type `CtxT`
+pub struct DeprecationTracker<CtxT> {
+    reporter: Box<dyn DeprecationReporter>,
+    client_identity: Option<ClientIdentity<CtxT>>,
+}
+
+impl<CtxT> DeprecationTracker<CtxT> {
+    /// Constructs a tracker sending the reports to the provided reporter
+    pub fn new<R: DeprecationReporter + 'static>(reporter: R) -> Self {
+        DeprecationTracker {
+            reporter: Box::new(reporter),
+            client_identity: None,
+        }
+    }
+
+    /// Identifies the client of each operation from its context, the reports
+    /// having no client otherwise
+    pub fn client_identity<F>(mut self, client_identity: F) -> Self
+    where
+        F: Fn(&CtxT) -> Option<String> + Send + Sync + 'static,
+    {
+        self.client_identity = Some(Box::new(client_identity));
+        self
+    }
+
+    /// Reports the operation if it selects deprecated fields
+    pub(crate) fn track<S>(
+        &self,
+        schema: &SchemaType<S>,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+        context: &CtxT,
+    ) where
+        S: ScalarValue,
+    {
+        let fields: Vec<_> = OperationInfo::new(document, operation, schema)
+            .fields
+            .into_iter()
+            .filter(|coordinate| is_deprecated(schema, coordinate))
+            .collect();
+        if fields.is_empty() {
+            return;
+        }
+
+        self.reporter.report(&DeprecationReport {
+            operation_name: operation.item.name.as_ref().map(|n| n.item.to_owned()),
+            fields,
+            client: self
+                .client_identity
+                .as_ref()
+                .and_then(|client_identity| client_identity(context)),
+        });
+    }
+}
+
+fn is_deprecated<S>(schema: &SchemaType<S>, coordinate: &str) -> bool {
+    let mut parts = coordinate.splitn(2, '.');
+    let (type_name, field_name) = match (parts.next(), parts.next()) {
+        (Some(type_name), Some(field_name)) => (type_name, field_name),
+        _ => return false,
+    };
+    let field = schema
+        .concrete_type_by_name(type_name)
+        .and_then(|t| t.field_by_name(field_name));
+    matches!(field, Some(f) if f.deprecation_status.is_deprecated())
+}
+
+impl<CtxT> fmt::Debug for DeprecationTracker<CtxT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeprecationTracker")
+            .field("client_identity", &self.client_identity.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,63 @@
+//! Limit on the size of responses, set with
+//! [`RootNode::with_max_response_values`](../struct.RootNode.html#method.with_max_response_values)
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{executor::FieldError, value::ScalarValue};
+
+/// Number of values an operation may still resolve
+pub(crate) struct ValueBudget {
+    limit: Option<usize>,
+    spent: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+/// Outcome of charging values to a budget
+pub(crate) enum Charge {
+    /// The values fit in the budget
+    Within,
+
+    /// The values exceed the budget, for the first time
+    Exceeded(usize),
+
+    /// The budget was already exceeded
+    Exhausted,
+}
+
+impl ValueBudget {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        ValueBudget {
+            limit,
+            spent: AtomicUsize::new(0),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Charges `count` values to the budget
+    pub(crate) fn charge(&self, count: usize) -> Charge {
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => return Charge::Within,
+        };
+        if self.exceeded.load(Ordering::Relaxed) {
+            return Charge::Exhausted;
+        }
+
+        let spent = self.spent.fetch_add(count, Ordering::Relaxed) + count;
+        if spent <= limit {
+            Charge::Within
+        } else if self.exceeded.swap(true, Ordering::Relaxed) {
+            Charge::Exhausted
+        } else {
+            Charge::Exceeded(limit)
+        }
+    }
+}
+
+/// Error of the value exceeding the budget of the response
+pub(crate) fn exceeded<S: ScalarValue>(limit: usize) -> FieldError<S> {
+    FieldError::new(
+        format!("Response exceeds the limit of {} values", limit),
+        graphql_value!({ "code": "RESPONSE_TOO_LARGE" }),
+    )
+}
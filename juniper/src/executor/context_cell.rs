@@ -0,0 +1,80 @@
+use std::{fmt, sync::Mutex};
+
+/// Per-request state updated by resolvers through the shared context
+///
+/// Resolvers only get `&Context`, so state accumulated while executing a
+/// request, like metrics counters or caches, needs interior mutability.
+/// `ContextCell` is `Send` and `Sync` whenever `T` is `Send`, as the
+/// asynchronous executor requires of contexts, and only gives access to the
+/// value within a closure, so the lock is never held across an `.await`:
+///
+/// ```
+/// # use juniper::ContextCell;
+/// struct Context {
+///     resolved_users: ContextCell<u32>,
+/// }
+///
+/// impl juniper::Context for Context {}
+///
+/// struct User;
+///
+/// #[juniper::graphql_object(Context = Context)]
+/// impl User {
+///     async fn name(context: &Context) -> String {
+///         context.resolved_users.update(|count| *count += 1);
+///         "Bob".into()
+///     }
+/// }
+/// ```
+///
+/// A panic within a closure doesn't poison the cell for the other resolvers.
+#[derive(Default)]
+pub struct ContextCell<T> {
+    value: Mutex<T>,
+}
+
+impl<T> ContextCell<T> {
+    /// Wraps the initial value
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Mutex::new(value),
+        }
+    }
+
+    /// Updates the value, returning the result of the closure
+    ///
+    /// Other resolvers accessing the cell wait until the closure returns.
+    pub fn update<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        let mut value = self
+            .value
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut value)
+    }
+
+    /// Returns a copy of the value
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.update(|value| value.clone())
+    }
+
+    /// Replaces the value, returning the previous one
+    pub fn replace(&self, value: T) -> T {
+        self.update(|old| std::mem::replace(old, value))
+    }
+
+    /// Returns the value, e.g. once the request is executed
+    pub fn into_inner(self) -> T {
+        self.value
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ContextCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.update(|value| f.debug_tuple("ContextCell").field(value).finish())
+    }
+}
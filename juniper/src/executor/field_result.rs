@@ -0,0 +1,184 @@
+//! Conversions of `Option`s and `Result`s into `FieldResult`s
+
+use std::fmt::Display;
+
+use crate::{
+    executor::{FieldError, FieldResult},
+    value::{Object, ScalarValue, Value},
+};
+
+/// Code of the errors of missing values
+const NOT_FOUND: &str = "NOT_FOUND";
+
+/// Conversions of an `Option` into a `FieldResult`, failing with a structured
+/// error when it's `None`
+///
+/// ```
+/// # use juniper::{FieldResult, OptionExt};
+/// # struct Order;
+/// # fn find_order(id: &str) -> Option<Order> { None }
+/// # fn charge(order: &Order) -> Option<u32> { None }
+/// fn pay(id: &str) -> FieldResult<u32> {
+///     let order = find_order(id).ok_or_not_found()?;
+///     charge(&order).or_field_err("PAYMENT_DECLINED", "The payment was declined")
+/// }
+/// ```
+pub trait OptionExt<T> {
+    /// The value, or an error with the `message` and the `code` in its
+    /// extensions
+    fn or_field_err<S, M>(self, code: &str, message: M) -> FieldResult<T, S>
+    where
+        S: ScalarValue,
+        M: Display;
+
+    /// The value, or a `Not found` error with the `NOT_FOUND` code in its
+    /// extensions
+    fn ok_or_not_found<S: ScalarValue>(self) -> FieldResult<T, S>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn or_field_err<S, M>(self, code: &str, message: M) -> FieldResult<T, S>
+    where
+        S: ScalarValue,
+        M: Display,
+    {
+        self.ok_or_else(|| coded(code, message))
+    }
+
+    fn ok_or_not_found<S: ScalarValue>(self) -> FieldResult<T, S> {
+        self.or_field_err(NOT_FOUND, "Not found")
+    }
+}
+
+/// Conversions of a `Result` into a `FieldResult`, replacing the error with a
+/// structured one
+///
+/// ```
+/// # use juniper::{FieldResult, ResultExt};
+/// # struct User;
+/// # fn load_user(id: &str) -> Result<Option<User>, std::io::Error> { Ok(None) }
+/// # fn parse_age(age: &str) -> Result<i32, std::num::ParseIntError> { age.parse() }
+/// fn user(id: &str) -> FieldResult<User> {
+///     load_user(id).ok_or_not_found()
+/// }
+///
+/// fn age(age: &str) -> FieldResult<i32> {
+///     parse_age(age).or_field_err("INVALID_AGE", "The age must be a number")
+/// }
+/// ```
+///
+/// The original errors aren't exposed to clients, except by
+/// [`with_code`](#tymethod.with_code) which keeps their message.
+pub trait ResultExt<T, E> {
+    /// The value, or an error with the `message` and the `code` in its
+    /// extensions
+    fn or_field_err<S, M>(self, code: &str, message: M) -> FieldResult<T, S>
+    where
+        S: ScalarValue,
+        M: Display;
+
+    /// The value, or an error with the message of the error and the `code` in
+    /// its extensions
+    fn with_code<S: ScalarValue>(self, code: &str) -> FieldResult<T, S>
+    where
+        E: Display;
+
+    /// The value of `Ok(Some(_))`, a `Not found` error with the `NOT_FOUND`
+    /// code for `Ok(None)`, or the error converted into a `FieldError`
+    fn ok_or_not_found<S, U>(self) -> FieldResult<U, S>
+    where
+        S: ScalarValue,
+        T: Into<Option<U>>,
+        E: Into<FieldError<S>>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn or_field_err<S, M>(self, code: &str, message: M) -> FieldResult<T, S>
+    where
+        S: ScalarValue,
+        M: Display,
+    {
+        self.map_err(|_| coded(code, message))
+    }
+
+    fn with_code<S: ScalarValue>(self, code: &str) -> FieldResult<T, S>
+    where
+        E: Display,
+    {
+        self.map_err(|e| coded(code, e))
+    }
+
+    fn ok_or_not_found<S, U>(self) -> FieldResult<U, S>
+    where
+        S: ScalarValue,
+        T: Into<Option<U>>,
+        E: Into<FieldError<S>>,
+    {
+        self.map_err(Into::into)?.into().ok_or_not_found()
+    }
+}
+
+fn coded<S: ScalarValue, M: Display>(code: &str, message: M) -> FieldError<S> {
+    let mut extensions = Object::with_capacity(1);
+    extensions.add_field("code", Value::scalar(code.to_owned()));
+    FieldError::new(message, Value::object(extensions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OptionExt, ResultExt};
+    use crate::{executor::FieldError, value::DefaultScalarValue, FieldResult};
+
+    fn coded(code: &str, message: &str) -> FieldError<DefaultScalarValue> {
+        FieldError::new(message, graphql_value!({ "code": code }))
+    }
+
+    #[test]
+    fn options_fail_when_none() {
+        let found: FieldResult<i32> = Some(1).ok_or_not_found();
+        assert_eq!(found, Ok(1));
+
+        let missing: FieldResult<i32> = None.ok_or_not_found();
+        assert_eq!(missing, Err(coded("NOT_FOUND", "Not found")));
+
+        let missing: FieldResult<i32> = None.or_field_err("NO_STOCK", "Out of stock");
+        assert_eq!(missing, Err(coded("NO_STOCK", "Out of stock")));
+    }
+
+    #[test]
+    fn results_replace_the_error() {
+        let parsed: FieldResult<i32> = "12".parse::<i32>().or_field_err("INVALID", "Invalid");
+        assert_eq!(parsed, Ok(12));
+
+        let parsed: FieldResult<i32> = "a".parse::<i32>().or_field_err("INVALID", "Invalid");
+        assert_eq!(parsed, Err(coded("INVALID", "Invalid")));
+
+        let parsed: FieldResult<i32> = "a".parse::<i32>().with_code("INVALID");
+        assert_eq!(
+            parsed,
+            Err(coded("INVALID", "invalid digit found in string")),
+        );
+    }
+
+    #[test]
+    fn results_of_options_fail_when_none() {
+        let loaded: FieldResult<i32> = Ok::<_, &str>(Some(1)).ok_or_not_found();
+        assert_eq!(loaded, Ok(1));
+
+        let loaded: FieldResult<i32> = Ok::<_, &str>(None).ok_or_not_found();
+        assert_eq!(loaded, Err(coded("NOT_FOUND", "Not found")));
+
+        let loaded: FieldResult<i32> = Err::<Option<i32>, _>("Timeout").ok_or_not_found();
+        assert_eq!(loaded, Err(FieldError::from("Timeout")));
+    }
+
+    #[test]
+    fn errors_convert_with_the_question_mark() {
+        fn total(a: Option<i32>, b: &str) -> FieldResult<i32> {
+            Ok(a.ok_or_not_found()? + b.parse::<i32>().with_code("INVALID")?)
+        }
+
+        assert_eq!(total(Some(1), "2"), Ok(3));
+        assert_eq!(total(None, "2"), Err(coded("NOT_FOUND", "Not found")));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     ast::{Directive, Fragment, InputValue, Selection},
     parser::Spanning,
+    types::base::is_excluded,
     value::ScalarValue,
 };
 
@@ -96,6 +97,52 @@ where
     }
 }
 
+/// A directive applied to a field of the query, like a custom
+/// `@locale(lang: "fr")` altering how the field is fetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookAheadDirective<'a, S: 'a> {
+    name: &'a str,
+    arguments: Vec<LookAheadArgument<'a, S>>,
+}
+
+impl<'a, S> LookAheadDirective<'a, S>
+where
+    S: ScalarValue,
+{
+    fn new(directive: &'a Directive<'a, S>, vars: &'a Variables<S>) -> Self {
+        LookAheadDirective {
+            name: directive.name.item,
+            arguments: directive
+                .arguments
+                .as_ref()
+                .map(|a| {
+                    a.item
+                        .items
+                        .iter()
+                        .map(|p| LookAheadArgument::new(p, vars))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The directive's name, without the `@`
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The arguments of the directive, their variables resolved
+    pub fn arguments(&self) -> &[LookAheadArgument<'a, S>] {
+        &self.arguments
+    }
+
+    /// The argument of the directive with a given name
+    pub fn argument(&self, name: &str) -> Option<&LookAheadArgument<'a, S>> {
+        self.arguments.iter().find(|a| a.name == name)
+    }
+}
+
+/// A child of a selection, with the types it applies to
 #[derive(Debug, Clone, PartialEq)]
 pub struct ChildSelection<'a, S: 'a> {
     pub(super) inner: LookAheadSelection<'a, S>,
@@ -108,6 +155,7 @@ pub struct LookAheadSelection<'a, S: 'a> {
     pub(super) name: &'a str,
     pub(super) alias: Option<&'a str>,
     pub(super) arguments: Vec<LookAheadArgument<'a, S>>,
+    pub(super) directives: Vec<LookAheadDirective<'a, S>>,
     pub(super) children: Vec<ChildSelection<'a, S>>,
 }
 
@@ -120,6 +168,7 @@ where
             name: "",
             alias: None,
             arguments: vec![],
+            directives: vec![],
             children: vec![],
         }
     }
@@ -129,59 +178,6 @@ impl<'a, S> LookAheadSelection<'a, S>
 where
     S: ScalarValue,
 {
-    fn should_include<'b, 'c>(
-        directives: Option<&'b Vec<Spanning<Directive<S>>>>,
-        vars: &'c Variables<S>,
-    ) -> bool
-    where
-        'b: 'a,
-        'c: 'a,
-    {
-        directives
-            .map(|d| {
-                d.iter().all(|d| {
-                    let d = &d.item;
-                    let arguments = &d.arguments;
-                    match (d.name.item, arguments) {
-                        ("include", &Some(ref a)) => a
-                            .item
-                            .items
-                            .iter()
-                            .find(|item| item.0.item == "if")
-                            .map(|&(_, ref v)| {
-                                if let LookAheadValue::Scalar(s) =
-                                    LookAheadValue::from_input_value(&v.item, vars)
-                                {
-                                    s.as_boolean().unwrap_or(false)
-                                } else {
-                                    false
-                                }
-                            })
-                            .unwrap_or(false),
-                        ("skip", &Some(ref a)) => a
-                            .item
-                            .items
-                            .iter()
-                            .find(|item| item.0.item == "if")
-                            .map(|&(_, ref v)| {
-                                if let LookAheadValue::Scalar(b) =
-                                    LookAheadValue::from_input_value(&v.item, vars)
-                                {
-                                    b.as_boolean().map(::std::ops::Not::not).unwrap_or(false)
-                                } else {
-                                    false
-                                }
-                            })
-                            .unwrap_or(false),
-                        ("skip", &None) => false,
-                        ("include", &None) => true,
-                        (_, _) => unreachable!(),
-                    }
-                })
-            })
-            .unwrap_or(true)
-    }
-
     pub(super) fn build_from_selection(
         s: &'a Selection<'a, S>,
         vars: &'a Variables<S>,
@@ -190,6 +186,28 @@ where
         Self::build_from_selection_with_parent(s, None, vars, fragments)
     }
 
+    /// The selection of the given name selecting the selection set, the
+    /// selections excluded by `@skip` and `@include` left out
+    pub(super) fn build_from_selection_set(
+        name: &'a str,
+        selection_set: &'a [Selection<'a, S>],
+        vars: &'a Variables<S>,
+        fragments: &'a HashMap<&'a str, Fragment<'a, S>>,
+    ) -> LookAheadSelection<'a, S> {
+        let mut ret = LookAheadSelection {
+            name,
+            alias: None,
+            arguments: Vec::new(),
+            directives: Vec::new(),
+            children: Vec::new(),
+        };
+        for s in selection_set {
+            let s = Self::build_from_selection_with_parent(s, Some(&mut ret), vars, fragments);
+            assert!(s.is_none());
+        }
+        ret
+    }
+
     fn build_from_selection_with_parent(
         s: &'a Selection<'a, S>,
         parent: Option<&mut Self>,
@@ -200,8 +218,7 @@ where
         match *s {
             Selection::Field(ref field) => {
                 let field = &field.item;
-                let include = Self::should_include(field.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&field.directives, vars) {
                     return None;
                 }
                 let name = field.name.item;
@@ -217,10 +234,20 @@ where
                             .collect()
                     })
                     .unwrap_or_else(Vec::new);
+                let directives = field
+                    .directives
+                    .as_ref()
+                    .map(|d| {
+                        d.iter()
+                            .map(|d| LookAheadDirective::new(&d.item, vars))
+                            .collect()
+                    })
+                    .unwrap_or_default();
                 let mut ret = LookAheadSelection {
                     name,
                     alias,
                     arguments,
+                    directives,
                     children: Vec::new(),
                 };
                 for c in field
@@ -249,8 +276,7 @@ where
                 }
             }
             Selection::FragmentSpread(ref fragment) => {
-                let include = Self::should_include(fragment.item.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&fragment.item.directives, vars) {
                     return None;
                 }
                 let f = fragments.get(&fragment.item.name.item).expect("a fragment");
@@ -275,8 +301,7 @@ where
                 None
             }
             Selection::InlineFragment(ref inline) if parent.is_some() => {
-                let include = Self::should_include(inline.item.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&inline.item.directives, vars) {
                     return None;
                 }
                 let parent = parent.unwrap();
@@ -317,16 +342,117 @@ where
             name: self.name,
             alias: self.alias,
             arguments: self.arguments.clone(),
+            directives: self.directives.clone(),
+        }
+    }
+
+    /// Appends an encoding of the selection to `out`, equal for equal
+    /// selections whatever the order of their arguments, and stable across
+    /// builds
+    pub(crate) fn encode(&self, out: &mut Vec<u8>) {
+        encode_str(self.name, out);
+        match self.alias {
+            Some(alias) => {
+                out.push(b'a');
+                encode_str(alias, out);
+            }
+            None => out.push(b'-'),
+        }
+        encode_arguments(&self.arguments, out);
+        encode_len(self.directives.len(), out);
+        for directive in &self.directives {
+            encode_str(directive.name, out);
+            encode_arguments(&directive.arguments, out);
+        }
+        encode_len(self.children.len(), out);
+        for child in &self.children {
+            match child.applies_for {
+                Applies::All => out.push(b'*'),
+                Applies::OnlyType(type_name) => {
+                    out.push(b't');
+                    encode_str(type_name, out);
+                }
+            }
+            child.inner.encode(out);
+        }
+    }
+}
+
+impl<'a, S> LookAheadValue<'a, S>
+where
+    S: ScalarValue,
+{
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            LookAheadValue::Null => out.push(b'n'),
+            LookAheadValue::Scalar(scalar) => {
+                if let Some(b) = scalar.as_boolean() {
+                    out.extend_from_slice(if b { b"T" } else { b"F" });
+                } else if let Some(i) = scalar.as_int() {
+                    out.push(b'i');
+                    out.extend_from_slice(&i.to_le_bytes());
+                } else if let Some(f) = scalar.as_float() {
+                    out.push(b'f');
+                    out.extend_from_slice(&f.to_bits().to_le_bytes());
+                } else if let Some(s) = scalar.as_str() {
+                    out.push(b's');
+                    encode_str(s, out);
+                } else {
+                    out.push(b'd');
+                    encode_str(&scalar.to_string(), out);
+                }
+            }
+            LookAheadValue::Enum(name) => {
+                out.push(b'e');
+                encode_str(name, out);
+            }
+            LookAheadValue::List(items) => {
+                out.push(b'l');
+                encode_len(items.len(), out);
+                for item in items {
+                    item.encode(out);
+                }
+            }
+            LookAheadValue::Object(fields) => {
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by_key(|(name, _)| *name);
+                out.push(b'o');
+                encode_len(fields.len(), out);
+                for (name, value) in fields {
+                    encode_str(name, out);
+                    value.encode(out);
+                }
+            }
         }
     }
 }
 
+fn encode_arguments<S: ScalarValue>(arguments: &[LookAheadArgument<S>], out: &mut Vec<u8>) {
+    let mut arguments = arguments.iter().collect::<Vec<_>>();
+    arguments.sort_by_key(|argument| argument.name);
+    encode_len(arguments.len(), out);
+    for argument in arguments {
+        encode_str(argument.name, out);
+        argument.value.encode(out);
+    }
+}
+
+fn encode_len(len: usize, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+fn encode_str(s: &str, out: &mut Vec<u8>) {
+    encode_len(s.len(), out);
+    out.extend_from_slice(s.as_bytes());
+}
+
 /// A selection performed by a query on a concrete type
 #[derive(Debug, PartialEq)]
 pub struct ConcreteLookAheadSelection<'a, S: 'a> {
     name: &'a str,
     alias: Option<&'a str>,
     arguments: Vec<LookAheadArgument<'a, S>>,
+    directives: Vec<LookAheadDirective<'a, S>>,
     children: Vec<ConcreteLookAheadSelection<'a, S>>,
 }
 
@@ -365,6 +491,18 @@ pub trait LookAheadMethods<'sel, S> {
         self.arguments().iter().find(|a| a.name == name)
     }
 
+    /// Get the directives applied to the current selection, like `@skip` or
+    /// custom ones registered in the schema
+    ///
+    /// Only the directives of the field itself are included, not the ones of
+    /// the fragments it is selected through.
+    fn directives(&self) -> &[LookAheadDirective<S>];
+
+    /// Get the directive with a given name applied to the current selection
+    fn directive(&self, name: &str) -> Option<&LookAheadDirective<S>> {
+        self.directives().iter().find(|d| d.name == name)
+    }
+
     /// Get the (possibly aliased) names of the top level children for the current selection
     #[deprecated(note = "please use `children` to access the child selections instead")]
     fn child_names(&self) -> Vec<&'sel str>;
@@ -386,6 +524,10 @@ impl<'a, S> LookAheadMethods<'a, S> for ConcreteLookAheadSelection<'a, S> {
         &self.arguments
     }
 
+    fn directives(&self) -> &[LookAheadDirective<S>] {
+        &self.directives
+    }
+
     fn child_names(&self) -> Vec<&'a str> {
         self.children.iter().map(|c| c.field_name()).collect()
     }
@@ -419,6 +561,10 @@ impl<'a, S> LookAheadMethods<'a, S> for LookAheadSelection<'a, S> {
         &self.arguments
     }
 
+    fn directives(&self) -> &[LookAheadDirective<S>] {
+        &self.directives
+    }
+
     fn child_names(&self) -> Vec<&'a str> {
         self.children.iter().map(|c| c.inner.field_name()).collect()
     }
@@ -502,12 +648,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -517,6 +665,7 @@ query Hero {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -556,12 +705,14 @@ query Hero {
                 name: "hero",
                 alias: Some("custom_hero"),
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -571,6 +722,7 @@ query Hero {
                             name: "name",
                             alias: Some("my_name"),
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -614,12 +766,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -629,6 +783,7 @@ query Hero {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -638,12 +793,14 @@ query Hero {
                             name: "friends",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: vec![
                                 ChildSelection {
                                     inner: LookAheadSelection {
                                         name: "name",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -653,6 +810,7 @@ query Hero {
                                         name: "id",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -699,12 +857,14 @@ query Hero {
                     name: "episode",
                     value: LookAheadValue::Enum("EMPIRE"),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -717,6 +877,7 @@ query Hero {
                                 name: "uppercase",
                                 value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(true)),
                             }],
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -760,12 +921,14 @@ query Hero($episode: Episode) {
                     name: "episode",
                     value: LookAheadValue::Enum("JEDI"),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -775,6 +938,7 @@ query Hero($episode: Episode) {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -816,11 +980,13 @@ query Hero($episode: Episode) {
                     name: "episode",
                     value: LookAheadValue::Null,
                 }],
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -863,12 +1029,14 @@ fragment commonFields on Character {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -878,6 +1046,7 @@ fragment commonFields on Character {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -887,6 +1056,7 @@ fragment commonFields on Character {
                             name: "appearsIn",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -927,12 +1097,22 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "include",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        true,
+                                    )),
+                                }],
+                            }],
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -942,6 +1122,15 @@ query Hero {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "skip",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        false,
+                                    )),
+                                }],
+                            }],
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -954,6 +1143,46 @@ query Hero {
         }
     }
 
+    #[test]
+    fn check_query_with_custom_directive() {
+        let docs = parse_document_source::<DefaultScalarValue>(
+            "
+query Hero($lang: String) {
+    hero {
+        name @locale(lang: $lang) @cached
+    }
+}",
+        )
+        .unwrap();
+        let fragments = extract_fragments(&docs);
+
+        if let crate::ast::Definition::Operation(ref op) = docs[0] {
+            let mut vars = Variables::default();
+            vars.insert("lang".into(), InputValue::scalar("fr"));
+            let look_ahead = LookAheadSelection::build_from_selection(
+                &op.item.selection_set[0],
+                &vars,
+                &fragments,
+            )
+            .unwrap();
+            let name = look_ahead.children()[0];
+
+            let names: Vec<_> = name.directives().iter().map(|d| d.name()).collect();
+            assert_eq!(names, vec!["locale", "cached"]);
+
+            let locale = name.directive("locale").unwrap();
+            assert_eq!(
+                locale.argument("lang").unwrap().value(),
+                &LookAheadValue::Scalar(&DefaultScalarValue::String("fr".into())),
+            );
+            assert!(name.directive("cached").unwrap().arguments().is_empty());
+            assert!(name.directive("skip").is_none());
+            assert!(look_ahead.directives().is_empty());
+        } else {
+            panic!("No Operation found");
+        }
+    }
+
     #[test]
     fn check_query_with_inline_fragments() {
         let docs = parse_document_source::<DefaultScalarValue>(
@@ -985,12 +1214,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1000,6 +1231,7 @@ query Hero {
                             name: "primaryFunction",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Droid"),
@@ -1009,6 +1241,7 @@ query Hero {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Human"),
@@ -1050,11 +1283,13 @@ query HeroAndHuman {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1072,11 +1307,13 @@ query HeroAndHuman {
                 name: "human",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1135,12 +1372,14 @@ fragment comparisonFields on Character {
                     name: "id",
                     value: LookAheadValue::Scalar(&DefaultScalarValue::Int(42)),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1150,6 +1389,7 @@ fragment comparisonFields on Character {
                             name: "__typename",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1159,6 +1399,7 @@ fragment comparisonFields on Character {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1168,6 +1409,7 @@ fragment comparisonFields on Character {
                             name: "appearsIn",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1177,6 +1419,7 @@ fragment comparisonFields on Character {
                             name: "primaryFunction",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Droid"),
@@ -1186,6 +1429,7 @@ fragment comparisonFields on Character {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Human"),
@@ -1195,12 +1439,22 @@ fragment comparisonFields on Character {
                             name: "friends",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "include",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        true,
+                                    )),
+                                }],
+                            }],
                             children: vec![
                                 ChildSelection {
                                     inner: LookAheadSelection {
                                         name: "__typename",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1210,6 +1464,7 @@ fragment comparisonFields on Character {
                                         name: "name",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1219,6 +1474,7 @@ fragment comparisonFields on Character {
                                         name: "appearsIn",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1228,6 +1484,7 @@ fragment comparisonFields on Character {
                                         name: "primaryFunction",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::OnlyType("Droid"),
@@ -1237,6 +1494,7 @@ fragment comparisonFields on Character {
                                         name: "height",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::OnlyType("Human"),
@@ -1285,17 +1543,20 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ConcreteLookAheadSelection {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     ConcreteLookAheadSelection {
                         name: "height",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                 ],
@@ -1313,12 +1574,14 @@ query Hero {
             name: "hero",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: vec![
                 ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1328,12 +1591,14 @@ query Hero {
                         name: "friends",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: vec![
                             ChildSelection {
                                 inner: LookAheadSelection {
                                     name: "id",
                                     alias: None,
                                     arguments: Vec::new(),
+                                    directives: Vec::new(),
                                     children: Vec::new(),
                                 },
                                 applies_for: Applies::All,
@@ -1343,6 +1608,7 @@ query Hero {
                                     name: "name",
                                     alias: None,
                                     arguments: Vec::new(),
+                                    directives: Vec::new(),
                                     children: Vec::new(),
                                 },
                                 applies_for: Applies::All,
@@ -1361,6 +1627,7 @@ query Hero {
             name: "id",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: Vec::new(),
         };
         assert_eq!(id, Some(&expected));
@@ -1375,12 +1642,14 @@ query Hero {
             name: "friends",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: vec![
                 ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1390,6 +1659,7 @@ query Hero {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1434,16 +1704,19 @@ fragment heroFriendNames on Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "friends",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: vec![ChildSelection {
                             inner: LookAheadSelection {
                                 name: "name",
                                 alias: None,
                                 arguments: Vec::new(),
+                                directives: Vec::new(),
                                 children: Vec::new(),
                             },
                             applies_for: Applies::All,
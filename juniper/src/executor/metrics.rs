@@ -0,0 +1,214 @@
+//! Reporting of the execution through the [`metrics`](https://docs.rs/metrics)
+//! facade, enabled by the `metrics` feature
+//!
+//! The following metrics are emitted, to the recorder installed by the
+//! application:
+//!
+//! - `graphql_operations_total`, counting the executed operations;
+//! - `graphql_errors_total`, counting the errors of the executed operations;
+//! - `graphql_operation_duration_seconds`, the duration of queries and
+//!   mutations;
+//! - `graphql_field_duration_seconds`, the duration of resolving each field,
+//!   labeled with `type` and `field`.
+//!
+//! The operation metrics are labeled with `operation_type`, being `query`,
+//! `mutation` or `subscription`, and `operation_name`, empty for anonymous
+//! operations.
+
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(feature = "metrics")]
+use ::metrics::{counter, histogram};
+
+use crate::ast::Operation;
+#[cfg(feature = "metrics")]
+use crate::ast::OperationType;
+
+/// Measures an operation from its start, reporting it once finished
+pub(crate) struct OperationMetrics {
+    #[cfg(feature = "metrics")]
+    labels: [(&'static str, String); 2],
+    #[cfg(feature = "metrics")]
+    start: Instant,
+}
+
+impl OperationMetrics {
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn start<S>(operation: &Operation<S>) -> Self {
+        OperationMetrics {
+            #[cfg(feature = "metrics")]
+            labels: [
+                (
+                    "operation_type",
+                    match operation.operation_type {
+                        OperationType::Query => "query",
+                        OperationType::Mutation => "mutation",
+                        OperationType::Subscription => "subscription",
+                    }
+                    .to_owned(),
+                ),
+                (
+                    "operation_name",
+                    operation
+                        .name
+                        .as_ref()
+                        .map_or("", |name| name.item)
+                        .to_owned(),
+                ),
+            ],
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
+        }
+    }
+
+    /// Reports the operation, which resulted in the provided number of errors
+    ///
+    /// The duration of subscriptions isn't reported, as they're only finished
+    /// being set up.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn finish(self, error_count: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            counter!("graphql_operations_total", 1, &self.labels);
+            counter!("graphql_errors_total", error_count as u64, &self.labels);
+            if self.labels[0].1 != "subscription" {
+                let duration = self.start.elapsed().as_secs_f64();
+                histogram!("graphql_operation_duration_seconds", duration, &self.labels);
+            }
+        }
+    }
+}
+
+/// Measures the resolution of a field, reporting it once finished
+pub(crate) struct FieldMetrics {
+    #[cfg(feature = "metrics")]
+    labels: [(&'static str, String); 2],
+    #[cfg(feature = "metrics")]
+    start: Instant,
+}
+
+impl FieldMetrics {
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn start(type_name: Option<&str>, field_name: &str) -> Self {
+        FieldMetrics {
+            #[cfg(feature = "metrics")]
+            labels: [
+                ("type", type_name.unwrap_or_default().to_owned()),
+                ("field", field_name.to_owned()),
+            ],
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn finish(self) {
+        #[cfg(feature = "metrics")]
+        {
+            let duration = self.start.elapsed().as_secs_f64();
+            histogram!("graphql_field_duration_seconds", duration, &self.labels);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Recorder, SharedString,
+        Unit,
+    };
+
+    use crate::{EmptyMutation, EmptySubscription, RootNode, Variables};
+
+    /// Logs the emitted metrics as `name{label=value,..}`
+    struct LogRecorder(Arc<Mutex<Vec<String>>>);
+
+    struct LogEntry(Arc<Mutex<Vec<String>>>, String);
+
+    impl CounterFn for LogEntry {
+        fn increment(&self, value: u64) {
+            let entry = format!("{} += {}", self.1, value);
+            self.0.lock().unwrap().push(entry);
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl HistogramFn for LogEntry {
+        fn record(&self, _: f64) {
+            let entry = format!("{} recorded", self.1);
+            self.0.lock().unwrap().push(entry);
+        }
+    }
+
+    impl LogRecorder {
+        fn entry(&self, key: &Key) -> Arc<LogEntry> {
+            let labels: Vec<_> = key
+                .labels()
+                .map(|l| format!("{}={}", l.key(), l.value()))
+                .collect();
+            let name = format!("{}{{{}}}", key.name(), labels.join(","));
+            Arc::new(LogEntry(self.0.clone(), name))
+        }
+    }
+
+    impl Recorder for LogRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key) -> Counter {
+            Counter::from_arc(self.entry(key))
+        }
+
+        fn register_gauge(&self, _: &Key) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key) -> Histogram {
+            Histogram::from_arc(self.entry(key))
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn greeting() -> crate::FieldResult<&str> {
+            Err("Unavailable".into())
+        }
+    }
+
+    #[test]
+    fn reports_operations_and_fields() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        metrics::set_boxed_recorder(Box::new(LogRecorder(log.clone()))).unwrap();
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        crate::execute_sync(
+            "query MetricsTest { greeting }",
+            None,
+            &schema,
+            &Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        let log = log.lock().unwrap();
+        let labels = "operation_type=query,operation_name=MetricsTest";
+        for expected in &[
+            "graphql_field_duration_seconds{type=Query,field=greeting} recorded".to_owned(),
+            format!("graphql_operations_total{{{}}} += 1", labels),
+            format!("graphql_errors_total{{{}}} += 1", labels),
+            format!("graphql_operation_duration_seconds{{{}}} recorded", labels),
+        ] {
+            assert!(log.contains(expected), "{} not in {:?}", expected, log);
+        }
+    }
+}
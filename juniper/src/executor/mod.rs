@@ -3,17 +3,27 @@ use std::{
     cmp::Ordering,
     collections::HashMap,
     fmt::{Debug, Display},
+    future::Future,
+    hash::Hash,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use fnv::FnvHashMap;
-use futures::Stream;
+use futures::{
+    future::{self, BoxFuture, LocalBoxFuture},
+    FutureExt as _, Stream,
+};
+use serde::Serialize;
 
 use crate::{
     ast::{
         Definition, Document, Fragment, FromInputValue, InputValue, Operation, OperationType,
         Selection, ToInputValue, Type,
     },
+    auth::Authorization,
+    cache::{self, CacheBackend, CachePolicy, CachePolicyCollector, FieldCache},
+    deprecation::DeprecationTracker,
     parser::{SourcePosition, Spanning},
     schema::{
         meta::{
@@ -21,28 +31,55 @@ use crate::{
             InterfaceMeta, ListMeta, MetaType, NullableMeta, ObjectMeta, PlaceholderMeta,
             ScalarMeta, UnionMeta,
         },
-        model::{RootNode, SchemaType, TypeType},
+        model::{RootNode, SchemaError, SchemaType, TypeType},
     },
+    stack::{ensure_sufficient_stack, SufficientStack},
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
-        base::{GraphQLType, GraphQLValue},
+        async_local::{GraphQLTypeAsyncLocal, GraphQLValueAsyncLocal},
+        base::{is_excluded, GraphQLType, GraphQLValue},
         name::Name,
         subscriptions::{GraphQLSubscriptionType, GraphQLSubscriptionValue},
     },
-    value::{DefaultScalarValue, ParseScalarValue, ScalarValue, Value},
+    value::{DefaultScalarValue, Object, ParseScalarValue, ScalarValue, Value},
     GraphQLError,
 };
 
 pub use self::{
+    context_cell::ContextCell,
+    field_result::{OptionExt, ResultExt},
     look_ahead::{
-        Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue,
+        Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadDirective,
+        LookAheadMethods, LookAheadSelection, LookAheadValue,
     },
+    operation_info::{OperationInfo, VariableInfo},
     owned_executor::OwnedExecutor,
+    retries::{FieldPolicy, Sleep},
+    spawn::{FieldScope, InScope, JoinHandle},
+    type_builder::{EnumBuilder, InputObjectBuilder, ObjectBuilder},
+};
+
+pub(crate) use self::{
+    budget::ValueBudget,
+    metrics::{FieldMetrics, OperationMetrics},
+    panics::{catch_panic, CatchPanic},
+    retries::Timer,
+    slow_fields::SlowFieldTimer,
 };
 
+mod budget;
+mod context_cell;
+mod field_result;
 mod look_ahead;
+mod metrics;
+mod operation_info;
 mod owned_executor;
+mod panics;
+mod retries;
+mod slow_fields;
+mod spawn;
+mod type_builder;
+mod warnings;
 
 /// A type registry used to build schemas
 ///
@@ -52,6 +89,17 @@ mod owned_executor;
 pub struct Registry<'r, S = DefaultScalarValue> {
     /// Currently registered types
     pub types: FnvHashMap<Name, MetaType<'r, S>>,
+    /// Rust types registering each GraphQL type name, the first one first
+    rust_types: FnvHashMap<String, Vec<&'static str>>,
+    /// Definitions of types registering an already taken name, to compare
+    /// with the registered ones once all the types are built
+    redefinitions: Vec<Redefinition<'r, S>>,
+}
+
+struct Redefinition<'r, S> {
+    name: String,
+    rust_type: &'static str,
+    definition: MetaType<'r, S>,
 }
 
 #[derive(Clone)]
@@ -77,6 +125,10 @@ where
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
+    cache_policy: &'r CachePolicyCollector,
+    extensions: &'r RwLock<Object<S>>,
+    authorization: &'r Arc<Authorization>,
+    budget: &'r ValueBudget,
     field_path: Arc<FieldPath<'a>>,
 }
 
@@ -84,7 +136,7 @@ where
 ///
 /// All execution errors contain the source position in the query of the field
 /// that failed to resolve. It also contains the field stack.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ExecutionError<S> {
     location: SourcePosition,
     path: Vec<String>,
@@ -93,6 +145,52 @@ pub struct ExecutionError<S> {
 
 impl<S> Eq for ExecutionError<S> where Self: PartialEq {}
 
+/// Output of an executed operation, sent as the members of the response
+///
+/// `D` is the type of the data, `Value<ValuesStream<S>>` for subscriptions
+/// resolved with [`resolve_into_stream`](../fn.resolve_into_stream.html).
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(bound(serialize = "D: Serialize, S: ScalarValue"))]
+pub struct ExecutionOutput<S = DefaultScalarValue, D = Value<S>> {
+    /// The output data.
+    pub data: D,
+
+    /// The errors that occurred. Note that the presence of errors does not mean there is no data.
+    /// The output can have both data and errors.
+    pub errors: Vec<ExecutionError<S>>,
+
+    /// The entries added with
+    /// [`Executor::add_extension`](struct.Executor.html#method.add_extension),
+    /// sent in the `extensions` member of the response
+    #[serde(skip_serializing_if = "is_empty")]
+    pub extensions: Object<S>,
+}
+
+impl<S> ExecutionOutput<S> {
+    /// Creates execution output from data, with no errors.
+    pub fn from_data(data: Value<S>) -> Self {
+        Self {
+            data,
+            errors: vec![],
+            extensions: Object::with_capacity(0),
+        }
+    }
+}
+
+fn is_empty<S>(extensions: &Object<S>) -> bool {
+    extensions.field_count() == 0
+}
+
+/// Data about an executed operation, besides its output
+#[derive(Debug, Default, PartialEq)]
+pub struct ExecutionMetadata {
+    /// How long, and by whom, the response may be cached, computed from the
+    /// hints of the resolved fields
+    ///
+    /// It's `None` for responses which must not be cached, mutations included.
+    pub cache_policy: Option<CachePolicy>,
+}
+
 impl<S> ExecutionError<S> {
     /// Construct a new execution error occuring at the beginning of the query
     pub fn at_origin(error: FieldError<S>) -> ExecutionError<S> {
@@ -146,7 +244,7 @@ where
 ///     Ok(s)
 /// }
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FieldError<S = DefaultScalarValue> {
     message: String,
     extensions: Value<S>,
@@ -433,7 +531,7 @@ where
     where
         T: GraphQLValue<S, Context = CtxT> + ?Sized,
     {
-        value.resolve(info, self.current_selection_set, self)
+        ensure_sufficient_stack(|| value.resolve(info, self.current_selection_set, self))
     }
 
     /// Resolve a single arbitrary value into an `ExecutionResult`
@@ -444,9 +542,7 @@ where
         CtxT: Sync,
         S: Send + Sync,
     {
-        value
-            .resolve_async(info, self.current_selection_set, self)
-            .await
+        SufficientStack(value.resolve_async(info, self.current_selection_set, self)).await
     }
 
     /// Resolve a single arbitrary value, mapping the context to a new type
@@ -494,6 +590,44 @@ where
         })
     }
 
+    /// Resolve a single arbitrary value into an `ExecutionResult` on the
+    /// current thread
+    pub async fn resolve_local<T>(&self, info: &T::TypeInfo, value: &T) -> ExecutionResult<S>
+    where
+        T: GraphQLValueAsyncLocal<S, Context = CtxT> + ?Sized,
+    {
+        SufficientStack(value.resolve_local(info, self.current_selection_set, self)).await
+    }
+
+    /// Resolve a single arbitrary value on the current thread, mapping the
+    /// context to a new type
+    pub async fn resolve_with_ctx_local<NewCtxT, T>(
+        &self,
+        info: &T::TypeInfo,
+        value: &T,
+    ) -> ExecutionResult<S>
+    where
+        T: GraphQLValueAsyncLocal<S, Context = NewCtxT> + ?Sized,
+        NewCtxT: FromContext<CtxT>,
+    {
+        let e = self.replaced_context(<NewCtxT as FromContext<CtxT>>::from(self.context));
+        e.resolve_local(info, value).await
+    }
+
+    /// Resolve a single arbitrary value into a return value on the current
+    /// thread
+    ///
+    /// If the field fails to resolve, `null` will be returned.
+    pub async fn resolve_into_value_local<T>(&self, info: &T::TypeInfo, value: &T) -> Value<S>
+    where
+        T: GraphQLValueAsyncLocal<S, Context = CtxT> + ?Sized,
+    {
+        self.resolve_local(info, value).await.unwrap_or_else(|e| {
+            self.push_error(e);
+            Value::null()
+        })
+    }
+
     /// Derive a new executor by replacing the context
     ///
     /// This can be used to connect different types, e.g. from different Rust
@@ -511,6 +645,10 @@ where
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            cache_policy: self.cache_policy,
+            extensions: self.extensions,
+            authorization: self.authorization,
+            budget: self.budget,
             field_path: self.field_path.clone(),
         }
     }
@@ -539,6 +677,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
+            extensions: self.extensions,
+            authorization: self.authorization,
+            budget: self.budget,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -565,6 +707,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
+            extensions: self.extensions,
+            authorization: self.authorization,
+            budget: self.budget,
             field_path: self.field_path.clone(),
         }
     }
@@ -587,6 +733,40 @@ where
         self.schema
     }
 
+    /// The decisions of the policy evaluator for the operation
+    pub(crate) fn authorization(&self) -> &Authorization {
+        self.authorization
+    }
+
+    /// Charges `count` values to the budget of the response, see
+    /// [`RootNode::with_max_response_values`](struct.RootNode.html#method.with_max_response_values)
+    ///
+    /// Returns the result of the values instead of resolving them if they
+    /// exceed the budget: an error the first time, and `null` afterwards.
+    pub(crate) fn charge_values(&self, count: usize) -> Option<ExecutionResult<S>> {
+        match self.budget.charge(count) {
+            budget::Charge::Within => None,
+            budget::Charge::Exceeded(limit) => Some(Err(budget::exceeded(limit))),
+            budget::Charge::Exhausted => Some(Ok(Value::null())),
+        }
+    }
+
+    /// The name of the GraphQL type the value is resolved as, e.g. for logging
+    ///
+    /// Interfaces and unions are resolved into their concrete object type, so
+    /// this is the name `__typename` would return. Returns `None` for values
+    /// of unnamed types, like lists.
+    pub fn type_name_of<T>(&self, value: &T, info: &T::TypeInfo) -> Option<String>
+    where
+        T: GraphQLValue<S, Context = CtxT> + ?Sized,
+    {
+        let name = value.type_name(info)?;
+        match self.schema.concrete_type_by_name(name) {
+            Some(meta) if meta.is_abstract() => Some(value.concrete_type_name(self.context, info)),
+            _ => Some(name.to_owned()),
+        }
+    }
+
     #[doc(hidden)]
     pub fn current_type(&self) -> &TypeType<'a, S> {
         &self.current_type
@@ -626,6 +806,27 @@ where
         });
     }
 
+    /// Adds an entry to the `extensions` member of the response, replacing the
+    /// value previously added with the same key
+    ///
+    /// Resolvers, and anything else given the executor, can report data about
+    /// the execution this way, like rate limits or trace IDs.
+    pub fn add_extension<K: Into<String>>(&self, key: K, value: Value<S>) {
+        let mut extensions = self.extensions.write().unwrap();
+
+        extensions.add_field(key.into(), value);
+    }
+
+    /// Adds a non-fatal warning at the current location to the `warnings`
+    /// entry of the `extensions` member of the response
+    ///
+    /// Juniper warns about the usage of deprecated fields and enum values this
+    /// way. Resolvers can warn about other legacy behavior, like arguments
+    /// that will soon be required, to let clients migrate before it's removed.
+    pub fn add_warning<M: Into<String>>(&self, message: M) {
+        self.add_warning_at(message.into(), *self.location());
+    }
+
     /// Returns new [`ExecutionError`] at current location
     pub fn new_error(&self, error: FieldError<S>) -> ExecutionError<S> {
         let mut path = Vec::new();
@@ -649,17 +850,15 @@ where
         };
         self.parent_selection_set
             .map(|p| {
-                let found_field = p.iter().find(|&x| {
-                    match *x {
-                        Selection::Field(ref field) => {
-                            let field = &field.item;
-                            // TODO: support excludes.
-                            let name = field.name.item;
-                            let alias = field.alias.as_ref().map(|a| a.item);
-                            alias.unwrap_or(name) == field_name
-                        }
-                        _ => false,
+                let found_field = p.iter().find(|&x| match *x {
+                    Selection::Field(ref field) => {
+                        let field = &field.item;
+                        let name = field.name.item;
+                        let alias = field.alias.as_ref().map(|a| a.item);
+                        alias.unwrap_or(name) == field_name
+                            && !is_excluded(&field.directives, self.variables)
                     }
+                    _ => false,
                 });
                 if let Some(p) = found_field {
                     LookAheadSelection::build_from_selection(&p, self.variables, self.fragments)
@@ -669,31 +868,228 @@ where
             })
             .filter(|s| s.is_some())
             .unwrap_or_else(|| {
-                Some(LookAheadSelection {
-                    name: self.current_type.innermost_concrete().name().unwrap_or(""),
-                    alias: None,
-                    arguments: Vec::new(),
-                    children: self
-                        .current_selection_set
-                        .map(|s| {
-                            s.iter()
-                                .map(|s| ChildSelection {
-                                    inner: LookAheadSelection::build_from_selection(
-                                        &s,
-                                        self.variables,
-                                        self.fragments,
-                                    )
-                                    .expect("a child selection"),
-                                    applies_for: Applies::All,
-                                })
-                                .collect()
-                        })
-                        .unwrap_or_else(Vec::new),
-                })
+                Some(LookAheadSelection::build_from_selection_set(
+                    self.current_type.innermost_concrete().name().unwrap_or(""),
+                    self.current_selection_set.unwrap_or(&[]),
+                    self.variables,
+                    self.fragments,
+                ))
             })
             .unwrap_or_default()
     }
 
+    /// Key of the cached result of the current field, `None` if the schema has
+    /// no cache backend
+    #[doc(hidden)]
+    pub fn field_cache_key<T>(
+        &self,
+        type_name: &str,
+        field_name: &str,
+        parent: &T,
+    ) -> Option<String>
+    where
+        T: Hash + ?Sized,
+    {
+        self.schema.field_cache.as_ref()?;
+        Some(cache::field_key(
+            type_name,
+            field_name,
+            parent,
+            &self.look_ahead(),
+            self.authorization,
+        ))
+    }
+
+    /// Timer of the fields with a timeout, `None` if the schema has none
+    #[doc(hidden)]
+    pub fn sleep(&self) -> Option<&dyn Sleep> {
+        self.schema.sleep.as_ref().map(|timer| &*timer.0)
+    }
+
+    #[doc(hidden)]
+    pub fn resolve_cached<F>(
+        &self,
+        key: Option<String>,
+        ttl: Duration,
+        resolve: F,
+    ) -> ExecutionResult<S>
+    where
+        F: FnOnce() -> ExecutionResult<S>,
+    {
+        let (cache, key) = match (&self.schema.field_cache, key) {
+            (Some(FieldCache(cache)), Some(key)) => (cache, key),
+            _ => return resolve(),
+        };
+        if let Some(value) = cache.get(&key) {
+            self.restrict_cache_policy_of_cached();
+            return Ok(value);
+        }
+        let result = resolve();
+        self.store_cached(cache.as_ref(), &key, &result, ttl);
+        result
+    }
+
+    #[doc(hidden)]
+    pub async fn resolve_cached_async<F>(
+        &self,
+        key: Option<String>,
+        ttl: Duration,
+        resolve: F,
+    ) -> ExecutionResult<S>
+    where
+        S: Send,
+        F: Future<Output = ExecutionResult<S>>,
+    {
+        let (cache, key) = match (&self.schema.field_cache, key) {
+            (Some(FieldCache(cache)), Some(key)) => (cache, key),
+            _ => return resolve.await,
+        };
+        if let Some(value) = cache.get_async(&key).await {
+            self.restrict_cache_policy_of_cached();
+            return Ok(value);
+        }
+        let result = resolve.await;
+        self.store_cached(cache.as_ref(), &key, &result, ttl);
+        result
+    }
+
+    /// Restricts the cache policy of the response by the hints of a field
+    /// about to be resolved
+    pub(crate) fn restrict_cache_policy(&self, parent_type: &MetaType<S>, field: &Field<S>) {
+        let is_introspection = |name: Option<&str>| matches!(name, Some(n) if n.starts_with("__"));
+        if field.name.starts_with("__") || is_introspection(parent_type.name()) {
+            // Introspection doesn't change over time.
+            return;
+        }
+        let field_type = self
+            .schema
+            .concrete_type_by_name(field.field_type.innermost_name());
+        let (type_hint, is_composite) = match field_type {
+            Some(MetaType::Object(ObjectMeta { cache_hint, .. }))
+            | Some(MetaType::Interface(InterfaceMeta { cache_hint, .. })) => (*cache_hint, true),
+            Some(MetaType::Union(_)) => (None, true),
+            _ => (None, false),
+        };
+        let field_hint = field.cache_hint.unwrap_or_default();
+        let type_hint = type_hint.unwrap_or_default();
+
+        let mut max_age = field_hint.max_age.or(type_hint.max_age);
+        let is_root = parent_type.name() == Some(self.schema.query_type_name.as_str());
+        if max_age.is_none() && (is_composite || is_root) {
+            max_age = Some(0);
+        }
+        self.cache_policy
+            .restrict(max_age, field_hint.scope.or(type_hint.scope));
+    }
+
+    /// Restricts the cache policy by the hints of the sub-fields of a field
+    /// served from the field cache, which aren't resolved
+    fn restrict_cache_policy_of_cached(&self) {
+        if let Some(selection_set) = self.current_selection_set {
+            let meta_type = self.current_type.innermost_concrete();
+            self.restrict_cache_policy_of_selection(meta_type, selection_set);
+        }
+    }
+
+    /// Restricts the cache policy by the hints of the fields of the selection
+    /// set, and of their own selection sets
+    ///
+    /// The concrete types of abstract values aren't known, so the fragments on
+    /// each of them are assumed to be selected.
+    fn restrict_cache_policy_of_selection(
+        &self,
+        meta_type: &MetaType<S>,
+        selection_set: &[Selection<S>],
+    ) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(Spanning { item: f, .. }) => {
+                    if is_excluded(&f.directives, self.variables) {
+                        continue;
+                    }
+                    let field = match meta_type.field_by_name(f.name.item) {
+                        Some(field) => field,
+                        None => continue,
+                    };
+                    self.restrict_cache_policy(meta_type, field);
+
+                    let field_type = self
+                        .schema
+                        .concrete_type_by_name(field.field_type.innermost_name());
+                    if let (Some(field_type), Some(selection_set)) = (field_type, &f.selection_set)
+                    {
+                        self.restrict_cache_policy_of_selection(field_type, selection_set);
+                    }
+                }
+                Selection::FragmentSpread(Spanning { item: spread, .. }) => {
+                    if is_excluded(&spread.directives, self.variables) {
+                        continue;
+                    }
+                    let fragment = match self.fragments.get(spread.name.item) {
+                        Some(fragment) => fragment,
+                        None => continue,
+                    };
+                    if let Some(fragment_type) = self
+                        .schema
+                        .concrete_type_by_name(fragment.type_condition.item)
+                    {
+                        self.restrict_cache_policy_of_selection(
+                            fragment_type,
+                            &fragment.selection_set,
+                        );
+                    }
+                }
+                Selection::InlineFragment(Spanning { item: fragment, .. }) => {
+                    if is_excluded(&fragment.directives, self.variables) {
+                        continue;
+                    }
+                    let fragment_type = match fragment.type_condition {
+                        Some(ref condition) => self.schema.concrete_type_by_name(condition.item),
+                        None => Some(meta_type),
+                    };
+                    if let Some(fragment_type) = fragment_type {
+                        self.restrict_cache_policy_of_selection(
+                            fragment_type,
+                            &fragment.selection_set,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Counts the resolution of a field in the usage statistics, if collected
+    pub(crate) fn record_field_usage(&self, type_name: Option<&str>, field: &str, failed: bool) {
+        if let Some(ref collector) = self.schema.usage_collector {
+            collector.record_field(type_name.unwrap_or_default(), field, failed);
+        }
+    }
+
+    /// Stores the result, unless resolving the field or its sub-fields failed
+    fn store_cached(
+        &self,
+        cache: &(dyn CacheBackend<S> + Send + Sync),
+        key: &str,
+        result: &ExecutionResult<S>,
+        ttl: Duration,
+    ) {
+        let value = match result {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+        let failed = self
+            .errors
+            .read()
+            .unwrap()
+            .iter()
+            .any(|e| e.path.starts_with(&path));
+        if !failed {
+            cache.set(key, value, ttl);
+        }
+    }
+
     /// Create new `OwnedExecutor` and clone all current data
     /// (except for errors) there
     ///
@@ -711,6 +1107,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: CachePolicyCollector::new(),
+            extensions: RwLock::new(Object::with_capacity(0)),
+            authorization: Arc::clone(self.authorization),
+            budget: ValueBudget::new(self.schema.max_response_values),
             field_path: Arc::clone(&self.field_path),
         }
     }
@@ -758,111 +1158,233 @@ impl<S> ExecutionError<S> {
     pub fn path(&self) -> &[String] {
         &self.path
     }
+
+    /// Replaces the error, keeping its location and path
+    pub(crate) fn replace_error(&mut self, error: FieldError<S>) {
+        self.error = error;
+    }
 }
 
-/// Create new `Executor` and start query/mutation execution.
-/// Returns `IsSubscription` error if subscription is passed.
-pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
-    document: &'b Document<S>,
-    operation: &'b Spanning<Operation<S>>,
-    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
-    variables: &Variables<S>,
-    context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+/// State of an operation being executed, shared by the synchronous,
+/// asynchronous, local and subscription entry points, so they only differ in
+/// how the root value is resolved.
+struct OperationExecution<'d, 'v, S: Clone> {
+    fragments: HashMap<&'d str, Fragment<'d, S>>,
+    variables: Cow<'v, Variables<S>>,
+    errors: RwLock<Vec<ExecutionError<S>>>,
+    cache_policy: CachePolicyCollector,
+    extensions: RwLock<Object<S>>,
+    authorization: Arc<Authorization>,
+    budget: ValueBudget,
+    metrics: OperationMetrics,
+}
+
+impl<'d, 'v, S> OperationExecution<'d, 'v, S>
 where
     S: ScalarValue,
-    QueryT: GraphQLType<S>,
-    MutationT: GraphQLType<S, Context = QueryT::Context>,
-    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
 {
-    if operation.item.operation_type == OperationType::Subscription {
-        return Err(GraphQLError::IsSubscription);
-    }
-
-    let mut fragments = vec![];
-    for def in document.iter() {
-        if let Definition::Fragment(f) = def {
-            fragments.push(f)
-        };
-    }
-
-    let default_variable_values = operation.item.variable_definitions.as_ref().map(|defs| {
-        defs.item
-            .items
+    fn start(
+        document: &Document<'d, S>,
+        operation: &Spanning<Operation<S>>,
+        variables: &'v Variables<S>,
+        authorization: Authorization,
+        max_values: Option<usize>,
+    ) -> Self {
+        let metrics = OperationMetrics::start(&operation.item);
+
+        let fragments = document
             .iter()
-            .filter_map(|&(ref name, ref def)| {
-                def.default_value
-                    .as_ref()
-                    .map(|i| (name.item.to_owned(), i.item.clone()))
+            .filter_map(|def| match def {
+                Definition::Fragment(f) => Some((f.item.name.item, f.item.clone())),
+                _ => None,
             })
-            .collect::<HashMap<String, InputValue<S>>>()
-    });
-
-    let errors = RwLock::new(Vec::new());
-    let value;
-
-    {
-        let mut all_vars;
-        let mut final_vars = variables;
-
-        if let Some(defaults) = default_variable_values {
-            all_vars = variables.clone();
-
-            for (name, value) in defaults {
-                all_vars.entry(name).or_insert(value);
+            .collect();
+
+        let mut variables = Cow::Borrowed(variables);
+        if let Some(ref defs) = operation.item.variable_definitions {
+            for &(ref name, ref def) in &defs.item.items {
+                if let Some(ref default) = def.default_value {
+                    if !variables.contains_key(name.item) {
+                        variables
+                            .to_mut()
+                            .insert(name.item.to_owned(), default.item.clone());
+                    }
+                }
             }
-
-            final_vars = &all_vars;
         }
 
-        let root_type = match operation.item.operation_type {
-            OperationType::Query => root_node.schema.query_type(),
-            OperationType::Mutation => root_node
-                .schema
-                .mutation_type()
-                .expect("No mutation type found"),
-            OperationType::Subscription => unreachable!(),
-        };
+        OperationExecution {
+            fragments,
+            variables,
+            errors: RwLock::new(Vec::new()),
+            cache_policy: CachePolicyCollector::new(),
+            extensions: RwLock::new(Object::with_capacity(0)),
+            authorization: Arc::new(authorization),
+            budget: ValueBudget::new(max_values),
+            metrics,
+        }
+    }
 
-        let executor = Executor {
-            fragments: &fragments
-                .iter()
-                .map(|f| (f.item.name.item, f.item.clone()))
-                .collect(),
-            variables: final_vars,
+    fn executor<'r, 'a, CtxT>(
+        &'r self,
+        operation: &'r Spanning<Operation<'a, S>>,
+        root_type: TypeType<'a, S>,
+        schema: &'a SchemaType<'a, S>,
+        context: &'a CtxT,
+    ) -> Executor<'r, 'a, CtxT, S>
+    where
+        'd: 'a,
+    {
+        Executor {
+            fragments: &self.fragments,
+            variables: &self.variables,
             current_selection_set: Some(&operation.item.selection_set[..]),
             parent_selection_set: None,
             current_type: root_type,
-            schema: &root_node.schema,
+            schema,
             context,
-            errors: &errors,
+            errors: &self.errors,
+            cache_policy: &self.cache_policy,
+            extensions: &self.extensions,
+            authorization: &self.authorization,
+            budget: &self.budget,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+        }
+    }
+
+    fn finish<CtxT, D>(
+        self,
+        data: D,
+        schema: &SchemaType<S>,
+        deprecation_tracker: Option<&DeprecationTracker<CtxT>>,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+        context: &CtxT,
+    ) -> (ExecutionOutput<S, D>, ExecutionMetadata) {
+        let mut errors = self.errors.into_inner().unwrap();
+        errors.sort();
+
+        self.metrics.finish(errors.len());
+        if let Some(ref collector) = schema.usage_collector {
+            collector.record_operation(operation.item.name.as_ref().map(|name| name.item));
+        }
+        if let Some(tracker) = deprecation_tracker {
+            tracker.track(schema, document, operation, context);
+        }
+
+        let metadata = ExecutionMetadata {
+            cache_policy: match operation.item.operation_type {
+                OperationType::Query => self.cache_policy.into_policy(
+                    schema
+                        .operation_policies
+                        .of(operation)
+                        .and_then(|policy| policy.cache_max_age),
+                ),
+                _ => None,
+            },
         };
+        let output = ExecutionOutput {
+            data,
+            errors,
+            extensions: self.extensions.into_inner().unwrap(),
+        };
+
+        (output, metadata)
+    }
+}
 
-        value = match operation.item.operation_type {
+/// Returns the root type of an operation which isn't a subscription
+fn query_root_type<'a, S>(
+    operation: &Spanning<Operation<S>>,
+    schema: &'a SchemaType<'a, S>,
+) -> TypeType<'a, S> {
+    match operation.item.operation_type {
+        OperationType::Query => schema.query_type(),
+        OperationType::Mutation => schema.mutation_type().expect("No mutation type found"),
+        OperationType::Subscription => unreachable!(),
+    }
+}
+
+/// Checks the schema's operation policies allow the `operation` with the
+/// `context`, then charges its cost to the budget of the client
+fn check_operation<QueryT, MutationT, SubscriptionT, S>(
+    document: &Document<S>,
+    operation: &Spanning<Operation<S>>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(), FieldError<S>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    root_node.schema.operation_policies.check(
+        operation,
+        root_node.policy_evaluator.as_deref(),
+        context,
+    )?;
+    match root_node.rate_limiter {
+        Some(ref limiter) => limiter.charge(document, operation, variables, context),
+        None => Ok(()),
+    }
+}
+
+/// How the root value of a query or a mutation is resolved, the only part of
+/// the execution differing between the synchronous, asynchronous and local
+/// entry points
+trait ResolveRoot<'e, QueryT, MutationT, SubscriptionT, S>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    type Future: Future<Output = Value<S>>;
+
+    fn resolve(
+        self,
+        executor: &'e Executor<'e, 'e, QueryT::Context, S>,
+        root_node: &'e RootNode<'e, QueryT, MutationT, SubscriptionT, S>,
+        operation_type: OperationType,
+    ) -> Self::Future;
+}
+
+/// Resolves the root value synchronously, the future being ready at once
+struct ResolveSync;
+
+impl<'e, QueryT, MutationT, SubscriptionT, S> ResolveRoot<'e, QueryT, MutationT, SubscriptionT, S>
+    for ResolveSync
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    type Future = future::Ready<Value<S>>;
+
+    fn resolve(
+        self,
+        executor: &'e Executor<'e, 'e, QueryT::Context, S>,
+        root_node: &'e RootNode<'e, QueryT, MutationT, SubscriptionT, S>,
+        operation_type: OperationType,
+    ) -> Self::Future {
+        future::ready(match operation_type {
             OperationType::Query => executor.resolve_into_value(&root_node.query_info, &root_node),
             OperationType::Mutation => {
                 executor.resolve_into_value(&root_node.mutation_info, &root_node.mutation_type)
             }
             OperationType::Subscription => unreachable!(),
-        };
+        })
     }
-
-    let mut errors = errors.into_inner().unwrap();
-    errors.sort();
-
-    Ok((value, errors))
 }
 
-/// Create new `Executor` and start asynchronous query execution.
-/// Returns `IsSubscription` error if subscription is passed.
-pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
-    document: &'b Document<'a, S>,
-    operation: &'b Spanning<Operation<'_, S>>,
-    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
-    variables: &Variables<S>,
-    context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+/// Resolves the root value asynchronously
+struct ResolveAsync;
+
+impl<'e, QueryT, MutationT, SubscriptionT, S> ResolveRoot<'e, QueryT, MutationT, SubscriptionT, S>
+    for ResolveAsync
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -873,89 +1395,202 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
-    if operation.item.operation_type == OperationType::Subscription {
-        return Err(GraphQLError::IsSubscription);
-    }
-
-    let mut fragments = vec![];
-    for def in document.iter() {
-        if let Definition::Fragment(f) = def {
-            fragments.push(f)
-        };
-    }
-
-    let default_variable_values = operation.item.variable_definitions.as_ref().map(|defs| {
-        defs.item
-            .items
-            .iter()
-            .filter_map(|&(ref name, ref def)| {
-                def.default_value
-                    .as_ref()
-                    .map(|i| (name.item.to_owned(), i.item.clone()))
-            })
-            .collect::<HashMap<String, InputValue<S>>>()
-    });
-
-    let errors = RwLock::new(Vec::new());
-    let value;
-
-    {
-        let mut all_vars;
-        let mut final_vars = variables;
-
-        if let Some(defaults) = default_variable_values {
-            all_vars = variables.clone();
-
-            for (name, value) in defaults {
-                all_vars.entry(name).or_insert(value);
+    type Future = BoxFuture<'e, Value<S>>;
+
+    fn resolve(
+        self,
+        executor: &'e Executor<'e, 'e, QueryT::Context, S>,
+        root_node: &'e RootNode<'e, QueryT, MutationT, SubscriptionT, S>,
+        operation_type: OperationType,
+    ) -> Self::Future {
+        match operation_type {
+            OperationType::Query => {
+                Box::pin(executor.resolve_into_value_async(&root_node.query_info, root_node))
             }
-
-            final_vars = &all_vars;
+            OperationType::Mutation => Box::pin(
+                executor
+                    .resolve_into_value_async(&root_node.mutation_info, &root_node.mutation_type),
+            ),
+            OperationType::Subscription => unreachable!(),
         }
+    }
+}
 
-        let root_type = match operation.item.operation_type {
-            OperationType::Query => root_node.schema.query_type(),
-            OperationType::Mutation => root_node
-                .schema
-                .mutation_type()
-                .expect("No mutation type found"),
-            OperationType::Subscription => unreachable!(),
-        };
+/// Resolves the root value asynchronously on the current thread
+struct ResolveLocal;
 
-        let executor = Executor {
-            fragments: &fragments
-                .iter()
-                .map(|f| (f.item.name.item, f.item.clone()))
-                .collect(),
-            variables: final_vars,
-            current_selection_set: Some(&operation.item.selection_set[..]),
-            parent_selection_set: None,
-            current_type: root_type,
-            schema: &root_node.schema,
-            context,
-            errors: &errors,
-            field_path: Arc::new(FieldPath::Root(operation.start)),
-        };
-
-        value = match operation.item.operation_type {
+impl<'e, QueryT, MutationT, SubscriptionT, S> ResolveRoot<'e, QueryT, MutationT, SubscriptionT, S>
+    for ResolveLocal
+where
+    QueryT: GraphQLTypeAsyncLocal<S>,
+    MutationT: GraphQLTypeAsyncLocal<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    type Future = LocalBoxFuture<'e, Value<S>>;
+
+    fn resolve(
+        self,
+        executor: &'e Executor<'e, 'e, QueryT::Context, S>,
+        root_node: &'e RootNode<'e, QueryT, MutationT, SubscriptionT, S>,
+        operation_type: OperationType,
+    ) -> Self::Future {
+        match operation_type {
             OperationType::Query => {
-                executor
-                    .resolve_into_value_async(&root_node.query_info, &root_node)
-                    .await
+                Box::pin(executor.resolve_into_value_local(&root_node.query_info, root_node))
             }
-            OperationType::Mutation => {
+            OperationType::Mutation => Box::pin(
                 executor
-                    .resolve_into_value_async(&root_node.mutation_info, &root_node.mutation_type)
-                    .await
-            }
+                    .resolve_into_value_local(&root_node.mutation_info, &root_node.mutation_type),
+            ),
             OperationType::Subscription => unreachable!(),
-        };
+        }
     }
+}
+
+/// Executes a query or a mutation, resolving its root value with `resolve_root`
+async fn execute_validated_operation<'a, QueryT, MutationT, SubscriptionT, S, R>(
+    document: &Document<'_, S>,
+    operation: &Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    resolve_root: R,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+    R: for<'e> ResolveRoot<'e, QueryT, MutationT, SubscriptionT, S>,
+{
+    if operation.item.operation_type == OperationType::Subscription {
+        return Err(GraphQLError::IsSubscription);
+    }
+    if let Err(error) = check_operation(document, operation, root_node, variables, context) {
+        return Ok((
+            ExecutionOutput {
+                errors: vec![ExecutionError::at_origin(error)],
+                ..ExecutionOutput::from_data(Value::null())
+            },
+            ExecutionMetadata::default(),
+        ));
+    }
+
+    let authorization = Authorization::evaluate(
+        &root_node.schema,
+        root_node.policy_evaluator.as_deref(),
+        context,
+    );
+    let execution = OperationExecution::start(
+        document,
+        operation,
+        variables,
+        authorization,
+        root_node.schema.max_response_values,
+    );
+    let value = {
+        let root_type = query_root_type(operation, &root_node.schema);
+        let executor = execution.executor(operation, root_type, &root_node.schema, context);
+
+        resolve_root
+            .resolve(&executor, root_node, operation.item.operation_type)
+            .await
+    };
+
+    Ok(execution.finish(
+        value,
+        &root_node.schema,
+        root_node.deprecation_tracker.as_ref(),
+        document,
+        operation,
+        context,
+    ))
+}
+
+/// Create new `Executor` and start query/mutation execution.
+/// Returns `IsSubscription` error if subscription is passed.
+pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+    document: &'b Document<S>,
+    operation: &'b Spanning<Operation<S>>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let execution = execute_validated_operation(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        ResolveSync,
+    );
+    execution
+        .now_or_never()
+        .expect("Synchronous resolution never waits")
+}
 
-    let mut errors = errors.into_inner().unwrap();
-    errors.sort();
+/// Create new `Executor` and start asynchronous query execution.
+/// Returns `IsSubscription` error if subscription is passed.
+pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_operation(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        ResolveAsync,
+    )
+    .await
+}
 
-    Ok((value, errors))
+/// Create new `Executor` and start asynchronous query execution on the
+/// current thread.
+/// Returns `IsSubscription` error if subscription is passed.
+pub async fn execute_validated_query_local<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsyncLocal<S>,
+    MutationT: GraphQLTypeAsyncLocal<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    execute_validated_operation(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        ResolveLocal,
+    )
+    .await
 }
 
 pub fn get_operation<'b, 'd, 'e, S>(
@@ -1005,7 +1640,7 @@ pub async fn resolve_validated_subscription<
     root_node: &'r RootNode<'r, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &'r QueryT::Context,
-) -> Result<(Value<ValuesStream<'r, S>>, Vec<ExecutionError<S>>), GraphQLError<'r>>
+) -> Result<ExecutionOutput<S, Value<ValuesStream<'r, S>>>, GraphQLError<'r>>
 where
     'r: 'exec_ref,
     'd: 'r,
@@ -1022,81 +1657,48 @@ where
     if operation.item.operation_type != OperationType::Subscription {
         return Err(GraphQLError::NotSubscription);
     }
-
-    let mut fragments = vec![];
-    for def in document.iter() {
-        match def {
-            Definition::Fragment(f) => fragments.push(f),
-            _ => (),
-        };
-    }
-
-    let default_variable_values = operation.item.variable_definitions.as_ref().map(|defs| {
-        defs.item
-            .items
-            .iter()
-            .filter_map(|&(ref name, ref def)| {
-                def.default_value
-                    .as_ref()
-                    .map(|i| (name.item.to_owned(), i.item.clone()))
-            })
-            .collect::<HashMap<String, InputValue<S>>>()
-    });
-
-    let errors = RwLock::new(Vec::new());
-    let value;
-
-    {
-        let mut all_vars;
-        let mut final_vars = variables;
-
-        if let Some(defaults) = default_variable_values {
-            all_vars = variables.clone();
-
-            for (name, value) in defaults {
-                all_vars.entry(name).or_insert(value);
-            }
-
-            final_vars = &all_vars;
-        }
-
-        let root_type = match operation.item.operation_type {
-            OperationType::Subscription => root_node
-                .schema
-                .subscription_type()
-                .expect("No subscription type found"),
-            _ => unreachable!(),
-        };
-
-        let executor: Executor<'_, 'r, _, _> = Executor {
-            fragments: &fragments
-                .iter()
-                .map(|f| (f.item.name.item, f.item.clone()))
-                .collect(),
-            variables: final_vars,
-            current_selection_set: Some(&operation.item.selection_set[..]),
-            parent_selection_set: None,
-            current_type: root_type,
-            schema: &root_node.schema,
-            context,
-            errors: &errors,
-            field_path: Arc::new(FieldPath::Root(operation.start)),
-        };
-
-        value = match operation.item.operation_type {
-            OperationType::Subscription => {
-                executor
-                    .resolve_into_stream(&root_node.subscription_info, &root_node.subscription_type)
-                    .await
-            }
-            _ => unreachable!(),
-        };
+    if let Err(error) = check_operation(document, operation, root_node, variables, context) {
+        return Ok(ExecutionOutput {
+            data: Value::Null,
+            errors: vec![ExecutionError::at_origin(error)],
+            extensions: Object::with_capacity(0),
+        });
     }
 
-    let mut errors = errors.into_inner().unwrap();
-    errors.sort();
+    let authorization = Authorization::evaluate(
+        &root_node.schema,
+        root_node.policy_evaluator.as_deref(),
+        context,
+    );
+    let execution = OperationExecution::start(
+        document,
+        operation,
+        variables,
+        authorization,
+        root_node.schema.max_response_values,
+    );
+    let value = {
+        let root_type = root_node
+            .schema
+            .subscription_type()
+            .expect("No subscription type found");
+        let executor: Executor<'_, 'r, _, _> =
+            execution.executor(operation, root_type, &root_node.schema, context);
+
+        executor
+            .resolve_into_stream(&root_node.subscription_info, &root_node.subscription_type)
+            .await
+    };
 
-    Ok((value, errors))
+    let (output, _) = execution.finish(
+        value,
+        &root_node.schema,
+        root_node.deprecation_tracker.as_ref(),
+        document,
+        operation,
+        context,
+    );
+    Ok(output)
 }
 
 impl<'r, S> Registry<'r, S>
@@ -1105,7 +1707,11 @@ where
 {
     /// Construct a new registry
     pub fn new(types: FnvHashMap<Name, MetaType<'r, S>>) -> Registry<'r, S> {
-        Registry { types }
+        Registry {
+            types,
+            rust_types: FnvHashMap::default(),
+            redefinitions: vec![],
+        }
     }
 
     /// Get the `Type` instance for a given GraphQL type
@@ -1118,13 +1724,29 @@ where
     {
         if let Some(name) = T::name(info) {
             let validated_name = name.parse::<Name>().unwrap();
+            let rust_type = std::any::type_name::<T>();
             if !self.types.contains_key(name) {
+                self.rust_types.insert(name.to_owned(), vec![rust_type]);
                 self.insert_placeholder(
                     validated_name.clone(),
                     Type::NonNullNamed(Cow::Owned(name.to_string())),
                 );
                 let meta = T::meta(info, self);
                 self.types.insert(validated_name, meta);
+            } else if let Some(rust_types) = self.rust_types.get_mut(name) {
+                // Wrappers like `Box<T>` or `&T` register the same name as `T`,
+                // so another Rust type is only an error when its definition
+                // differs. The registered type may still be a placeholder,
+                // hence comparing once all the types are built.
+                if !rust_types.contains(&rust_type) {
+                    rust_types.push(rust_type);
+                    let definition = T::meta(info, self);
+                    self.redefinitions.push(Redefinition {
+                        name: name.to_owned(),
+                        rust_type,
+                        definition,
+                    });
+                }
             }
             self.types[name].as_type()
         } else {
@@ -1132,6 +1754,75 @@ where
         }
     }
 
+    /// Add the metadata of a type to the registry
+    ///
+    /// This is how types built at runtime, without a Rust type backing them,
+    /// become part of the schema. A type registered under the same name
+    /// before is replaced.
+    ///
+    /// # Panics
+    ///
+    /// If the name of the type isn't a valid GraphQL name.
+    pub fn register_type(&mut self, meta: MetaType<'r, S>) -> Type<'r> {
+        let name = meta.name().expect("Only named types can be registered");
+        self.rust_types.remove(name);
+        let name = name
+            .parse::<Name>()
+            .expect("Type names must be valid GraphQL names");
+        let of_type = meta.as_type();
+        self.types.insert(name, meta);
+        of_type
+    }
+
+    /// Check that the Rust types registering the same GraphQL type name
+    /// define it the same way
+    pub(crate) fn check_redefinitions(&self) -> Result<(), SchemaError> {
+        for redefinition in &self.redefinitions {
+            let registered = match self.types.get(redefinition.name.as_str()) {
+                Some(meta) => meta,
+                None => continue,
+            };
+            if !registered.has_same_definition(&redefinition.definition) {
+                return Err(SchemaError::DuplicateTypeName {
+                    name: redefinition.name.clone(),
+                    first_type: self.rust_types[&redefinition.name][0],
+                    second_type: redefinition.rust_type,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Start building an object type with the provided name
+    ///
+    /// ```
+    /// # use juniper::{DefaultScalarValue, Registry};
+    /// # fn build(registry: &mut Registry<DefaultScalarValue>) {
+    /// let user = registry
+    ///     .build_object("User")
+    ///     .field::<String>("name", &())
+    ///     .field_with(|registry| {
+    ///         registry
+    ///             .field::<Vec<String>>("friends", &())
+    ///             .argument(registry.arg::<Option<i32>>("first", &()))
+    ///     })
+    ///     .register();
+    /// # }
+    /// ```
+    pub fn build_object(&mut self, name: &str) -> ObjectBuilder<'_, 'r, S> {
+        ObjectBuilder::new(self, name)
+    }
+
+    /// Start building an input object type with the provided name
+    pub fn build_input_object(&mut self, name: &str) -> InputObjectBuilder<'_, 'r, S> {
+        InputObjectBuilder::new(self, name)
+    }
+
+    /// Start building an enum type with the provided name
+    pub fn build_enum(&mut self, name: &str) -> EnumBuilder<'_, 'r, S> {
+        EnumBuilder::new(self, name)
+    }
+
     /// Create a field with the provided name
     pub fn field<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r, S>
     where
@@ -1143,6 +1834,10 @@ where
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            cache_hint: None,
+            default_impl: false,
+            serial: false,
+            required_scopes: None,
         }
     }
 
@@ -1161,6 +1856,10 @@ where
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            cache_hint: None,
+            default_impl: false,
+            serial: false,
+            required_scopes: None,
         }
     }
 
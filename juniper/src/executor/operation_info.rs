@@ -0,0 +1,161 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    ast::{Definition, Document, Fragment, InputValue, Operation, OperationType, Selection, Type},
+    parser::Spanning,
+    schema::model::SchemaType,
+    value::{DefaultScalarValue, ScalarValue},
+};
+
+/// Metadata of an operation, available before resolving anything
+///
+/// Obtained via [`operation_info`](crate::operation_info), this allows HTTP layers to route,
+/// log or rate-limit operations by their kind, name and the schema fields they
+/// touch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationInfo<'a, S = DefaultScalarValue> {
+    /// The kind of the operation
+    pub operation_type: OperationType,
+
+    /// The name of the operation, if it has one
+    pub name: Option<&'a str>,
+
+    /// The variables declared by the operation, in declaration order
+    pub variables: Vec<VariableInfo<'a, S>>,
+
+    /// The schema coordinates (e.g. `"Query.hero"`) of all the fields selected
+    /// by the operation, including the ones selected through fragments
+    ///
+    /// Sorted and free of duplicates.
+    pub fields: Vec<String>,
+}
+
+/// A variable declared by an operation
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableInfo<'a, S = DefaultScalarValue> {
+    /// The name of the variable, without the leading `$`
+    pub name: &'a str,
+
+    /// The declared type of the variable
+    pub var_type: Type<'a>,
+
+    /// The default value of the variable, if declared
+    pub default_value: Option<InputValue<S>>,
+}
+
+impl<'a, S> VariableInfo<'a, S> {
+    /// Whether a value for this variable must be provided by the client
+    pub fn is_required(&self) -> bool {
+        self.var_type.is_non_null() && self.default_value.is_none()
+    }
+}
+
+impl<'a, S> OperationInfo<'a, S>
+where
+    S: ScalarValue,
+{
+    pub(crate) fn new(
+        document: &Document<'a, S>,
+        operation: &Spanning<Operation<'a, S>>,
+        schema: &SchemaType<S>,
+    ) -> Self {
+        let operation = &operation.item;
+
+        let variables = operation
+            .variable_definitions
+            .as_ref()
+            .map(|defs| {
+                defs.item
+                    .items
+                    .iter()
+                    .map(|(name, def)| VariableInfo {
+                        name: name.item,
+                        var_type: def.var_type.item.clone(),
+                        default_value: def.default_value.as_ref().map(|v| v.item.clone()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let root_type = match operation.operation_type {
+            OperationType::Query => Some(schema.concrete_query_type()),
+            OperationType::Mutation => schema.concrete_mutation_type(),
+            OperationType::Subscription => schema.concrete_subscription_type(),
+        };
+
+        let mut collector = FieldCollector {
+            schema,
+            fragments: document
+                .iter()
+                .filter_map(|def| match def {
+                    Definition::Fragment(f) => Some((f.item.name.item, &f.item)),
+                    _ => None,
+                })
+                .collect(),
+            visited_fragments: HashSet::new(),
+            fields: BTreeSet::new(),
+        };
+        if let Some(type_name) = root_type.and_then(|t| t.name()) {
+            collector.collect(type_name, &operation.selection_set);
+        }
+
+        OperationInfo {
+            operation_type: operation.operation_type,
+            name: operation.name.as_ref().map(|n| n.item),
+            variables,
+            fields: collector.fields.into_iter().collect(),
+        }
+    }
+
+    /// The variables a client must provide values for
+    pub fn required_variables(&self) -> impl Iterator<Item = &VariableInfo<'a, S>> {
+        self.variables.iter().filter(|v| v.is_required())
+    }
+}
+
+struct FieldCollector<'a, 'd, 's, S> {
+    schema: &'s SchemaType<'s, S>,
+    fragments: HashMap<&'a str, &'d Fragment<'a, S>>,
+    visited_fragments: HashSet<&'a str>,
+    fields: BTreeSet<String>,
+}
+
+impl<'a, 'd, 's, S> FieldCollector<'a, 'd, 's, S>
+where
+    S: ScalarValue,
+{
+    fn collect(&mut self, type_name: &str, selection_set: &'d [Selection<'a, S>]) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(f) => {
+                    let field_name = f.item.name.item;
+                    self.fields.insert(format!("{}.{}", type_name, field_name));
+
+                    if let Some(ref selection_set) = f.item.selection_set {
+                        let field_type = self
+                            .schema
+                            .concrete_type_by_name(type_name)
+                            .and_then(|t| t.field_by_name(field_name))
+                            .map(|f| f.field_type.innermost_name().to_owned());
+
+                        if let Some(field_type) = field_type {
+                            self.collect(&field_type, selection_set);
+                        }
+                    }
+                }
+                Selection::InlineFragment(f) => {
+                    let type_name = f.item.type_condition.as_ref().map_or(type_name, |c| c.item);
+                    self.collect(type_name, &f.item.selection_set);
+                }
+                Selection::FragmentSpread(s) => {
+                    let name = s.item.name.item;
+                    if self.visited_fragments.insert(name) {
+                        if let Some(fragment) = self.fragments.get(name) {
+                            self.collect(fragment.type_condition.item, &fragment.selection_set);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
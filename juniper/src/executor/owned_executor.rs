@@ -5,9 +5,12 @@ use std::{
 
 use crate::{
     ast::Fragment,
-    executor::FieldPath,
+    auth::Authorization,
+    cache::CachePolicyCollector,
+    executor::{FieldPath, ValueBudget},
     parser::SourcePosition,
     schema::model::{SchemaType, TypeType},
+    value::Object,
     ExecutionError, Executor, Selection, Variables,
 };
 
@@ -22,6 +25,10 @@ pub struct OwnedExecutor<'a, CtxT, S> {
     pub(super) schema: &'a SchemaType<'a, S>,
     pub(super) context: &'a CtxT,
     pub(super) errors: RwLock<Vec<ExecutionError<S>>>,
+    pub(super) cache_policy: CachePolicyCollector,
+    pub(super) extensions: RwLock<Object<S>>,
+    pub(super) authorization: Arc<Authorization>,
+    pub(super) budget: ValueBudget,
     pub(super) field_path: Arc<FieldPath<'a>>,
 }
 
@@ -39,6 +46,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: CachePolicyCollector::new(),
+            extensions: RwLock::new(Object::with_capacity(0)),
+            authorization: Arc::clone(&self.authorization),
+            budget: ValueBudget::new(self.schema.max_response_values),
             field_path: self.field_path.clone(),
         }
     }
@@ -66,6 +77,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: CachePolicyCollector::new(),
+            extensions: RwLock::new(Object::with_capacity(0)),
+            authorization: Arc::clone(&self.authorization),
+            budget: ValueBudget::new(self.schema.max_response_values),
             field_path: self.field_path.clone(),
         }
     }
@@ -99,6 +114,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: CachePolicyCollector::new(),
+            extensions: RwLock::new(Object::with_capacity(0)),
+            authorization: Arc::clone(&self.authorization),
+            budget: ValueBudget::new(self.schema.max_response_values),
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -126,6 +145,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
+            cache_policy: &self.cache_policy,
+            extensions: &self.extensions,
+            authorization: &self.authorization,
+            budget: &self.budget,
             field_path: Arc::clone(&self.field_path),
         }
     }
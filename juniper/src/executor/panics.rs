@@ -0,0 +1,67 @@
+//! Catching the panics of resolvers, enabled with
+//! [`RootNode::with_panic_catching`](../struct.RootNode.html#method.with_panic_catching)
+
+use std::{
+    any::Any,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    executor::FieldError,
+    value::{ScalarValue, Value},
+};
+
+/// Runs the resolver of a field, turning a panic into an error of the field
+/// if `catch` is set
+pub(crate) fn catch_panic<T, S, F>(catch: bool, resolve: F) -> Result<T, FieldError<S>>
+where
+    S: ScalarValue,
+    F: FnOnce() -> Result<T, FieldError<S>>,
+{
+    if !catch {
+        return resolve();
+    }
+    panic::catch_unwind(AssertUnwindSafe(resolve)).unwrap_or_else(|p| Err(panic_error(p)))
+}
+
+/// Future polling the future of a field's resolver, turning a panic into an
+/// error of the field if `catch` is set
+pub(crate) struct CatchPanic<F> {
+    pub(crate) catch: bool,
+    pub(crate) future: F,
+}
+
+impl<T, S, F> Future for CatchPanic<F>
+where
+    S: ScalarValue,
+    F: Future<Output = Result<T, FieldError<S>>> + Unpin,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let catch = self.catch;
+        let future = &mut self.future;
+        let polled = catch_panic(catch, || match Pin::new(future).poll(cx) {
+            Poll::Ready(res) => res.map(Poll::Ready),
+            Poll::Pending => Ok(Poll::Pending),
+        });
+        match polled {
+            Ok(Poll::Ready(value)) => Poll::Ready(Ok(value)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+/// The error of a field whose resolver panicked, holding the panic message
+fn panic_error<S: ScalarValue>(payload: Box<dyn Any + Send>) -> FieldError<S> {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Resolver panicked");
+    FieldError::new(message, Value::null())
+}
@@ -0,0 +1,220 @@
+//! Timeouts and retries of the fields marked with
+//! `#[graphql(timeout = "...", retries = ...)]`
+//!
+//! Timeouts are driven by the [`Sleep`](trait.Sleep.html) timer of the
+//! runtime executing the queries, set with
+//! [`RootNode::with_sleep`](../struct.RootNode.html#method.with_sleep).
+
+use std::{fmt, future::Future, sync::Arc, time::Duration};
+
+use futures::future::{self, BoxFuture, Either};
+
+use crate::{
+    executor::{FieldError, FieldResult},
+    value::{Object, ScalarValue, Value},
+};
+
+/// Timer of the runtime executing the queries, completing the timeouts of
+/// fields marked with `#[graphql(timeout = "...")]`
+///
+/// Closures taking a `Duration` and returning a future implement it too:
+///
+/// ```
+/// # use juniper::{EmptyMutation, EmptySubscription, RootNode};
+/// # struct Query;
+/// # #[juniper::graphql_object]
+/// # impl Query {
+/// #     fn hello() -> &str {
+/// #         "world"
+/// #     }
+/// # }
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_sleep(tokio::time::delay_for);
+/// ```
+pub trait Sleep: Send + Sync {
+    /// Future completing once the `duration` elapsed
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+impl<F, Fut> Sleep for F
+where
+    F: Fn(Duration) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(self(duration))
+    }
+}
+
+/// The timer set on a schema
+#[derive(Clone)]
+pub(crate) struct Timer(pub(crate) Arc<dyn Sleep>);
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Timer(..)")
+    }
+}
+
+/// How a field is resolved, generated for the fields with a `timeout` or
+/// `retries`
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct FieldPolicy {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
+}
+
+impl FieldPolicy {
+    /// Resolve the field with the futures of the `attempt`, until one succeeds
+    /// in time or the retries are exhausted
+    ///
+    /// The error of the last attempt is returned, with the number of attempts
+    /// in the `attempts` extension. Fields with a timeout fail without a
+    /// `sleep` timer.
+    pub async fn resolve<F, Fut, T, S>(
+        self,
+        sleep: Option<&dyn Sleep>,
+        mut attempt: F,
+    ) -> FieldResult<T, S>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = FieldResult<T, S>>,
+        S: ScalarValue,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let res = match (self.timeout, sleep) {
+                (Some(timeout), Some(sleep)) => {
+                    let attempt = attempt();
+                    futures::pin_mut!(attempt);
+                    match future::select(attempt, sleep.sleep(timeout)).await {
+                        Either::Left((res, _)) => res,
+                        Either::Right(..) => Err(FieldError::new(
+                            format!("Timed out after {:?}", timeout),
+                            Value::null(),
+                        )),
+                    }
+                }
+                (Some(_), None) => {
+                    return Err(FieldError::new(
+                        "Timeouts require a timer, set with `RootNode::with_sleep`",
+                        Value::null(),
+                    ))
+                }
+                (None, _) => attempt().await,
+            };
+            match res {
+                Err(_) if attempts <= self.retries => continue,
+                Err(e) => return Err(with_attempts(e, attempts)),
+                Ok(value) => return Ok(value),
+            }
+        }
+    }
+}
+
+/// Add the number of `attempts` to the extensions of the error, unless they
+/// aren't an object
+fn with_attempts<S: ScalarValue>(error: FieldError<S>, attempts: u32) -> FieldError<S> {
+    let attempts = Value::scalar(attempts as i32);
+    let extensions = match error.extensions {
+        Value::Null => {
+            let mut extensions = Object::with_capacity(1);
+            extensions.add_field("attempts", attempts);
+            Value::Object(extensions)
+        }
+        Value::Object(mut extensions) => {
+            extensions.add_field("attempts", attempts);
+            Value::Object(extensions)
+        }
+        extensions => extensions,
+    };
+    FieldError {
+        message: error.message,
+        extensions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        time::{Duration, Instant},
+    };
+
+    use super::FieldPolicy;
+    use crate::{executor::FieldError, value::DefaultScalarValue};
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let attempts = Cell::new(0);
+        let policy = FieldPolicy {
+            timeout: None,
+            retries: 2,
+        };
+
+        let res = policy
+            .resolve(None, || {
+                attempts.set(attempts.get() + 1);
+                let attempt = attempts.get();
+                async move {
+                    if attempt < 3 {
+                        Err(FieldError::<DefaultScalarValue>::from("flaky"))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(res, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn reports_the_attempts_of_the_last_error() {
+        let policy = FieldPolicy {
+            timeout: Some(Duration::from_millis(20)),
+            retries: 1,
+        };
+
+        let started = Instant::now();
+        let sleep = |duration| tokio::time::delay_for(duration);
+        let res = policy
+            .resolve(Some(&sleep), || async {
+                tokio::time::delay_for(Duration::from_secs(5)).await;
+                Ok::<_, FieldError<DefaultScalarValue>>(())
+            })
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            res,
+            Err(FieldError::new(
+                "Timed out after 20ms",
+                graphql_value!({ "attempts": 2 }),
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn timeouts_require_a_timer() {
+        let policy = FieldPolicy {
+            timeout: Some(Duration::from_millis(20)),
+            retries: 1,
+        };
+
+        let res = policy
+            .resolve(None, || async {
+                Ok::<_, FieldError<DefaultScalarValue>>(())
+            })
+            .await;
+
+        assert_eq!(
+            res,
+            Err(FieldError::from(
+                "Timeouts require a timer, set with `RootNode::with_sleep`",
+            )),
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Logging of slow resolvers through the [`log`](https://docs.rs/log) facade,
+//! enabled with
+//! [`RootNode::with_slow_field_threshold`](../struct.RootNode.html#method.with_slow_field_threshold)
+//!
+//! A field whose resolution exceeds the threshold is logged as a warning with
+//! the `juniper::slow_field` target, like:
+//!
+//! ```text
+//! Slow field User.friends at user.friends took 1250ms (arguments: first: 100)
+//! ```
+
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{executor::Executor, types::base::Arguments, value::ScalarValue};
+
+/// Target of the logged slow fields, to filter them with the logger
+pub(crate) const LOG_TARGET: &str = "juniper::slow_field";
+
+/// Maximum length of a logged argument value, longer ones being truncated
+const MAX_ARGUMENT_LEN: usize = 64;
+
+/// Times the resolution of a field, logging it once finished if it exceeds
+/// the threshold of the schema
+pub(crate) struct SlowFieldTimer {
+    start: Option<(Duration, Instant)>,
+}
+
+impl SlowFieldTimer {
+    pub(crate) fn start<CtxT, S>(executor: &Executor<CtxT, S>) -> Self {
+        // `Instant::now` panics on some targets, so it's only called when needed.
+        SlowFieldTimer {
+            start: executor
+                .schema
+                .slow_field_threshold
+                .map(|threshold| (threshold, Instant::now())),
+        }
+    }
+
+    /// Logs the field, resolved by the `executor` of the field, if it exceeded
+    /// the threshold
+    pub(crate) fn finish<CtxT, S>(
+        self,
+        executor: &Executor<CtxT, S>,
+        type_name: Option<&str>,
+        field_name: &str,
+        args: &Arguments<S>,
+    ) where
+        S: ScalarValue,
+    {
+        let (threshold, start) = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let elapsed = start.elapsed();
+        if elapsed <= threshold {
+            return;
+        }
+
+        let mut path = Vec::new();
+        executor.field_path.construct_path(&mut path);
+        log::warn!(
+            target: LOG_TARGET,
+            "Slow field {}.{} at {} took {}ms (arguments: {})",
+            type_name.unwrap_or_default(),
+            field_name,
+            path.join("."),
+            elapsed.as_millis(),
+            summarize_arguments(args),
+        );
+    }
+}
+
+/// Formats the arguments as `name: value` pairs, truncating long values
+fn summarize_arguments<S: ScalarValue>(args: &Arguments<S>) -> String {
+    let mut summary = String::new();
+    for (name, value) in args.iter() {
+        if !summary.is_empty() {
+            summary.push_str(", ");
+        }
+        let value = value.to_string();
+        match value.char_indices().nth(MAX_ARGUMENT_LEN) {
+            Some((end, _)) => write!(summary, "{}: {}...", name, &value[..end]),
+            None => write!(summary, "{}: {}", name, value),
+        }
+        .unwrap();
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+
+    use crate::{EmptyMutation, EmptySubscription, RootNode, Variables};
+
+    use super::LOG_TARGET;
+
+    /// Keeps the messages of the logged slow fields
+    struct SlowFieldLogger(Arc<Mutex<Vec<String>>>);
+
+    impl Log for SlowFieldLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target() == LOG_TARGET
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) && record.level() == Level::Warn {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(Scalar = crate::DefaultScalarValue)]
+    impl Query {
+        fn fast() -> i32 {
+            1
+        }
+
+        fn slow(name: String, tags: Vec<String>) -> i32 {
+            thread::sleep(Duration::from_millis(50));
+            tags.len() as i32 + name.len() as i32
+        }
+
+        fn nested() -> Query {
+            Query
+        }
+    }
+
+    #[test]
+    fn logs_fields_exceeding_the_threshold() {
+        let logged = Arc::new(Mutex::new(vec![]));
+        log::set_logger(Box::leak(Box::new(SlowFieldLogger(logged.clone())))).unwrap();
+        log::set_max_level(LevelFilter::Warn);
+
+        let doc = r#"{
+            fast
+            nested {
+                slow(name: "a", tags: ["aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"])
+            }
+        }"#;
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_slow_field_threshold(Duration::from_millis(20));
+        let crate::ExecutionOutput { errors, .. } =
+            crate::execute_sync(doc, None, &schema, &Variables::new(), &()).unwrap();
+        assert!(errors.is_empty());
+
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 2, "{:?}", logged);
+        assert!(logged[0].starts_with("Slow field Query.slow at nested.slow took "));
+        assert!(logged[0].ends_with(&format!(
+            "(arguments: name: \"a\", tags: [\"{}...)",
+            "a".repeat(62),
+        )));
+        assert!(logged[1].starts_with("Slow field Query.nested at nested took "));
+    }
+}
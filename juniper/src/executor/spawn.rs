@@ -0,0 +1,236 @@
+//! Background work of resolvers, run in the execution scope of their field
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{
+    channel::oneshot::{self, Canceled},
+    task::{Spawn, SpawnError, SpawnExt},
+};
+
+use crate::{
+    executor::{Executor, LookAheadMethods, LookAheadSelection},
+    parser::SourcePosition,
+    value::ScalarValue,
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<FieldScope>> = const { RefCell::new(None) };
+}
+
+/// Execution scope of a field, owned so it can be moved into spawned tasks
+///
+/// A resolver fetching several things concurrently may spawn them as tasks of
+/// the application's runtime, with
+/// [`Executor::spawn`](struct.Executor.html#method.spawn), or by wrapping them
+/// with [`Executor::in_scope`](struct.Executor.html#method.in_scope) before
+/// handing them to a runtime without a
+/// [`Spawn`](https://docs.rs/futures/0.3/futures/task/trait.Spawn.html)
+/// implementation, like `tokio::spawn`. The tasks outlive the borrows of the
+/// executor, so they get the scope of the field instead, returned by
+/// [`FieldScope::current`](#method.current) while they're polled. Logging and
+/// tracing code called from the tasks, e.g. by a database client, finds the
+/// field this way:
+///
+/// ```
+/// # use juniper::{Executor, FieldResult, FieldScope};
+/// fn fetch_price(sku: &str) -> f64 {
+///     if let Some(scope) = FieldScope::current() {
+///         log::debug!("Fetching the price of {} for {:?}", sku, scope.path());
+///     }
+///     # 1.0
+/// }
+///
+/// struct Product;
+///
+/// #[juniper::graphql_object]
+/// impl Product {
+///     async fn price(executor: &Executor) -> FieldResult<f64> {
+///         let price = tokio::spawn(executor.in_scope(async { fetch_price("42") }));
+///         Ok(price.await?)
+///     }
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FieldScope(Arc<ScopeData>);
+
+#[derive(PartialEq, Eq)]
+struct ScopeData {
+    path: Vec<String>,
+    location: SourcePosition,
+    type_name: Option<String>,
+    selected_fields: Vec<String>,
+}
+
+impl FieldScope {
+    /// The scope of the task being polled, `None` outside of the futures
+    /// returned by [`Executor::in_scope`](struct.Executor.html#method.in_scope)
+    pub fn current() -> Option<FieldScope> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// The path of the field in the response, as in its errors
+    pub fn path(&self) -> &[String] {
+        &self.0.path
+    }
+
+    /// The location of the field in the query
+    pub fn location(&self) -> &SourcePosition {
+        &self.0.location
+    }
+
+    /// The name of the type of the field, without the lists and non-nulls
+    pub fn type_name(&self) -> Option<&str> {
+        self.0.type_name.as_deref()
+    }
+
+    /// The names, or aliases, of the sub-fields selected on the field, as
+    /// by its [look-ahead](struct.Executor.html#method.look_ahead)
+    pub fn selected_fields(&self) -> &[String] {
+        &self.0.selected_fields
+    }
+}
+
+impl fmt::Debug for FieldScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldScope")
+            .field("path", &self.0.path)
+            .field("location", &self.0.location)
+            .field("type_name", &self.0.type_name)
+            .field("selected_fields", &self.0.selected_fields)
+            .finish()
+    }
+}
+
+/// Future polling another one in the scope of a field
+///
+/// Returned by [`Executor::in_scope`](struct.Executor.html#method.in_scope).
+pub struct InScope<F> {
+    scope: FieldScope,
+    future: Pin<Box<F>>,
+}
+
+impl<F> InScope<F> {
+    /// The scope the future is polled in
+    pub fn scope(&self) -> &FieldScope {
+        &self.scope
+    }
+}
+
+impl<F: Future> Future for InScope<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let previous = CURRENT.with(|current| current.replace(Some(this.scope.clone())));
+        // Restores the previous scope even if the future panics.
+        let _restore = Restore(previous);
+        this.future.as_mut().poll(cx)
+    }
+}
+
+impl<F> fmt::Debug for InScope<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InScope")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+struct Restore(Option<FieldScope>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Output of a task spawned with
+/// [`Executor::spawn`](struct.Executor.html#method.spawn)
+///
+/// Resolves to `Err(Canceled)`, from `futures::channel::oneshot`, if the
+/// runtime drops the task before it completes. Dropping the handle doesn't
+/// cancel the task.
+pub struct JoinHandle<T>(oneshot::Receiver<T>);
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("JoinHandle(..)")
+    }
+}
+
+impl<'r, 'a, CtxT, S> Executor<'r, 'a, CtxT, S>
+where
+    S: ScalarValue,
+{
+    /// The execution scope of the current field, to be moved into background
+    /// work
+    pub fn field_scope(&self) -> FieldScope {
+        let type_name = self
+            .current_type
+            .innermost_concrete()
+            .name()
+            .map(str::to_owned);
+        let selection = LookAheadSelection::build_from_selection_set(
+            type_name.as_deref().unwrap_or(""),
+            self.current_selection_set.unwrap_or(&[]),
+            self.variables,
+            self.fragments,
+        );
+        let selected_fields = selection
+            .children
+            .iter()
+            .map(|child| child.inner.field_name().to_owned())
+            .collect();
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+
+        FieldScope(Arc::new(ScopeData {
+            path,
+            location: *self.location(),
+            type_name,
+            selected_fields,
+        }))
+    }
+
+    /// Wraps the `future` to be polled in the execution scope of the current
+    /// field, see [`FieldScope`](struct.FieldScope.html)
+    pub fn in_scope<F: Future>(&self, future: F) -> InScope<F> {
+        InScope {
+            scope: self.field_scope(),
+            future: Box::pin(future),
+        }
+    }
+
+    /// Spawns the `future` as a task of the `spawner`, polled in the execution
+    /// scope of the current field, see [`FieldScope`](struct.FieldScope.html)
+    pub fn spawn<Sp, F>(&self, spawner: &Sp, future: F) -> Result<JoinHandle<F::Output>, SpawnError>
+    where
+        Sp: Spawn + ?Sized,
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let task = self.in_scope(async move {
+            // The resolver may have stopped waiting for the output.
+            let _ = sender.send(future.await);
+        });
+        spawner.spawn(task)?;
+        Ok(JoinHandle(receiver))
+    }
+}
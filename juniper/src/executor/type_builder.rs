@@ -0,0 +1,211 @@
+use std::borrow::Cow;
+
+use crate::{
+    ast::{FromInputValue, Type},
+    executor::Registry,
+    schema::meta::{
+        accept_any_input, reject_unlisted_input, Argument, EnumMeta, EnumValue, Field,
+        InputObjectMeta, MetaType, ObjectMeta,
+    },
+    types::base::GraphQLType,
+    value::ScalarValue,
+};
+
+/// Builder of an object type registered by name, without a Rust type backing it
+///
+/// Created by [`Registry::build_object`](struct.Registry.html#method.build_object).
+pub struct ObjectBuilder<'a, 'r, S> {
+    registry: &'a mut Registry<'r, S>,
+    meta: ObjectMeta<'r, S>,
+}
+
+impl<'a, 'r, S> ObjectBuilder<'a, 'r, S>
+where
+    S: ScalarValue + 'r,
+{
+    pub(crate) fn new(registry: &'a mut Registry<'r, S>, name: &str) -> Self {
+        ObjectBuilder {
+            registry,
+            meta: ObjectMeta::new(Cow::Owned(name.to_owned()), &[]),
+        }
+    }
+
+    /// Set the description of the object
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> Self {
+        self.meta = self.meta.description(description);
+        self
+    }
+
+    /// Add a field of type `T` with the provided name
+    pub fn field<T>(mut self, name: &str, info: &T::TypeInfo) -> Self
+    where
+        T: GraphQLType<S>,
+    {
+        let field = self.registry.field::<T>(name, info);
+        self.meta.fields.push(field);
+        self
+    }
+
+    /// Add a field built with access to the registry
+    ///
+    /// Use it for fields having arguments, descriptions or types registered
+    /// by name.
+    pub fn field_with<F>(mut self, build: F) -> Self
+    where
+        F: FnOnce(&mut Registry<'r, S>) -> Field<'r, S>,
+    {
+        let field = build(self.registry);
+        self.meta.fields.push(field);
+        self
+    }
+
+    /// Declare the object as implementing the interface with the provided name
+    pub fn interface(mut self, name: &str) -> Self {
+        self.meta.interface_names.push(name.to_owned());
+        self
+    }
+
+    /// Finish the object, adding the `__typename` field
+    ///
+    /// Return the result from `GraphQLType::meta` of a type named the same.
+    pub fn into_meta(self) -> MetaType<'r, S> {
+        self.finish().1
+    }
+
+    /// Finish the object and add it to the registry
+    pub fn register(self) -> Type<'r> {
+        let (registry, meta) = self.finish();
+        registry.register_type(meta)
+    }
+
+    fn finish(mut self) -> (&'a mut Registry<'r, S>, MetaType<'r, S>) {
+        let typename = self.registry.field::<String>("__typename", &());
+        self.meta.fields.push(typename);
+        (self.registry, self.meta.into_meta())
+    }
+}
+
+/// Builder of an input object type registered by name, without a Rust type
+/// backing it
+///
+/// Created by [`Registry::build_input_object`](struct.Registry.html#method.build_input_object).
+/// Values of the type are checked against its fields only, and are available
+/// to resolvers as `InputValue`s.
+pub struct InputObjectBuilder<'a, 'r, S> {
+    registry: &'a mut Registry<'r, S>,
+    meta: InputObjectMeta<'r, S>,
+}
+
+impl<'a, 'r, S> InputObjectBuilder<'a, 'r, S>
+where
+    S: ScalarValue + 'r,
+{
+    pub(crate) fn new(registry: &'a mut Registry<'r, S>, name: &str) -> Self {
+        InputObjectBuilder {
+            registry,
+            meta: InputObjectMeta {
+                name: Cow::Owned(name.to_owned()),
+                description: None,
+                input_fields: vec![],
+                try_parse_fn: accept_any_input,
+            },
+        }
+    }
+
+    /// Set the description of the input object
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> Self {
+        self.meta.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a field of type `T` with the provided name
+    pub fn field<T>(mut self, name: &str, info: &T::TypeInfo) -> Self
+    where
+        T: GraphQLType<S> + FromInputValue<S>,
+    {
+        let field = self.registry.arg::<T>(name, info);
+        self.meta.input_fields.push(field);
+        self
+    }
+
+    /// Add a field built with access to the registry
+    ///
+    /// Use it for fields having default values, descriptions or types
+    /// registered by name.
+    pub fn field_with<F>(mut self, build: F) -> Self
+    where
+        F: FnOnce(&mut Registry<'r, S>) -> Argument<'r, S>,
+    {
+        let field = build(self.registry);
+        self.meta.input_fields.push(field);
+        self
+    }
+
+    /// Finish the input object
+    ///
+    /// Return the result from `GraphQLType::meta` of a type named the same.
+    pub fn into_meta(self) -> MetaType<'r, S> {
+        self.meta.into_meta()
+    }
+
+    /// Finish the input object and add it to the registry
+    pub fn register(self) -> Type<'r> {
+        self.registry.register_type(self.meta.into_meta())
+    }
+}
+
+/// Builder of an enum type registered by name, without a Rust type backing it
+///
+/// Created by [`Registry::build_enum`](struct.Registry.html#method.build_enum).
+/// Only the listed values are accepted as input.
+pub struct EnumBuilder<'a, 'r, S> {
+    registry: &'a mut Registry<'r, S>,
+    meta: EnumMeta<'r, S>,
+}
+
+impl<'a, 'r, S> EnumBuilder<'a, 'r, S>
+where
+    S: ScalarValue + 'r,
+{
+    pub(crate) fn new(registry: &'a mut Registry<'r, S>, name: &str) -> Self {
+        EnumBuilder {
+            registry,
+            meta: EnumMeta {
+                name: Cow::Owned(name.to_owned()),
+                description: None,
+                values: vec![],
+                try_parse_fn: reject_unlisted_input,
+            },
+        }
+    }
+
+    /// Set the description of the enum
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
+    pub fn description(mut self, description: &str) -> Self {
+        self.meta.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a value to the enum
+    pub fn value(mut self, value: EnumValue) -> Self {
+        self.meta.values.push(value);
+        self
+    }
+
+    /// Finish the enum
+    ///
+    /// Return the result from `GraphQLType::meta` of a type named the same.
+    pub fn into_meta(self) -> MetaType<'r, S> {
+        self.meta.into_meta()
+    }
+
+    /// Finish the enum and add it to the registry
+    pub fn register(self) -> Type<'r> {
+        self.registry.register_type(self.meta.into_meta())
+    }
+}
@@ -0,0 +1,185 @@
+//! Non-fatal warnings about an executed operation, sent in the `warnings`
+//! entry of the `extensions` member of the response
+//!
+//! The operation still executes, the warnings telling clients about the legacy
+//! parts of the schema they rely on, like deprecated fields and enum values:
+//!
+//! ```json
+//! {
+//!   "data": { "user": { "name": "Alice" } },
+//!   "extensions": {
+//!     "warnings": [
+//!       {
+//!         "message": "The field `User.name` is deprecated: Use `fullName`",
+//!         "locations": [{ "line": 1, "column": 10 }]
+//!       }
+//!     ]
+//!   }
+//! }
+//! ```
+
+use crate::{
+    ast::{self, InputValue, Type},
+    executor::Executor,
+    parser::{SourcePosition, Spanning},
+    schema::meta::{DeprecationStatus, EnumMeta, Field, InputObjectMeta, MetaType},
+    value::{Object, ScalarValue, Value},
+};
+
+/// Key of the warnings in the `extensions` member of the response
+pub(crate) const EXTENSION_KEY: &str = "warnings";
+
+impl<'r, 'a, CtxT, S> Executor<'r, 'a, CtxT, S>
+where
+    S: ScalarValue,
+{
+    /// Adds a warning at `location` to the response, unless an identical one
+    /// was already added, e.g. by another item of a list
+    pub(crate) fn add_warning_at(&self, message: String, location: SourcePosition) {
+        let mut warning = Object::with_capacity(2);
+        warning.add_field("message", Value::scalar(message));
+        warning.add_field("locations", Value::list(vec![location_value(location)]));
+        let warning = Value::object(warning);
+
+        let mut extensions = self.extensions.write().unwrap();
+        match extensions.get_mut_field_value(EXTENSION_KEY) {
+            Some(Value::List(warnings)) => {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+            _ => {
+                extensions.add_field(EXTENSION_KEY, Value::list(vec![warning]));
+            }
+        }
+    }
+
+    /// Warns about the usage of a deprecated field, and of the deprecated enum
+    /// values in its arguments
+    pub(crate) fn warn_deprecated_usage(
+        &self,
+        parent_type: &MetaType<S>,
+        field: &Field<S>,
+        arguments: Option<&Spanning<ast::Arguments<S>>>,
+        location: SourcePosition,
+    ) {
+        if let DeprecationStatus::Deprecated(ref reason) = field.deprecation_status {
+            let name = format!("{}.{}", parent_type.name().unwrap_or_default(), field.name);
+            self.add_warning_at(deprecation_message("field", &name, reason), location);
+        }
+
+        let (arguments, meta_arguments) = match (arguments, &field.arguments) {
+            (Some(arguments), Some(meta_arguments)) => (arguments, meta_arguments),
+            _ => return,
+        };
+        for (name, value) in &arguments.item.items {
+            if let Some(meta_argument) = meta_arguments.iter().find(|a| a.name == name.item) {
+                self.warn_deprecated_input(
+                    &value.item,
+                    &meta_argument.arg_type,
+                    value.start,
+                    false,
+                );
+            }
+        }
+    }
+
+    /// Warns about the deprecated enum values of an input value, including the
+    /// values of the variables it uses
+    ///
+    /// The values of variables aren't located in the document, so the warnings
+    /// about them are at the location of the variable, set `in_variable`.
+    fn warn_deprecated_input(
+        &self,
+        value: &InputValue<S>,
+        value_type: &Type,
+        location: SourcePosition,
+        in_variable: bool,
+    ) {
+        if let InputValue::Variable(name) = value {
+            if let Some(value) = self.variables.get(name) {
+                self.warn_deprecated_input(value, value_type, location, true);
+            }
+            return;
+        }
+        let location_of = |item: &Spanning<InputValue<S>>| {
+            if in_variable {
+                location
+            } else {
+                item.start
+            }
+        };
+
+        let name = match value_type {
+            Type::List(inner) | Type::NonNullList(inner) => {
+                match value {
+                    InputValue::List(items) => {
+                        for item in items {
+                            self.warn_deprecated_input(
+                                &item.item,
+                                inner,
+                                location_of(item),
+                                in_variable,
+                            );
+                        }
+                    }
+                    // A single value is coerced into a list.
+                    _ => self.warn_deprecated_input(value, inner, location, in_variable),
+                }
+                return;
+            }
+            Type::Named(name) | Type::NonNullNamed(name) => name,
+        };
+
+        match (self.schema.concrete_type_by_name(name), value) {
+            (Some(MetaType::Enum(EnumMeta { name, values, .. })), _) => {
+                // Variables hold enum values as strings.
+                let value_name = value
+                    .as_enum_value()
+                    .or_else(|| value.as_scalar().and_then(ScalarValue::as_str));
+                let deprecated = values
+                    .iter()
+                    .find(|v| Some(v.name.as_str()) == value_name)
+                    .and_then(|v| match v.deprecation_status {
+                        DeprecationStatus::Deprecated(ref reason) => Some((&v.name, reason)),
+                        DeprecationStatus::Current => None,
+                    });
+                if let Some((value_name, reason)) = deprecated {
+                    let name = format!("{}.{}", name, value_name);
+                    self.add_warning_at(deprecation_message("enum value", &name, reason), location);
+                }
+            }
+            (
+                Some(MetaType::InputObject(InputObjectMeta { input_fields, .. })),
+                InputValue::Object(fields),
+            ) => {
+                for (name, value) in fields {
+                    if let Some(field) = input_fields.iter().find(|f| f.name == name.item) {
+                        self.warn_deprecated_input(
+                            &value.item,
+                            &field.arg_type,
+                            location_of(value),
+                            in_variable,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn deprecation_message(kind: &str, name: &str, reason: &Option<String>) -> String {
+    match reason {
+        Some(reason) => format!("The {} `{}` is deprecated: {}", kind, name, reason),
+        None => format!("The {} `{}` is deprecated", kind, name),
+    }
+}
+
+/// The location as in the `locations` of errors, counting from 1
+fn location_value<S: ScalarValue>(location: SourcePosition) -> Value<S> {
+    let mut value = Object::with_capacity(2);
+    value.add_field("line", Value::scalar(location.line() as i32 + 1));
+    value.add_field("column", Value::scalar(location.column() as i32 + 1));
+    Value::object(value)
+}
@@ -85,7 +85,11 @@ async fn async_simple() {
     "#;
 
     let vars = Default::default();
-    let (res, errs) = crate::execute(doc, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: res,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &vars, &())
         .await
         .unwrap();
 
@@ -107,3 +111,252 @@ async fn async_simple() {
         }),
     );
 }
+
+mod local {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{EmptyMutation, EmptySubscription, RootNode};
+
+    use super::UserKind;
+
+    #[derive(Default)]
+    struct Context {
+        visited: RefCell<Vec<String>>,
+    }
+
+    impl crate::Context for Context {}
+
+    struct User {
+        name: Rc<str>,
+        kind: UserKind,
+    }
+
+    #[crate::graphql_object(Context = Context, local)]
+    impl User {
+        async fn name(&self, context: &Context) -> String {
+            let name = Rc::clone(&self.name);
+            futures::future::ready(()).await;
+            context.visited.borrow_mut().push(name.to_string());
+            name.to_string()
+        }
+
+        fn kind(&self) -> &UserKind {
+            &self.kind
+        }
+
+        async fn friends(&self) -> Vec<Rc<User>> {
+            vec![Rc::new(User {
+                name: format!("{}'s friend", self.name).into(),
+                kind: UserKind::Guest,
+            })]
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(Context = Context, local)]
+    impl Query {
+        async fn user(name: String) -> Option<User> {
+            Some(User {
+                name: name.into(),
+                kind: UserKind::Admin,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn executes_non_send_resolvers() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+        let doc = r#"{
+            user(name: "alice") {
+                name
+                kind
+                friends { name kind }
+            }
+        }"#;
+
+        let ctx = Context::default();
+        let crate::ExecutionOutput {
+            data: res,
+            errors: errs,
+            ..
+        } = crate::execute_local(doc, None, &schema, &Default::default(), &ctx)
+            .await
+            .unwrap();
+
+        assert!(errs.is_empty());
+        assert_eq!(
+            res,
+            graphql_value!({
+                "user": {
+                    "name": "alice",
+                    "kind": "ADMIN",
+                    "friends": [{"name": "alice's friend", "kind": "GUEST"}],
+                },
+            }),
+        );
+        assert_eq!(
+            *ctx.visited.borrow(),
+            vec!["alice".to_string(), "alice's friend".to_string()],
+        );
+    }
+}
+
+mod serial {
+    use std::{sync::Mutex, time::Duration};
+
+    use crate::{EmptyMutation, EmptySubscription, RootNode};
+
+    #[derive(Default)]
+    struct Context {
+        log: Mutex<Vec<String>>,
+    }
+
+    impl crate::Context for Context {}
+
+    impl Context {
+        async fn visit(&self, field: &str, millis: u64) -> bool {
+            self.log.lock().unwrap().push(format!("start {}", field));
+            tokio::time::delay_for(Duration::from_millis(millis)).await;
+            self.log.lock().unwrap().push(format!("end {}", field));
+            true
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(Context = Context)]
+    impl Query {
+        async fn a(context: &Context) -> bool {
+            context.visit("a", 10).await
+        }
+
+        #[graphql(serial)]
+        async fn b(context: &Context) -> bool {
+            context.visit("b", 10).await
+        }
+
+        async fn c(context: &Context) -> bool {
+            context.visit("c", 20).await
+        }
+
+        async fn d(context: &Context) -> bool {
+            context.visit("d", 10).await
+        }
+    }
+
+    #[tokio::test]
+    async fn serial_fields_are_resolved_apart() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+
+        let ctx = Context::default();
+        let crate::ExecutionOutput {
+            data: res,
+            errors: errs,
+            ..
+        } = crate::execute("{ a b c d }", None, &schema, &Default::default(), &ctx)
+            .await
+            .unwrap();
+
+        assert!(errs.is_empty());
+        assert_eq!(
+            res,
+            graphql_value!({ "a": true, "b": true, "c": true, "d": true }),
+        );
+        assert_eq!(
+            *ctx.log.lock().unwrap(),
+            vec!["start a", "end a", "start b", "end b", "start c", "start d", "end d", "end c",],
+        );
+    }
+}
+
+mod retries {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use crate::{EmptyMutation, EmptySubscription, ExecutionError, FieldError, RootNode};
+
+    #[derive(Default)]
+    struct Context {
+        attempts: AtomicUsize,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Query;
+
+    #[crate::graphql_object(Context = Context)]
+    impl Query {
+        #[graphql(retries = 2)]
+        async fn flaky(context: &Context) -> Result<i32, FieldError> {
+            let attempt = context.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt < 3 {
+                Err("flaky".into())
+            } else {
+                Ok(attempt as i32)
+            }
+        }
+
+        #[graphql(timeout = "20ms", retries = 1)]
+        async fn slow() -> bool {
+            tokio::time::delay_for(Duration::from_secs(5)).await;
+            true
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        )
+        .with_sleep(tokio::time::delay_for)
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_retried() {
+        let ctx = Context::default();
+        let crate::ExecutionOutput {
+            data: res,
+            errors: errs,
+            ..
+        } = crate::execute("{ flaky }", None, &schema(), &Default::default(), &ctx)
+            .await
+            .unwrap();
+
+        assert!(errs.is_empty());
+        assert_eq!(res, graphql_value!({ "flaky": 3 }));
+    }
+
+    #[tokio::test]
+    async fn timeouts_report_the_attempts() {
+        let ctx = Context::default();
+        let crate::ExecutionOutput {
+            data: res,
+            errors: errs,
+            ..
+        } = crate::execute("{ slow }", None, &schema(), &Default::default(), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!(None));
+        assert_eq!(
+            errs,
+            vec![ExecutionError::new(
+                crate::parser::SourcePosition::new(2, 0, 2),
+                &["slow"],
+                FieldError::new("Timed out after 20ms", graphql_value!({ "attempts": 2 })),
+            )],
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    cache::InMemoryCache,
+    executor::{ExecutionError, FieldError},
+    parser::SourcePosition,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+    ExecutionOutput,
+};
+
+struct Context {
+    scopes: Option<Vec<&'static str>>,
+}
+
+impl crate::Context for Context {}
+
+#[derive(crate::GraphQLObject)]
+#[graphql(Context = Context)]
+struct User {
+    name: String,
+    #[graphql(require_auth("users:email"))]
+    email: Option<String>,
+}
+
+struct Audit;
+
+#[crate::graphql_object(Context = Context, require_auth("admin"))]
+impl Audit {
+    fn entries() -> i32 {
+        3
+    }
+}
+
+struct Query;
+
+#[crate::graphql_object(Context = Context)]
+impl Query {
+    fn public() -> bool {
+        true
+    }
+
+    #[graphql(require_auth)]
+    fn me() -> Option<User> {
+        Some(User {
+            name: "Ferris".to_owned(),
+            email: Some("ferris@example.com".to_owned()),
+        })
+    }
+
+    fn audit() -> Option<Audit> {
+        Some(Audit)
+    }
+}
+
+#[derive(Hash)]
+struct CachedQuery;
+
+#[crate::graphql_object(Context = Context)]
+impl CachedQuery {
+    #[graphql(cache(ttl = "1h"))]
+    fn user() -> User {
+        User {
+            name: "Ferris".to_owned(),
+            email: Some("ferris@example.com".to_owned()),
+        }
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+fn schema() -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+}
+
+fn is_authorized(context: &Context, required: &[String]) -> bool {
+    match context.scopes {
+        Some(ref scopes) => required.iter().all(|s| scopes.contains(&s.as_str())),
+        None => false,
+    }
+}
+
+fn evaluated_schema() -> Schema {
+    schema().with_policy_evaluator(is_authorized)
+}
+
+fn run(
+    schema: &Schema,
+    query: &str,
+    scopes: Option<Vec<&'static str>>,
+) -> (
+    Value<DefaultScalarValue>,
+    Vec<ExecutionError<DefaultScalarValue>>,
+) {
+    let ExecutionOutput { data, errors, .. } = crate::execute_sync(
+        query,
+        None,
+        schema,
+        &crate::Variables::new(),
+        &Context { scopes },
+    )
+    .expect("Execution failed");
+    (data, errors)
+}
+
+fn forbidden(column: usize, path: &[&str]) -> ExecutionError<DefaultScalarValue> {
+    ExecutionError::new(
+        SourcePosition::new(column, 0, column),
+        path,
+        FieldError::new("Not authorized", graphql_value!({ "code": "FORBIDDEN" })),
+    )
+}
+
+#[test]
+fn requirements_are_denied_without_evaluator() {
+    let (data, errors) = run(
+        &schema(),
+        "{ public me { name } }",
+        Some(vec!["users:email"]),
+    );
+
+    assert_eq!(data, graphql_value!({ "public": true, "me": None }));
+    assert_eq!(errors, vec![forbidden(9, &["me"])]);
+}
+
+#[test]
+fn fields_are_resolved_with_the_required_scopes() {
+    let query = "{ me { name email } audit { entries } }";
+
+    let (data, errors) = run(&evaluated_schema(), query, Some(vec![]));
+    assert_eq!(
+        data,
+        graphql_value!({ "me": { "name": "Ferris", "email": None }, "audit": None }),
+    );
+    assert_eq!(
+        errors,
+        vec![forbidden(12, &["me", "email"]), forbidden(20, &["audit"])],
+    );
+
+    let scopes = vec!["users:email", "admin"];
+    let (data, errors) = run(&evaluated_schema(), query, Some(scopes));
+    assert_eq!(
+        data,
+        graphql_value!({
+            "me": { "name": "Ferris", "email": "ferris@example.com" },
+            "audit": { "entries": 3 },
+        }),
+    );
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn introspection_hides_the_denied_fields_and_types() {
+    let query = r#"{
+        query: __type(name: "Query") { fields { name } }
+        user: __type(name: "User") { fields { name } }
+        audit: __type(name: "Audit") { name }
+        __schema { types { name } }
+    }"#;
+
+    let (data, errors) = run(&evaluated_schema(), query, Some(vec![]));
+    assert!(errors.is_empty());
+
+    let data = data.as_object_value().unwrap();
+    assert_eq!(
+        data.get_field_value("query"),
+        Some(&graphql_value!({ "fields": [{ "name": "public" }, { "name": "me" }] })),
+    );
+    assert_eq!(
+        data.get_field_value("user"),
+        Some(&graphql_value!({ "fields": [{ "name": "name" }] })),
+    );
+    assert_eq!(data.get_field_value("audit"), Some(&graphql_value!(None)));
+    let types = data
+        .get_field_value("__schema")
+        .and_then(|s| s.as_object_value())
+        .and_then(|s| s.get_field_value("types"))
+        .and_then(|t| t.as_list_value())
+        .unwrap();
+    assert!(!types.contains(&graphql_value!({ "name": "Audit" })));
+    assert!(types.contains(&graphql_value!({ "name": "User" })));
+
+    let (data, _) = run(&evaluated_schema(), query, Some(vec!["admin"]));
+    let data = data.as_object_value().unwrap();
+    assert_eq!(
+        data.get_field_value("audit"),
+        Some(&graphql_value!({ "name": "Audit" })),
+    );
+}
+
+#[tokio::test]
+async fn async_execution_uses_the_evaluator() {
+    let ExecutionOutput { data, errors, .. } = crate::execute(
+        "{ me { name email } }",
+        None,
+        &evaluated_schema(),
+        &crate::Variables::new(),
+        &Context {
+            scopes: Some(vec![]),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        data,
+        graphql_value!({ "me": { "name": "Ferris", "email": None } })
+    );
+    assert_eq!(errors, vec![forbidden(12, &["me", "email"])]);
+}
+
+#[test]
+fn cached_results_are_only_served_to_operations_with_the_same_denials() {
+    let schema = RootNode::new(
+        CachedQuery,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .with_policy_evaluator(is_authorized)
+    .with_field_cache(InMemoryCache::new());
+    let run = |scopes| {
+        let ExecutionOutput { data, errors, .. } = crate::execute_sync(
+            "{ user { name email } }",
+            None,
+            &schema,
+            &crate::Variables::new(),
+            &Context {
+                scopes: Some(scopes),
+            },
+        )
+        .unwrap();
+        (data, errors)
+    };
+
+    let (data, errors) = run(vec!["users:email"]);
+    assert_eq!(
+        data,
+        graphql_value!({ "user": { "name": "Ferris", "email": "ferris@example.com" } }),
+    );
+    assert!(errors.is_empty());
+
+    let (data, errors) = run(vec![]);
+    assert_eq!(
+        data,
+        graphql_value!({ "user": { "name": "Ferris", "email": None } })
+    );
+    assert_eq!(errors, vec![forbidden(14, &["user", "email"])]);
+}
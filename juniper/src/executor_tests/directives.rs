@@ -1,6 +1,7 @@
 use crate::{
-    executor::Variables,
-    schema::model::RootNode,
+    ast::InputValue,
+    executor::{LookAheadMethods, Variables},
+    schema::model::{DirectiveLocation, DirectiveType, RootNode},
     types::scalars::{EmptyMutation, EmptySubscription},
     value::{DefaultScalarValue, Object, Value},
 };
@@ -16,6 +17,45 @@ impl TestType {
     fn b() -> &str {
         "b"
     }
+
+    fn child(executor: &Executor) -> Child {
+        let look_ahead = executor.look_ahead();
+        Child {
+            selected: look_ahead
+                .children()
+                .into_iter()
+                .map(|child| child.field_name().to_owned())
+                .collect(),
+        }
+    }
+
+    fn greeting(executor: &Executor) -> &str {
+        if executor.look_ahead().directive("shout").is_some() {
+            "HELLO"
+        } else {
+            "hello"
+        }
+    }
+}
+
+/// Reports the fields selected on it, as seen by the look-ahead
+struct Child {
+    selected: Vec<String>,
+}
+
+#[crate::graphql_object]
+impl Child {
+    fn a() -> &str {
+        "a"
+    }
+
+    fn b() -> &str {
+        "b"
+    }
+
+    fn selected(&self) -> &[String] {
+        &self.selected
+    }
 }
 
 async fn run_variable_query<F>(query: &str, vars: Variables<DefaultScalarValue>, f: F)
@@ -28,7 +68,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(query, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(query, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -245,3 +289,146 @@ async fn scalar_include_false_skip_false() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn scalar_skip_variable_default() {
+    run_query(
+        "query Q($skip: Boolean = true) { a, b @skip(if: $skip) }",
+        |result| {
+            assert_eq!(result.get_field_value("a"), Some(&Value::scalar("a")));
+            assert_eq!(result.get_field_value("b"), None);
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn scalar_skip_variable_default_overridden() {
+    let vars = vec![("skip".to_owned(), InputValue::scalar(false))]
+        .into_iter()
+        .collect();
+
+    run_variable_query(
+        "query Q($skip: Boolean = true) { a, b @skip(if: $skip) }",
+        vars,
+        |result| {
+            assert_eq!(result.get_field_value("a"), Some(&Value::scalar("a")));
+            assert_eq!(result.get_field_value("b"), Some(&Value::scalar("b")));
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn fragment_spread_include_variable_default() {
+    run_query(
+        "query Q($include: Boolean = false) { a, ...Frag @include(if: $include) } \
+         fragment Frag on TestType { b }",
+        |result| {
+            assert_eq!(result.get_field_value("a"), Some(&Value::scalar("a")));
+            assert_eq!(result.get_field_value("b"), None);
+        },
+    )
+    .await;
+}
+
+#[test]
+fn scalar_skip_variable_default_sync() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let crate::ExecutionOutput { data, errors, .. } = crate::execute_sync(
+        "query Q($skip: Boolean = true) { a, b @skip(if: $skip) }",
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .expect("Execution failed");
+
+    assert_eq!(errors, []);
+    let result = data.as_object_value().expect("Result is not an object");
+    assert_eq!(result.get_field_value("a"), Some(&Value::scalar("a")));
+    assert_eq!(result.get_field_value("b"), None);
+}
+
+#[tokio::test]
+async fn look_ahead_leaves_out_skipped_children() {
+    run_query(
+        "query Q($skip: Boolean = true) { child { a, b @skip(if: $skip), selected } }",
+        |result| {
+            let child = result
+                .get_field_value("child")
+                .and_then(Value::as_object_value)
+                .expect("child is not an object");
+            assert_eq!(
+                child.get_field_value("selected"),
+                Some(&Value::list(vec![
+                    Value::scalar("a"),
+                    Value::scalar("selected"),
+                ])),
+            );
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn look_ahead_leaves_out_skipped_children_of_fragments() {
+    run_query(
+        "query Q($skip: Boolean = true) { ...Frag } \
+         fragment Frag on TestType { child { a, ... on Child @skip(if: $skip) { b }, selected } }",
+        |result| {
+            let child = result
+                .get_field_value("child")
+                .and_then(Value::as_object_value)
+                .expect("child is not an object");
+            assert_eq!(
+                child.get_field_value("selected"),
+                Some(&Value::list(vec![
+                    Value::scalar("a"),
+                    Value::scalar("selected"),
+                ])),
+            );
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn look_ahead_exposes_custom_directives() {
+    let mut schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    schema.schema.add_directive(DirectiveType::new(
+        "shout",
+        &[DirectiveLocation::Field],
+        &[],
+    ));
+
+    let crate::ExecutionOutput { data, errors, .. } = crate::execute(
+        "{ greeting, loud: greeting @shout }",
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .await
+    .expect("Execution failed");
+
+    assert_eq!(errors, []);
+    let result = data.as_object_value().expect("Result is not an object");
+    assert_eq!(
+        result.get_field_value("greeting"),
+        Some(&Value::scalar("hello")),
+    );
+    assert_eq!(
+        result.get_field_value("loud"),
+        Some(&Value::scalar("HELLO"))
+    );
+}
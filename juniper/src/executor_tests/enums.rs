@@ -39,7 +39,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(query, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(query, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -142,7 +146,7 @@ async fn does_not_accept_incorrect_enum_name_in_variables() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$color" got invalid value. Invalid value for enum "Color"."#,
+            r#"Variable "$color" got invalid value. Invalid value for enum "Color", expected one of: RED, GREEN, BLUE."#,
             &[SourcePosition::new(8, 0, 8)],
         )])
     );
@@ -93,7 +93,11 @@ mod field_execution {
             .into_iter()
             .collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -206,7 +210,11 @@ mod merge_parallel_fragments {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -333,7 +341,11 @@ mod merge_parallel_inline_fragments {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -438,7 +450,11 @@ mod threads_context_correctly {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(
             doc,
             None,
             &schema,
@@ -558,7 +574,11 @@ mod dynamic_context_switching {
             .collect(),
         };
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &ctx)
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &ctx)
             .await
             .expect("Execution failed");
 
@@ -620,7 +640,11 @@ mod dynamic_context_switching {
             .collect(),
         };
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &ctx)
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &ctx)
             .await
             .expect("Execution failed");
 
@@ -679,7 +703,11 @@ mod dynamic_context_switching {
             .collect(),
         };
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &ctx)
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &ctx)
             .await
             .expect("Execution failed");
 
@@ -733,7 +761,11 @@ mod dynamic_context_switching {
             .collect(),
         };
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &ctx)
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &ctx)
             .await
             .expect("Execution failed");
 
@@ -799,7 +831,11 @@ mod dynamic_context_switching {
             .collect(),
         };
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &ctx)
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &ctx)
             .await
             .expect("Execution failed");
 
@@ -900,7 +936,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -932,7 +972,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -961,7 +1005,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -990,7 +1038,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1022,7 +1074,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1051,7 +1107,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1083,7 +1143,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1112,7 +1176,11 @@ mod propagates_errors_to_nullable_fields {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1185,7 +1253,11 @@ mod named_operations {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1208,7 +1280,11 @@ mod named_operations {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1232,7 +1308,11 @@ mod named_operations {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, Some("OtherExample"), &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, Some("OtherExample"), &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -1280,3 +1360,2454 @@ mod named_operations {
         assert_eq!(err, GraphQLError::UnknownOperationName);
     }
 }
+
+mod lazy_values {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+        GraphQLObject, GraphQLValue, Lazy,
+    };
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = crate::DefaultScalarValue)]
+    struct Author {
+        name: String,
+        books: Vec<Lazy<Book>>,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = crate::DefaultScalarValue)]
+    struct Book {
+        title: String,
+        author: Lazy<Author>,
+    }
+
+    fn author(built: Arc<AtomicUsize>) -> Author {
+        built.fetch_add(1, Ordering::SeqCst);
+        Author {
+            name: "Ann".into(),
+            books: vec![Lazy::new(move || book(built.clone()))],
+        }
+    }
+
+    fn book(built: Arc<AtomicUsize>) -> Book {
+        built.fetch_add(1, Ordering::SeqCst);
+        Book {
+            title: "Tales".into(),
+            author: Lazy::new(move || author(built.clone())),
+        }
+    }
+
+    struct Schema {
+        built: Arc<AtomicUsize>,
+    }
+
+    #[crate::graphql_object]
+    impl Schema {
+        fn author(&self) -> Author {
+            author(self.built.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_selected_values_only() {
+        let built = Arc::new(AtomicUsize::new(0));
+        let schema = RootNode::new(
+            Schema {
+                built: built.clone(),
+            },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let doc = r"{ author { books { author { name } } } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            Value::object(
+                vec![(
+                    "author",
+                    Value::object(
+                        vec![(
+                            "books",
+                            Value::list(vec![Value::object(
+                                vec![(
+                                    "author",
+                                    Value::object(
+                                        vec![("name", Value::scalar("Ann"))].into_iter().collect(),
+                                    ),
+                                )]
+                                .into_iter()
+                                .collect(),
+                            )]),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        );
+        // The root author, its book and the book's author.
+        assert_eq!(built.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn registers_the_type_without_a_value() {
+        let lazy = Lazy::<Book>::new(|| panic!("Book built"));
+        assert_eq!(
+            GraphQLValue::<crate::DefaultScalarValue>::type_name(&lazy, &()),
+            Some("Book"),
+        );
+
+        let schema = RootNode::new(
+            Schema {
+                built: Arc::new(AtomicUsize::new(0)),
+            },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        assert!(schema.schema.concrete_type_by_name("Book").is_some());
+        assert!(schema.schema.concrete_type_by_name("Author").is_some());
+    }
+}
+
+mod context_cell {
+    use crate::{
+        executor::{Context, ContextCell},
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+    };
+
+    struct Schema;
+
+    #[derive(Default)]
+    struct TestContext {
+        resolved: ContextCell<Vec<String>>,
+    }
+
+    impl Context for TestContext {}
+
+    #[crate::graphql_object(
+        Context = TestContext,
+    )]
+    impl Schema {
+        async fn a(context: &TestContext) -> i32 {
+            context.resolved.update(|r| r.push("a".into()));
+            1
+        }
+
+        async fn b(context: &TestContext) -> i32 {
+            context.resolved.update(|r| r.push("b".into()));
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn accumulates_state_across_resolvers() {
+        let schema = RootNode::new(
+            Schema,
+            EmptyMutation::<TestContext>::new(),
+            EmptySubscription::<TestContext>::new(),
+        );
+        let doc = r"{ a b first: a }";
+
+        let vars = vec![].into_iter().collect();
+        let context = TestContext::default();
+
+        let crate::ExecutionOutput { errors: errs, .. } =
+            crate::execute(doc, None, &schema, &vars, &context)
+                .await
+                .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        let mut resolved = context.resolved.into_inner();
+        resolved.sort();
+        assert_eq!(resolved, vec!["a", "a", "b"]);
+    }
+}
+
+mod field_cache {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        cache::InMemoryCache,
+        executor::{Context, FieldResult},
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Value},
+        GraphQLObject, Variables,
+    };
+
+    #[derive(Default)]
+    struct Counters {
+        rate: AtomicUsize,
+        quote: AtomicUsize,
+        failing: AtomicUsize,
+    }
+
+    impl Context for Counters {}
+
+    #[derive(Hash)]
+    struct Query;
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = DefaultScalarValue)]
+    struct Quote {
+        text: String,
+        author: String,
+    }
+
+    #[crate::graphql_object(Context = Counters)]
+    impl Query {
+        #[graphql(cache(ttl = "1h"))]
+        fn rate(context: &Counters, currency: String, precision: Option<i32>) -> String {
+            let count = context.rate.fetch_add(1, Ordering::SeqCst) + 1;
+            match precision {
+                Some(precision) => format!("{}:{}:{}", currency, count, precision),
+                None => format!("{}:{}", currency, count),
+            }
+        }
+
+        #[graphql(cache(ttl = "1h"))]
+        async fn quote(context: &Counters) -> Quote {
+            context.quote.fetch_add(1, Ordering::SeqCst);
+            Quote {
+                text: "Hello".into(),
+                author: "Ann".into(),
+            }
+        }
+
+        #[graphql(cache(ttl = "1h"))]
+        fn failing(context: &Counters) -> FieldResult<i32> {
+            context.failing.fetch_add(1, Ordering::SeqCst);
+            Err("Unavailable".into())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Counters>, EmptySubscription<Counters>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Counters>::new(),
+            EmptySubscription::<Counters>::new(),
+        )
+        .with_field_cache(InMemoryCache::new())
+    }
+
+    async fn run(schema: &Schema, doc: &str, context: &Counters) -> Value {
+        let crate::ExecutionOutput { data: result, .. } =
+            crate::execute(doc, None, schema, &Variables::new(), context)
+                .await
+                .expect("Execution failed");
+        result
+    }
+
+    #[tokio::test]
+    async fn resolves_once_per_arguments() {
+        let schema = schema();
+        let context = Counters::default();
+
+        let first = run(&schema, r#"{ rate(currency: "EUR") }"#, &context).await;
+        let second = run(&schema, r#"{ rate(currency: "EUR") }"#, &context).await;
+        let other = run(&schema, r#"{ rate(currency: "USD") }"#, &context).await;
+
+        assert_eq!(first, second);
+        assert_eq!(
+            other,
+            Value::object(vec![("rate", Value::scalar("USD:2"))].into_iter().collect()),
+        );
+        assert_eq!(context.rate.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn ignores_the_order_of_arguments() {
+        let schema = schema();
+        let context = Counters::default();
+
+        let first = run(
+            &schema,
+            r#"{ rate(currency: "EUR", precision: 2) }"#,
+            &context,
+        )
+        .await;
+        let second = run(
+            &schema,
+            r#"{ rate(precision: 2, currency: "EUR") }"#,
+            &context,
+        )
+        .await;
+
+        assert_eq!(first, second);
+        assert_eq!(context.rate.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn resolves_once_per_selection() {
+        let schema = schema();
+        let context = Counters::default();
+
+        run(&schema, "{ quote { text } }", &context).await;
+        run(&schema, "{ quote { text } }", &context).await;
+        let result = run(&schema, "{ quote { text author } }", &context).await;
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![(
+                    "quote",
+                    Value::object(
+                        vec![
+                            ("text", Value::scalar("Hello")),
+                            ("author", Value::scalar("Ann")),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        );
+        assert_eq!(context.quote.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn skips_errors() {
+        let schema = schema();
+        let context = Counters::default();
+
+        run(&schema, "{ failing }", &context).await;
+        run(&schema, "{ failing }", &context).await;
+
+        assert_eq!(context.failing.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn requires_backend() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Counters>::new(),
+            EmptySubscription::<Counters>::new(),
+        );
+        let context = Counters::default();
+
+        run(&schema, r#"{ rate(currency: "EUR") }"#, &context).await;
+        run(&schema, r#"{ rate(currency: "EUR") }"#, &context).await;
+
+        assert_eq!(context.rate.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn resolves_once_synchronously() {
+        let schema = schema();
+        let context = Counters::default();
+        let doc = r#"{ rate(currency: "EUR") }"#;
+
+        for _ in 0..2 {
+            crate::execute_sync(doc, None, &schema, &Variables::new(), &context)
+                .expect("Execution failed");
+        }
+
+        assert_eq!(context.rate.load(Ordering::SeqCst), 1);
+    }
+}
+
+mod cache_policy {
+    use crate::{
+        cache::{CachePolicy, CacheScope, InMemoryCache},
+        executor::FieldResult,
+        http::{GraphQLBatchRequest, GraphQLRequest},
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::DefaultScalarValue,
+        GraphQLObject,
+    };
+
+    struct Query;
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = DefaultScalarValue, cache_control(max_age = 300))]
+    struct Country {
+        name: String,
+        #[graphql(cache_control(max_age = 30))]
+        population: i32,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = DefaultScalarValue)]
+    struct Profile {
+        name: String,
+    }
+
+    #[crate::graphql_object]
+    impl Query {
+        #[graphql(cache_control(max_age = 60))]
+        fn greeting() -> &str {
+            "Hello"
+        }
+
+        fn country() -> Country {
+            Country {
+                name: "Iceland".into(),
+                population: 364_000,
+            }
+        }
+
+        #[graphql(cache_control(max_age = 600, scope = "private"))]
+        fn profile() -> Profile {
+            Profile { name: "Ann".into() }
+        }
+
+        fn uncached() -> i32 {
+            1
+        }
+
+        #[graphql(cache_control(max_age = 60))]
+        fn failing() -> FieldResult<i32> {
+            Err("Unavailable".into())
+        }
+    }
+
+    struct Mutation;
+
+    #[crate::graphql_object]
+    impl Mutation {
+        #[graphql(cache_control(max_age = 60))]
+        fn touch() -> bool {
+            true
+        }
+    }
+
+    type Schema = RootNode<'static, Query, Mutation, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, Mutation, EmptySubscription::<()>::new())
+    }
+
+    fn request(query: &str) -> GraphQLRequest {
+        GraphQLRequest::new(query.into(), None, None)
+    }
+
+    async fn policy(query: &str) -> Option<CachePolicy> {
+        let schema = schema();
+        let request = request(query);
+        let response = request.execute(&schema, &()).await;
+        assert!(response.is_ok());
+        response.cache_policy()
+    }
+
+    fn public(max_age: u32) -> Option<CachePolicy> {
+        Some(CachePolicy {
+            max_age,
+            scope: CacheScope::Public,
+        })
+    }
+
+    #[tokio::test]
+    async fn uses_the_field_hint() {
+        assert_eq!(policy("{ greeting }").await, public(60));
+    }
+
+    #[tokio::test]
+    async fn uses_the_type_hint_and_the_shortest_max_age() {
+        assert_eq!(policy("{ country { name } }").await, public(300));
+        assert_eq!(policy("{ country { name population } }").await, public(30));
+        assert_eq!(policy("{ greeting country { name } }").await, public(60));
+    }
+
+    #[tokio::test]
+    async fn private_hints_make_the_response_private() {
+        let expected = Some(CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Private,
+        });
+        assert_eq!(policy("{ greeting profile { name } }").await, expected);
+    }
+
+    #[tokio::test]
+    async fn unhinted_root_fields_prevent_caching() {
+        assert_eq!(policy("{ uncached }").await, None);
+        assert_eq!(policy("{ greeting uncached }").await, None);
+    }
+
+    #[tokio::test]
+    async fn introspection_fields_are_ignored() {
+        assert_eq!(
+            policy("{ __schema { queryType { name } } greeting }").await,
+            public(60)
+        );
+    }
+
+    #[tokio::test]
+    async fn responses_having_errors_arent_cached() {
+        assert_eq!(policy("{ failing }").await, None);
+    }
+
+    #[tokio::test]
+    async fn mutations_arent_cached() {
+        assert_eq!(policy("mutation { touch }").await, None);
+    }
+
+    #[test]
+    fn synchronous_execution_computes_the_policy() {
+        let schema = schema();
+        let request = request("{ country { name } }");
+        assert_eq!(
+            request.execute_sync(&schema, &()).cache_policy(),
+            public(300)
+        );
+    }
+
+    #[tokio::test]
+    async fn batches_use_the_policies_of_all_responses() {
+        let schema = schema();
+
+        let batch = GraphQLBatchRequest::Batch(vec![
+            request("{ greeting }"),
+            request("{ country { name population } }"),
+        ]);
+        let response = batch.execute(&schema, &()).await;
+        assert_eq!(response.cache_policy(), public(30));
+
+        let batch =
+            GraphQLBatchRequest::Batch(vec![request("{ greeting }"), request("{ uncached }")]);
+        let response = batch.execute(&schema, &()).await;
+        assert_eq!(response.cache_policy(), None);
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = DefaultScalarValue)]
+    struct Account {
+        name: String,
+        #[graphql(cache_control(scope = "private"))]
+        email: String,
+    }
+
+    #[derive(Hash)]
+    struct CachedQuery;
+
+    #[crate::graphql_object]
+    impl CachedQuery {
+        #[graphql(cache(ttl = "1h"), cache_control(max_age = 60))]
+        fn account() -> Account {
+            Account {
+                name: "Ann".into(),
+                email: "ann@example.com".into(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn cached_fields_keep_the_hints_of_their_sub_fields() {
+        let schema = RootNode::new(
+            CachedQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_field_cache(InMemoryCache::new());
+        let expected = Some(CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Private,
+        });
+
+        for query in &[
+            "{ account { name email } }",
+            "{ account { ...Contact } } fragment Contact on Account { email }",
+            "{ account { ... on Account { email } } }",
+        ] {
+            let request = request(query);
+            let miss = request.execute(&schema, &()).await.cache_policy();
+            let hit = request.execute(&schema, &()).await.cache_policy();
+            let sync_hit = request.execute_sync(&schema, &()).cache_policy();
+            assert_eq!(miss, expected, "{}", query);
+            assert_eq!(hit, expected, "{}", query);
+            assert_eq!(sync_hit, expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn formats_the_cache_control_header() {
+        let policy = CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Private,
+        };
+        assert_eq!(policy.to_string(), "max-age=60, private");
+    }
+}
+
+mod response_extensions {
+    use crate::{
+        http::GraphQLRequest,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Object, Value},
+        InputValue, Variables,
+    };
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn sync_field(executor: &Executor) -> i32 {
+            executor.add_extension("rateLimit", Value::scalar(99));
+            1
+        }
+
+        async fn async_field(executor: &Executor) -> i32 {
+            executor.add_extension("traceId", Value::scalar("abc"));
+            executor.add_extension("rateLimit", Value::scalar(98));
+            2
+        }
+
+        fn plain() -> i32 {
+            3
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn resolvers_add_extensions() {
+        let schema = schema();
+        let crate::ExecutionOutput {
+            errors, extensions, ..
+        } = crate::execute(
+            "{ syncField asyncField }",
+            None,
+            &schema,
+            &Variables::new(),
+            &(),
+        )
+        .await
+        .expect("Execution failed");
+
+        assert_eq!(errors, []);
+        assert_eq!(extensions.field_count(), 2);
+        assert_eq!(
+            extensions.get_field_value("rateLimit"),
+            Some(&Value::scalar(98)),
+        );
+        assert_eq!(
+            extensions.get_field_value("traceId"),
+            Some(&Value::scalar("abc")),
+        );
+    }
+
+    #[test]
+    fn synchronous_execution_adds_extensions() {
+        let schema = schema();
+        let output = crate::execute_sync("{ syncField }", None, &schema, &Variables::new(), &())
+            .expect("Execution failed");
+
+        let expected: Object<_> = vec![("rateLimit", Value::scalar(99))].into_iter().collect();
+        assert_eq!(output.extensions, expected);
+    }
+
+    #[tokio::test]
+    async fn responses_serialize_extensions() {
+        let schema = schema();
+
+        let request = GraphQLRequest::new("{ syncField }".into(), None, None);
+        let response = request.execute(&schema, &()).await;
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "data": { "syncField": 1 },
+                "extensions": { "rateLimit": 99 },
+            }),
+        );
+
+        let request = GraphQLRequest::new("{ plain }".into(), None, None);
+        let response = request.execute(&schema, &()).await;
+        assert_eq!(response.extensions().map(Object::field_count), Some(0));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({ "data": { "plain": 3 } }),
+        );
+    }
+
+    #[test]
+    fn requests_carry_extensions() {
+        let json = serde_json::json!({
+            "query": "{ plain }",
+            "operationName": null,
+            "variables": null,
+            "extensions": {
+                "traceId": "abc",
+                "persistedQuery": { "version": 1 },
+            },
+        });
+        let request: GraphQLRequest = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(request.query(), "{ plain }");
+        assert_eq!(
+            request.extension("traceId"),
+            Some(&InputValue::scalar("abc")),
+        );
+        assert!(request.extension("persistedQuery").is_some());
+        assert_eq!(request.extension("missing"), None);
+        assert_eq!(serde_json::to_value(&request).unwrap(), json);
+
+        let request = GraphQLRequest::<DefaultScalarValue>::new("{ plain }".into(), None, None);
+        assert_eq!(request.extensions(), None);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({ "query": "{ plain }", "operationName": null, "variables": null }),
+        );
+        let request = request.with_extensions(InputValue::object(
+            vec![("traceId", InputValue::scalar("def"))]
+                .into_iter()
+                .collect(),
+        ));
+        assert_eq!(
+            request.extension("traceId"),
+            Some(&InputValue::scalar("def")),
+        );
+    }
+}
+
+mod error_formatting {
+    use crate::{
+        executor::{ExecutionError, FieldError, FieldResult},
+        http::GraphQLRequest,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::DefaultScalarValue,
+    };
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn missing() -> FieldResult<Option<i32>> {
+            Err(FieldError::new(
+                "No row 42",
+                graphql_value!({ "code": "NOT_FOUND" }),
+            ))
+        }
+
+        fn broken() -> FieldResult<Option<i32>> {
+            Err("connection refused by db-1:5432".into())
+        }
+    }
+
+    fn mask(error: &ExecutionError<DefaultScalarValue>) -> FieldError<DefaultScalarValue> {
+        match error.error().extensions().as_object_value() {
+            Some(_) => FieldError::new("Not found", error.error().extensions().clone()),
+            None => FieldError::new(
+                format!("Internal error at {}", error.path().join(".")),
+                graphql_value!({ "correlationId": "4f2e" }),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn formats_resolver_errors() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let request = GraphQLRequest::new("{ missing broken }".into(), None, None);
+        let response = request.execute(&schema, &()).await.format_errors(&mask);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "data": { "missing": null, "broken": null },
+                "errors": [
+                    {
+                        "message": "Not found",
+                        "locations": [{ "line": 1, "column": 3 }],
+                        "path": ["missing"],
+                        "extensions": { "code": "NOT_FOUND" },
+                    },
+                    {
+                        "message": "Internal error at broken",
+                        "locations": [{ "line": 1, "column": 11 }],
+                        "path": ["broken"],
+                        "extensions": { "correlationId": "4f2e" },
+                    },
+                ],
+            }),
+        );
+
+        let request = GraphQLRequest::new("{ unknown }".into(), None, None);
+        let response = request.execute(&schema, &()).await.format_errors(&mask);
+        assert!(!response.is_ok());
+    }
+}
+
+mod panic_catching {
+    use crate::{
+        executor::{ExecutionError, FieldError},
+        parser::SourcePosition,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Value},
+        Variables,
+    };
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn fine() -> i32 {
+            1
+        }
+
+        fn panicking() -> Option<i32> {
+            panic!("index out of bounds")
+        }
+
+        async fn panicking_async(id: i32) -> Option<i32> {
+            panic!("no user {}", id)
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_panic_catching()
+    }
+
+    fn expected() -> (
+        Value<DefaultScalarValue>,
+        Vec<ExecutionError<DefaultScalarValue>>,
+    ) {
+        (
+            graphql_value!({ "fine": 1, "panicking": None, "panickingAsync": None }),
+            vec![
+                ExecutionError::new(
+                    SourcePosition::new(7, 0, 7),
+                    &["panicking"],
+                    FieldError::from("index out of bounds"),
+                ),
+                ExecutionError::new(
+                    SourcePosition::new(17, 0, 17),
+                    &["panickingAsync"],
+                    FieldError::from("no user 42"),
+                ),
+            ],
+        )
+    }
+
+    const DOC: &str = "{ fine panicking panickingAsync(id: 42) }";
+
+    #[tokio::test]
+    async fn panics_become_field_errors() {
+        let schema = schema();
+        let crate::ExecutionOutput {
+            data: value,
+            mut errors,
+            ..
+        } = crate::execute(DOC, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+        errors.sort();
+
+        assert_eq!((value, errors), expected());
+    }
+
+    struct LocalQuery;
+
+    #[crate::graphql_object(local)]
+    impl LocalQuery {
+        fn fine() -> i32 {
+            1
+        }
+
+        fn panicking() -> Option<i32> {
+            panic!("index out of bounds")
+        }
+
+        async fn panicking_async(id: i32) -> Option<i32> {
+            panic!("no user {}", id)
+        }
+    }
+
+    #[tokio::test]
+    async fn panics_become_field_errors_on_the_current_thread() {
+        let schema = RootNode::new(
+            LocalQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_panic_catching();
+        let crate::ExecutionOutput {
+            data: value,
+            mut errors,
+            ..
+        } = crate::execute_local(DOC, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+        errors.sort();
+
+        assert_eq!((value, errors), expected());
+    }
+
+    #[test]
+    fn panics_become_field_errors_synchronously() {
+        let schema = schema();
+        let crate::ExecutionOutput {
+            data: value,
+            errors,
+            ..
+        } = crate::execute_sync("{ fine panicking }", None, &schema, &Variables::new(), &())
+            .expect("Execution failed");
+
+        assert_eq!(value, graphql_value!({ "fine": 1, "panicking": None }));
+        assert_eq!(
+            errors,
+            vec![ExecutionError::new(
+                SourcePosition::new(7, 0, 7),
+                &["panicking"],
+                FieldError::from("index out of bounds"),
+            )],
+        );
+    }
+}
+
+mod usage_stats {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        executor::FieldResult,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        usage::{FieldUsage, OperationUsage, UsageCollector, UsageReport},
+        value::DefaultScalarValue,
+        GraphQLObject, Variables,
+    };
+
+    struct Query;
+
+    #[derive(GraphQLObject)]
+    #[graphql(scalar = DefaultScalarValue)]
+    struct User {
+        name: String,
+        email: String,
+    }
+
+    #[crate::graphql_object]
+    impl Query {
+        fn user() -> User {
+            User {
+                name: "Ann".into(),
+                email: "ann@example.com".into(),
+            }
+        }
+
+        async fn failing() -> FieldResult<i32> {
+            Err("Unavailable".into())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn collect(collector: UsageCollector) -> (Schema, Arc<UsageCollector>) {
+        let collector = Arc::new(collector);
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_usage_collector(collector.clone());
+        (schema, collector)
+    }
+
+    fn exporter() -> (
+        Arc<Mutex<Vec<UsageReport>>>,
+        impl Fn(&UsageReport) + Send + Sync,
+    ) {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let exported = reports.clone();
+        (reports, move |report: &UsageReport| {
+            exported.lock().unwrap().push(report.clone())
+        })
+    }
+
+    fn field(type_name: &str, field_name: &str, count: u64, errors: u64) -> FieldUsage {
+        FieldUsage {
+            type_name: type_name.into(),
+            field_name: field_name.into(),
+            count,
+            errors,
+        }
+    }
+
+    #[tokio::test]
+    async fn counts_operations_and_fields() {
+        let (reports, exporter) = exporter();
+        let (schema, collector) = collect(UsageCollector::new(exporter));
+        let vars = Variables::new();
+
+        crate::execute_sync("query Profile { user { name } }", None, &schema, &vars, &()).unwrap();
+        crate::execute("{ user { name } failing }", None, &schema, &vars, &())
+            .await
+            .unwrap();
+        assert!(reports.lock().unwrap().is_empty());
+
+        collector.flush();
+        let report = reports.lock().unwrap().pop().unwrap();
+        assert_eq!(
+            report.operations,
+            vec![
+                OperationUsage {
+                    name: "".into(),
+                    count: 1,
+                },
+                OperationUsage {
+                    name: "Profile".into(),
+                    count: 1,
+                },
+            ],
+        );
+        assert_eq!(
+            report.fields,
+            vec![
+                field("Query", "failing", 1, 1),
+                field("Query", "user", 2, 0),
+                field("User", "email", 0, 0),
+                field("User", "name", 2, 0),
+            ],
+        );
+        assert_eq!(
+            report.unused_fields().collect::<Vec<_>>(),
+            vec![&field("User", "email", 0, 0)],
+        );
+
+        collector.flush();
+        let report = reports.lock().unwrap().pop().unwrap();
+        assert!(report.operations.is_empty());
+        assert_eq!(report.unused_fields().count(), 4);
+    }
+
+    #[test]
+    fn flushes_at_intervals() {
+        let (reports, exporter) = exporter();
+        let (schema, _) =
+            collect(UsageCollector::new(exporter).flush_interval(Duration::from_secs(0)));
+
+        crate::execute_sync("{ user { email } }", None, &schema, &Variables::new(), &()).unwrap();
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].fields[2], field("User", "email", 1, 0));
+    }
+
+    #[test]
+    fn serializes_reports_to_json() {
+        let report = UsageReport {
+            operations: vec![OperationUsage {
+                name: "Profile".into(),
+                count: 3,
+            }],
+            fields: vec![field("User", "name", 3, 1)],
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "operations": [{ "name": "Profile", "count": 3 }],
+                "fields": [{ "typeName": "User", "fieldName": "name", "count": 3, "errors": 1 }],
+            }),
+        );
+    }
+}
+
+mod query_rewriting {
+    use crate::{
+        ast::{Arguments, Definition, Document, Field, InputValue, Selection},
+        parser::Spanning,
+        rewrite::QueryRewriter,
+        schema::model::{RootNode, SchemaType},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Value},
+        GraphQLError, Variables,
+    };
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn orders(tenant: Option<String>) -> String {
+            format!("orders of {}", tenant.unwrap_or_else(|| "everyone".into()))
+        }
+
+        fn secret() -> &str {
+            "42"
+        }
+    }
+
+    fn root_fields<'a, 'd>(
+        document: &'a mut Document<'d, DefaultScalarValue>,
+    ) -> &'a mut Vec<Selection<'d, DefaultScalarValue>> {
+        match document.first_mut() {
+            Some(Definition::Operation(op)) => &mut op.item.selection_set,
+            _ => panic!("Expected an operation"),
+        }
+    }
+
+    /// Removes the `secret` field and restricts `orders` to a tenant
+    struct Restrict;
+
+    impl QueryRewriter for Restrict {
+        fn rewrite(
+            &self,
+            document: &mut Document<DefaultScalarValue>,
+            _: &SchemaType<DefaultScalarValue>,
+        ) {
+            let fields = root_fields(document);
+            fields.retain(|s| !matches!(s, Selection::Field(f) if f.item.name.item == "secret"));
+            for selection in fields {
+                if let Selection::Field(f) = selection {
+                    if f.item.name.item == "orders" {
+                        f.item.arguments = Some(Spanning::unlocated(Arguments {
+                            items: vec![(
+                                Spanning::unlocated("tenant"),
+                                Spanning::unlocated(InputValue::scalar("acme")),
+                            )],
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Selects a field which doesn't exist
+    struct SelectMissing;
+
+    impl QueryRewriter for SelectMissing {
+        fn rewrite(
+            &self,
+            document: &mut Document<DefaultScalarValue>,
+            _: &SchemaType<DefaultScalarValue>,
+        ) {
+            root_fields(document).push(Selection::Field(Spanning::unlocated(Field {
+                alias: None,
+                name: Spanning::unlocated("missing"),
+                arguments: None,
+                directives: None,
+                selection_set: None,
+            })));
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn rewrites_documents_before_execution() {
+        let schema = schema().with_query_rewriter(Restrict);
+        let doc = r#"{ orders(tenant: "other") secret }"#;
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors,
+            ..
+        } = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+        assert_eq!(errors, []);
+        assert_eq!(
+            result,
+            Value::object(
+                vec![("orders", Value::scalar("orders of acme"))]
+                    .into_iter()
+                    .collect()
+            ),
+        );
+
+        let crate::ExecutionOutput {
+            data: sync_result, ..
+        } = crate::execute_sync(doc, None, &schema, &Variables::new(), &())
+            .expect("Execution failed");
+        assert_eq!(sync_result, result);
+    }
+
+    #[test]
+    fn validates_rewritten_documents() {
+        let schema = schema().with_query_rewriter(SelectMissing);
+
+        let result = crate::execute_sync("{ secret }", None, &schema, &Variables::new(), &());
+        match result {
+            Err(GraphQLError::ValidationError(errors)) => assert_eq!(errors.len(), 1),
+            other => panic!("Expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn applies_rewriters_in_order() {
+        let schema = schema()
+            .with_query_rewriter(SelectMissing)
+            .with_query_rewriter(
+                |document: &mut Document<DefaultScalarValue>,
+                 _: &SchemaType<DefaultScalarValue>| {
+                    root_fields(document).retain(
+                        |s| !matches!(s, Selection::Field(f) if f.item.name.item == "missing"),
+                    )
+                },
+            );
+
+        let crate::ExecutionOutput { data: result, .. } =
+            crate::execute_sync("{ secret }", None, &schema, &Variables::new(), &())
+                .expect("Execution failed");
+        assert_eq!(
+            result,
+            Value::object(vec![("secret", Value::scalar("42"))].into_iter().collect()),
+        );
+    }
+}
+
+#[cfg(all(feature = "stacker", not(target_arch = "wasm32")))]
+mod deep_nesting {
+    use std::thread;
+
+    use crate::{
+        parser::DEFAULT_RECURSION_LIMIT,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+        Variables,
+    };
+
+    /// Too small for resolving the queries below on a single stack segment.
+    const THREAD_STACK_SIZE: usize = 256 * 1024;
+
+    struct Node(i32);
+
+    #[crate::graphql_object]
+    impl Node {
+        fn depth(&self) -> i32 {
+            self.0
+        }
+
+        fn child(&self) -> Option<Node> {
+            Some(Node(self.0 + 1))
+        }
+
+        async fn children(&self) -> Vec<Node> {
+            vec![Node(self.0 + 1)]
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn root() -> Node {
+            Node(0)
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    /// Query selecting `field` as deep as the nesting depth limit allows.
+    fn query(field: &str) -> (String, usize) {
+        let depth = DEFAULT_RECURSION_LIMIT - 2;
+        let query = format!(
+            "{{ root {{ {} depth {} }} }}",
+            format!("{} {{ ", field).repeat(depth),
+            "}".repeat(depth),
+        );
+        (query, depth)
+    }
+
+    fn innermost_depth(mut value: &Value) -> i32 {
+        loop {
+            let object = match value {
+                Value::List(items) => {
+                    value = &items[0];
+                    continue;
+                }
+                v => v.as_object_value().expect("Expected an object"),
+            };
+            match object.get_field_value("depth") {
+                Some(depth) => return depth.as_scalar_value::<i32>().copied().unwrap(),
+                None => {
+                    value = ["root", "child", "children"]
+                        .iter()
+                        .find_map(|&name| object.get_field_value(name))
+                        .expect("Expected a nested node")
+                }
+            }
+        }
+    }
+
+    fn on_small_stack<F: FnOnce() + Send + 'static>(f: F) {
+        thread::Builder::new()
+            .stack_size(THREAD_STACK_SIZE)
+            .spawn(f)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn resolves_deeply_nested_values_synchronously() {
+        on_small_stack(|| {
+            let (doc, depth) = query("child");
+
+            let crate::ExecutionOutput {
+                data: result,
+                errors,
+                ..
+            } = crate::execute_sync(&doc, None, &schema(), &Variables::new(), &())
+                .expect("Execution failed");
+
+            assert_eq!(errors, []);
+            assert_eq!(innermost_depth(&result), depth as i32);
+        });
+    }
+
+    #[test]
+    fn resolves_deeply_nested_values_asynchronously() {
+        on_small_stack(|| {
+            let (doc, depth) = query("children");
+            let schema = schema();
+            let mut runtime = tokio::runtime::Builder::new()
+                .basic_scheduler()
+                .build()
+                .unwrap();
+
+            let crate::ExecutionOutput {
+                data: result,
+                errors,
+                ..
+            } = runtime
+                .block_on(crate::execute(&doc, None, &schema, &Variables::new(), &()))
+                .expect("Execution failed");
+
+            assert_eq!(errors, []);
+            assert_eq!(innermost_depth(&result), depth as i32);
+        });
+    }
+}
+
+mod warnings {
+    use crate::{
+        http::GraphQLRequest,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        GraphQLEnum, GraphQLInputObject, InputValue,
+    };
+
+    #[derive(GraphQLEnum)]
+    enum Order {
+        Asc,
+        #[graphql(deprecated = "Use `ASC`")]
+        Desc,
+    }
+
+    #[derive(GraphQLInputObject)]
+    struct Filter {
+        orders: Vec<Order>,
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn name() -> &'static str {
+            "Alice"
+        }
+
+        #[deprecated(note = "Use `name`")]
+        fn login() -> &'static str {
+            "alice"
+        }
+
+        #[deprecated]
+        fn friends() -> Vec<Query> {
+            vec![Query, Query]
+        }
+
+        fn posts(order: Option<Order>, filter: Option<Filter>) -> i32 {
+            let _ = (order, filter);
+            0
+        }
+
+        fn legacy(executor: &Executor, limit: Option<i32>) -> i32 {
+            if limit.is_none() {
+                executor.add_warning("`limit` will soon be required");
+            }
+            limit.unwrap_or(10)
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    async fn execute(query: &str, variables: Option<InputValue>) -> serde_json::Value {
+        let schema = schema();
+        let request = GraphQLRequest::new(query.into(), None, variables);
+        let response = request.execute(&schema, &()).await;
+        serde_json::to_value(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn warns_about_deprecated_fields() {
+        let response = execute("{\n  login\n  friends { name login }\n}", None).await;
+
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "data": {
+                    "login": "alice",
+                    "friends": [
+                        { "name": "Alice", "login": "alice" },
+                        { "name": "Alice", "login": "alice" },
+                    ],
+                },
+                "extensions": {
+                    "warnings": [
+                        {
+                            "message": "The field `Query.login` is deprecated: Use `name`",
+                            "locations": [{ "line": 2, "column": 3 }],
+                        },
+                        {
+                            "message": "The field `Query.friends` is deprecated",
+                            "locations": [{ "line": 3, "column": 3 }],
+                        },
+                        {
+                            "message": "The field `Query.login` is deprecated: Use `name`",
+                            "locations": [{ "line": 3, "column": 18 }],
+                        },
+                    ],
+                },
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn warns_about_deprecated_enum_values_in_inputs() {
+        let query = "query ($order: Order, $filter: Filter) {
+            a: posts(order: DESC)
+            b: posts(order: ASC, filter: {orders: [ASC, DESC]})
+            c: posts(order: $order, filter: $filter)
+        }";
+        let variables = InputValue::object(
+            vec![
+                ("order", InputValue::scalar("DESC")),
+                (
+                    "filter",
+                    InputValue::object(
+                        vec![("orders", InputValue::scalar("DESC"))]
+                            .into_iter()
+                            .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let response = execute(query, Some(variables)).await;
+
+        let message = "The enum value `Order.DESC` is deprecated: Use `ASC`";
+        assert_eq!(
+            response["extensions"],
+            serde_json::json!({
+                "warnings": [
+                    { "message": message, "locations": [{ "line": 2, "column": 29 }] },
+                    { "message": message, "locations": [{ "line": 3, "column": 57 }] },
+                    { "message": message, "locations": [{ "line": 4, "column": 29 }] },
+                    { "message": message, "locations": [{ "line": 4, "column": 45 }] },
+                ],
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolvers_add_warnings() {
+        let response = execute("{ a: legacy b: legacy(limit: 5) name }", None).await;
+
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "data": { "a": 10, "b": 5, "name": "Alice" },
+                "extensions": {
+                    "warnings": [{
+                        "message": "`limit` will soon be required",
+                        "locations": [{ "line": 1, "column": 3 }],
+                    }],
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn synchronous_execution_warns() {
+        let schema = schema();
+        let output = crate::execute_sync("{ login }", None, &schema, &crate::Variables::new(), &())
+            .expect("Execution failed");
+
+        assert!(output.extensions.contains_field("warnings"));
+    }
+}
+
+mod deprecation_reports {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        deprecation::{DeprecationReport, DeprecationTracker},
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        Variables,
+    };
+
+    struct Context {
+        client_name: Option<String>,
+    }
+
+    impl crate::Context for Context {}
+
+    struct User;
+
+    #[crate::graphql_object(Context = Context)]
+    impl User {
+        fn name() -> &'static str {
+            "Ann"
+        }
+
+        #[deprecated(note = "Use `name`")]
+        fn login() -> &'static str {
+            "ann"
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(Context = Context)]
+    impl Query {
+        fn user() -> User {
+            User
+        }
+
+        #[deprecated]
+        fn me() -> User {
+            User
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    fn track() -> (Schema, Arc<Mutex<Vec<DeprecationReport>>>) {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let reported = reports.clone();
+        let tracker = DeprecationTracker::new(move |report: &DeprecationReport| {
+            reported.lock().unwrap().push(report.clone())
+        })
+        .client_identity(|context: &Context| context.client_name.clone());
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        )
+        .with_deprecation_tracker(tracker);
+        (schema, reports)
+    }
+
+    #[tokio::test]
+    async fn reports_operations_selecting_deprecated_fields() {
+        let (schema, reports) = track();
+        let vars = Variables::new();
+        let web = Context {
+            client_name: Some("web".into()),
+        };
+
+        crate::execute(
+            "query Profile { me { ...UserFields } }
+             fragment UserFields on User { name login }",
+            None,
+            &schema,
+            &vars,
+            &web,
+        )
+        .await
+        .unwrap();
+        crate::execute_sync(
+            "{ user { login } }",
+            None,
+            &schema,
+            &vars,
+            &Context { client_name: None },
+        )
+        .unwrap();
+        crate::execute_sync("{ user { name } }", None, &schema, &vars, &web).unwrap();
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![
+                DeprecationReport {
+                    operation_name: Some("Profile".into()),
+                    fields: vec!["Query.me".into(), "User.login".into()],
+                    client: Some("web".into()),
+                },
+                DeprecationReport {
+                    operation_name: None,
+                    fields: vec!["User.login".into()],
+                    client: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn serializes_reports() {
+        let report = DeprecationReport {
+            operation_name: Some("Profile".into()),
+            fields: vec!["Query.me".into()],
+            client: Some("web".into()),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "operationName": "Profile",
+                "fields": ["Query.me"],
+                "client": "web",
+            }),
+        );
+    }
+}
+
+mod response_order {
+    use crate::{
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{Object, OnDuplicateField, Value},
+        Variables,
+    };
+
+    struct Pet;
+
+    #[crate::graphql_object]
+    impl Pet {
+        fn name() -> &'static str {
+            "Rex"
+        }
+
+        fn age() -> i32 {
+            3
+        }
+
+        fn friends() -> Vec<Pet> {
+            vec![Pet, Pet]
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn pet() -> Pet {
+            Pet
+        }
+
+        fn count() -> i32 {
+            1
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    const DOC: &str = r#"
+        {
+            count
+            pet {
+                age
+                __typename
+                ... on Pet { friends { age } name }
+                friends { name __typename }
+                alias: name
+                ...PetFields
+            }
+            other: count
+        }
+
+        fragment PetFields on Pet {
+            age
+            friends { age alias: age }
+            last: __typename
+        }
+    "#;
+
+    fn keys(object: &Object<crate::DefaultScalarValue>) -> Vec<&str> {
+        object.iter().map(|(k, _)| k.as_str()).collect()
+    }
+
+    fn assert_selection_order(value: &Value) {
+        let data = value.as_object_value().unwrap();
+        assert_eq!(keys(data), ["count", "pet", "other"]);
+
+        let pet = data
+            .get_field_value("pet")
+            .unwrap()
+            .as_object_value()
+            .unwrap();
+        assert_eq!(
+            keys(pet),
+            ["age", "__typename", "friends", "name", "alias", "last"],
+        );
+
+        let friends = match pet.get_field_value("friends") {
+            Some(Value::List(friends)) => friends,
+            other => panic!("Unexpected friends: {:?}", other),
+        };
+        for friend in friends {
+            assert_eq!(
+                keys(friend.as_object_value().unwrap()),
+                ["age", "name", "__typename", "alias"],
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn async_execution_keeps_the_selection_order() {
+        let schema = schema();
+        let crate::ExecutionOutput {
+            data: value,
+            errors,
+            ..
+        } = crate::execute(DOC, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(errors, []);
+        assert_selection_order(&value);
+    }
+
+    #[test]
+    fn sync_execution_keeps_the_selection_order() {
+        let schema = schema();
+        let crate::ExecutionOutput {
+            data: value,
+            errors,
+            ..
+        } = crate::execute_sync(DOC, None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(errors, []);
+        assert_selection_order(&value);
+    }
+
+    #[test]
+    fn replaced_fields_keep_their_position() {
+        let mut object: Object<crate::DefaultScalarValue> = vec![
+            ("a", Value::scalar(1)),
+            ("b", Value::scalar(2)),
+            ("c", Value::scalar(3)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            object.add_field("a", Value::scalar(4)),
+            Some(Value::scalar(1))
+        );
+        assert_eq!(keys(&object), ["a", "b", "c"]);
+        assert_eq!(object.get_field_value("a"), Some(&Value::scalar(4)));
+    }
+
+    #[test]
+    fn duplicate_fields_follow_the_policy() {
+        fn object(fields: Vec<(&str, Value)>) -> Object<crate::DefaultScalarValue> {
+            fields.into_iter().collect()
+        }
+
+        let mut user = object(vec![
+            ("name", Value::scalar("Ann")),
+            (
+                "friends",
+                Value::list(vec![Value::object(object(vec![("id", Value::scalar(1))]))]),
+            ),
+        ]);
+
+        let replaced = user.insert_field("name", Value::scalar("Bob"), OnDuplicateField::Replace);
+        assert_eq!(replaced, Ok(Some(Value::scalar("Ann"))));
+
+        let merged = user.insert_field(
+            "friends",
+            Value::list(vec![Value::object(object(vec![
+                ("id", Value::scalar(2)),
+                ("name", Value::scalar("Cid")),
+            ]))]),
+            OnDuplicateField::Merge,
+        );
+        assert_eq!(merged, Ok(None));
+        assert_eq!(
+            user.get_field_value("friends"),
+            Some(&Value::list(vec![Value::object(object(vec![
+                ("id", Value::scalar(1)),
+                ("name", Value::scalar("Cid")),
+            ]))])),
+        );
+
+        let failed = user.insert_field("name", Value::scalar("Dan"), OnDuplicateField::Fail);
+        assert_eq!(
+            failed.unwrap_err().to_string(),
+            r#"Field "name" is already present"#,
+        );
+        assert_eq!(user.get_field_value("name"), Some(&Value::scalar("Bob")));
+
+        let added = user.insert_field("age", Value::scalar(3), OnDuplicateField::Fail);
+        assert_eq!(added, Ok(None));
+        assert_eq!(keys(&user), ["name", "friends", "age"]);
+    }
+}
+
+mod sync_async_parity {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+        FieldError, FieldResult, Variables,
+    };
+
+    #[derive(Default)]
+    struct Context {
+        pet_calls: AtomicUsize,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Pet;
+
+    #[crate::graphql_object]
+    impl Pet {
+        fn name() -> &'static str {
+            "Rex"
+        }
+
+        fn age() -> i32 {
+            3
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(context = Context)]
+    impl Query {
+        fn pet(context: &Context) -> FieldResult<Option<Pet>> {
+            match context.pet_calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Ok(Some(Pet)),
+                _ => Err(FieldError::from("Pet ran away")),
+            }
+        }
+
+        fn count(include: bool) -> Option<i32> {
+            if include {
+                Some(1)
+            } else {
+                None
+            }
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    #[tokio::test]
+    async fn sync_and_async_execution_agree() {
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let doc = r#"
+            query($skip: Boolean!) {
+                pet { name }
+                count(include: true) @skip(if: $skip)
+                ... on Query @include(if: true) { total: count(include: false) }
+                pet { age }
+            }"#;
+        let vars: Variables = vec![("skip".to_owned(), crate::InputValue::scalar(true))]
+            .into_iter()
+            .collect();
+
+        let sync_output =
+            crate::execute_sync(doc, None, &schema, &vars, &Context::default()).unwrap();
+        let async_output = crate::execute(doc, None, &schema, &vars, &Context::default())
+            .await
+            .unwrap();
+
+        assert_eq!(sync_output, async_output);
+        assert_eq!(
+            sync_output.data,
+            Value::object(
+                vec![
+                    (
+                        "pet",
+                        Value::object(vec![("name", Value::scalar("Rex"))].into_iter().collect()),
+                    ),
+                    ("total", Value::null()),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        );
+        assert_eq!(sync_output.errors.len(), 1);
+        assert_eq!(sync_output.errors[0].error().message(), "Pet ran away");
+    }
+}
+
+mod batch_execution {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use crate::{
+        ast::{Definition, Document},
+        http::{GraphQLBatchRequest, GraphQLBatchResponse, GraphQLRequest},
+        rewrite::QueryRewriter,
+        schema::model::{RootNode, SchemaType},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        DefaultScalarValue,
+    };
+
+    #[derive(Default)]
+    struct Context {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Query;
+
+    #[crate::graphql_object(context = Context)]
+    impl Query {
+        async fn wait(context: &Context) -> i32 {
+            let running = context.running.fetch_add(1, Ordering::SeqCst) + 1;
+            context.max_running.fetch_max(running, Ordering::SeqCst);
+            tokio::time::delay_for(Duration::from_millis(10)).await;
+            context.running.fetch_sub(1, Ordering::SeqCst);
+            1
+        }
+    }
+
+    /// Panics while rewriting the operations named `Boom`
+    struct Boom;
+
+    impl QueryRewriter for Boom {
+        fn rewrite(
+            &self,
+            document: &mut Document<DefaultScalarValue>,
+            _: &SchemaType<DefaultScalarValue>,
+        ) {
+            for definition in document.iter() {
+                if let Definition::Operation(op) = definition {
+                    if op.item.name.as_ref().map(|name| name.item) == Some("Boom") {
+                        panic!("Rewriter exploded");
+                    }
+                }
+            }
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    fn schema() -> Schema {
+        Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    fn batch(queries: &[&str]) -> GraphQLBatchRequest {
+        GraphQLBatchRequest::Batch(
+            queries
+                .iter()
+                .map(|query| GraphQLRequest::new(query.to_string(), None, None))
+                .collect(),
+        )
+    }
+
+    fn to_json(response: &GraphQLBatchResponse) -> serde_json::Value {
+        serde_json::to_value(response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn executes_the_operations_concurrently() {
+        let schema = schema();
+        let context = Context::default();
+
+        batch(&["{ wait }"; 4]).execute(&schema, &context).await;
+        assert_eq!(context.max_running.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn limits_the_concurrency() {
+        let schema = schema().with_batch_concurrency(2);
+        let context = Context::default();
+
+        let request = batch(&["{ wait }"; 5]);
+        let response = request.execute(&schema, &context).await;
+        assert_eq!(context.max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            to_json(&response),
+            serde_json::Value::Array(vec![serde_json::json!({ "data": { "wait": 1 } }); 5]),
+        );
+    }
+
+    #[tokio::test]
+    async fn isolates_failing_operations() {
+        let schema = schema().with_panic_catching().with_query_rewriter(Boom);
+        let context = Context::default();
+
+        let request = batch(&[
+            "{ wait }",
+            "query Boom { wait }",
+            "{ unknown }",
+            "{ w: wait }",
+        ]);
+        let response = request.execute(&schema, &context).await;
+
+        assert!(!response.is_ok());
+        let responses = to_json(&response);
+        assert_eq!(responses[0], serde_json::json!({ "data": { "wait": 1 } }));
+        assert_eq!(responses[1]["data"], serde_json::Value::Null);
+        assert_eq!(responses[1]["errors"][0]["message"], "Rewriter exploded");
+        assert!(responses[2]["errors"].is_array());
+        assert_eq!(responses[3], serde_json::json!({ "data": { "w": 1 } }));
+    }
+}
+
+mod field_scheduling {
+    use crate::{
+        executor::{Context, ContextCell},
+        schedule::ScheduledField,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        Variables,
+    };
+
+    #[derive(Default)]
+    struct Log {
+        started: ContextCell<Vec<String>>,
+    }
+
+    impl Context for Log {}
+
+    impl Log {
+        fn start(&self, name: &str) {
+            self.started.update(|s| s.push(name.into()));
+        }
+    }
+
+    struct User;
+
+    #[crate::graphql_object(Context = Log)]
+    impl User {
+        async fn name(context: &Log) -> &str {
+            context.start("name");
+            "Ann"
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object(Context = Log)]
+    impl Query {
+        async fn count(context: &Log) -> i32 {
+            context.start("count");
+            1
+        }
+
+        async fn user(context: &Log) -> User {
+            context.start("user");
+            User
+        }
+
+        async fn total(context: &Log) -> i32 {
+            context.start("total");
+            2
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Log>, EmptySubscription<Log>>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    async fn started(schema: &Schema) -> Vec<String> {
+        let context = Log::default();
+        let output = crate::execute(
+            "{ count user { name } total }",
+            None,
+            schema,
+            &Variables::new(),
+            &context,
+        )
+        .await
+        .expect("Execution failed");
+
+        assert_eq!(output.errors, []);
+        assert_eq!(
+            output.data,
+            graphql_value!({ "count": 1, "user": { "name": "Ann" }, "total": 2 }),
+        );
+        context.started.into_inner()
+    }
+
+    #[tokio::test]
+    async fn starts_fields_in_the_order_of_the_query() {
+        assert_eq!(
+            started(&schema()).await,
+            vec!["count", "user", "name", "total"],
+        );
+    }
+
+    #[tokio::test]
+    async fn starts_fields_by_priority() {
+        let schema = schema().with_field_scheduler(
+            |field: &ScheduledField| {
+                if field.is_leaf() {
+                    0
+                } else {
+                    1
+                }
+            },
+        );
+
+        assert_eq!(
+            started(&schema).await,
+            vec!["user", "name", "count", "total"]
+        );
+    }
+
+    #[tokio::test]
+    async fn schedules_fields_by_name_and_type() {
+        let schema = schema().with_field_scheduler(|field: &ScheduledField| {
+            match (field.type_name(), field.field_name()) {
+                ("Query", "total") => 2,
+                ("Query", "count") => 1,
+                _ => 0,
+            }
+        });
+
+        assert_eq!(
+            started(&schema).await,
+            vec!["total", "count", "user", "name"]
+        );
+    }
+}
+
+mod response_budget {
+    use crate::{
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        Variables,
+    };
+
+    struct User(i32);
+
+    #[crate::graphql_object]
+    impl User {
+        fn id(&self) -> Option<i32> {
+            Some(self.0)
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn users() -> Option<Vec<User>> {
+            Some((0..3).map(User).collect())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema(limit: usize) -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_max_response_values(limit)
+    }
+
+    const DOC: &str = "{ users { id } }";
+
+    #[tokio::test]
+    async fn resolves_responses_within_the_limit() {
+        let output = crate::execute(DOC, None, &schema(7), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(output.errors, []);
+        assert_eq!(
+            output.data,
+            graphql_value!({ "users": [{ "id": 0 }, { "id": 1 }, { "id": 2 }] }),
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_limit() {
+        let output = crate::execute(DOC, None, &schema(5), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(
+            output.data,
+            graphql_value!({ "users": [{ "id": 0 }, { "id": None }, { "id": None }] }),
+        );
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.errors[0].path(), &["users", "id"]);
+        assert_eq!(
+            output.errors[0].error().message(),
+            "Response exceeds the limit of 5 values",
+        );
+        assert_eq!(
+            output.errors[0].error().extensions(),
+            &graphql_value!({ "code": "RESPONSE_TOO_LARGE" }),
+        );
+    }
+
+    #[test]
+    fn stops_at_the_limit_synchronously() {
+        let output = crate::execute_sync(DOC, None, &schema(6), &Variables::new(), &())
+            .expect("Execution failed");
+
+        assert_eq!(
+            output.data,
+            graphql_value!({ "users": [{ "id": 0 }, { "id": 1 }, { "id": None }] }),
+        );
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.errors[0].path(), &["users", "id"]);
+    }
+
+    #[tokio::test]
+    async fn counts_the_items_of_lists() {
+        let output = crate::execute(DOC, None, &schema(3), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(output.data, graphql_value!({ "users": None }));
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.errors[0].path(), &["users"]);
+    }
+}
+
+mod required_pagination {
+    use crate::{
+        parser::SourcePosition,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        validation::RuleError,
+        GraphQLError, InputValue, Variables,
+    };
+
+    struct User(i32);
+
+    #[crate::graphql_object]
+    impl User {
+        fn id(&self) -> i32 {
+            self.0
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn users(first: Option<i32>, last: Option<i32>) -> Option<Vec<User>> {
+            let count = first.or(last).unwrap_or(3);
+            Some((0..count).map(User).collect())
+        }
+
+        #[graphql(arguments(first(default = 2)))]
+        fn recent_users(first: i32) -> Vec<User> {
+            (0..first).map(User).collect()
+        }
+
+        fn all_users() -> Vec<User> {
+            (0..3).map(User).collect()
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+        .with_required_pagination("Query", "users", 2)
+        .with_required_pagination("Query", "recentUsers", 2)
+    }
+
+    fn validation_errors(doc: &str) -> Vec<RuleError> {
+        match crate::execute_sync(doc, None, &schema(), &Variables::new(), &()) {
+            Err(GraphQLError::ValidationError(errors)) => errors,
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_paginated_selections() {
+        let output = crate::execute_sync(
+            "{ users(first: 2) { id } last: users(last: 1) { id } recentUsers { id } allUsers { id } }",
+            None,
+            &schema(),
+            &Variables::new(),
+            &(),
+        )
+        .expect("Execution failed");
+
+        assert_eq!(output.errors, []);
+        assert_eq!(
+            output.data,
+            graphql_value!({
+                "users": [{ "id": 0 }, { "id": 1 }],
+                "last": [{ "id": 0 }],
+                "recentUsers": [{ "id": 0 }, { "id": 1 }],
+                "allUsers": [{ "id": 0 }, { "id": 1 }, { "id": 2 }],
+            }),
+        );
+    }
+
+    #[test]
+    fn rejects_unpaginated_selections() {
+        assert_eq!(
+            validation_errors("{ users { id } }"),
+            vec![RuleError::new(
+                r#"Field "users" of type "Query" must be paginated with the "first" or "last" argument"#,
+                &[SourcePosition::new(2, 0, 2)],
+            )],
+        );
+        assert_eq!(
+            validation_errors("{ users(first: null) { id } }"),
+            vec![RuleError::new(
+                r#"Field "users" of type "Query" must be paginated with the "first" or "last" argument"#,
+                &[SourcePosition::new(2, 0, 2)],
+            )],
+        );
+    }
+
+    #[test]
+    fn rejects_pages_above_the_limit() {
+        assert_eq!(
+            validation_errors("{ users(last: 3) { id } }"),
+            vec![RuleError::new(
+                r#"Argument "last" of field "users" must be at most 2"#,
+                &[SourcePosition::new(2, 0, 2)],
+            )],
+        );
+        assert_eq!(
+            validation_errors("{ recentUsers(first: 3) { id } }"),
+            vec![RuleError::new(
+                r#"Argument "first" of field "recentUsers" must be at most 2"#,
+                &[SourcePosition::new(2, 0, 2)],
+            )],
+        );
+    }
+
+    #[test]
+    fn checks_variables_at_execution() {
+        let doc = "query Users($count: Int) { users(first: $count) { id } }";
+
+        let vars = vec![("count".to_owned(), InputValue::scalar(1))]
+            .into_iter()
+            .collect();
+        let output =
+            crate::execute_sync(doc, None, &schema(), &vars, &()).expect("Execution failed");
+        assert_eq!(output.errors, []);
+        assert_eq!(output.data, graphql_value!({ "users": [{ "id": 0 }] }));
+
+        let vars = vec![("count".to_owned(), InputValue::scalar(3))]
+            .into_iter()
+            .collect();
+        let output =
+            crate::execute_sync(doc, None, &schema(), &vars, &()).expect("Execution failed");
+        assert_eq!(output.data, graphql_value!({ "users": None }));
+        assert_eq!(
+            output.errors[0].error().message(),
+            r#"Argument "first" of field "users" must be at most 2"#,
+        );
+
+        let output = crate::execute_sync(doc, None, &schema(), &Variables::new(), &())
+            .expect("Execution failed");
+        assert_eq!(output.data, graphql_value!({ "users": None }));
+        assert_eq!(
+            output.errors[0].error().message(),
+            r#"Field "users" of type "Query" must be paginated with the "first" or "last" argument"#,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field Query.allUsers takes neither a `first` nor a `last` argument")]
+    fn requires_pagination_arguments() {
+        schema().with_required_pagination("Query", "allUsers", 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Field Query.unknown not found in the schema")]
+    fn requires_existing_fields() {
+        schema().with_required_pagination("Query", "unknown", 2);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+};
+
+trait Pet: Sync {
+    fn name(&self) -> &str;
+
+    fn as_dog(&self) -> Option<&Dog> {
+        None
+    }
+    fn as_cat(&self) -> Option<&Cat> {
+        None
+    }
+}
+
+graphql_interface!(<'a> &'a dyn Pet: () as "Pet" |&self| {
+    field name() -> &str { self.name() }
+
+    /// The name, shouted
+    #[graphql_interface(default_impl)]
+    field shout(times: Option<i32>) -> String {
+        let times = times.unwrap_or(1) as usize;
+        format!("{}{}", self.name().to_uppercase(), "!".repeat(times))
+    }
+
+    instance_resolvers: |&_| {
+        &Dog => self.as_dog(),
+        &Cat => self.as_cat(),
+    }
+});
+
+struct Dog {
+    name: String,
+}
+
+impl Pet for Dog {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn as_dog(&self) -> Option<&Dog> {
+        Some(self)
+    }
+}
+
+#[crate::graphql_object(
+    interfaces = [&dyn Pet],
+    noasync
+)]
+impl Dog {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+struct Cat {
+    name: String,
+}
+
+impl Pet for Cat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn as_cat(&self) -> Option<&Cat> {
+        Some(self)
+    }
+}
+
+#[crate::graphql_object(
+    interfaces = [&dyn Pet],
+    noasync
+)]
+impl Cat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn shout(&self, times: Option<i32>) -> String {
+        let times = times.unwrap_or(1) as usize;
+        format!("{}{}", self.name, "?".repeat(times))
+    }
+}
+
+struct Schema {
+    pets: Vec<Box<dyn Pet>>,
+}
+
+#[crate::graphql_object(noasync)]
+impl Schema {
+    fn pets(&self) -> Vec<&dyn Pet> {
+        self.pets.iter().map(|p| p.as_ref()).collect()
+    }
+
+    fn dog(&self) -> Dog {
+        Dog {
+            name: "Snoopy".to_owned(),
+        }
+    }
+}
+
+fn schema() -> RootNode<'static, Schema, EmptyMutation<()>, EmptySubscription<()>> {
+    RootNode::new(
+        Schema {
+            pets: vec![
+                Box::new(Dog {
+                    name: "Odie".to_owned(),
+                }),
+                Box::new(Cat {
+                    name: "Garfield".to_owned(),
+                }),
+            ],
+        },
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    )
+}
+
+const DOC: &str = r"
+  {
+    pets {
+      shout
+      ... on Dog { loud: shout(times: 3) }
+      ... on Cat { own: shout }
+    }
+    dog { shout }
+  }";
+
+fn expected() -> Value<DefaultScalarValue> {
+    graphql_value!({
+        "pets": [
+            { "shout": "ODIE!", "loud": "ODIE!!!" },
+            { "shout": "GARFIELD!", "own": "Garfield?" },
+        ],
+        "dog": { "shout": "SNOOPY!" },
+    })
+}
+
+#[test]
+fn implementers_inherit_the_field() {
+    let schema = schema();
+    let dog = schema.schema.concrete_type_by_name("Dog").unwrap();
+    let cat = schema.schema.concrete_type_by_name("Cat").unwrap();
+
+    assert!(dog.field_by_name("shout").unwrap().default_impl);
+    assert!(!cat.field_by_name("shout").unwrap().default_impl);
+}
+
+#[test]
+fn omitted_fields_are_resolved_by_the_interface() {
+    let vars = vec![].into_iter().collect();
+
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute_sync(DOC, None, &schema(), &vars, &()).expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(result, expected());
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::{
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+};
+
+struct Row(HashMap<&'static str, &'static str>);
+
+impl Row {
+    fn new(columns: &[(&'static str, &'static str)]) -> Self {
+        Self(columns.iter().cloned().collect())
+    }
+
+    fn to_dog(&self) -> Dog {
+        Dog {
+            name: self.0["name"].to_owned(),
+            woofs: self.0["sound"] == "woof",
+        }
+    }
+
+    fn to_cat(&self) -> Cat {
+        Cat {
+            name: self.0["name"].to_owned(),
+            meows: self.0["sound"] == "meow",
+        }
+    }
+}
+
+graphql_interface!(Row: () as "Pet" |&self| {
+    field name() -> &str { self.0["name"] }
+
+    instance_resolvers: |&_| {
+        resolve_type => self.0["kind"],
+        Dog => self.to_dog(),
+        Cat => self.to_cat(),
+    }
+});
+
+struct Dog {
+    name: String,
+    woofs: bool,
+}
+
+#[crate::graphql_object(
+    interfaces = [Row]
+)]
+impl Dog {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn woofs(&self) -> bool {
+        self.woofs
+    }
+}
+
+struct Cat {
+    name: String,
+    meows: bool,
+}
+
+#[crate::graphql_object(
+    interfaces = [Row]
+)]
+impl Cat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn meows(&self) -> bool {
+        self.meows
+    }
+}
+
+struct Schema {
+    pets: Vec<Row>,
+}
+
+#[crate::graphql_object(noasync)]
+impl Schema {
+    fn pets(&self) -> &[Row] {
+        &self.pets
+    }
+}
+
+#[test]
+fn resolves_the_named_type() {
+    let schema = RootNode::new(
+        Schema {
+            pets: vec![
+                Row::new(&[("kind", "Dog"), ("name", "Odie"), ("sound", "woof")]),
+                Row::new(&[("kind", "Cat"), ("name", "Garfield"), ("sound", "purr")]),
+            ],
+        },
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let doc = r"
+      {
+        pets {
+          __typename
+          name
+          ... on Dog {
+            woofs
+          }
+          ... on Cat {
+            meows
+          }
+        }
+      }";
+
+    let vars = vec![].into_iter().collect();
+
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute_sync(doc, None, &schema, &vars, &()).expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    assert_eq!(
+        result,
+        graphql_value!({
+            "pets": [
+                {"__typename": "Dog", "name": "Odie", "woofs": true},
+                {"__typename": "Cat", "name": "Garfield", "meows": false},
+            ],
+        }),
+    );
+}
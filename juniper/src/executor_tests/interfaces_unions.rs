@@ -1,5 +1,6 @@
 mod interface {
     use crate::{
+        executor::Executor,
         schema::model::RootNode,
         types::scalars::{EmptyMutation, EmptySubscription},
         value::Value,
@@ -86,6 +87,13 @@ mod interface {
         fn pets(&self) -> Vec<&dyn Pet> {
             self.pets.iter().map(|p| p.as_ref()).collect()
         }
+
+        fn pet_types(&self, executor: &Executor) -> Vec<String> {
+            self.pets
+                .iter()
+                .filter_map(|p| executor.type_name_of(&p.as_ref(), &()))
+                .collect()
+        }
     }
 
     #[tokio::test]
@@ -121,7 +129,11 @@ mod interface {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -158,6 +170,57 @@ mod interface {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_typename() {
+        let schema = RootNode::new(
+            Schema {
+                pets: vec![
+                    Box::new(Dog {
+                        name: "Odie".to_owned(),
+                        woofs: true,
+                    }),
+                    Box::new(Cat {
+                        name: "Garfield".to_owned(),
+                        meows: false,
+                    }),
+                ],
+            },
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let doc = r"
+          {
+            pets {
+              __typename
+              kind: __typename
+            }
+            petTypes
+          }";
+
+        let vars = vec![].into_iter().collect();
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            graphql_value!({
+                "pets": [
+                    {"__typename": "Dog", "kind": "Dog"},
+                    {"__typename": "Cat", "kind": "Cat"},
+                ],
+                "petTypes": ["Dog", "Cat"],
+            }),
+        );
+    }
 }
 
 mod union {
@@ -265,7 +328,11 @@ mod union {
 
         let vars = vec![].into_iter().collect();
 
-        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &vars, &())
             .await
             .expect("Execution failed");
 
@@ -122,7 +122,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
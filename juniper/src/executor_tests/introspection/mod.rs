@@ -89,7 +89,11 @@ async fn test_execution() {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -138,7 +142,11 @@ async fn enum_introspection() {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -252,7 +260,11 @@ async fn interface_introspection() {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -404,7 +416,11 @@ async fn object_introspection() {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -616,7 +632,11 @@ async fn scalar_introspection() {
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -649,3 +669,89 @@ async fn scalar_introspection() {
         )
     );
 }
+
+#[tokio::test]
+async fn default_values_introspection() {
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        #[graphql(arguments(
+            sample(default = Sample::Two),
+            samples(default = [Sample::One, Sample::Two]),
+            text(default = "say \"hi\""),
+        ))]
+        fn defaults(sample: Sample, samples: Vec<Sample>, text: String) -> i32 {
+            let _ = (sample, samples, text);
+            0
+        }
+    }
+
+    let doc = r#"
+    {
+        __type(name: "Query") {
+            fields {
+                args {
+                    name
+                    defaultValue
+                }
+            }
+        }
+    }
+    "#;
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    let arg = |name: &str, default_value: &str| {
+        Value::object(
+            vec![
+                ("name", Value::scalar(name)),
+                ("defaultValue", Value::scalar(default_value)),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    };
+    assert_eq!(
+        result,
+        Value::object(
+            vec![(
+                "__type",
+                Value::object(
+                    vec![(
+                        "fields",
+                        Value::list(vec![Value::object(
+                            vec![(
+                                "args",
+                                Value::list(vec![
+                                    arg("sample", "TWO"),
+                                    arg("samples", "[ONE, TWO]"),
+                                    arg("text", r#""say \"hi\"""#),
+                                ]),
+                            )]
+                            .into_iter()
+                            .collect(),
+                        )]),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )
+    );
+}
@@ -1,7 +1,13 @@
+mod auth;
 mod directives;
 mod enums;
 mod executor;
+mod interface_default_impl;
+mod interface_resolve_type;
 mod introspection;
+mod operations;
+mod rate_limit;
+mod spawn;
 mod variables;
 
 // FIXME: re-enable
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::{
+    cache::{CachePolicy, CacheScope},
+    executor::{ExecutionError, FieldError},
+    operations::{OperationPolicy, RateLimit},
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+    ExecutionMetadata, ExecutionOutput, GraphQLError,
+};
+
+struct Context {
+    scopes: Vec<&'static str>,
+}
+
+impl crate::Context for Context {}
+
+struct Query;
+
+#[crate::graphql_object(Context = Context)]
+impl Query {
+    #[graphql(cache_control(max_age = 60))]
+    fn stock() -> i32 {
+        42
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+fn schema() -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .with_operation_policy("PublicStock", OperationPolicy::default())
+    .with_operation_policy(
+        "PartnerStock",
+        OperationPolicy {
+            required_scopes: Some(vec!["stock:read".into()]),
+            rate_limit: Some(RateLimit {
+                requests: 10,
+                period: Duration::from_secs(60),
+            }),
+            cache_max_age: Some(30),
+        },
+    )
+}
+
+fn evaluated_schema() -> Schema {
+    schema().with_policy_evaluator(|context: &Context, required: &[String]| {
+        required
+            .iter()
+            .all(|s| context.scopes.contains(&s.as_str()))
+    })
+}
+
+fn run<'a>(
+    schema: &'a Schema,
+    query: &'a str,
+    scopes: Vec<&'static str>,
+) -> Result<(ExecutionOutput<DefaultScalarValue>, ExecutionMetadata), GraphQLError<'a>> {
+    crate::execute_sync_with_metadata(
+        query,
+        None,
+        schema,
+        &crate::Variables::new(),
+        &Context { scopes },
+    )
+}
+
+fn rejected(message: &str, code: &str) -> Vec<ExecutionError<DefaultScalarValue>> {
+    vec![ExecutionError::at_origin(FieldError::new(
+        message,
+        graphql_value!({ "code": code }),
+    ))]
+}
+
+#[test]
+fn only_registered_operations_are_executed() {
+    let schema = evaluated_schema();
+
+    let (output, _) = run(&schema, "query PublicStock { stock }", vec![]).unwrap();
+    assert_eq!(output.data, graphql_value!({ "stock": 42 }));
+    assert!(output.errors.is_empty());
+
+    for query in &["query Other { stock }", "{ stock }"] {
+        let (output, metadata) = run(&schema, query, vec![]).unwrap();
+        assert_eq!(output.data, Value::null());
+        assert_eq!(
+            output.errors,
+            rejected("Operation not allowed", "OPERATION_NOT_ALLOWED"),
+        );
+        assert_eq!(metadata.cache_policy, None);
+    }
+}
+
+#[test]
+fn operations_are_executed_with_the_required_scopes() {
+    let query = "query PartnerStock { stock }";
+
+    let evaluated = evaluated_schema();
+    let (output, _) = run(&evaluated, query, vec![]).unwrap();
+    assert_eq!(output.data, Value::null());
+    assert_eq!(output.errors, rejected("Not authorized", "FORBIDDEN"));
+
+    let (output, _) = run(&evaluated, query, vec!["stock:read"]).unwrap();
+    assert_eq!(output.data, graphql_value!({ "stock": 42 }));
+    assert!(output.errors.is_empty());
+
+    let (output, _) = run(&schema(), query, vec!["stock:read"]).unwrap();
+    assert_eq!(output.errors, rejected("Not authorized", "FORBIDDEN"));
+}
+
+#[test]
+fn cache_max_age_overrides_the_hints() {
+    let schema = evaluated_schema();
+
+    let (_, metadata) = run(&schema, "query PublicStock { stock }", vec![]).unwrap();
+    assert_eq!(
+        metadata.cache_policy,
+        Some(CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Public,
+        }),
+    );
+
+    let query = "query PartnerStock { stock }";
+    let (_, metadata) = run(&schema, query, vec!["stock:read"]).unwrap();
+    assert_eq!(
+        metadata.cache_policy,
+        Some(CachePolicy {
+            max_age: 30,
+            scope: CacheScope::Public,
+        }),
+    );
+}
+
+#[test]
+fn policies_are_looked_up_by_name() {
+    let schema = schema();
+
+    assert_eq!(
+        schema
+            .operation_policy("PartnerStock")
+            .and_then(|p| p.rate_limit),
+        Some(RateLimit {
+            requests: 10,
+            period: Duration::from_secs(60),
+        }),
+    );
+    assert_eq!(
+        schema.operation_policy("PublicStock"),
+        Some(&OperationPolicy::default()),
+    );
+    assert_eq!(schema.operation_policy("Other"), None);
+}
@@ -0,0 +1,233 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    executor::{get_operation, ExecutionError, FieldError},
+    parser::parse_document_source,
+    rate_limit::{operation_cost, BudgetStorage, CostBudget, RateLimiter},
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+    ExecutionOutput,
+};
+
+struct Context {
+    client: Option<&'static str>,
+}
+
+impl crate::Context for Context {}
+
+struct Item;
+
+#[crate::graphql_object(Context = Context)]
+impl Item {
+    fn name() -> &str {
+        "item"
+    }
+}
+
+struct Query;
+
+#[crate::graphql_object(Context = Context)]
+impl Query {
+    fn stock() -> i32 {
+        42
+    }
+
+    fn items(first: Option<i32>) -> Vec<Item> {
+        (0..first.unwrap_or(1)).map(|_| Item).collect()
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+fn schema(capacity: u32) -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .with_rate_limiter(RateLimiter::new(
+        CostBudget {
+            capacity,
+            period: Duration::from_secs(3600),
+        },
+        |context: &Context| context.client.map(Into::into),
+    ))
+}
+
+fn run(
+    schema: &Schema,
+    query: &str,
+    variables: crate::Variables,
+    client: Option<&'static str>,
+) -> ExecutionOutput<DefaultScalarValue> {
+    crate::execute_sync(query, None, schema, &variables, &Context { client }).unwrap()
+}
+
+fn rate_limited(extensions: Value<DefaultScalarValue>) -> Vec<ExecutionError<DefaultScalarValue>> {
+    vec![ExecutionError::at_origin(FieldError::new(
+        "Rate limited",
+        extensions,
+    ))]
+}
+
+/// The cost of the `query`, as reported by a limiter without budget
+fn cost(query: &str, variables: crate::Variables) -> Value<DefaultScalarValue> {
+    let output = run(&schema(0), query, variables, Some("client"));
+    output.errors[0]
+        .error()
+        .extensions()
+        .as_object_value()
+        .and_then(|extensions| extensions.get_field_value("cost"))
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn operations_are_charged_until_the_budget_is_spent() {
+    let schema = schema(5);
+    let query = "{ items(first: 3) { name } }";
+
+    let output = run(&schema, query, crate::Variables::new(), Some("a"));
+    assert!(output.errors.is_empty());
+    assert_eq!(
+        output.data,
+        graphql_value!({ "items": [{ "name": "item" }, { "name": "item" }, { "name": "item" }] }),
+    );
+
+    let output = run(&schema, query, crate::Variables::new(), Some("a"));
+    assert_eq!(output.data, Value::null());
+    assert_eq!(
+        output.errors,
+        rate_limited(graphql_value!({ "code": "RATE_LIMITED", "cost": 4, "retryAfter": 2160 })),
+    );
+
+    let output = run(&schema, "{ stock }", crate::Variables::new(), Some("a"));
+    assert!(output.errors.is_empty());
+}
+
+#[test]
+fn clients_have_their_own_budget() {
+    let schema = schema(1);
+
+    for client in &[Some("a"), Some("b"), None, None] {
+        let output = run(&schema, "{ stock }", crate::Variables::new(), *client);
+        assert!(output.errors.is_empty());
+    }
+
+    let output = run(&schema, "{ stock }", crate::Variables::new(), Some("b"));
+    assert_eq!(output.data, Value::null());
+    assert_eq!(
+        output.errors,
+        rate_limited(graphql_value!({ "code": "RATE_LIMITED", "cost": 1, "retryAfter": 3600 })),
+    );
+}
+
+#[test]
+fn operations_exceeding_the_capacity_are_never_executed() {
+    let schema = schema(3);
+
+    let output = run(
+        &schema,
+        "{ items(first: 3) { name } }",
+        crate::Variables::new(),
+        Some("a"),
+    );
+    assert_eq!(output.data, Value::null());
+    assert_eq!(
+        output.errors,
+        rate_limited(graphql_value!({ "code": "RATE_LIMITED", "cost": 4 })),
+    );
+}
+
+#[test]
+fn cost_counts_the_selected_fields() {
+    assert_eq!(
+        cost("{ stock __typename }", crate::Variables::new()),
+        Value::scalar(1)
+    );
+    assert_eq!(
+        cost("{ stock items { name } }", crate::Variables::new()),
+        Value::scalar(3),
+    );
+    assert_eq!(
+        cost(
+            "{ ...Items ... on Query { stock } } fragment Items on Query { items(first: 10) { name __typename } }",
+            crate::Variables::new(),
+        ),
+        Value::scalar(12),
+    );
+
+    let mut variables = crate::Variables::new();
+    variables.insert("first".into(), crate::InputValue::scalar(5));
+    assert_eq!(
+        cost(
+            "query Items($first: Int) { items(first: $first) { name } }",
+            variables,
+        ),
+        Value::scalar(6),
+    );
+}
+
+#[test]
+fn fragments_spread_several_times_are_walked_once() {
+    let depth = 64;
+    let mut query = "{ ...F0 }".to_owned();
+    for i in 0..depth {
+        query += &format!(
+            " fragment F{} on Query {{ ...F{} ...F{} }}",
+            i,
+            i + 1,
+            i + 1
+        );
+    }
+    query += &format!(" fragment F{} on Query {{ stock }}", depth);
+
+    let schema = schema(0);
+    let document = parse_document_source(&query, &schema.schema).unwrap();
+    let operation = get_operation(&document, None).unwrap();
+
+    assert_eq!(
+        operation_cost(&document, operation, &crate::Variables::new()),
+        u32::MAX,
+    );
+}
+
+#[derive(Default)]
+struct RecordingStorage(Arc<Mutex<Vec<(String, u32)>>>);
+
+impl BudgetStorage for RecordingStorage {
+    fn charge(&self, client: &str, cost: u32, _: &CostBudget) -> Result<(), Duration> {
+        self.0.lock().unwrap().push((client.into(), cost));
+        Err(Duration::from_millis(1500))
+    }
+}
+
+#[test]
+fn budgets_are_kept_in_the_storage() {
+    let storage = RecordingStorage::default();
+    let charges = storage.0.clone();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .with_rate_limiter(RateLimiter::with_storage(
+        CostBudget {
+            capacity: 10,
+            period: Duration::from_secs(60),
+        },
+        |context: &Context| context.client.map(Into::into),
+        storage,
+    ));
+
+    let output = run(&schema, "{ stock }", crate::Variables::new(), Some("a"));
+    assert_eq!(
+        output.errors,
+        rate_limited(graphql_value!({ "code": "RATE_LIMITED", "cost": 1, "retryAfter": 2 })),
+    );
+    assert_eq!(*charges.lock().unwrap(), vec![("a".to_owned(), 1)]);
+}
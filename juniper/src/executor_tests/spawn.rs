@@ -0,0 +1,111 @@
+use futures::task::{FutureObj, Spawn, SpawnError};
+
+use crate::{
+    executor::{FieldResult, FieldScope},
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+};
+
+/// Spawns the tasks on the Tokio runtime of the test
+struct TokioSpawner;
+
+impl Spawn for TokioSpawner {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        tokio::spawn(future);
+        Ok(())
+    }
+}
+
+#[derive(crate::GraphQLObject)]
+struct Scope {
+    path: Vec<String>,
+    line: i32,
+    column: i32,
+    type_name: Option<String>,
+    selected_fields: Vec<String>,
+}
+
+impl Scope {
+    fn current() -> Option<Self> {
+        FieldScope::current().map(|scope| Scope {
+            path: scope.path().to_vec(),
+            line: scope.location().line() as i32,
+            column: scope.location().column() as i32,
+            type_name: scope.type_name().map(Into::into),
+            selected_fields: scope.selected_fields().to_vec(),
+        })
+    }
+}
+
+struct Query;
+
+#[crate::graphql_object]
+impl Query {
+    async fn in_scope(executor: &Executor) -> Option<Scope> {
+        executor.in_scope(async { Scope::current() }).await
+    }
+
+    async fn spawned(executor: &Executor) -> FieldResult<Option<Scope>> {
+        let scope = executor.spawn(&TokioSpawner, async { Scope::current() })?;
+        Ok(scope.await?)
+    }
+
+    fn outside() -> Option<Scope> {
+        Scope::current()
+    }
+}
+
+async fn run(query: &str) -> Value<DefaultScalarValue> {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let output = crate::execute(query, None, &schema, &crate::Variables::new(), &())
+        .await
+        .unwrap();
+    assert!(output.errors.is_empty(), "{:?}", output.errors);
+    output.data
+}
+
+#[tokio::test]
+async fn futures_are_polled_in_the_scope_of_the_field() {
+    let data = run("{ scope: inScope { path ...Location typeName } }
+         fragment Location on Scope { line column selectedFields }")
+    .await;
+
+    assert_eq!(
+        data,
+        graphql_value!({
+            "scope": {
+                "path": ["scope"],
+                "typeName": "Scope",
+                "line": 0,
+                "column": 2,
+                "selectedFields": ["path", "line", "column", "selectedFields", "typeName"],
+            },
+        }),
+    );
+}
+
+#[tokio::test]
+async fn spawned_tasks_are_polled_in_the_scope_of_the_field() {
+    let data = run("{ spawned { path typeName } }").await;
+
+    assert_eq!(
+        data,
+        graphql_value!({ "spawned": { "path": ["spawned"], "typeName": "Scope" } }),
+    );
+}
+
+#[tokio::test]
+async fn there_is_no_scope_outside_of_the_futures() {
+    let data = run("{ inScope { path } outside { path } }").await;
+
+    assert_eq!(
+        data,
+        graphql_value!({ "inScope": { "path": ["inScope"] }, "outside": None }),
+    );
+    assert_eq!(FieldScope::current(), None);
+}
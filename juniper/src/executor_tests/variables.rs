@@ -1,3 +1,5 @@
+use std::num::{NonZeroI32, NonZeroU32};
+
 use crate::{
     ast::InputValue,
     executor::Variables,
@@ -7,7 +9,7 @@ use crate::{
     validation::RuleError,
     value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, Value},
     GraphQLError::ValidationError,
-    GraphQLInputObject,
+    GraphQLInputObject, Nullable,
 };
 
 #[derive(Debug)]
@@ -58,6 +60,12 @@ struct ExampleInputObject {
     b: i32,
 }
 
+#[derive(GraphQLInputObject, Debug)]
+struct PatchInput {
+    name: Nullable<String>,
+    age: Nullable<i32>,
+}
+
 #[derive(GraphQLInputObject, Debug)]
 struct InputWithDefaults {
     #[graphql(default = "123")]
@@ -124,6 +132,22 @@ impl TestType {
     fn float_input(value: f64) -> String {
         format!("value: {}", value)
     }
+
+    fn non_zero_input(value: NonZeroI32) -> String {
+        format!("value: {}", value)
+    }
+
+    fn positive_input(value: NonZeroU32) -> NonZeroU32 {
+        value
+    }
+
+    fn nullable_input(value: Nullable<i32>) -> String {
+        format!("{:?}", value)
+    }
+
+    fn patch_input(patch: PatchInput) -> String {
+        format!("{:?}", patch)
+    }
 }
 
 async fn run_variable_query<F>(query: &str, vars: Variables<DefaultScalarValue>, f: F)
@@ -136,7 +160,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(query, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(query, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -318,7 +346,7 @@ async fn variable_error_on_nested_non_null() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
+            r#"Variable "$input" got invalid value at "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )])
     );
@@ -380,7 +408,7 @@ async fn variable_error_on_omit_non_null() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
+            r#"Variable "$input" got invalid value at "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )])
     );
@@ -418,17 +446,70 @@ async fn variable_multiple_errors_with_nesting() {
         error,
         ValidationError(vec![
             RuleError::new(
-                r#"Variable "$input" got invalid value. In field "na": In field "c": Expected "String!", found null."#,
+                r#"Variable "$input" got invalid value at "na.c": Expected "String!", found null."#,
                 &[SourcePosition::new(8, 0, 8)],
             ),
             RuleError::new(
-                r#"Variable "$input" got invalid value. In field "nb": Expected "String!", found null."#,
+                r#"Variable "$input" got invalid value at "nb": Expected "String!", found null."#,
                 &[SourcePosition::new(8, 0, 8)],
             ),
         ])
     );
 }
 
+#[tokio::test]
+async fn variable_error_reports_nested_list_path() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query =
+        r#"query q($input: TestNestedInputObject) { fieldWithNestedObjectInput(input: $input) }"#;
+    let vars = vec![(
+        "input".to_owned(),
+        InputValue::object(
+            vec![
+                (
+                    "na",
+                    InputValue::object(
+                        vec![
+                            (
+                                "b",
+                                InputValue::list(vec![
+                                    InputValue::scalar("foo"),
+                                    InputValue::scalar(1),
+                                ]),
+                            ),
+                            ("c", InputValue::scalar("bar")),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+                ("nb", InputValue::scalar("baz")),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Variable "$input" got invalid value at "na.b[1]": Expected "String"."#,
+            &[SourcePosition::new(8, 0, 8)],
+        )])
+    );
+}
+
 #[tokio::test]
 async fn variable_error_on_additional_field() {
     let schema = RootNode::new(
@@ -461,7 +542,7 @@ async fn variable_error_on_additional_field() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$input" got invalid value. In field "extra": Unknown field."#,
+            r#"Variable "$input" got invalid value at "extra": Unknown field."#,
             &[SourcePosition::new(8, 0, 8)],
         )])
     );
@@ -832,7 +913,7 @@ async fn does_not_allow_lists_of_non_null_to_contain_null() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
+            r#"Variable "$input" got invalid value at "[1]": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         ),])
     );
@@ -865,7 +946,7 @@ async fn does_not_allow_non_null_lists_of_non_null_to_contain_null() {
     assert_eq!(
         error,
         ValidationError(vec![RuleError::new(
-            r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
+            r#"Variable "$input" got invalid value at "[1]": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         ),])
     );
@@ -1320,3 +1401,168 @@ mod floats {
         );
     }
 }
+
+mod non_zero_integers {
+    use super::*;
+
+    #[tokio::test]
+    async fn non_zero_values_should_work() {
+        run_variable_query(
+            r#"query q($var: NonZeroInt!) { nonZeroInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar(-1))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("nonZeroInput"),
+                    Some(&Value::scalar(r#"value: -1"#))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn does_not_accept_zero() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: NonZeroInt!) { nonZeroInput(value: $var) }"#;
+        let vars = vec![("var".to_owned(), InputValue::scalar(0))]
+            .into_iter()
+            .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Variable "$var" got invalid value. Expected "NonZeroInt": must not be zero."#,
+                &[SourcePosition::new(8, 0, 8)],
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn positive_values_above_int_range_use_floats() {
+        run_variable_query(
+            r#"query q($var: PositiveInt!) { small: positiveInput(value: 1), big: positiveInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar(4_000_000_000.0))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(result.get_field_value("small"), Some(&Value::scalar(1)));
+                assert_eq!(
+                    result.get_field_value("big"),
+                    Some(&Value::scalar(4_000_000_000.0))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn does_not_accept_negative_literals() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"{ positiveInput(value: -1) }"#;
+        let vars = vec![].into_iter().collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Invalid value for argument "value", expected type "PositiveInt!""#,
+                &[SourcePosition::new(23, 0, 23)],
+            )])
+        );
+    }
+}
+
+mod nullable {
+    use super::*;
+
+    #[tokio::test]
+    async fn tells_absent_arguments_from_null_ones() {
+        run_query(
+            r#"{ absent: nullableInput, null: nullableInput(value: null), some: nullableInput(value: 1) }"#,
+            |result| {
+                assert_eq!(
+                    result.get_field_value("absent"),
+                    Some(&Value::scalar("Absent"))
+                );
+                assert_eq!(result.get_field_value("null"), Some(&Value::scalar("Null")));
+                assert_eq!(
+                    result.get_field_value("some"),
+                    Some(&Value::scalar("Some(1)"))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn treats_undefined_variables_as_absent() {
+        run_variable_query(
+            r#"query q($undefined: Int, $null: Int, $name: String) {
+                undefined: nullableInput(value: $undefined)
+                null: nullableInput(value: $null)
+                patch: patchInput(patch: { name: $name, age: $null })
+            }"#,
+            vec![("null".to_owned(), InputValue::null())]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("undefined"),
+                    Some(&Value::scalar("Absent"))
+                );
+                assert_eq!(result.get_field_value("null"), Some(&Value::scalar("Null")));
+                assert_eq!(
+                    result.get_field_value("patch"),
+                    Some(&Value::scalar("PatchInput { name: Absent, age: Null }"))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn tells_omitted_input_fields_from_null_ones() {
+        run_variable_query(
+            r#"query q($patch: PatchInput!) {
+                literal: patchInput(patch: { name: null })
+                variable: patchInput(patch: $patch)
+            }"#,
+            vec![(
+                "patch".to_owned(),
+                InputValue::object(vec![("age", InputValue::scalar(3))].into_iter().collect()),
+            )]
+            .into_iter()
+            .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("literal"),
+                    Some(&Value::scalar("PatchInput { name: Null, age: Absent }"))
+                );
+                assert_eq!(
+                    result.get_field_value("variable"),
+                    Some(&Value::scalar("PatchInput { name: Absent, age: Some(3) }"))
+                );
+            },
+        )
+        .await;
+    }
+}
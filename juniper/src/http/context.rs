@@ -0,0 +1,128 @@
+//! Building the context of GraphQL requests from HTTP requests, shared by all
+//! the integrations
+
+use std::future::Future;
+
+use crate::{http::HttpError, BoxFuture};
+
+/// The parts of an HTTP request available when building the context of a
+/// GraphQL request
+///
+/// Each integration converts the requests of its framework into these, so a
+/// [`ContextFactory`](trait.ContextFactory.html) works with all of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestParts {
+    /// The request method, e.g. `"POST"`
+    pub method: String,
+    /// The path of the request URI
+    pub path: String,
+    /// The query string of the request URI, if any
+    pub query: Option<String>,
+    /// The request headers, in the order they were received
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestParts {
+    /// Returns the value of the first header with the provided name, compared
+    /// case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The media type of the `Content-Type` header, empty if it's missing
+    pub(crate) fn media_type(&self) -> MediaType<'_> {
+        MediaType::parse(self.header("Content-Type").unwrap_or(""))
+    }
+}
+
+/// The media type of a `Content-Type` header, with its parameters
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MediaType<'a> {
+    essence: &'a str,
+    #[cfg(feature = "http-strict")]
+    params: &'a str,
+}
+
+impl<'a> MediaType<'a> {
+    fn parse(content_type: &'a str) -> Self {
+        let mut split = content_type.splitn(2, ';');
+        Self {
+            essence: split.next().unwrap_or("").trim(),
+            #[cfg(feature = "http-strict")]
+            params: split.next().unwrap_or(""),
+        }
+    }
+
+    /// Whether the header is missing or has no media type
+    pub(crate) fn is_empty(&self) -> bool {
+        self.essence.is_empty()
+    }
+
+    /// Whether it's the provided media type, compared case-insensitively
+    pub(crate) fn is(&self, media_type: &str) -> bool {
+        self.essence.eq_ignore_ascii_case(media_type)
+    }
+
+    /// The value of the first parameter with the provided name, compared
+    /// case-insensitively, without its quotes
+    #[cfg(feature = "http-strict")]
+    pub(crate) fn param(&self, name: &str) -> Option<&'a str> {
+        self.params.split(';').find_map(|param| {
+            let mut key_value = param.splitn(2, '=');
+            match (key_value.next(), key_value.next()) {
+                (Some(key), Some(value)) if key.trim().eq_ignore_ascii_case(name) => {
+                    Some(value.trim().trim_matches('"'))
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Builds the context of each GraphQL request from its HTTP request
+///
+/// Per-request setup, like authenticating the user, is written once and used
+/// by all the integrations. Closures taking `RequestParts` and returning a
+/// future implement it too:
+///
+/// ```
+/// # use juniper::http::{HttpError, ContextFactory, RequestParts};
+/// struct Context {
+///     user: String,
+/// }
+///
+/// let factory = |parts: RequestParts| async move {
+///     match parts.header("Authorization") {
+///         Some(user) => Ok(Context { user: user.into() }),
+///         None => Err(HttpError::unauthorized("Missing credentials")),
+///     }
+/// };
+/// # fn assert_factory<F: ContextFactory<Context = Context>>(_: &F) {}
+/// # assert_factory(&factory);
+/// ```
+pub trait ContextFactory: Send + Sync {
+    /// The context of the GraphQL requests
+    type Context;
+
+    /// Builds the context of the GraphQL request sent with the provided HTTP
+    /// request
+    fn build<'a>(
+        &'a self,
+        parts: &'a RequestParts,
+    ) -> BoxFuture<'a, Result<Self::Context, HttpError>>;
+}
+
+impl<F, Fut, C> ContextFactory for F
+where
+    F: Fn(RequestParts) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<C, HttpError>> + Send + 'static,
+{
+    type Context = C;
+
+    fn build<'a>(&'a self, parts: &'a RequestParts) -> BoxFuture<'a, Result<C, HttpError>> {
+        Box::pin(self(parts.clone()))
+    }
+}
@@ -0,0 +1,201 @@
+//! Rejecting the requests browsers send across sites without asking the
+//! server first, shared by all the integrations
+
+use crate::http::{HttpError, MediaType, RequestParts};
+
+/// Content types of the requests browsers send across sites without a CORS
+/// preflight request
+const SIMPLE_CONTENT_TYPES: [&str; 3] = [
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+/// Protection against cross-site request forgery (CSRF)
+///
+/// A page of another site can make a browser send `GET` requests, and
+/// `POST` requests with a form content type, with the cookies of the user,
+/// without a CORS preflight request, so the server can't refuse them before
+/// a query, or even a mutation, is executed. The protection only accepts the
+/// requests which browsers preflight: the ones with a header among the
+/// [required ones](#method.headers), or with a content type other than
+/// `application/x-www-form-urlencoded`, `multipart/form-data` and
+/// `text/plain`, like `application/json`. Other clients just have to send one
+/// of the headers with their `GET` requests.
+///
+/// Integrations call [`check`](#method.check) before parsing the requests:
+///
+/// ```
+/// # use juniper::http::{CsrfProtection, RequestParts};
+/// let csrf = CsrfProtection::new();
+///
+/// let form = RequestParts {
+///     method: "POST".into(),
+///     headers: vec![("Content-Type".into(), "text/plain".into())],
+///     ..RequestParts::default()
+/// };
+/// assert_eq!(csrf.check(&form).unwrap_err().status(), 400);
+///
+/// let json = RequestParts {
+///     headers: vec![("Content-Type".into(), "application/json".into())],
+///     ..form
+/// };
+/// assert!(csrf.check(&json).is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrfProtection {
+    headers: Vec<String>,
+}
+
+impl CsrfProtection {
+    /// Constructs a protection requiring one of the `X-Requested-With`,
+    /// `Apollo-Require-Preflight` or `X-Apollo-Operation-Name` headers from
+    /// the requests with a simple content type, or none
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the headers of which one must be sent with the requests with
+    /// a simple content type, or none
+    ///
+    /// Their names are compared case-insensitively, and they must not be
+    /// empty.
+    pub fn headers<I, H>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+        H: Into<String>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Accepts the request if browsers only send it after a CORS preflight
+    /// request
+    ///
+    /// The rejected requests are answered with `400 Bad Request`.
+    pub fn check(&self, parts: &RequestParts) -> Result<(), HttpError> {
+        let has_header = self
+            .headers
+            .iter()
+            .any(|name| matches!(parts.header(name), Some(v) if !v.trim().is_empty()));
+        let is_simple = is_simple_media_type(parts.media_type());
+
+        if has_header || !is_simple {
+            Ok(())
+        } else {
+            Err(HttpError::bad_request(format!(
+                "This request requires a non-empty `{}` header, or a content type other than `{}`",
+                self.headers.join("` or `"),
+                SIMPLE_CONTENT_TYPES.join("`, `"),
+            )))
+        }
+    }
+}
+
+impl Default for CsrfProtection {
+    fn default() -> Self {
+        Self {
+            headers: vec![
+                "X-Requested-With".into(),
+                "Apollo-Require-Preflight".into(),
+                "X-Apollo-Operation-Name".into(),
+            ],
+        }
+    }
+}
+
+/// Whether browsers send requests with the media type without a CORS
+/// preflight request, missing and empty ones included
+fn is_simple_media_type(media_type: MediaType) -> bool {
+    media_type.is_empty()
+        || SIMPLE_CONTENT_TYPES
+            .iter()
+            .any(|simple| media_type.is(simple))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CsrfProtection;
+    use crate::http::RequestParts;
+
+    fn request(method: &str, headers: &[(&str, &str)]) -> RequestParts {
+        RequestParts {
+            method: method.into(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| ((*name).into(), (*value).into()))
+                .collect(),
+            ..RequestParts::default()
+        }
+    }
+
+    #[test]
+    fn simple_requests_are_rejected() {
+        let csrf = CsrfProtection::new();
+
+        for headers in &[
+            &[][..],
+            &[("Content-Type", "")],
+            &[("Content-Type", "text/plain")],
+            &[("Content-Type", "Text/Plain; charset=utf-8")],
+            &[("Content-Type", "application/x-www-form-urlencoded")],
+            &[("Content-Type", "multipart/form-data; boundary=x")],
+            &[("X-Requested-With", "")],
+        ] {
+            assert!(
+                csrf.check(&request("GET", headers)).is_err(),
+                "{:?}",
+                headers
+            );
+            assert!(
+                csrf.check(&request("POST", headers)).is_err(),
+                "{:?}",
+                headers
+            );
+        }
+    }
+
+    #[test]
+    fn preflighted_requests_are_accepted() {
+        let csrf = CsrfProtection::new();
+
+        for headers in &[
+            &[("Content-Type", "application/json")][..],
+            &[("Content-Type", "application/graphql")],
+            &[("x-requested-with", "XMLHttpRequest")],
+            &[("Apollo-Require-Preflight", "true")],
+            &[
+                ("Content-Type", "text/plain"),
+                ("X-Apollo-Operation-Name", "Hello"),
+            ],
+        ] {
+            assert!(
+                csrf.check(&request("GET", headers)).is_ok(),
+                "{:?}",
+                headers
+            );
+            assert!(
+                csrf.check(&request("POST", headers)).is_ok(),
+                "{:?}",
+                headers
+            );
+        }
+    }
+
+    #[test]
+    fn the_required_headers_can_be_replaced() {
+        let csrf = CsrfProtection::new().headers(vec!["X-CSRF-Token"]);
+
+        assert!(csrf
+            .check(&request("GET", &[("X-CSRF-Token", "1")]))
+            .is_ok());
+        assert!(csrf
+            .check(&request("GET", &[("X-Requested-With", "XMLHttpRequest")]))
+            .is_err());
+        assert_eq!(
+            csrf.check(&request("GET", &[])).unwrap_err().message(),
+            "This request requires a non-empty `X-CSRF-Token` header, or a content type other \
+             than `application/x-www-form-urlencoded`, `multipart/form-data`, `text/plain`",
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Formatting the errors of GraphQL responses before they're sent to clients
+
+use crate::{
+    executor::{ExecutionError, FieldError},
+    value::DefaultScalarValue,
+};
+
+/// Rewrites the errors raised while executing a request before they're
+/// serialized into its response
+///
+/// Used with [`GraphQLResponse::format_errors`], it keeps internal details,
+/// like database errors, from leaking to clients, maps error codes to
+/// messages, or attaches correlation IDs to find the errors in the logs.
+/// Closures taking an `&ExecutionError` implement it too:
+///
+/// ```
+/// # use juniper::{
+/// #     graphql_value, http::ErrorFormatter, DefaultScalarValue, ExecutionError, FieldError,
+/// # };
+/// let formatter = |error: &ExecutionError<DefaultScalarValue>| {
+///     // Logged with the correlation ID of the request, e.g. taken from a header
+///     eprintln!("4f2e: {} at {:?}", error.error().message(), error.path());
+///
+///     let code = error
+///         .error()
+///         .extensions()
+///         .as_object_value()
+///         .and_then(|e| e.get_field_value("code"))
+///         .and_then(|code| code.as_string_value());
+///     match code {
+///         Some("NOT_FOUND") => FieldError::new("Not found", graphql_value!({ "code": "NOT_FOUND" })),
+///         _ => FieldError::new("Internal error", graphql_value!({ "correlationId": "4f2e" })),
+///     }
+/// };
+/// # fn assert_formatter<F: ErrorFormatter>(_: &F) {}
+/// # assert_formatter(&formatter);
+/// ```
+///
+/// Only the errors raised by resolvers are formatted. Parse and validation
+/// errors only describe the document sent by the client.
+///
+/// [`GraphQLResponse::format_errors`]: struct.GraphQLResponse.html#method.format_errors
+pub trait ErrorFormatter<S = DefaultScalarValue>: Send + Sync {
+    /// Returns the error sent to the client instead of the provided one, at
+    /// the same location and path
+    fn format_error(&self, error: &ExecutionError<S>) -> FieldError<S>;
+}
+
+impl<F, S> ErrorFormatter<S> for F
+where
+    F: Fn(&ExecutionError<S>) -> FieldError<S> + Send + Sync,
+{
+    fn format_error(&self, error: &ExecutionError<S>) -> FieldError<S> {
+        self(error)
+    }
+}
@@ -0,0 +1,444 @@
+//! Limiting the size of requests before they're parsed, shared by all the
+//! integrations
+
+use std::{convert::TryFrom as _, fmt, io};
+
+use crate::{
+    ast::InputValue,
+    executor::FieldError,
+    http::{GraphQLBatchRequest, GraphQLRequest, HttpError, RequestParts},
+    value::{Object, ScalarValue, Value},
+};
+
+/// Limits of the size of requests, in bytes
+///
+/// Integrations check each request in steps, failing as early as possible:
+///
+/// 1. [`check_parts`](#method.check_parts) before reading the body, with its
+///    `Content-Length` header, or the query string of a `GET` request;
+/// 2. [`check_body`](#method.check_body) once the body is read, or while it's
+///    read for bodies without a `Content-Length` header, before it's
+///    deserialized;
+/// 3. [`check_request`](#method.check_request) once it's deserialized,
+///    from the body or from the query string, before the documents are
+///    parsed.
+///
+/// ```
+/// # use juniper::http::{
+/// #     GraphQLBatchRequest, GraphQLRequest, HttpError, Limit, RequestLimits, RequestParts,
+/// # };
+/// let limits = RequestLimits::new()
+///     .max_body_bytes(64 * 1024)
+///     .max_query_bytes(8 * 1024)
+///     .max_variables_bytes(16 * 1024);
+///
+/// let parts = RequestParts {
+///     method: "POST".into(),
+///     headers: vec![("Content-Length".into(), "1048576".into())],
+///     ..RequestParts::default()
+/// };
+/// let error = limits.check_parts(&parts).unwrap_err();
+/// assert_eq!(error.limit(), Limit::Body);
+/// assert_eq!(HttpError::from(error).status(), 413);
+///
+/// let query = "{ hello }".repeat(1000);
+/// let request = GraphQLBatchRequest::Single(GraphQLRequest::<juniper::DefaultScalarValue>::new(
+///     query, None, None,
+/// ));
+/// assert_eq!(limits.check_request(&request).unwrap_err().limit(), Limit::Query);
+/// ```
+///
+/// A request with the `multipart/form-data` content type, like the uploads of
+/// the [GraphQL multipart request](https://github.com/jaydenseric/graphql-multipart-request-spec)
+/// specification, has the upload limit instead of the body limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RequestLimits {
+    max_body_bytes: Option<usize>,
+    max_query_bytes: Option<usize>,
+    max_variables_bytes: Option<usize>,
+    max_upload_bytes: Option<usize>,
+}
+
+/// A limit of the [`RequestLimits`](struct.RequestLimits.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// The size of the body, or of the query string of a `GET` request
+    Body,
+    /// The size of the document of each operation
+    Query,
+    /// The size of the variables of each operation, serialized as JSON
+    Variables,
+    /// The size of the body of a `multipart/form-data` request
+    Upload,
+}
+
+impl Limit {
+    fn name(self) -> &'static str {
+        match self {
+            Limit::Body => "body",
+            Limit::Query => "query",
+            Limit::Variables => "variables",
+            Limit::Upload => "upload",
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Constructs limits without any limit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the size of the body, or of the query string of a `GET`
+    /// request
+    pub fn max_body_bytes(mut self, max: usize) -> Self {
+        self.max_body_bytes = Some(max);
+        self
+    }
+
+    /// Limits the size of the document of each operation
+    pub fn max_query_bytes(mut self, max: usize) -> Self {
+        self.max_query_bytes = Some(max);
+        self
+    }
+
+    /// Limits the size of the variables of each operation, serialized as
+    /// JSON
+    pub fn max_variables_bytes(mut self, max: usize) -> Self {
+        self.max_variables_bytes = Some(max);
+        self
+    }
+
+    /// Limits the size of the body of `multipart/form-data` requests
+    pub fn max_upload_bytes(mut self, max: usize) -> Self {
+        self.max_upload_bytes = Some(max);
+        self
+    }
+
+    /// The maximum size of the body of the request, to stop reading bodies
+    /// without a `Content-Length` header once it's exceeded
+    pub fn body_limit(&self, parts: &RequestParts) -> Option<usize> {
+        let (_, max) = self.body_kind(parts);
+        max
+    }
+
+    /// Checks the `Content-Length` header of the request, or its query string
+    /// for a `GET` request
+    ///
+    /// The `query` and `variables` parameters of a `GET` request are checked
+    /// by [`check_request`](#method.check_request), like the ones of a body.
+    pub fn check_parts(&self, parts: &RequestParts) -> Result<(), LimitError> {
+        if let Some(length) = parts
+            .header("Content-Length")
+            .and_then(|length| length.trim().parse().ok())
+        {
+            let (limit, max) = self.body_kind(parts);
+            check(limit, max, length)?;
+        }
+
+        if parts.method.eq_ignore_ascii_case("GET") {
+            let query_string = parts.query.as_deref().unwrap_or("");
+            check(Limit::Body, self.max_body_bytes, query_string.len())?;
+        }
+        Ok(())
+    }
+
+    /// Checks the size of the body of the request
+    pub fn check_body(&self, parts: &RequestParts, body: &[u8]) -> Result<(), LimitError> {
+        let (limit, max) = self.body_kind(parts);
+        check(limit, max, body.len())
+    }
+
+    /// Checks the size of the document and of the variables of each
+    /// operation of the request
+    pub fn check_request<S>(&self, request: &GraphQLBatchRequest<S>) -> Result<(), LimitError>
+    where
+        S: ScalarValue,
+    {
+        match request {
+            GraphQLBatchRequest::Single(request) => self.check_operation(request),
+            GraphQLBatchRequest::Batch(requests) => requests
+                .iter()
+                .try_for_each(|request| self.check_operation(request)),
+        }
+    }
+
+    fn check_operation<S>(&self, request: &GraphQLRequest<S>) -> Result<(), LimitError>
+    where
+        S: ScalarValue,
+    {
+        check(Limit::Query, self.max_query_bytes, request.query.len())?;
+        if let (Some(max), Some(variables)) = (self.max_variables_bytes, &request.variables) {
+            check(Limit::Variables, Some(max), json_len(variables))?;
+        }
+        Ok(())
+    }
+
+    fn body_kind(&self, parts: &RequestParts) -> (Limit, Option<usize>) {
+        if parts.media_type().is("multipart/form-data") {
+            (Limit::Upload, self.max_upload_bytes)
+        } else {
+            (Limit::Body, self.max_body_bytes)
+        }
+    }
+}
+
+fn check(limit: Limit, max: Option<usize>, size: usize) -> Result<(), LimitError> {
+    match max {
+        Some(max) if size > max => Err(LimitError { limit, max, size }),
+        _ => Ok(()),
+    }
+}
+
+/// The length of the value serialized as JSON, without collecting it
+fn json_len<S: ScalarValue>(value: &InputValue<S>) -> usize {
+    struct Counter(usize);
+
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+/// A request exceeding a limit of the
+/// [`RequestLimits`](struct.RequestLimits.html), answered with
+/// `413 Payload Too Large` once converted into an
+/// [`HttpError`](struct.HttpError.html)
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitError {
+    limit: Limit,
+    max: usize,
+    size: usize,
+}
+
+impl LimitError {
+    /// The exceeded limit
+    pub fn limit(&self) -> Limit {
+        self.limit
+    }
+
+    /// The maximum size, in bytes
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// The size of the request, in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The error of a GraphQL response, for integrations answering with one,
+    /// with the `REQUEST_TOO_LARGE` code, the `limit` and the `max` size in
+    /// its extensions
+    pub fn to_field_error<S: ScalarValue>(&self) -> FieldError<S> {
+        let mut extensions = Object::with_capacity(3);
+        extensions.add_field("code", Value::scalar("REQUEST_TOO_LARGE".to_owned()));
+        extensions.add_field("limit", Value::scalar(self.limit.name().to_owned()));
+        extensions.add_field(
+            "max",
+            Value::scalar(i32::try_from(self.max).unwrap_or(i32::MAX)),
+        );
+        FieldError::new(self, Value::object(extensions))
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {} of the request exceeds the limit of {} bytes",
+            self.limit.name(),
+            self.max,
+        )
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+impl From<LimitError> for HttpError {
+    fn from(error: LimitError) -> Self {
+        HttpError::new(413, error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Limit, LimitError, RequestLimits};
+    use crate::{
+        ast::InputValue,
+        executor::FieldError,
+        http::{GraphQLBatchRequest, GraphQLRequest, HttpError, RequestParts},
+        value::DefaultScalarValue,
+    };
+
+    fn limits() -> RequestLimits {
+        RequestLimits::new()
+            .max_body_bytes(100)
+            .max_query_bytes(20)
+            .max_variables_bytes(20)
+            .max_upload_bytes(1000)
+    }
+
+    fn parts(method: &str, query: Option<&str>, headers: &[(&str, &str)]) -> RequestParts {
+        RequestParts {
+            method: method.into(),
+            query: query.map(Into::into),
+            headers: headers
+                .iter()
+                .map(|(name, value)| ((*name).into(), (*value).into()))
+                .collect(),
+            ..RequestParts::default()
+        }
+    }
+
+    fn exceeded(result: Result<(), LimitError>) -> Option<Limit> {
+        result.err().map(|error| error.limit())
+    }
+
+    #[test]
+    fn nothing_is_limited_by_default() {
+        let parts = parts("POST", None, &[("Content-Length", "1000000000")]);
+        let request = GraphQLBatchRequest::<DefaultScalarValue>::Single(GraphQLRequest::new(
+            "{ a }".repeat(1000),
+            None,
+            None,
+        ));
+
+        assert!(RequestLimits::new().check_parts(&parts).is_ok());
+        assert!(RequestLimits::new().check_body(&parts, &[0; 1000]).is_ok());
+        assert!(RequestLimits::new().check_request(&request).is_ok());
+        assert_eq!(RequestLimits::new().body_limit(&parts), None);
+    }
+
+    #[test]
+    fn bodies_are_limited_before_being_read() {
+        let limits = limits();
+        let json = parts("POST", None, &[("Content-Length", "101")]);
+        let upload = parts(
+            "POST",
+            None,
+            &[
+                ("Content-Length", "101"),
+                ("Content-Type", "multipart/form-data; boundary=x"),
+            ],
+        );
+
+        assert_eq!(exceeded(limits.check_parts(&json)), Some(Limit::Body));
+        assert_eq!(exceeded(limits.check_parts(&upload)), None);
+        assert_eq!(limits.body_limit(&json), Some(100));
+        assert_eq!(limits.body_limit(&upload), Some(1000));
+
+        assert_eq!(exceeded(limits.check_body(&json, &[0; 100])), None);
+        assert_eq!(
+            exceeded(limits.check_body(&json, &[0; 101])),
+            Some(Limit::Body),
+        );
+        assert_eq!(
+            exceeded(limits.check_body(&upload, &[0; 1001])),
+            Some(Limit::Upload),
+        );
+    }
+
+    #[test]
+    fn get_query_strings_are_limited_as_a_whole() {
+        let limits = limits();
+        let get = |query: &str| limits.check_parts(&parts("GET", Some(query), &[]));
+
+        assert_eq!(exceeded(get(&format!("query={}", "a".repeat(94)))), None);
+        assert_eq!(
+            exceeded(get(&format!("query=a&operationName={}", "a".repeat(100)))),
+            Some(Limit::Body),
+        );
+    }
+
+    #[test]
+    fn variables_are_measured_as_json() {
+        let limits = limits();
+        let request = |value: &str| {
+            let variables = InputValue::object(
+                vec![("a", InputValue::<DefaultScalarValue>::scalar(value))]
+                    .into_iter()
+                    .collect(),
+            );
+            GraphQLBatchRequest::Single(GraphQLRequest::new("{ a }".into(), None, Some(variables)))
+        };
+
+        assert_eq!(
+            exceeded(limits.check_request(&request(&"a".repeat(12)))),
+            None
+        );
+        // `{"a":"aaaaaaaaaaaaa"}`
+        assert_eq!(
+            exceeded(limits.check_request(&request(&"a".repeat(13)))),
+            Some(Limit::Variables),
+        );
+        // `{"a":"\n\n\n\n\n\n\n"}`
+        assert_eq!(
+            exceeded(limits.check_request(&request(&"\n".repeat(7)))),
+            Some(Limit::Variables),
+        );
+    }
+
+    #[test]
+    fn operations_are_limited_before_being_parsed() {
+        let limits = limits();
+        let request = |query: &str, variables: Option<InputValue<DefaultScalarValue>>| {
+            GraphQLRequest::new(query.into(), None, variables)
+        };
+        let small = request(
+            "{ a }",
+            Some(InputValue::object(
+                vec![("a", InputValue::scalar(1))].into_iter().collect(),
+            )),
+        );
+        let long_query = request(&"{ a }".repeat(5), None);
+        let long_variables = request(
+            "{ a }",
+            Some(InputValue::list(vec![InputValue::scalar("aaaaaaaaaa"); 2])),
+        );
+
+        let check = |request: GraphQLBatchRequest| exceeded(limits.check_request(&request));
+        assert_eq!(check(GraphQLBatchRequest::Single(small.clone())), None);
+        assert_eq!(
+            check(GraphQLBatchRequest::Single(long_query)),
+            Some(Limit::Query),
+        );
+        assert_eq!(
+            check(GraphQLBatchRequest::Batch(vec![small, long_variables])),
+            Some(Limit::Variables),
+        );
+    }
+
+    #[test]
+    fn errors_are_structured() {
+        let error = limits()
+            .check_body(&parts("POST", None, &[]), &[0; 101])
+            .unwrap_err();
+
+        assert_eq!((error.max(), error.size()), (100, 101));
+        assert_eq!(
+            error.to_field_error(),
+            FieldError::<DefaultScalarValue>::new(
+                "The body of the request exceeds the limit of 100 bytes",
+                graphql_value!({ "code": "REQUEST_TOO_LARGE", "limit": "body", "max": 100 }),
+            ),
+        );
+
+        let error = HttpError::from(error);
+        assert_eq!(error.status(), 413);
+        assert_eq!(
+            error.message(),
+            "The body of the request exceeds the limit of 100 bytes",
+        );
+    }
+}
@@ -1,8 +1,26 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+mod context;
+mod csrf;
+mod errors;
 pub mod graphiql;
+mod limits;
 pub mod playground;
+mod rejection;
+#[cfg(feature = "http-strict")]
+pub mod strict;
+
+pub use self::{
+    context::{ContextFactory, RequestParts},
+    csrf::CsrfProtection,
+    errors::ErrorFormatter,
+    limits::{Limit, LimitError, RequestLimits},
+    rejection::HttpError,
+};
+
+pub(crate) use self::context::MediaType;
 
+use futures::{future::BoxFuture, stream, FutureExt as _, StreamExt as _};
 use serde::{
     de,
     ser::{self, SerializeMap},
@@ -11,8 +29,9 @@ use serde::{
 
 use crate::{
     ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
-    value::{DefaultScalarValue, ScalarValue},
+    cache::CachePolicy,
+    executor::{CatchPanic, ExecutionError, ExecutionMetadata, ExecutionOutput, ValuesStream},
+    value::{DefaultScalarValue, Object, ScalarValue},
     FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     Value, Variables,
 };
@@ -23,7 +42,7 @@ use crate::{
 /// into this struct - it derives Deserialize for exactly this reason.
 ///
 /// For GET, you will need to parse the query string and extract "query",
-/// "operationName", and "variables" manually.
+/// "operationName", "variables" and "extensions" manually.
 #[derive(Deserialize, Clone, Serialize, PartialEq, Debug)]
 pub struct GraphQLRequest<S = DefaultScalarValue>
 where
@@ -34,18 +53,44 @@ where
     operation_name: Option<String>,
     #[serde(bound(deserialize = "InputValue<S>: Deserialize<'de> + Serialize"))]
     variables: Option<InputValue<S>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        bound(deserialize = "InputValue<S>: Deserialize<'de> + Serialize")
+    )]
+    extensions: Option<InputValue<S>>,
 }
 
 impl<S> GraphQLRequest<S>
 where
     S: ScalarValue,
 {
+    /// Returns the query document of this request.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
     /// Returns the `operation_name` associated with this request.
     pub fn operation_name(&self) -> Option<&str> {
         self.operation_name.as_deref()
     }
 
-    fn variables(&self) -> Variables<S> {
+    /// Returns the `extensions` member of this request, e.g. holding the hash
+    /// of a persisted query or tracing options.
+    pub fn extensions(&self) -> Option<&InputValue<S>> {
+        self.extensions.as_ref()
+    }
+
+    /// Returns the entry of the `extensions` member with the provided name.
+    pub fn extension(&self, name: &str) -> Option<&InputValue<S>> {
+        self.extensions
+            .as_ref()?
+            .to_object_value()?
+            .get(name)
+            .copied()
+    }
+
+    /// Returns the variables of this request, empty if none were provided.
+    pub fn variables(&self) -> Variables<S> {
         self.variables
             .as_ref()
             .and_then(|iv| {
@@ -68,9 +113,16 @@ where
             query,
             operation_name,
             variables,
+            extensions: None,
         }
     }
 
+    /// Set the `extensions` member of this request
+    pub fn with_extensions(mut self, extensions: InputValue<S>) -> Self {
+        self.extensions = Some(extensions);
+        self
+    }
+
     /// Execute a GraphQL request synchronously using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute_sync` function exposed at the
@@ -86,7 +138,7 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
+        GraphQLResponse::from_result_with_metadata(crate::execute_sync_with_metadata(
             &self.query,
             self.operation_name(),
             root_node,
@@ -116,8 +168,8 @@ where
     {
         let op = self.operation_name();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let res = crate::execute_with_metadata(&self.query, op, root_node, vars, context).await;
+        GraphQLResponse::from_result_with_metadata(res)
     }
 }
 
@@ -129,7 +181,7 @@ pub async fn resolve_into_stream<'req, 'rn, 'ctx, 'a, QueryT, MutationT, Subscri
     req: &'req GraphQLRequest<S>,
     root_node: &'rn RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     context: &'ctx QueryT::Context,
-) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<ExecutionOutput<S, Value<ValuesStream<'a, S>>>, GraphQLError<'a>>
 where
     'req: 'a,
     'rn: 'a,
@@ -156,7 +208,8 @@ where
 /// whether to send a 200 or 400 HTTP status code.
 #[derive(Debug)]
 pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
-    Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    Result<ExecutionOutput<S>, GraphQLError<'a>>,
+    ExecutionMetadata,
 );
 
 impl<'a, S> GraphQLResponse<'a, S>
@@ -164,13 +217,30 @@ where
     S: ScalarValue,
 {
     /// Constructs new `GraphQLResponse` using the given result
-    pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+    pub fn from_result(r: Result<ExecutionOutput<S>, GraphQLError<'a>>) -> Self {
+        Self(r, ExecutionMetadata::default())
+    }
+
+    /// Constructs new `GraphQLResponse` using the given result, returned by
+    /// e.g. [`execute_with_metadata`](../fn.execute_with_metadata.html)
+    pub fn from_result_with_metadata(
+        r: Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>,
+    ) -> Self {
+        match r {
+            Ok((output, metadata)) => Self(Ok(output), metadata),
+            Err(e) => Self(Err(e), ExecutionMetadata::default()),
+        }
     }
 
     /// Constructs an error response outside of the normal execution flow
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        GraphQLResponse(
+            Ok(ExecutionOutput {
+                errors: vec![ExecutionError::at_origin(error)],
+                ..ExecutionOutput::from_data(Value::null())
+            }),
+            ExecutionMetadata::default(),
+        )
     }
 
     /// Was the request successful or not?
@@ -180,6 +250,41 @@ where
     pub fn is_ok(&self) -> bool {
         self.0.is_ok()
     }
+
+    /// How long, and by whom, the response may be cached
+    ///
+    /// Use it to set the `Cache-Control` header, formatted by the `Display`
+    /// implementation of [`CachePolicy`](../cache/struct.CachePolicy.html).
+    /// It's `None` for responses having errors.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self.0 {
+            Ok(ref output) if output.errors.is_empty() => self.1.cache_policy,
+            _ => None,
+        }
+    }
+
+    /// The entries of the `extensions` member of the response, added with
+    /// [`Executor::add_extension`](../struct.Executor.html#method.add_extension)
+    ///
+    /// It's `None` for requests which failed before being executed.
+    pub fn extensions(&self) -> Option<&Object<S>> {
+        self.0.as_ref().ok().map(|output| &output.extensions)
+    }
+
+    /// Rewrites the errors raised by resolvers with the provided formatter,
+    /// e.g. to hide internal details from clients
+    pub fn format_errors<F>(mut self, formatter: &F) -> Self
+    where
+        F: ErrorFormatter<S> + ?Sized,
+    {
+        if let Ok(ref mut output) = self.0 {
+            for error in &mut output.errors {
+                let formatted = formatter.format_error(error);
+                error.replace_error(formatted);
+            }
+        }
+        self
+    }
 }
 
 impl<'a, T> Serialize for GraphQLResponse<'a, T>
@@ -194,15 +299,20 @@ where
         S: ser::Serializer,
     {
         match self.0 {
-            Ok((ref res, ref err)) => {
+            Ok(ref output) => {
                 let mut map = serializer.serialize_map(None)?;
 
                 map.serialize_key("data")?;
-                map.serialize_value(res)?;
+                map.serialize_value(&output.data)?;
 
-                if !err.is_empty() {
+                if !output.errors.is_empty() {
                     map.serialize_key("errors")?;
-                    map.serialize_value(err)?;
+                    map.serialize_value(&output.errors)?;
+                }
+
+                if output.extensions.field_count() > 0 {
+                    map.serialize_key("extensions")?;
+                    map.serialize_value(&output.extensions)?;
                 }
 
                 map.end()
@@ -281,8 +391,13 @@ where
 
     /// Executes a GraphQL request using the specified schema and context
     ///
-    /// This is a simple wrapper around the `execute` function exposed in
-    /// GraphQLRequest
+    /// The operations of a batch are executed concurrently, at most as many at
+    /// once as set with
+    /// [`RootNode::with_batch_concurrency`](../struct.RootNode.html#method.with_batch_concurrency),
+    /// and the responses keep the order of the requests. Each operation fails
+    /// on its own: with [panic catching](../struct.RootNode.html#method.with_panic_catching),
+    /// an operation panicking outside of its resolvers gets an error response
+    /// instead of failing the whole batch.
     pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
         &'a self,
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
@@ -304,10 +419,23 @@ where
                 GraphQLBatchResponse::Single(resp)
             }
             Self::Batch(reqs) => {
-                let resps = futures::future::join_all(
-                    reqs.iter().map(|req| req.execute(root_node, context)),
-                )
-                .await;
+                let catch = root_node.schema.catch_panics;
+                let limit = root_node.schema.batch_concurrency.unwrap_or(reqs.len());
+                // Boxed before being awaited, so that the future of the batch
+                // stays `Send` for any lifetime of the requests.
+                let futures: Vec<BoxFuture<'a, _>> = reqs
+                    .iter()
+                    .map(|req| -> BoxFuture<'a, GraphQLResponse<'a, S>> {
+                        let future = Box::pin(req.execute(root_node, context).map(Ok));
+                        Box::pin(async move {
+                            match (CatchPanic { catch, future }).await {
+                                Ok(resp) => resp,
+                                Err(e) => GraphQLResponse::error(e),
+                            }
+                        })
+                    })
+                    .collect();
+                let resps = stream::iter(futures).buffered(limit.max(1)).collect().await;
                 GraphQLBatchResponse::Batch(resps)
             }
         }
@@ -351,6 +479,36 @@ where
             Self::Batch(resps) => resps.iter().all(GraphQLResponse::is_ok),
         }
     }
+
+    /// Rewrites the errors raised by resolvers in all the responses, see
+    /// [`GraphQLResponse::format_errors`](struct.GraphQLResponse.html#method.format_errors)
+    pub fn format_errors<F>(self, formatter: &F) -> Self
+    where
+        F: ErrorFormatter<S> + ?Sized,
+    {
+        match self {
+            Self::Single(resp) => Self::Single(resp.format_errors(formatter)),
+            Self::Batch(resps) => Self::Batch(
+                resps
+                    .into_iter()
+                    .map(|resp| resp.format_errors(formatter))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// How long, and by whom, all the responses may be cached, see
+    /// [`GraphQLResponse::cache_policy`](struct.GraphQLResponse.html#method.cache_policy)
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self {
+            Self::Single(resp) => resp.cache_policy(),
+            Self::Batch(resps) => {
+                let mut policies = resps.iter().map(GraphQLResponse::cache_policy);
+                let first = policies.next()??;
+                policies.try_fold(first, |merged, policy| Some(merged.merge(policy?)))
+            }
+        }
+    }
 }
 
 #[cfg(feature = "expose-test-schema")]
@@ -0,0 +1,72 @@
+//! Requests rejected before being executed, answered with an HTTP error by
+//! all the integrations
+
+use std::fmt;
+
+/// A request rejected before being executed, answered with an HTTP error
+///
+/// Returned by the [`ContextFactory`](trait.ContextFactory.html), the
+/// [`CsrfProtection`](struct.CsrfProtection.html) and the strict mode, and
+/// converted from the [`LimitError`](struct.LimitError.html) of the
+/// [`RequestLimits`](struct.RequestLimits.html), so integrations answer all
+/// of them the same way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpError {
+    status: u16,
+    message: String,
+    allow: Option<&'static str>,
+}
+
+impl HttpError {
+    /// Constructs an error answered with the provided HTTP status code
+    pub fn new<M: Into<String>>(status: u16, message: M) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            allow: None,
+        }
+    }
+
+    /// Constructs an error answered with `400 Bad Request`
+    pub fn bad_request<M: Into<String>>(message: M) -> Self {
+        Self::new(400, message)
+    }
+
+    /// Constructs an error answered with `401 Unauthorized`
+    pub fn unauthorized<M: Into<String>>(message: M) -> Self {
+        Self::new(401, message)
+    }
+
+    /// Constructs an error answered with `405 Method Not Allowed`, and the
+    /// allowed methods in the `Allow` header
+    pub fn method_not_allowed<M: Into<String>>(message: M, allow: &'static str) -> Self {
+        Self {
+            allow: Some(allow),
+            ..Self::new(405, message)
+        }
+    }
+
+    /// The HTTP status code of the response
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The body of the response
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The value of the `Allow` header of the response, for
+    /// `405 Method Not Allowed`
+    pub fn allow(&self) -> Option<&'static str> {
+        self.allow
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for HttpError {}
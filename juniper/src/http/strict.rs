@@ -0,0 +1,676 @@
+//! Handling of requests following the
+//! [GraphQL over HTTP](https://graphql.github.io/graphql-over-http/draft/)
+//! specification exactly, enabled by the `http-strict` feature
+//!
+//! Integrations accept requests leniently, e.g. batches, or bodies of any
+//! content type. The strict mode only accepts the requests described by the
+//! specification, answered with its status codes:
+//!
+//! - `GET` requests have their parameters in the query string, with the
+//!   `variables` and `extensions` encoded as JSON, and can't execute
+//!   mutations, failing with `405 Method Not Allowed`;
+//! - `POST` requests have a JSON object body, with the `application/json`
+//!   content type, failing with `415 Unsupported Media Type` otherwise;
+//! - other methods fail with `405 Method Not Allowed`;
+//! - a malformed body, a missing `query`, or parameters of the wrong type
+//!   fail with `400 Bad Request`;
+//! - the response has the `application/graphql-response+json` content type if
+//!   the `Accept` header allows it, or `application/json`, failing with
+//!   `406 Not Acceptable` if it allows neither;
+//! - requests which can't be executed, as their document is invalid, are
+//!   answered with `400 Bad Request` in `application/graphql-response+json`,
+//!   and `200 OK` in `application/json`, as legacy clients expect.
+//!
+//! ```
+//! # use juniper::{
+//! #     http::{strict::StrictRequest, RequestParts},
+//! #     EmptyMutation, EmptySubscription, RootNode,
+//! # };
+//! # struct Query;
+//! # #[juniper::graphql_object]
+//! # impl Query {
+//! #     fn hello() -> &str {
+//! #         "world"
+//! #     }
+//! # }
+//! # let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//! let parts = RequestParts {
+//!     method: "POST".into(),
+//!     headers: vec![
+//!         ("Content-Type".into(), "application/json".into()),
+//!         ("Accept".into(), "application/graphql-response+json".into()),
+//!     ],
+//!     ..RequestParts::default()
+//! };
+//! let body = br#"{"query": "{ hello }"}"#;
+//!
+//! let (status, content_type, body) = match StrictRequest::from_http(&parts, body) {
+//!     Ok(request) => {
+//!         let response = request.execute_sync(&schema, &())?;
+//!         (response.status(), response.content_type(), serde_json::to_string(response.response())?)
+//!     }
+//!     Err(error) => (error.status(), "text/plain", error.message().to_owned()),
+//! };
+//! assert_eq!(status, 200);
+//! assert_eq!(content_type, "application/graphql-response+json");
+//! assert_eq!(body, r#"{"data":{"hello":"world"}}"#);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::str;
+
+use serde_json::{Map as JsonMap, Value as Json};
+
+use crate::{
+    ast::{InputValue, OperationType},
+    executor::get_operation,
+    http::{GraphQLRequest, GraphQLResponse, HttpError, MediaType, RequestParts},
+    parser::parse_document_source,
+    value::{DefaultScalarValue, ScalarValue},
+    GraphQLType, GraphQLTypeAsync, RootNode,
+};
+
+/// Media type of the responses to the clients accepting it
+pub const GRAPHQL_RESPONSE_JSON: &str = "application/graphql-response+json";
+
+/// Media type of the requests, and of the responses to legacy clients
+pub const JSON: &str = "application/json";
+
+/// A request following the specification, see the [module](index.html)
+/// documentation
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrictRequest<S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    request: GraphQLRequest<S>,
+    is_get: bool,
+    content_type: &'static str,
+}
+
+impl<S> StrictRequest<S>
+where
+    S: ScalarValue,
+{
+    /// Reads the request from the parts and the body of an HTTP request
+    pub fn from_http(parts: &RequestParts, body: &[u8]) -> Result<Self, HttpError> {
+        let is_get = match parts.method.as_str() {
+            "GET" => true,
+            "POST" => false,
+            _ => {
+                return Err(HttpError::method_not_allowed(
+                    "Only GET and POST requests are supported",
+                    "GET, POST",
+                ))
+            }
+        };
+
+        let content_type = response_content_type(parts.header("Accept")).ok_or_else(|| {
+            HttpError::new(
+                406,
+                format!(
+                    "The response can only be sent as {} or {}",
+                    GRAPHQL_RESPONSE_JSON, JSON
+                ),
+            )
+        })?;
+
+        let params = if is_get {
+            get_params(parts.query.as_deref().unwrap_or(""))?
+        } else {
+            post_params(parts.media_type(), body)?
+        };
+
+        Ok(StrictRequest {
+            request: graphql_request(params)?,
+            is_get,
+            content_type,
+        })
+    }
+
+    /// The GraphQL request
+    pub fn request(&self) -> &GraphQLRequest<S> {
+        &self.request
+    }
+
+    /// The content type of the response, either
+    /// [`GRAPHQL_RESPONSE_JSON`](constant.GRAPHQL_RESPONSE_JSON.html) or
+    /// [`JSON`](constant.JSON.html)
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// Executes the request synchronously
+    ///
+    /// Fails if a `GET` request selects a mutation.
+    pub fn execute_sync<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> Result<StrictResponse<'a, S>, HttpError>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.check_method(root_node)?;
+        Ok(self.respond(self.request.execute_sync(root_node, context)))
+    }
+
+    /// Executes the request
+    ///
+    /// Fails if a `GET` request selects a mutation.
+    pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> Result<StrictResponse<'a, S>, HttpError>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        self.check_method(root_node)?;
+        Ok(self.respond(self.request.execute(root_node, context).await))
+    }
+
+    /// Checks a `GET` request doesn't select a mutation, the documents which
+    /// can't be parsed failing once executed
+    fn check_method<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    ) -> Result<(), HttpError>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        if !self.is_get {
+            return Ok(());
+        }
+        let document = match parse_document_source(self.request.query(), &root_node.schema) {
+            Ok(document) => document,
+            Err(_) => return Ok(()),
+        };
+        match get_operation(&document, self.request.operation_name()) {
+            Ok(operation) if operation.item.operation_type == OperationType::Mutation => Err(
+                HttpError::method_not_allowed("Mutations can only be sent with POST", "POST"),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn respond<'a>(&self, response: GraphQLResponse<'a, S>) -> StrictResponse<'a, S> {
+        let status = if response.is_ok() || self.content_type == JSON {
+            200
+        } else {
+            400
+        };
+        StrictResponse {
+            response,
+            status,
+            content_type: self.content_type,
+        }
+    }
+}
+
+/// The response to a [`StrictRequest`](struct.StrictRequest.html), with its
+/// HTTP status code and content type
+#[derive(Debug)]
+pub struct StrictResponse<'a, S = DefaultScalarValue> {
+    response: GraphQLResponse<'a, S>,
+    status: u16,
+    content_type: &'static str,
+}
+
+impl<'a, S> StrictResponse<'a, S> {
+    /// The HTTP status code of the response
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The value of the `Content-Type` header of the response
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// The body of the response, to serialize as JSON
+    pub fn response(&self) -> &GraphQLResponse<'a, S> {
+        &self.response
+    }
+
+    /// The body of the response, to serialize as JSON
+    pub fn into_response(self) -> GraphQLResponse<'a, S> {
+        self.response
+    }
+}
+
+/// The media type of the response the `accept` header allows, the one with
+/// the highest quality being preferred, `application/json` for wildcards
+fn response_content_type(accept: Option<&str>) -> Option<&'static str> {
+    let accept = match accept {
+        Some(accept) if !accept.trim().is_empty() => accept,
+        _ => return Some(JSON),
+    };
+
+    // By quality, then by preference of the media types.
+    let mut best: Option<(f32, u8, &'static str)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let media_type = params.next().unwrap_or("").trim().to_ascii_lowercase();
+        let quality = params
+            .filter_map(|p| {
+                let mut kv = p.splitn(2, '=');
+                match (kv.next().map(str::trim), kv.next()) {
+                    (Some(k), Some(v)) if k.eq_ignore_ascii_case("q") => v.trim().parse().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(1.0);
+
+        let (preference, content_type) = match media_type.as_str() {
+            GRAPHQL_RESPONSE_JSON => (2, GRAPHQL_RESPONSE_JSON),
+            JSON => (1, JSON),
+            "application/*" | "*/*" => (0, JSON),
+            _ => continue,
+        };
+        let is_better = match best {
+            Some((q, p, _)) => (quality, preference) > (q, p),
+            None => true,
+        };
+        if quality > 0.0 && is_better {
+            best = Some((quality, preference, content_type));
+        }
+    }
+    best.map(|(_, _, content_type)| content_type)
+}
+
+/// The parameters of a GraphQL request, others being ignored
+const PARAMS: &[&str] = &["query", "operationName", "variables", "extensions"];
+
+/// The parameters of a `GET` request, from its query string
+fn get_params(query: &str) -> Result<JsonMap<String, Json>, HttpError> {
+    let mut params = JsonMap::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let mut kv = pair.splitn(2, '=');
+        let key = percent_decode(kv.next().unwrap_or(""))?;
+        // Unknown parameters are ignored, duplicated or not.
+        if !PARAMS.contains(&key.as_str()) {
+            continue;
+        }
+        let value = percent_decode(kv.next().unwrap_or(""))?;
+
+        let value = match key.as_str() {
+            "variables" | "extensions" => serde_json::from_str(&value).map_err(|_| {
+                HttpError::bad_request(format!("The `{}` parameter must be JSON", key))
+            })?,
+            _ => Json::String(value),
+        };
+        if params.insert(key.clone(), value).is_some() {
+            return Err(HttpError::bad_request(format!(
+                "The `{}` parameter is duplicated",
+                key,
+            )));
+        }
+    }
+    Ok(params)
+}
+
+/// The parameters of a `POST` request, from its JSON body
+fn post_params(media_type: MediaType, body: &[u8]) -> Result<JsonMap<String, Json>, HttpError> {
+    let is_utf8 = match media_type.param("charset") {
+        Some(charset) => charset.eq_ignore_ascii_case("utf-8"),
+        None => true,
+    };
+    if !media_type.is(JSON) || !is_utf8 {
+        return Err(HttpError::new(
+            415,
+            format!("The body must be {} encoded in UTF-8", JSON),
+        ));
+    }
+
+    match serde_json::from_slice(body) {
+        Ok(Json::Object(params)) => Ok(params),
+        Ok(_) => Err(HttpError::bad_request("The body must be a JSON object")),
+        Err(_) => Err(HttpError::bad_request("The body must be valid JSON")),
+    }
+}
+
+/// Builds the GraphQL request from its parameters, checking their types
+fn graphql_request<S: ScalarValue>(
+    mut params: JsonMap<String, Json>,
+) -> Result<GraphQLRequest<S>, HttpError> {
+    let query = match params.remove("query") {
+        Some(Json::String(query)) => query,
+        Some(_) => return Err(HttpError::bad_request("The `query` must be a string")),
+        None => return Err(HttpError::bad_request("The `query` is missing")),
+    };
+    let operation_name = match params.remove("operationName") {
+        Some(Json::String(name)) => Some(name),
+        Some(Json::Null) | None => None,
+        Some(_) => {
+            return Err(HttpError::bad_request(
+                "The `operationName` must be a string",
+            ))
+        }
+    };
+    let variables = object_param("variables", params.remove("variables"))?;
+    let extensions = object_param("extensions", params.remove("extensions"))?;
+
+    let request = GraphQLRequest::new(query, operation_name, variables);
+    Ok(match extensions {
+        Some(extensions) => request.with_extensions(extensions),
+        None => request,
+    })
+}
+
+fn object_param<S: ScalarValue>(
+    name: &str,
+    value: Option<Json>,
+) -> Result<Option<InputValue<S>>, HttpError> {
+    match value {
+        Some(Json::Null) | None => Ok(None),
+        Some(value @ Json::Object(_)) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(|e| HttpError::bad_request(format!("The `{}` are invalid: {}", name, e))),
+        Some(_) => Err(HttpError::bad_request(format!(
+            "The `{}` must be an object",
+            name,
+        ))),
+    }
+}
+
+/// Decodes a component of a query string, `+` being a space
+fn percent_decode(s: &str) -> Result<String, HttpError> {
+    let malformed = || HttpError::bad_request("The query string is malformed");
+
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        bytes.push(match b {
+            b'+' => b' ',
+            b'%' => {
+                let high = iter.next().and_then(hex_digit).ok_or_else(malformed)?;
+                let low = iter.next().and_then(hex_digit).ok_or_else(malformed)?;
+                high << 4 | low
+            }
+            b => b,
+        });
+    }
+    String::from_utf8(bytes).map_err(|_| malformed())
+}
+
+/// The value of an ASCII hexadecimal digit
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value as Json};
+
+    use super::{StrictRequest, GRAPHQL_RESPONSE_JSON, JSON};
+    use crate::{
+        http::{HttpError, RequestParts},
+        schema::model::RootNode,
+        types::scalars::EmptySubscription,
+    };
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn hello(name: Option<String>) -> String {
+            format!("Hello, {}", name.unwrap_or_else(|| "world".into()))
+        }
+    }
+
+    struct Mutation;
+
+    #[crate::graphql_object]
+    impl Mutation {
+        fn bump() -> i32 {
+            1
+        }
+    }
+
+    type Schema = RootNode<'static, Query, Mutation, EmptySubscription<()>>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, Mutation, EmptySubscription::<()>::new())
+    }
+
+    fn get(query: &str, accept: Option<&str>) -> RequestParts {
+        RequestParts {
+            method: "GET".into(),
+            query: Some(query.into()),
+            headers: accept
+                .map(|a| vec![("Accept".into(), a.into())])
+                .unwrap_or_default(),
+            ..RequestParts::default()
+        }
+    }
+
+    fn post(content_type: &str, accept: Option<&str>) -> RequestParts {
+        let mut headers = vec![("Content-Type".to_owned(), content_type.to_owned())];
+        headers.extend(accept.map(|a| ("Accept".to_owned(), a.to_owned())));
+        RequestParts {
+            method: "POST".into(),
+            headers,
+            ..RequestParts::default()
+        }
+    }
+
+    /// The status, content type and body of the response
+    fn respond(parts: RequestParts, body: &str) -> (u16, &'static str, Json) {
+        let schema = schema();
+        let response = StrictRequest::from_http(&parts, body.as_bytes()).and_then(|request| {
+            let response = request.execute_sync(&schema, &())?;
+            let body = serde_json::to_value(response.response()).unwrap();
+            Ok((response.status(), response.content_type(), body))
+        });
+        match response {
+            Ok(response) => response,
+            Err(error) => (error.status(), "text/plain", Json::Null),
+        }
+    }
+
+    fn reject(parts: RequestParts, body: &str) -> HttpError {
+        StrictRequest::<crate::DefaultScalarValue>::from_http(&parts, body.as_bytes())
+            .and_then(|request| request.execute_sync(&schema(), &()).map(|_| ()))
+            .unwrap_err()
+    }
+
+    #[test]
+    fn get_requests_have_url_encoded_parameters() {
+        let query = "query=query%20Hello(%24name%3A%20String)%20%7B%20hello(name%3A%20%24name)%20%7D\
+                     &operationName=Hello&variables=%7B%22name%22%3A%22Ferris%22%7D&extensions=%7B%7D";
+
+        assert_eq!(
+            respond(get(query, None), ""),
+            (200, JSON, json!({ "data": { "hello": "Hello, Ferris" } })),
+        );
+        assert_eq!(
+            respond(get("query=%7B+hello+%7D", None), ""),
+            (200, JSON, json!({ "data": { "hello": "Hello, world" } })),
+        );
+    }
+
+    #[test]
+    fn get_requests_cannot_execute_mutations() {
+        let error = reject(get("query=mutation%20%7B%20bump%20%7D", None), "");
+        assert_eq!(error.status(), 405);
+        assert_eq!(error.allow(), Some("POST"));
+
+        let query = "query=query%20A%20%7B%20hello%20%7D%20mutation%20B%20%7B%20bump%20%7D";
+        let error = reject(get(&format!("{}&operationName=B", query), None), "");
+        assert_eq!(error.status(), 405);
+        assert_eq!(
+            respond(get(&format!("{}&operationName=A", query), None), "").0,
+            200,
+        );
+
+        assert_eq!(
+            respond(post(JSON, None), r#"{"query": "mutation { bump }"}"#),
+            (200, JSON, json!({ "data": { "bump": 1 } })),
+        );
+    }
+
+    #[test]
+    fn only_get_and_post_are_allowed() {
+        for method in &["PUT", "DELETE", "PATCH", "HEAD", "get"] {
+            let parts = RequestParts {
+                method: (*method).into(),
+                ..post(JSON, None)
+            };
+            let error = reject(parts, r#"{"query": "{ hello }"}"#);
+            assert_eq!(error.status(), 405);
+            assert_eq!(error.allow(), Some("GET, POST"));
+        }
+    }
+
+    #[test]
+    fn post_requests_have_a_json_body() {
+        for content_type in &[
+            "application/json",
+            "application/json; charset=utf-8",
+            "Application/JSON;charset=\"UTF-8\"",
+        ] {
+            assert_eq!(
+                respond(post(content_type, None), r#"{"query": "{ hello }"}"#).0,
+                200,
+            );
+        }
+
+        for content_type in &[
+            "",
+            "text/plain",
+            "application/graphql",
+            "application/x-www-form-urlencoded",
+            "application/json; charset=latin1",
+        ] {
+            let error = reject(post(content_type, None), r#"{"query": "{ hello }"}"#);
+            assert_eq!(error.status(), 415, "{}", content_type);
+        }
+    }
+
+    #[test]
+    fn malformed_requests_are_bad() {
+        for body in &[
+            "",
+            "{",
+            "[]",
+            r#"[{"query": "{ hello }"}]"#,
+            r#""{ hello }""#,
+            "{}",
+            r#"{"query": null}"#,
+            r#"{"query": 1}"#,
+            r#"{"query": "{ hello }", "operationName": 1}"#,
+            r#"{"query": "{ hello }", "variables": "{}"}"#,
+            r#"{"query": "{ hello }", "variables": []}"#,
+            r#"{"query": "{ hello }", "extensions": 1}"#,
+        ] {
+            assert_eq!(reject(post(JSON, None), body).status(), 400, "{}", body);
+        }
+
+        for query in &[
+            "",
+            "operationName=A",
+            "query=%7B%20hello%20%7D&query=%7B%20hello%20%7D",
+            "query=%7B%20hello%20%7D&variables=%7B",
+            "query=%7B%20hello%20%7D&variables=1",
+            "query=%7B%20hello%20%7D%zz",
+            "query=%7B%20hello%20%7D%+1",
+            "query=%7B%20hello%20%7D%1",
+            "query=%FF",
+        ] {
+            assert_eq!(reject(get(query, None), "").status(), 400, "{}", query);
+        }
+    }
+
+    #[test]
+    fn unknown_get_parameters_are_ignored() {
+        assert_eq!(
+            respond(get("query=%7B%20hello%20%7D&a=1&a=2&b=%zz", None), ""),
+            (200, JSON, json!({ "data": { "hello": "Hello, world" } })),
+        );
+    }
+
+    #[test]
+    fn null_parameters_are_omitted() {
+        let body = r#"{"query": "{ hello }", "operationName": null, "variables": null, "extensions": null}"#;
+        assert_eq!(
+            respond(post(JSON, None), body),
+            (200, JSON, json!({ "data": { "hello": "Hello, world" } })),
+        );
+    }
+
+    #[test]
+    fn responses_have_the_accepted_content_type() {
+        for (accept, content_type) in &[
+            (None, JSON),
+            (Some(""), JSON),
+            (Some("application/json"), JSON),
+            (Some(GRAPHQL_RESPONSE_JSON), GRAPHQL_RESPONSE_JSON),
+            (
+                Some("application/json, application/graphql-response+json"),
+                GRAPHQL_RESPONSE_JSON,
+            ),
+            (
+                Some("application/graphql-response+json;q=0.5, application/json"),
+                JSON,
+            ),
+            (
+                Some("application/graphql-response+json, application/json;q=0.9"),
+                GRAPHQL_RESPONSE_JSON,
+            ),
+            (Some("*/*"), JSON),
+            (Some("text/html, application/*;q=0.8"), JSON),
+        ] {
+            let response = respond(post(JSON, *accept), r#"{"query": "{ hello }"}"#);
+            assert_eq!(response.1, *content_type, "{:?}", accept);
+        }
+
+        for accept in &["text/html", "application/xml, application/json;q=0"] {
+            let error = reject(post(JSON, Some(accept)), r#"{"query": "{ hello }"}"#);
+            assert_eq!(error.status(), 406, "{}", accept);
+        }
+    }
+
+    #[test]
+    fn request_errors_depend_on_the_content_type() {
+        for body in &[
+            r#"{"query": "{ hello"}"#,
+            r#"{"query": "{ unknown }"}"#,
+            r#"{"query": "query A { hello }", "operationName": "B"}"#,
+        ] {
+            let (status, content_type, response) =
+                respond(post(JSON, Some(GRAPHQL_RESPONSE_JSON)), body);
+            assert_eq!(
+                (status, content_type),
+                (400, GRAPHQL_RESPONSE_JSON),
+                "{}",
+                body
+            );
+            assert!(response.get("data").is_none());
+            assert!(response.get("errors").is_some());
+
+            let (status, content_type, _) = respond(post(JSON, None), body);
+            assert_eq!((status, content_type), (200, JSON), "{}", body);
+        }
+
+        let (status, _, _) = respond(get("query=%7B%20hello", None), "");
+        assert_eq!(status, 200);
+    }
+}
@@ -43,7 +43,7 @@ where
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
         if let ScalarToken::String(value) = value {
-            Ok(S::from(value.to_owned()))
+            Ok(S::from_string(value.to_owned()))
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -66,7 +66,7 @@ where
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
         if let ScalarToken::String(value) = value {
-            Ok(S::from(value.to_owned()))
+            Ok(S::from_string(value.to_owned()))
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -94,7 +94,7 @@ where
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
         if let ScalarToken::String(value) = value {
-            Ok(S::from(value.to_owned()))
+            Ok(S::from_string(value.to_owned()))
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -118,7 +118,7 @@ where
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
         if let ScalarToken::String(value) = value {
-            Ok(S::from(value.to_owned()))
+            Ok(S::from_string(value.to_owned()))
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -326,7 +326,11 @@ mod integration_test {
             EmptySubscription::<()>::new(),
         );
 
-        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(doc, None, &schema, &Variables::new(), &())
             .await
             .expect("Execution failed");
 
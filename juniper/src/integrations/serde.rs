@@ -5,10 +5,10 @@ use serde::{
     Serialize,
 };
 
-use std::fmt;
+use std::{fmt, iter, slice};
 
 use crate::{
-    ast::InputValue,
+    ast::{FromInputError, InputValue},
     executor::ExecutionError,
     parser::{ParseError, SourcePosition, Spanning},
     validation::RuleError,
@@ -285,6 +285,285 @@ where
     }
 }
 
+impl de::Error for FromInputError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FromInputError::new(msg.to_string())
+    }
+}
+
+/// Deserializer of the Rust data types from an input value, see
+/// [`InputValue::deserialize_into`](../../enum.InputValue.html#method.deserialize_into)
+pub(crate) struct InputValueDeserializer<'de, S>(pub(crate) &'de InputValue<S>);
+
+impl<'de, S> de::Deserializer<'de> for InputValueDeserializer<'de, S>
+where
+    S: ScalarValue,
+{
+    type Error = FromInputError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            InputValue::Null => visitor.visit_unit(),
+            InputValue::Scalar(s) => {
+                if let Some(b) = s.as_boolean() {
+                    visitor.visit_bool(b)
+                } else if let Some(i) = s.as_int() {
+                    visitor.visit_i32(i)
+                } else if let Some(s) = s.as_str() {
+                    visitor.visit_borrowed_str(s)
+                } else if let Some(f) = s.as_float() {
+                    visitor.visit_f64(f)
+                } else {
+                    Err(de::Error::custom(format!("unsupported scalar {}", s)))
+                }
+            }
+            InputValue::Enum(e) => visitor.visit_borrowed_str(e),
+            InputValue::Variable(name) => {
+                Err(de::Error::custom(format!("unresolved variable ${}", name,)))
+            }
+            InputValue::List(items) => visitor.visit_seq(ListAccess {
+                items: items.iter().enumerate(),
+            }),
+            InputValue::Object(fields) => visitor.visit_map(ObjectAccess {
+                fields: fields.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.0 {
+            InputValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (variant, value) = match self.0 {
+            InputValue::Enum(variant) => (variant.as_str(), None),
+            InputValue::Object(fields) if fields.len() == 1 => {
+                let (variant, value) = &fields[0];
+                (variant.item.as_str(), Some(&value.item))
+            }
+            value => match value.as_string_value() {
+                Some(variant) => (variant, None),
+                None => return Err(de::Error::invalid_type(unexpected(value), &"an enum value")),
+            },
+        };
+        visitor.visit_enum(VariantAccess { variant, value })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// How an input value is reported when it doesn't fit the type
+fn unexpected<S: ScalarValue>(value: &InputValue<S>) -> de::Unexpected<'_> {
+    match value {
+        InputValue::Null => de::Unexpected::Unit,
+        InputValue::Scalar(s) => match (s.as_boolean(), s.as_int(), s.as_str(), s.as_float()) {
+            (Some(b), ..) => de::Unexpected::Bool(b),
+            (_, Some(i), ..) => de::Unexpected::Signed(i.into()),
+            (_, _, Some(s), _) => de::Unexpected::Str(s),
+            (.., Some(f)) => de::Unexpected::Float(f),
+            _ => de::Unexpected::Other("scalar"),
+        },
+        InputValue::Enum(_) => de::Unexpected::Other("enum value"),
+        InputValue::Variable(_) => de::Unexpected::Other("variable"),
+        InputValue::List(_) => de::Unexpected::Seq,
+        InputValue::Object(_) => de::Unexpected::Map,
+    }
+}
+
+struct ListAccess<'de, S> {
+    items: iter::Enumerate<slice::Iter<'de, Spanning<InputValue<S>>>>,
+}
+
+impl<'de, S> de::SeqAccess<'de> for ListAccess<'de, S>
+where
+    S: ScalarValue,
+{
+    type Error = FromInputError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, FromInputError>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.items.next() {
+            Some((index, item)) => seed
+                .deserialize(InputValueDeserializer(&item.item))
+                .map(Some)
+                .map_err(|e| e.at(index.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct ObjectAccess<'de, S> {
+    fields: slice::Iter<'de, (Spanning<String>, Spanning<InputValue<S>>)>,
+    value: Option<(&'de str, &'de InputValue<S>)>,
+}
+
+impl<'de, S> de::MapAccess<'de> for ObjectAccess<'de, S>
+where
+    S: ScalarValue,
+{
+    type Error = FromInputError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, FromInputError>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.fields.next() {
+            Some((name, value)) => {
+                self.value = Some((&name.item, &value.item));
+                seed.deserialize(de::value::BorrowedStrDeserializer::new(&name.item))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, FromInputError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let (name, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(InputValueDeserializer(value))
+            .map_err(|e| e.at(name))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+/// Variant of an enum, either an enum value or an object with a single field
+/// holding the content of the variant
+struct VariantAccess<'de, S> {
+    variant: &'de str,
+    value: Option<&'de InputValue<S>>,
+}
+
+impl<'de, S> de::EnumAccess<'de> for VariantAccess<'de, S>
+where
+    S: ScalarValue,
+{
+    type Error = FromInputError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), FromInputError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(de::value::BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, S> de::VariantAccess<'de> for VariantAccess<'de, S>
+where
+    S: ScalarValue,
+{
+    type Error = FromInputError;
+
+    fn unit_variant(self) -> Result<(), FromInputError> {
+        match self.value {
+            None | Some(InputValue::Null) => Ok(()),
+            Some(value) => Err(de::Error::invalid_type(
+                unexpected(value),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, FromInputError>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed
+                .deserialize(InputValueDeserializer(value))
+                .map_err(|e| e.at(self.variant)),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"a newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => {
+                de::Deserializer::deserialize_seq(InputValueDeserializer(value), visitor)
+                    .map_err(|e| e.at(self.variant))
+            }
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"a tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FromInputError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => {
+                de::Deserializer::deserialize_map(InputValueDeserializer(value), visitor)
+                    .map_err(|e| e.at(self.variant))
+            }
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"a struct variant",
+            )),
+        }
+    }
+}
+
 impl<T> ser::Serialize for InputValue<T>
 where
     T: ScalarValue,
@@ -381,7 +660,7 @@ where
     {
         let mut map = serializer.serialize_map(Some(self.field_count()))?;
 
-        for &(ref f, ref v) in self.iter() {
+        for (f, v) in self.iter() {
             map.serialize_key(f)?;
             map.serialize_value(v)?;
         }
@@ -407,16 +686,75 @@ where
     }
 }
 
+impl<'de, S> de::Deserialize<'de> for Value<S>
+where
+    S: ScalarValue,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Value<S>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        fn into_value<S: ScalarValue>(input: InputValue<S>) -> Value<S> {
+            match input {
+                InputValue::Null | InputValue::Variable(_) => Value::null(),
+                InputValue::Scalar(s) => Value::Scalar(s),
+                InputValue::Enum(e) => Value::Scalar(S::from_string(e)),
+                InputValue::List(l) => {
+                    Value::list(l.into_iter().map(|v| into_value(v.item)).collect())
+                }
+                InputValue::Object(o) => {
+                    let mut object = Object::with_capacity(o.len());
+                    for (k, v) in o {
+                        object.add_field(k.item, into_value(v.item));
+                    }
+                    Value::object(object)
+                }
+            }
+        }
+
+        InputValue::deserialize(deserializer).map(into_value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use serde::Deserialize;
+
     use super::{ExecutionError, GraphQLError};
     use crate::{
-        ast::InputValue,
+        ast::{FromInputError, InputValue},
         value::{DefaultScalarValue, Object},
-        FieldError, Value,
+        FieldError, ToInputValue, Value,
     };
     use serde_json::{from_str, to_string};
 
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Filter<'a> {
+        name: &'a str,
+        tags: Vec<String>,
+        limit: Option<u8>,
+        order: Order,
+        range: Option<Range>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum Order {
+        Asc,
+        Desc,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    enum Range {
+        Between { from: f64, to: f64 },
+        AtLeast(f64),
+    }
+
+    fn input(value: Value) -> InputValue {
+        value.to_input_value()
+    }
+
     #[test]
     fn int() {
         assert_eq!(
@@ -459,4 +797,84 @@ mod tests {
             r#"{"message":"foo error","locations":[{"line":1,"column":1}],"path":[],"extensions":{"foo":"bar"}}"#
         );
     }
+
+    #[test]
+    fn deserializes_input_values() {
+        let value = InputValue::object(
+            vec![
+                ("name", InputValue::scalar("rust")),
+                ("tags", input(graphql_value!(["lang", "systems"]))),
+                ("limit", InputValue::scalar(10)),
+                ("order", InputValue::enum_value("DESC")),
+                (
+                    "range",
+                    input(graphql_value!({ "between": { "from": 1, "to": 2.5 } })),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(
+            value.deserialize_into::<Filter>(),
+            Ok(Filter {
+                name: "rust",
+                tags: vec!["lang".into(), "systems".into()],
+                limit: Some(10),
+                order: Order::Desc,
+                range: Some(Range::Between { from: 1.0, to: 2.5 }),
+            }),
+        );
+        assert_eq!(
+            input(graphql_value!({ "atLeast": 3 })).deserialize_into::<Range>(),
+            Ok(Range::AtLeast(3.0)),
+        );
+        assert_eq!(
+            input(graphql_value!([None, "ASC"])).deserialize_into::<Vec<Option<Order>>>(),
+            Ok(vec![None, Some(Order::Asc)]),
+        );
+    }
+
+    #[test]
+    fn locates_deserialization_errors() {
+        let value = input(graphql_value!({
+            "name": "rust",
+            "tags": ["lang", 7],
+            "order": "ASC",
+        }));
+
+        let error = value.deserialize_into::<Filter>().unwrap_err();
+
+        assert_eq!(error.path(), ["tags", "1"]);
+        assert_eq!(
+            error.to_string(),
+            r#"At "tags.1": invalid type: integer `7`, expected a string"#,
+        );
+
+        let errors = vec![
+            input(graphql_value!({ "name": "rust", "tags": [], "order": "UP" }))
+                .deserialize_into::<Filter>(),
+            input(graphql_value!({ "name": "rust", "tags": [], "order": "ASC", "limit": 300 }))
+                .deserialize_into::<Filter>(),
+            InputValue::<DefaultScalarValue>::variable("filter").deserialize_into::<Filter>(),
+        ]
+        .into_iter()
+        .map(|r| r.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                r#"At "order": unknown variant `UP`, expected `ASC` or `DESC`"#,
+                r#"At "limit": invalid value: integer `300`, expected u8"#,
+                "unresolved variable $filter",
+            ],
+        );
+        assert_eq!(
+            input(graphql_value!(1)).deserialize_into::<String>(),
+            Err(FromInputError::new(
+                "invalid type: integer `1`, expected a string"
+            )),
+        );
+    }
 }
@@ -0,0 +1,439 @@
+//! Building the model of a schema from the result of the introspection query,
+//! e.g. to validate documents against the schema of another server
+
+use std::borrow::Cow;
+
+use crate::{
+    ast::{InputValue, Type},
+    executor::Registry,
+    parser::{parse_value_literal, Lexer, Parser, Token},
+    schema::{
+        meta::{
+            accept_any_input, reject_scalar_token, reject_unlisted_input, Argument, EnumMeta,
+            EnumValue, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, ScalarMeta,
+            UnionMeta,
+        },
+        model::{DirectiveLocation, DirectiveType, SchemaError, SchemaType},
+    },
+    types::{name::Name, scalars::ID},
+    value::{ScalarValue, Value},
+};
+
+impl<'a, S> SchemaType<'a, S>
+where
+    S: ScalarValue + 'a,
+{
+    /// Build the model of the schema described by the result of the
+    /// [introspection query](../fn.introspect.html)
+    ///
+    /// The `result` is either the data of the query, holding `__schema`, or
+    /// the whole response, holding `data`, like the JSON returned by another
+    /// server. The model serves to compare, merge or validate documents
+    /// against the schema, but not to execute them, having no resolvers:
+    ///
+    /// - custom scalars accept any value;
+    /// - the directives only used in the schema language, like `@deprecated`,
+    ///   are left out.
+    ///
+    /// Returns [`SchemaError::MalformedIntrospection`] if the `result`
+    /// doesn't describe a schema, e.g. referencing undefined types, and
+    /// [`SchemaError::Invalid`] if the schema breaks the type system rules.
+    ///
+    /// [`SchemaError::MalformedIntrospection`]: enum.SchemaError.html#variant.MalformedIntrospection
+    /// [`SchemaError::Invalid`]: enum.SchemaError.html#variant.Invalid
+    pub fn from_introspection(result: &Value<S>) -> Result<Self, SchemaError> {
+        let root = Member {
+            value: Some(result),
+            path: String::new(),
+        };
+        let data = root.get("data")?;
+        let schema = if data.value.is_some() {
+            data.get("__schema")?
+        } else {
+            root.get("__schema")?
+        };
+
+        // Default values are parsed as literals of their types, so the types
+        // are imported once without them beforehand.
+        let types = Importer::new(None).import(&schema)?;
+        Importer::new(Some(&types)).import(&schema)
+    }
+}
+
+/// Member of the introspection result, along with its path for the errors
+struct Member<'v, S> {
+    /// The value of the member, `None` if missing or null
+    value: Option<&'v Value<S>>,
+    path: String,
+}
+
+impl<'v, S: ScalarValue> Member<'v, S> {
+    fn get(&self, name: &str) -> Result<Self, SchemaError> {
+        let object = self
+            .value
+            .and_then(Value::as_object_value)
+            .ok_or_else(|| self.error("expected an object"))?;
+        Ok(Member {
+            value: object.get_field_value(name).filter(|v| !v.is_null()),
+            path: if self.path.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{}.{}", self.path, name)
+            },
+        })
+    }
+
+    /// The items of the list, none if the member is missing or null
+    fn items(&self) -> Result<Vec<Self>, SchemaError> {
+        let items = match self.value {
+            Some(value) => value
+                .as_list_value()
+                .ok_or_else(|| self.error("expected a list"))?,
+            None => return Ok(vec![]),
+        };
+        Ok(items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| Member {
+                value: Some(item).filter(|v| !v.is_null()),
+                path: format!("{}[{}]", self.path, i),
+            })
+            .collect())
+    }
+
+    fn str(&self) -> Result<&'v str, SchemaError> {
+        self.opt_str()?
+            .ok_or_else(|| self.error("expected a string"))
+    }
+
+    fn opt_str(&self) -> Result<Option<&'v str>, SchemaError> {
+        self.value
+            .map(|v| {
+                v.as_scalar()
+                    .and_then(ScalarValue::as_str)
+                    .ok_or_else(|| self.error("expected a string"))
+            })
+            .transpose()
+    }
+
+    /// The value of the boolean, `false` if the member is missing or null
+    fn bool(&self) -> Result<bool, SchemaError> {
+        self.value
+            .map(|v| {
+                v.as_scalar()
+                    .and_then(ScalarValue::as_boolean)
+                    .ok_or_else(|| self.error("expected a boolean"))
+            })
+            .unwrap_or(Ok(false))
+    }
+
+    /// A name of a type, field, argument or value
+    fn name(&self) -> Result<&'v str, SchemaError> {
+        let name = self.str()?;
+        if !Name::is_valid(name) {
+            return Err(self.error(format!("\"{}\" is not a valid GraphQL name", name)));
+        }
+        Ok(name)
+    }
+
+    fn error<M: Into<String>>(&self, message: M) -> SchemaError {
+        SchemaError::MalformedIntrospection {
+            path: self.path.clone(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Registers the types described by the introspection result
+struct Importer<'a, 's, S> {
+    registry: Registry<'a, S>,
+    /// The types referenced by fields and arguments, with their path
+    references: Vec<(String, String)>,
+    /// The schema to parse the default values against, if they're imported
+    defaults_schema: Option<&'s SchemaType<'a, S>>,
+}
+
+impl<'a, 's, S> Importer<'a, 's, S>
+where
+    S: ScalarValue + 'a,
+{
+    fn new(defaults_schema: Option<&'s SchemaType<'a, S>>) -> Self {
+        Importer {
+            registry: Registry::new(Default::default()),
+            references: vec![],
+            defaults_schema,
+        }
+    }
+
+    fn import(mut self, schema: &Member<S>) -> Result<SchemaType<'a, S>, SchemaError> {
+        for meta_type in schema.get("types")?.items()? {
+            self.import_type(&meta_type)?;
+        }
+
+        let mut directives = vec![];
+        for directive in schema.get("directives")?.items()? {
+            if let Some(directive) = self.directive(&directive)? {
+                directives.push(directive);
+            }
+        }
+
+        for (name, path) in &self.references {
+            if !self.registry.types.contains_key(name.as_str()) {
+                return Err(SchemaError::MalformedIntrospection {
+                    path: path.clone(),
+                    message: format!("type \"{}\" is not defined", name),
+                });
+            }
+        }
+
+        let query_type = schema.get("queryType")?;
+        let query_type = self
+            .root_type(&query_type)?
+            .ok_or_else(|| query_type.error("expected an object"))?;
+        let mutation_type = self.root_type(&schema.get("mutationType")?)?;
+        let subscription_type = self.root_type(&schema.get("subscriptionType")?)?;
+
+        let mut imported =
+            SchemaType::from_registry(self.registry, query_type, mutation_type, subscription_type)?;
+        for directive in directives {
+            imported.add_directive(directive);
+        }
+        Ok(imported)
+    }
+
+    fn import_type(&mut self, meta_type: &Member<S>) -> Result<(), SchemaError> {
+        let name = meta_type.get("name")?.name()?;
+        if name.starts_with("__") {
+            // Introspection types, registered along with the schema
+            return Ok(());
+        }
+        let description = meta_type.get("description")?.opt_str()?.map(str::to_owned);
+
+        let kind = meta_type.get("kind")?;
+        let meta = match kind.str()? {
+            "SCALAR" => {
+                match name {
+                    "Int" => self.registry.get_type::<i32>(&()),
+                    "Float" => self.registry.get_type::<f64>(&()),
+                    "String" => self.registry.get_type::<String>(&()),
+                    "Boolean" => self.registry.get_type::<bool>(&()),
+                    "ID" => self.registry.get_type::<ID>(&()),
+                    _ => {
+                        self.check_undefined(meta_type, name)?;
+                        self.registry.register_type(MetaType::Scalar(ScalarMeta {
+                            name: Cow::Owned(name.to_owned()),
+                            description,
+                            try_parse_fn: accept_any_input,
+                            parse_fn: reject_scalar_token,
+                        }))
+                    }
+                };
+                return Ok(());
+            }
+            "OBJECT" => {
+                let mut fields = self.fields(meta_type)?;
+                fields.push(self.registry.field::<String>("__typename", &()));
+                let mut meta = ObjectMeta::new(Cow::Owned(name.to_owned()), &fields);
+                meta.description = description;
+                meta.interface_names = self.type_names(&meta_type.get("interfaces")?)?;
+                MetaType::Object(meta)
+            }
+            "INTERFACE" => {
+                let mut fields = self.fields(meta_type)?;
+                fields.push(self.registry.field::<String>("__typename", &()));
+                let mut meta = InterfaceMeta::new(Cow::Owned(name.to_owned()), &fields);
+                meta.description = description;
+                meta.instance_type_names = Some(self.type_names(&meta_type.get("possibleTypes")?)?);
+                MetaType::Interface(meta)
+            }
+            "UNION" => {
+                let mut meta = UnionMeta::new(Cow::Owned(name.to_owned()), &[]);
+                meta.description = description;
+                meta.of_type_names = self.type_names(&meta_type.get("possibleTypes")?)?;
+                MetaType::Union(meta)
+            }
+            "ENUM" => {
+                let mut values = vec![];
+                for value in meta_type.get("enumValues")?.items()? {
+                    let mut enum_value = EnumValue::new(value.get("name")?.name()?);
+                    enum_value.description =
+                        value.get("description")?.opt_str()?.map(str::to_owned);
+                    if value.get("isDeprecated")?.bool()? {
+                        enum_value =
+                            enum_value.deprecated(value.get("deprecationReason")?.opt_str()?);
+                    }
+                    values.push(enum_value);
+                }
+                MetaType::Enum(EnumMeta {
+                    name: Cow::Owned(name.to_owned()),
+                    description,
+                    values,
+                    try_parse_fn: reject_unlisted_input,
+                })
+            }
+            "INPUT_OBJECT" => {
+                let mut input_fields = vec![];
+                for field in meta_type.get("inputFields")?.items()? {
+                    input_fields.push(self.argument(&field)?);
+                }
+                MetaType::InputObject(InputObjectMeta {
+                    name: Cow::Owned(name.to_owned()),
+                    description,
+                    input_fields,
+                    try_parse_fn: accept_any_input,
+                })
+            }
+            other => {
+                return Err(kind.error(format!("\"{}\" is not the kind of a named type", other)))
+            }
+        };
+        self.check_undefined(meta_type, name)?;
+        self.registry.register_type(meta);
+        Ok(())
+    }
+
+    fn check_undefined(&self, meta_type: &Member<S>, name: &str) -> Result<(), SchemaError> {
+        if self.registry.types.contains_key(name) {
+            return Err(meta_type.error(format!("type \"{}\" is defined twice", name)));
+        }
+        Ok(())
+    }
+
+    fn fields(&mut self, meta_type: &Member<S>) -> Result<Vec<Field<'a, S>>, SchemaError> {
+        let mut fields = vec![];
+        for member in meta_type.get("fields")?.items()? {
+            let field_type = self.type_ref(&member.get("type")?)?;
+            let mut field = Field::new(member.get("name")?.name()?, field_type);
+            field.description = member.get("description")?.opt_str()?.map(str::to_owned);
+            for argument in member.get("args")?.items()? {
+                field = field.argument(self.argument(&argument)?);
+            }
+            if member.get("isDeprecated")?.bool()? {
+                field = field.deprecated(member.get("deprecationReason")?.opt_str()?);
+            }
+            fields.push(field);
+        }
+        Ok(fields)
+    }
+
+    /// An argument of a field or directive, or a field of an input object
+    fn argument(&mut self, member: &Member<S>) -> Result<Argument<'a, S>, SchemaError> {
+        let arg_type = self.type_ref(&member.get("type")?)?;
+        let mut argument = Argument::new(member.get("name")?.name()?, arg_type);
+        argument.description = member.get("description")?.opt_str()?.map(str::to_owned);
+        if let Some(schema) = self.defaults_schema {
+            let default_value = member.get("defaultValue")?;
+            if let Some(literal) = default_value.opt_str()? {
+                argument.default_value = Some(parse_default_value(
+                    schema,
+                    &default_value,
+                    literal,
+                    &argument.arg_type,
+                )?);
+            }
+        }
+        Ok(argument)
+    }
+
+    fn type_ref(&mut self, member: &Member<S>) -> Result<Type<'a>, SchemaError> {
+        match member.get("kind")?.str()? {
+            "NON_NULL" => match self.type_ref(&member.get("ofType")?)? {
+                Type::Named(name) => Ok(Type::NonNullNamed(name)),
+                Type::List(inner) => Ok(Type::NonNullList(inner)),
+                _ => Err(member.error("non-null type wraps a non-null type")),
+            },
+            "LIST" => Ok(Type::List(Box::new(self.type_ref(&member.get("ofType")?)?))),
+            _ => {
+                let name = member.get("name")?;
+                let type_name = name.name()?.to_owned();
+                self.references.push((type_name.clone(), name.path));
+                Ok(Type::Named(Cow::Owned(type_name)))
+            }
+        }
+    }
+
+    /// The names of the types listed as interfaces or possible types
+    fn type_names(&mut self, member: &Member<S>) -> Result<Vec<String>, SchemaError> {
+        member
+            .items()?
+            .iter()
+            .map(|item| {
+                self.type_ref(item)
+                    .map(|of_type| of_type.innermost_name().to_owned())
+            })
+            .collect()
+    }
+
+    /// The name of the query, mutation or subscription type, `None` if missing
+    fn root_type(&self, member: &Member<S>) -> Result<Option<String>, SchemaError> {
+        let name = match member.value {
+            Some(_) => member.get("name")?,
+            None => return Ok(None),
+        };
+        match self.registry.types.get(name.str()?) {
+            Some(MetaType::Object(_)) => Ok(Some(name.str()?.to_owned())),
+            _ => Err(name.error(format!("\"{}\" is not an object type", name.str()?))),
+        }
+    }
+
+    /// A directive used in executable documents, `None` for the ones only
+    /// used in the schema language, or specified by GraphQL
+    fn directive(
+        &mut self,
+        member: &Member<S>,
+    ) -> Result<Option<DirectiveType<'a, S>>, SchemaError> {
+        let name = member.get("name")?.name()?;
+        if name == "skip" || name == "include" {
+            return Ok(None);
+        }
+
+        let mut locations = vec![];
+        for location in member.get("locations")?.items()? {
+            locations.extend(directive_location(location.str()?));
+        }
+        if locations.is_empty() {
+            return Ok(None);
+        }
+
+        let mut arguments = vec![];
+        for argument in member.get("args")?.items()? {
+            arguments.push(self.argument(&argument)?);
+        }
+        let mut directive = DirectiveType::new(name, &locations, &arguments);
+        directive.description = member.get("description")?.opt_str()?.map(str::to_owned);
+        Ok(Some(directive))
+    }
+}
+
+/// The location of a directive in executable documents, `None` for the
+/// locations in the schema language
+fn directive_location(name: &str) -> Option<DirectiveLocation> {
+    match name {
+        "QUERY" => Some(DirectiveLocation::Query),
+        "MUTATION" => Some(DirectiveLocation::Mutation),
+        "SUBSCRIPTION" => Some(DirectiveLocation::Subscription),
+        "FIELD" => Some(DirectiveLocation::Field),
+        "FRAGMENT_DEFINITION" => Some(DirectiveLocation::FragmentDefinition),
+        "FRAGMENT_SPREAD" => Some(DirectiveLocation::FragmentSpread),
+        "INLINE_FRAGMENT" => Some(DirectiveLocation::InlineFragment),
+        _ => None,
+    }
+}
+
+fn parse_default_value<S: ScalarValue>(
+    schema: &SchemaType<S>,
+    member: &Member<S>,
+    literal: &str,
+    arg_type: &Type,
+) -> Result<InputValue<S>, SchemaError> {
+    let invalid = || member.error(format!("`{}` is not a valid default value", literal));
+
+    let mut lexer = Lexer::new(literal);
+    let mut parser = Parser::new(&mut lexer).map_err(|_| invalid())?;
+    let value = parse_value_literal(&mut parser, true, schema, schema.lookup_type(arg_type))
+        .map_err(|_| invalid())?;
+    if parser.peek().item != Token::EndOfFile {
+        return Err(invalid());
+    }
+    Ok(value.item)
+}
@@ -1,7 +1,14 @@
+mod import;
+
+use std::collections::HashSet;
+
+use crate::{ast::Selection, executor::Executor, value::ScalarValue};
+
 /// From <https://github.com/graphql/graphql-js/blob/8c96dc8276f2de27b8af9ffbd71a4597d483523f/src/utilities/introspectionQuery.js#L21>
 pub(crate) const INTROSPECTION_QUERY: &str = include_str!("./query.graphql");
 pub(crate) const INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS: &str =
     include_str!("./query_without_descriptions.graphql");
+pub(crate) const TYPE_INDEX_QUERY: &str = include_str!("./query_type_index.graphql");
 
 /// The desired GraphQL introspection format for the canonical query
 /// (<https://github.com/graphql/graphql-js/blob/8c96dc8276f2de27b8af9ffbd71a4597d483523f/src/utilities/introspectionQuery.js#L21>)
@@ -10,6 +17,11 @@ pub enum IntrospectionFormat {
     All,
     /// The canonical GraphQL introspection query without descriptions.
     WithoutDescriptions,
+    /// The kinds and names of the types of the schema, and its root types.
+    ///
+    /// A lightweight index of large schemas, whose types are then introspected
+    /// one by one with `__type(name: ...)`.
+    TypeIndex,
 }
 
 impl Default for IntrospectionFormat {
@@ -17,3 +29,44 @@ impl Default for IntrospectionFormat {
         IntrospectionFormat::All
     }
 }
+
+/// The number of levels of selections below the introspection field being
+/// resolved by the `executor`, following fragment spreads
+pub(crate) fn selection_depth<CtxT, S: ScalarValue>(executor: &Executor<CtxT, S>) -> usize {
+    fn depth<'a, CtxT, S: ScalarValue>(
+        selection_set: &'a [Selection<'a, S>],
+        executor: &'a Executor<CtxT, S>,
+        spreading: &mut HashSet<&'a str>,
+    ) -> usize {
+        selection_set
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(f) => {
+                    1 + f
+                        .item
+                        .selection_set
+                        .as_ref()
+                        .map_or(0, |selection_set| depth(selection_set, executor, spreading))
+                }
+                Selection::InlineFragment(f) => depth(&f.item.selection_set, executor, spreading),
+                Selection::FragmentSpread(s) => {
+                    let name = s.item.name.item;
+                    // Cycles are rejected by the validation already.
+                    match executor.fragment_by_name(name) {
+                        Some(fragment) if spreading.insert(name) => {
+                            let d = depth(&fragment.selection_set, executor, spreading);
+                            spreading.remove(name);
+                            d
+                        }
+                        _ => 0,
+                    }
+                }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    executor.current_selection_set().map_or(0, |selection_set| {
+        depth(selection_set, executor, &mut HashSet::new())
+    })
+}
@@ -66,6 +66,21 @@ your Schemas automatically.
 * [rocket][rocket]
 * [iron][iron]
 
+### WebAssembly
+
+Juniper compiles for `wasm32-unknown-unknown`, so the parser and the
+validation of documents against a schema (see [`validate_document`]) can run in
+browsers and edge runtimes. Disabling the default features leaves out the
+integrations with other crates, for the smallest set of dependencies:
+
+```toml
+[dependencies]
+juniper = { version = "0.14", default-features = false }
+```
+
+`InMemoryCache` isn't available on this target, as it relies on
+`std::time::Instant`.
+
 
 ## API Stability
 
@@ -97,7 +112,7 @@ Juniper has not reached 1.0 yet, thus some API instability should be expected.
 // path correctly, without errors.
 extern crate self as juniper;
 
-use std::fmt;
+use std::{collections::HashSet, fmt};
 
 #[doc(hidden)]
 pub extern crate serde;
@@ -105,16 +120,16 @@ pub extern crate serde;
 #[cfg(any(test, feature = "expose-test-schema"))]
 extern crate serde_json;
 
-#[cfg(any(test, feature = "chrono"))]
+#[cfg(feature = "chrono")]
 extern crate chrono;
 
-#[cfg(any(test, feature = "url"))]
+#[cfg(feature = "url")]
 extern crate url;
 
-#[cfg(any(test, feature = "uuid"))]
+#[cfg(feature = "uuid")]
 extern crate uuid;
 
-#[cfg(any(test, feature = "bson"))]
+#[cfg(feature = "bson")]
 extern crate bson;
 
 // These are required by the code generated via the `juniper_codegen` macros.
@@ -136,12 +151,24 @@ pub use juniper_codegen::{
 mod value;
 #[macro_use]
 mod macros;
-mod ast;
+pub mod ast;
+pub mod auth;
+pub mod cache;
+pub mod deprecation;
 mod executor;
 mod introspection;
+pub mod messages;
+pub mod operations;
 pub mod parser;
+pub mod rate_limit;
+#[cfg(feature = "relay")]
+pub mod relay;
+pub mod rewrite;
+pub mod schedule;
 pub(crate) mod schema;
+mod stack;
 mod types;
+pub mod usage;
 mod util;
 mod validation;
 // This needs to be public until docs have support for private modules:
@@ -158,40 +185,59 @@ pub mod tests;
 mod executor_tests;
 
 // Needs to be public because macros use it.
+#[doc(hidden)]
+pub use crate::macros::interface_helpers;
 pub use crate::util::to_camel_case;
 
 use crate::{
+    ast::{Definition, Document},
     executor::{execute_validated_query, get_operation},
-    introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS},
+    introspection::{
+        INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS, TYPE_INDEX_QUERY,
+    },
     parser::{parse_document_source, ParseError, Spanning},
     validation::{validate_input_values, visit_all_rules, ValidatorContext},
 };
 
 pub use crate::{
-    ast::{FromInputValue, InputValue, Selection, ToInputValue, Type},
+    ast::{
+        FromInputError, FromInputValue, InputValue, OperationType, Selection, ToInputValue, Type,
+        TypeWrapper,
+    },
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, ChildSelection, ConcreteLookAheadSelection, Context, ContextCell, EnumBuilder,
+        ExecutionError, ExecutionMetadata, ExecutionOutput, ExecutionResult, Executor, FieldError,
+        FieldPolicy, FieldResult, FieldScope, FromContext, InScope, InputObjectBuilder,
+        IntoFieldError, IntoResolvable, JoinHandle, LookAheadArgument, LookAheadDirective,
+        LookAheadMethods, LookAheadSelection, LookAheadValue, ObjectBuilder, OperationInfo,
+        OptionExt, OwnedExecutor, Registry, ResultExt, Sleep, ValuesStream, VariableInfo,
+        Variables,
     },
     introspection::IntrospectionFormat,
     macros::subscription_helpers::{ExtractTypeFromStream, IntoFieldResult},
     schema::{
         meta,
-        model::{RootNode, SchemaType},
+        model::{RootNode, SchemaError, SchemaType},
     },
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
+        async_local::{GraphQLTypeAsyncLocal, GraphQLValueAsyncLocal},
         base::{Arguments, GraphQLType, GraphQLValue, TypeKind},
+        lazy::Lazy,
         marker::{self, GraphQLUnion, IsOutputType},
-        scalars::{EmptyMutation, EmptySubscription, ID},
+        nullable::Nullable,
+        scalars::{EmptyMutation, EmptySubscription, SharedString, ID},
         subscriptions::{
-            ExecutionOutput, GraphQLSubscriptionType, GraphQLSubscriptionValue,
-            SubscriptionConnection, SubscriptionCoordinator,
+            GraphQLSubscriptionType, GraphQLSubscriptionValue, SubscriptionConnection,
+            SubscriptionCoordinator,
         },
     },
     validation::RuleError,
-    value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+    value::{
+        parse_number_literal, parse_token, DefaultScalarValue, DuplicateFieldError,
+        IntoScalarValue, Object, OnDuplicateField, OrParser, ParseScalarResult, ParseScalarValue,
+        ScalarConversionError, ScalarTokenParser, ScalarValue, Value,
+    },
 };
 
 /// An error that prevented query execution
@@ -228,6 +274,159 @@ impl<'a> fmt::Display for GraphQLError<'a> {
 
 impl<'a> std::error::Error for GraphQLError<'a> {}
 
+/// Parse and validate a document in a provided schema, without executing it
+///
+/// All violated validation rules are reported at once in
+/// [`GraphQLError::ValidationError`], so this can be used to check stored
+/// operations in linters, CI jobs or editor tooling.
+pub fn validate_document<'a, S>(
+    document_source: &'a str,
+    schema: &SchemaType<'a, S>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let document = parse_document(document_source, schema)?;
+    validate_parsed_document(schema, &document)
+}
+
+/// Parse and validate a document in a provided schema, and describe the
+/// selected operation without executing it
+///
+/// The operation is selected by `operation_name` the same way [`execute`] does.
+pub fn operation_info<'a, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    schema: &SchemaType<'a, S>,
+) -> Result<OperationInfo<'a, S>, GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let document = parse_document(document_source, schema)?;
+    validate_parsed_document(schema, &document)?;
+
+    let operation = get_operation(&document, operation_name)?;
+    Ok(OperationInfo::new(&document, operation, schema))
+}
+
+/// Parse and validate a document in a provided schema, and check the
+/// `variables` of the selected operation without executing it
+///
+/// The variables are coerced as [`execute`] does before running any resolver,
+/// so forms can report invalid values, e.g. a missing required variable or a
+/// string given for an `Int`, in [`GraphQLError::ValidationError`] while
+/// they're being filled in.
+pub fn validate_variables<'a, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    schema: &SchemaType<'a, S>,
+    variables: &Variables<S>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let document = parse_document(document_source, schema)?;
+    validate_parsed_document(schema, &document)?;
+
+    let operation = get_operation(&document, operation_name)?;
+    let errors = validate_input_values(variables, operation, schema);
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
+    }
+
+    Ok(())
+}
+
+/// Parse a document, completing it with the fragments of the schema's
+/// fragment library it spreads
+fn parse_document<'a, S>(
+    document_source: &'a str,
+    schema: &SchemaType<'a, S>,
+) -> Result<Document<'a, S>, GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let mut document = parse_document_source(document_source, schema)?;
+    if schema.fragment_library.is_empty() {
+        return Ok(document);
+    }
+
+    let mut defined = HashSet::new();
+    let mut spread = vec![];
+    for definition in &document {
+        match definition {
+            Definition::Operation(op) => collect_spreads(&op.item.selection_set, &mut spread),
+            Definition::Fragment(f) => {
+                defined.insert(f.item.name.item);
+                collect_spreads(&f.item.selection_set, &mut spread);
+            }
+        }
+    }
+    while let Some(name) = spread.pop() {
+        if let Some(fragment) = schema.fragment_library.get(name) {
+            if defined.insert(name) {
+                collect_spreads(&fragment.item.selection_set, &mut spread);
+                document.push(Definition::Fragment(fragment.clone()));
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Collect the names of the fragments spread in the selection set, also
+/// through inline fragments and nested fields
+fn collect_spreads<'a, S>(selection_set: &[Selection<'a, S>], names: &mut Vec<&'a str>) {
+    for selection in selection_set {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(selection_set) = &field.item.selection_set {
+                    collect_spreads(selection_set, names);
+                }
+            }
+            Selection::FragmentSpread(spread) => names.push(spread.item.name.item),
+            Selection::InlineFragment(fragment) => {
+                collect_spreads(&fragment.item.selection_set, names)
+            }
+        }
+    }
+}
+
+fn validate_parsed_document<'a, S>(
+    schema: &SchemaType<S>,
+    document: &Document<S>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let mut ctx = ValidatorContext::new(schema, document);
+    visit_all_rules(&mut ctx, document);
+
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
+    }
+
+    Ok(())
+}
+
+/// Applies the schema's query rewriters to a validated document, validating
+/// the rewritten document again
+fn rewrite_document<'a, S>(
+    schema: &SchemaType<S>,
+    document: &mut Document<S>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    if schema.query_rewriters.is_empty() {
+        return Ok(());
+    }
+
+    schema.query_rewriters.rewrite(document, schema);
+    validate_parsed_document(schema, document)
+}
+
 /// Execute a query synchronously in a provided schema
 pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     document_source: &'a str,
@@ -235,24 +434,42 @@ pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<ExecutionOutput<S>, GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
     MutationT: GraphQLType<S, Context = QueryT::Context>,
     SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
 {
-    let document = parse_document_source(document_source, &root_node.schema)?;
-
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+    execute_sync_with_metadata(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+    )
+    .map(|(output, _)| output)
+}
 
-        let errors = ctx.into_errors();
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
-        }
-    }
+/// Execute a query synchronously in a provided schema, also returning the
+/// [`ExecutionMetadata`](struct.ExecutionMetadata.html), like how long the
+/// response may be cached
+pub fn execute_sync_with_metadata<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let mut document = parse_document(document_source, &root_node.schema)?;
+    validate_parsed_document(&root_node.schema, &document)?;
+    rewrite_document(&root_node.schema, &mut document)?;
 
     let operation = get_operation(&document, operation_name)?;
 
@@ -274,7 +491,38 @@ pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<ExecutionOutput<S>, GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_with_metadata(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+    )
+    .await
+    .map(|(output, _)| output)
+}
+
+/// Execute a query in a provided schema, also returning the
+/// [`ExecutionMetadata`](struct.ExecutionMetadata.html), like how long the
+/// response may be cached
+pub async fn execute_with_metadata<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -285,18 +533,75 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let document = parse_document_source(document_source, &root_node.schema)?;
+    let mut document = parse_document(document_source, &root_node.schema)?;
+    validate_parsed_document(&root_node.schema, &document)?;
+    rewrite_document(&root_node.schema, &mut document)?;
+
+    let operation = get_operation(&document, operation_name)?;
 
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+        let errors = validate_input_values(variables, operation, &root_node.schema);
 
-        let errors = ctx.into_errors();
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
+    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
+        .await
+}
+
+/// Execute a query in a provided schema on the current thread
+///
+/// Unlike [`execute`], neither the schema types nor the context are required
+/// to be [`Sync`], and the returned future is not [`Send`]. This allows using
+/// [`Rc`](std::rc::Rc)-based state in resolvers and running queries on
+/// single-threaded or thread-per-core runtimes. See
+/// [`GraphQLValueAsyncLocal`] for details.
+pub async fn execute_local<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<ExecutionOutput<S>, GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsyncLocal<S>,
+    MutationT: GraphQLTypeAsyncLocal<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    execute_local_with_metadata(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+    )
+    .await
+    .map(|(output, _)| output)
+}
+
+/// Execute a query in a provided schema on the current thread, also
+/// returning the [`ExecutionMetadata`](struct.ExecutionMetadata.html), like
+/// how long the response may be cached
+pub async fn execute_local_with_metadata<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(ExecutionOutput<S>, ExecutionMetadata), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsyncLocal<S>,
+    MutationT: GraphQLTypeAsyncLocal<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    let mut document = parse_document(document_source, &root_node.schema)?;
+    validate_parsed_document(&root_node.schema, &document)?;
+    rewrite_document(&root_node.schema, &mut document)?;
+
     let operation = get_operation(&document, operation_name)?;
 
     {
@@ -307,7 +612,7 @@ where
         }
     }
 
-    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
+    executor::execute_validated_query_local(&document, operation, root_node, variables, context)
         .await
 }
 
@@ -318,7 +623,7 @@ pub async fn resolve_into_stream<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &'a QueryT::Context,
-) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<ExecutionOutput<S, Value<ValuesStream<'a, S>>>, GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -329,18 +634,10 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let document: crate::ast::Document<'a, S> =
-        parse_document_source(document_source, &root_node.schema)?;
-
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
-
-        let errors = ctx.into_errors();
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
-        }
-    }
+    let mut document: crate::ast::Document<'a, S> =
+        parse_document(document_source, &root_node.schema)?;
+    validate_parsed_document(&root_node.schema, &document)?;
+    rewrite_document(&root_node.schema, &mut document)?;
 
     let operation = get_operation(&document, operation_name)?;
 
@@ -361,7 +658,7 @@ pub fn introspect<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
     context: &QueryT::Context,
     format: IntrospectionFormat,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<ExecutionOutput<S>, GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -372,6 +669,7 @@ where
         match format {
             IntrospectionFormat::All => INTROSPECTION_QUERY,
             IntrospectionFormat::WithoutDescriptions => INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS,
+            IntrospectionFormat::TypeIndex => TYPE_INDEX_QUERY,
         },
         None,
         root_node,
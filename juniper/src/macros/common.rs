@@ -440,6 +440,42 @@ macro_rules! __juniper_parse_field_list {
         );
     };
 
+    (
+        success_callback = $success_callback: ident,
+        additional_parser = {$($additional:tt)*},
+        meta = {$($meta:tt)*},
+        items = [$({$($items: tt)*},)*],
+        rest = $(#[doc = $desc: tt])*
+        #[graphql_interface(default_impl)]
+        field $name: ident (
+            $(&$executor: ident)* $(,)*
+            $($(#[doc = $arg_desc: expr])* $arg_name:ident $(= $arg_default: tt)* : $arg_ty: ty),* $(,)*
+        ) -> $return_ty: ty $body: block
+            $($rest:tt)*
+    ) => {
+        $crate::__juniper_parse_field_list!(
+            success_callback = $success_callback,
+            additional_parser = {$($additional)*},
+            meta = {$($meta)*},
+            items = [$({$($items)*},)* {
+                name = $name,
+                body = $body,
+                return_ty = $return_ty,
+                args = [
+                    $({
+                        arg_name = $arg_name,
+                        arg_ty = $arg_ty,
+                        $(arg_default = $arg_default,)*
+                        $(arg_docstring = $arg_desc,)*
+                    },)*
+                ],
+                $(docstring = $desc,)*
+                $(executor_var = $executor,)*
+                default_impl = default_impl,
+            },],
+            rest = $($rest)*
+        );
+    };
     (
         success_callback = $success_callback: ident,
         additional_parser = {$($additional:tt)*},
@@ -606,6 +642,107 @@ macro_rules! __juniper_parse_field_list {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __juniper_parse_instance_resolver {
+    (
+        success_callback = $success_callback: ident,
+        additional_parser = {$($additional:tt)*},
+        meta = {
+            lifetimes = [$($lifetime:tt,)*],
+            name = $name:ty,
+            ctx = $ctxt:ty,
+            main_self = $mainself:ident,
+            outname = {$($outname:tt)*},
+            scalar = {$($scalar:tt)*},
+            $(description = $desciption:tt,)*
+                $(additional = {
+                    $(resolver = {$($ignored_resolver:tt)*},)*
+                },)*
+
+        },
+        items = [$({$($items: tt)*},)*],
+        rest = instance_resolvers: |&$context: ident| {
+            resolve_type => $type_resolver:expr,
+            $( $srctype:ty => $resolver:expr ),* $(,)*
+        } $($rest:tt)*
+    ) => {
+        $crate::__juniper_parse_field_list!(
+            success_callback = $success_callback,
+            additional_parser = {$($additional)*},
+            meta = {
+                lifetimes = [$($lifetime,)*],
+                name = $name,
+                ctx = $ctxt,
+                main_self = $mainself,
+                outname = {$($outname)*},
+                scalar = {$($scalar)*},
+                $(description = $desciption,)*
+                additional = {
+                    resolver = {
+                        context = $context,
+                        type_resolver = $type_resolver,
+                        items = [
+                            $({
+                                src = $srctype,
+                                resolver = $resolver,
+                            },)*
+                        ],
+                    },
+                },
+            },
+            items = [$({$($items)*},)*],
+            rest = $($rest)*
+        );
+    };
+
+    (
+        success_callback = $success_callback: ident,
+        additional_parser = {$($additional:tt)*},
+        meta = {
+            lifetimes = [$($lifetime:tt,)*],
+            name = $name:ty,
+            ctx = $ctxt:ty,
+            main_self = $mainself:ident,
+            outname = {$($outname:tt)*},
+            scalar = {$($scalar:tt)*},
+            $(description = $desciption:tt,)*
+            $(additional = {
+                $(resolver = {$($ignored_resolver:tt)*},)*
+            },)*
+
+        },
+        items = [$({$($items: tt)*},)*],
+        rest = instance_resolvers: |$(&)* _| {
+            resolve_type => $type_resolver:expr,
+            $( $srctype:ty => $resolver:expr ),* $(,)*
+        } $($rest:tt)*
+    ) => {
+        $crate::__juniper_parse_field_list!(
+            success_callback = $success_callback,
+            additional_parser = {$($additional)*},
+            meta = {
+                lifetimes = [$($lifetime,)*],
+                name = $name,
+                ctx = $ctxt,
+                main_self = $mainself,
+                outname = {$($outname)*},
+                scalar = {$($scalar)*},
+                $(description = $desciption,)*
+                additional = {
+                    resolver = {
+                        type_resolver = $type_resolver,
+                        items = [
+                            $({
+                                src = $srctype,
+                                resolver = $resolver,
+                            },)*
+                        ],
+                    },
+                },
+            },
+            items = [$({$($items)*},)*],
+            rest = $($rest)*
+        );
+    };
+
     (
         success_callback = $success_callback: ident,
         additional_parser = {$($additional:tt)*},
@@ -702,6 +839,39 @@ macro_rules! __juniper_parse_instance_resolver {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __juniper_concrete_type_name {
+    (
+        outname = {$($outname:tt)*},
+        type_resolver = [],
+        items = [$({
+            src = $resolver_src:ty,
+            resolver = $resolver_expr:expr,
+        },)*],
+    ) => {{
+        $(
+            if ($resolver_expr as ::std::option::Option<$resolver_src>).is_some() {
+                return
+                    <$resolver_src as $crate::GraphQLType<_>>::name(&()).unwrap().to_owned();
+            }
+        )*
+
+        panic!("Concrete type not handled by instance resolvers on {}", $($outname)*);
+    }};
+
+    (
+        outname = {$($outname:tt)*},
+        type_resolver = [$type_resolver:expr],
+        items = [$({
+            src = $resolver_src:ty,
+            resolver = $resolver_expr:expr,
+        },)*],
+    ) => {
+        ::std::string::ToString::to_string(&$type_resolver)
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __juniper_create_arg {
@@ -34,6 +34,51 @@ in order - the first one returning `Some` will be the determined type name. When
 resolving fragment type conditions, only the corresponding match arm will be
 executed.
 
+Instead of trying each instance resolver in turn, the concrete type may be
+decided by a `resolve_type` arm, listed first. It returns the name of the type
+to resolve into, e.g. from a field of a JSON document or a database row. Only
+the match arm of that type is executed then, and it may return the type by
+value instead of an `Option`:
+
+```rust,ignore
+instance_resolvers: |&_| {
+    resolve_type => self.row["kind"].as_str(),
+    Human => Human::from_row(&self.row),
+    Droid => Droid::from_row(&self.row),
+},
+```
+
+The listed types must be exactly the objects implementing the interface:
+creating a schema fails if one of them doesn't declare the interface, or if an
+object declaring the interface isn't listed, as it could never be resolved.
+
+## Implementing objects
+
+Objects declaring the interface with `interfaces = [...]` are checked against
+it at compile time: the build fails at the interface if one of them lacks a
+field of the interface or an argument of such a field, or adds a required
+argument to it. The types of the fields and arguments are only checked when
+creating the schema, which also requires them to be compatible.
+
+## Default implementations
+
+A field marked with `#[graphql_interface(default_impl)]`, after its doc
+comments, may be omitted by the implementing objects. The field is added to
+the objects lacking it when the schema is built, and resolved by the interface
+for them, which saves repeating fields computed from the interface alone:
+
+```rust,ignore
+/// The id, as shown to humans
+#[graphql_interface(default_impl)]
+field display_id() -> String { format!("#{}", self.id()) }
+```
+
+Resolving the field on an object, e.g. in `...on Human`, turns the object into
+the interface, so this requires a `&dyn Trait` interface whose trait the
+object implements. As for any field of an interface, selecting it on the
+interface always uses the resolver of the interface, even for objects
+defining the field themselves.
+
 ## Example
 
 A simplified extract from the StarWars schema example shows how to use the
@@ -106,6 +151,7 @@ macro_rules! graphql_interface {
             additional = {
                 resolver = {
                     $(context = $resolver_ctx: ident,)*
+                    $(type_resolver = $type_resolver: expr,)*
                     items = [
                         $({
                             src = $resolver_src: ty,
@@ -130,6 +176,7 @@ macro_rules! graphql_interface {
             $(docstring = $docstring: expr,)*
             $(deprecated = $deprecated: expr,)*
             $(executor_var = $executor: ident,)*
+            $(default_impl = $default_impl: ident,)*
         },)*],
     ) => {
         $crate::__juniper_impl_trait!(
@@ -146,9 +193,9 @@ macro_rules! graphql_interface {
                     $crate::__juniper_insert_generic!($($scalar)+): 'r
                 {
                     // Ensure all child types are registered
-                    $(
-                        let _ = registry.get_type::<$resolver_src>(info);
-                    )*
+                    let instance_types: &[$crate::Type] = &[$(
+                        registry.get_type::<$resolver_src>(info),
+                    )*];
                     let fields = &[$(
                         registry.field_convert::<$return_ty, _, Self::Context>(
                             &$crate::to_camel_case(stringify!($fn_name)),
@@ -157,6 +204,8 @@ macro_rules! graphql_interface {
                             $(.description($fn_description))*
                             .push_docstring(&[$($docstring,)*])
                             $(.deprecated($deprecated))*
+                            // `$default_impl` is the `default_impl` method itself
+                            $(.$default_impl())*
                             $(.argument(
                                 $crate::__juniper_create_arg!(
                                     registry = registry,
@@ -172,9 +221,29 @@ macro_rules! graphql_interface {
                     registry.build_interface_type::<$name>(
                         info, fields
                     )
+                        .instance_types(instance_types)
                         $(.description($desciption))*
                         .into_meta()
                 }
+
+                const INTERFACE_FIELDS: &'static [$crate::interface_helpers::InterfaceField] = &[$(
+                    $crate::interface_helpers::InterfaceField {
+                        name: stringify!($fn_name),
+                        arguments: &[$(
+                            $crate::interface_helpers::InterfaceArgument {
+                                name: stringify!($arg_name),
+                                missing: concat!(
+                                    "Missing argument `", stringify!($arg_name),
+                                    "` of field `", stringify!($fn_name), "` of the interface",
+                                ),
+                            },
+                        )*],
+                        default_impl: !<[&str]>::is_empty(&[$(stringify!($default_impl)),*]),
+                        missing: concat!(
+                            "Missing field `", stringify!($fn_name), "` of the interface",
+                        ),
+                    },
+                )*];
             }
         );
 
@@ -237,14 +306,14 @@ macro_rules! graphql_interface {
                 fn concrete_type_name(&$main_self, context: &Self::Context, _info: &Self::TypeInfo) -> String {
                     $(let $resolver_ctx = &context;)*
 
-                    $(
-                        if ($resolver_expr as ::std::option::Option<$resolver_src>).is_some() {
-                            return
-                                <$resolver_src as $crate::GraphQLType<_>>::name(&()).unwrap().to_owned();
-                        }
-                    )*
-
-                    panic!("Concrete type not handled by instance resolvers on {}", $($outname)*);
+                    $crate::__juniper_concrete_type_name!(
+                        outname = {$($outname)*},
+                        type_resolver = [$($type_resolver)*],
+                        items = [$({
+                            src = $resolver_src,
+                            resolver = $resolver_expr,
+                        },)*],
+                    )
                 }
 
                 fn resolve_into_type(
@@ -256,6 +325,14 @@ macro_rules! graphql_interface {
                 ) -> $crate::ExecutionResult<$crate::__juniper_insert_generic!($($scalar)*)> {
                     $(let $resolver_ctx = &executor.context();)*
 
+                    // Fragments on the other types are resolved into `null`, as the instance
+                    // resolvers aren't expected to fail when called for some other type.
+                    $(
+                        if type_name != $type_resolver {
+                            return Ok($crate::Value::null());
+                        }
+                    )*
+
                     $(
                         if type_name == (<$resolver_src as $crate::GraphQLType<_>>::name(&())).unwrap() {
                             return executor.resolve(&(), &$resolver_expr);
@@ -0,0 +1,201 @@
+//! Helper types for checking objects against their interfaces at compile time.
+//!
+//! `graphql_interface!` lists the fields of an interface in
+//! [`GraphQLType::INTERFACE_FIELDS`], and objects implementing it check their
+//! fields against them in a constant, so a missing field or argument fails
+//! the build instead of the creation of the schema.
+//!
+//! Only names are checked: the types of the fields and arguments aren't known
+//! at compile time, and are checked along with the rest of the schema by
+//! [`RootNode::new`].
+//!
+//! [`GraphQLType::INTERFACE_FIELDS`]: crate::GraphQLType::INTERFACE_FIELDS
+//! [`RootNode::new`]: crate::RootNode::new
+
+/// A field of an interface.
+#[derive(Debug)]
+pub struct InterfaceField {
+    /// The name of the resolver of the field, as written in Rust.
+    pub name: &'static str,
+
+    /// The arguments of the field.
+    pub arguments: &'static [InterfaceArgument],
+
+    /// Whether objects may omit the field, the interface resolving it for
+    /// them.
+    pub default_impl: bool,
+
+    /// The error of objects lacking the field.
+    pub missing: &'static str,
+}
+
+/// An argument of a field of an interface.
+#[derive(Debug)]
+pub struct InterfaceArgument {
+    /// The name of the argument, as written in Rust.
+    pub name: &'static str,
+
+    /// The error of objects lacking the argument.
+    pub missing: &'static str,
+}
+
+/// A field of an object.
+#[derive(Debug)]
+pub struct ObjectField {
+    /// The name of the field, in GraphQL.
+    pub name: &'static str,
+
+    /// The arguments of the field.
+    pub arguments: &'static [ObjectArgument],
+}
+
+/// An argument of a field of an object.
+#[derive(Debug)]
+pub struct ObjectArgument {
+    /// The name of the argument, in GraphQL.
+    pub name: &'static str,
+
+    /// The error of the argument being required while the interface doesn't
+    /// define it, or `None` if the argument is optional.
+    pub required: Option<&'static str>,
+}
+
+/// Checks the fields of an object against the ones of an interface it
+/// implements, panicking with the error of the first mismatch.
+///
+/// Each field of the interface must be defined by the object, unless the
+/// interface implements it by default, with all its arguments. The object may
+/// add optional arguments only.
+#[track_caller]
+pub const fn check_interface_fields(interface: &[InterfaceField], object: &[ObjectField]) {
+    let mut i = 0;
+    while i < interface.len() {
+        let field = &interface[i];
+        match find_field(object, field.name) {
+            Some(object_field) => check_arguments(field, object_field),
+            None if !field.default_impl => panic!("{}", field.missing),
+            None => {}
+        }
+        i += 1;
+    }
+}
+
+#[track_caller]
+const fn check_arguments(field: &InterfaceField, object_field: &ObjectField) {
+    let mut i = 0;
+    while i < field.arguments.len() {
+        let argument = &field.arguments[i];
+        if !has_argument(object_field, argument.name) {
+            panic!("{}", argument.missing);
+        }
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < object_field.arguments.len() {
+        let argument = &object_field.arguments[i];
+        if let Some(error) = argument.required {
+            if !defines_argument(field, argument.name) {
+                panic!("{}", error);
+            }
+        }
+        i += 1;
+    }
+}
+
+const fn find_field<'a>(fields: &'a [ObjectField], name: &str) -> Option<&'a ObjectField> {
+    let mut i = 0;
+    while i < fields.len() {
+        if camel_case_eq(name, fields[i].name) {
+            return Some(&fields[i]);
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn has_argument(field: &ObjectField, name: &str) -> bool {
+    let mut i = 0;
+    while i < field.arguments.len() {
+        if camel_case_eq(name, field.arguments[i].name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn defines_argument(field: &InterfaceField, name: &str) -> bool {
+    let mut i = 0;
+    while i < field.arguments.len() {
+        if camel_case_eq(field.arguments[i].name, name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether [`to_camel_case`] turns the Rust `name` into `camel`, for ASCII
+/// names.
+///
+/// [`to_camel_case`]: crate::to_camel_case
+const fn camel_case_eq(name: &str, camel: &str) -> bool {
+    let name = name.as_bytes();
+    let camel = camel.as_bytes();
+
+    let mut i = 0;
+    if name.len() >= 2 && name[0] == b'r' && name[1] == b'#' {
+        i = 2;
+    }
+    if i < name.len() && name[i] == b'_' {
+        i += 1;
+    }
+
+    let mut j = 0;
+    let mut upper = false;
+    while i < name.len() {
+        let c = name[i];
+        i += 1;
+        if c == b'_' {
+            upper = true;
+            continue;
+        }
+        let c = if upper { c.to_ascii_uppercase() } else { c };
+        upper = false;
+        if j >= camel.len() || camel[j] != c {
+            return false;
+        }
+        j += 1;
+    }
+    j == camel.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::camel_case_eq;
+    use crate::to_camel_case;
+
+    #[test]
+    fn camel_case_eq_follows_to_camel_case() {
+        for name in &[
+            "test",
+            "_test",
+            "first_second",
+            "first_",
+            "a_b_c",
+            "a_bc",
+            "a_b",
+            "r#type",
+            "some_r#type",
+            "__typename",
+            "a__b",
+        ] {
+            let camel = to_camel_case(name);
+            assert!(camel_case_eq(name, &camel), "{} != {}", name, camel);
+            assert!(!camel_case_eq(name, &format!("{}x", camel)));
+        }
+        assert!(!camel_case_eq("first_second", "firstsecond"));
+        assert!(!camel_case_eq("first_second", "first_second"));
+    }
+}
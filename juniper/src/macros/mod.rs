@@ -1,5 +1,6 @@
-// Wrapper macros which allows built-in macros to be recognized as "crate-local"
-// and helper traits for #[juniper::graphql_subscription] macro.
+// Wrapper macros which allows built-in macros to be recognized as "crate-local",
+// helper traits for #[juniper::graphql_subscription] macro
+// and helper types checking objects against their interfaces.
 
 #[macro_use]
 mod common;
@@ -9,4 +10,5 @@ mod interface;
 #[cfg(test)]
 mod tests;
 
+pub mod interface_helpers;
 pub mod subscription_helpers;
@@ -29,6 +29,12 @@ struct Point {
     x: i32,
 }
 
+#[derive(GraphQLInputObject, Debug)]
+struct Shape {
+    name: String,
+    points: Vec<Point>,
+}
+
 #[crate::graphql_object]
 impl Root {
     fn simple() -> i32 {
@@ -139,6 +145,18 @@ impl Root {
         let _ = arg2;
         0
     }
+
+    #[graphql(arguments(
+        arg1(default = [1, 2]),
+        arg2(default = Some(Shape {
+            name: "square",
+            points: [Point { x: 1 }, Point { x: 2 }],
+        })),
+    ))]
+    fn args_with_nested_default(arg1: Vec<i32>, arg2: Option<Shape>) -> i32 {
+        let _ = arg2;
+        arg1.len() as i32
+    }
 }
 
 async fn run_args_info_query<F>(field_name: &str, f: F)
@@ -171,7 +189,11 @@ where
         EmptySubscription::<()>::new(),
     );
 
-    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &Variables::new(), &())
         .await
         .expect("Execution failed");
 
@@ -924,3 +946,28 @@ async fn introspect_field_args_with_complex_default() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn introspect_field_args_with_nested_default() {
+    run_args_info_query("argsWithNestedDefault", |args| {
+        let defaults = args
+            .iter()
+            .map(|arg| {
+                arg.as_object_value()
+                    .unwrap()
+                    .get_field_value("defaultValue")
+                    .unwrap()
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            defaults,
+            vec![
+                Value::scalar("[1, 2]"),
+                Value::scalar(r#"{name: "square", points: [{x: 1}, {x: 2}]}"#),
+            ],
+        );
+    })
+    .await;
+}
@@ -168,7 +168,11 @@ where
         .into_iter()
         .collect();
 
-    let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -283,7 +283,11 @@ async fn object_query() {
     );
     let vars = std::collections::HashMap::new();
 
-    let (result, errs) = crate::execute(doc, None, &schema, &vars, &Context { flag1: true })
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &vars, &Context { flag1: true })
         .await
         .expect("Execution failed");
     assert_eq!(errs, []);
@@ -306,3 +310,136 @@ async fn object_query() {
         })
     );
 }
+
+mod injected_arguments {
+    use super::Context;
+    use crate::{EmptyMutation, EmptySubscription, RootNode};
+
+    struct Query;
+
+    #[crate::graphql_object(context = Context)]
+    impl Query {
+        fn context_last(arg: bool, context: &Context) -> bool {
+            arg && context.flag1
+        }
+
+        fn executor_between(first: i32, executor: &Executor<Context>, second: i32) -> i32 {
+            assert!(executor.context().flag1);
+            first + second
+        }
+
+        fn optional_context(context: Option<&Context>) -> bool {
+            context.unwrap().flag1
+        }
+
+        fn optional_executor(arg: bool, executor: Option<&Executor<Context>>) -> bool {
+            arg && executor.unwrap().context().flag1
+        }
+
+        fn annotated_context(arg: bool, #[graphql(context)] state: &Context) -> bool {
+            arg && state.flag1
+        }
+
+        fn annotated_optional_context(#[graphql(context)] state: Option<&Context>) -> bool {
+            state.unwrap().flag1
+        }
+
+        fn annotated_executor(
+            #[graphql(executor)] ex: &crate::Executor<Context>,
+            #[graphql(context)] state: &Context,
+        ) -> bool {
+            ex.context().flag1 && state.flag1
+        }
+    }
+
+    #[tokio::test]
+    async fn injects_arguments_in_any_position() {
+        let doc = r#"{
+            contextLast(arg: true)
+            executorBetween(first: 1, second: 2)
+            optionalContext
+            optionalExecutor(arg: true)
+            annotatedContext(arg: true)
+            annotatedOptionalContext
+            annotatedExecutor
+        }"#;
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(
+            doc,
+            None,
+            &schema,
+            &crate::Variables::new(),
+            &Context { flag1: true },
+        )
+        .await
+        .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "contextLast": true,
+                "executorBetween": 3,
+                "optionalContext": true,
+                "optionalExecutor": true,
+                "annotatedContext": true,
+                "annotatedOptionalContext": true,
+                "annotatedExecutor": true,
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn exposes_only_graphql_arguments() {
+        let doc = r#"{
+            __type(name: "Query") {
+                fields {
+                    name
+                    args { name }
+                }
+            }
+        }"#;
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors: errs,
+            ..
+        } = crate::execute(
+            doc,
+            None,
+            &schema,
+            &crate::Variables::new(),
+            &Context::default(),
+        )
+        .await
+        .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({"__type": {"fields": [
+                {"name": "contextLast", "args": [{"name": "arg"}]},
+                {"name": "executorBetween", "args": [{"name": "first"}, {"name": "second"}]},
+                {"name": "optionalContext", "args": []},
+                {"name": "optionalExecutor", "args": [{"name": "arg"}]},
+                {"name": "annotatedContext", "args": [{"name": "arg"}]},
+                {"name": "annotatedOptionalContext", "args": []},
+                {"name": "annotatedExecutor", "args": []},
+            ]}}),
+        );
+    }
+}
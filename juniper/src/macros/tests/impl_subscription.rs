@@ -304,10 +304,13 @@ async fn object_query() {
     );
     let vars = std::collections::HashMap::new();
 
-    let (stream_val, errs) =
-        crate::resolve_into_stream(doc, None, &schema, &vars, &Context { flag1: true })
-            .await
-            .expect("Execution failed");
+    let crate::ExecutionOutput {
+        data: stream_val,
+        errors: errs,
+        ..
+    } = crate::resolve_into_stream(doc, None, &schema, &vars, &Context { flag1: true })
+        .await
+        .expect("Execution failed");
 
     let result = if let Value::Object(obj) = stream_val {
         let mut result = Vec::new();
@@ -353,3 +356,55 @@ async fn object_query() {
         ]
     );
 }
+
+mod impl_stream {
+    use futures::StreamExt as _;
+
+    use crate::{EmptyMutation, RootNode, Value};
+
+    use super::{Context, Query};
+
+    struct Subscription;
+
+    #[crate::graphql_subscription(context = Context)]
+    impl Subscription {
+        async fn counter(from: i32) -> impl futures::Stream<Item = i32> + Send {
+            futures::stream::iter(from..from + 2)
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_impl_stream_fields() {
+        let schema = RootNode::new(Query, EmptyMutation::<Context>::new(), Subscription);
+        let vars = std::collections::HashMap::new();
+        let ctx = Context::default();
+
+        let crate::ExecutionOutput {
+            data: stream_val,
+            errors: errs,
+            ..
+        } = crate::resolve_into_stream(
+            "subscription { counter(from: 3) }",
+            None,
+            &schema,
+            &vars,
+            &ctx,
+        )
+        .await
+        .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        let mut stream = match stream_val {
+            Value::Object(obj) => match obj.into_iter().next() {
+                Some((name, Value::Scalar(stream))) if name == "counter" => stream,
+                _ => panic!("Expected `counter` to be a stream"),
+            },
+            _ => panic!("Expected to get Value::Object"),
+        };
+        let mut values = vec![];
+        while let Some(value) = stream.next().await {
+            values.push(value.expect("Error resolving counter"));
+        }
+        assert_eq!(values, vec![graphql_value!(3), graphql_value!(4)]);
+    }
+}
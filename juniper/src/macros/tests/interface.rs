@@ -16,6 +16,7 @@ Syntax to validate:
 * Custom name vs. default name
 * Optional commas between items
 * Optional trailing commas on instance resolvers
+* Optional type resolver before the instance resolvers
 
 */
 
@@ -42,13 +43,39 @@ struct CommasOnMeta;
 
 struct ResolversWithTrailingComma;
 
+struct KeywordFields;
+
+struct ResolveType;
+
 struct Root;
 
-#[crate::graphql_object]
+#[crate::graphql_object(
+    interfaces = [
+        CustomName,
+        WithLifetime<'static>,
+        WithGenerics<i32>,
+        DescriptionFirst,
+        FieldsFirst,
+        InterfacesFirst,
+        CommasWithTrailing,
+        CommasOnMeta,
+        ResolversWithTrailingComma,
+        KeywordFields,
+        ResolveType,
+    ],
+)]
 impl Concrete {
     fn simple() -> i32 {
         0
     }
+
+    fn r#type(r#where: i32) -> i32 {
+        r#where
+    }
+
+    fn r#async_value() -> bool {
+        true
+    }
 }
 
 graphql_interface!(CustomName: () as "ACustomNamedInterface" |&self| {
@@ -113,6 +140,22 @@ graphql_interface!(ResolversWithTrailingComma: () |&self| {
     field simple() -> i32 { 0 }
 });
 
+graphql_interface!(KeywordFields: () |&self| {
+    field r#type(r#where: i32) -> i32 { r#where }
+    field r#async_value() -> bool { true }
+
+    instance_resolvers: |_| { Concrete => Some(Concrete) }
+});
+
+graphql_interface!(ResolveType: () |&self| {
+    field simple() -> i32 { 0 }
+
+    instance_resolvers: |&context| {
+        resolve_type => <Concrete as crate::GraphQLType>::name(context).unwrap(),
+        Concrete => Concrete,
+    }
+});
+
 #[crate::graphql_object(
     // FIXME: make async work
     noasync
@@ -149,6 +192,14 @@ impl<'a> Root {
     fn resolvers_with_trailing_comma() -> ResolversWithTrailingComma {
         ResolversWithTrailingComma {}
     }
+
+    fn keyword_fields() -> KeywordFields {
+        KeywordFields {}
+    }
+
+    fn resolve_type() -> ResolveType {
+        ResolveType {}
+    }
 }
 
 async fn run_type_info_query<F>(type_name: &str, f: F)
@@ -175,7 +226,11 @@ where
         .into_iter()
         .collect();
 
-    let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(doc, None, &schema, &vars, &())
         .await
         .expect("Execution failed");
 
@@ -379,3 +434,35 @@ async fn introspect_resolvers_with_trailing_comma() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn introspect_keyword_fields() {
+    run_type_info_query("KeywordFields", |_, fields| {
+        assert!(fields.contains(&Value::object(
+            vec![("name", Value::scalar("type"))].into_iter().collect(),
+        )));
+        assert!(fields.contains(&Value::object(
+            vec![("name", Value::scalar("asyncValue"))]
+                .into_iter()
+                .collect(),
+        )));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn introspect_resolve_type() {
+    run_type_info_query("ResolveType", |object, fields| {
+        assert_eq!(
+            object.get_field_value("name"),
+            Some(&Value::scalar("ResolveType"))
+        );
+
+        assert!(fields.contains(&Value::object(
+            vec![("name", Value::scalar("simple"))]
+                .into_iter()
+                .collect(),
+        )));
+    })
+    .await;
+}
@@ -176,7 +176,7 @@ where
         .into_iter()
         .collect();
 
-    let (result, errs) =
+    let crate::ExecutionOutput { data: result, errors: errs, .. } =
         crate::execute_sync(doc, None, &schema, &vars, &InnerContext).expect("Execution failed");
 
     assert_eq!(errs, []);
@@ -14,7 +14,11 @@ where
         Mutation::default(),
         Subscription::default(),
     );
-    let (result, errs) = crate::execute(
+    let crate::ExecutionOutput {
+        data: result,
+        errors: errs,
+        ..
+    } = crate::execute(
         query,
         None,
         &schema,
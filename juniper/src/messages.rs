@@ -0,0 +1,743 @@
+//! Wording of the validation and coercion errors
+//!
+//! Every error reported while validating a document or coercing its input
+//! values is described by a [`Message`](enum.Message.html), whose `Display`
+//! implementation gives the default English text. A
+//! [`MessageCatalog`](trait.MessageCatalog.html) added with
+//! [`RootNode::with_message_catalog`](../struct.RootNode.html#method.with_message_catalog)
+//! may word any of them differently, e.g. to translate them:
+//!
+//! ```
+//! # use juniper::{
+//! #     messages::{Message, MessageCatalog},
+//! #     EmptyMutation, EmptySubscription, GraphQLError, RootNode, Variables,
+//! # };
+//! struct French;
+//!
+//! impl MessageCatalog for French {
+//!     fn message(&self, message: &Message) -> Option<String> {
+//!         match message {
+//!             Message::UnknownField { field, type_name, .. } => Some(format!(
+//!                 r#"Champ "{}" inconnu sur le type "{}""#,
+//!                 field, type_name,
+//!             )),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn name() -> &str {
+//!         "Ann"
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_message_catalog(French);
+//!
+//! match juniper::execute_sync("{ age }", None, &schema, &Variables::new(), &()) {
+//!     Err(GraphQLError::ValidationError(errors)) => assert_eq!(
+//!         errors[0].message(),
+//!         r#"Champ "age" inconnu sur le type "Query""#,
+//!     ),
+//!     other => panic!("Unexpected result: {:?}", other),
+//! }
+//! ```
+//!
+//! Errors raised by the parsing functions of custom scalars keep their own
+//! wording.
+
+use std::fmt;
+
+use crate::validation::did_you_mean;
+
+/// Error reported while validating a document or coercing its input values,
+/// with the names and types it's about
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Message<'a> {
+    /// A field isn't defined on the type it's selected on
+    UnknownField {
+        /// Name of the field
+        field: &'a str,
+        /// Name of the type the field is selected on
+        type_name: &'a str,
+        /// Names of the fields of the type close to the unknown one
+        suggestions: &'a [&'a str],
+    },
+
+    /// An argument isn't defined on the field it's provided to
+    UnknownFieldArgument {
+        /// Name of the argument
+        argument: &'a str,
+        /// Name of the field
+        field: &'a str,
+        /// Name of the type the field belongs to
+        type_name: &'a str,
+        /// Names of the arguments of the field close to the unknown one
+        suggestions: &'a [&'a str],
+    },
+
+    /// An argument isn't defined on the directive it's provided to
+    UnknownDirectiveArgument {
+        /// Name of the argument
+        argument: &'a str,
+        /// Name of the directive
+        directive: &'a str,
+        /// Names of the arguments of the directive close to the unknown one
+        suggestions: &'a [&'a str],
+    },
+
+    /// The value of an argument can't be coerced into its type
+    InvalidArgumentValue {
+        /// Name of the argument
+        argument: &'a str,
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// The default value of a variable can't be coerced into its type
+    InvalidDefaultValue {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// A non-null variable has a default value
+    NonNullDefaultValue {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Type of the variable
+        variable_type: &'a str,
+    },
+
+    /// A fragment, or an inline one if unnamed, has a type condition which is
+    /// neither an object, an interface nor an union
+    NonCompositeFragment {
+        /// Name of the fragment, if not inline
+        fragment: Option<&'a str>,
+        /// Name of the type condition
+        type_name: &'a str,
+    },
+
+    /// A directive isn't defined by the schema
+    UnknownDirective {
+        /// Name of the directive
+        directive: &'a str,
+    },
+
+    /// A directive is used where it's not allowed, e.g. on a `field`
+    MisplacedDirective {
+        /// Name of the directive
+        directive: &'a str,
+        /// Where the directive is used, e.g. `fragment spread`
+        location: &'a str,
+    },
+
+    /// A spread fragment isn't defined
+    UnknownFragment {
+        /// Name of the fragment
+        fragment: &'a str,
+    },
+
+    /// A type isn't defined by the schema
+    UnknownType {
+        /// Name of the type
+        type_name: &'a str,
+    },
+
+    /// An anonymous operation is defined along with other operations
+    AnonymousOperationNotAlone,
+
+    /// A fragment spreads itself, directly or through other fragments
+    FragmentCycle {
+        /// Name of the fragment
+        fragment: &'a str,
+    },
+
+    /// A variable is used without being defined by the operation, named if
+    /// known
+    UndefinedVariable {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Name of the operation, if not anonymous
+        operation: Option<&'a str>,
+    },
+
+    /// A fragment is never spread
+    UnusedFragment {
+        /// Name of the fragment
+        fragment: &'a str,
+    },
+
+    /// A variable is defined without being used by the operation, named if
+    /// known
+    UnusedVariable {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Name of the operation, if not anonymous
+        operation: Option<&'a str>,
+    },
+
+    /// Fields with the same response name can't be merged, for the `reason`
+    FieldsConflict {
+        /// Name of the fields in the response
+        response_name: &'a str,
+        /// Why the fields conflict, e.g. `they have differing arguments`
+        reason: &'a str,
+    },
+
+    /// A fragment, or an inline one if unnamed, is spread where its type can
+    /// never apply
+    ImpossibleFragmentSpread {
+        /// Name of the fragment, if not inline
+        fragment: Option<&'a str>,
+        /// Type the fragment is spread on
+        parent_type: &'a str,
+        /// Type condition of the fragment
+        fragment_type: &'a str,
+    },
+
+    /// A required argument of a field isn't provided
+    MissingFieldArgument {
+        /// Name of the field
+        field: &'a str,
+        /// Name of the argument
+        argument: &'a str,
+        /// Type of the argument
+        argument_type: &'a str,
+    },
+
+    /// A required argument of a directive isn't provided
+    MissingDirectiveArgument {
+        /// Name of the directive
+        directive: &'a str,
+        /// Name of the argument
+        argument: &'a str,
+        /// Type of the argument
+        argument_type: &'a str,
+    },
+
+    /// A field of a type without fields has a selection set
+    UnexpectedSelection {
+        /// Name of the field
+        field: &'a str,
+        /// Type of the field
+        field_type: &'a str,
+    },
+
+    /// A field of a type with fields has no selection set
+    MissingSelection {
+        /// Name of the field
+        field: &'a str,
+        /// Type of the field
+        field_type: &'a str,
+    },
+
+    /// An argument is provided more than once
+    DuplicateArgument {
+        /// Name of the argument
+        argument: &'a str,
+    },
+
+    /// A fragment is defined more than once
+    DuplicateFragment {
+        /// Name of the fragment
+        fragment: &'a str,
+    },
+
+    /// A field of an input object is provided more than once
+    DuplicateInputField {
+        /// Name of the field
+        field: &'a str,
+    },
+
+    /// An operation is defined more than once
+    DuplicateOperation {
+        /// Name of the operation
+        operation: &'a str,
+    },
+
+    /// A variable is defined more than once
+    DuplicateVariable {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+    },
+
+    /// A variable has a type which isn't an input one
+    NonInputVariableType {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Type of the variable
+        variable_type: &'a str,
+    },
+
+    /// A variable is used where a value of another type is expected
+    VariableInWrongPosition {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Type of the variable
+        variable_type: &'a str,
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// A selection is nested deeper than the limit
+    NestingTooDeep {
+        /// Maximum nesting depth
+        limit: usize,
+    },
+
+    /// A field required to be paginated is selected without a `first` or
+    /// `last` argument
+    UnpaginatedField {
+        /// Name of the field
+        field: &'a str,
+        /// Name of the type the field is selected on
+        type_name: &'a str,
+    },
+
+    /// A `first` or `last` argument exceeds the limit of its field
+    PaginationAboveLimit {
+        /// Name of the argument
+        argument: &'a str,
+        /// Name of the field
+        field: &'a str,
+        /// Maximum value of the argument
+        limit: i32,
+    },
+
+    /// A required variable isn't provided
+    MissingVariable {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Type of the variable
+        variable_type: &'a str,
+    },
+
+    /// The value of a variable can't be coerced into its type, at the `path`
+    /// inside of it, e.g. `filters.ages[1]`, for the `reason`
+    InvalidVariableValue {
+        /// Name of the variable, without the `$`
+        variable: &'a str,
+        /// Location of the invalid value inside of the variable, if nested
+        path: Option<&'a str>,
+        /// Why the value is invalid, as worded by the catalog
+        reason: &'a str,
+    },
+
+    /// An input value is null where a non-null one is expected
+    UnexpectedNull {
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// An input value is expected to be of a type which isn't an input one
+    NonInputType {
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// A list is provided where a scalar is expected
+    UnexpectedList {
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// An object is provided where a scalar is expected
+    UnexpectedObject {
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// An enum value isn't one of the values of the enum
+    InvalidEnumValue {
+        /// Name of the enum
+        enum_type: &'a str,
+        /// Values of the enum
+        values: &'a [&'a str],
+    },
+
+    /// A value which isn't an enum one, or a string for variables, is
+    /// provided for an enum
+    NotAnEnum {
+        /// Name of the enum
+        enum_type: &'a str,
+        /// Whether strings are accepted too, as they are for variables
+        strings_allowed: bool,
+    },
+
+    /// A value which isn't an object is provided for an input object
+    NotAnObject {
+        /// Type the value is expected to be of
+        expected_type: &'a str,
+    },
+
+    /// A field of an input object isn't defined
+    UnknownInputField,
+
+    /// The parsing function of an input object rejected a value, for the
+    /// `reason` if given
+    InvalidInputObject {
+        /// Name of the input object
+        expected_type: &'a str,
+        /// The rejected value
+        value: &'a str,
+        /// Why the value was rejected, if told
+        reason: Option<&'a str>,
+    },
+}
+
+impl<'a> fmt::Display for Message<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Message::UnknownField {
+                field,
+                type_name,
+                suggestions,
+            } => write!(
+                f,
+                r#"Unknown field "{}" on type "{}"{}"#,
+                field,
+                type_name,
+                did_you_mean(suggestions),
+            ),
+            Message::UnknownFieldArgument {
+                argument,
+                field,
+                type_name,
+                suggestions,
+            } => write!(
+                f,
+                r#"Unknown argument "{}" on field "{}" of type "{}"{}"#,
+                argument,
+                field,
+                type_name,
+                did_you_mean(suggestions),
+            ),
+            Message::UnknownDirectiveArgument {
+                argument,
+                directive,
+                suggestions,
+            } => write!(
+                f,
+                r#"Unknown argument "{}" on directive "{}"{}"#,
+                argument,
+                directive,
+                did_you_mean(suggestions),
+            ),
+            Message::InvalidArgumentValue {
+                argument,
+                expected_type,
+            } => write!(
+                f,
+                r#"Invalid value for argument "{}", expected type "{}""#,
+                argument, expected_type,
+            ),
+            Message::InvalidDefaultValue {
+                variable,
+                expected_type,
+            } => write!(
+                f,
+                r#"Invalid default value for argument "{}", expected type "{}""#,
+                variable, expected_type,
+            ),
+            Message::NonNullDefaultValue {
+                variable,
+                variable_type,
+            } => write!(
+                f,
+                r#"Argument "{}" has type "{}" and is not nullable, so it't can't have a default value"#,
+                variable, variable_type,
+            ),
+            Message::NonCompositeFragment {
+                fragment: Some(fragment),
+                type_name,
+            } => write!(
+                f,
+                r#"Fragment "{}" cannot condition non composite type "{}"#,
+                fragment, type_name,
+            ),
+            Message::NonCompositeFragment {
+                fragment: None,
+                type_name,
+            } => write!(
+                f,
+                r#"Fragment cannot condition on non composite type "{}""#,
+                type_name,
+            ),
+            Message::UnknownDirective { directive } => {
+                write!(f, r#"Unknown directive "{}""#, directive)
+            }
+            Message::MisplacedDirective {
+                directive,
+                location,
+            } => write!(
+                f,
+                r#"Directive "{}" may not be used on {}"#,
+                directive, location,
+            ),
+            Message::UnknownFragment { fragment } => {
+                write!(f, r#"Unknown fragment: "{}""#, fragment)
+            }
+            Message::UnknownType { type_name } => write!(f, r#"Unknown type "{}""#, type_name),
+            Message::AnonymousOperationNotAlone => {
+                f.write_str("This anonymous operation must be the only defined operation")
+            }
+            Message::FragmentCycle { fragment } => {
+                write!(f, r#"Cannot spread fragment "{}""#, fragment)
+            }
+            Message::UndefinedVariable {
+                variable,
+                operation: Some(operation),
+            } => write!(
+                f,
+                r#"Variable "${}" is not defined by operation "{}""#,
+                variable, operation,
+            ),
+            Message::UndefinedVariable {
+                variable,
+                operation: None,
+            } => write!(f, r#"Variable "${}" is not defined"#, variable),
+            Message::UnusedFragment { fragment } => {
+                write!(f, r#"Fragment "{}" is never used"#, fragment)
+            }
+            Message::UnusedVariable {
+                variable,
+                operation: Some(operation),
+            } => write!(
+                f,
+                r#"Variable "${}" is not used by operation "{}""#,
+                variable, operation,
+            ),
+            Message::UnusedVariable {
+                variable,
+                operation: None,
+            } => write!(f, r#"Variable "${}" is not used"#, variable),
+            Message::FieldsConflict {
+                response_name,
+                reason,
+            } => write!(
+                f,
+                r#"Fields "{}" conflict because {}. Use different aliases on the fields to fetch both if this was intentional"#,
+                response_name, reason,
+            ),
+            Message::ImpossibleFragmentSpread {
+                fragment: Some(fragment),
+                parent_type,
+                fragment_type,
+            } => write!(
+                f,
+                r#"Fragment "{}" cannot be spread here as objects of type "{}" can never be of type "{}""#,
+                fragment, parent_type, fragment_type,
+            ),
+            Message::ImpossibleFragmentSpread {
+                fragment: None,
+                parent_type,
+                fragment_type,
+            } => write!(
+                f,
+                r#"Fragment cannot be spread here as objects of type "{}" can never be of type "{}""#,
+                parent_type, fragment_type,
+            ),
+            Message::MissingFieldArgument {
+                field,
+                argument,
+                argument_type,
+            } => write!(
+                f,
+                r#"Field "{}" argument "{}" of type "{}" is required but not provided"#,
+                field, argument, argument_type,
+            ),
+            Message::MissingDirectiveArgument {
+                directive,
+                argument,
+                argument_type,
+            } => write!(
+                f,
+                r#"Directive "@{}" argument "{}" of type "{}" is required but not provided"#,
+                directive, argument, argument_type,
+            ),
+            Message::UnexpectedSelection { field, field_type } => write!(
+                f,
+                r#"Field "{}" must not have a selection since type {} has no subfields"#,
+                field, field_type,
+            ),
+            Message::MissingSelection { field, field_type } => write!(
+                f,
+                r#"Field "{}" of type "{}" must have a selection of subfields. Did you mean "{} {{ ... }}"?"#,
+                field, field_type, field,
+            ),
+            Message::DuplicateArgument { argument } => {
+                write!(f, r#"There can only be one argument named "{}""#, argument)
+            }
+            Message::DuplicateFragment { fragment } => {
+                write!(f, "There can only be one fragment named {}", fragment)
+            }
+            Message::DuplicateInputField { field } => {
+                write!(f, r#"There can only be one input field named "{}""#, field)
+            }
+            Message::DuplicateOperation { operation } => {
+                write!(f, "There can only be one operation named {}", operation)
+            }
+            Message::DuplicateVariable { variable } => {
+                write!(f, "There can only be one variable named {}", variable)
+            }
+            Message::NonInputVariableType {
+                variable,
+                variable_type,
+            } => write!(
+                f,
+                r#"Variable "{}" cannot be of non-input type "{}""#,
+                variable, variable_type,
+            ),
+            Message::VariableInWrongPosition {
+                variable,
+                variable_type,
+                expected_type,
+            } => write!(
+                f,
+                r#"Variable "{}" of type "{}" used in position expecting type "{}""#,
+                variable, variable_type, expected_type,
+            ),
+            Message::NestingTooDeep { limit } => write!(
+                f,
+                "Selection exceeds the maximum nesting depth of {}",
+                limit,
+            ),
+            Message::UnpaginatedField { field, type_name } => write!(
+                f,
+                r#"Field "{}" of type "{}" must be paginated with the "first" or "last" argument"#,
+                field, type_name,
+            ),
+            Message::PaginationAboveLimit {
+                argument,
+                field,
+                limit,
+            } => write!(
+                f,
+                r#"Argument "{}" of field "{}" must be at most {}"#,
+                argument, field, limit,
+            ),
+            Message::MissingVariable {
+                variable,
+                variable_type,
+            } => write!(
+                f,
+                r#"Variable "${}" of required type "{}" was not provided."#,
+                variable, variable_type,
+            ),
+            Message::InvalidVariableValue {
+                variable,
+                path: None,
+                reason,
+            } => write!(
+                f,
+                r#"Variable "${}" got invalid value. {}."#,
+                variable, reason
+            ),
+            Message::InvalidVariableValue {
+                variable,
+                path: Some(path),
+                reason,
+            } => write!(
+                f,
+                r#"Variable "${}" got invalid value at "{}": {}."#,
+                variable, path, reason,
+            ),
+            Message::UnexpectedNull { expected_type } => {
+                write!(f, r#"Expected "{}", found null"#, expected_type)
+            }
+            Message::NonInputType { expected_type } => {
+                write!(f, r#"Expected "{}", found non-input type"#, expected_type)
+            }
+            Message::UnexpectedList { expected_type } => {
+                write!(f, r#"Expected "{}", found list"#, expected_type)
+            }
+            Message::UnexpectedObject { expected_type } => {
+                write!(f, r#"Expected "{}", found object"#, expected_type)
+            }
+            Message::InvalidEnumValue { enum_type, values } => write!(
+                f,
+                r#"Invalid value for enum "{}", expected one of: {}"#,
+                enum_type,
+                values.join(", "),
+            ),
+            Message::NotAnEnum {
+                enum_type,
+                strings_allowed,
+            } => write!(
+                f,
+                r#"Expected "{}", found not {}"#,
+                enum_type,
+                if strings_allowed {
+                    "a string or enum"
+                } else {
+                    "an enum"
+                },
+            ),
+            Message::NotAnObject { expected_type } => {
+                write!(f, r#"Expected "{}", found not an object"#, expected_type)
+            }
+            Message::UnknownInputField => f.write_str("Unknown field"),
+            Message::InvalidInputObject {
+                expected_type,
+                value,
+                reason,
+            } => {
+                write!(
+                    f,
+                    r#"Expected input of type "{}". Got: "{}""#,
+                    expected_type, value,
+                )?;
+                if let Some(reason) = reason {
+                    write!(f, ": {}", reason)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Wording of the validation and coercion errors reported with a schema,
+/// replacing the default English one
+pub trait MessageCatalog: Send + Sync {
+    /// Text of the `message`, or `None` to keep the default one
+    fn message(&self, message: &Message) -> Option<String>;
+}
+
+impl<F> MessageCatalog for F
+where
+    F: Fn(&Message) -> Option<String> + Send + Sync,
+{
+    fn message(&self, message: &Message) -> Option<String> {
+        self(message)
+    }
+}
+
+/// The catalog added to a schema, if any
+#[derive(Default)]
+pub(crate) struct Messages(pub(crate) Option<Box<dyn MessageCatalog>>);
+
+impl Messages {
+    /// Text of the `message`, from the catalog if it words it
+    pub(crate) fn render(&self, message: &Message) -> String {
+        self.0
+            .as_ref()
+            .and_then(|catalog| catalog.message(message))
+            .unwrap_or_else(|| message.to_string())
+    }
+}
+
+impl fmt::Debug for Messages {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("Messages(custom)"),
+            None => f.write_str("Messages(default)"),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Policies of the named operations a schema executes
+//!
+//! Registering an [`OperationPolicy`](struct.OperationPolicy.html) with
+//! [`RootNode::with_operation_policy`](../struct.RootNode.html#method.with_operation_policy)
+//! turns the schema into an allowlist of operation names, for APIs exposing a
+//! small fixed set of operations, e.g. to partners:
+//!
+//! - operations not named after a registered policy, anonymous ones included,
+//!   fail with an `OPERATION_NOT_ALLOWED` error without being executed;
+//! - operations requiring scopes fail with a `FORBIDDEN` error when the
+//!   [`PolicyEvaluator`](../auth/trait.PolicyEvaluator.html) of the schema
+//!   denies them, all of them being denied until an evaluator is set;
+//! - the responses of queries with a `cache_max_age` may be cached for that
+//!   long, whatever the cache hints of their fields.
+//!
+//! Rate limits are left to the integration, which looks the policy of an
+//! operation up after parsing it, with
+//! [`operation_info`](../fn.operation_info.html) and
+//! [`RootNode::operation_policy`](../struct.RootNode.html#method.operation_policy).
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use juniper::{
+//! #     operations::{OperationPolicy, RateLimit},
+//! #     EmptyMutation, EmptySubscription, RootNode,
+//! # };
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn stock(sku: String) -> i32 {
+//!         42
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_operation_policy(
+//!         "PartnerStock",
+//!         OperationPolicy {
+//!             required_scopes: Some(vec!["stock:read".into()]),
+//!             rate_limit: Some(RateLimit {
+//!                 requests: 100,
+//!                 period: Duration::from_secs(60),
+//!             }),
+//!             cache_max_age: Some(30),
+//!         },
+//!     )
+//!     .with_policy_evaluator(|_: &(), scopes: &[String]| scopes.is_empty());
+//!
+//! let limit = schema
+//!     .operation_policy("PartnerStock")
+//!     .and_then(|policy| policy.rate_limit);
+//! assert_eq!(limit.map(|l| l.requests), Some(100));
+//! ```
+
+use std::time::Duration;
+
+use fnv::FnvHashMap;
+
+use crate::{
+    ast::Operation,
+    auth::{self, PolicyEvaluator},
+    executor::FieldError,
+    parser::Spanning,
+    value::ScalarValue,
+};
+
+/// Policy of a named operation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationPolicy {
+    /// Scopes the context must have to execute the operation, as evaluated by
+    /// the [`PolicyEvaluator`](../auth/trait.PolicyEvaluator.html) of the
+    /// schema
+    ///
+    /// `Some(vec![])` only requires the context to be authenticated, like
+    /// `#[graphql(require_auth)]`.
+    pub required_scopes: Option<Vec<String>>,
+
+    /// How often a client may execute the operation, enforced by the
+    /// integration
+    pub rate_limit: Option<RateLimit>,
+
+    /// Seconds the responses of the operation may be cached for, overriding
+    /// the cache hints of the resolved fields
+    ///
+    /// Ignored for mutations and subscriptions.
+    pub cache_max_age: Option<u32>,
+}
+
+/// Number of `requests` a client may send per `period`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per period
+    pub requests: u32,
+    /// Period the requests are counted over
+    pub period: Duration,
+}
+
+/// The policies of a schema, by operation name
+#[derive(Debug, Default)]
+pub(crate) struct OperationPolicies(FnvHashMap<String, OperationPolicy>);
+
+impl OperationPolicies {
+    pub(crate) fn insert(&mut self, name: String, policy: OperationPolicy) {
+        self.0.insert(name, policy);
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&OperationPolicy> {
+        self.0.get(name)
+    }
+
+    /// The policy of the `operation`, if it's named after one
+    pub(crate) fn of<S>(&self, operation: &Spanning<Operation<S>>) -> Option<&OperationPolicy> {
+        operation
+            .item
+            .name
+            .as_ref()
+            .and_then(|name| self.get(name.item))
+    }
+
+    /// Checks the `operation` may be executed with the `context`, any
+    /// operation being allowed until a policy is registered
+    pub(crate) fn check<CtxT, S>(
+        &self,
+        operation: &Spanning<Operation<S>>,
+        evaluator: Option<&dyn PolicyEvaluator<CtxT>>,
+        context: &CtxT,
+    ) -> Result<(), FieldError<S>>
+    where
+        S: ScalarValue,
+    {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+
+        let policy = self.of(operation).ok_or_else(|| {
+            FieldError::new(
+                "Operation not allowed",
+                graphql_value!({ "code": "OPERATION_NOT_ALLOWED" }),
+            )
+        })?;
+        match (&policy.required_scopes, evaluator) {
+            (None, _) => Ok(()),
+            (Some(scopes), Some(evaluator)) if evaluator.is_authorized(context, scopes) => Ok(()),
+            (Some(_), _) => Err(auth::forbidden()),
+        }
+    }
+}
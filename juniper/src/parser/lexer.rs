@@ -24,10 +24,43 @@ pub struct Lexer<'a> {
 #[allow(missing_docs)]
 pub enum ScalarToken<'a> {
     String(&'a str),
+    /// Raw content of a block string (`"""`), see [`block_string_value`]
+    BlockString(&'a str),
     Float(&'a str),
     Int(&'a str),
 }
 
+/// The kind of a [`ScalarToken`], telling which form a scalar literal was
+/// written in
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[allow(missing_docs)]
+pub enum ScalarTokenKind {
+    String,
+    Float,
+    Int,
+}
+
+impl<'a> ScalarToken<'a> {
+    /// Returns the kind of this token
+    pub fn kind(&self) -> ScalarTokenKind {
+        match self {
+            ScalarToken::String(_) | ScalarToken::BlockString(_) => ScalarTokenKind::String,
+            ScalarToken::Float(_) => ScalarTokenKind::Float,
+            ScalarToken::Int(_) => ScalarTokenKind::Int,
+        }
+    }
+}
+
+impl fmt::Display for ScalarTokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ScalarTokenKind::String => "String",
+            ScalarTokenKind::Float => "Float",
+            ScalarTokenKind::Int => "Int",
+        })
+    }
+}
+
 /// A single token in the input source
 #[derive(Debug, PartialEq, Clone, Copy)]
 #[allow(missing_docs)]
@@ -122,7 +155,10 @@ impl<'a> Lexer<'a> {
         let next = self.iterator.next();
 
         if let Some((_, ch)) = next {
-            if ch == '\n' {
+            // `\r\n` is a single line terminator, ending the line at `\n`
+            let ends_line =
+                ch == '\n' || (ch == '\r' && self.peek_char().map(|(_, c)| c) != Some('\n'));
+            if ends_line {
                 self.position.advance_line();
             } else {
                 self.position.advance_col();
@@ -214,6 +250,12 @@ impl<'a> Lexer<'a> {
     }
 
     fn scan_string(&mut self) -> LexerResult<'a> {
+        if let Some((idx, _)) = self.peek_char() {
+            if self.source[idx..].starts_with("\"\"\"") {
+                return self.scan_block_string();
+            }
+        }
+
         let start_pos = self.position;
         let (start_idx, start_ch) = self
             .next_char()
@@ -233,7 +275,7 @@ impl<'a> Lexer<'a> {
                     escaped = false;
                 }
                 'u' if escaped => {
-                    self.scan_escaped_unicode(&old_pos)?;
+                    self.scan_escaped_char(&old_pos)?;
                     escaped = false;
                 }
                 c if escaped => {
@@ -273,10 +315,84 @@ impl<'a> Lexer<'a> {
         ))
     }
 
-    fn scan_escaped_unicode(
+    fn scan_block_string(&mut self) -> LexerResult<'a> {
+        let start_pos = self.position;
+        let (start_idx, _) = self
+            .next_char()
+            .ok_or_else(|| Spanning::zero_width(&self.position, LexerError::UnexpectedEndOfFile))?;
+        self.next_char();
+        self.next_char();
+
+        while let Some((idx, ch)) = self.peek_char() {
+            let rest = &self.source[idx..];
+            if rest.starts_with("\"\"\"") {
+                for _ in 0..3 {
+                    self.next_char();
+                }
+                return Ok(Spanning::start_end(
+                    &start_pos,
+                    &self.position,
+                    Token::Scalar(ScalarToken::BlockString(&self.source[start_idx + 3..idx])),
+                ));
+            } else if rest.starts_with("\\\"\"\"") {
+                for _ in 0..4 {
+                    self.next_char();
+                }
+            } else if !is_source_char(ch) {
+                return Err(Spanning::zero_width(
+                    &self.position,
+                    LexerError::UnknownCharacterInString(ch),
+                ));
+            } else {
+                self.next_char();
+            }
+        }
+
+        Err(Spanning::zero_width(
+            &self.position,
+            LexerError::UnterminatedString,
+        ))
+    }
+
+    /// Scans the code point of a `\u` escape sequence, combining a surrogate
+    /// pair into a single character
+    fn scan_escaped_char(
         &mut self,
         start_pos: &SourcePosition,
     ) -> Result<(), Spanning<LexerError>> {
+        let code_point = self.scan_escaped_unicode(start_pos)?;
+        let code_point = if is_high_surrogate(code_point) {
+            let escape = format!("\\u{:X}", code_point);
+            let low = match (self.next_char(), self.next_char()) {
+                (Some((_, '\\')), Some((_, 'u'))) => self.scan_escaped_unicode(start_pos)?,
+                _ => {
+                    return Err(Spanning::zero_width(
+                        start_pos,
+                        LexerError::UnknownEscapeSequence(escape),
+                    ))
+                }
+            };
+            combine_surrogates(code_point, low).ok_or_else(|| {
+                Spanning::zero_width(start_pos, LexerError::UnknownEscapeSequence(escape))
+            })?
+        } else {
+            code_point
+        };
+
+        char::from_u32(code_point)
+            .ok_or_else(|| {
+                Spanning::zero_width(
+                    start_pos,
+                    LexerError::UnknownEscapeSequence(format!("\\u{:X}", code_point)),
+                )
+            })
+            .map(|_| ())
+    }
+
+    fn scan_escaped_unicode(
+        &mut self,
+        start_pos: &SourcePosition,
+    ) -> Result<u32, Spanning<LexerError>> {
         let (start_idx, _) = self
             .peek_char()
             .ok_or_else(|| Spanning::zero_width(&self.position, LexerError::UnterminatedString))?;
@@ -309,21 +425,12 @@ impl<'a> Lexer<'a> {
             ));
         }
 
-        let code_point = u32::from_str_radix(escape, 16).map_err(|_| {
+        u32::from_str_radix(escape, 16).map_err(|_| {
             Spanning::zero_width(
                 start_pos,
                 LexerError::UnknownEscapeSequence("\\u".to_owned() + escape),
             )
-        })?;
-
-        char::from_u32(code_point)
-            .ok_or_else(|| {
-                Spanning::zero_width(
-                    start_pos,
-                    LexerError::UnknownEscapeSequence("\\u".to_owned() + escape),
-                )
-            })
-            .map(|_| ())
+        })
     }
 
     fn scan_number(&mut self) -> LexerResult<'a> {
@@ -490,6 +597,7 @@ impl<'a> fmt::Display for Token<'a> {
             Token::Scalar(ScalarToken::String(s)) => {
                 write!(f, "\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
             }
+            Token::Scalar(ScalarToken::BlockString(s)) => write!(f, "\"\"\"{}\"\"\"", s),
             Token::ExclamationMark => write!(f, "!"),
             Token::Dollar => write!(f, "$"),
             Token::ParenOpen => write!(f, "("),
@@ -508,6 +616,64 @@ impl<'a> fmt::Display for Token<'a> {
     }
 }
 
+/// Returns the value of a block string from its raw content, as defined by
+/// the [spec](https://spec.graphql.org/June2018/#BlockStringValue())
+///
+/// Escaped triple quotes are unescaped, line terminators are normalized to
+/// `\n`, the indentation common to all the lines but the first one is
+/// stripped, and so are the leading and trailing blank lines. Descriptions
+/// written as block strings should be read with it too.
+pub fn block_string_value(raw: &str) -> String {
+    let raw = raw.replace("\\\"\"\"", "\"\"\"");
+    let lines: Vec<&str> = raw
+        .split("\r\n")
+        .flat_map(|l| l.split(&['\n', '\r'][..]))
+        .collect();
+
+    let is_blank = |line: &&str| line.chars().all(|c| c == ' ' || c == '\t');
+    let indent_of = |line: &&str| line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !is_blank(line))
+        .map(indent_of)
+        .min()
+        .unwrap_or(0);
+
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => line,
+            _ => line.get(common_indent..).unwrap_or(""),
+        })
+        .collect();
+    let start = lines
+        .iter()
+        .position(|l| !is_blank(l))
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !is_blank(l))
+        .map_or(start, |i| i + 1);
+
+    lines[start..end].join("\n")
+}
+
+pub(crate) fn is_high_surrogate(code_point: u32) -> bool {
+    (0xD800..=0xDBFF).contains(&code_point)
+}
+
+/// Combines a UTF-16 surrogate pair into a single code point, returning
+/// `None` if `low` isn't a low surrogate
+pub(crate) fn combine_surrogates(high: u32, low: u32) -> Option<u32> {
+    if (0xDC00..=0xDFFF).contains(&low) {
+        Some(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+    } else {
+        None
+    }
+}
+
 fn is_source_char(c: char) -> bool {
     c == '\t' || c == '\n' || c == '\r' || c >= ' '
 }
@@ -13,7 +13,15 @@ mod tests;
 pub use self::document::parse_document_source;
 
 pub use self::{
-    lexer::{Lexer, LexerError, ScalarToken, Token},
-    parser::{OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult},
+    lexer::{block_string_value, Lexer, LexerError, ScalarToken, ScalarTokenKind, Token},
+    parser::{
+        OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult,
+        DEFAULT_RECURSION_LIMIT,
+    },
     utils::{SourcePosition, Spanning},
 };
+
+pub(crate) use self::{
+    lexer::{combine_surrogates, is_high_surrogate},
+    value::parse_value_literal,
+};
@@ -1,7 +1,14 @@
-use std::{fmt, result::Result};
+use std::{collections::VecDeque, fmt, result::Result};
 
 use crate::parser::{Lexer, LexerError, Spanning, Token};
 
+/// Default maximum nesting depth of selection sets, list/object values and type literals
+///
+/// Documents nested deeper than this are rejected with
+/// [`ParseError::RecursionLimitExceeded`] instead of exhausting the stack of the
+/// recursive-descent parser, the validator and the executor.
+pub const DEFAULT_RECURSION_LIMIT: usize = 128;
+
 /// Error while parsing a GraphQL query
 #[derive(Debug, PartialEq)]
 pub enum ParseError<'a> {
@@ -16,6 +23,9 @@ pub enum ParseError<'a> {
 
     /// A scalar of unexpected type occurred in the source
     ExpectedScalarError(&'static str),
+
+    /// The source is nested deeper than the parser's recursion budget allows
+    RecursionLimitExceeded,
 }
 
 #[doc(hidden)]
@@ -30,22 +40,33 @@ pub type OptionParseResult<'a, T> = Result<Option<Spanning<T>>, Spanning<ParseEr
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct Parser<'a> {
-    tokens: Vec<Spanning<Token<'a>>>,
+    tokens: VecDeque<Spanning<Token<'a>>>,
+    recursion_budget: usize,
 }
 
 impl<'a> Parser<'a> {
     #[doc(hidden)]
     pub fn new(lexer: &mut Lexer<'a>) -> Result<Parser<'a>, Spanning<LexerError>> {
-        let mut tokens = Vec::new();
+        let mut tokens = VecDeque::new();
 
         for res in lexer {
             match res {
-                Ok(s) => tokens.push(s),
+                Ok(s) => tokens.push_back(s),
                 Err(e) => return Err(e),
             }
         }
 
-        Ok(Parser { tokens })
+        Ok(Parser {
+            tokens,
+            recursion_budget: DEFAULT_RECURSION_LIMIT,
+        })
+    }
+
+    /// Sets the maximum nesting depth this parser accepts
+    #[cfg(test)]
+    pub(crate) fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_budget = limit;
+        self
     }
 
     #[doc(hidden)]
@@ -53,6 +74,27 @@ impl<'a> Parser<'a> {
         &self.tokens[0]
     }
 
+    /// Runs `f` one nesting level deeper, failing if the recursion budget is
+    /// exhausted
+    #[doc(hidden)]
+    pub fn nested<T, F>(&mut self, f: F) -> Result<T, Spanning<ParseError<'a>>>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, Spanning<ParseError<'a>>>,
+    {
+        if self.recursion_budget == 0 {
+            return Err(Spanning::start_end(
+                &self.peek().start,
+                &self.peek().end,
+                ParseError::RecursionLimitExceeded,
+            ));
+        }
+
+        self.recursion_budget -= 1;
+        let res = crate::stack::ensure_sufficient_stack(|| f(self));
+        self.recursion_budget += 1;
+        res
+    }
+
     #[doc(hidden)]
     pub fn next_token(&mut self) -> ParseResult<'a, Token<'a>> {
         if self.tokens.len() == 1 {
@@ -62,7 +104,7 @@ impl<'a> Parser<'a> {
                 ParseError::UnexpectedEndOfFile,
             ))
         } else {
-            Ok(self.tokens.remove(0))
+            Ok(self.tokens.pop_front().unwrap())
         }
     }
 
@@ -200,6 +242,7 @@ impl<'a> fmt::Display for ParseError<'a> {
             ParseError::UnexpectedEndOfFile => write!(f, "Unexpected end of input"),
             ParseError::LexerError(ref err) => err.fmt(f),
             ParseError::ExpectedScalarError(err) => err.fmt(f),
+            ParseError::RecursionLimitExceeded => write!(f, "Recursion limit exceeded"),
         }
     }
 }
@@ -2,7 +2,10 @@ use crate::{
     ast::{
         Arguments, Definition, Document, Field, InputValue, Operation, OperationType, Selection,
     },
-    parser::{document::parse_document_source, ParseError, SourcePosition, Spanning, Token},
+    parser::{
+        document::parse_document_source, ParseError, SourcePosition, Spanning, Token,
+        DEFAULT_RECURSION_LIMIT,
+    },
     schema::model::SchemaType,
     types::scalars::{EmptyMutation, EmptySubscription},
     validation::test_harness::{MutationRoot, QueryRoot, SubscriptionRoot},
@@ -174,3 +177,41 @@ fn issue_427_panic_is_not_expected() {
         ParseError::ExpectedScalarError("There needs to be a Float type")
     );
 }
+
+#[test]
+fn deeply_nested_selections_hit_recursion_limit() {
+    let depth = 100_000;
+    let query = format!("{}{}", "{ a ".repeat(depth), "}".repeat(depth));
+
+    assert_eq!(
+        parse_document_error::<DefaultScalarValue>(&query).item,
+        ParseError::RecursionLimitExceeded,
+    );
+}
+
+#[test]
+fn deeply_nested_values_hit_recursion_limit() {
+    let depth = 100_000;
+    let lists = format!("{{ a(b: {}{}) }}", "[".repeat(depth), "]".repeat(depth));
+    let objects = format!("{{ a(b: {}{}) }}", "{c: ".repeat(depth), "}".repeat(depth));
+    let types = format!(
+        "query($v: {}Int{}) {{ a }}",
+        "[".repeat(depth),
+        "]".repeat(depth),
+    );
+
+    for query in &[lists, objects, types] {
+        assert_eq!(
+            parse_document_error::<DefaultScalarValue>(query).item,
+            ParseError::RecursionLimitExceeded,
+        );
+    }
+}
+
+#[test]
+fn nesting_within_recursion_limit_is_accepted() {
+    let depth = DEFAULT_RECURSION_LIMIT;
+    let query = format!("{}{}", "{ a ".repeat(depth), "}".repeat(depth));
+
+    parse_document::<DefaultScalarValue>(&query);
+}
@@ -0,0 +1,145 @@
+//! Randomized inputs checking that the parser never panics or overflows the
+//! stack, whatever garbage it is fed.
+
+use crate::{
+    parser::document::parse_document_source,
+    schema::model::SchemaType,
+    validation::test_harness::{MutationRoot, QueryRoot, SubscriptionRoot},
+    value::DefaultScalarValue,
+};
+
+const FRAGMENTS: &[&str] = &[
+    "{",
+    "}",
+    "[",
+    "]",
+    "(",
+    ")",
+    ":",
+    "$",
+    "@",
+    "!",
+    "=",
+    "|",
+    "...",
+    "\"",
+    "\\",
+    "\\u",
+    "\"\"\"",
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "on",
+    "null",
+    "true",
+    "false",
+    "dog",
+    "name",
+    "human",
+    "complicatedArgs",
+    "0",
+    "-1",
+    "1.5e",
+    "1e10",
+    "0x",
+    "\u{0}",
+    "\u{feff}",
+    "\n",
+    "\r\n",
+    " ",
+    ",",
+    "#",
+    "é",
+    "\u{1F600}",
+];
+
+const SEEDS: &[&str] = &[
+    "{ dog { name } }",
+    "query Q($v: [Int!]! = [1, 2]) { complicatedArgs { intArgField(intArg: $v) } }",
+    "{ dog { ...F } } fragment F on Dog { name @include(if: true) }",
+    "{ complicatedArgs { complexArgField(complexArg: { requiredField: true, intField: 4 }) } }",
+    "mutation { testInput(input: { id: \"x\", list: [[1], null] }) }",
+];
+
+/// Tiny xorshift generator, so the test is deterministic and needs no extra
+/// dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.next() % n
+    }
+}
+
+fn parse(s: &str) {
+    let schema = SchemaType::new::<QueryRoot, MutationRoot, SubscriptionRoot>(&(), &(), &());
+    let _ = parse_document_source::<DefaultScalarValue>(s, &schema);
+}
+
+fn mutate(rng: &mut Rng, seed: &str) -> String {
+    let mut chars: Vec<char> = seed.chars().collect();
+
+    for _ in 0..=rng.below(4) {
+        let at = rng.below(chars.len() + 1);
+        match rng.below(3) {
+            0 if at < chars.len() => {
+                chars.remove(at);
+            }
+            1 => {
+                let insert = FRAGMENTS[rng.below(FRAGMENTS.len())];
+                for (i, c) in insert.chars().enumerate() {
+                    chars.insert(at + i, c);
+                }
+            }
+            _ => chars.truncate(at),
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+#[test]
+fn random_token_soup() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..2_000 {
+        let len = rng.below(40);
+        let input: String = (0..len)
+            .map(|_| FRAGMENTS[rng.below(FRAGMENTS.len())])
+            .collect::<Vec<_>>()
+            .join(if rng.below(2) == 0 { " " } else { "" });
+
+        parse(&input);
+    }
+}
+
+#[test]
+fn mutated_documents() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..2_000 {
+        let seed = SEEDS[rng.below(SEEDS.len())];
+        parse(&mutate(&mut rng, seed));
+    }
+}
+
+#[test]
+fn deeply_nested_random_brackets() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+
+    for _ in 0..20 {
+        let depth = 1_000 + rng.below(50_000);
+        let open = ["{ a ", "[", "{b: ", "... on Dog { "][rng.below(4)];
+        let input = format!("{{ a(b: {}", open.repeat(depth));
+
+        parse(&input);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::parser::{Lexer, LexerError, ScalarToken, SourcePosition, Spanning, Token};
+use crate::parser::{
+    block_string_value, Lexer, LexerError, ScalarToken, SourcePosition, Spanning, Token,
+};
 
 fn tokenize_to_vec<'a>(s: &'a str) -> Vec<Spanning<Token<'a>>> {
     let mut tokens = Vec::new();
@@ -333,6 +335,127 @@ fn string_errors() {
     );
 }
 
+#[test]
+fn surrogate_pair_escapes() {
+    assert_eq!(
+        tokenize_single(r#""smile \uD83D\uDE00""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(20, 0, 20),
+            Token::Scalar(ScalarToken::String(r#"smile \uD83D\uDE00"#))
+        )
+    );
+
+    assert_eq!(
+        tokenize_error(r#""lone \uD83D surrogate""#),
+        Spanning::zero_width(
+            &SourcePosition::new(7, 0, 7),
+            LexerError::UnknownEscapeSequence("\\uD83D".into())
+        )
+    );
+
+    assert_eq!(
+        tokenize_error(r#""unpaired \uD83DA""#),
+        Spanning::zero_width(
+            &SourcePosition::new(11, 0, 11),
+            LexerError::UnknownEscapeSequence("\\uD83D".into())
+        )
+    );
+
+    assert_eq!(
+        tokenize_error(r#""low \uDE00 first""#),
+        Spanning::zero_width(
+            &SourcePosition::new(6, 0, 6),
+            LexerError::UnknownEscapeSequence("\\uDE00".into())
+        )
+    );
+}
+
+#[test]
+fn block_strings() {
+    assert_eq!(
+        tokenize_single(r#""""simple""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(12, 0, 12),
+            Token::Scalar(ScalarToken::BlockString("simple"))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single(r#""""escaped \""" and "quoted" \n""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(34, 0, 34),
+            Token::Scalar(ScalarToken::BlockString(r#"escaped \""" and "quoted" \n"#))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single("\"\"\"multi\r\n  line\r  text\"\"\""),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(26, 2, 9),
+            Token::Scalar(ScalarToken::BlockString("multi\r\n  line\r  text"))
+        )
+    );
+
+    assert_eq!(
+        tokenize_error("\"\"\"no end quotes\"\""),
+        Spanning::zero_width(
+            &SourcePosition::new(18, 0, 18),
+            LexerError::UnterminatedString
+        )
+    );
+
+    assert_eq!(
+        tokenize_error("\"\"\"contains \u{0007} control char\"\"\""),
+        Spanning::zero_width(
+            &SourcePosition::new(12, 0, 12),
+            LexerError::UnknownCharacterInString('\u{0007}')
+        )
+    );
+}
+
+#[test]
+fn block_string_values() {
+    assert_eq!(block_string_value("simple"), "simple");
+    assert_eq!(
+        block_string_value(r#"escaped \""" quotes"#),
+        r#"escaped """ quotes"#,
+    );
+    assert_eq!(
+        block_string_value("\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  "),
+        "Hello,\n  World!\n\nYours,\n  GraphQL.",
+    );
+    assert_eq!(
+        block_string_value("  first line\n    indented\n  rest"),
+        "  first line\n  indented\nrest",
+    );
+    assert_eq!(
+        block_string_value("windows\r\n\tand\r\tmac"),
+        "windows\nand\nmac",
+    );
+    assert_eq!(block_string_value(" \n\t\n "), "");
+}
+
+#[test]
+fn line_terminators() {
+    assert_eq!(
+        tokenize_to_vec("a\r\nb\rc\nd")
+            .into_iter()
+            .map(|t| t.start)
+            .collect::<Vec<_>>(),
+        vec![
+            SourcePosition::new(0, 0, 0),
+            SourcePosition::new(3, 1, 0),
+            SourcePosition::new(5, 2, 0),
+            SourcePosition::new(7, 3, 0),
+            SourcePosition::new(8, 3, 1),
+        ],
+    );
+}
+
 #[test]
 fn numbers() {
     fn assert_float_token_eq(
@@ -684,6 +807,14 @@ fn display() {
         "\"string with \\\\ escape and \\\" quote\""
     );
 
+    assert_eq!(
+        format!(
+            "{}",
+            Token::Scalar(ScalarToken::BlockString("block \\\"\"\" string"))
+        ),
+        "\"\"\"block \\\"\"\" string\"\"\"",
+    );
+
     assert_eq!(format!("{}", Token::ExclamationMark), "!");
     assert_eq!(format!("{}", Token::Dollar), "$");
     assert_eq!(format!("{}", Token::ParenOpen), "(");
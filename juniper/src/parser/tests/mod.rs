@@ -1,3 +1,4 @@
 mod document;
+mod fuzz;
 mod lexer;
 mod value;
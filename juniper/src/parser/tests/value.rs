@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 
 use crate::{
     ast::{FromInputValue, InputValue, Type},
-    parser::{value::parse_value_literal, Lexer, Parser, SourcePosition, Spanning},
+    parser::{value::parse_value_literal, Lexer, ParseError, Parser, SourcePosition, Spanning},
     schema::{
         meta::{Argument, EnumMeta, EnumValue, InputObjectMeta, MetaType, ScalarMeta},
         model::SchemaType,
@@ -117,6 +117,17 @@ fn input_value_literals() {
             InputValue::scalar("test")
         )
     );
+    assert_eq!(
+        parse_value::<DefaultScalarValue>(
+            "\"\"\"\n  block\n    test\n\"\"\"",
+            &scalar_meta::<String>("String"),
+        ),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(24, 3, 3),
+            InputValue::scalar("block\n  test")
+        )
+    );
     let values = &[EnumValue::new("enum_value")];
     let e: EnumMeta<DefaultScalarValue> = EnumMeta::new::<Enum>("TestEnum".into(), values);
 
@@ -233,3 +244,22 @@ fn input_value_literals() {
         )
     );
 }
+
+#[test]
+fn nesting_beyond_custom_recursion_limit_is_rejected() {
+    let schema = SchemaType::new::<Query, EmptyMutation<()>, EmptySubscription<()>>(&(), &(), &());
+    let meta = scalar_meta::<i32>("Int");
+
+    let mut lexer = Lexer::new("[[1]]");
+    let mut parser = Parser::new(&mut lexer).unwrap().with_recursion_limit(2);
+    assert!(parse_value_literal(&mut parser, false, &schema, Some(&meta)).is_ok());
+
+    let mut lexer = Lexer::new("[[[1]]]");
+    let mut parser = Parser::new(&mut lexer).unwrap().with_recursion_limit(2);
+    assert_eq!(
+        parse_value_literal(&mut parser, false, &schema, Some(&meta))
+            .unwrap_err()
+            .item,
+        ParseError::RecursionLimitExceeded,
+    );
+}
@@ -129,11 +129,13 @@ where
     S: ScalarValue,
 {
     Ok(parser
-        .delimited_list(
-            &Token::BracketOpen,
-            |p| parse_value_literal(p, is_const, schema, tpe),
-            &Token::BracketClose,
-        )?
+        .nested(|parser| {
+            parser.delimited_list(
+                &Token::BracketOpen,
+                |p| parse_value_literal(p, is_const, schema, tpe),
+                &Token::BracketClose,
+            )
+        })?
         .map(InputValue::parsed_list))
 }
 
@@ -147,11 +149,13 @@ where
     S: ScalarValue,
 {
     Ok(parser
-        .delimited_list(
-            &Token::CurlyOpen,
-            |p| parse_object_field(p, is_const, schema, object_tpe),
-            &Token::CurlyClose,
-        )?
+        .nested(|parser| {
+            parser.delimited_list(
+                &Token::CurlyOpen,
+                |p| parse_object_field(p, is_const, schema, object_tpe),
+                &Token::CurlyClose,
+            )
+        })?
         .map(|items| InputValue::parsed_object(items.into_iter().map(|s| s.item).collect())))
 }
 
@@ -211,7 +215,7 @@ where
     S: ScalarValue,
 {
     let result = match token {
-        ScalarToken::String(_) => {
+        ScalarToken::String(_) | ScalarToken::BlockString(_) => {
             if let Some(&MetaType::Scalar(ref s)) = schema.concrete_type_by_name("String") {
                 (s.parse_fn)(token).map(InputValue::Scalar)
             } else {
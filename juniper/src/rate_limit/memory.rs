@@ -0,0 +1,93 @@
+use std::{
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use fnv::FnvHashMap;
+
+use crate::rate_limit::{BudgetStorage, CostBudget};
+
+/// Storage keeping the buckets in memory, shared by the requests executed by
+/// the process
+pub struct InMemoryBudgetStorage {
+    buckets: Mutex<Buckets>,
+}
+
+struct Buckets {
+    /// Points left in the bucket of each client, when it was last charged
+    points: FnvHashMap<String, (f64, Instant)>,
+    /// Number of buckets from which the full ones are removed
+    purge_at: usize,
+}
+
+const MIN_PURGE_AT: usize = 64;
+
+impl InMemoryBudgetStorage {
+    /// Constructs a storage with every bucket full
+    pub fn new() -> Self {
+        InMemoryBudgetStorage {
+            buckets: Mutex::new(Buckets {
+                points: FnvHashMap::default(),
+                purge_at: MIN_PURGE_AT,
+            }),
+        }
+    }
+
+    /// Fills all the buckets
+    pub fn clear(&self) {
+        self.buckets().points.clear();
+    }
+
+    fn buckets(&self) -> MutexGuard<'_, Buckets> {
+        self.buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for InMemoryBudgetStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for InMemoryBudgetStorage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InMemoryBudgetStorage")
+            .field("len", &self.buckets().points.len())
+            .finish()
+    }
+}
+
+impl BudgetStorage for InMemoryBudgetStorage {
+    fn charge(&self, client: &str, cost: u32, budget: &CostBudget) -> Result<(), Duration> {
+        let now = Instant::now();
+        let capacity = f64::from(budget.capacity);
+        let refilled = |points: f64, at: Instant| {
+            let refill = now.duration_since(at).as_secs_f64() / budget.period.as_secs_f64();
+            (points + refill * capacity).min(capacity)
+        };
+
+        let mut buckets = self.buckets();
+        if buckets.points.len() >= buckets.purge_at {
+            buckets
+                .points
+                .retain(|_, (points, at)| refilled(*points, *at) < capacity);
+            buckets.purge_at = MIN_PURGE_AT.max(buckets.points.len() * 2);
+        }
+
+        let points = buckets
+            .points
+            .get(client)
+            .map_or(capacity, |(points, at)| refilled(*points, *at));
+        let cost = f64::from(cost);
+        if points < cost {
+            return Err(budget.refill_time(cost - points));
+        }
+        buckets
+            .points
+            .insert(client.to_owned(), (points - cost, now));
+        Ok(())
+    }
+}
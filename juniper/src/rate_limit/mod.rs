@@ -0,0 +1,308 @@
+//! Per-client budgets of query cost
+//!
+//! A [`RateLimiter`](struct.RateLimiter.html) set with
+//! [`RootNode::with_rate_limiter`](../struct.RootNode.html#method.with_rate_limiter)
+//! charges the cost of each executed operation to the budget of its client,
+//! a token bucket holding up to `capacity` points refilled over `period`.
+//! Operations costing more than the points left fail with a `RATE_LIMITED`
+//! error without being executed, telling the client how many seconds to wait
+//! with a `retryAfter` extension.
+//!
+//! The cost of an operation is the number of fields it selects, the cost of
+//! the sub-selection of a field having a `first` or `last` integer argument
+//! being multiplied by its value, as for paginated connections. Fragments
+//! count each time they're spread and `__typename` is free.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use juniper::{
+//! #     rate_limit::{CostBudget, RateLimiter},
+//! #     EmptyMutation, EmptySubscription, RootNode,
+//! # };
+//! struct Context {
+//!     api_key: Option<String>,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object(Context = Context)]
+//! impl Query {
+//!     fn stock(sku: String) -> i32 {
+//!         42
+//!     }
+//! }
+//!
+//! let limiter = RateLimiter::new(
+//!     CostBudget {
+//!         capacity: 1000,
+//!         period: Duration::from_secs(60),
+//!     },
+//!     |context: &Context| context.api_key.clone(),
+//! );
+//! let schema = RootNode::new(
+//!     Query,
+//!     EmptyMutation::<Context>::new(),
+//!     EmptySubscription::<Context>::new(),
+//! )
+//! .with_rate_limiter(limiter);
+//! ```
+//!
+//! The budgets are kept in memory by default, see
+//! [`RateLimiter::with_storage`](struct.RateLimiter.html#method.with_storage)
+//! to share them between processes, e.g. with
+//! [`RedisBudgetStorage`](struct.RedisBudgetStorage.html) when the
+//! `redis-cache` feature is enabled.
+
+use std::{collections::HashMap, fmt, time::Duration};
+
+use crate::{
+    ast::{Definition, Document, Fragment, InputValue, Operation, Selection},
+    executor::{FieldError, Variables},
+    parser::Spanning,
+    value::{Object, ScalarValue, Value},
+};
+
+// `std::time::Instant` panics on `wasm32-unknown-unknown`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod memory;
+#[cfg(feature = "redis-cache")]
+mod redis;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use self::memory::InMemoryBudgetStorage;
+
+#[cfg(feature = "redis-cache")]
+pub use self::redis::RedisBudgetStorage;
+
+/// Points of cost a client may spend per `period`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostBudget {
+    /// Points a client may spend at once, its bucket being full
+    pub capacity: u32,
+    /// Time an empty bucket takes to be full again
+    pub period: Duration,
+}
+
+impl CostBudget {
+    /// Time the bucket takes to refill the `points`
+    pub fn refill_time(&self, points: f64) -> Duration {
+        let capacity = f64::from(self.capacity.max(1));
+        Duration::from_secs_f64(self.period.as_secs_f64() * points / capacity)
+    }
+}
+
+/// Identifies the client an operation is charged to from its context
+pub trait ClientIdentity<CtxT>: Send + Sync {
+    /// The client of the operations executed with the `context`, `None`
+    /// exempting them from the budgets
+    fn client_id(&self, context: &CtxT) -> Option<String>;
+}
+
+impl<CtxT, F> ClientIdentity<CtxT> for F
+where
+    F: Fn(&CtxT) -> Option<String> + Send + Sync,
+{
+    fn client_id(&self, context: &CtxT) -> Option<String> {
+        self(context)
+    }
+}
+
+/// Storage of the buckets of the clients
+///
+/// The storage is used from the executing thread, before executing each
+/// operation, so it shouldn't block for long.
+pub trait BudgetStorage: Send + Sync {
+    /// Takes the `cost` from the bucket of the `client`, refilled according
+    /// to the `budget` since it was last charged
+    ///
+    /// Returns the time the bucket takes to hold the `cost` when it holds
+    /// less, leaving it untouched. The `cost` never exceeds the capacity of the
+    /// `budget`.
+    fn charge(&self, client: &str, cost: u32, budget: &CostBudget) -> Result<(), Duration>;
+}
+
+/// Charges the cost of the operations executed with a context of type `CtxT`
+/// to the budgets of their clients
+pub struct RateLimiter<CtxT> {
+    budget: CostBudget,
+    client_identity: Box<dyn ClientIdentity<CtxT>>,
+    storage: Box<dyn BudgetStorage>,
+}
+
+impl<CtxT> RateLimiter<CtxT> {
+    /// Constructs a limiter giving the `budget` to each client identified by
+    /// the `client_identity`, keeping the buckets in memory
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn new<I>(budget: CostBudget, client_identity: I) -> Self
+    where
+        I: ClientIdentity<CtxT> + 'static,
+    {
+        Self::with_storage(budget, client_identity, InMemoryBudgetStorage::new())
+    }
+
+    /// Constructs a limiter giving the `budget` to each client identified by
+    /// the `client_identity`, keeping the buckets in the `storage`
+    pub fn with_storage<I, B>(budget: CostBudget, client_identity: I, storage: B) -> Self
+    where
+        I: ClientIdentity<CtxT> + 'static,
+        B: BudgetStorage + 'static,
+    {
+        RateLimiter {
+            budget,
+            client_identity: Box::new(client_identity),
+            storage: Box::new(storage),
+        }
+    }
+
+    /// The budget of each client
+    pub fn budget(&self) -> &CostBudget {
+        &self.budget
+    }
+
+    /// Charges the cost of the `operation` to the client of the `context`
+    pub(crate) fn charge<S>(
+        &self,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+        variables: &Variables<S>,
+        context: &CtxT,
+    ) -> Result<(), FieldError<S>>
+    where
+        S: ScalarValue,
+    {
+        let client = match self.client_identity.client_id(context) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+
+        let cost = operation_cost(document, operation, variables);
+        if cost == 0 {
+            return Ok(());
+        }
+        if cost > self.budget.capacity {
+            return Err(rate_limited(cost, None));
+        }
+        self.storage
+            .charge(&client, cost, &self.budget)
+            .map_err(|retry_after| rate_limited(cost, Some(retry_after)))
+    }
+}
+
+impl<CtxT> fmt::Debug for RateLimiter<CtxT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
+/// Error of the operations exceeding the budget of their client, the ones
+/// costing more than the capacity having no `retryAfter`
+fn rate_limited<S: ScalarValue>(cost: u32, retry_after: Option<Duration>) -> FieldError<S> {
+    let mut extensions = Object::with_capacity(3);
+    extensions.add_field("code", Value::from("RATE_LIMITED"));
+    extensions.add_field("cost", Value::from(clamp(u64::from(cost))));
+    if let Some(retry_after) = retry_after {
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        extensions.add_field("retryAfter", Value::from(clamp(seconds)));
+    }
+    FieldError::new("Rate limited", Value::object(extensions))
+}
+
+fn clamp(n: u64) -> i32 {
+    n.min(i32::MAX as u64) as i32
+}
+
+/// The cost of the `operation`, see the [module](index.html) documentation
+pub(crate) fn operation_cost<S>(
+    document: &Document<S>,
+    operation: &Spanning<Operation<S>>,
+    variables: &Variables<S>,
+) -> u32
+where
+    S: ScalarValue,
+{
+    let mut calculator = CostCalculator {
+        fragments: document
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Fragment(f) => Some((f.item.name.item, &f.item)),
+                _ => None,
+            })
+            .collect(),
+        variables,
+        spreading: Vec::new(),
+        fragment_costs: HashMap::new(),
+    };
+    calculator.cost(&operation.item.selection_set)
+}
+
+struct CostCalculator<'a, 'd, 'v, S> {
+    fragments: HashMap<&'a str, &'d Fragment<'a, S>>,
+    variables: &'v Variables<S>,
+    /// The fragments being spread, to stop at cycles
+    spreading: Vec<&'a str>,
+    /// The costs of the fragments already walked, so that fragments spread
+    /// several times are walked only once
+    fragment_costs: HashMap<&'a str, u32>,
+}
+
+impl<'a, 'd, 'v, S> CostCalculator<'a, 'd, 'v, S>
+where
+    S: ScalarValue,
+{
+    fn cost(&mut self, selection_set: &'d [Selection<'a, S>]) -> u32 {
+        selection_set.iter().fold(0u32, |cost, selection| {
+            let selection_cost = match selection {
+                Selection::Field(f) => {
+                    let field = &f.item;
+                    let own = if field.name.item == "__typename" {
+                        0
+                    } else {
+                        1
+                    };
+                    let children = field.selection_set.as_ref().map_or(0, |s| self.cost(s));
+                    let multiplier = field
+                        .arguments
+                        .as_ref()
+                        .and_then(|args| {
+                            args.item
+                                .items
+                                .iter()
+                                .filter(|(name, _)| name.item == "first" || name.item == "last")
+                                .filter_map(|(_, value)| self.int_value(&value.item))
+                                .max()
+                        })
+                        .unwrap_or(1);
+                    own + children.saturating_mul(multiplier)
+                }
+                Selection::InlineFragment(f) => self.cost(&f.item.selection_set),
+                Selection::FragmentSpread(s) => {
+                    let name = s.item.name.item;
+                    match (self.fragment_costs.get(name), self.fragments.get(name)) {
+                        (Some(&cost), _) => cost,
+                        (None, Some(fragment)) if !self.spreading.contains(&name) => {
+                            self.spreading.push(name);
+                            let cost = self.cost(&fragment.selection_set);
+                            self.spreading.pop();
+                            self.fragment_costs.insert(name, cost);
+                            cost
+                        }
+                        _ => 0,
+                    }
+                }
+            };
+            cost.saturating_add(selection_cost)
+        })
+    }
+
+    fn int_value(&self, value: &InputValue<S>) -> Option<u32> {
+        let value = match value {
+            InputValue::Variable(name) => self.variables.get(name)?,
+            value => value,
+        };
+        value.as_int_value().map(|n| n.max(0) as u32)
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use redis::{Client, Connection, IntoConnectionInfo, RedisResult, Script};
+
+use crate::rate_limit::{BudgetStorage, CostBudget};
+
+/// Refills and charges a bucket atomically, returning the milliseconds to
+/// wait for it to hold the cost, or 0 once charged
+const CHARGE: &str = r"
+local capacity = tonumber(ARGV[1])
+local period = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+local now = tonumber(ARGV[4])
+local bucket = redis.call('HMGET', KEYS[1], 'points', 'at')
+local points = tonumber(bucket[1]) or capacity
+local at = tonumber(bucket[2]) or now
+points = math.min(capacity, points + math.max(0, now - at) * capacity / period)
+if points < cost then
+    return math.max(1, math.ceil((cost - points) * period / capacity))
+end
+redis.call('HMSET', KEYS[1], 'points', points - cost, 'at', now)
+redis.call('PEXPIRE', KEYS[1], period)
+return 0
+";
+
+/// Storage keeping the buckets in Redis, shared by all the processes using
+/// the server
+///
+/// Buckets expire once full again. A single connection is used, established
+/// again after failing. Operations are allowed while the server can't be
+/// reached.
+pub struct RedisBudgetStorage {
+    client: Client,
+    prefix: String,
+    script: Script,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl RedisBudgetStorage {
+    /// Constructs a storage using the server with the provided URL, e.g.
+    /// `redis://127.0.0.1/`
+    ///
+    /// The connection is only established once the storage is used.
+    pub fn open<T: IntoConnectionInfo>(params: T) -> RedisResult<Self> {
+        Ok(RedisBudgetStorage {
+            client: Client::open(params)?,
+            prefix: String::new(),
+            script: Script::new(CHARGE),
+            connection: Mutex::new(None),
+        })
+    }
+
+    /// Prepends the provided prefix to the keys of the buckets, to share the
+    /// server with other data
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Option<Connection>> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for RedisBudgetStorage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RedisBudgetStorage")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl BudgetStorage for RedisBudgetStorage {
+    fn charge(&self, client: &str, cost: u32, budget: &CostBudget) -> Result<(), Duration> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let period = budget.period.as_millis().max(1) as u64;
+
+        let mut connection = self.connection();
+        if connection.is_none() {
+            *connection = self.client.get_connection().ok();
+        }
+        let connection_ref = match connection.as_mut() {
+            Some(connection) => connection,
+            None => return Ok(()),
+        };
+        let result: RedisResult<u64> = self
+            .script
+            .key(self.prefix.clone() + client)
+            .arg(budget.capacity)
+            .arg(period)
+            .arg(cost)
+            .arg(now)
+            .invoke(connection_ref);
+        match result {
+            Ok(0) => Ok(()),
+            Ok(wait) => Err(Duration::from_millis(wait)),
+            Err(_) => {
+                *connection = None;
+                Ok(())
+            }
+        }
+    }
+}
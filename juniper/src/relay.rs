@@ -0,0 +1,880 @@
+//! Utilities for [Relay](https://relay.dev/docs/guides/graphql-server-specification/)
+//! compliant schemas
+//!
+//! A [`Cursor`](struct.Cursor.html) is an opaque string encoding any
+//! serializable payload, like the position of an edge in a list. Cursors are
+//! signed with the key set by
+//! [`RootNode::with_cursor_key`](../struct.RootNode.html#method.with_cursor_key),
+//! so the server only accepts the cursors it returned:
+//!
+//! ```
+//! # use juniper::{relay::Cursor, EmptyMutation, EmptySubscription, Executor, FieldResult, RootNode};
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn users(executor: &Executor, after: Option<Cursor>) -> FieldResult<Vec<Cursor>> {
+//!         let offset: usize = match after {
+//!             Some(cursor) => cursor.decode::<usize, _>(executor.schema())? + 1,
+//!             None => 0,
+//!         };
+//!         let cursors = (offset..offset + 10)
+//!             .map(|position| Cursor::encode(&position, executor.schema()))
+//!             .collect::<Result<_, _>>()?;
+//!         Ok(cursors)
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_cursor_key("a secret key");
+//! ```
+//!
+//! The payload is only encoded, not encrypted: clients can still read it.
+//!
+//! Objects having a [`GlobalId`](struct.GlobalId.html) are fetched by the
+//! `node` and `nodes` fields of the query type once their types have fetchers,
+//! added with
+//! [`RootNode::with_node_fetcher`](../struct.RootNode.html#method.with_node_fetcher):
+//!
+//! ```
+//! # use juniper::{relay::GlobalId, EmptyMutation, EmptySubscription, RootNode, ID};
+//! struct User {
+//!     id: i32,
+//! }
+//!
+//! #[juniper::graphql_object]
+//! impl User {
+//!     fn id(&self) -> ID {
+//!         GlobalId::new("User", self.id.to_string()).into()
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn me() -> User {
+//!         User { id: 1 }
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_node_fetcher(|id: &str, _: &()| Ok(Some(User { id: id.parse()? })));
+//! ```
+
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+
+use fnv::FnvHashMap;
+use futures::future;
+
+use crate::{
+    ast::{Selection, Type},
+    executor::{ExecutionResult, Executor, FieldError, FieldResult, Registry},
+    parser::{ParseError, ScalarToken, Token},
+    schema::{
+        meta::{MetaType, ObjectMeta},
+        model::SchemaType,
+    },
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        scalars::ID,
+    },
+    value::{ParseScalarResult, ScalarValue, Value},
+    BoxFuture,
+};
+
+/// An opaque pagination cursor
+///
+/// Represented as a string, made of the base64url encoding of the JSON of the
+/// payload, followed by a `.` and the base64url encoding of its HMAC-SHA256
+/// signature when the schema has a cursor key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// Encodes the `payload`, signed with the cursor key of the `schema`
+    pub fn encode<T, S>(payload: &T, schema: &SchemaType<S>) -> Result<Cursor, CursorError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_vec(payload).map_err(CursorError::Encoding)?;
+        let mut cursor = base64::encode_config(json, base64::URL_SAFE_NO_PAD);
+        if let Some(key) = &schema.cursor_key {
+            let signature = key.sign(cursor.as_bytes()).finalize().into_bytes();
+            cursor.push('.');
+            base64::encode_config_buf(signature, base64::URL_SAFE_NO_PAD, &mut cursor);
+        }
+        Ok(Cursor(cursor))
+    }
+
+    /// Decodes the payload, checking the signature against the cursor key of
+    /// the `schema`
+    ///
+    /// Cursors are rejected when they aren't signed by the key, and when they
+    /// are signed while the schema has no key.
+    pub fn decode<T, S>(&self, schema: &SchemaType<S>) -> Result<T, CursorError>
+    where
+        T: DeserializeOwned,
+    {
+        let mut parts = self.0.splitn(2, '.');
+        let payload = parts.next().unwrap_or_default();
+        match (&schema.cursor_key, parts.next()) {
+            (Some(key), Some(signature)) => {
+                let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+                    .map_err(|_| CursorError::Malformed)?;
+                key.sign(payload.as_bytes())
+                    .verify_slice(&signature)
+                    .map_err(|_| CursorError::InvalidSignature)?;
+            }
+            (Some(_), None) => return Err(CursorError::InvalidSignature),
+            (None, Some(_)) => return Err(CursorError::Malformed),
+            (None, None) => {}
+        }
+
+        let json = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| CursorError::Malformed)?;
+        serde_json::from_slice(&json).map_err(CursorError::Decoding)
+    }
+
+    /// The cursor as sent to the clients
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(s: String) -> Cursor {
+        Cursor(s)
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[crate::graphql_scalar(name = "Cursor", description = "An opaque pagination cursor")]
+impl<S> GraphQLScalar for Cursor
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.0.clone())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Cursor> {
+        v.as_string_value().map(|s| Cursor(s.to_owned()))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(value) => Ok(S::from_string(value.to_owned())),
+            _ => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+/// Error of encoding or decoding a cursor
+#[derive(Debug)]
+pub enum CursorError {
+    /// The payload can't be serialized to JSON
+    Encoding(serde_json::Error),
+
+    /// The cursor isn't made of base64url encoded parts
+    Malformed,
+
+    /// The cursor isn't signed with the key of the schema
+    InvalidSignature,
+
+    /// The payload doesn't deserialize into the requested type
+    Decoding(serde_json::Error),
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CursorError::Encoding(e) => write!(f, "Failed to encode the cursor: {}", e),
+            CursorError::Malformed | CursorError::InvalidSignature | CursorError::Decoding(_) => {
+                f.write_str("Invalid cursor")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CursorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CursorError::Encoding(e) | CursorError::Decoding(e) => Some(e),
+            CursorError::Malformed | CursorError::InvalidSignature => None,
+        }
+    }
+}
+
+/// The key signing the cursors of a schema
+pub(crate) struct CursorKey(pub(crate) Vec<u8>);
+
+impl CursorKey {
+    fn sign(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC takes keys of any size");
+        mac.update(payload);
+        mac
+    }
+}
+
+// The key is secret.
+impl fmt::Debug for CursorKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CursorKey(..)")
+    }
+}
+
+/// A global ID, identifying an object among the objects of all the types, as
+/// required by the `Node` interface
+///
+/// Converts to an [`ID`](../struct.ID.html) made of the base64url encoding of
+/// the name of the type and of the ID of the object within the type, separated
+/// by a `:`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GlobalId {
+    type_name: String,
+    id: String,
+}
+
+impl GlobalId {
+    /// Identifies the object of the type `type_name` having the ID `id` within
+    /// the type
+    pub fn new<T: Into<String>, I: Into<String>>(type_name: T, id: I) -> Self {
+        GlobalId {
+            type_name: type_name.into(),
+            id: id.into(),
+        }
+    }
+
+    /// Decodes a global ID, returning `None` if the `id` isn't one
+    pub fn from_id(id: &ID) -> Option<Self> {
+        let decoded = base64::decode_config(&**id, base64::URL_SAFE_NO_PAD).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let (type_name, id) = decoded.split_once(':')?;
+        Some(GlobalId::new(type_name, id))
+    }
+
+    /// The name of the type of the object
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The ID of the object within its type
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl From<GlobalId> for ID {
+    fn from(id: GlobalId) -> ID {
+        let global = format!("{}:{}", id.type_name, id.id);
+        ID::new(base64::encode_config(global, base64::URL_SAFE_NO_PAD))
+    }
+}
+
+/// Object resolved behind the `Node` interface
+pub(crate) trait NodeObject<CtxT, S>: Send + Sync {
+    fn resolve_field(
+        &self,
+        field_name: &str,
+        arguments: &Arguments<S>,
+        executor: &Executor<CtxT, S>,
+    ) -> ExecutionResult<S>;
+
+    fn resolve_field_async<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<CtxT, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>>;
+}
+
+impl<T, CtxT, S> NodeObject<CtxT, S> for T
+where
+    T: GraphQLValueAsync<S, Context = CtxT, TypeInfo = ()> + Send,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field(
+        &self,
+        field_name: &str,
+        arguments: &Arguments<S>,
+        executor: &Executor<CtxT, S>,
+    ) -> ExecutionResult<S> {
+        GraphQLValue::resolve_field(self, &(), field_name, arguments, executor)
+    }
+
+    fn resolve_field_async<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<CtxT, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        GraphQLValueAsync::resolve_field_async(self, &(), field_name, arguments, executor)
+    }
+}
+
+/// A fetched object, as a value of the `Node` interface
+///
+/// Its selection set is resolved against the concrete type of the object, so
+/// its fields and fragments are those of the object.
+pub(crate) struct Node<CtxT, S> {
+    type_name: &'static str,
+    object: Box<dyn NodeObject<CtxT, S>>,
+}
+
+impl<CtxT, S> GraphQLType<S> for Node<CtxT, S>
+where
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<&str> {
+        Some("Node")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let fields = &[registry.field::<ID>("id", &())];
+        registry
+            .build_interface_type::<Self>(&(), fields)
+            .description("An object with a global ID")
+            .into_meta()
+    }
+}
+
+impl<CtxT, S> GraphQLValue<S> for Node<CtxT, S>
+where
+    S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, _: &'i ()) -> Option<&'i str> {
+        Some(self.type_name)
+    }
+
+    fn resolve_field(
+        &self,
+        _: &(),
+        field_name: &str,
+        arguments: &Arguments<S>,
+        executor: &Executor<CtxT, S>,
+    ) -> ExecutionResult<S> {
+        self.object.resolve_field(field_name, arguments, executor)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &(),
+        type_name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<CtxT, S>,
+    ) -> ExecutionResult<S> {
+        if type_name == self.type_name || type_name == "Node" {
+            self.resolve(info, selection_set, executor)
+        } else {
+            Ok(Value::null())
+        }
+    }
+
+    fn concrete_type_name(&self, _: &CtxT, _: &()) -> String {
+        self.type_name.to_owned()
+    }
+}
+
+impl<CtxT, S> GraphQLValueAsync<S> for Node<CtxT, S>
+where
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_async<'a>(
+        &'a self,
+        _: &'a (),
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<CtxT, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        self.object
+            .resolve_field_async(field_name, arguments, executor)
+    }
+
+    fn resolve_into_type_async<'a>(
+        &'a self,
+        info: &'a (),
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, CtxT, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        if type_name == self.type_name || type_name == "Node" {
+            self.resolve_async(info, selection_set, executor)
+        } else {
+            Box::pin(future::ready(Ok(Value::null())))
+        }
+    }
+}
+
+/// The nodes selected by the `node` or `nodes` field of the query type
+pub(crate) enum FetchedNodes<CtxT, S> {
+    One(Option<Node<CtxT, S>>),
+    Many(Vec<Option<Node<CtxT, S>>>),
+}
+
+type Fetcher<CtxT, S> =
+    Box<dyn Fn(&str, &CtxT) -> FieldResult<Option<Node<CtxT, S>>, S> + Send + Sync>;
+
+/// The fetchers added with
+/// [`RootNode::with_node_fetcher`](../struct.RootNode.html#method.with_node_fetcher),
+/// by name of the type they fetch
+pub(crate) struct NodeFetchers<CtxT, S>(FnvHashMap<&'static str, Fetcher<CtxT, S>>);
+
+impl<CtxT, S> NodeFetchers<CtxT, S>
+where
+    S: ScalarValue,
+{
+    pub(crate) fn new() -> Self {
+        NodeFetchers(FnvHashMap::default())
+    }
+
+    /// Whether the schema has the `node` and `nodes` fields
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds the `fetcher` of the objects of the type `T`, making `T` implement
+    /// the `Node` interface of the `schema`
+    pub(crate) fn register<'a, T, F>(&mut self, schema: &mut SchemaType<'a, S>, fetcher: F)
+    where
+        T: GraphQLValueAsync<S, Context = CtxT, TypeInfo = ()> + GraphQLType<S> + Send + 'static,
+        F: Fn(&str, &CtxT) -> FieldResult<Option<T>, S> + Send + Sync + 'static,
+        CtxT: Sync + 'static,
+        S: Send + Sync + 'a,
+    {
+        let type_name = T::name(&()).expect("Node types must be named");
+        match schema.types.get_mut(type_name) {
+            Some(MetaType::Object(ObjectMeta {
+                fields,
+                interface_names,
+                ..
+            })) => {
+                let id_type = Type::NonNullNamed("ID".into());
+                if !fields
+                    .iter()
+                    .any(|f| f.name == "id" && f.field_type == id_type)
+                {
+                    panic!("Type {} has no `id: ID!` field", type_name);
+                }
+                if !interface_names.iter().any(|name| name == "Node") {
+                    interface_names.push("Node".to_owned());
+                }
+            }
+            _ => panic!("Object type {} not found in the schema", type_name),
+        }
+
+        if self.is_empty() {
+            add_node_fields::<CtxT, S>(schema);
+        }
+        self.0.insert(
+            type_name,
+            Box::new(move |id, context| {
+                Ok(fetcher(id, context)?.map(|object| Node {
+                    type_name,
+                    object: Box::new(object),
+                }))
+            }),
+        );
+    }
+
+    /// Fetches the nodes selected by the `node` or `nodes` field
+    pub(crate) fn fetch_field(
+        &self,
+        field_name: &str,
+        arguments: &Arguments<S>,
+        context: &CtxT,
+    ) -> FieldResult<FetchedNodes<CtxT, S>, S> {
+        if field_name == "node" {
+            let id = arguments.get::<ID>("id").expect("Argument id missing");
+            Ok(FetchedNodes::One(self.fetch(&id, context)?))
+        } else {
+            let ids = arguments
+                .get::<Vec<ID>>("ids")
+                .expect("Argument ids missing");
+            let nodes = ids
+                .iter()
+                .map(|id| self.fetch(id, context))
+                .collect::<Result<_, _>>()?;
+            Ok(FetchedNodes::Many(nodes))
+        }
+    }
+
+    /// Fetches the object of the global `id`, if its type has a fetcher
+    fn fetch(&self, id: &ID, context: &CtxT) -> FieldResult<Option<Node<CtxT, S>>, S> {
+        let id = GlobalId::from_id(id).ok_or_else(|| {
+            FieldError::new(format!("Invalid node ID \"{}\"", &**id), Value::null())
+        })?;
+        match self.0.get(id.type_name()) {
+            Some(fetch) => fetch(id.id(), context),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<CtxT, S> fmt::Debug for NodeFetchers<CtxT, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NodeFetchers")
+            .field(&self.0.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Adds the `Node` interface to the `schema`, and the `node` and `nodes`
+/// fields to its query type
+fn add_node_fields<'a, CtxT, S>(schema: &mut SchemaType<'a, S>)
+where
+    S: ScalarValue + 'a,
+{
+    let mut registry = Registry::new(FnvHashMap::default());
+    let node = registry
+        .field::<Option<Node<CtxT, S>>>("node", &())
+        .argument(registry.arg::<ID>("id", &()))
+        .description("Fetches an object given its ID");
+    let nodes = registry
+        .field::<Vec<Option<Node<CtxT, S>>>>("nodes", &())
+        .argument(registry.arg::<Vec<ID>>("ids", &()))
+        .description("Fetches objects given their IDs");
+    for (name, meta) in registry.types {
+        schema.types.entry(name).or_insert(meta);
+    }
+
+    let query_type_name = schema.query_type_name.clone();
+    let fields = match schema.types.get_mut(&*query_type_name) {
+        Some(MetaType::Object(ObjectMeta { fields, .. })) => fields,
+        _ => unreachable!("The query type is an object"),
+    };
+    for field in [node, nodes] {
+        if fields.iter().any(|f| f.name == field.name) {
+            panic!(
+                "Type {} already has a `{}` field",
+                query_type_name, field.name,
+            );
+        }
+        fields.push(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Cursor, CursorError, GlobalId};
+    use crate::{
+        executor::Variables,
+        schema::model::{RootNode, SchemaType},
+        types::scalars::{EmptyMutation, EmptySubscription, ID},
+        value::DefaultScalarValue,
+        FieldResult,
+    };
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Position {
+        id: i32,
+        rank: String,
+    }
+
+    struct User {
+        id: i32,
+        name: &'static str,
+    }
+
+    #[crate::graphql_object]
+    impl User {
+        fn id(&self) -> ID {
+            GlobalId::new("User", self.id.to_string()).into()
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    struct Post {
+        id: i32,
+    }
+
+    #[crate::graphql_object]
+    impl Post {
+        fn id(&self) -> ID {
+            GlobalId::new("Post", self.id.to_string()).into()
+        }
+
+        fn title(&self) -> String {
+            format!("Post #{}", self.id)
+        }
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn next(executor: &Executor, after: Cursor) -> FieldResult<Cursor> {
+            let position: i32 = after.decode(executor.schema())?;
+            Ok(Cursor::encode(&(position + 1), executor.schema())?)
+        }
+
+        fn users() -> Vec<User> {
+            vec![]
+        }
+
+        fn posts() -> Vec<Post> {
+            vec![]
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    fn signed(key: &str) -> SchemaType<'static, DefaultScalarValue> {
+        schema().with_cursor_key(key).schema
+    }
+
+    fn position() -> Position {
+        Position {
+            id: 42,
+            rank: "b".into(),
+        }
+    }
+
+    #[test]
+    fn round_trips_unsigned_cursors() {
+        let schema = schema().schema;
+
+        let cursor = Cursor::encode(&position(), &schema).unwrap();
+
+        assert_eq!(cursor.as_str(), "eyJpZCI6NDIsInJhbmsiOiJiIn0");
+        assert_eq!(cursor.decode::<Position, _>(&schema).unwrap(), position());
+    }
+
+    #[test]
+    fn round_trips_signed_cursors() {
+        let schema = signed("secret");
+
+        let cursor = Cursor::encode(&position(), &schema).unwrap();
+
+        assert!(cursor.as_str().starts_with("eyJpZCI6NDIsInJhbmsiOiJiIn0."));
+        assert_eq!(cursor.decode::<Position, _>(&schema).unwrap(), position());
+    }
+
+    #[test]
+    fn rejects_forged_cursors() {
+        let key_schema = signed("secret");
+        let cursor = Cursor::encode(&position(), &key_schema).unwrap();
+        let signature = cursor.as_str().split('.').nth(1).unwrap();
+        let other = Position {
+            id: 43,
+            ..position()
+        };
+        let unsigned = Cursor::encode(&other, &schema().schema).unwrap();
+        let forged = Cursor::from(format!("{}.{}", unsigned, signature));
+        let garbled = Cursor::from(format!("{}.!", unsigned));
+
+        let other_schema = signed("other secret");
+        let decode = |cursor: &Cursor, schema| cursor.decode::<Position, _>(schema);
+
+        assert!(matches!(
+            decode(&forged, &key_schema),
+            Err(CursorError::InvalidSignature)
+        ));
+        assert!(matches!(
+            decode(&unsigned, &key_schema),
+            Err(CursorError::InvalidSignature)
+        ));
+        assert!(matches!(
+            decode(&cursor, &other_schema),
+            Err(CursorError::InvalidSignature)
+        ));
+        assert!(matches!(
+            decode(&garbled, &key_schema),
+            Err(CursorError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn rejects_signed_cursors_without_key() {
+        let cursor = Cursor::encode(&position(), &signed("secret")).unwrap();
+
+        let result = cursor.decode::<Position, _>(&schema().schema);
+
+        assert!(matches!(result, Err(CursorError::Malformed)));
+    }
+
+    #[test]
+    fn rejects_payloads_of_other_types() {
+        let schema = schema().schema;
+        let cursor = Cursor::encode(&position(), &schema).unwrap();
+
+        let result = cursor.decode::<i32, _>(&schema);
+
+        assert!(matches!(result, Err(CursorError::Decoding(_))));
+    }
+
+    #[test]
+    fn cursors_are_scalars() {
+        let schema = schema().with_cursor_key("secret");
+        let cursor = Cursor::encode(&1, &schema.schema).unwrap();
+        let query = format!(r#"{{ next(after: "{}") }}"#, cursor);
+
+        let output = crate::execute_sync(&query, None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(output.errors, []);
+        let next = Cursor::encode(&2, &schema.schema).unwrap();
+        assert_eq!(output.data, graphql_value!({ "next": (next.as_str()) }));
+
+        let output = crate::execute_sync(
+            r#"{ next(after: "MQ") }"#,
+            None,
+            &schema,
+            &Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_eq!(output.data, graphql_value!(None));
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.errors[0].error().message(), "Invalid cursor");
+    }
+
+    fn node_schema() -> Schema {
+        schema()
+            .with_node_fetcher(|id: &str, _: &()| {
+                let name = match id.parse()? {
+                    1 => "Ann",
+                    2 => "Bob",
+                    _ => return Ok(None),
+                };
+                Ok(Some(User {
+                    id: id.parse()?,
+                    name,
+                }))
+            })
+            .with_node_fetcher(|id: &str, _: &()| Ok(Some(Post { id: id.parse()? })))
+    }
+
+    fn global_id(type_name: &str, id: &str) -> String {
+        ID::from(GlobalId::new(type_name, id)).to_string()
+    }
+
+    #[test]
+    fn round_trips_global_ids() {
+        let id = ID::from(GlobalId::new("User", "1:2"));
+
+        assert_eq!(&*id, "VXNlcjoxOjI");
+        assert_eq!(GlobalId::from_id(&id), Some(GlobalId::new("User", "1:2")));
+        assert_eq!(GlobalId::from_id(&ID::new("VXNlcg")), None);
+        assert_eq!(GlobalId::from_id(&ID::new("!")), None);
+    }
+
+    #[tokio::test]
+    async fn resolves_nodes_by_global_id() {
+        let schema = node_schema();
+        let query = format!(
+            r#"{{
+                node(id: "{}") {{ id __typename ... on User {{ name }} ... on Post {{ title }} }}
+                nodes(ids: ["{}", "{}", "{}"]) {{ ... on Node {{ id }} ... on Post {{ title }} }}
+            }}"#,
+            global_id("User", "1"),
+            global_id("Post", "7"),
+            global_id("User", "3"),
+            global_id("Comment", "1"),
+        );
+
+        let output = crate::execute(&query, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(output.errors, []);
+        assert_eq!(
+            output.data,
+            graphql_value!({
+                "node": {
+                    "id": (global_id("User", "1")),
+                    "__typename": "User",
+                    "name": "Ann",
+                },
+                "nodes": [
+                    { "id": (global_id("Post", "7")), "title": "Post #7" },
+                    None,
+                    None,
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn resolves_nodes_synchronously() {
+        let schema = node_schema();
+        let query = format!(
+            r#"{{ node(id: "{}") {{ ... on User {{ name }} }} }}"#,
+            global_id("User", "2"),
+        );
+
+        let output = crate::execute_sync(&query, None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(output.errors, []);
+        assert_eq!(output.data, graphql_value!({ "node": { "name": "Bob" } }));
+    }
+
+    #[test]
+    fn rejects_invalid_node_ids() {
+        let schema = node_schema();
+        let query = format!(
+            r#"{{ node(id: "{}") {{ id }} }}"#,
+            global_id("Post", "first"),
+        );
+
+        for query in &[r#"{ node(id: "!") { id } }"#, &*query] {
+            let output = crate::execute_sync(query, None, &schema, &Variables::new(), &()).unwrap();
+
+            assert_eq!(output.data, graphql_value!({ "node": None }));
+            assert_eq!(output.errors.len(), 1);
+        }
+    }
+
+    #[test]
+    fn node_types_implement_the_interface() {
+        let schema = node_schema();
+        let query = r#"{
+            __type(name: "User") { interfaces { name } }
+            users { id }
+        }"#;
+
+        let output = crate::execute_sync(query, None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(output.errors, []);
+        assert_eq!(
+            output.data,
+            graphql_value!({
+                "__type": { "interfaces": [{ "name": "Node" }] },
+                "users": [],
+            }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Type Query has no `id: ID!` field")]
+    fn requires_node_types_to_have_ids() {
+        schema().with_node_fetcher(|_: &str, _: &()| Ok(Some(Query)));
+    }
+}
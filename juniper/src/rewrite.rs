@@ -0,0 +1,128 @@
+//! Rewriting of documents before they're executed
+//!
+//! A [`QueryRewriter`](trait.QueryRewriter.html) added with
+//! [`RootNode::with_query_rewriter`](../struct.RootNode.html#method.with_query_rewriter)
+//! receives every validated document before it's executed, and may change its
+//! selections or arguments, e.g. to select `__typename` on all the objects:
+//!
+//! ```
+//! # use juniper::{
+//! #     ast::{Definition, Document, Field, Selection},
+//! #     parser::Spanning,
+//! #     rewrite::QueryRewriter,
+//! #     graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription, RootNode, SchemaType,
+//! #     Variables,
+//! # };
+//! struct SelectTypename;
+//!
+//! /// Selects `__typename` in the selection sets nested in the provided one
+//! fn select_typename<S>(selection_set: &mut Vec<Selection<S>>) {
+//!     for selection in selection_set.iter_mut() {
+//!         match selection {
+//!             Selection::Field(field) => {
+//!                 if let Some(ref mut selection_set) = field.item.selection_set {
+//!                     select_typename(selection_set);
+//!                     selection_set.push(Selection::Field(Spanning::unlocated(Field {
+//!                         alias: None,
+//!                         name: Spanning::unlocated("__typename"),
+//!                         arguments: None,
+//!                         directives: None,
+//!                         selection_set: None,
+//!                     })));
+//!                 }
+//!             }
+//!             Selection::InlineFragment(fragment) => {
+//!                 select_typename(&mut fragment.item.selection_set);
+//!             }
+//!             Selection::FragmentSpread(_) => {}
+//!         }
+//!     }
+//! }
+//!
+//! impl QueryRewriter for SelectTypename {
+//!     fn rewrite(
+//!         &self,
+//!         document: &mut Document<DefaultScalarValue>,
+//!         _: &SchemaType<DefaultScalarValue>,
+//!     ) {
+//!         for definition in document.iter_mut() {
+//!             match definition {
+//!                 Definition::Operation(op) => select_typename(&mut op.item.selection_set),
+//!                 Definition::Fragment(f) => select_typename(&mut f.item.selection_set),
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(juniper::GraphQLObject)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn user() -> User {
+//!         User { name: "Ann".into() }
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_query_rewriter(SelectTypename);
+//!
+//! let output =
+//!     juniper::execute_sync("{ user { name } }", None, &schema, &Variables::new(), &()).unwrap();
+//! assert_eq!(
+//!     output.data,
+//!     graphql_value!({ "user": { "name": "Ann", "__typename": "User" } }),
+//! );
+//! ```
+//!
+//! Rewritten documents are validated again, so a rewriter selecting fields
+//! which don't exist makes the request fail with a validation error, instead
+//! of the execution panicking.
+
+use std::fmt;
+
+use crate::{ast::Document, schema::model::SchemaType, value::DefaultScalarValue};
+
+/// Rewriter of the documents executed with a schema
+///
+/// Gateways and auditing layers can use it to select additional fields,
+/// remove the selections a client isn't allowed to make, or restrict the
+/// arguments of fields.
+pub trait QueryRewriter<S = DefaultScalarValue>: Send + Sync {
+    /// Rewrites the document, which passed validation
+    fn rewrite(&self, document: &mut Document<S>, schema: &SchemaType<S>);
+}
+
+impl<S, F> QueryRewriter<S> for F
+where
+    F: Fn(&mut Document<S>, &SchemaType<S>) + Send + Sync,
+{
+    fn rewrite(&self, document: &mut Document<S>, schema: &SchemaType<S>) {
+        self(document, schema)
+    }
+}
+
+/// The rewriters added to a schema, applied in order
+pub(crate) struct QueryRewriters<S>(pub(crate) Vec<Box<dyn QueryRewriter<S>>>);
+
+impl<S> QueryRewriters<S> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn rewrite(&self, document: &mut Document<S>, schema: &SchemaType<S>) {
+        for rewriter in &self.0 {
+            rewriter.rewrite(document, schema);
+        }
+    }
+}
+
+impl<S> fmt::Debug for QueryRewriters<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QueryRewriters({})", self.0.len())
+    }
+}
@@ -0,0 +1,220 @@
+//! Ordering of the resolution of fields
+//!
+//! The fields of a selection set are resolved concurrently by the asynchronous
+//! executor, the resolvers being started in the order of the query. A
+//! [`FieldScheduler`](trait.FieldScheduler.html) added with
+//! [`RootNode::with_field_scheduler`](../struct.RootNode.html#method.with_field_scheduler)
+//! prioritizes some of them instead, e.g. to start the expensive fields
+//! before the other ones:
+//!
+//! ```
+//! # use juniper::{schedule::ScheduledField, EmptyMutation, EmptySubscription, RootNode};
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     async fn name() -> &str {
+//!         "Ann"
+//!     }
+//!
+//!     async fn recommendations() -> Vec<String> {
+//!         // Long running search.
+//!         vec!["Bob".into()]
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_field_scheduler(|field: &ScheduledField| {
+//!         if field.field_name() == "recommendations" {
+//!             1
+//!         } else {
+//!             0
+//!         }
+//!     });
+//! ```
+//!
+//! Priorities only change when the resolvers start: the fields of the
+//! responses keep the order of the query, and serial fields are still resolved
+//! apart from their siblings. Synchronous execution resolves the fields one
+//! after another, in the order of the query.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    future::{self, Join, Ready},
+    ready,
+    stream::{FuturesUnordered, Stream, StreamExt as _},
+};
+
+use crate::{ast::Type, schema::meta::Field, types::base::Arguments, value::DefaultScalarValue};
+
+/// Prioritizer of the fields resolved concurrently
+///
+/// Applications can use it to start the resolvers of expensive fields early,
+/// or the ones of cheap fields first.
+pub trait FieldScheduler<S = DefaultScalarValue>: Send + Sync {
+    /// Priority of the field, the fields of a selection set being started
+    /// from the highest priority to the lowest one
+    ///
+    /// Fields of the same priority are started in the order of the query, and
+    /// fragments with the priority `0`.
+    fn priority(&self, field: &ScheduledField<S>) -> i32;
+}
+
+impl<S, F> FieldScheduler<S> for F
+where
+    F: Fn(&ScheduledField<S>) -> i32 + Send + Sync,
+{
+    fn priority(&self, field: &ScheduledField<S>) -> i32 {
+        self(field)
+    }
+}
+
+/// Field being scheduled
+pub struct ScheduledField<'a, S = DefaultScalarValue> {
+    pub(crate) type_name: &'a str,
+    pub(crate) field: &'a Field<'a, S>,
+    pub(crate) response_name: &'a str,
+    pub(crate) arguments: &'a Arguments<'a, S>,
+    pub(crate) is_leaf: bool,
+}
+
+impl<'a, S> ScheduledField<'a, S> {
+    /// The name of the type defining the field
+    pub fn type_name(&self) -> &'a str {
+        self.type_name
+    }
+
+    /// The name of the field
+    pub fn field_name(&self) -> &'a str {
+        &self.field.name
+    }
+
+    /// The alias of the field, or its name
+    pub fn response_name(&self) -> &'a str {
+        self.response_name
+    }
+
+    /// The type of the field, like `[User!]!`
+    pub fn field_type(&self) -> &'a Type<'a> {
+        &self.field.field_type
+    }
+
+    /// Whether the field resolves to scalars or enums, instead of objects
+    /// having fields of their own
+    pub fn is_leaf(&self) -> bool {
+        self.is_leaf
+    }
+
+    /// The arguments of the field, with the variables substituted
+    pub fn arguments(&self) -> &'a Arguments<'a, S> {
+        self.arguments
+    }
+}
+
+/// The scheduler added to a schema
+pub(crate) struct Scheduler<S>(pub(crate) Box<dyn FieldScheduler<S>>);
+
+impl<S> fmt::Debug for Scheduler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Scheduler(..)")
+    }
+}
+
+/// Futures started in the order of their priorities, yielding their outputs in
+/// the order they're pushed
+///
+/// The futures pushed since the last poll are started at the next one.
+pub(crate) struct ScheduledFutures<F: Future> {
+    queued: Vec<(i32, usize, F)>,
+    running: FuturesUnordered<Join<F, Ready<usize>>>,
+    outputs: BTreeMap<usize, F::Output>,
+    pushed: usize,
+    yielded: usize,
+}
+
+// The futures are only pinned once moved into `running`, which boxes them.
+impl<F: Future> Unpin for ScheduledFutures<F> {}
+
+impl<F: Future> ScheduledFutures<F> {
+    pub(crate) fn new() -> Self {
+        ScheduledFutures {
+            queued: Vec::new(),
+            running: FuturesUnordered::new(),
+            outputs: BTreeMap::new(),
+            pushed: 0,
+            yielded: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, priority: i32, future: F) {
+        self.queued.push((priority, self.pushed, future));
+        self.pushed += 1;
+    }
+}
+
+impl<F: Future> Stream for ScheduledFutures<F> {
+    type Item = F::Output;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        // `FuturesUnordered` first polls its futures in the order they're
+        // pushed, and the sort is stable.
+        this.queued.sort_by_key(|&(priority, ..)| Reverse(priority));
+        for (_, index, future) in this.queued.drain(..) {
+            this.running
+                .push(future::join(future, future::ready(index)));
+        }
+
+        loop {
+            if let Some(output) = this.outputs.remove(&this.yielded) {
+                this.yielded += 1;
+                return Poll::Ready(Some(output));
+            }
+            match ready!(this.running.poll_next_unpin(cx)) {
+                Some((output, index)) => {
+                    this.outputs.insert(index, output);
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::{future, StreamExt as _};
+
+    use super::ScheduledFutures;
+
+    #[tokio::test]
+    async fn starts_by_priority_and_yields_in_order() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let mut futures = ScheduledFutures::new();
+        for &(name, priority) in &[("a", 0), ("b", 2), ("c", 1), ("d", 2)] {
+            let started = started.clone();
+            futures.push(
+                priority,
+                future::lazy(move |_| {
+                    started.lock().unwrap().push(name);
+                    name
+                }),
+            );
+        }
+
+        let outputs = futures.collect::<Vec<_>>().await;
+
+        assert_eq!(outputs, vec!["a", "b", "c", "d"]);
+        assert_eq!(*started.lock().unwrap(), vec!["b", "d", "c", "a"]);
+    }
+}
@@ -0,0 +1,324 @@
+//! Checks of a schema against the type system rules of the GraphQL
+//! specification, run once the schema is built
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::Type,
+    schema::{
+        meta::{Argument, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, UnionMeta},
+        model::SchemaType,
+    },
+    types::name::Name,
+};
+
+impl<'a, S> SchemaType<'a, S> {
+    /// Collect the violations of the type system rules, sorted by type name
+    pub(crate) fn violations(&self) -> Vec<String> {
+        let mut types = self.types.values().collect::<Vec<_>>();
+        types.sort_by_key(|t| t.name());
+
+        let referenced = self.referenced_type_names();
+
+        let mut violations = vec![];
+        for meta in types {
+            let type_name = meta.name().unwrap_or_default();
+            if !meta.is_builtin() && !referenced.contains(type_name) {
+                violations.push(format!(
+                    r#"Type "{}" is not referenced from the root operation types"#,
+                    type_name,
+                ));
+            }
+            if type_name.starts_with("__") {
+                // Introspection types
+                continue;
+            }
+            match meta {
+                MetaType::Object(object) => {
+                    check_fields(type_name, &object.fields, &mut violations);
+                    self.check_implementations(object, &mut violations);
+                }
+                MetaType::Interface(interface) => {
+                    check_fields(type_name, &interface.fields, &mut violations);
+                    self.check_instance_types(interface, &mut violations);
+                }
+                MetaType::Union(union) => self.check_union(union, &mut violations),
+                MetaType::Enum(enum_meta) => {
+                    for value in &enum_meta.values {
+                        if !Name::is_valid(&value.name)
+                            || ["true", "false", "null"].contains(&value.name.as_str())
+                        {
+                            violations.push(format!(
+                                r#"Enum "{}" has an invalid value "{}""#,
+                                type_name, value.name,
+                            ));
+                        }
+                    }
+                }
+                MetaType::InputObject(input_object) => {
+                    check_arguments(type_name, &input_object.input_fields, &mut violations);
+                    self.check_input_cycle(input_object, &mut violations);
+                }
+                _ => {}
+            }
+        }
+        violations
+    }
+
+    /// Collect the names of the types reachable from the root operation types
+    /// and the arguments of the directives, through fields, arguments,
+    /// interfaces, their implementations and union members
+    fn referenced_type_names(&self) -> HashSet<&str> {
+        let mut pending = vec![self.query_type_name.as_str()];
+        pending.extend(self.mutation_type_name.as_deref());
+        pending.extend(self.subscription_type_name.as_deref());
+        pending.extend(
+            self.directives
+                .values()
+                .flat_map(|d| &d.arguments)
+                .map(|a| a.arg_type.innermost_name()),
+        );
+
+        let mut referenced = HashSet::new();
+        while let Some(name) = pending.pop() {
+            if !referenced.insert(name) {
+                continue;
+            }
+            match self.types.get(name) {
+                Some(MetaType::Object(object)) => {
+                    push_field_types(&object.fields, &mut pending);
+                    pending.extend(object.interface_names.iter().map(String::as_str));
+                }
+                Some(MetaType::Interface(interface)) => {
+                    push_field_types(&interface.fields, &mut pending);
+                    pending.extend(
+                        interface
+                            .instance_type_names
+                            .iter()
+                            .flatten()
+                            .map(String::as_str),
+                    );
+                    pending.extend(self.types.values().filter_map(|t| match t {
+                        MetaType::Object(object)
+                            if object.interface_names.iter().any(|i| i == name) =>
+                        {
+                            Some(object.name.as_ref())
+                        }
+                        _ => None,
+                    }));
+                }
+                Some(MetaType::Union(union)) => {
+                    pending.extend(union.of_type_names.iter().map(String::as_str));
+                }
+                Some(MetaType::InputObject(input_object)) => {
+                    pending.extend(
+                        input_object
+                            .input_fields
+                            .iter()
+                            .map(|f| f.arg_type.innermost_name()),
+                    );
+                }
+                _ => {}
+            }
+        }
+        referenced
+    }
+
+    fn check_implementations(&self, object: &ObjectMeta<'a, S>, violations: &mut Vec<String>) {
+        for interface_name in &object.interface_names {
+            let interface = match self.types.get(interface_name.as_str()) {
+                Some(MetaType::Interface(interface)) => interface,
+                _ => {
+                    violations.push(format!(
+                        r#"Object "{}" implements "{}", which is not an interface"#,
+                        object.name, interface_name,
+                    ));
+                    continue;
+                }
+            };
+
+            if let Some(instance_type_names) = &interface.instance_type_names {
+                if !instance_type_names.contains(&object.name.to_string()) {
+                    violations.push(format!(
+                        r#"Object "{}" implements "{}", which can't resolve into it"#,
+                        object.name, interface.name,
+                    ));
+                }
+            }
+
+            for interface_field in &interface.fields {
+                let location = format!(r#""{}.{}""#, object.name, interface_field.name);
+                let field = match object
+                    .fields
+                    .iter()
+                    .find(|f| f.name == interface_field.name)
+                {
+                    Some(field) => field,
+                    None => {
+                        violations.push(format!(
+                            r#"Field {} of interface "{}" is missing"#,
+                            location, interface.name,
+                        ));
+                        continue;
+                    }
+                };
+
+                if !self.is_subtype(&field.field_type, &interface_field.field_type) {
+                    violations.push(format!(
+                        r#"Field {} has type "{}", which is not a subtype of "{}" on interface "{}""#,
+                        location, field.field_type, interface_field.field_type, interface.name,
+                    ));
+                }
+
+                let arguments = field.arguments.as_deref().unwrap_or_default();
+                let interface_arguments = interface_field.arguments.as_deref().unwrap_or_default();
+                for interface_argument in interface_arguments {
+                    match arguments.iter().find(|a| a.name == interface_argument.name) {
+                        Some(argument) if argument.arg_type == interface_argument.arg_type => {}
+                        Some(argument) => violations.push(format!(
+                            r#"Argument "{}" of field {} has type "{}" instead of "{}" on interface "{}""#,
+                            argument.name,
+                            location,
+                            argument.arg_type,
+                            interface_argument.arg_type,
+                            interface.name,
+                        )),
+                        None => violations.push(format!(
+                            r#"Argument "{}" of field {} from interface "{}" is missing"#,
+                            interface_argument.name, location, interface.name,
+                        )),
+                    }
+                }
+                for argument in arguments {
+                    if argument.arg_type.is_non_null()
+                        && argument.default_value.is_none()
+                        && !interface_arguments.iter().any(|a| a.name == argument.name)
+                    {
+                        violations.push(format!(
+                            r#"Argument "{}" of field {} is required, but not declared on interface "{}""#,
+                            argument.name, location, interface.name,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_instance_types(&self, interface: &InterfaceMeta<'a, S>, violations: &mut Vec<String>) {
+        for instance in interface.instance_type_names.iter().flatten() {
+            match self.types.get(instance.as_str()) {
+                Some(MetaType::Object(object))
+                    if object.interface_names.contains(&interface.name.to_string()) => {}
+                Some(MetaType::Object(_)) => violations.push(format!(
+                    r#"Interface "{}" resolves into "{}", which doesn't implement it"#,
+                    interface.name, instance,
+                )),
+                _ => violations.push(format!(
+                    r#"Interface "{}" resolves into "{}", which is not an object type"#,
+                    interface.name, instance,
+                )),
+            }
+        }
+    }
+
+    fn check_union(&self, union: &UnionMeta<'a>, violations: &mut Vec<String>) {
+        for member in &union.of_type_names {
+            match self.types.get(member.as_str()) {
+                Some(MetaType::Object(_)) => {}
+                _ => violations.push(format!(
+                    r#"Union "{}" has member "{}", which is not an object type"#,
+                    union.name, member,
+                )),
+            }
+        }
+    }
+
+    /// Check that the input object can't require itself, directly or through
+    /// other input objects, via non-null fields, which makes it impossible to
+    /// provide a value
+    fn check_input_cycle(
+        &self,
+        input_object: &InputObjectMeta<'a, S>,
+        violations: &mut Vec<String>,
+    ) {
+        let mut path = vec![];
+        if self.requires_input_object(&input_object.name, input_object, &mut path) {
+            violations.push(format!(
+                r#"Input object "{}" can't be provided, as it requires itself through non-null fields: {}"#,
+                input_object.name,
+                path.join(" -> "),
+            ));
+        }
+    }
+
+    fn requires_input_object(
+        &self,
+        target: &str,
+        input_object: &InputObjectMeta<'a, S>,
+        path: &mut Vec<String>,
+    ) -> bool {
+        for field in &input_object.input_fields {
+            let field_type = match field.arg_type {
+                Type::NonNullNamed(ref name) if field.default_value.is_none() => name,
+                _ => continue,
+            };
+            let location = format!("{}.{}", input_object.name, field.name);
+            if path.contains(&location) {
+                continue;
+            }
+            path.push(location);
+            if field_type == target {
+                return true;
+            }
+            if let Some(MetaType::InputObject(nested)) = self.types.get(field_type.as_ref()) {
+                if self.requires_input_object(target, nested, path) {
+                    return true;
+                }
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+fn push_field_types<'a, S>(fields: &'a [Field<S>], pending: &mut Vec<&'a str>) {
+    for field in fields {
+        pending.push(field.field_type.innermost_name());
+        for argument in field.arguments.iter().flatten() {
+            pending.push(argument.arg_type.innermost_name());
+        }
+    }
+}
+
+fn check_fields<S>(type_name: &str, fields: &[Field<S>], violations: &mut Vec<String>) {
+    for field in fields {
+        if ["__typename", "__schema", "__type"].contains(&field.name.as_str()) {
+            continue;
+        }
+        check_name("Field", type_name, &field.name, violations);
+        for argument in field.arguments.as_deref().unwrap_or_default() {
+            let location = format!("{}.{}", type_name, field.name);
+            check_name("Argument", &location, &argument.name, violations);
+        }
+    }
+}
+
+fn check_arguments<S>(type_name: &str, fields: &[Argument<S>], violations: &mut Vec<String>) {
+    for field in fields {
+        check_name("Field", type_name, &field.name, violations);
+    }
+}
+
+fn check_name(kind: &str, location: &str, name: &str, violations: &mut Vec<String>) {
+    if !Name::is_valid(name) {
+        violations.push(format!(
+            r#"{} "{}" of "{}" is not a valid GraphQL name"#,
+            kind, name, location,
+        ));
+    } else if name.starts_with("__") {
+        violations.push(format!(
+            r#"{} "{}" of "{}" must not begin with "__", which is reserved by GraphQL"#,
+            kind, name, location,
+        ));
+    }
+}
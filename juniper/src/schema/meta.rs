@@ -6,13 +6,17 @@ use std::{
 };
 
 use crate::{
-    ast::{FromInputValue, InputValue, Type},
+    ast::{FromInputError, FromInputValue, InputValue, Type},
+    cache::CacheHint,
     parser::{ParseError, ScalarToken},
     schema::model::SchemaType,
     types::base::TypeKind,
     value::{DefaultScalarValue, ParseScalarValue, ScalarValue},
 };
 
+/// Function checking whether an `InputValue` can be parsed as a type, or why it can't
+pub type InputValueParseFn<S> = for<'b> fn(&'b InputValue<S>) -> Result<(), FromInputError>;
+
 /// Whether an item is deprecated, with context.
 #[derive(Debug, PartialEq, Hash, Clone)]
 pub enum DeprecationStatus {
@@ -38,87 +42,125 @@ impl DeprecationStatus {
             DeprecationStatus::Deprecated(ref reason) => reason.as_ref(),
         }
     }
+
+    /// The `@deprecated` directive of a deprecated item, or none if `Current`.
+    pub fn directive<S: ScalarValue>(&self) -> Option<AppliedDirective<S>> {
+        match self {
+            DeprecationStatus::Current => None,
+            DeprecationStatus::Deprecated(reason) => Some(AppliedDirective {
+                name: "deprecated".to_owned(),
+                arguments: reason
+                    .iter()
+                    .map(|r| ("reason".to_owned(), InputValue::scalar(r.clone())))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// A directive applied to an item of the schema, as printed in the schema
+/// language
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppliedDirective<S> {
+    /// The name of the directive, without the `@`
+    pub name: String,
+    /// The arguments of the directive, in order
+    pub arguments: Vec<(String, InputValue<S>)>,
 }
 
 /// Scalar type metadata
 pub struct ScalarMeta<'a, S> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
     pub(crate) parse_fn: for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>,
 }
 
 /// List type metadata
 #[derive(Debug)]
 pub struct ListMeta<'a> {
-    #[doc(hidden)]
+    /// The type of the list items
     pub of_type: Type<'a>,
 }
 
 /// Nullable type metadata
 #[derive(Debug)]
 pub struct NullableMeta<'a> {
-    #[doc(hidden)]
+    /// The type being made nullable
     pub of_type: Type<'a>,
 }
 
 /// Object type metadata
 #[derive(Debug)]
 pub struct ObjectMeta<'a, S> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The fields of the type, including `__typename` for objects
     pub fields: Vec<Field<'a, S>>,
-    #[doc(hidden)]
+    /// The names of the interfaces the object implements
     pub interface_names: Vec<String>,
+    /// How long fields returning the object may be cached, unless they have
+    /// their own hint
+    pub cache_hint: Option<CacheHint>,
+    /// The scopes required to access the object, if it requires
+    /// authorization, see the [`auth`](../auth/index.html) module
+    pub required_scopes: Option<Vec<String>>,
 }
 
 /// Enum type metadata
 pub struct EnumMeta<'a, S> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The possible values of the enum
     pub values: Vec<EnumValue>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
 }
 
 /// Interface type metadata
 #[derive(Debug)]
 pub struct InterfaceMeta<'a, S> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The fields of the type, including `__typename` for objects
     pub fields: Vec<Field<'a, S>>,
+    /// How long fields returning the interface may be cached, unless they
+    /// have their own hint
+    pub cache_hint: Option<CacheHint>,
+    /// The names of the object types the interface can resolve into, if known
+    ///
+    /// Every object implementing the interface must be one of them, as it
+    /// couldn't be resolved otherwise.
+    pub instance_type_names: Option<Vec<String>>,
 }
 
 /// Union type metadata
 #[derive(Debug)]
 pub struct UnionMeta<'a> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The names of the object types the union consists of
     pub of_type_names: Vec<String>,
 }
 
 /// Input object metadata
 pub struct InputObjectMeta<'a, S> {
-    #[doc(hidden)]
+    /// The name of the type
     pub name: Cow<'a, str>,
-    #[doc(hidden)]
+    /// The optional description of the type
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The fields of the input object
     pub input_fields: Vec<Argument<'a, S>>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
 }
 
 /// A placeholder for not-yet-registered types
@@ -127,46 +169,57 @@ pub struct InputObjectMeta<'a, S> {
 /// is inserted into a registry to indicate existence.
 #[derive(Debug)]
 pub struct PlaceholderMeta<'a> {
-    #[doc(hidden)]
+    /// The type being built
     pub of_type: Type<'a>,
 }
 
 /// Generic type metadata
 #[derive(Debug)]
 pub enum MetaType<'a, S = DefaultScalarValue> {
-    #[doc(hidden)]
+    /// A scalar type
     Scalar(ScalarMeta<'a, S>),
-    #[doc(hidden)]
+    /// A list type
     List(ListMeta<'a>),
-    #[doc(hidden)]
+    /// A nullable type
     Nullable(NullableMeta<'a>),
-    #[doc(hidden)]
+    /// An object type
     Object(ObjectMeta<'a, S>),
-    #[doc(hidden)]
+    /// An enum type
     Enum(EnumMeta<'a, S>),
-    #[doc(hidden)]
+    /// An interface type
     Interface(InterfaceMeta<'a, S>),
-    #[doc(hidden)]
+    /// A union type
     Union(UnionMeta<'a>),
-    #[doc(hidden)]
+    /// An input object type
     InputObject(InputObjectMeta<'a, S>),
-    #[doc(hidden)]
+    /// A type whose metadata is still being built
     Placeholder(PlaceholderMeta<'a>),
 }
 
 /// Metadata for a field
 #[derive(Debug, Clone)]
 pub struct Field<'a, S> {
-    #[doc(hidden)]
+    /// The name of the field
     pub name: String,
-    #[doc(hidden)]
+    /// The optional description of the field
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The arguments of the field, if any
     pub arguments: Option<Vec<Argument<'a, S>>>,
-    #[doc(hidden)]
+    /// The type of the field
     pub field_type: Type<'a>,
-    #[doc(hidden)]
+    /// Whether the field is deprecated or not, with an optional reason
     pub deprecation_status: DeprecationStatus,
+    /// How long the field may be cached
+    pub cache_hint: Option<CacheHint>,
+    /// Whether the objects implementing the interface of the field may omit
+    /// it, leaving its resolution to the interface
+    pub default_impl: bool,
+    /// Whether the field is resolved apart from its siblings, instead of
+    /// concurrently with them
+    pub serial: bool,
+    /// The scopes required to access the field, if it requires
+    /// authorization, see the [`auth`](../auth/index.html) module
+    pub required_scopes: Option<Vec<String>>,
 }
 
 impl<'a, S> Field<'a, S> {
@@ -175,18 +228,57 @@ impl<'a, S> Field<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// The arguments of the field, empty if it has none
+    pub fn argument_list(&self) -> &[Argument<'a, S>] {
+        self.arguments.as_deref().unwrap_or(&[])
+    }
+
+    /// Access an argument's meta data given its name
+    pub fn argument_by_name(&self, name: &str) -> Option<&Argument<'a, S>> {
+        self.argument_list().iter().find(|a| a.name == name)
+    }
+
+    /// Returns true if the field may resolve to `null`.
+    pub fn is_nullable(&self) -> bool {
+        !self.field_type.is_non_null()
+    }
+
+    /// The name of the type of the field, without its list and non-null
+    /// wrappers, see [`Type::wrappers`](../struct.Type.html#method.wrappers)
+    pub fn type_name(&self) -> &str {
+        self.field_type.innermost_name()
+    }
+
+    /// Returns true if the field is deprecated.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_status.is_deprecated()
+    }
+
+    /// The reason why the field is deprecated, if given
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_status.reason().map(String::as_str)
+    }
+
+    /// The directives applied to the field in the schema language
+    pub fn directives(&self) -> Vec<AppliedDirective<S>>
+    where
+        S: ScalarValue,
+    {
+        self.deprecation_status.directive().into_iter().collect()
+    }
 }
 
 /// Metadata for an argument to a field
 #[derive(Debug, Clone)]
 pub struct Argument<'a, S> {
-    #[doc(hidden)]
+    /// The name of the argument
     pub name: String,
-    #[doc(hidden)]
+    /// The optional description of the argument
     pub description: Option<String>,
-    #[doc(hidden)]
+    /// The type of the argument
     pub arg_type: Type<'a>,
-    #[doc(hidden)]
+    /// The default value of the argument, if any
     pub default_value: Option<InputValue<S>>,
 }
 
@@ -196,6 +288,23 @@ impl<'a, S> Argument<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// Returns true if the argument accepts `null`.
+    pub fn is_nullable(&self) -> bool {
+        !self.arg_type.is_non_null()
+    }
+
+    /// Returns true if the argument has to be given, being non-null without a
+    /// default value.
+    pub fn is_required(&self) -> bool {
+        self.arg_type.is_non_null() && self.default_value.is_none()
+    }
+
+    /// The name of the type of the argument, without its list and non-null
+    /// wrappers, see [`Type::wrappers`](../struct.Type.html#method.wrappers)
+    pub fn type_name(&self) -> &str {
+        self.arg_type.innermost_name()
+    }
 }
 
 /// Metadata for a single value in an enum
@@ -214,6 +323,23 @@ pub struct EnumValue {
     pub deprecation_status: DeprecationStatus,
 }
 
+impl EnumValue {
+    /// Returns true if the enum value is deprecated.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_status.is_deprecated()
+    }
+
+    /// The reason why the enum value is deprecated, if given
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_status.reason().map(String::as_str)
+    }
+
+    /// The directives applied to the enum value in the schema language
+    pub fn directives<S: ScalarValue>(&self) -> Vec<AppliedDirective<S>> {
+        self.deprecation_status.directive().into_iter().collect()
+    }
+}
+
 impl<'a, S> MetaType<'a, S> {
     /// Access the name of the type, if applicable
     ///
@@ -288,6 +414,83 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// The fields of the type
+    ///
+    /// Only objects and interfaces have fields. This method always returns an empty slice for
+    /// other types.
+    pub fn field_list(&self) -> &[Field<'a, S>] {
+        match *self {
+            MetaType::Object(ObjectMeta { ref fields, .. })
+            | MetaType::Interface(InterfaceMeta { ref fields, .. }) => fields,
+            _ => &[],
+        }
+    }
+
+    /// The input fields of the type
+    ///
+    /// Only input objects have input fields. This method always returns an empty slice for
+    /// other types.
+    pub fn input_field_list(&self) -> &[Argument<'a, S>] {
+        match *self {
+            MetaType::InputObject(InputObjectMeta {
+                ref input_fields, ..
+            }) => input_fields,
+            _ => &[],
+        }
+    }
+
+    /// The values of the type
+    ///
+    /// Only enums have values. This method always returns an empty slice for other types.
+    pub fn enum_value_list(&self) -> &[EnumValue] {
+        match *self {
+            MetaType::Enum(EnumMeta { ref values, .. }) => values,
+            _ => &[],
+        }
+    }
+
+    /// The names of the interfaces the type implements
+    ///
+    /// Only objects implement interfaces. This method always returns an empty slice for other
+    /// types.
+    pub fn interface_names(&self) -> &[String] {
+        match *self {
+            MetaType::Object(ObjectMeta {
+                ref interface_names,
+                ..
+            }) => interface_names,
+            _ => &[],
+        }
+    }
+
+    /// The names of the object types the type may resolve into, if known
+    ///
+    /// Only unions and interfaces listing their instance types have possible types. Use
+    /// `SchemaType::possible_types` to also find the objects implementing an interface.
+    pub fn possible_type_names(&self) -> Option<&[String]> {
+        match *self {
+            MetaType::Union(UnionMeta {
+                ref of_type_names, ..
+            }) => Some(of_type_names),
+            MetaType::Interface(InterfaceMeta {
+                ref instance_type_names,
+                ..
+            }) => instance_type_names.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The wrapped type of a list or nullable wrapper
+    ///
+    /// This method always returns `None` for named types.
+    pub fn of_type(&self) -> Option<&Type<'a>> {
+        match *self {
+            MetaType::List(ListMeta { ref of_type })
+            | MetaType::Nullable(NullableMeta { ref of_type }) => Some(of_type),
+            _ => None,
+        }
+    }
+
     /// Access an input field's meta data given its name
     ///
     /// Only input objects have input fields. This method always returns `None` for other types.
@@ -326,10 +529,10 @@ impl<'a, S> MetaType<'a, S> {
     /// Access the input value parse function, if applicable
     ///
     /// An input value parse function is a function that takes an `InputValue` instance and returns
-    /// `true` if it can be parsed as the provided type.
+    /// `Ok` if it can be parsed as the provided type, or the reason why it can't.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<for<'b> fn(&'b InputValue<S>) -> bool> {
+    pub fn input_value_parse_fn(&self) -> Option<InputValueParseFn<S>> {
         match *self {
             MetaType::Scalar(ScalarMeta {
                 ref try_parse_fn, ..
@@ -419,6 +622,62 @@ impl<'a, S> MetaType<'a, S> {
     }
 }
 
+impl<'a, S: ScalarValue> MetaType<'a, S> {
+    /// Whether the other type has the same definition, comparing the names,
+    /// kinds, types, arguments, values and members, but not the descriptions
+    pub(crate) fn has_same_definition(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MetaType::Scalar(a), MetaType::Scalar(b)) => a.name == b.name,
+            (MetaType::List(a), MetaType::List(b)) => a.of_type == b.of_type,
+            (MetaType::Nullable(a), MetaType::Nullable(b)) => a.of_type == b.of_type,
+            (MetaType::Placeholder(a), MetaType::Placeholder(b)) => a.of_type == b.of_type,
+            (MetaType::Object(a), MetaType::Object(b)) => {
+                a.name == b.name
+                    && a.interface_names == b.interface_names
+                    && same_fields(&a.fields, &b.fields)
+            }
+            (MetaType::Interface(a), MetaType::Interface(b)) => {
+                a.name == b.name
+                    && a.instance_type_names == b.instance_type_names
+                    && same_fields(&a.fields, &b.fields)
+            }
+            (MetaType::Union(a), MetaType::Union(b)) => {
+                a.name == b.name && a.of_type_names == b.of_type_names
+            }
+            (MetaType::Enum(a), MetaType::Enum(b)) => {
+                a.name == b.name
+                    && a.values
+                        .iter()
+                        .map(|v| &v.name)
+                        .eq(b.values.iter().map(|v| &v.name))
+            }
+            (MetaType::InputObject(a), MetaType::InputObject(b)) => {
+                a.name == b.name && same_arguments(&a.input_fields, &b.input_fields)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn same_fields<S: ScalarValue>(a: &[Field<S>], b: &[Field<S>]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.name == b.name
+                && a.field_type == b.field_type
+                && same_arguments(
+                    a.arguments.as_deref().unwrap_or_default(),
+                    b.arguments.as_deref().unwrap_or_default(),
+                )
+        })
+}
+
+fn same_arguments<S: ScalarValue>(a: &[Argument<S>], b: &[Argument<S>]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.name == b.name && a.arg_type == b.arg_type && a.default_value == b.default_value
+        })
+}
+
 impl<'a, S> ScalarMeta<'a, S>
 where
     S: ScalarValue + 'a,
@@ -485,6 +744,8 @@ where
             description: None,
             fields: fields.to_vec(),
             interface_names: vec![],
+            cache_hint: None,
+            required_scopes: None,
         }
     }
 
@@ -508,6 +769,21 @@ where
         self
     }
 
+    /// Set how long fields returning the object may be cached
+    pub fn cache_hint(mut self, hint: CacheHint) -> ObjectMeta<'a, S> {
+        self.cache_hint = Some(hint);
+        self
+    }
+
+    /// Require authorization with the `scopes` to access the object and its
+    /// fields
+    ///
+    /// With no `scopes`, an authenticated context is required.
+    pub fn require_auth(mut self, scopes: &[&str]) -> ObjectMeta<'a, S> {
+        self.required_scopes = Some(scopes.iter().map(|&s| s.to_owned()).collect());
+        self
+    }
+
     /// Wrap this object type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Object(self)
@@ -555,6 +831,8 @@ where
             name,
             description: None,
             fields: fields.to_vec(),
+            cache_hint: None,
+            instance_type_names: None,
         }
     }
 
@@ -566,6 +844,26 @@ where
         self
     }
 
+    /// Set how long fields returning the interface may be cached
+    pub fn cache_hint(mut self, hint: CacheHint) -> InterfaceMeta<'a, S> {
+        self.cache_hint = Some(hint);
+        self
+    }
+
+    /// Set the object types the interface can resolve into
+    ///
+    /// The schema is checked to contain exactly these types as implementers
+    /// of the interface.
+    pub fn instance_types(mut self, types: &[Type]) -> InterfaceMeta<'a, S> {
+        self.instance_type_names = Some(
+            types
+                .iter()
+                .map(|t| t.innermost_name().to_owned())
+                .collect(),
+        );
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
@@ -631,6 +929,21 @@ where
 }
 
 impl<'a, S> Field<'a, S> {
+    /// Build a new field with the specified name and type
+    pub fn new(name: &str, field_type: Type<'a>) -> Self {
+        Field {
+            name: name.to_owned(),
+            description: None,
+            arguments: None,
+            field_type,
+            deprecation_status: DeprecationStatus::Current,
+            cache_hint: None,
+            default_impl: false,
+            serial: false,
+            required_scopes: None,
+        }
+    }
+
     /// Set the description of the field
     ///
     /// This overwrites the description if any was previously set.
@@ -662,6 +975,41 @@ impl<'a, S> Field<'a, S> {
         self
     }
 
+    /// Set how long the field may be cached
+    ///
+    /// This takes precedence over the hint of the type the field returns.
+    pub fn cache_hint(mut self, hint: CacheHint) -> Self {
+        self.cache_hint = Some(hint);
+        self
+    }
+
+    /// Let the objects implementing the interface of the field omit it
+    ///
+    /// The field is added to the implementers lacking it when the schema is
+    /// built, and resolved there by the interface.
+    pub fn default_impl(mut self) -> Self {
+        self.default_impl = true;
+        self
+    }
+
+    /// Resolve the field apart from its siblings
+    ///
+    /// The asynchronous execution resolves the fields of a selection set
+    /// concurrently, except such fields: the fields selected before them are
+    /// resolved first, and the fields selected after them wait for them.
+    pub fn serial(mut self) -> Self {
+        self.serial = true;
+        self
+    }
+
+    /// Require authorization with the `scopes` to access the field
+    ///
+    /// With no `scopes`, an authenticated context is required.
+    pub fn require_auth(mut self, scopes: &[&str]) -> Self {
+        self.required_scopes = Some(scopes.iter().map(|&s| s.to_owned()).collect());
+        self
+    }
+
     /// Add an argument to the field
     ///
     /// Arguments are unordered and can't contain duplicates by name.
@@ -688,7 +1036,7 @@ impl<'a, S> Field<'a, S> {
 }
 
 impl<'a, S> Argument<'a, S> {
-    #[doc(hidden)]
+    /// Build a new argument with the specified name and type
     pub fn new(name: &str, arg_type: Type<'a>) -> Self {
         Argument {
             name: name.to_owned(),
@@ -792,11 +1140,31 @@ impl<'a, S: fmt::Debug> fmt::Debug for InputObjectMeta<'a, S> {
     }
 }
 
-fn try_parse_fn<S, T>(v: &InputValue<S>) -> bool
+fn try_parse_fn<S, T>(v: &InputValue<S>) -> Result<(), FromInputError>
 where
     T: FromInputValue<S>,
 {
-    <T as FromInputValue<S>>::from_input_value(v).is_some()
+    <T as FromInputValue<S>>::try_from_input_value(v).map(drop)
+}
+
+/// Parse function of input objects built at runtime, whose values are fully
+/// checked against the listed fields during validation.
+pub(crate) fn accept_any_input<S>(_: &InputValue<S>) -> Result<(), FromInputError> {
+    Ok(())
+}
+
+/// Parse function of enums built at runtime; listed values are accepted by
+/// name before this is reached, so everything reaching it is rejected.
+pub(crate) fn reject_unlisted_input<S>(_: &InputValue<S>) -> Result<(), FromInputError> {
+    Err(FromInputError::invalid())
+}
+
+/// Parse function of scalars built at runtime, whose literals are parsed as
+/// the built-in scalar inferred from their token instead.
+pub(crate) fn reject_scalar_token<S>(_: ScalarToken) -> Result<S, ParseError> {
+    Err(ParseError::ExpectedScalarError(
+        "Scalars built at runtime have no parse function",
+    ))
 }
 
 fn clean_docstring(multiline: &[&str]) -> Option<String> {
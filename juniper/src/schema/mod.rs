@@ -1,5 +1,6 @@
 #![allow(clippy::module_inception)]
 
+mod consistency;
 pub mod meta;
 pub mod model;
 pub mod schema;
@@ -1,20 +1,45 @@
-use std::fmt;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    sync::Arc,
+    time::Duration,
+};
 
 use fnv::FnvHashMap;
 #[cfg(feature = "graphql-parser-integration")]
 use graphql_parser::schema::Document;
 
 use crate::{
-    ast::Type,
-    executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
+    ast::{Definition, Fragment, InputValue, Type},
+    auth::PolicyEvaluator,
+    cache::{CacheBackend, FieldCache},
+    deprecation::DeprecationTracker,
+    executor::{Context, Registry, Sleep, Timer},
+    messages::{Message, MessageCatalog, Messages},
+    operations::{OperationPolicies, OperationPolicy},
+    parser::{parse_document_source, Spanning},
+    rate_limit::RateLimiter,
+    rewrite::{QueryRewriter, QueryRewriters},
+    schedule::{FieldScheduler, Scheduler},
+    schema::meta::{
+        Argument, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta,
+    },
     types::{base::GraphQLType, name::Name},
+    usage::UsageCollector,
+    validation::RuleError,
     value::{DefaultScalarValue, ScalarValue},
-    GraphQLEnum,
+    GraphQLEnum, GraphQLError,
 };
 
 #[cfg(feature = "graphql-parser-integration")]
 use crate::schema::translate::{graphql_parser::GraphQLParserTranslator, SchemaTranslator};
+#[cfg(feature = "relay")]
+use crate::{
+    executor::FieldResult,
+    relay::{CursorKey, NodeFetchers},
+    types::async_await::GraphQLValueAsync,
+};
 
 /// Root query node of a schema
 ///
@@ -44,6 +69,14 @@ pub struct RootNode<
     pub subscription_info: SubscriptionT::TypeInfo,
     #[doc(hidden)]
     pub schema: SchemaType<'a, S>,
+    #[doc(hidden)]
+    pub deprecation_tracker: Option<DeprecationTracker<QueryT::Context>>,
+    #[doc(hidden)]
+    pub policy_evaluator: Option<Box<dyn PolicyEvaluator<QueryT::Context>>>,
+    #[doc(hidden)]
+    pub rate_limiter: Option<RateLimiter<QueryT::Context>>,
+    #[cfg(feature = "relay")]
+    pub(crate) node_fetchers: NodeFetchers<QueryT::Context, S>,
 }
 
 /// Metadata for a schema
@@ -53,11 +86,89 @@ pub struct SchemaType<'a, S> {
     pub(crate) query_type_name: String,
     pub(crate) mutation_type_name: Option<String>,
     pub(crate) subscription_type_name: Option<String>,
-    directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) field_cache: Option<FieldCache<S>>,
+    pub(crate) usage_collector: Option<Arc<UsageCollector>>,
+    pub(crate) query_rewriters: QueryRewriters<S>,
+    pub(crate) field_scheduler: Option<Scheduler<S>>,
+    pub(crate) sleep: Option<Timer>,
+    pub(crate) fragment_library: FnvHashMap<&'a str, Spanning<Fragment<'a, S>>>,
+    pub(crate) catch_panics: bool,
+    pub(crate) slow_field_threshold: Option<Duration>,
+    pub(crate) batch_concurrency: Option<usize>,
+    pub(crate) introspection_max_depth: Option<usize>,
+    pub(crate) max_response_values: Option<usize>,
+    pub(crate) pagination_limits: FnvHashMap<String, FnvHashMap<String, i32>>,
+    #[cfg(feature = "relay")]
+    pub(crate) cursor_key: Option<CursorKey>,
+    pub(crate) introspection_descriptions: bool,
+    pub(crate) messages: Messages,
+    pub(crate) auth_requirements: Vec<Vec<String>>,
+    pub(crate) operation_policies: OperationPolicies,
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
 
+/// Error building a schema
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaError {
+    /// Two Rust types register the same GraphQL type name with differing
+    /// definitions
+    DuplicateTypeName {
+        /// The GraphQL type name
+        name: String,
+        /// The Rust type registering the name first
+        first_type: &'static str,
+        /// The Rust type registering a differing definition under the name
+        second_type: &'static str,
+    },
+
+    /// The schema breaks the type system rules of the GraphQL specification,
+    /// e.g. an object doesn't implement the fields of its interfaces
+    Invalid(Vec<String>),
+
+    /// The introspection result a schema is built from doesn't describe a
+    /// schema, with the path of the offending member
+    MalformedIntrospection {
+        /// The path of the member, like `__schema.types[3].kind`
+        path: String,
+        /// What's wrong with the member
+        message: String,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaError::DuplicateTypeName {
+                name,
+                first_type,
+                second_type,
+            } => write!(
+                f,
+                "GraphQL type \"{}\" is defined differently by `{}` and `{}`",
+                name, first_type, second_type,
+            ),
+            SchemaError::Invalid(violations) => {
+                write!(f, "Invalid GraphQL schema:")?;
+                for violation in violations {
+                    write!(f, "\n- {}", violation)?;
+                }
+                Ok(())
+            }
+            SchemaError::MalformedIntrospection { path, message } => {
+                write!(
+                    f,
+                    "Malformed introspection result at `{}`: {}",
+                    path, message
+                )
+            }
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
 #[derive(Clone)]
 pub enum TypeType<'a, S: 'a> {
     Concrete(&'a MetaType<'a, S>),
@@ -99,6 +210,10 @@ where
     ///
     /// If the schema should not support mutations, use the
     /// `new` constructor instead.
+    ///
+    /// # Panics
+    ///
+    /// If the schema is invalid, see [`try_new`](#method.try_new).
     pub fn new(
         query_obj: QueryT,
         mutation_obj: MutationT,
@@ -107,13 +222,25 @@ where
         RootNode::new_with_info(query_obj, mutation_obj, subscription_obj, (), (), ())
     }
 
+    /// Construct a new root node from query, mutation, and subscription nodes
+    ///
+    /// Returns an error if the schema is invalid, e.g. because two Rust types
+    /// define the same GraphQL type name differently, or an object doesn't
+    /// implement the fields of its interfaces.
+    pub fn try_new(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+    ) -> Result<Self, SchemaError> {
+        RootNode::try_new_with_info(query_obj, mutation_obj, subscription_obj, (), (), ())
+    }
+
     #[cfg(feature = "schema-language")]
     /// The schema definition as a `String` in the
     /// [GraphQL Schema Language](https://graphql.org/learn/schema/#type-language)
     /// format.
     pub fn as_schema_language(&self) -> String {
-        let doc = self.as_parser_document();
-        format!("{}", doc)
+        self.schema.as_schema_language()
     }
 
     #[cfg(feature = "graphql-parser-integration")]
@@ -134,6 +261,10 @@ where
     /// Construct a new root node from query and mutation nodes,
     /// while also providing type info objects for the query and
     /// mutation types.
+    ///
+    /// # Panics
+    ///
+    /// If the schema is invalid, see [`try_new`](#method.try_new).
     pub fn new_with_info(
         query_obj: QueryT,
         mutation_obj: MutationT,
@@ -142,24 +273,374 @@ where
         mutation_info: MutationT::TypeInfo,
         subscription_info: SubscriptionT::TypeInfo,
     ) -> Self {
-        RootNode {
+        RootNode::try_new_with_info(
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            query_info,
+            mutation_info,
+            subscription_info,
+        )
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Set the backend caching the results of fields marked with
+    /// `#[graphql(cache(ttl = "..."))]`
+    ///
+    /// Such fields are resolved each time until a backend is set, see the
+    /// [`cache`](cache/index.html) module.
+    pub fn with_field_cache<C>(mut self, backend: C) -> Self
+    where
+        C: CacheBackend<S> + Send + Sync + 'static,
+    {
+        self.schema.field_cache = Some(FieldCache(Arc::new(backend)));
+        self
+    }
+
+    /// Add a rewriter of the documents before they're executed, see the
+    /// [`rewrite`](rewrite/index.html) module
+    ///
+    /// Rewriters are applied in the order they're added.
+    pub fn with_query_rewriter<R>(mut self, rewriter: R) -> Self
+    where
+        R: QueryRewriter<S> + 'static,
+    {
+        self.schema.query_rewriters.0.push(Box::new(rewriter));
+        self
+    }
+
+    /// Set the prioritizer of the fields resolved concurrently, see the
+    /// [`schedule`](schedule/index.html) module
+    pub fn with_field_scheduler<F>(mut self, scheduler: F) -> Self
+    where
+        F: FieldScheduler<S> + 'static,
+    {
+        self.schema.field_scheduler = Some(Scheduler(Box::new(scheduler)));
+        self
+    }
+
+    /// Set the timer of the runtime executing the queries, completing the
+    /// timeouts of the fields marked with `#[graphql(timeout = "...")]`
+    ///
+    /// Without one, the fields with a timeout fail.
+    pub fn with_sleep<F>(mut self, sleep: F) -> Self
+    where
+        F: Sleep + 'static,
+    {
+        self.schema.sleep = Some(Timer(Arc::new(sleep)));
+        self
+    }
+
+    /// Turn panics of the resolvers of queries and mutations into errors of
+    /// their fields
+    ///
+    /// A panicking resolver fails its field with the panic message, at the
+    /// path of the field, instead of the whole request. As the panic is caught
+    /// with [`catch_unwind`](std::panic::catch_unwind), resolvers must not
+    /// leave shared state inconsistent when panicking, and no panics are
+    /// caught when building with `panic = "abort"`.
+    pub fn with_panic_catching(mut self) -> Self {
+        self.schema.catch_panics = true;
+        self
+    }
+
+    /// Log the fields of queries and mutations whose resolution takes longer
+    /// than the `threshold`
+    ///
+    /// Each slow field is logged as a warning through the
+    /// [`log`](https://docs.rs/log) facade, with the `juniper::slow_field`
+    /// target, along with its path, the type it belongs to, the time it took
+    /// and a summary of its arguments. The time of a field includes resolving
+    /// its own fields, so the ancestors of a slow field are usually logged
+    /// too.
+    pub fn with_slow_field_threshold(mut self, threshold: Duration) -> Self {
+        self.schema.slow_field_threshold = Some(threshold);
+        self
+    }
+
+    /// Stop executing operations once they resolved more than `limit` values,
+    /// protecting the server from queries selecting every field of huge lists
+    ///
+    /// Each field and each item of a list counts as a value. The field or
+    /// list exceeding the limit fails with a `RESPONSE_TOO_LARGE` error, and
+    /// the fields left are resolved to `null` without calling their
+    /// resolvers, the response holding the data resolved so far. The values of
+    /// subscriptions are limited event by event.
+    pub fn with_max_response_values(mut self, limit: usize) -> Self {
+        self.schema.max_response_values = Some(limit);
+        self
+    }
+
+    /// Reject the selections of the field `field_name` of the type
+    /// `type_name` lacking a `first` or `last` argument of at most `limit`,
+    /// banning unbounded queries of the field
+    ///
+    /// The selections are validated along with the rest of the documents,
+    /// arguments provided by variables being checked once the variables are
+    /// known, failing the field. Default values of the arguments count as
+    /// provided. Fields of interfaces are limited when selected on the
+    /// interface only, each implementing object being configured apart.
+    ///
+    /// # Panics
+    ///
+    /// If the type has no such field, or if the field takes neither a `first`
+    /// nor a `last` argument.
+    pub fn with_required_pagination(
+        mut self,
+        type_name: &str,
+        field_name: &str,
+        limit: i32,
+    ) -> Self {
+        let field = self
+            .schema
+            .concrete_type_by_name(type_name)
+            .and_then(|t| t.field_by_name(field_name))
+            .unwrap_or_else(|| {
+                panic!("Field {}.{} not found in the schema", type_name, field_name)
+            });
+        let paginated = field
+            .arguments
+            .iter()
+            .flatten()
+            .any(|a| a.name == "first" || a.name == "last");
+        if !paginated {
+            panic!(
+                "Field {}.{} takes neither a `first` nor a `last` argument",
+                type_name, field_name,
+            );
+        }
+
+        self.schema
+            .pagination_limits
+            .entry(type_name.to_owned())
+            .or_default()
+            .insert(field_name.to_owned(), limit);
+        self
+    }
+
+    /// Sign the [cursors](relay/struct.Cursor.html) of the schema with the
+    /// `key`, so clients can't forge them
+    ///
+    /// Cursors encoded before the key is changed are rejected once it's
+    /// changed.
+    #[cfg(feature = "relay")]
+    pub fn with_cursor_key<K: Into<Vec<u8>>>(mut self, key: K) -> Self {
+        self.schema.cursor_key = Some(CursorKey(key.into()));
+        self
+    }
+
+    /// Make the type `T` implement the `Node` interface, its objects being
+    /// fetched by the `fetcher` from the ID within the type of a
+    /// [`GlobalId`](relay/struct.GlobalId.html)
+    ///
+    /// Adding the first fetcher adds the `node(id: ID!): Node` and
+    /// `nodes(ids: [ID!]!): [Node]!` fields to the query type, resolving the
+    /// global IDs of the types having fetchers, and `null` for the other ones.
+    /// Objects fetched by single-threaded executions are resolved
+    /// synchronously.
+    ///
+    /// # Panics
+    ///
+    /// If `T` isn't an object of the schema, if it has no `id: ID!` field, or
+    /// if the query type already has a `node` or `nodes` field.
+    #[cfg(feature = "relay")]
+    pub fn with_node_fetcher<T, F>(mut self, fetcher: F) -> Self
+    where
+        T: GraphQLValueAsync<S, Context = QueryT::Context, TypeInfo = ()>
+            + GraphQLType<S>
+            + Send
+            + 'static,
+        F: Fn(&str, &QueryT::Context) -> FieldResult<Option<T>, S> + Send + Sync + 'static,
+        QueryT::Context: Sync + 'static,
+        S: Send + Sync,
+    {
+        self.node_fetchers.register(&mut self.schema, fetcher);
+        self
+    }
+
+    /// Limit the number of operations of an HTTP batch executed at once by
+    /// [`GraphQLBatchRequest::execute`](http/enum.GraphQLBatchRequest.html#method.execute)
+    ///
+    /// All the operations of a batch are executed concurrently otherwise. The
+    /// responses keep the order of the requests either way.
+    pub fn with_batch_concurrency(mut self, limit: usize) -> Self {
+        self.schema.batch_concurrency = Some(limit.max(1));
+        self
+    }
+
+    /// Fail the introspection fields `__schema` and `__type` selecting fields
+    /// nested deeper than `depth` in them
+    ///
+    /// The depth counts the levels of selections below the introspection
+    /// field, through fragments: `{ __type(name: "User") { fields { name } } }`
+    /// has a depth of 2. The canonical introspection query has a depth of 12,
+    /// while the [`TypeIndex`](enum.IntrospectionFormat.html#variant.TypeIndex)
+    /// one has a depth of 2, letting clients list the types then fetch the
+    /// ones they need with `__type`.
+    pub fn with_introspection_max_depth(mut self, depth: usize) -> Self {
+        self.schema.introspection_max_depth = Some(depth);
+        self
+    }
+
+    /// Resolve the `description` fields of the introspection to `null`,
+    /// shrinking introspection results of documented schemas
+    ///
+    /// The descriptions are still part of the
+    /// [schema language](#method.as_schema_language) of the schema.
+    pub fn without_introspection_descriptions(mut self) -> Self {
+        self.schema.introspection_descriptions = false;
+        self
+    }
+
+    /// Word the validation and coercion errors with the `catalog`, see the
+    /// [`messages`](messages/index.html) module
+    pub fn with_message_catalog<M>(mut self, catalog: M) -> Self
+    where
+        M: MessageCatalog + 'static,
+    {
+        self.schema.messages = Messages(Some(Box::new(catalog)));
+        self
+    }
+
+    /// Register fragments which documents may spread without defining them,
+    /// like the fragments shared by the operations of a client
+    ///
+    /// The `source` may only contain fragment definitions, and is parsed once
+    /// here. Before a document is validated, it's completed with the library
+    /// fragments it spreads, directly or through other fragments, unless it
+    /// defines a fragment of the same name itself. Errors in a library
+    /// fragment are reported by the validation of the documents spreading it.
+    pub fn with_fragment_library(mut self, source: &'a str) -> Result<Self, GraphQLError<'a>> {
+        let document = parse_document_source(source, &self.schema)?;
+
+        let mut errors = vec![];
+        for definition in document {
+            match definition {
+                Definition::Fragment(fragment) => {
+                    let name = fragment.item.name.item;
+                    if self.schema.fragment_library.contains_key(name) {
+                        let message = Message::DuplicateFragment { fragment: name };
+                        errors.push(RuleError::new(
+                            self.schema.messages.render(&message),
+                            &[fragment.item.name.start],
+                        ));
+                    } else {
+                        self.schema.fragment_library.insert(name, fragment);
+                    }
+                }
+                Definition::Operation(operation) => errors.push(RuleError::new(
+                    "A fragment library may only contain fragment definitions",
+                    &[operation.start],
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(GraphQLError::ValidationError(errors))
+        }
+    }
+
+    /// Set the collector of the usage statistics of the schema's fields, see
+    /// the [`usage`](usage/index.html) module
+    ///
+    /// A collector may be shared by several schemas.
+    pub fn with_usage_collector(mut self, collector: Arc<UsageCollector>) -> Self {
+        collector.register_schema(&self.schema);
+        self.schema.usage_collector = Some(collector);
+        self
+    }
+
+    /// Set the tracker of the deprecated fields selected by the executed
+    /// operations, see the [`deprecation`](deprecation/index.html) module
+    pub fn with_deprecation_tracker(
+        mut self,
+        tracker: DeprecationTracker<QueryT::Context>,
+    ) -> Self {
+        self.deprecation_tracker = Some(tracker);
+        self
+    }
+
+    /// Set the evaluator of the authorization required by the types and
+    /// fields, see the [`auth`](auth/index.html) module
+    ///
+    /// All the requirements are denied until an evaluator is set.
+    pub fn with_policy_evaluator<P>(mut self, evaluator: P) -> Self
+    where
+        P: PolicyEvaluator<QueryT::Context> + 'static,
+    {
+        self.policy_evaluator = Some(Box::new(evaluator));
+        self
+    }
+
+    /// Only execute the operations named after a registered policy, applying
+    /// the `policy` to the ones named `name`, see the
+    /// [`operations`](operations/index.html) module
+    pub fn with_operation_policy<N: Into<String>>(
+        mut self,
+        name: N,
+        policy: OperationPolicy,
+    ) -> Self {
+        self.schema.operation_policies.insert(name.into(), policy);
+        self
+    }
+
+    /// Set the limiter charging the cost of the executed operations to the
+    /// budgets of their clients, see the [`rate_limit`](rate_limit/index.html)
+    /// module
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter<QueryT::Context>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// The policy of the operations named `name`, to enforce its rate limit
+    /// once the operation name of a request is known
+    pub fn operation_policy(&self, name: &str) -> Option<&OperationPolicy> {
+        self.schema.operation_policies.get(name)
+    }
+
+    /// Construct a new root node from query and mutation nodes,
+    /// while also providing type info objects for the query and
+    /// mutation types.
+    ///
+    /// Returns an error if the schema is invalid, see
+    /// [`try_new`](#method.try_new).
+    pub fn try_new_with_info(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+    ) -> Result<Self, SchemaError> {
+        Ok(RootNode {
             query_type: query_obj,
             mutation_type: mutation_obj,
             subscription_type: subscription_obj,
-            schema: SchemaType::new::<QueryT, MutationT, SubscriptionT>(
+            schema: SchemaType::try_new::<QueryT, MutationT, SubscriptionT>(
                 &query_info,
                 &mutation_info,
                 &subscription_info,
-            ),
+            )?,
             query_info,
             mutation_info,
             subscription_info,
-        }
+            deprecation_tracker: None,
+            policy_evaluator: None,
+            rate_limiter: None,
+            #[cfg(feature = "relay")]
+            node_fetchers: NodeFetchers::new(),
+        })
     }
 }
 
 impl<'a, S> SchemaType<'a, S> {
     /// Create a new schema.
+    ///
+    /// # Panics
+    ///
+    /// If the schema is invalid, see [`try_new`](#method.try_new).
     pub fn new<QueryT, MutationT, SubscriptionT>(
         query_info: &QueryT::TypeInfo,
         mutation_info: &MutationT::TypeInfo,
@@ -171,29 +652,75 @@ impl<'a, S> SchemaType<'a, S> {
         MutationT: GraphQLType<S>,
         SubscriptionT: GraphQLType<S>,
     {
-        let mut directives = FnvHashMap::default();
-        let query_type_name: String;
-        let mutation_type_name: String;
-        let subscription_type_name: String;
+        Self::try_new::<QueryT, MutationT, SubscriptionT>(
+            query_info,
+            mutation_info,
+            subscription_info,
+        )
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
 
+    /// Create a new schema, returning an error if two Rust types define the
+    /// same GraphQL type name differently, or if the schema breaks the type
+    /// system rules, reporting all the violations at once.
+    pub fn try_new<QueryT, MutationT, SubscriptionT>(
+        query_info: &QueryT::TypeInfo,
+        mutation_info: &MutationT::TypeInfo,
+        subscription_info: &SubscriptionT::TypeInfo,
+    ) -> Result<Self, SchemaError>
+    where
+        S: ScalarValue + 'a,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S>,
+        SubscriptionT: GraphQLType<S>,
+    {
         let mut registry = Registry::new(FnvHashMap::default());
-        query_type_name = registry
+        let query_type_name = registry
             .get_type::<QueryT>(query_info)
             .innermost_name()
             .to_owned();
 
-        mutation_type_name = registry
+        let mutation_type_name = registry
             .get_type::<MutationT>(mutation_info)
             .innermost_name()
             .to_owned();
 
-        subscription_type_name = registry
+        let subscription_type_name = registry
             .get_type::<SubscriptionT>(subscription_info)
             .innermost_name()
             .to_owned();
 
+        registry.check_redefinitions()?;
+
+        for meta_type in registry.types.values() {
+            if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
+                panic!("Type {:?} is still a placeholder type", of_type);
+            }
+        }
+        DefaultLiterals::new(&registry.types).apply(&mut registry.types);
+
+        Self::from_registry(
+            registry,
+            query_type_name,
+            Some(mutation_type_name).filter(|name| name != "_EmptyMutation"),
+            Some(subscription_type_name).filter(|name| name != "_EmptySubscription"),
+        )
+    }
+
+    /// Finish the schema of the types in the `registry`, adding the types and
+    /// fields of introspection, and checking the type system rules
+    pub(crate) fn from_registry(
+        mut registry: Registry<'a, S>,
+        query_type_name: String,
+        mutation_type_name: Option<String>,
+        subscription_type_name: Option<String>,
+    ) -> Result<Self, SchemaError>
+    where
+        S: ScalarValue + 'a,
+    {
         registry.get_type::<SchemaType<S>>(&());
 
+        let mut directives = FnvHashMap::default();
         directives.insert("skip".to_owned(), DirectiveType::new_skip(&mut registry));
         directives.insert(
             "include".to_owned(),
@@ -203,10 +730,12 @@ impl<'a, S> SchemaType<'a, S> {
         let mut meta_fields = vec![
             registry.field::<SchemaType<S>>("__schema", &()),
             registry
-                .field::<TypeType<S>>("__type", &())
+                .field::<Option<TypeType<S>>>("__type", &())
                 .argument(registry.arg::<String>("name", &())),
         ];
 
+        inherit_default_impls(&mut registry.types);
+
         if let Some(root_type) = registry.types.get_mut(&query_type_name) {
             if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
                 fields.append(&mut meta_fields);
@@ -217,28 +746,53 @@ impl<'a, S> SchemaType<'a, S> {
             panic!("Root type not found");
         }
 
-        for meta_type in registry.types.values() {
-            if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
-                panic!("Type {:?} is still a placeholder type", of_type);
-            }
-        }
-        SchemaType {
+        let auth_requirements = auth_requirements(&registry.types);
+        let schema = SchemaType {
             types: registry.types,
             query_type_name,
-            mutation_type_name: if &mutation_type_name != "_EmptyMutation" {
-                Some(mutation_type_name)
-            } else {
-                None
-            },
-            subscription_type_name: if &subscription_type_name != "_EmptySubscription" {
-                Some(subscription_type_name)
-            } else {
-                None
-            },
+            mutation_type_name,
+            subscription_type_name,
             directives,
+            field_cache: None,
+            usage_collector: None,
+            query_rewriters: QueryRewriters(vec![]),
+            field_scheduler: None,
+            sleep: None,
+            fragment_library: FnvHashMap::default(),
+            catch_panics: false,
+            slow_field_threshold: None,
+            introspection_max_depth: None,
+            max_response_values: None,
+            pagination_limits: FnvHashMap::default(),
+            #[cfg(feature = "relay")]
+            cursor_key: None,
+            introspection_descriptions: true,
+            batch_concurrency: None,
+            messages: Messages::default(),
+            auth_requirements,
+            operation_policies: OperationPolicies::default(),
+        };
+
+        let violations = schema.violations();
+        if violations.is_empty() {
+            Ok(schema)
+        } else {
+            Err(SchemaError::Invalid(violations))
         }
     }
 
+    #[cfg(feature = "schema-language")]
+    /// The schema definition as a `String` in the
+    /// [GraphQL Schema Language](https://graphql.org/learn/schema/#type-language)
+    /// format.
+    pub fn as_schema_language(&self) -> String
+    where
+        S: ScalarValue,
+    {
+        let doc: Document<&str> = GraphQLParserTranslator::translate_schema(self);
+        doc.to_string()
+    }
+
     /// Add a directive like `skip` or `include`.
     pub fn add_directive(&mut self, directive: DirectiveType<'a, S>) {
         self.directives.insert(directive.name.clone(), directive);
@@ -254,6 +808,26 @@ impl<'a, S> SchemaType<'a, S> {
         self.types.get(name)
     }
 
+    #[doc(hidden)]
+    pub fn is_default_impl(&self, interface_name: &str, field_name: &str) -> bool {
+        match self.types.get(interface_name) {
+            Some(MetaType::Interface(interface)) => interface
+                .fields
+                .iter()
+                .any(|f| f.name == field_name && f.default_impl),
+            _ => false,
+        }
+    }
+
+    /// The maximum `first` or `last` argument of the field `field_name`
+    /// selected on the type `type_name`, if it's required to be paginated
+    pub(crate) fn pagination_limit(&self, type_name: &str, field_name: &str) -> Option<i32> {
+        self.pagination_limits
+            .get(type_name)
+            .and_then(|fields| fields.get(field_name))
+            .copied()
+    }
+
     pub(crate) fn lookup_type(&self, tpe: &Type) -> Option<&MetaType<S>> {
         match *tpe {
             Type::NonNullNamed(ref name) | Type::Named(ref name) => {
@@ -531,6 +1105,165 @@ where
     }
 }
 
+/// Adds the fields of interfaces marked as `default_impl` to the objects
+/// implementing them without defining the fields themselves
+fn inherit_default_impls<S: Clone>(types: &mut FnvHashMap<Name, MetaType<'_, S>>) {
+    let mut default_impls = HashMap::new();
+    for meta_type in types.values() {
+        if let MetaType::Interface(ref interface) = *meta_type {
+            let fields = interface
+                .fields
+                .iter()
+                .filter(|f| f.default_impl)
+                .cloned()
+                .collect::<Vec<_>>();
+            if !fields.is_empty() {
+                default_impls.insert(interface.name.to_string(), fields);
+            }
+        }
+    }
+    if default_impls.is_empty() {
+        return;
+    }
+
+    for meta_type in types.values_mut() {
+        if let MetaType::Object(ref mut object) = *meta_type {
+            for interface_name in &object.interface_names {
+                for field in default_impls.get(interface_name).into_iter().flatten() {
+                    if !object.fields.iter().any(|f| f.name == field.name) {
+                        object.fields.push(field.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The distinct scopes required by the objects and fields of a schema
+fn auth_requirements<S>(types: &FnvHashMap<Name, MetaType<'_, S>>) -> Vec<Vec<String>> {
+    let mut requirements = HashSet::new();
+    for meta_type in types.values() {
+        let fields = match *meta_type {
+            MetaType::Object(ref object) => {
+                requirements.extend(object.required_scopes.iter());
+                &object.fields
+            }
+            MetaType::Interface(ref interface) => &interface.fields,
+            _ => continue,
+        };
+        requirements.extend(fields.iter().filter_map(|f| f.required_scopes.as_ref()));
+    }
+    requirements.into_iter().cloned().collect()
+}
+
+/// Turns default values of arguments and input object fields into proper
+/// GraphQL literals of their types.
+///
+/// Defaults are registered from Rust values, which represent enum values as
+/// strings, so they'd otherwise be shown as strings by introspection and in the
+/// schema language.
+struct DefaultLiterals<'a> {
+    enums: HashSet<String>,
+    input_fields: HashMap<String, Vec<(String, Type<'a>)>>,
+}
+
+impl<'a> DefaultLiterals<'a> {
+    fn new<S>(types: &FnvHashMap<Name, MetaType<'a, S>>) -> Self {
+        let mut enums = HashSet::new();
+        let mut input_fields = HashMap::new();
+        for meta_type in types.values() {
+            match *meta_type {
+                MetaType::Enum(ref meta) => {
+                    enums.insert(meta.name.to_string());
+                }
+                MetaType::InputObject(ref meta) => {
+                    let fields = meta
+                        .input_fields
+                        .iter()
+                        .map(|f| (f.name.clone(), f.arg_type.clone()))
+                        .collect();
+                    input_fields.insert(meta.name.to_string(), fields);
+                }
+                _ => {}
+            }
+        }
+        Self {
+            enums,
+            input_fields,
+        }
+    }
+
+    fn apply<S: ScalarValue>(&self, types: &mut FnvHashMap<Name, MetaType<'a, S>>) {
+        for meta_type in types.values_mut() {
+            match *meta_type {
+                MetaType::Object(ObjectMeta { ref mut fields, .. })
+                | MetaType::Interface(InterfaceMeta { ref mut fields, .. }) => {
+                    for args in fields.iter_mut().filter_map(|f| f.arguments.as_mut()) {
+                        args.iter_mut().for_each(|arg| self.apply_to_argument(arg));
+                    }
+                }
+                MetaType::InputObject(InputObjectMeta {
+                    ref mut input_fields,
+                    ..
+                }) => input_fields
+                    .iter_mut()
+                    .for_each(|arg| self.apply_to_argument(arg)),
+                _ => {}
+            }
+        }
+    }
+
+    fn apply_to_argument<S: ScalarValue>(&self, arg: &mut Argument<'a, S>) {
+        if let Some(value) = arg.default_value.take() {
+            arg.default_value = Some(self.literal(value, &arg.arg_type));
+        }
+    }
+
+    fn literal<S: ScalarValue>(&self, value: InputValue<S>, ty: &Type) -> InputValue<S> {
+        match (ty, value) {
+            (&Type::List(ref inner), InputValue::List(items))
+            | (&Type::NonNullList(ref inner), InputValue::List(items)) => InputValue::List(
+                items
+                    .into_iter()
+                    .map(|item| item.map(|v| self.literal(v, inner)))
+                    .collect(),
+            ),
+            // A single value is coerced into a list of one element.
+            (&Type::List(ref inner), value) | (&Type::NonNullList(ref inner), value) => {
+                self.literal(value, inner)
+            }
+            (&Type::Named(ref name), InputValue::Scalar(s))
+            | (&Type::NonNullNamed(ref name), InputValue::Scalar(s))
+                if self.enums.contains(name.as_ref()) =>
+            {
+                match s.as_str() {
+                    Some(v) => InputValue::enum_value(v),
+                    None => InputValue::Scalar(s),
+                }
+            }
+            (&Type::Named(ref name), InputValue::Object(fields))
+            | (&Type::NonNullNamed(ref name), InputValue::Object(fields)) => {
+                let field_types = self.input_fields.get(name.as_ref());
+                InputValue::Object(
+                    fields
+                        .into_iter()
+                        .map(|(k, v)| {
+                            let ty = field_types
+                                .and_then(|types| types.iter().find(|(n, _)| *n == k.item))
+                                .map(|(_, ty)| ty);
+                            match ty {
+                                Some(ty) => (k, v.map(|v| self.literal(v, ty))),
+                                None => (k, v),
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            (_, value) => value,
+        }
+    }
+}
+
 impl fmt::Display for DirectiveLocation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match *self {
@@ -557,6 +1290,229 @@ impl<'a, S> fmt::Display for TypeType<'a, S> {
 
 #[cfg(test)]
 mod test {
+    mod duplicate_type_names {
+        use crate as juniper;
+        use crate::{EmptyMutation, EmptySubscription, GraphQLObject, RootNode, SchemaError};
+
+        mod v1 {
+            use super::*;
+
+            #[derive(GraphQLObject)]
+            #[graphql(name = "Item")]
+            pub struct Item {
+                pub id: i32,
+            }
+        }
+
+        mod v2 {
+            use super::*;
+
+            #[derive(GraphQLObject)]
+            #[graphql(name = "Item")]
+            pub struct Item {
+                pub name: String,
+            }
+        }
+
+        mod v3 {
+            use super::*;
+
+            /// The same item, documented
+            #[derive(GraphQLObject)]
+            #[graphql(name = "Item")]
+            pub struct Item {
+                /// The id of the item
+                pub id: i32,
+            }
+        }
+
+        struct Query;
+
+        #[juniper::graphql_object]
+        impl Query {
+            fn item() -> v1::Item {
+                v1::Item { id: 1 }
+            }
+
+            fn boxed_item() -> Box<v1::Item> {
+                Box::new(v1::Item { id: 2 })
+            }
+
+            fn other_item() -> v2::Item {
+                v2::Item { name: "b".into() }
+            }
+        }
+
+        struct SameQuery;
+
+        #[juniper::graphql_object]
+        impl SameQuery {
+            fn item() -> v1::Item {
+                v1::Item { id: 1 }
+            }
+
+            fn boxed_item() -> Box<v1::Item> {
+                Box::new(v1::Item { id: 2 })
+            }
+        }
+
+        struct DocumentedQuery;
+
+        #[juniper::graphql_object]
+        impl DocumentedQuery {
+            fn item() -> v1::Item {
+                v1::Item { id: 1 }
+            }
+
+            fn documented_item() -> v3::Item {
+                v3::Item { id: 2 }
+            }
+        }
+
+        #[test]
+        fn differing_definitions_are_an_error() {
+            let err = RootNode::try_new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .err()
+            .expect("schema should be invalid");
+
+            assert_eq!(
+                err,
+                SchemaError::DuplicateTypeName {
+                    name: "Item".into(),
+                    first_type: std::any::type_name::<v1::Item>(),
+                    second_type: std::any::type_name::<v2::Item>(),
+                },
+            );
+            assert!(err.to_string().contains("v2::Item"));
+        }
+
+        #[test]
+        #[should_panic(expected = r#"GraphQL type "Item" is defined differently"#)]
+        fn new_panics_on_differing_definitions() {
+            RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            );
+        }
+
+        #[test]
+        fn wrappers_are_not_an_error() {
+            assert!(RootNode::try_new(
+                SameQuery,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .is_ok());
+        }
+
+        #[test]
+        fn differing_descriptions_are_not_an_error() {
+            assert!(RootNode::try_new(
+                DocumentedQuery,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .is_ok());
+        }
+    }
+
+    mod invalid_schemas {
+        use std::borrow::Cow;
+
+        use crate::{
+            ast::Type,
+            schema::meta::{Argument, Field, InterfaceMeta, MetaType, UnionMeta},
+            DefaultScalarValue, EmptyMutation, EmptySubscription, GraphQLType, GraphQLValue,
+            Registry, RootNode, ScalarValue, SchemaError,
+        };
+
+        struct Query;
+
+        impl<S: ScalarValue> GraphQLType<S> for Query {
+            fn name(_: &()) -> Option<&'static str> {
+                Some("Query")
+            }
+
+            fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+            where
+                S: 'r,
+            {
+                let name = registry.field::<String>("name", &());
+                let robot = registry
+                    .build_object("Robot")
+                    .field::<String>("name", &())
+                    .register();
+                let named = registry.register_type(
+                    InterfaceMeta::new(Cow::Borrowed("Named"), &[name])
+                        .instance_types(&[robot])
+                        .into_meta(),
+                );
+                let pet = registry
+                    .build_object("Pet")
+                    .field::<Option<String>>("name", &())
+                    .interface("Named")
+                    .register();
+                let anything = registry.register_type(
+                    UnionMeta::new(Cow::Borrowed("Anything"), &[pet, named.clone()]).into_meta(),
+                );
+                let node = registry
+                    .build_input_object("Node")
+                    .field_with(|_| {
+                        Argument::new("next", Type::NonNullNamed(Cow::Borrowed("Node")))
+                    })
+                    .register();
+                registry
+                    .build_object("Orphan")
+                    .field::<String>("name", &())
+                    .register();
+
+                registry
+                    .build_object("Query")
+                    .field_with(|_| Field::new("named", named))
+                    .field_with(|_| Field::new("anything", anything))
+                    .field_with(|_| Field::new("__secret", node))
+                    .into_meta()
+            }
+        }
+
+        impl<S: ScalarValue> GraphQLValue<S> for Query {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn type_name(&self, _: &()) -> Option<&'static str> {
+                Some("Query")
+            }
+        }
+
+        #[test]
+        fn reports_all_violations() {
+            let err = RootNode::<_, _, _, DefaultScalarValue>::try_new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .err()
+            .expect("schema should be invalid");
+
+            assert_eq!(
+                err,
+                SchemaError::Invalid(vec![
+                    r#"Union "Anything" has member "Named", which is not an object type"#.into(),
+                    r#"Interface "Named" resolves into "Robot", which doesn't implement it"#.into(),
+                    r#"Input object "Node" can't be provided, as it requires itself through non-null fields: Node.next"#.into(),
+                    r#"Type "Orphan" is not referenced from the root operation types"#.into(),
+                    r#"Object "Pet" implements "Named", which can't resolve into it"#.into(),
+                    r#"Field "Pet.name" has type "String", which is not a subtype of "String!" on interface "Named""#.into(),
+                    r#"Field "__secret" of "Query" must not begin with "__", which is reserved by GraphQL"#.into(),
+                ]),
+            );
+        }
+    }
 
     #[cfg(feature = "graphql-parser-integration")]
     mod graphql_parser_integration {
@@ -706,5 +1662,153 @@ mod test {
             .unwrap();
             assert_eq!(format!("{}", ast), schema.as_schema_language());
         }
+
+        #[test]
+        fn default_values_as_literals() {
+            #[derive(GraphQLEnum)]
+            enum Fruit {
+                Apple,
+                Orange,
+            }
+            #[derive(GraphQLInputObject)]
+            struct Basket {
+                fruits: Vec<Fruit>,
+                label: String,
+            }
+            struct Query;
+            #[juniper::graphql_object]
+            impl Query {
+                #[graphql(arguments(
+                    fruit(default = Fruit::Orange),
+                    fruits(default = [Fruit::Apple, Fruit::Orange]),
+                    basket(default = Basket { fruits: [Fruit::Apple], label: "say \"hi\"" }),
+                ))]
+                fn count(fruit: Fruit, fruits: Vec<Fruit>, basket: Basket) -> i32 {
+                    let _ = (fruit, basket);
+                    fruits.len() as i32
+                }
+            };
+
+            let schema = crate::RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            );
+            let ast = graphql_parser::parse_schema::<&str>(
+                r#"
+                input Basket {
+                    fruits: [Fruit!]!
+                    label: String!
+                }
+                enum Fruit {
+                    APPLE
+                    ORANGE
+                }
+                type Query {
+                  count(fruit: Fruit = ORANGE, fruits: [Fruit!] = [APPLE, ORANGE], basket: Basket = {fruits: [APPLE], label: "say \"hi\""}): Int!
+                }
+                schema {
+                  query: Query
+                }
+            "#,
+            )
+            .unwrap();
+            assert_eq!(format!("{}", ast), schema.as_schema_language());
+        }
+    }
+    mod meta_accessors {
+        use crate as juniper;
+        use crate::{
+            meta::AppliedDirective, DefaultScalarValue, EmptyMutation, EmptySubscription,
+            GraphQLEnum, InputValue, RootNode, TypeWrapper,
+        };
+
+        #[derive(GraphQLEnum)]
+        enum Fruit {
+            Apple,
+            #[graphql(deprecated = "Too sour")]
+            Lemon,
+        }
+
+        struct Query;
+
+        #[juniper::graphql_object]
+        impl Query {
+            #[graphql(arguments(first(default = 10)))]
+            fn fruits(first: i32, name: Option<String>, kind: Fruit) -> Vec<Option<Fruit>> {
+                let _ = (first, name, kind);
+                vec![]
+            }
+
+            #[graphql(deprecated = "Use fruits")]
+            fn fruit() -> Option<Fruit> {
+                None
+            }
+        }
+
+        #[test]
+        fn typed_accessors() {
+            let root = RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            );
+            let schema = &root.schema;
+
+            let query = schema.concrete_query_type();
+            assert_eq!(
+                query
+                    .field_list()
+                    .iter()
+                    .filter(|f| !f.is_builtin())
+                    .map(|f| f.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["fruits", "fruit"],
+            );
+            assert!(query.interface_names().is_empty());
+            assert!(query.enum_value_list().is_empty());
+
+            let fruits = query.field_by_name("fruits").unwrap();
+            assert_eq!(fruits.type_name(), "Fruit");
+            assert!(!fruits.is_nullable());
+            assert_eq!(
+                fruits.field_type.wrappers(),
+                vec![TypeWrapper::NonNull, TypeWrapper::List],
+            );
+            assert!(!fruits.is_deprecated());
+            assert!(fruits.directives().is_empty());
+            assert_eq!(fruits.argument_list().len(), 3);
+            assert!(!fruits.argument_by_name("first").unwrap().is_required());
+            assert!(fruits.argument_by_name("name").unwrap().is_nullable());
+            assert!(fruits.argument_by_name("kind").unwrap().is_required());
+            assert_eq!(
+                fruits.argument_by_name("kind").unwrap().type_name(),
+                "Fruit"
+            );
+
+            let fruit = query.field_by_name("fruit").unwrap();
+            assert!(fruit.is_nullable());
+            assert!(fruit.field_type.wrappers().is_empty());
+            assert_eq!(fruit.deprecation_reason(), Some("Use fruits"));
+            assert_eq!(
+                fruit.directives(),
+                vec![AppliedDirective {
+                    name: "deprecated".to_owned(),
+                    arguments: vec![("reason".to_owned(), InputValue::scalar("Use fruits"))],
+                }],
+            );
+
+            let values = schema
+                .concrete_type_by_name("Fruit")
+                .unwrap()
+                .enum_value_list();
+            assert_eq!(values.len(), 2);
+            assert!(!values[0].is_deprecated());
+            assert_eq!(values[1].deprecation_reason(), Some("Too sour"));
+            assert_eq!(
+                values[1].directives::<DefaultScalarValue>()[0].name,
+                "deprecated",
+            );
+        }
     }
 }
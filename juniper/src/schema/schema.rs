@@ -1,13 +1,17 @@
 use crate::{
     ast::Selection,
-    executor::{ExecutionResult, Executor, Registry},
+    executor::{ExecutionResult, Executor, FieldError, Registry},
+    introspection::selection_depth,
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
+        async_local::{GraphQLTypeAsyncLocal, GraphQLValueAsyncLocal},
         base::{Arguments, GraphQLType, GraphQLValue, TypeKind},
     },
     value::{ScalarValue, Value},
 };
 
+#[cfg(feature = "relay")]
+use crate::relay::FetchedNodes;
 use crate::schema::{
     meta::{
         Argument, EnumMeta, EnumValue, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta,
@@ -58,15 +62,41 @@ where
         args: &Arguments<S>,
         executor: &Executor<Self::Context, S>,
     ) -> ExecutionResult<S> {
+        if field == "__schema" || field == "__type" {
+            if let Some(max) = self.schema.introspection_max_depth {
+                if selection_depth(executor) > max {
+                    return Err(FieldError::new(
+                        format!("Introspection is limited to a depth of {}", max),
+                        Value::null(),
+                    ));
+                }
+            }
+        }
+
         match field {
             "__schema" => executor
                 .replaced_context(&self.schema)
                 .resolve(&(), &self.schema),
             "__type" => {
                 let type_name: String = args.get("name").unwrap();
+                let meta_type = self
+                    .schema
+                    .concrete_type_by_name(&type_name)
+                    .filter(|t| executor.authorization().allows_type(t))
+                    .map(TypeType::Concrete);
                 executor
                     .replaced_context(&self.schema)
-                    .resolve(&(), &self.schema.type_by_name(&type_name))
+                    .resolve(&(), &meta_type)
+            }
+            #[cfg(feature = "relay")]
+            "node" | "nodes" if !self.node_fetchers.is_empty() => {
+                match self
+                    .node_fetchers
+                    .fetch_field(field, args, executor.context())?
+                {
+                    FetchedNodes::One(node) => executor.resolve(&(), &node),
+                    FetchedNodes::Many(nodes) => executor.resolve(&(), &nodes),
+                }
             }
             _ => self.query_type.resolve_field(info, field, args, executor),
         }
@@ -118,6 +148,16 @@ where
                 let v = self.resolve_field(info, field_name, arguments, executor);
                 Box::pin(ready(v))
             }
+            #[cfg(feature = "relay")]
+            "node" | "nodes" if !self.node_fetchers.is_empty() => Box::pin(async move {
+                match self
+                    .node_fetchers
+                    .fetch_field(field_name, arguments, executor.context())?
+                {
+                    FetchedNodes::One(node) => executor.resolve_async(&(), &node).await,
+                    FetchedNodes::Many(nodes) => executor.resolve_async(&(), &nodes).await,
+                }
+            }),
             _ => self
                 .query_type
                 .resolve_field_async(info, field_name, arguments, executor),
@@ -125,6 +165,40 @@ where
     }
 }
 
+impl<'a, S, QueryT, MutationT, SubscriptionT> GraphQLValueAsyncLocal<S>
+    for RootNode<'a, QueryT, MutationT, SubscriptionT, S>
+where
+    QueryT: GraphQLTypeAsyncLocal<S>,
+    QueryT::Context: 'a,
+    MutationT: GraphQLTypeAsyncLocal<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    fn resolve_field_local<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'b, ExecutionResult<S>> {
+        use futures::future::ready;
+        match field_name {
+            "__schema" | "__type" => {
+                let v = self.resolve_field(info, field_name, arguments, executor);
+                Box::pin(ready(v))
+            }
+            #[cfg(feature = "relay")]
+            "node" | "nodes" if !self.node_fetchers.is_empty() => {
+                let v = self.resolve_field(info, field_name, arguments, executor);
+                Box::pin(ready(v))
+            }
+            _ => self
+                .query_type
+                .resolve_field_local(info, field_name, arguments, executor),
+        }
+    }
+}
+
 #[crate::graphql_object(
     name = "__Schema"
     Context = SchemaType<'a, S>,
@@ -137,7 +211,7 @@ impl<'a, S> SchemaType<'a, S>
 where
     S: crate::ScalarValue + 'a,
 {
-    fn types(&self) -> Vec<TypeType<S>> {
+    fn types(&self, executor: &Executor<'_, '_, SchemaType<'a, S>, S>) -> Vec<TypeType<S>> {
         self.type_list()
             .into_iter()
             .filter(|t| {
@@ -145,6 +219,7 @@ where
                     .map(|t| {
                         !(t.name() == Some("_EmptyMutation")
                             || t.name() == Some("_EmptySubscription"))
+                            && executor.authorization().allows_type(t)
                     })
                     .unwrap_or(false)
             })
@@ -187,9 +262,9 @@ where
         }
     }
 
-    fn description(&self) -> Option<&String> {
+    fn description(&self, context: &SchemaType<'a, S>) -> Option<&String> {
         match *self {
-            TypeType::Concrete(t) => t.description(),
+            TypeType::Concrete(t) if context.introspection_descriptions => t.description(),
             _ => None,
         }
     }
@@ -203,16 +278,26 @@ where
     }
 
     #[graphql(arguments(include_deprecated(default = false)))]
-    fn fields(&self, include_deprecated: bool) -> Option<Vec<&Field<S>>> {
+    fn fields(
+        &self,
+        include_deprecated: bool,
+        executor: &Executor<'_, '_, SchemaType<'a, S>, S>,
+    ) -> Option<Vec<&Field<S>>> {
         match *self {
-            TypeType::Concrete(&MetaType::Interface(InterfaceMeta { ref fields, .. }))
-            | TypeType::Concrete(&MetaType::Object(ObjectMeta { ref fields, .. })) => Some(
-                fields
-                    .iter()
-                    .filter(|f| include_deprecated || !f.deprecation_status.is_deprecated())
-                    .filter(|f| !f.name.starts_with("__"))
-                    .collect(),
-            ),
+            TypeType::Concrete(
+                meta_type @ &MetaType::Interface(InterfaceMeta { ref fields, .. }),
+            )
+            | TypeType::Concrete(meta_type @ &MetaType::Object(ObjectMeta { ref fields, .. })) => {
+                let authorization = executor.authorization();
+                Some(
+                    fields
+                        .iter()
+                        .filter(|f| include_deprecated || !f.deprecation_status.is_deprecated())
+                        .filter(|f| !f.name.starts_with("__"))
+                        .filter(|f| authorization.allows_field(executor.context(), meta_type, f))
+                        .collect(),
+                )
+            }
             _ => None,
         }
     }
@@ -249,8 +334,12 @@ where
         }
     }
 
-    fn possible_types(&self, schema: &SchemaType<'a, S>) -> Option<Vec<TypeType<S>>> {
-        match *self {
+    fn possible_types(
+        &self,
+        executor: &Executor<'_, '_, SchemaType<'a, S>, S>,
+    ) -> Option<Vec<TypeType<S>>> {
+        let schema = executor.context();
+        let possible_types = match *self {
             TypeType::Concrete(&MetaType::Union(UnionMeta {
                 ref of_type_names, ..
             })) => Some(
@@ -285,7 +374,19 @@ where
                     .collect(),
             ),
             _ => None,
-        }
+        };
+
+        let authorization = executor.authorization();
+        possible_types.map(|types: Vec<TypeType<S>>| {
+            types
+                .into_iter()
+                .filter(|t| {
+                    t.to_concrete()
+                        .into_iter()
+                        .all(|t| authorization.allows_type(t))
+                })
+                .collect()
+        })
     }
 
     #[graphql(arguments(include_deprecated(default = false)))]
@@ -318,8 +419,10 @@ where
         &self.name
     }
 
-    fn description(&self) -> &Option<String> {
-        &self.description
+    fn description(&self, context: &SchemaType<'a, S>) -> Option<&String> {
+        self.description
+            .as_ref()
+            .filter(|_| context.introspection_descriptions)
     }
 
     fn args(&self) -> Vec<&Argument<S>> {
@@ -358,8 +461,10 @@ where
         &self.name
     }
 
-    fn description(&self) -> &Option<String> {
-        &self.description
+    fn description(&self, context: &SchemaType<'a, S>) -> Option<&String> {
+        self.description
+            .as_ref()
+            .filter(|_| context.introspection_descriptions)
     }
 
     #[graphql(name = "type")]
@@ -387,8 +492,10 @@ where
         &self.name
     }
 
-    fn description(&self) -> &Option<String> {
-        &self.description
+    fn description(&self, executor: &Executor<'_, '_, (), S>) -> Option<&String> {
+        self.description
+            .as_ref()
+            .filter(|_| executor.schema().introspection_descriptions)
     }
 
     fn is_deprecated(&self) -> bool {
@@ -416,8 +523,10 @@ where
         &self.name
     }
 
-    fn description(&self) -> &Option<String> {
-        &self.description
+    fn description(&self, context: &SchemaType<'a, S>) -> Option<&String> {
+        self.description
+            .as_ref()
+            .filter(|_| context.introspection_descriptions)
     }
 
     fn locations(&self) -> &Vec<DirectiveLocation> {
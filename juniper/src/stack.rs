@@ -0,0 +1,57 @@
+//! Keeping the processing of deeply nested queries from overflowing the stack
+//!
+//! Parsing, validating and resolving a query recurse into its selection sets,
+//! and resolving a value recurses into the values of its fields, so the stack
+//! grows with the depth of the query. The depth is bounded by the nesting
+//! depth limit of queries, but every level takes a few kilobytes of stack,
+//! which exhausts the small stacks of some threads long before that limit.
+//! Instead of recursing further on such a stack, the processing continues on a
+//! new, heap-allocated segment of stack.
+//!
+//! Switching stacks needs the `stacker` feature, enabled by default. Without
+//! it, and on `wasm32` targets where the stack can't be switched, queries are
+//! processed on the current stack as is, so the stack of the thread must be
+//! large enough for the deepest queries the recursion limit lets through.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Space left on the stack below which a new segment is allocated
+#[cfg(all(feature = "stacker", not(target_arch = "wasm32")))]
+const RED_ZONE: usize = 64 * 1024;
+
+/// Size of every newly allocated segment of stack
+#[cfg(all(feature = "stacker", not(target_arch = "wasm32")))]
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+/// Calls `f`, on a new segment of stack if the current one is running out
+#[cfg(all(feature = "stacker", not(target_arch = "wasm32")))]
+#[inline]
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_SEGMENT_SIZE, f)
+}
+
+/// Calls `f` on the current stack, which can't be switched
+#[cfg(not(all(feature = "stacker", not(target_arch = "wasm32"))))]
+#[inline]
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+/// Future polling the wrapped one with [`ensure_sufficient_stack`]
+///
+/// Polling the future of a value polls the futures of its fields in turn, so
+/// asynchronous resolution needs the same care as the synchronous one.
+pub(crate) struct SufficientStack<F>(pub(crate) F);
+
+impl<F: Future + Unpin> Future for SufficientStack<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.0;
+        ensure_sufficient_stack(|| Pin::new(inner).poll(cx))
+    }
+}
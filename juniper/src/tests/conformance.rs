@@ -0,0 +1,589 @@
+//! Runner of the [graphql-cats](https://github.com/graphql-cats/graphql-cats)
+//! conformance scenarios
+//!
+//! Scenarios are read from the `cats` directory next to this file, which holds
+//! scenarios written in the format of the suite (see its `README.md` for their
+//! origin), or from the directory set in the `GRAPHQL_CATS_DIR` environment
+//! variable, e.g. the `scenarios` of a checkout of the full suite:
+//!
+//! ```sh
+//! GRAPHQL_CATS_DIR=../graphql-cats/scenarios cargo test -p juniper conformance -- --nocapture
+//! ```
+//!
+//! Parsing and validation tests are run, the latter against the schema of the
+//! validation tests, which is the `validation.schema.graphql` of the suite.
+//! Errors are compared by the language independent codes and arguments of the
+//! suite, or by their message. Execution tests need schemas built from SDL,
+//! which juniper can't do, so they're skipped, as are the tests relying on
+//! anything else the runner doesn't support.
+//!
+//! Tests failing for known reasons are listed in the `DIVERGENCES`. The run
+//! fails if any other test fails, or if one of them passes, so that the list
+//! is kept current as the divergences get fixed.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Map as JsonMap, Value as Json};
+use serde_yaml::Value as Yaml;
+
+use crate::{
+    messages::{Message, MessageCatalog},
+    parser::parse_document_source,
+    schema::model::RootNode,
+    validation::{
+        test_harness::{test_root_node, MutationRoot, QueryRoot, SubscriptionRoot},
+        visit_all_rules, visit_rule, RuleError, ValidatorContext,
+    },
+    value::DefaultScalarValue,
+};
+
+/// Tests of the suite failing for known reasons, by scenario and test name
+const DIVERGENCES: &[(&str, &str)] = &[
+    // Ints out of the 32 bits range are rejected by the parser, not by the
+    // validation.
+    ("Validation: Arguments of correct type", "Big int into int"),
+];
+
+/// The only schema validation tests can be run against
+const VALIDATION_SCHEMA: &str = "validation.schema.graphql";
+
+type Schema = RootNode<'static, QueryRoot, MutationRoot, SubscriptionRoot, DefaultScalarValue>;
+
+#[derive(Debug)]
+enum Outcome {
+    Passed,
+    Failed(Vec<String>),
+    Skipped(String),
+}
+
+/// Error reported by juniper, with the code and arguments the suite refers to
+/// it with, if it has any
+#[derive(Debug)]
+struct Reported {
+    code: Option<String>,
+    args: JsonMap<String, Json>,
+    message: String,
+    locations: Vec<(u64, u64)>,
+}
+
+/// Catalog wording the validation errors as JSON objects holding their codes,
+/// arguments and default messages, so that they can be compared to the suite
+struct ErrorCodes;
+
+impl MessageCatalog for ErrorCodes {
+    fn message(&self, message: &Message) -> Option<String> {
+        let (code, args) = error_code(message)?;
+        let args: JsonMap<_, _> = args
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), json!(value)))
+            .collect();
+
+        Some(json!({ "code": code, "args": args, "message": message.to_string() }).to_string())
+    }
+}
+
+/// Code of the suite for the `message`, along with its arguments
+fn error_code<'m>(message: &Message<'m>) -> Option<(&'static str, Vec<(&'static str, &'m str)>)> {
+    Some(match *message {
+        Message::UnknownField {
+            field, type_name, ..
+        } => (
+            "undefined-field",
+            vec![("field", field), ("type", type_name)],
+        ),
+        Message::UnknownFieldArgument {
+            argument,
+            field,
+            type_name,
+            ..
+        } => (
+            "unknown-argument",
+            vec![
+                ("argumentName", argument),
+                ("fieldName", field),
+                ("typeName", type_name),
+            ],
+        ),
+        Message::UnknownDirectiveArgument {
+            argument,
+            directive,
+            ..
+        } => (
+            "unknown-directive-argument",
+            vec![("argumentName", argument), ("directiveName", directive)],
+        ),
+        Message::InvalidArgumentValue {
+            argument,
+            expected_type,
+        } => (
+            "bad-value",
+            vec![("argName", argument), ("type", expected_type)],
+        ),
+        Message::InvalidDefaultValue {
+            variable,
+            expected_type,
+        } => (
+            "bad-variable-default-value",
+            vec![("varName", variable), ("type", expected_type)],
+        ),
+        Message::NonNullDefaultValue {
+            variable,
+            variable_type,
+        } => (
+            "default-for-non-null-arg",
+            vec![("varName", variable), ("type", variable_type)],
+        ),
+        Message::NonCompositeFragment {
+            fragment: Some(fragment),
+            type_name,
+        } => (
+            "fragment-on-non-composite-type",
+            vec![("fragmentName", fragment), ("type", type_name)],
+        ),
+        Message::NonCompositeFragment {
+            fragment: None,
+            type_name,
+        } => (
+            "inline-fragment-on-non-composite-type",
+            vec![("type", type_name)],
+        ),
+        Message::UnknownDirective { directive } => {
+            ("unknown-directive", vec![("directiveName", directive)])
+        }
+        Message::MisplacedDirective {
+            directive,
+            location,
+        } => (
+            "misplaced-directive",
+            vec![("directiveName", directive), ("location", location)],
+        ),
+        Message::UnknownFragment { fragment } => {
+            ("undefined-fragment", vec![("fragmentName", fragment)])
+        }
+        Message::UnknownType { type_name } => ("unknown-type", vec![("type", type_name)]),
+        Message::AnonymousOperationNotAlone => ("anon-not-alone", vec![]),
+        Message::FragmentCycle { fragment } => {
+            ("no-fragment-cycles", vec![("fragmentName", fragment)])
+        }
+        Message::UndefinedVariable {
+            variable,
+            operation,
+        } => (
+            "undefined-variable",
+            with_operation(vec![("varName", variable)], operation),
+        ),
+        Message::UnusedFragment { fragment } => {
+            ("unused-fragment", vec![("fragmentName", fragment)])
+        }
+        Message::UnusedVariable {
+            variable,
+            operation,
+        } => (
+            "unused-variable",
+            with_operation(vec![("varName", variable)], operation),
+        ),
+        Message::FieldsConflict {
+            response_name,
+            reason,
+        } => (
+            "fields-conflict",
+            vec![("responseName", response_name), ("reason", reason)],
+        ),
+        Message::ImpossibleFragmentSpread {
+            fragment: Some(fragment),
+            parent_type,
+            fragment_type,
+        } => (
+            "type-incompatible-spread",
+            vec![
+                ("fragName", fragment),
+                ("parentType", parent_type),
+                ("fragType", fragment_type),
+            ],
+        ),
+        Message::ImpossibleFragmentSpread {
+            fragment: None,
+            parent_type,
+            fragment_type,
+        } => (
+            "type-incompatible-anon-spread",
+            vec![("parentType", parent_type), ("fragType", fragment_type)],
+        ),
+        Message::MissingFieldArgument {
+            field,
+            argument,
+            argument_type,
+        } => (
+            "missing-required-argument",
+            vec![
+                ("fieldName", field),
+                ("argName", argument),
+                ("type", argument_type),
+            ],
+        ),
+        Message::MissingDirectiveArgument {
+            directive,
+            argument,
+            argument_type,
+        } => (
+            "missing-required-directive-argument",
+            vec![
+                ("directiveName", directive),
+                ("argName", argument),
+                ("type", argument_type),
+            ],
+        ),
+        Message::UnexpectedSelection { field, field_type } => (
+            "no-subselection-allowed",
+            vec![("field", field), ("type", field_type)],
+        ),
+        Message::MissingSelection { field, field_type } => (
+            "required-subselection",
+            vec![("field", field), ("type", field_type)],
+        ),
+        Message::DuplicateArgument { argument } => {
+            ("duplicate-argument-names", vec![("argName", argument)])
+        }
+        Message::DuplicateFragment { fragment } => {
+            ("duplicate-fragment", vec![("fragmentName", fragment)])
+        }
+        Message::DuplicateInputField { field } => {
+            ("duplicate-input-field", vec![("fieldName", field)])
+        }
+        Message::DuplicateOperation { operation } => {
+            ("duplicate-operation", vec![("operationName", operation)])
+        }
+        Message::DuplicateVariable { variable } => {
+            ("duplicate-variable", vec![("variableName", variable)])
+        }
+        Message::NonInputVariableType {
+            variable,
+            variable_type,
+        } => (
+            "non-input-type-on-variable",
+            vec![("variableName", variable), ("typeName", variable_type)],
+        ),
+        Message::VariableInWrongPosition {
+            variable,
+            variable_type,
+            expected_type,
+        } => (
+            "bad-var-position",
+            vec![
+                ("varName", variable),
+                ("varType", variable_type),
+                ("expectedType", expected_type),
+            ],
+        ),
+        _ => return None,
+    })
+}
+
+fn with_operation<'m>(
+    mut args: Vec<(&'static str, &'m str)>,
+    operation: Option<&'m str>,
+) -> Vec<(&'static str, &'m str)> {
+    if let Some(operation) = operation {
+        args.push(("opName", operation));
+    }
+    args
+}
+
+impl From<RuleError> for Reported {
+    fn from(error: RuleError) -> Self {
+        let locations = error
+            .locations()
+            .iter()
+            .map(|p| (p.line() as u64 + 1, p.column() as u64 + 1))
+            .collect();
+
+        match serde_json::from_str::<Json>(error.message()) {
+            Ok(Json::Object(mut encoded)) => Reported {
+                code: encoded["code"].as_str().map(ToOwned::to_owned),
+                args: match encoded.remove("args") {
+                    Some(Json::Object(args)) => args,
+                    _ => JsonMap::new(),
+                },
+                message: encoded["message"].as_str().unwrap_or_default().to_owned(),
+                locations,
+            },
+            _ => Reported {
+                code: None,
+                args: JsonMap::new(),
+                message: error.message().to_owned(),
+                locations,
+            },
+        }
+    }
+}
+
+/// What juniper made of the query of a test
+struct Run {
+    syntax_error: bool,
+    errors: Vec<Reported>,
+}
+
+fn run_test(schema: &Schema, background: &Yaml, test: &Yaml) -> Outcome {
+    let given = &test["given"];
+    let query = match given["query"].as_str() {
+        Some(query) => query,
+        None => return Outcome::Skipped("no query is given".into()),
+    };
+    let schema_file = given["schema-file"]
+        .as_str()
+        .or_else(|| background["schema-file"].as_str());
+    if !given["schema"].is_null() || !background["schema"].is_null() {
+        return Outcome::Skipped("inline schemas aren't supported".into());
+    }
+
+    let when = &test["when"];
+    let run = if !when["parse"].is_null() {
+        Run {
+            syntax_error: parse_document_source(query, &schema.schema).is_err(),
+            errors: vec![],
+        }
+    } else if !when["validate"].is_null() {
+        if schema_file != Some(VALIDATION_SCHEMA) {
+            return Outcome::Skipped(format!("schema {:?} isn't supported", schema_file));
+        }
+
+        let doc = match parse_document_source(query, &schema.schema) {
+            Ok(doc) => doc,
+            Err(e) => return Outcome::Failed(vec![format!("unexpected syntax error: {}", e)]),
+        };
+        let mut ctx = ValidatorContext::new(&schema.schema, &doc);
+        match when["validate"].as_sequence() {
+            Some(rules) => {
+                for rule in rules {
+                    let rule = rule.as_str().unwrap_or_default();
+                    if !visit_rule(rule, &mut ctx, &doc) {
+                        return Outcome::Skipped(format!("rule {} isn't supported", rule));
+                    }
+                }
+            }
+            None => visit_all_rules(&mut ctx, &doc),
+        }
+
+        Run {
+            syntax_error: false,
+            errors: ctx.into_errors().into_iter().map(Reported::from).collect(),
+        }
+    } else if !when["execute"].is_null() {
+        return Outcome::Skipped("execution isn't supported".into());
+    } else {
+        return Outcome::Skipped(format!("action {:?} isn't supported", when));
+    };
+
+    let assertions = match test["then"] {
+        Yaml::Sequence(ref assertions) => assertions.iter().collect(),
+        ref assertion => vec![assertion],
+    };
+    let mut mismatches = vec![];
+    for assertion in assertions {
+        match check(&run, assertion) {
+            Ok(None) => {}
+            Ok(Some(mismatch)) => mismatches.push(mismatch),
+            Err(skipped) => return Outcome::Skipped(skipped),
+        }
+    }
+
+    if mismatches.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(mismatches)
+    }
+}
+
+/// Checks the `assertion` against the `run`, returning how they differ, or an
+/// error if the assertion isn't supported
+fn check(run: &Run, assertion: &Yaml) -> Result<Option<String>, String> {
+    let mismatch = if !assertion["passes"].is_null() {
+        if run.syntax_error || !run.errors.is_empty() {
+            Some(format!(
+                "expected to pass, got {}",
+                describe_errors(&run.errors, run.syntax_error),
+            ))
+        } else {
+            None
+        }
+    } else if !assertion["syntax-error"].is_null() {
+        if run.syntax_error {
+            None
+        } else {
+            Some("expected a syntax error".to_owned())
+        }
+    } else if let Some(count) = assertion["error-count"].as_u64() {
+        if run.errors.len() as u64 == count {
+            None
+        } else {
+            Some(format!(
+                "expected {} errors, got {}",
+                count,
+                describe_errors(&run.errors, run.syntax_error),
+            ))
+        }
+    } else if let Some(code) = assertion["error-code"].as_str() {
+        let expected_args = assertion["args"].as_mapping();
+        let found = run.errors.iter().any(|e| {
+            e.code.as_deref() == Some(code)
+                && expected_args.into_iter().flatten().all(|(name, value)| {
+                    e.args
+                        .get(name.as_str().unwrap_or_default())
+                        .and_then(Json::as_str)
+                        == Some(scalar_to_string(value).as_str())
+                })
+                && is_located(e, &assertion["loc"])
+        });
+        if found {
+            None
+        } else {
+            Some(format!(
+                "expected error {} {:?} at {:?}, got {}",
+                code,
+                expected_args,
+                assertion["loc"],
+                describe_errors(&run.errors, run.syntax_error),
+            ))
+        }
+    } else if let Some(message) = assertion["error"].as_str() {
+        let found = run
+            .errors
+            .iter()
+            .any(|e| e.message == message && is_located(e, &assertion["loc"]));
+        if found {
+            None
+        } else {
+            Some(format!(
+                "expected error {:?} at {:?}, got {}",
+                message,
+                assertion["loc"],
+                describe_errors(&run.errors, run.syntax_error),
+            ))
+        }
+    } else {
+        return Err(format!("assertion {:?} isn't supported", assertion));
+    };
+
+    Ok(mismatch)
+}
+
+/// Whether the `error` is reported at all the locations of `loc`, given as a
+/// single `{ line, column }` mapping or as a sequence of them
+fn is_located(error: &Reported, loc: &Yaml) -> bool {
+    let locations = match *loc {
+        Yaml::Null => return true,
+        Yaml::Sequence(ref locations) => locations.iter().collect(),
+        ref location => vec![location],
+    };
+
+    locations.into_iter().all(|location| {
+        match (location["line"].as_u64(), location["column"].as_u64()) {
+            (Some(line), Some(column)) => error.locations.contains(&(line, column)),
+            _ => false,
+        }
+    })
+}
+
+fn scalar_to_string(value: &Yaml) -> String {
+    match *value {
+        Yaml::String(ref s) => s.clone(),
+        Yaml::Bool(b) => b.to_string(),
+        Yaml::Number(ref n) => n.to_string(),
+        ref other => format!("{:?}", other),
+    }
+}
+
+fn describe_errors(errors: &[Reported], syntax_error: bool) -> String {
+    if syntax_error {
+        return "a syntax error".to_owned();
+    }
+    if errors.is_empty() {
+        return "no errors".to_owned();
+    }
+    errors
+        .iter()
+        .map(|e| format!("{:?}", e))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// YAML files of the `dir`, recursively, in a stable order
+fn scenario_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).unwrap_or_else(|e| panic!("{}: {}", dir.display(), e)) {
+            let path = entry.expect("Can't read directory entry").path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension() == Some("yaml".as_ref()) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[test]
+fn conforms_to_graphql_cats() {
+    let dir = env::var_os("GRAPHQL_CATS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/cats"));
+    let schema: Schema =
+        test_root_node(QueryRoot, MutationRoot, SubscriptionRoot).with_message_catalog(ErrorCodes);
+
+    let (mut passed, mut diverging, mut skipped) = (0, 0, 0);
+    let mut failures = vec![];
+
+    for file in scenario_files(&dir) {
+        let source = fs::read_to_string(&file).expect("Can't read scenario");
+        let scenario: Yaml = serde_yaml::from_str(&source)
+            .unwrap_or_else(|e| panic!("{}: invalid scenario: {}", file.display(), e));
+        let scenario_name = scenario["scenario"].as_str().unwrap_or_default();
+
+        for test in scenario["tests"].as_sequence().into_iter().flatten() {
+            let test_name = test["name"].as_str().unwrap_or_default();
+            let known = DIVERGENCES.contains(&(scenario_name, test_name));
+
+            match run_test(&schema, &scenario["background"], test) {
+                Outcome::Passed if known => failures.push(format!(
+                    "{} / {}: passes, remove it from the divergences",
+                    scenario_name, test_name,
+                )),
+                Outcome::Passed => passed += 1,
+                Outcome::Failed(mismatches) => {
+                    let failure = format!(
+                        "{} / {}:\n    {}",
+                        scenario_name,
+                        test_name,
+                        mismatches.join("\n    "),
+                    );
+                    if known {
+                        println!("Known divergence: {}", failure);
+                        diverging += 1;
+                    } else {
+                        failures.push(failure);
+                    }
+                }
+                Outcome::Skipped(reason) => {
+                    println!("Skipped {} / {}: {}", scenario_name, test_name, reason);
+                    skipped += 1;
+                }
+            }
+        }
+    }
+
+    println!(
+        "graphql-cats: {} passed, {} known divergences, {} skipped, {} failed",
+        passed,
+        diverging,
+        skipped,
+        failures.len(),
+    );
+    assert!(passed > 0, "No scenarios found in {}", dir.display());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
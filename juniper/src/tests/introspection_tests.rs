@@ -28,17 +28,14 @@ async fn test_introspection_query_type_name() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            graphql_value!({
-                "__schema": {
-                    "queryType": {
-                        "name": "Query"
-                    }
+        Ok(crate::ExecutionOutput::from_data(graphql_value!({
+            "__schema": {
+                "queryType": {
+                    "name": "Query"
                 }
+            }
 
-            }),
-            vec![]
-        ))
+        })))
     );
 }
 
@@ -59,14 +56,11 @@ async fn test_introspection_type_name() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            graphql_value!({
-                "__type": {
-                    "name": "Droid",
-                },
-            }),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(graphql_value!({
+            "__type": {
+                "name": "Droid",
+            },
+        })))
     );
 }
 
@@ -89,15 +83,12 @@ async fn test_introspection_specific_object_type_name_and_kind() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            graphql_value!({
-                "__type": {
-                    "name": "Droid",
-                    "kind": "OBJECT",
-                }
-            }),
-            vec![],
-        ))
+        Ok(crate::ExecutionOutput::from_data(graphql_value!({
+            "__type": {
+                "name": "Droid",
+                "kind": "OBJECT",
+            }
+        })))
     );
 }
 
@@ -120,15 +111,12 @@ async fn test_introspection_specific_interface_type_name_and_kind() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            graphql_value!({
-                "__type": {
-                    "name": "Character",
-                    "kind": "INTERFACE",
-                }
-            }),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(graphql_value!({
+            "__type": {
+                "name": "Character",
+                "kind": "INTERFACE",
+            }
+        })))
     );
 }
 
@@ -151,15 +139,12 @@ async fn test_introspection_documentation() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            graphql_value!({
-                "__type": {
-                    "name": "Droid",
-                    "description": "A mechanical creature in the Star Wars universe.",
-                },
-            }),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(graphql_value!({
+            "__type": {
+                "name": "Droid",
+                "description": "A mechanical creature in the Star Wars universe.",
+            },
+        })))
     );
 }
 
@@ -186,7 +171,7 @@ async fn test_introspection_directives() {
     let mut result = crate::execute(q, None, &schema, &Variables::new(), &database)
         .await
         .unwrap();
-    sort_schema_value(&mut result.0);
+    sort_schema_value(&mut result.data);
 
     let mut expected = graphql_value!({
         "__schema": {
@@ -212,7 +197,7 @@ async fn test_introspection_directives() {
     });
     sort_schema_value(&mut expected);
 
-    assert_eq!(result, (expected, vec![]));
+    assert_eq!(result, crate::ExecutionOutput::from_data(expected));
 }
 
 #[tokio::test]
@@ -235,7 +220,11 @@ async fn test_introspection_possible_types() {
 
     let result = crate::execute(doc, None, &schema, &Variables::new(), &database).await;
 
-    let (result, errors) = result.ok().expect("Query returned error");
+    let crate::ExecutionOutput {
+        data: result,
+        errors,
+        ..
+    } = result.ok().expect("Query returned error");
 
     assert_eq!(errors, vec![]);
 
@@ -273,9 +262,9 @@ async fn test_builtin_introspection_query() {
         EmptySubscription::<Database>::new(),
     );
     let mut result = crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
-    sort_schema_value(&mut result.0);
+    sort_schema_value(&mut result.data);
     let expected = schema_introspection_result();
-    assert_eq!(result, (expected, vec![]));
+    assert_eq!(result, crate::ExecutionOutput::from_data(expected));
 }
 
 #[tokio::test]
@@ -289,8 +278,332 @@ async fn test_builtin_introspection_query_without_descriptions() {
 
     let mut result =
         crate::introspect(&schema, &database, IntrospectionFormat::WithoutDescriptions).unwrap();
-    sort_schema_value(&mut result.0);
+    sort_schema_value(&mut result.data);
     let expected = schema_introspection_result_without_descriptions();
 
-    assert_eq!(result, (expected, vec![]));
+    assert_eq!(result, crate::ExecutionOutput::from_data(expected));
+}
+
+#[cfg(feature = "graphql-parser-integration")]
+#[test]
+fn test_import_introspection_result() {
+    use crate::{
+        schema::{
+            model::SchemaType,
+            translate::{graphql_parser::GraphQLParserTranslator, SchemaTranslator},
+        },
+        value::DefaultScalarValue,
+    };
+
+    fn sorted_definitions(schema: &SchemaType<DefaultScalarValue>) -> Vec<String> {
+        let document: graphql_parser::schema::Document<&str> =
+            GraphQLParserTranslator::translate_schema(schema);
+        let mut definitions = document
+            .definitions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        definitions.sort();
+        definitions
+    }
+
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let crate::ExecutionOutput { data: result, .. } =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+
+    let imported = SchemaType::from_introspection(&result).unwrap();
+
+    assert_eq!(
+        sorted_definitions(&imported),
+        sorted_definitions(&schema.schema),
+    );
+    assert_eq!(
+        crate::validate_document(
+            "{ hero(episode: EMPIRE) { name ... on Droid { primaryFunction } } }",
+            &imported,
+        ),
+        Ok(()),
+    );
+    assert!(crate::validate_document("{ hero { age } }", &imported).is_err());
+}
+
+#[test]
+fn test_import_introspection_json() {
+    use crate::{
+        ast::InputValue,
+        schema::{meta::MetaType, model::SchemaType},
+        value::{DefaultScalarValue, Value},
+    };
+
+    let response = r#"{
+        "data": {
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": null,
+                "subscriptionType": null,
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [
+                            {
+                                "name": "events",
+                                "args": [
+                                    {
+                                        "name": "filter",
+                                        "type": { "kind": "INPUT_OBJECT", "name": "Filter" },
+                                        "defaultValue": "{since: \"2020-01-01\", order: DESC}"
+                                    }
+                                ],
+                                "type": {
+                                    "kind": "NON_NULL",
+                                    "ofType": {
+                                        "kind": "LIST",
+                                        "ofType": { "kind": "SCALAR", "name": "String" }
+                                    }
+                                },
+                                "isDeprecated": false
+                            }
+                        ],
+                        "interfaces": []
+                    },
+                    {
+                        "kind": "INPUT_OBJECT",
+                        "name": "Filter",
+                        "inputFields": [
+                            {
+                                "name": "since",
+                                "type": { "kind": "SCALAR", "name": "DateTime" },
+                                "defaultValue": null
+                            },
+                            {
+                                "name": "order",
+                                "type": { "kind": "ENUM", "name": "Order" },
+                                "defaultValue": "ASC"
+                            }
+                        ]
+                    },
+                    {
+                        "kind": "ENUM",
+                        "name": "Order",
+                        "enumValues": [
+                            { "name": "ASC", "isDeprecated": false },
+                            { "name": "DESC", "isDeprecated": true, "deprecationReason": "No" }
+                        ]
+                    },
+                    { "kind": "SCALAR", "name": "DateTime", "description": "RFC 3339" },
+                    { "kind": "SCALAR", "name": "String" },
+                    { "kind": "SCALAR", "name": "Int" },
+                    { "kind": "SCALAR", "name": "Boolean" }
+                ],
+                "directives": [
+                    {
+                        "name": "cached",
+                        "locations": ["FIELD"],
+                        "args": [
+                            {
+                                "name": "ttl",
+                                "type": { "kind": "SCALAR", "name": "Int" },
+                                "defaultValue": "60"
+                            }
+                        ]
+                    },
+                    {
+                        "name": "deprecated",
+                        "locations": ["FIELD_DEFINITION", "ENUM_VALUE"],
+                        "args": []
+                    }
+                ]
+            }
+        }
+    }"#;
+    let result: Value<DefaultScalarValue> = serde_json::from_str(response).unwrap();
+
+    let imported = SchemaType::from_introspection(&result).unwrap();
+
+    let events = imported
+        .concrete_query_type()
+        .field_by_name("events")
+        .unwrap();
+    let default_value = events.arguments.as_ref().unwrap()[0].default_value.as_ref();
+    assert_eq!(
+        default_value.map(ToString::to_string).as_deref(),
+        Some(r#"{since: "2020-01-01", order: DESC}"#),
+    );
+    assert_eq!(
+        default_value.and_then(|v| v.to_object_value()).unwrap()["order"],
+        &InputValue::enum_value("DESC"),
+    );
+    match imported.concrete_type_by_name("DateTime") {
+        Some(MetaType::Scalar(scalar)) => {
+            assert_eq!(scalar.description.as_deref(), Some("RFC 3339"))
+        }
+        other => panic!("DateTime is not a scalar: {:?}", other),
+    }
+    assert_eq!(
+        crate::validate_document(
+            r#"{ events(filter: {since: 1588291200, order: ASC}) @cached }"#,
+            &imported,
+        ),
+        Ok(()),
+    );
+    assert!(crate::validate_document("{ events @deprecated }", &imported).is_err());
+    assert!(crate::validate_document("{ events(filter: {order: ALL}) }", &imported).is_err());
+}
+
+#[test]
+fn test_import_malformed_introspection() {
+    use crate::{
+        schema::model::{SchemaError, SchemaType},
+        value::DefaultScalarValue,
+    };
+
+    let result = graphql_value!({
+        "__schema": {
+            "queryType": { "name": "Query" },
+            "types": [{
+                "kind": "OBJECT",
+                "name": "Query",
+                "fields": [{
+                    "name": "user",
+                    "args": [],
+                    "type": { "kind": "OBJECT", "name": "User" },
+                }],
+                "interfaces": [],
+            }],
+            "directives": [],
+        },
+    });
+
+    assert_eq!(
+        SchemaType::<DefaultScalarValue>::from_introspection(&result).err(),
+        Some(SchemaError::MalformedIntrospection {
+            path: "__schema.types[0].fields[0].type.name".to_owned(),
+            message: r#"type "User" is not defined"#.to_owned(),
+        }),
+    );
+    assert_eq!(
+        SchemaType::<DefaultScalarValue>::from_introspection(&graphql_value!({ "data": None }))
+            .err(),
+        Some(SchemaError::MalformedIntrospection {
+            path: "__schema".to_owned(),
+            message: "expected an object".to_owned(),
+        }),
+    );
+}
+
+fn count_descriptions(value: &crate::Value) -> usize {
+    use crate::Value;
+
+    match value {
+        Value::Object(o) => o
+            .iter()
+            .map(|(k, v)| match v {
+                Value::Scalar(_) if k == "description" => 1,
+                _ => count_descriptions(v),
+            })
+            .sum(),
+        Value::List(l) => l.iter().map(count_descriptions).sum(),
+        _ => 0,
+    }
+}
+
+#[tokio::test]
+async fn test_introspection_without_descriptions() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let result = crate::introspect(&schema, &database, IntrospectionFormat::All).unwrap();
+    assert!(count_descriptions(&result.data) > 0);
+
+    let schema = schema.without_introspection_descriptions();
+    let result = crate::introspect(&schema, &database, IntrospectionFormat::All).unwrap();
+    assert!(result.errors.is_empty());
+    assert_eq!(count_descriptions(&result.data), 0);
+    #[cfg(feature = "schema-language")]
+    assert!(schema
+        .as_schema_language()
+        .contains("Which movies they appear in"));
+}
+
+#[tokio::test]
+async fn test_introspection_max_depth() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+
+    let schema = schema.with_introspection_max_depth(12);
+    let result = crate::introspect(&schema, &database, IntrospectionFormat::All).unwrap();
+    assert!(result.errors.is_empty());
+
+    let schema = schema.with_introspection_max_depth(11);
+    let result = crate::introspect(&schema, &database, IntrospectionFormat::All).unwrap();
+    assert_eq!(result.data, graphql_value!(None));
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(
+        result.errors[0].error().message(),
+        "Introspection is limited to a depth of 11",
+    );
+
+    let doc = r#"
+        { ...Root }
+        fragment Root on Query { __type(name: "Droid") { ...Type } }
+        fragment Type on __Type { fields { name } }
+    "#;
+    let schema = schema.with_introspection_max_depth(1);
+    let result = crate::execute(doc, None, &schema, &Variables::new(), &database)
+        .await
+        .unwrap();
+    assert_eq!(result.data, graphql_value!({ "__type": None }));
+    assert_eq!(result.errors.len(), 1);
+
+    let schema = schema.with_introspection_max_depth(2);
+    let result = crate::execute(doc, None, &schema, &Variables::new(), &database)
+        .await
+        .unwrap();
+    assert!(result.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_type_index_introspection_query() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+    .with_introspection_max_depth(2);
+
+    let result = crate::introspect(&schema, &database, IntrospectionFormat::TypeIndex).unwrap();
+    assert!(result.errors.is_empty());
+
+    let schema_value = result
+        .data
+        .as_object_value()
+        .unwrap()
+        .get_field_value("__schema")
+        .unwrap();
+    let schema_value = schema_value.as_object_value().unwrap();
+    assert_eq!(
+        schema_value.get_field_value("queryType"),
+        Some(&graphql_value!({ "name": "Query" })),
+    );
+    let types = schema_value
+        .get_field_value("types")
+        .unwrap()
+        .as_list_value()
+        .unwrap();
+    assert!(types.contains(&graphql_value!({ "kind": "OBJECT", "name": "Droid" })));
+    assert!(types.contains(&graphql_value!({ "kind": "ENUM", "name": "Episode" })));
 }
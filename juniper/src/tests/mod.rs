@@ -1,5 +1,7 @@
 //! Library tests and fixtures
 
+#[cfg(test)]
+mod conformance;
 pub mod fixtures;
 #[cfg(test)]
 mod introspection_tests;
@@ -11,3 +13,5 @@ mod schema_introspection;
 mod subscriptions;
 #[cfg(test)]
 mod type_info_tests;
+#[cfg(test)]
+mod validation_tests;
@@ -24,17 +24,14 @@ async fn test_hero_name() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(vec![("name", Value::scalar("R2-D2"))].into_iter().collect()),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(vec![("name", Value::scalar("R2-D2"))].into_iter().collect()),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -56,24 +53,21 @@ async fn test_hero_field_order() {
         }"#;
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("id", Value::scalar("2001")),
-                            ("name", Value::scalar("R2-D2")),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("id", Value::scalar("2001")),
+                        ("name", Value::scalar("R2-D2")),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 
     let doc_reversed = r#"
@@ -85,24 +79,21 @@ async fn test_hero_field_order() {
         }"#;
     assert_eq!(
         crate::execute(doc_reversed, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("name", Value::scalar("R2-D2")),
-                            ("id", Value::scalar("2001")),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("name", Value::scalar("R2-D2")),
+                        ("id", Value::scalar("2001")),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -127,44 +118,41 @@ async fn test_hero_name_and_friends() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("id", Value::scalar("2001")),
-                            ("name", Value::scalar("R2-D2")),
-                            (
-                                "friends",
-                                Value::list(vec![
-                                    Value::object(
-                                        vec![("name", Value::scalar("Luke Skywalker"))]
-                                            .into_iter()
-                                            .collect(),
-                                    ),
-                                    Value::object(
-                                        vec![("name", Value::scalar("Han Solo"))]
-                                            .into_iter()
-                                            .collect(),
-                                    ),
-                                    Value::object(
-                                        vec![("name", Value::scalar("Leia Organa"))]
-                                            .into_iter()
-                                            .collect(),
-                                    ),
-                                ]),
-                            ),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("id", Value::scalar("2001")),
+                        ("name", Value::scalar("R2-D2")),
+                        (
+                            "friends",
+                            Value::list(vec![
+                                Value::object(
+                                    vec![("name", Value::scalar("Luke Skywalker"))]
+                                        .into_iter()
+                                        .collect(),
+                                ),
+                                Value::object(
+                                    vec![("name", Value::scalar("Han Solo"))]
+                                        .into_iter()
+                                        .collect(),
+                                ),
+                                Value::object(
+                                    vec![("name", Value::scalar("Leia Organa"))]
+                                        .into_iter()
+                                        .collect(),
+                                ),
+                            ]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -193,156 +181,145 @@ async fn test_hero_name_and_friends_and_friends_of_friends() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("id", Value::scalar("2001")),
-                            ("name", Value::scalar("R2-D2")),
-                            (
-                                "friends",
-                                Value::list(vec![
-                                    Value::object(
-                                        vec![
-                                            ("name", Value::scalar("Luke Skywalker")),
-                                            (
-                                                "appearsIn",
-                                                Value::list(vec![
-                                                    Value::scalar("NEW_HOPE"),
-                                                    Value::scalar("EMPIRE"),
-                                                    Value::scalar("JEDI"),
-                                                ]),
-                                            ),
-                                            (
-                                                "friends",
-                                                Value::list(vec![
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("Han Solo"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![(
-                                                            "name",
-                                                            Value::scalar("Leia Organa"),
-                                                        )]
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("id", Value::scalar("2001")),
+                        ("name", Value::scalar("R2-D2")),
+                        (
+                            "friends",
+                            Value::list(vec![
+                                Value::object(
+                                    vec![
+                                        ("name", Value::scalar("Luke Skywalker")),
+                                        (
+                                            "appearsIn",
+                                            Value::list(vec![
+                                                Value::scalar("NEW_HOPE"),
+                                                Value::scalar("EMPIRE"),
+                                                Value::scalar("JEDI"),
+                                            ]),
+                                        ),
+                                        (
+                                            "friends",
+                                            Value::list(vec![
+                                                Value::object(
+                                                    vec![("name", Value::scalar("Han Solo"))]
                                                         .into_iter()
                                                         .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("C-3PO"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("R2-D2"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                ]),
-                                            ),
-                                        ]
-                                        .into_iter()
-                                        .collect(),
-                                    ),
-                                    Value::object(
-                                        vec![
-                                            ("name", Value::scalar("Han Solo")),
-                                            (
-                                                "appearsIn",
-                                                Value::list(vec![
-                                                    Value::scalar("NEW_HOPE"),
-                                                    Value::scalar("EMPIRE"),
-                                                    Value::scalar("JEDI"),
-                                                ]),
-                                            ),
-                                            (
-                                                "friends",
-                                                Value::list(vec![
-                                                    Value::object(
-                                                        vec![(
-                                                            "name",
-                                                            Value::scalar("Luke Skywalker"),
-                                                        )]
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("Leia Organa"),)]
                                                         .into_iter()
                                                         .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![(
-                                                            "name",
-                                                            Value::scalar("Leia Organa"),
-                                                        )]
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("C-3PO"))]
                                                         .into_iter()
                                                         .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("R2-D2"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                ]),
-                                            ),
-                                        ]
-                                        .into_iter()
-                                        .collect(),
-                                    ),
-                                    Value::object(
-                                        vec![
-                                            ("name", Value::scalar("Leia Organa")),
-                                            (
-                                                "appearsIn",
-                                                Value::list(vec![
-                                                    Value::scalar("NEW_HOPE"),
-                                                    Value::scalar("EMPIRE"),
-                                                    Value::scalar("JEDI"),
-                                                ]),
-                                            ),
-                                            (
-                                                "friends",
-                                                Value::list(vec![
-                                                    Value::object(
-                                                        vec![(
-                                                            "name",
-                                                            Value::scalar("Luke Skywalker"),
-                                                        )]
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("R2-D2"))]
                                                         .into_iter()
                                                         .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("Han Solo"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("C-3PO"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                    Value::object(
-                                                        vec![("name", Value::scalar("R2-D2"))]
-                                                            .into_iter()
-                                                            .collect(),
-                                                    ),
-                                                ]),
-                                            ),
-                                        ]
-                                        .into_iter()
-                                        .collect(),
-                                    ),
-                                ]),
-                            ),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+                                                ),
+                                            ]),
+                                        ),
+                                    ]
+                                    .into_iter()
+                                    .collect(),
+                                ),
+                                Value::object(
+                                    vec![
+                                        ("name", Value::scalar("Han Solo")),
+                                        (
+                                            "appearsIn",
+                                            Value::list(vec![
+                                                Value::scalar("NEW_HOPE"),
+                                                Value::scalar("EMPIRE"),
+                                                Value::scalar("JEDI"),
+                                            ]),
+                                        ),
+                                        (
+                                            "friends",
+                                            Value::list(vec![
+                                                Value::object(
+                                                    vec![
+                                                        ("name", Value::scalar("Luke Skywalker"),)
+                                                    ]
+                                                    .into_iter()
+                                                    .collect(),
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("Leia Organa"),)]
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("R2-D2"))]
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                            ]),
+                                        ),
+                                    ]
+                                    .into_iter()
+                                    .collect(),
+                                ),
+                                Value::object(
+                                    vec![
+                                        ("name", Value::scalar("Leia Organa")),
+                                        (
+                                            "appearsIn",
+                                            Value::list(vec![
+                                                Value::scalar("NEW_HOPE"),
+                                                Value::scalar("EMPIRE"),
+                                                Value::scalar("JEDI"),
+                                            ]),
+                                        ),
+                                        (
+                                            "friends",
+                                            Value::list(vec![
+                                                Value::object(
+                                                    vec![
+                                                        ("name", Value::scalar("Luke Skywalker"),)
+                                                    ]
+                                                    .into_iter()
+                                                    .collect(),
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("Han Solo"))]
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("C-3PO"))]
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                                Value::object(
+                                                    vec![("name", Value::scalar("R2-D2"))]
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                            ]),
+                                        ),
+                                    ]
+                                    .into_iter()
+                                    .collect(),
+                                ),
+                            ]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -358,21 +335,18 @@ async fn test_query_name() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "human",
-                    Value::object(
-                        vec![("name", Value::scalar("Luke Skywalker"))]
-                            .into_iter()
-                            .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "human",
+                Value::object(
+                    vec![("name", Value::scalar("Luke Skywalker"))]
+                        .into_iter()
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -388,21 +362,18 @@ async fn test_query_alias_single() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "luke",
-                    Value::object(
-                        vec![("name", Value::scalar("Luke Skywalker"))]
-                            .into_iter()
-                            .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "luke",
+                Value::object(
+                    vec![("name", Value::scalar("Luke Skywalker"))]
+                        .into_iter()
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -422,31 +393,28 @@ async fn test_query_alias_multiple() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![
-                    (
-                        "luke",
-                        Value::object(
-                            vec![("name", Value::scalar("Luke Skywalker"))]
-                                .into_iter()
-                                .collect(),
-                        ),
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![
+                (
+                    "luke",
+                    Value::object(
+                        vec![("name", Value::scalar("Luke Skywalker"))]
+                            .into_iter()
+                            .collect(),
                     ),
-                    (
-                        "leia",
-                        Value::object(
-                            vec![("name", Value::scalar("Leia Organa"))]
-                                .into_iter()
-                                .collect(),
-                        ),
+                ),
+                (
+                    "leia",
+                    Value::object(
+                        vec![("name", Value::scalar("Leia Organa"))]
+                            .into_iter()
+                            .collect(),
                     ),
-                ]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+                ),
+            ]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -471,37 +439,34 @@ async fn test_query_alias_multiple_with_fragment() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![
-                    (
-                        "luke",
-                        Value::object(
-                            vec![
-                                ("name", Value::scalar("Luke Skywalker")),
-                                ("homePlanet", Value::scalar("Tatooine")),
-                            ]
-                            .into_iter()
-                            .collect(),
-                        ),
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![
+                (
+                    "luke",
+                    Value::object(
+                        vec![
+                            ("name", Value::scalar("Luke Skywalker")),
+                            ("homePlanet", Value::scalar("Tatooine")),
+                        ]
+                        .into_iter()
+                        .collect(),
                     ),
-                    (
-                        "leia",
-                        Value::object(
-                            vec![
-                                ("name", Value::scalar("Leia Organa")),
-                                ("homePlanet", Value::scalar("Alderaan")),
-                            ]
-                            .into_iter()
-                            .collect(),
-                        ),
+                ),
+                (
+                    "leia",
+                    Value::object(
+                        vec![
+                            ("name", Value::scalar("Leia Organa")),
+                            ("homePlanet", Value::scalar("Alderaan")),
+                        ]
+                        .into_iter()
+                        .collect(),
                     ),
-                ]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+                ),
+            ]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -521,21 +486,18 @@ async fn test_query_name_variable() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &vars, &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "human",
-                    Value::object(
-                        vec![("name", Value::scalar("Luke Skywalker"))]
-                            .into_iter()
-                            .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "human",
+                Value::object(
+                    vec![("name", Value::scalar("Luke Skywalker"))]
+                        .into_iter()
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -555,10 +517,9 @@ async fn test_query_name_invalid_variable() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &vars, &database).await,
-        Ok((
-            Value::object(vec![("human", Value::null())].into_iter().collect()),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![("human", Value::null())].into_iter().collect()
+        )))
     );
 }
 
@@ -574,41 +535,38 @@ async fn test_query_friends_names() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "human",
-                    Value::object(
-                        vec![(
-                            "friends",
-                            Value::list(vec![
-                                Value::object(
-                                    vec![("name", Value::scalar("Han Solo"))]
-                                        .into_iter()
-                                        .collect(),
-                                ),
-                                Value::object(
-                                    vec![("name", Value::scalar("Leia Organa"))]
-                                        .into_iter()
-                                        .collect(),
-                                ),
-                                Value::object(
-                                    vec![("name", Value::scalar("C-3PO"))].into_iter().collect(),
-                                ),
-                                Value::object(
-                                    vec![("name", Value::scalar("R2-D2"))].into_iter().collect(),
-                                ),
-                            ]),
-                        )]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "human",
+                Value::object(
+                    vec![(
+                        "friends",
+                        Value::list(vec![
+                            Value::object(
+                                vec![("name", Value::scalar("Han Solo"))]
+                                    .into_iter()
+                                    .collect(),
+                            ),
+                            Value::object(
+                                vec![("name", Value::scalar("Leia Organa"))]
+                                    .into_iter()
+                                    .collect(),
+                            ),
+                            Value::object(
+                                vec![("name", Value::scalar("C-3PO"))].into_iter().collect(),
+                            ),
+                            Value::object(
+                                vec![("name", Value::scalar("R2-D2"))].into_iter().collect(),
+                            ),
+                        ]),
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -635,25 +593,22 @@ async fn test_query_inline_fragments_droid() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("name", Value::scalar("R2-D2")),
-                            ("__typename", Value::scalar("Droid")),
-                            ("primaryFunction", Value::scalar("Astromech")),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("name", Value::scalar("R2-D2")),
+                        ("__typename", Value::scalar("Droid")),
+                        ("primaryFunction", Value::scalar("Astromech")),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -676,24 +631,21 @@ async fn test_query_inline_fragments_human() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "hero",
-                    Value::object(
-                        vec![
-                            ("name", Value::scalar("Luke Skywalker")),
-                            ("__typename", Value::scalar("Human")),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "hero",
+                Value::object(
+                    vec![
+                        ("name", Value::scalar("Luke Skywalker")),
+                        ("__typename", Value::scalar("Human")),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
     );
 }
 
@@ -714,20 +666,43 @@ async fn test_object_typename() {
 
     assert_eq!(
         crate::execute(doc, None, &schema, &Variables::new(), &database).await,
-        Ok((
-            Value::object(
-                vec![(
-                    "human",
-                    Value::object(
-                        vec![("__typename", Value::scalar("Human"))]
-                            .into_iter()
-                            .collect(),
-                    ),
-                )]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![(
+                "human",
+                Value::object(
+                    vec![("__typename", Value::scalar("Human"))]
+                        .into_iter()
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect()
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_fragment_library() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+    .with_fragment_library("fragment HeroName on Character { name }")
+    .unwrap();
+
+    assert_eq!(
+        crate::execute(
+            "{ hero { ...HeroName } }",
+            None,
+            &schema,
+            &Variables::new(),
+            &database,
+        )
+        .await,
+        Ok(crate::ExecutionOutput::from_data(
+            graphql_value!({"hero": {"name": "R2-D2"}})
+        )),
     );
 }
@@ -107,7 +107,11 @@ fn create_and_execute(
 
     assert!(response.is_ok());
 
-    let (values, errors) = response.unwrap();
+    let crate::ExecutionOutput {
+        data: values,
+        errors,
+        ..
+    } = response.unwrap();
 
     if errors.len() > 0 {
         return Err(errors);
@@ -299,6 +303,39 @@ fn resolves_nontyped_inline_fragments() {
     assert_eq!(collected_values, expected_values);
 }
 
+#[test]
+fn skips_with_variable_defaults() {
+    let query = r#"subscription Sub($withName: Boolean = false, $skip: Boolean = true) {
+            asyncHuman(id: "32") {
+              id
+              name @include(if: $withName)
+            }
+            errorHuman(id: "32") @skip(if: $skip) {
+              id
+            }
+          }"#
+    .to_string();
+
+    let (names, collected_values) = create_and_execute(query).expect("Got error from stream");
+
+    let mut iterator_count = 0;
+    let expected_values = vec![vec![Ok(Value::Object(Object::from_iter(iter::from_fn(
+        move || {
+            iterator_count += 1;
+            match iterator_count {
+                1 => Some((
+                    "id",
+                    Value::Scalar(DefaultScalarValue::String("stream id".to_string())),
+                )),
+                _ => None,
+            }
+        },
+    ))))]];
+
+    assert_eq!(names, vec!["asyncHuman"]);
+    assert_eq!(collected_values, expected_values);
+}
+
 #[test]
 fn can_access_arguments() {
     let query = r#"subscription {
@@ -2,7 +2,10 @@ use indexmap::IndexMap;
 
 use crate::{
     executor::{ExecutionResult, Executor, Registry, Variables},
-    schema::{meta::MetaType, model::RootNode},
+    schema::{
+        meta::{Argument, EnumValue, MetaType},
+        model::RootNode,
+    },
     types::{
         base::{Arguments, GraphQLType, GraphQLValue},
         scalars::{EmptyMutation, EmptySubscription},
@@ -94,17 +97,176 @@ fn test_node() {
 
     assert_eq!(
         crate::execute_sync(doc, None, &schema, &Variables::new(), &()),
-        Ok((
-            Value::object(
-                vec![
-                    ("foo", Value::scalar("1")),
-                    ("bar", Value::scalar("2")),
-                    ("baz", Value::scalar("3")),
-                ]
-                .into_iter()
-                .collect()
-            ),
-            vec![]
-        ))
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![
+                ("foo", Value::scalar("1")),
+                ("bar", Value::scalar("2")),
+                ("baz", Value::scalar("3")),
+            ]
+            .into_iter()
+            .collect()
+        )))
     );
 }
+
+pub struct CatalogTypeInfo {
+    name: String,
+    colors: Vec<String>,
+}
+
+pub struct Catalog;
+
+impl<S> GraphQLType<S> for Catalog
+where
+    S: ScalarValue,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        Some(&info.name)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let color = info
+            .colors
+            .iter()
+            .fold(registry.build_enum("Color"), |color, name| {
+                color.value(EnumValue::new(name))
+            })
+            .register();
+        let filter = registry
+            .build_input_object("Filter")
+            .description("Items to describe")
+            .field::<String>("name", &())
+            .field_with(|_| Argument::new("color", color.clone()))
+            .register();
+
+        registry
+            .build_object(&info.name)
+            .field::<i32>("count", &())
+            .field_with(|registry| {
+                registry
+                    .field::<String>("describe", &())
+                    .argument(Argument::new("filter", filter))
+            })
+            .into_meta()
+    }
+}
+
+impl<S> GraphQLValue<S> for Catalog
+where
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = CatalogTypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field_name: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field_name {
+            "count" => executor.resolve(&(), &(info.colors.len() as i32)),
+            "describe" => {
+                let filter = args.get_input_value("filter").unwrap();
+                let field = |name: &str| filter.to_object_value().unwrap()[name].to_string();
+                executor.resolve(&(), &format!("{} {}", field("color"), field("name")))
+            }
+            _ => panic!("Field {} not found on type {}", field_name, info.name),
+        }
+    }
+}
+
+#[test]
+fn test_types_built_at_runtime() {
+    let doc = r#"
+        {
+            count
+            describe(filter: {name: "hat", color: RED})
+            __type(name: "Filter") {
+                description
+                inputFields { name type { kind ofType { name kind } } }
+            }
+        }"#;
+    let info = CatalogTypeInfo {
+        name: "Catalog".to_string(),
+        colors: vec!["RED".to_string(), "GREEN".to_string()],
+    };
+    let schema: RootNode<_, _, _> = RootNode::new_with_info(
+        Catalog,
+        EmptyMutation::new(),
+        EmptySubscription::new(),
+        info,
+        (),
+        (),
+    );
+
+    let input_field = |name: &str, type_name: &str, kind: &str| {
+        Value::object(
+            vec![
+                ("name", Value::scalar(name)),
+                (
+                    "type",
+                    Value::object(
+                        vec![
+                            ("kind", Value::scalar("NON_NULL")),
+                            (
+                                "ofType",
+                                Value::object(
+                                    vec![
+                                        ("name", Value::scalar(type_name)),
+                                        ("kind", Value::scalar(kind)),
+                                    ]
+                                    .into_iter()
+                                    .collect(),
+                                ),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    };
+    assert_eq!(
+        crate::execute_sync(doc, None, &schema, &Variables::new(), &()),
+        Ok(crate::ExecutionOutput::from_data(Value::object(
+            vec![
+                ("count", Value::scalar(2)),
+                ("describe", Value::scalar(r#"RED "hat""#)),
+                (
+                    "__type",
+                    Value::object(
+                        vec![
+                            ("description", Value::scalar("Items to describe")),
+                            (
+                                "inputFields",
+                                Value::list(vec![
+                                    input_field("name", "String", "SCALAR"),
+                                    input_field("color", "Color", "ENUM"),
+                                ]),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect()
+        )))
+    );
+
+    let doc = r#"{ describe(filter: {name: "hat", color: BLUE}) }"#;
+    assert!(crate::execute_sync(doc, None, &schema, &Variables::new(), &()).is_err());
+}
@@ -0,0 +1,266 @@
+use crate::{
+    ast::InputValue,
+    messages::Message,
+    operation_info,
+    parser::SourcePosition,
+    schema::model::RootNode,
+    tests::fixtures::starwars::{model::Database, schema::Query},
+    types::scalars::{EmptyMutation, EmptySubscription},
+    validate_document, validate_variables,
+    validation::RuleError,
+    GraphQLError, OperationType, Variables,
+};
+
+fn schema<'a>() -> RootNode<'a, Query, EmptyMutation<Database>, EmptySubscription<Database>> {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+}
+
+#[test]
+fn valid_document_passes() {
+    let doc = r#"
+        query HeroNameAndFriends($episode: Episode) {
+            hero(episode: $episode) {
+                name
+                friends { ...FriendName }
+            }
+        }
+
+        fragment FriendName on Character { name }"#;
+
+    assert_eq!(validate_document(doc, &schema().schema), Ok(()));
+}
+
+#[test]
+fn all_rule_errors_are_reported() {
+    let doc = r#"
+        query Hero($unused: String) {
+            hero {
+                nme
+            }
+        }"#;
+
+    assert_eq!(
+        validate_document(doc, &schema().schema),
+        Err(GraphQLError::ValidationError(vec![
+            RuleError::new(
+                r#"Variable "$unused" is not used by operation "Hero""#,
+                &[SourcePosition::new(20, 1, 19)],
+            ),
+            RuleError::new(
+                r#"Unknown field "nme" on type "Character". Did you mean "name"?"#,
+                &[SourcePosition::new(74, 3, 16)],
+            ),
+        ])),
+    );
+}
+
+#[test]
+fn parse_errors_are_reported() {
+    assert!(matches!(
+        validate_document("{ hero ", &schema().schema),
+        Err(GraphQLError::ParseError(_)),
+    ));
+}
+
+#[test]
+fn operation_info_describes_selected_operation() {
+    let doc = r#"
+        query Other { hero { id } }
+
+        query HeroNameAndFriends($episode: Episode, $id: String!, $withFriends: Boolean = true) {
+            hero(episode: $episode) {
+                name
+                friends @include(if: $withFriends) { ...FriendName }
+            }
+            human(id: $id) { ... on Human { homePlanet } }
+        }
+
+        fragment FriendName on Character { name }"#;
+
+    let info = operation_info(doc, Some("HeroNameAndFriends"), &schema().schema).unwrap();
+
+    assert_eq!(info.operation_type, OperationType::Query);
+    assert_eq!(info.name, Some("HeroNameAndFriends"));
+    assert_eq!(
+        info.variables
+            .iter()
+            .map(|v| (v.name, v.var_type.to_string(), v.is_required()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("episode", "Episode".to_owned(), false),
+            ("id", "String!".to_owned(), true),
+            ("withFriends", "Boolean".to_owned(), false),
+        ],
+    );
+    assert_eq!(
+        info.required_variables()
+            .map(|v| v.name)
+            .collect::<Vec<_>>(),
+        vec!["id"],
+    );
+    assert_eq!(
+        info.fields,
+        vec![
+            "Character.friends",
+            "Character.name",
+            "Human.homePlanet",
+            "Query.hero",
+            "Query.human",
+        ],
+    );
+}
+
+#[test]
+fn operation_info_requires_valid_document() {
+    assert!(matches!(
+        operation_info("{ hero { nme } }", None, &schema().schema),
+        Err(GraphQLError::ValidationError(_)),
+    ));
+    assert_eq!(
+        operation_info("query A { hero { id } }", Some("B"), &schema().schema),
+        Err(GraphQLError::UnknownOperationName),
+    );
+}
+
+#[test]
+fn variables_are_validated_without_execution() {
+    let doc = r#"
+        query Other { hero { id } }
+
+        query Human($id: String!, $episode: Episode = JEDI) {
+            human(id: $id) { name }
+            hero(episode: $episode) { name }
+        }"#;
+    let vars = |vars: Vec<(&str, InputValue)>| -> Variables {
+        vars.into_iter().map(|(k, v)| (k.to_owned(), v)).collect()
+    };
+
+    assert_eq!(
+        validate_variables(
+            doc,
+            Some("Human"),
+            &schema().schema,
+            &vars(vec![("id", InputValue::scalar("1000"))]),
+        ),
+        Ok(()),
+    );
+    assert_eq!(
+        validate_variables(
+            doc,
+            Some("Human"),
+            &schema().schema,
+            &vars(vec![("episode", InputValue::scalar(4))]),
+        ),
+        Err(GraphQLError::ValidationError(vec![
+            RuleError::new(
+                r#"Variable "$id" of required type "String!" was not provided."#,
+                &[SourcePosition::new(58, 3, 20)],
+            ),
+            RuleError::new(
+                r#"Variable "$episode" got invalid value. Expected "Episode", found not a string or enum."#,
+                &[SourcePosition::new(72, 3, 34)],
+            ),
+        ])),
+    );
+    assert_eq!(
+        validate_variables(doc, None, &schema().schema, &vars(vec![])),
+        Err(GraphQLError::MultipleOperationsProvided),
+    );
+}
+
+#[test]
+fn message_catalog_words_the_errors() {
+    let schema = schema().with_message_catalog(|message: &Message| match *message {
+        Message::UnknownField {
+            field, type_name, ..
+        } => Some(format!("{}.{} n'existe pas", type_name, field)),
+        Message::NotAnEnum { enum_type, .. } => Some(format!("{} attendu", enum_type)),
+        Message::InvalidVariableValue {
+            variable, reason, ..
+        } => Some(format!("${} : {}", variable, reason)),
+        _ => None,
+    });
+
+    assert_eq!(
+        validate_document("{ hero { nme } }", &schema.schema),
+        Err(GraphQLError::ValidationError(vec![RuleError::new(
+            "Character.nme n'existe pas",
+            &[SourcePosition::new(9, 0, 9)],
+        )])),
+    );
+    assert_eq!(
+        validate_variables(
+            "query Hero($episode: Episode!) { hero(episode: $episode) { id } }",
+            None,
+            &schema.schema,
+            &vec![("episode".to_owned(), InputValue::scalar(4))]
+                .into_iter()
+                .collect(),
+        ),
+        Err(GraphQLError::ValidationError(vec![RuleError::new(
+            "$episode : Episode attendu",
+            &[SourcePosition::new(11, 0, 11)],
+        )])),
+    );
+    assert_eq!(
+        validate_document("{ hero { id } human { id } }", &schema.schema),
+        Err(GraphQLError::ValidationError(vec![RuleError::new(
+            r#"Field "human" argument "id" of type "String!" is required but not provided"#,
+            &[SourcePosition::new(14, 0, 14)],
+        )])),
+    );
+}
+
+const FRAGMENT_LIBRARY: &str = r#"
+    fragment FriendName on Character { name ...CharacterId }
+    fragment CharacterId on Character { id }
+    fragment Broken on Character { nme }"#;
+
+#[test]
+fn documents_spread_library_fragments() {
+    let schema = schema().with_fragment_library(FRAGMENT_LIBRARY).unwrap();
+
+    assert_eq!(
+        validate_document("{ hero { friends { ...FriendName } } }", &schema.schema),
+        Ok(()),
+    );
+    assert_eq!(
+        validate_document(
+            "{ hero { ...FriendName } } fragment FriendName on Character { id }",
+            &schema.schema,
+        ),
+        Ok(()),
+    );
+    assert_eq!(
+        validate_document("{ hero { ...Broken } }", &schema.schema),
+        Err(GraphQLError::ValidationError(vec![RuleError::new(
+            r#"Unknown field "nme" on type "Character". Did you mean "name"?"#,
+            &[SourcePosition::new(142, 3, 35)],
+        )])),
+    );
+}
+
+#[test]
+fn fragment_library_only_contains_unique_fragments() {
+    assert_eq!(
+        schema()
+            .with_fragment_library(
+                "fragment A on Character { id } fragment A on Character { name } { hero { id } }",
+            )
+            .err(),
+        Some(GraphQLError::ValidationError(vec![
+            RuleError::new(
+                "There can only be one fragment named A",
+                &[SourcePosition::new(40, 0, 40)],
+            ),
+            RuleError::new(
+                "A fragment library may only contain fragment definitions",
+                &[SourcePosition::new(64, 0, 64)],
+            ),
+        ])),
+    );
+}
@@ -0,0 +1,138 @@
+use crate::{
+    ast::Selection,
+    executor::{ExecutionResult, Executor},
+    value::{DefaultScalarValue, ScalarValue},
+};
+
+use crate::LocalBoxFuture;
+
+use super::base::{Arguments, GraphQLType, GraphQLValue};
+
+/// Counterpart of [`GraphQLValueAsync`] trait for single-threaded executors, with resolvers
+/// returning non-[`Send`] futures.
+///
+/// Neither the value, nor its context or type info are required to be [`Sync`], so resolvers may
+/// use [`Rc`]-based state and await futures pinned to the current thread. Queries over
+/// implementors of this trait are executed with [`execute_local`].
+///
+/// Convenience macros expand into an implementation of this trait instead of the
+/// [`GraphQLValueAsync`] one when the `local` attribute argument is specified. [Enums][4] and
+/// [scalars][5] always implement both.
+///
+/// [`GraphQLValueAsync`]: crate::GraphQLValueAsync
+/// [`Rc`]: std::rc::Rc
+/// [`execute_local`]: crate::execute_local
+/// [4]: https://spec.graphql.org/June2018/#sec-Enums
+/// [5]: https://spec.graphql.org/June2018/#sec-Scalars
+pub trait GraphQLValueAsyncLocal<S = DefaultScalarValue>: GraphQLValue<S>
+where
+    S: ScalarValue,
+{
+    /// Resolves the value of a single field on this [`GraphQLValueAsyncLocal`].
+    ///
+    /// See [`GraphQLValueAsync::resolve_field_async`] for details.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics.
+    ///
+    /// [`GraphQLValueAsync::resolve_field_async`]: crate::GraphQLValueAsync::resolve_field_async
+    fn resolve_field_local<'a>(
+        &'a self,
+        _info: &'a Self::TypeInfo,
+        _field_name: &'a str,
+        _arguments: &'a Arguments<S>,
+        _executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        panic!(
+            "GraphQLValueAsyncLocal::resolve_field_local() must be implemented by objects and \
+             interfaces",
+        );
+    }
+
+    /// Resolves this [`GraphQLValueAsyncLocal`] (being an [interface][1] or an [union][2]) into a
+    /// concrete downstream [object][3] type.
+    ///
+    /// See [`GraphQLValueAsync::resolve_into_type_async`] for details.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics.
+    ///
+    /// [`GraphQLValueAsync::resolve_into_type_async`]: crate::GraphQLValueAsync::resolve_into_type_async
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    /// [2]: https://spec.graphql.org/June2018/#sec-Unions
+    /// [3]: https://spec.graphql.org/June2018/#sec-Objects
+    fn resolve_into_type_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        if self.type_name(info).unwrap() == type_name {
+            self.resolve_local(info, selection_set, executor)
+        } else {
+            panic!(
+                "GraphQLValueAsyncLocal::resolve_into_type_local() must be implemented by unions \
+                 and interfaces",
+            );
+        }
+    }
+
+    /// Resolves the provided `selection_set` against this [`GraphQLValueAsyncLocal`].
+    ///
+    /// See [`GraphQLValueAsync::resolve_async`] for details.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics, if `selection_set` is [`None`].
+    ///
+    /// [`GraphQLValueAsync::resolve_async`]: crate::GraphQLValueAsync::resolve_async
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        if let Some(sel) = selection_set {
+            Box::pin(async move {
+                Ok(resolve_selection_set_into_local(self, info, sel, executor).await)
+            })
+        } else {
+            panic!(
+                "GraphQLValueAsyncLocal::resolve_local() must be implemented by non-object output \
+                 types",
+            );
+        }
+    }
+}
+
+crate::sa::assert_obj_safe!(GraphQLValueAsyncLocal<Context = (), TypeInfo = ()>);
+
+/// Extension of [`GraphQLType`] trait with single-threaded asynchronous queries/mutations
+/// resolvers.
+///
+/// It's automatically implemented for [`GraphQLValueAsyncLocal`] and [`GraphQLType`]
+/// implementors, so doesn't require manual or code-generated implementation.
+pub trait GraphQLTypeAsyncLocal<S = DefaultScalarValue>:
+    GraphQLValueAsyncLocal<S> + GraphQLType<S>
+where
+    S: ScalarValue,
+{
+}
+
+impl<S, T> GraphQLTypeAsyncLocal<S> for T
+where
+    T: GraphQLValueAsyncLocal<S> + GraphQLType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+
+resolve_selection_set_async! {
+    fn resolve_selection_set_into_local, resolve_selection_set_into_local_recursive -> LocalBoxFuture;
+    impl GraphQLValueAsyncLocal { resolve_field_local, resolve_into_type_local }
+    where {
+        S: ScalarValue,
+    }
+}
@@ -2,13 +2,16 @@ use indexmap::IndexMap;
 
 use crate::{
     ast::{Directive, FromInputValue, InputValue, Selection},
-    executor::{ExecutionResult, Executor, Registry, Variables},
+    executor::{catch_panic, ExecutionResult, Executor, Registry, Variables},
+    macros::interface_helpers::InterfaceField,
     parser::Spanning,
     schema::meta::{Argument, MetaType},
     value::{DefaultScalarValue, Object, ScalarValue, Value},
     GraphQLEnum,
 };
 
+use super::selection::{walk_selection_set, Step};
+
 /// GraphQL type kind
 ///
 /// The GraphQL specification defines a number of type kinds - the meta type\
@@ -91,8 +94,6 @@ where
                 if !args.contains_key(arg.name.as_str()) || args[arg.name.as_str()].is_null() {
                     if let Some(ref default_value) = arg.default_value {
                         args.insert(arg.name.as_str(), default_value.clone());
-                    } else {
-                        args.insert(arg.name.as_str(), InputValue::null());
                     }
                 }
             }
@@ -104,18 +105,33 @@ where
     /// Get and convert an argument into the desired type.
     ///
     /// If the argument is found, or a default argument has been provided,
-    /// the `InputValue` will be converted into the type `T`.
+    /// the `InputValue` will be converted into the type `T`. Otherwise, the
+    /// argument is converted with [`FromInputValue::from_implicit_null`].
     ///
-    /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
+    /// Returns `Some` if the type conversion succeeeds.
     pub fn get<T>(&self, key: &str) -> Option<T>
     where
         T: FromInputValue<S>,
     {
+        match self.args.as_ref().and_then(|args| args.get(key)) {
+            Some(value) => value.convert(),
+            None => T::from_implicit_null(),
+        }
+    }
+
+    /// Get an argument as is, without converting it.
+    ///
+    /// Useful for arguments of types built at runtime, which have no Rust
+    /// type to convert them into.
+    pub fn get_input_value(&self, key: &str) -> Option<&InputValue<S>> {
+        self.args.as_ref().and_then(|args| args.get(key))
+    }
+
+    /// Iterates over the provided and defaulted arguments, as is
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'a str, &InputValue<S>)> {
         self.args
-            .as_ref()
-            .and_then(|args| args.get(key))
-            .and_then(InputValue::convert)
+            .iter()
+            .flat_map(|args| args.iter().map(|(&name, value)| (name, value)))
     }
 }
 
@@ -394,6 +410,12 @@ where
     fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
     where
         S: 'r;
+
+    /// The fields of this [`GraphQLType`], if it's an interface defined by
+    /// `graphql_interface!`, which objects implementing it are checked against
+    /// at compile time.
+    #[doc(hidden)]
+    const INTERFACE_FIELDS: &'static [InterfaceField] = &[];
 }
 
 /// Resolver logic for queries'/mutations' selection set.
@@ -413,140 +435,51 @@ where
     T: GraphQLValue<S> + ?Sized,
     S: ScalarValue,
 {
-    let meta_type = executor
-        .schema()
-        .concrete_type_by_name(
-            instance
-                .type_name(info)
-                .expect("Resolving named type's selection set")
-                .as_ref(),
-        )
-        .expect("Type not found in schema");
-
-    for selection in selection_set {
-        match *selection {
-            Selection::Field(Spanning {
-                item: ref f,
-                start: ref start_pos,
-                ..
-            }) => {
-                if is_excluded(&f.directives, executor.variables()) {
-                    continue;
-                }
-
-                let response_name = f.alias.as_ref().unwrap_or(&f.name).item;
-
-                if f.name.item == "__typename" {
-                    result.add_field(
-                        response_name,
-                        Value::scalar(instance.concrete_type_name(executor.context(), info)),
-                    );
-                    continue;
-                }
-
-                let meta_field = meta_type.field_by_name(f.name.item).unwrap_or_else(|| {
-                    panic!(format!(
-                        "Field {} not found on type {:?}",
-                        f.name.item,
-                        meta_type.name()
-                    ))
+    for step in walk_selection_set(instance, info, selection_set, executor) {
+        match step {
+            Step::Typename(response_name, value) => result.merge_field(response_name, value),
+            Step::Field(field) => {
+                let started = field.start();
+                let field_result = field.charge().unwrap_or_else(|| {
+                    field.admit().and_then(|()| {
+                        catch_panic(executor.schema().catch_panics, || {
+                            instance.resolve_field(
+                                info,
+                                field.name,
+                                &field.arguments,
+                                &field.executor,
+                            )
+                        })
+                    })
                 });
 
-                let exec_vars = executor.variables();
-
-                let sub_exec = executor.field_sub_executor(
-                    response_name,
-                    f.name.item,
-                    *start_pos,
-                    f.selection_set.as_ref().map(|v| &v[..]),
-                );
-
-                let field_result = instance.resolve_field(
-                    info,
-                    f.name.item,
-                    &Arguments::new(
-                        f.arguments.as_ref().map(|m| {
-                            m.item
-                                .iter()
-                                .map(|&(ref k, ref v)| {
-                                    (k.item, v.item.clone().into_const(exec_vars))
-                                })
-                                .collect()
-                        }),
-                        &meta_field.arguments,
-                    ),
-                    &sub_exec,
-                );
-
-                match field_result {
-                    Ok(Value::Null) if meta_field.field_type.is_non_null() => return false,
-                    Ok(v) => merge_key_into(result, response_name, v),
-                    Err(e) => {
-                        sub_exec.push_error_at(e, *start_pos);
-
-                        if meta_field.field_type.is_non_null() {
-                            return false;
-                        }
-
-                        result.add_field(response_name, Value::null());
-                    }
+                match field.finish(started, field_result) {
+                    Some(value) => result.merge_field(field.response_name, value),
+                    None => return false,
                 }
             }
-            Selection::FragmentSpread(Spanning {
-                item: ref spread, ..
-            }) => {
-                if is_excluded(&spread.directives, executor.variables()) {
-                    continue;
-                }
-
-                let fragment = &executor
-                    .fragment_by_name(spread.name.item)
-                    .expect("Fragment could not be found");
-
-                if !resolve_selection_set_into(
-                    instance,
-                    info,
-                    &fragment.selection_set[..],
-                    executor,
-                    result,
-                ) {
+            Step::Spread(selection_set) => {
+                if !resolve_selection_set_into(instance, info, selection_set, executor, result) {
                     return false;
                 }
             }
-            Selection::InlineFragment(Spanning {
-                item: ref fragment,
-                start: ref start_pos,
-                ..
-            }) => {
-                if is_excluded(&fragment.directives, executor.variables()) {
-                    continue;
-                }
-
-                let sub_exec = executor.type_sub_executor(
-                    fragment.type_condition.as_ref().map(|c| c.item),
-                    Some(&fragment.selection_set[..]),
-                );
-
-                if let Some(ref type_condition) = fragment.type_condition {
+            Step::InlineFragment(fragment) => {
+                if let Some(type_condition) = fragment.type_condition {
                     let sub_result = instance.resolve_into_type(
                         info,
-                        type_condition.item,
-                        Some(&fragment.selection_set[..]),
-                        &sub_exec,
+                        type_condition,
+                        Some(fragment.selection_set),
+                        &fragment.executor,
                     );
 
-                    if let Ok(Value::Object(object)) = sub_result {
-                        for (k, v) in object {
-                            merge_key_into(result, &k, v);
-                        }
-                    } else if let Err(e) = sub_result {
-                        sub_exec.push_error_at(e, *start_pos);
+                    for (k, v) in fragment.fields(sub_result) {
+                        result.merge_field(k, v);
                     }
                 } else if !resolve_selection_set_into(
                     instance,
                     info,
-                    &fragment.selection_set[..],
-                    &sub_exec,
+                    fragment.selection_set,
+                    &fragment.executor,
                     result,
                 ) {
                     return false;
@@ -558,7 +491,13 @@ where
     true
 }
 
-pub(super) fn is_excluded<S>(
+/// Whether the `@skip` and `@include` directives exclude a selection, their
+/// conditions evaluated with the variables of the operation, which hold the
+/// defaults of the variables not provided
+///
+/// Conditions which can't be evaluated, like the ones of other directives,
+/// don't exclude the selection.
+pub(crate) fn is_excluded<S>(
     directives: &Option<Vec<Spanning<Directive<S>>>>,
     vars: &Variables<S>,
 ) -> bool
@@ -577,7 +516,7 @@ where
                 .flat_map(|m| m.item.get("if"))
                 .flat_map(|v| v.item.clone().into_const(vars).convert())
                 .next()
-                .unwrap();
+                .unwrap_or(directive.name.item == "include");
 
             if (directive.name.item == "skip" && condition)
                 || (directive.name.item == "include" && !condition)
@@ -588,47 +527,3 @@ where
     }
     false
 }
-
-/// Merges `response_name`/`value` pair into `result`
-pub(crate) fn merge_key_into<S>(result: &mut Object<S>, response_name: &str, value: Value<S>) {
-    if let Some(&mut (_, ref mut e)) = result
-        .iter_mut()
-        .find(|&&mut (ref key, _)| key == response_name)
-    {
-        match *e {
-            Value::Object(ref mut dest_obj) => {
-                if let Value::Object(src_obj) = value {
-                    merge_maps(dest_obj, src_obj);
-                }
-            }
-            Value::List(ref mut dest_list) => {
-                if let Value::List(src_list) = value {
-                    dest_list
-                        .iter_mut()
-                        .zip(src_list.into_iter())
-                        .for_each(|(d, s)| {
-                            if let Value::Object(ref mut d_obj) = *d {
-                                if let Value::Object(s_obj) = s {
-                                    merge_maps(d_obj, s_obj);
-                                }
-                            }
-                        });
-                }
-            }
-            _ => {}
-        }
-        return;
-    }
-    result.add_field(response_name, value);
-}
-
-/// Merges `src` object's fields into `dest`
-fn merge_maps<S>(dest: &mut Object<S>, src: Object<S>) {
-    for (key, value) in src {
-        if dest.contains_field(&key) {
-            merge_key_into(dest, &key, value);
-        } else {
-            dest.add_field(key, value);
-        }
-    }
-}
@@ -4,6 +4,7 @@ use crate::{
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
+        async_local::GraphQLValueAsyncLocal,
         base::{GraphQLType, GraphQLValue},
     },
     value::{ScalarValue, Value},
@@ -75,6 +76,28 @@ where
     }
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for Option<T>
+where
+    T: GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, ExecutionResult<S>> {
+        let f = async move {
+            let value = match self {
+                Some(obj) => executor.resolve_into_value_local(info, obj).await,
+                None => Value::null(),
+            };
+            Ok(value)
+        };
+        Box::pin(f)
+    }
+}
+
 impl<S, T> FromInputValue<S> for Option<T>
 where
     T: FromInputValue<S>,
@@ -158,6 +181,22 @@ where
     }
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for Vec<T>
+where
+    T: GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_local(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
 impl<T, S> FromInputValue<S> for Vec<T>
 where
     T: FromInputValue<S>,
@@ -247,6 +286,22 @@ where
     }
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for [T]
+where
+    T: GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_local(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
 impl<'a, T, S> ToInputValue<S> for &'a [T]
 where
     T: ToInputValue<S>,
@@ -257,6 +312,89 @@ where
     }
 }
 
+impl<S, T, const N: usize> GraphQLType<S> for [T; N]
+where
+    S: ScalarValue,
+    T: GraphQLType<S>,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+}
+
+impl<S, T, const N: usize> GraphQLValue<S> for [T; N]
+where
+    S: ScalarValue,
+    T: GraphQLValue<S>,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        _: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        resolve_into_list(executor, info, self.iter())
+    }
+}
+
+impl<S, T, const N: usize> GraphQLValueAsync<S> for [T; N]
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_async(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
+impl<S, T, const N: usize> GraphQLValueAsyncLocal<S> for [T; N]
+where
+    T: GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_local(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
+impl<T, S, const N: usize> ToInputValue<S> for [T; N]
+where
+    T: ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(T::to_input_value).collect())
+    }
+}
+
 fn resolve_into_list<'t, S, T, I>(
     executor: &Executor<T::Context, S>,
     info: &T::TypeInfo,
@@ -267,6 +405,10 @@ where
     I: Iterator<Item = &'t T> + ExactSizeIterator,
     T: GraphQLValue<S> + ?Sized + 't,
 {
+    if let Some(res) = executor.charge_values(iter.len()) {
+        return res;
+    }
+
     let stop_on_null = executor
         .current_type()
         .list_contents()
@@ -301,6 +443,10 @@ where
     use futures::stream::{FuturesOrdered, StreamExt as _};
     use std::iter::FromIterator;
 
+    if let Some(res) = executor.charge_values(items.len()) {
+        return res;
+    }
+
     let stop_on_null = executor
         .current_type()
         .list_contents()
@@ -320,3 +466,40 @@ where
 
     Ok(Value::list(values))
 }
+
+async fn resolve_into_list_local<'a, 't, S, T, I>(
+    executor: &'a Executor<'a, 'a, T::Context, S>,
+    info: &'a T::TypeInfo,
+    items: I,
+) -> ExecutionResult<S>
+where
+    I: Iterator<Item = &'t T> + ExactSizeIterator,
+    T: GraphQLValueAsyncLocal<S> + ?Sized + 't,
+    S: ScalarValue,
+{
+    use futures::stream::{FuturesOrdered, StreamExt as _};
+    use std::iter::FromIterator;
+
+    if let Some(res) = executor.charge_values(items.len()) {
+        return res;
+    }
+
+    let stop_on_null = executor
+        .current_type()
+        .list_contents()
+        .expect("Current type is not a list type")
+        .is_non_null();
+
+    let iter = items.map(|it| async move { executor.resolve_into_value_local(info, it).await });
+    let mut futures = FuturesOrdered::from_iter(iter);
+
+    let mut values = Vec::with_capacity(futures.len());
+    while let Some(value) = futures.next().await {
+        if stop_on_null && value.is_null() {
+            return Ok(value);
+        }
+        values.push(value);
+    }
+
+    Ok(Value::list(values))
+}
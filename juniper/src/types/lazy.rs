@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::{
+    ast::Selection,
+    executor::{ExecutionResult, Executor, Registry},
+    schema::meta::{MetaType, PlaceholderMeta},
+    types::{
+        async_await::GraphQLValueAsync,
+        async_local::GraphQLValueAsyncLocal,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        marker::IsOutputType,
+    },
+    value::ScalarValue,
+    BoxFuture, LocalBoxFuture,
+};
+
+/// A value built on demand, when a query selects it
+///
+/// `Lazy` only refers to the wrapped type by name: registering it registers
+/// the wrapped type as itself, and its name is known without building a
+/// value. So mutually recursive types, even from different crates, may refer
+/// to each other through `Lazy` without boxing, and without building the
+/// whole graph of values up front:
+///
+/// ```
+/// # use juniper::{GraphQLObject, Lazy};
+/// #[derive(GraphQLObject)]
+/// struct Author {
+///     name: String,
+///     books: Vec<Lazy<Book>>,
+/// }
+///
+/// #[derive(GraphQLObject)]
+/// struct Book {
+///     title: String,
+///     author: Lazy<Author>,
+/// }
+/// ```
+///
+/// The closure is called each time the value is resolved, and to find the
+/// concrete type of an interface or a union.
+pub struct Lazy<T> {
+    init: Box<dyn Fn() -> T + Send + Sync>,
+}
+
+impl<T> Lazy<T> {
+    /// Wraps the closure building the value
+    pub fn new<F>(init: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Lazy {
+            init: Box::new(init),
+        }
+    }
+
+    /// Builds the value
+    pub fn get(&self) -> T {
+        (self.init)()
+    }
+}
+
+impl<T> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Lazy(..)")
+    }
+}
+
+impl<S, T> GraphQLType<S> for Lazy<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    // Unnamed, as the wrapped type is registered as itself instead.
+    fn name(_: &Self::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        MetaType::Placeholder(PlaceholderMeta {
+            of_type: registry.get_type::<T>(info),
+        })
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Lazy<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        T::name(info)
+    }
+
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        self.get().concrete_type_name(context, info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.get()
+            .resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.get().resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.get().resolve(info, selection_set, executor)
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for Lazy<T>
+where
+    T: GraphQLType<S> + GraphQLValueAsync<S> + Send,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        let value = self.get();
+        Box::pin(async move { value.resolve_async(info, selection_set, executor).await })
+    }
+}
+
+impl<S, T> GraphQLValueAsyncLocal<S> for Lazy<T>
+where
+    T: GraphQLType<S> + GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        let value = self.get();
+        Box::pin(async move { value.resolve_local(info, selection_set, executor).await })
+    }
+}
+
+impl<S, T> IsOutputType<S> for Lazy<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
@@ -5,7 +5,9 @@
 //! traits are used. Encountering an error where one of these traits
 //! is involved implies that the construct is not valid in GraphQL.
 
-use crate::{GraphQLType, ScalarValue};
+use std::rc::Rc;
+
+use crate::{GraphQLType, ScalarValue, SharedString};
 
 /// Maker object for GraphQL objects.
 ///
@@ -104,6 +106,13 @@ where
 {
 }
 
+impl<S, T, const N: usize> IsOutputType<S> for [T; N]
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
 impl<S, T> IsInputType<S> for Vec<T>
 where
     T: IsInputType<S>,
@@ -144,5 +153,15 @@ where
 {
 }
 
+impl<S, T> IsOutputType<S> for Rc<T>
+where
+    T: IsOutputType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+
 impl<'a, S> IsInputType<S> for &str where S: ScalarValue {}
 impl<'a, S> IsOutputType<S> for &str where S: ScalarValue {}
+
+impl<S> IsInputType<S> for SharedString where S: ScalarValue {}
+impl<S> IsOutputType<S> for SharedString where S: ScalarValue {}
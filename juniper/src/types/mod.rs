@@ -1,9 +1,14 @@
+#[macro_use]
+pub(crate) mod selection;
+
 pub mod async_await;
+pub mod async_local;
 pub mod base;
 pub mod containers;
+pub mod lazy;
 pub mod marker;
 pub mod name;
+pub mod nullable;
 pub mod pointers;
 pub mod scalars;
 pub mod subscriptions;
-pub mod utilities;
@@ -0,0 +1,234 @@
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        async_local::GraphQLValueAsyncLocal,
+        base::{GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+    },
+    value::{ScalarValue, Value},
+};
+
+/// A nullable value telling an omitted value apart from an explicit null
+///
+/// Use it in place of `Option` for arguments and input object fields when
+/// "not provided" and "set to null" have different meanings, e.g. in a mutation
+/// updating only the fields it's given:
+///
+/// ```
+/// # use juniper::{GraphQLInputObject, Nullable};
+/// #[derive(GraphQLInputObject)]
+/// struct UserPatch {
+///     // `Absent` keeps the nickname, `Null` removes it.
+///     nickname: Nullable<String>,
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Nullable<T> {
+    /// No value was provided
+    #[default]
+    Absent,
+
+    /// The value was explicitly set to null
+    Null,
+
+    /// A value was provided
+    Some(T),
+}
+
+impl<T> Nullable<T> {
+    /// Returns `true` if no value was provided
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Nullable::Absent)
+    }
+
+    /// Returns `true` if the value was explicitly set to null
+    pub fn is_null(&self) -> bool {
+        matches!(self, Nullable::Null)
+    }
+
+    /// Returns `true` if a value was provided
+    pub fn is_some(&self) -> bool {
+        matches!(self, Nullable::Some(_))
+    }
+
+    /// Converts from `&Nullable<T>` to `Nullable<&T>`
+    pub fn as_ref(&self) -> Nullable<&T> {
+        match *self {
+            Nullable::Absent => Nullable::Absent,
+            Nullable::Null => Nullable::Null,
+            Nullable::Some(ref v) => Nullable::Some(v),
+        }
+    }
+
+    /// Maps the provided value, if any, keeping absent and null values as is
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Nullable<U> {
+        match self {
+            Nullable::Absent => Nullable::Absent,
+            Nullable::Null => Nullable::Null,
+            Nullable::Some(v) => Nullable::Some(f(v)),
+        }
+    }
+
+    /// Returns the provided value, if any, losing the difference between
+    /// absent and null values
+    pub fn some(self) -> Option<T> {
+        match self {
+            Nullable::Some(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `None` if no value was provided, `Some(None)` if it was
+    /// explicitly set to null, and `Some(Some(v))` otherwise
+    pub fn explicit(self) -> Option<Option<T>> {
+        match self {
+            Nullable::Absent => None,
+            Nullable::Null => Some(None),
+            Nullable::Some(v) => Some(Some(v)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    fn from(o: Option<T>) -> Self {
+        match o {
+            Some(v) => Nullable::Some(v),
+            None => Nullable::Null,
+        }
+    }
+}
+
+impl<S, T> GraphQLType<S> for Nullable<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_nullable_type::<T>(info).into_meta()
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Nullable<T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S>,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        _: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match *self {
+            Nullable::Some(ref obj) => executor.resolve(info, obj),
+            _ => Ok(Value::null()),
+        }
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for Nullable<T>
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+        let f = async move {
+            let value = match self {
+                Nullable::Some(obj) => executor.resolve_into_value_async(info, obj).await,
+                _ => Value::null(),
+            };
+            Ok(value)
+        };
+        Box::pin(f)
+    }
+}
+
+impl<S, T> GraphQLValueAsyncLocal<S> for Nullable<T>
+where
+    T: GraphQLValueAsyncLocal<S>,
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, ExecutionResult<S>> {
+        let f = async move {
+            let value = match self {
+                Nullable::Some(obj) => executor.resolve_into_value_local(info, obj).await,
+                _ => Value::null(),
+            };
+            Ok(value)
+        };
+        Box::pin(f)
+    }
+}
+
+impl<S, T> FromInputValue<S> for Nullable<T>
+where
+    T: FromInputValue<S>,
+    S: ScalarValue,
+{
+    fn from_input_value(v: &InputValue<S>) -> Option<Nullable<T>> {
+        match v {
+            &InputValue::Null => Some(Nullable::Null),
+            v => v.convert().map(Nullable::Some),
+        }
+    }
+
+    fn from_implicit_null() -> Option<Nullable<T>> {
+        Some(Nullable::Absent)
+    }
+}
+
+impl<S, T> ToInputValue<S> for Nullable<T>
+where
+    T: ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        match *self {
+            Nullable::Some(ref v) => v.to_input_value(),
+            _ => InputValue::null(),
+        }
+    }
+}
+
+impl<S, T> IsInputType<S> for Nullable<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T> IsOutputType<S> for Nullable<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
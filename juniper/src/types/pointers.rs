@@ -1,15 +1,17 @@
-use std::{fmt, sync::Arc};
+use std::{fmt, rc::Rc, sync::Arc};
 
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
     executor::{ExecutionResult, Executor, Registry},
+    macros::interface_helpers::InterfaceField,
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
+        async_local::GraphQLValueAsyncLocal,
         base::{Arguments, GraphQLType, GraphQLValue},
     },
     value::ScalarValue,
-    BoxFuture,
+    BoxFuture, LocalBoxFuture,
 };
 
 impl<S, T> GraphQLType<S> for Box<T>
@@ -27,6 +29,8 @@ where
     {
         T::meta(info, registry)
     }
+
+    const INTERFACE_FIELDS: &'static [InterfaceField] = T::INTERFACE_FIELDS;
 }
 
 impl<S, T> GraphQLValue<S> for Box<T>
@@ -41,6 +45,10 @@ where
         (**self).type_name(info)
     }
 
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        (**self).concrete_type_name(context, info)
+    }
+
     fn resolve_into_type(
         &self,
         info: &Self::TypeInfo,
@@ -88,6 +96,41 @@ where
     }
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for Box<T>
+where
+    T: GraphQLValueAsyncLocal<S> + ?Sized,
+    S: ScalarValue,
+{
+    fn resolve_field_local<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'b, ExecutionResult<S>> {
+        (**self).resolve_field_local(info, field_name, arguments, executor)
+    }
+
+    fn resolve_into_type_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_into_type_local(info, type_name, selection_set, executor)
+    }
+
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_local(info, selection_set, executor)
+    }
+}
+
 impl<T, S> FromInputValue<S> for Box<T>
 where
     S: ScalarValue,
@@ -126,6 +169,8 @@ where
     {
         T::meta(info, registry)
     }
+
+    const INTERFACE_FIELDS: &'static [InterfaceField] = T::INTERFACE_FIELDS;
 }
 
 impl<'e, S, T> GraphQLValue<S> for &'e T
@@ -140,6 +185,10 @@ where
         (**self).type_name(info)
     }
 
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        (**self).concrete_type_name(context, info)
+    }
+
     fn resolve_into_type(
         &self,
         info: &Self::TypeInfo,
@@ -197,6 +246,41 @@ where
     }
 }
 
+impl<'e, S, T> GraphQLValueAsyncLocal<S> for &'e T
+where
+    T: GraphQLValueAsyncLocal<S> + ?Sized,
+    S: ScalarValue,
+{
+    fn resolve_field_local<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'b, ExecutionResult<S>> {
+        (**self).resolve_field_local(info, field_name, arguments, executor)
+    }
+
+    fn resolve_into_type_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_into_type_local(info, type_name, selection_set, executor)
+    }
+
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_local(info, selection_set, executor)
+    }
+}
+
 impl<'a, T, S> ToInputValue<S> for &'a T
 where
     S: fmt::Debug,
@@ -222,6 +306,8 @@ where
     {
         T::meta(info, registry)
     }
+
+    const INTERFACE_FIELDS: &'static [InterfaceField] = T::INTERFACE_FIELDS;
 }
 
 impl<S, T> GraphQLValue<S> for Arc<T>
@@ -236,6 +322,10 @@ where
         (**self).type_name(info)
     }
 
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        (**self).concrete_type_name(context, info)
+    }
+
     fn resolve_into_type(
         &self,
         info: &Self::TypeInfo,
@@ -283,6 +373,41 @@ where
     }
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for Arc<T>
+where
+    T: GraphQLValueAsyncLocal<S> + ?Sized,
+    S: ScalarValue,
+{
+    fn resolve_field_local<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'b, ExecutionResult<S>> {
+        (**self).resolve_field_local(info, field_name, arguments, executor)
+    }
+
+    fn resolve_into_type_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_into_type_local(info, type_name, selection_set, executor)
+    }
+
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_local(info, selection_set, executor)
+    }
+}
+
 impl<T, S> ToInputValue<S> for Arc<T>
 where
     S: fmt::Debug,
@@ -292,3 +417,113 @@ where
         (**self).to_input_value()
     }
 }
+
+impl<S, T> GraphQLType<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + ?Sized,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+
+    const INTERFACE_FIELDS: &'static [InterfaceField] = T::INTERFACE_FIELDS;
+}
+
+impl<S, T> GraphQLValue<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S> + ?Sized,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        (**self).type_name(info)
+    }
+
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        (**self).concrete_type_name(context, info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<S, T> GraphQLValueAsyncLocal<S> for Rc<T>
+where
+    T: GraphQLValueAsyncLocal<S> + ?Sized,
+    S: ScalarValue,
+{
+    fn resolve_field_local<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'b, ExecutionResult<S>> {
+        (**self).resolve_field_local(info, field_name, arguments, executor)
+    }
+
+    fn resolve_into_type_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        type_name: &str,
+        selection_set: Option<&'a [Selection<'a, S>]>,
+        executor: &'a Executor<'a, 'a, Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_into_type_local(info, type_name, selection_set, executor)
+    }
+
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> LocalBoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_local(info, selection_set, executor)
+    }
+}
+
+impl<T, S> ToInputValue<S> for Rc<T>
+where
+    S: fmt::Debug,
+    T: ToInputValue<S>,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        (**self).to_input_value()
+    }
+}
@@ -1,18 +1,34 @@
-use std::{char, convert::From, marker::PhantomData, ops::Deref, rc::Rc, thread::JoinHandle, u32};
+use std::{
+    char,
+    convert::{From, TryFrom},
+    marker::PhantomData,
+    num::{NonZeroI32, NonZeroU32},
+    ops::Deref,
+    rc::Rc,
+    sync::Arc,
+    thread::JoinHandle,
+    u32,
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ast::{InputValue, Selection, ToInputValue},
+    ast::{FromInputError, FromInputValue, InputValue, Selection, ToInputValue},
     executor::{ExecutionResult, Executor, Registry},
-    parser::{LexerError, ParseError, ScalarToken, Token},
+    parser::{
+        block_string_value, combine_surrogates, is_high_surrogate, LexerError, ParseError,
+        ScalarToken, Token,
+    },
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
+        async_local::GraphQLValueAsyncLocal,
         base::{GraphQLType, GraphQLValue},
         subscriptions::GraphQLSubscriptionValue,
     },
-    value::{ParseScalarResult, ScalarValue, Value},
+    value::{
+        parse_token, ParseScalarResult, ParseScalarValue, ScalarTokenParser, ScalarValue, Value,
+    },
 };
 
 /// An ID as defined by the GraphQL specification
@@ -63,7 +79,10 @@ where
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
         match value {
-            ScalarToken::String(value) | ScalarToken::Int(value) => Ok(S::from(value.to_owned())),
+            ScalarToken::String(value) | ScalarToken::Int(value) => {
+                Ok(S::from_string(value.to_owned()))
+            }
+            ScalarToken::BlockString(raw) => Ok(S::from_string(block_string_value(raw))),
             _ => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
         }
     }
@@ -86,7 +105,9 @@ where
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
-        if let ScalarToken::String(value) = value {
+        if let ScalarToken::BlockString(raw) = value {
+            Ok(S::from_string(block_string_value(raw)))
+        } else if let ScalarToken::String(value) = value {
             let mut ret = String::with_capacity(value.len());
             let mut char_iter = value.chars();
             while let Some(ch) = char_iter.next() {
@@ -131,7 +152,7 @@ where
                     }
                 }
             }
-            Ok(ret.into())
+            Ok(S::from_string(ret))
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -139,6 +160,29 @@ where
 }
 
 fn parse_unicode_codepoint<'a, I>(char_iter: &mut I) -> Result<char, ParseError<'a>>
+where
+    I: Iterator<Item = char>,
+{
+    let code_point = parse_code_unit(char_iter)?;
+    let unknown_escape = |code_point| {
+        ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
+            "\\u{:X}",
+            code_point
+        )))
+    };
+    let code_point = if is_high_surrogate(code_point) {
+        let low = match (char_iter.next(), char_iter.next()) {
+            (Some('\\'), Some('u')) => parse_code_unit(char_iter)?,
+            _ => return Err(unknown_escape(code_point)),
+        };
+        combine_surrogates(code_point, low).ok_or_else(|| unknown_escape(code_point))?
+    } else {
+        code_point
+    };
+    char::from_u32(code_point).ok_or_else(|| unknown_escape(code_point))
+}
+
+fn parse_code_unit<'a, I>(char_iter: &mut I) -> Result<u32, ParseError<'a>>
 where
     I: Iterator<Item = char>,
 {
@@ -183,13 +227,7 @@ where
                     s
                 })
         })?;
-    let code_point = u32::from_str_radix(&escaped_code_point, 16).map_err(|_| {
-        ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
-            "\\u{}",
-            escaped_code_point
-        )))
-    })?;
-    char::from_u32(code_point).ok_or_else(|| {
+    u32::from_str_radix(&escaped_code_point, 16).map_err(|_| {
         ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
             "\\u{}",
             escaped_code_point
@@ -249,6 +287,21 @@ where
     }
 }
 
+impl<S> GraphQLValueAsyncLocal<S> for str
+where
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, crate::ExecutionResult<S>> {
+        use futures::future;
+        Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+    }
+}
+
 impl<'a, S> ToInputValue<S> for &'a str
 where
     S: ScalarValue,
@@ -258,6 +311,133 @@ where
     }
 }
 
+/// A `String` shared by reference counting
+///
+/// Resolving it passes the shared string to
+/// [`ScalarValue::from_shared_string`](../trait.ScalarValue.html#method.from_shared_string),
+/// so scalar values storing `Arc<str>` don't copy it, e.g. for large texts
+/// kept in memory and returned by many requests.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SharedString(Arc<str>);
+
+impl SharedString {
+    /// Returns the shared string
+    pub fn into_inner(self) -> Arc<str> {
+        self.0
+    }
+}
+
+impl From<Arc<str>> for SharedString {
+    fn from(s: Arc<str>) -> Self {
+        SharedString(s)
+    }
+}
+
+impl From<String> for SharedString {
+    fn from(s: String) -> Self {
+        SharedString(s.into())
+    }
+}
+
+impl<'a> From<&'a str> for SharedString {
+    fn from(s: &'a str) -> Self {
+        SharedString(s.into())
+    }
+}
+
+impl Deref for SharedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<S> GraphQLType<S> for SharedString
+where
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<&'static str> {
+        Some("String")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_scalar_type::<String>(&()).into_meta()
+    }
+}
+
+impl<S> GraphQLValue<S> for SharedString
+where
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(Value::scalar(S::from_shared_string(self.0.clone())))
+    }
+}
+
+impl<S> GraphQLValueAsync<S> for SharedString
+where
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, crate::ExecutionResult<S>> {
+        use futures::future;
+        Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+    }
+}
+
+impl<S> GraphQLValueAsyncLocal<S> for SharedString
+where
+    S: ScalarValue,
+{
+    fn resolve_local<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::LocalBoxFuture<'a, crate::ExecutionResult<S>> {
+        use futures::future;
+        Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+    }
+}
+
+impl<S> FromInputValue<S> for SharedString
+where
+    S: ScalarValue,
+{
+    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+        v.as_scalar().and_then(ScalarValue::as_str).map(Into::into)
+    }
+}
+
+impl<S> ToInputValue<S> for SharedString
+where
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::scalar(S::from_shared_string(self.0.clone()))
+    }
+}
+
 #[crate::graphql_scalar(name = "Boolean")]
 impl<S> GraphQLScalar for bool
 where
@@ -300,7 +480,7 @@ where
         if let ScalarToken::Int(v) = value {
             v.parse()
                 .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
-                .map(|s: i32| s.into())
+                .map(S::from_int)
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -328,9 +508,75 @@ where
             ScalarToken::Int(v) | ScalarToken::Float(v) => v
                 .parse()
                 .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
-                .map(|s: f64| s.into()),
-            ScalarToken::String(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+                .map(S::from_float),
+            ScalarToken::String(_) | ScalarToken::BlockString(_) => {
+                Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+            }
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "NonZeroInt", description = "An `Int` that can't be zero")]
+impl<S> GraphQLScalar for NonZeroI32
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.get())
+    }
+
+    fn try_from_input_value(v: &InputValue) -> Result<NonZeroI32, FromInputError> {
+        let i = match *v {
+            InputValue::Scalar(ref i) => i.as_int(),
+            _ => None,
         }
+        .ok_or_else(FromInputError::invalid)?;
+        NonZeroI32::new(i).ok_or_else(|| FromInputError::new("must not be zero"))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <i32 as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+/// `u32` values above `i32::MAX` don't fit into an `Int`, so they are resolved
+/// as, and accepted from whole `Float`s.
+#[crate::graphql_scalar(
+    name = "PositiveInt",
+    description = "An `Int` greater than zero, or a whole `Float` for values above 2^31 - 1"
+)]
+impl<S> GraphQLScalar for NonZeroU32
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        match i32::try_from(self.get()) {
+            Ok(i) => Value::scalar(i),
+            Err(_) => Value::scalar(f64::from(self.get())),
+        }
+    }
+
+    fn try_from_input_value(v: &InputValue) -> Result<NonZeroU32, FromInputError> {
+        let scalar = match *v {
+            InputValue::Scalar(ref s) => s,
+            _ => return Err(FromInputError::invalid()),
+        };
+        let u = match (scalar.as_int(), scalar.as_float()) {
+            (Some(i), _) => u32::try_from(i).ok(),
+            (None, Some(f)) if f.fract() == 0.0 && f >= 0.0 && f <= f64::from(u32::MAX) => {
+                Some(f as u32)
+            }
+            (None, Some(_)) => None,
+            (None, None) => return Err(FromInputError::invalid()),
+        };
+        u.and_then(NonZeroU32::new)
+            .ok_or_else(|| FromInputError::new("must be a whole number between 1 and 4294967295"))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        parse_token::<i32, S>()
+            .or(parse_token::<f64, S>())
+            .parse(value)
     }
 }
 
@@ -388,6 +634,8 @@ where
 {
 }
 
+impl<S, T> GraphQLValueAsyncLocal<S> for EmptyMutation<T> where S: ScalarValue {}
+
 impl<T> Default for EmptyMutation<T> {
     #[inline]
     fn default() -> Self {
@@ -459,8 +707,8 @@ impl<T> Default for EmptySubscription<T> {
 mod tests {
     use super::{EmptyMutation, EmptySubscription, ID};
     use crate::{
-        parser::ScalarToken,
-        value::{DefaultScalarValue, ParseScalarValue},
+        parser::{ParseError, ScalarToken, ScalarTokenKind, Token},
+        value::{parse_token, DefaultScalarValue, ParseScalarValue, ScalarTokenParser},
     };
 
     #[test]
@@ -503,6 +751,43 @@ mod tests {
             r#"unicode \u1234\u5678\u90AB\uCDEF"#,
             "unicode \u{1234}\u{5678}\u{90ab}\u{cdef}",
         );
+        parse_string(r#"smile \uD83D\uDE00"#, "smile \u{1f600}");
+    }
+
+    #[test]
+    fn parse_block_strings() {
+        let s = <String as ParseScalarValue<DefaultScalarValue>>::from_str(
+            ScalarToken::BlockString("\n    Block \\n\r\n      \\\"\"\"string\"\"\"\n  "),
+        );
+        assert_eq!(
+            s,
+            Ok(DefaultScalarValue::String(
+                "Block \\n\n  \"\"\"string\"\"\"".into()
+            )),
+        );
+    }
+
+    #[test]
+    fn combines_token_parsers() {
+        let parser = parse_token::<i32, DefaultScalarValue>().or(parse_token::<String, _>());
+
+        assert_eq!(
+            parser.parse_kind(ScalarToken::Int("300")),
+            Ok((DefaultScalarValue::Int(300), ScalarTokenKind::Int)),
+        );
+        assert_eq!(
+            parser.parse_kind(ScalarToken::String("5m")),
+            Ok((
+                DefaultScalarValue::String("5m".into()),
+                ScalarTokenKind::String
+            )),
+        );
+        assert_eq!(
+            parser.parse(ScalarToken::Float("1.5")),
+            Err(ParseError::UnexpectedToken(Token::Scalar(
+                ScalarToken::Float("1.5"),
+            ))),
+        );
     }
 
     #[test]
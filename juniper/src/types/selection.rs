@@ -0,0 +1,576 @@
+//! Walking of queries'/mutations' selection sets, shared by the synchronous,
+//! asynchronous and local resolvers so they only differ in how fields are
+//! resolved.
+
+use std::slice;
+
+use crate::{
+    ast::Selection,
+    auth,
+    executor::{ExecutionResult, Executor, FieldError, FieldMetrics, SlowFieldTimer},
+    messages::Message,
+    parser::{SourcePosition, Spanning},
+    schedule::ScheduledField,
+    schema::meta::MetaType,
+    validation::{pagination, Pagination},
+    value::{Object, ScalarValue, Value},
+};
+
+use super::base::{is_excluded, Arguments, GraphQLValue};
+
+/// Step of the resolution of a selection set
+pub(crate) enum Step<'s, 'a, CtxT, S> {
+    /// `__typename` field, with its value
+    Typename(&'a str, Value<S>),
+
+    /// Field to resolve
+    Field(FieldStep<'s, 'a, CtxT, S>),
+
+    /// Selections of a fragment spread, to resolve on the same object
+    Spread(&'s [Selection<'a, S>]),
+
+    /// Inline fragment to resolve
+    InlineFragment(FragmentStep<'s, 'a, CtxT, S>),
+}
+
+/// Field of a selection set, with its arguments and sub executor
+pub(crate) struct FieldStep<'s, 'a, CtxT, S> {
+    pub(crate) response_name: &'a str,
+    pub(crate) name: &'a str,
+    pub(crate) arguments: Arguments<'s, S>,
+    pub(crate) executor: Executor<'s, 'a, CtxT, S>,
+    pub(crate) serial: bool,
+    pub(crate) priority: i32,
+    authorized: bool,
+    pagination_limit: Option<i32>,
+    type_name: Option<&'s str>,
+    is_non_null: bool,
+    location: SourcePosition,
+}
+
+/// Measures of a field being resolved
+pub(crate) struct StartedField {
+    metrics: FieldMetrics,
+    slow_field_timer: SlowFieldTimer,
+}
+
+impl<'s, 'a, CtxT, S> FieldStep<'s, 'a, CtxT, S>
+where
+    S: ScalarValue,
+{
+    /// Fails the field if it's denied to the operation, see the
+    /// [`auth`](../../auth/index.html) module, or if it's required to be
+    /// paginated and its arguments aren't within the limit
+    pub(crate) fn admit(&self) -> Result<(), FieldError<S>> {
+        if !self.authorized {
+            return Err(auth::forbidden());
+        }
+
+        let limit = match self.pagination_limit {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let message = match pagination(limit, |name| self.arguments.get_input_value(name)) {
+            Pagination::Paginated => return Ok(()),
+            Pagination::Unpaginated => Message::UnpaginatedField {
+                field: self.name,
+                type_name: self.type_name.unwrap_or_default(),
+            },
+            Pagination::AboveLimit(argument) => Message::PaginationAboveLimit {
+                argument,
+                field: self.name,
+                limit,
+            },
+        };
+        Err(FieldError::new(
+            self.executor.schema().messages.render(&message),
+            Value::null(),
+        ))
+    }
+
+    /// The result of the field if it exceeds the budget of the response,
+    /// instead of resolving it
+    pub(crate) fn charge(&self) -> Option<ExecutionResult<S>> {
+        self.executor.charge_values(1)
+    }
+
+    /// Starts measuring the resolution of the field
+    pub(crate) fn start(&self) -> StartedField {
+        StartedField {
+            metrics: FieldMetrics::start(self.type_name, self.name),
+            slow_field_timer: SlowFieldTimer::start(&self.executor),
+        }
+    }
+
+    /// Finishes the resolution of the field, pushing its error if it failed.
+    ///
+    /// Returns the value of the field, or `None` when it's null although non
+    /// nullable, the parent object being null then.
+    pub(crate) fn finish(
+        &self,
+        started: StartedField,
+        result: ExecutionResult<S>,
+    ) -> Option<Value<S>> {
+        started.metrics.finish();
+        started
+            .slow_field_timer
+            .finish(&self.executor, self.type_name, self.name, &self.arguments);
+        self.executor
+            .record_field_usage(self.type_name, self.name, result.is_err());
+
+        match result {
+            Ok(Value::Null) if self.is_non_null => None,
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.executor.push_error_at(e, self.location);
+
+                if self.is_non_null {
+                    None
+                } else {
+                    Some(Value::null())
+                }
+            }
+        }
+    }
+}
+
+/// Inline fragment of a selection set, with its sub executor
+pub(crate) struct FragmentStep<'s, 'a, CtxT, S> {
+    pub(crate) type_condition: Option<&'a str>,
+    pub(crate) selection_set: &'s [Selection<'a, S>],
+    pub(crate) executor: Executor<'s, 'a, CtxT, S>,
+    location: SourcePosition,
+}
+
+impl<'s, 'a, CtxT, S> FragmentStep<'s, 'a, CtxT, S>
+where
+    S: ScalarValue,
+{
+    /// Fields of the object the fragment resolved into, pushing its error if
+    /// it failed
+    pub(crate) fn fields(&self, result: ExecutionResult<S>) -> Object<S> {
+        match result {
+            Ok(Value::Object(object)) => object,
+            Ok(_) => Object::with_capacity(0),
+            Err(e) => {
+                self.executor.push_error_at(e, self.location);
+                Object::with_capacity(0)
+            }
+        }
+    }
+}
+
+/// Steps of the resolution of a selection set, skipping the excluded
+/// selections
+pub(crate) struct SelectionSteps<'s, 'a, T, S>
+where
+    T: GraphQLValue<S> + ?Sized,
+    S: ScalarValue,
+{
+    instance: &'s T,
+    info: &'s T::TypeInfo,
+    selections: slice::Iter<'s, Selection<'a, S>>,
+    executor: &'s Executor<'s, 'a, T::Context, S>,
+    meta_type: &'s MetaType<'s, S>,
+
+    // Telling the concrete type of an interface or union may involve its
+    // resolvers, so it's done at most once, however often `__typename` is
+    // selected.
+    concrete_type_name: Option<String>,
+}
+
+/// Walks the selection set of the `instance`, to resolve it
+pub(crate) fn walk_selection_set<'s, 'a, T, S>(
+    instance: &'s T,
+    info: &'s T::TypeInfo,
+    selection_set: &'s [Selection<'a, S>],
+    executor: &'s Executor<'s, 'a, T::Context, S>,
+) -> SelectionSteps<'s, 'a, T, S>
+where
+    T: GraphQLValue<S> + ?Sized,
+    S: ScalarValue,
+{
+    let meta_type = executor
+        .schema()
+        .concrete_type_by_name(
+            instance
+                .type_name(info)
+                .expect("Resolving named type's selection set")
+                .as_ref(),
+        )
+        .expect("Type not found in schema");
+
+    SelectionSteps {
+        instance,
+        info,
+        selections: selection_set.iter(),
+        executor,
+        meta_type,
+        concrete_type_name: None,
+    }
+}
+
+impl<'s, 'a, T, S> Iterator for SelectionSteps<'s, 'a, T, S>
+where
+    T: GraphQLValue<S> + ?Sized,
+    S: ScalarValue,
+{
+    type Item = Step<'s, 'a, T::Context, S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let executor = self.executor;
+
+        loop {
+            match *self.selections.next()? {
+                Selection::Field(Spanning {
+                    item: ref f,
+                    start: ref start_pos,
+                    ..
+                }) => {
+                    if is_excluded(&f.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    let response_name = f.alias.as_ref().unwrap_or(&f.name).item;
+
+                    if f.name.item == "__typename" {
+                        let (instance, info) = (self.instance, self.info);
+                        let name = self.concrete_type_name.get_or_insert_with(|| {
+                            instance.concrete_type_name(executor.context(), info)
+                        });
+                        return Some(Step::Typename(response_name, Value::scalar(name.clone())));
+                    }
+
+                    let meta_type = self.meta_type;
+                    let meta_field = meta_type.field_by_name(f.name.item).unwrap_or_else(|| {
+                        panic!(format!(
+                            "Field {} not found on type {:?}",
+                            f.name.item,
+                            meta_type.name()
+                        ))
+                    });
+
+                    executor.restrict_cache_policy(meta_type, meta_field);
+                    executor.warn_deprecated_usage(
+                        meta_type,
+                        meta_field,
+                        f.arguments.as_ref(),
+                        *start_pos,
+                    );
+
+                    let exec_vars = executor.variables();
+
+                    let sub_exec = executor.field_sub_executor(
+                        response_name,
+                        f.name.item,
+                        *start_pos,
+                        f.selection_set.as_ref().map(|v| &v[..]),
+                    );
+
+                    let arguments = Arguments::new(
+                        f.arguments.as_ref().map(|m| {
+                            m.item
+                                .iter()
+                                .filter_map(|&(ref k, ref v)| {
+                                    v.item
+                                        .clone()
+                                        .into_defined_const(exec_vars)
+                                        .map(|v| (k.item, v))
+                                })
+                                .collect()
+                        }),
+                        &meta_field.arguments,
+                    );
+
+                    let priority =
+                        executor
+                            .schema()
+                            .field_scheduler
+                            .as_ref()
+                            .map_or(0, |scheduler| {
+                                scheduler.0.priority(&ScheduledField {
+                                    type_name: meta_type.name().unwrap_or_default(),
+                                    field: meta_field,
+                                    response_name,
+                                    arguments: &arguments,
+                                    is_leaf: matches!(
+                                        executor.schema().concrete_type_by_name(
+                                            meta_field.field_type.innermost_name(),
+                                        ),
+                                        Some(t) if t.is_leaf()
+                                    ),
+                                })
+                            });
+
+                    return Some(Step::Field(FieldStep {
+                        response_name,
+                        name: f.name.item,
+                        arguments,
+                        executor: sub_exec,
+                        serial: meta_field.serial,
+                        priority,
+                        authorized: executor.authorization().allows_field(
+                            executor.schema(),
+                            meta_type,
+                            meta_field,
+                        ),
+                        pagination_limit: meta_type
+                            .name()
+                            .and_then(|t| executor.schema().pagination_limit(t, f.name.item)),
+                        type_name: meta_type.name(),
+                        is_non_null: meta_field.field_type.is_non_null(),
+                        location: *start_pos,
+                    }));
+                }
+                Selection::FragmentSpread(Spanning {
+                    item: ref spread, ..
+                }) => {
+                    if is_excluded(&spread.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    let fragment = executor
+                        .fragment_by_name(spread.name.item)
+                        .expect("Fragment could not be found");
+
+                    return Some(Step::Spread(&fragment.selection_set[..]));
+                }
+                Selection::InlineFragment(Spanning {
+                    item: ref fragment,
+                    start: ref start_pos,
+                    ..
+                }) => {
+                    if is_excluded(&fragment.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    let sub_exec = executor.type_sub_executor(
+                        fragment.type_condition.as_ref().map(|c| c.item),
+                        Some(&fragment.selection_set[..]),
+                    );
+
+                    return Some(Step::InlineFragment(FragmentStep {
+                        type_condition: fragment.type_condition.as_ref().map(|c| c.item),
+                        selection_set: &fragment.selection_set[..],
+                        executor: sub_exec,
+                        location: *start_pos,
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Value of a field or of a fragment resolved asynchronously, to merge into
+/// the object of its selection set
+pub(crate) enum AsyncValue<S> {
+    /// Field, with its response name and its value if it isn't null
+    Field(String, Option<Value<S>>),
+
+    /// Object of the fields of a fragment
+    Nested(Value<S>),
+}
+
+/// Merges the values of the `values` resolved so far into the `object`, in
+/// order, returning `false` if a null value makes the whole object null
+pub(crate) async fn merge_async_values<St, S>(values: &mut St, object: &mut Object<S>) -> bool
+where
+    St: futures::Stream<Item = AsyncValue<S>> + Unpin,
+    S: ScalarValue,
+{
+    use futures::stream::StreamExt as _;
+
+    while let Some(item) = values.next().await {
+        match item {
+            AsyncValue::Field(name, value) => {
+                if let Some(value) = value {
+                    object.merge_field(name, value);
+                } else {
+                    return false;
+                }
+            }
+            AsyncValue::Nested(obj) => match obj {
+                Value::Null => {
+                    return false;
+                }
+                Value::Object(obj) => {
+                    for (k, v) in obj {
+                        object.merge_field(k, v);
+                    }
+                }
+                _ => unreachable!(),
+            },
+        }
+    }
+    true
+}
+
+/// Defines the asynchronous resolution of selection sets over the implementors
+/// of a value trait, given the boxed future type, the names of the trait's
+/// field and type resolvers, and the bounds on the context and type info
+///
+/// Both the [`Send`] and the local flavours are generated by this macro, as
+/// they only differ in those.
+macro_rules! resolve_selection_set_async {
+    (
+        fn $wrapper:ident, $recursive:ident -> $boxed:ident;
+        impl $value:ident { $resolve_field:ident, $resolve_into_type:ident }
+        where { $($bounds:tt)* }
+    ) => {
+        // Wrapper function around the recursive resolution.
+        // This wrapper is necessary because async fns can not be recursive.
+        fn $wrapper<'a, 'e, T, S>(
+            instance: &'a T,
+            info: &'a T::TypeInfo,
+            selection_set: &'e [$crate::ast::Selection<'e, S>],
+            executor: &'e $crate::executor::Executor<'e, 'e, T::Context, S>,
+        ) -> $crate::$boxed<'a, $crate::value::Value<S>>
+        where
+            T: $value<S> + ?Sized,
+            $($bounds)*
+            'e: 'a,
+        {
+            Box::pin($recursive(instance, info, selection_set, executor))
+        }
+
+        async fn $recursive<'a, T, S>(
+            instance: &'a T,
+            info: &'a T::TypeInfo,
+            selection_set: &'a [$crate::ast::Selection<'a, S>],
+            executor: &'a $crate::executor::Executor<'a, 'a, T::Context, S>,
+        ) -> $crate::value::Value<S>
+        where
+            T: $value<S> + ?Sized,
+            $($bounds)*
+        {
+            use futures::future;
+
+            use $crate::{
+                executor::{catch_panic, CatchPanic},
+                schedule::ScheduledFutures,
+                types::selection::{merge_async_values, walk_selection_set, AsyncValue, Step},
+                value::{Object, Value},
+            };
+
+            #[derive(futures_enum::Future)]
+            enum AsyncValueFuture<A, B, C, D, E> {
+                Typename(E),
+                Field(A),
+                FragmentSpread(B),
+                InlineFragment1(C),
+                InlineFragment2(D),
+            }
+
+            let mut object = Object::with_capacity(selection_set.len());
+
+            let mut async_values = ScheduledFutures::<AsyncValueFuture<_, _, _, _, _>>::new();
+
+            for step in walk_selection_set(instance, info, selection_set, executor) {
+                match step {
+                    Step::Typename(response_name, value) => {
+                        // Queued like the other fields, to keep the order of the selections.
+                        async_values.push(
+                            0,
+                            AsyncValueFuture::Typename(future::ready(AsyncValue::Field(
+                                response_name.to_owned(),
+                                Some(value),
+                            ))),
+                        );
+                    }
+                    Step::Field(field) => {
+                        // Serial fields are resolved once the fields queued before
+                        // them are, and before queueing the next ones.
+                        let (serial, priority) = (field.serial, field.priority);
+                        if serial && !merge_async_values(&mut async_values, &mut object).await {
+                            return Value::null();
+                        }
+                        async_values.push(
+                            priority,
+                            AsyncValueFuture::Field(async move {
+                                let started = field.start();
+                                // Sync resolvers run once the future is created, not when it's
+                                // polled.
+                                let catch = field.executor.schema().catch_panics;
+                                let res = match field.charge() {
+                                    Some(res) => res,
+                                    None => match field.admit().and_then(|()| {
+                                        catch_panic(catch, || {
+                                            Ok(instance.$resolve_field(
+                                                info,
+                                                field.name,
+                                                &field.arguments,
+                                                &field.executor,
+                                            ))
+                                        })
+                                    }) {
+                                        Ok(future) => CatchPanic { catch, future }.await,
+                                        Err(e) => Err(e),
+                                    },
+                                };
+
+                                AsyncValue::Field(
+                                    field.response_name.to_owned(),
+                                    field.finish(started, res),
+                                )
+                            }),
+                        );
+                        if serial && !merge_async_values(&mut async_values, &mut object).await {
+                            return Value::null();
+                        }
+                    }
+                    Step::Spread(selection_set) => {
+                        async_values.push(
+                            0,
+                            AsyncValueFuture::FragmentSpread(async move {
+                                let value =
+                                    $wrapper(instance, info, selection_set, executor).await;
+                                AsyncValue::Nested(value)
+                            }),
+                        );
+                    }
+                    Step::InlineFragment(fragment) => {
+                        if let Some(type_condition) = fragment.type_condition {
+                            let sub_result = instance
+                                .$resolve_into_type(
+                                    info,
+                                    type_condition,
+                                    Some(fragment.selection_set),
+                                    &fragment.executor,
+                                )
+                                .await;
+
+                            for (k, v) in fragment.fields(sub_result) {
+                                async_values.push(
+                                    0,
+                                    AsyncValueFuture::InlineFragment1(async move {
+                                        AsyncValue::Field(k, Some(v))
+                                    }),
+                                );
+                            }
+                        } else {
+                            async_values.push(
+                                0,
+                                AsyncValueFuture::InlineFragment2(async move {
+                                    let value = $wrapper(
+                                        instance,
+                                        info,
+                                        fragment.selection_set,
+                                        &fragment.executor,
+                                    )
+                                    .await;
+                                    AsyncValue::Nested(value)
+                                }),
+                            );
+                        }
+                    }
+                }
+            }
+
+            if !merge_async_values(&mut async_values, &mut object).await {
+                return Value::null();
+            }
+
+            Value::Object(object)
+        }
+    };
+}
@@ -1,37 +1,14 @@
 use futures::{future, stream};
-use serde::Serialize;
 
 use crate::{
+    auth,
     http::GraphQLRequest,
     parser::Spanning,
-    types::base::{is_excluded, merge_key_into, GraphQLType, GraphQLValue},
-    Arguments, BoxFuture, DefaultScalarValue, ExecutionError, Executor, FieldError, Object,
+    types::base::{is_excluded, GraphQLType, GraphQLValue},
+    Arguments, BoxFuture, DefaultScalarValue, ExecutionOutput, Executor, FieldError, Object,
     ScalarValue, Selection, Value, ValuesStream,
 };
 
-/// Represents the result of executing a GraphQL operation (after parsing and validating has been
-/// done).
-#[derive(Debug, Serialize)]
-pub struct ExecutionOutput<S> {
-    /// The output data.
-    pub data: Value<S>,
-
-    /// The errors that occurred. Note that the presence of errors does not mean there is no data.
-    /// The output can have both data and errors.
-    #[serde(bound(serialize = "S: ScalarValue"))]
-    pub errors: Vec<ExecutionError<S>>,
-}
-
-impl<S> ExecutionOutput<S> {
-    /// Creates execution output from data, with no errors.
-    pub fn from_data(data: Value<S>) -> Self {
-        Self {
-            data,
-            errors: vec![],
-        }
-    }
-}
-
 /// Global subscription coordinator trait.
 ///
 /// With regular queries we could get away with not having some in-between
@@ -325,7 +302,12 @@ where
                     f.arguments.as_ref().map(|m| {
                         m.item
                             .iter()
-                            .map(|&(ref k, ref v)| (k.item, v.item.clone().into_const(&exec_vars)))
+                            .filter_map(|&(ref k, ref v)| {
+                                v.item
+                                    .clone()
+                                    .into_defined_const(&exec_vars)
+                                    .map(|v| (k.item, v))
+                            })
                             .collect()
                     }),
                     &meta_field.arguments,
@@ -333,15 +315,20 @@ where
 
                 let is_non_null = meta_field.field_type.is_non_null();
 
-                let res = instance
-                    .resolve_field_into_stream(info, f.name.item, args, &sub_exec)
-                    .await;
+                let authorization = executor.authorization();
+                let res = if authorization.allows_field(executor.schema(), meta_type, &meta_field) {
+                    instance
+                        .resolve_field_into_stream(info, f.name.item, args, &sub_exec)
+                        .await
+                } else {
+                    Err(auth::forbidden())
+                };
 
                 match res {
                     Ok(Value::Null) if is_non_null => {
                         return Value::Null;
                     }
-                    Ok(v) => merge_key_into(&mut object, response_name, v),
+                    Ok(v) => object.merge_field(response_name, v),
                     Err(e) => {
                         sub_exec.push_error_at(e, *start_pos);
 
@@ -381,7 +368,7 @@ where
                         match val {
                             Value::Object(o) => {
                                 for (k, v) in o {
-                                    merge_key_into(&mut object, &k, v);
+                                    object.merge_field(k, v);
                                 }
                             }
                             // since this was a wrapper of current function,
@@ -414,7 +401,7 @@ where
 
                     if let Ok(Value::Object(obj)) = sub_result {
                         for (k, v) in obj {
-                            merge_key_into(&mut object, &k, v);
+                            object.merge_field(k, v);
                         }
                     } else if let Err(e) = sub_result {
                         sub_exec.push_error_at(e, *start_pos);
@@ -426,7 +413,7 @@ where
 
                     if let Ok(Value::Object(obj)) = sub_result {
                         for (k, v) in obj {
-                            merge_key_into(&mut object, &k, v);
+                            object.merge_field(k, v);
                         }
                     } else if let Err(e) = sub_result {
                         sub_exec.push_error_at(e, *start_pos);
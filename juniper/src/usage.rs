@@ -0,0 +1,262 @@
+//! Statistics about the usage of the schema's fields
+//!
+//! A [`UsageCollector`](struct.UsageCollector.html) set with
+//! [`RootNode::with_usage_collector`](../struct.RootNode.html#method.with_usage_collector)
+//! counts the executed operations, and how many times each field is resolved
+//! and fails. The statistics are sent as a [`UsageReport`](struct.UsageReport.html)
+//! to a [`UsageExporter`](trait.UsageExporter.html) when flushed, e.g. to find
+//! the fields no clients use anymore before removing them:
+//!
+//! ```
+//! # use std::{sync::Arc, time::Duration};
+//! # use juniper::{usage::{UsageCollector, UsageReport}, EmptyMutation, EmptySubscription, RootNode};
+//! struct Query;
+//!
+//! #[juniper::graphql_object]
+//! impl Query {
+//!     fn greeting() -> &str {
+//!         "Hello"
+//!     }
+//! }
+//!
+//! let collector = Arc::new(
+//!     UsageCollector::new(|report: &UsageReport| {
+//!         for field in report.unused_fields() {
+//!             println!("{}.{} is unused", field.type_name, field.field_name);
+//!         }
+//!     })
+//!     .flush_interval(Duration::from_secs(60)),
+//! );
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+//!     .with_usage_collector(collector.clone());
+//! ```
+//!
+//! Reports serialize to JSON with `serde`, in a format neutral to the service
+//! receiving them.
+
+use std::{
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use fnv::FnvHashMap;
+use serde::Serialize;
+
+use crate::schema::{meta::MetaType, model::SchemaType};
+
+/// Receiver of the reports flushed by a [`UsageCollector`](struct.UsageCollector.html)
+///
+/// Exporting is done within the request flushing the statistics, so it
+/// shouldn't block for long, e.g. by sending the report from another thread.
+pub trait UsageExporter: Send + Sync {
+    /// Handles the statistics collected since the previous report
+    fn export(&self, report: &UsageReport);
+}
+
+impl<F> UsageExporter for F
+where
+    F: Fn(&UsageReport) + Send + Sync,
+{
+    fn export(&self, report: &UsageReport) {
+        self(report)
+    }
+}
+
+/// Statistics collected between two flushes of a
+/// [`UsageCollector`](struct.UsageCollector.html)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    /// Number of executed operations, by operation name, empty for anonymous
+    /// operations
+    pub operations: Vec<OperationUsage>,
+    /// Usage of each field of the schema's objects, unused ones included,
+    /// sorted by type and field name
+    pub fields: Vec<FieldUsage>,
+}
+
+impl UsageReport {
+    /// The fields resolved by none of the reported operations
+    pub fn unused_fields(&self) -> impl Iterator<Item = &FieldUsage> {
+        self.fields.iter().filter(|field| field.count == 0)
+    }
+}
+
+/// Number of executions of an operation
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationUsage {
+    /// Name of the operation, empty if anonymous
+    pub name: String,
+    /// Number of executions
+    pub count: u64,
+}
+
+/// Usage of an object's field
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldUsage {
+    /// Name of the object
+    pub type_name: String,
+    /// Name of the field
+    pub field_name: String,
+    /// Number of times the field was resolved
+    pub count: u64,
+    /// Number of times resolving the field failed
+    pub errors: u64,
+}
+
+/// Collector of the usage statistics of a schema
+pub struct UsageCollector {
+    exporter: Box<dyn UsageExporter>,
+    flush_interval: Option<Duration>,
+    stats: Mutex<Stats>,
+}
+
+struct Stats {
+    operations: FnvHashMap<String, u64>,
+    /// Counts and errors by type and field name
+    fields: FnvHashMap<(String, String), (u64, u64)>,
+    /// Only tracked with a flush interval, as `Instant` isn't available on
+    /// every target, e.g. `wasm32-unknown-unknown`
+    last_flush: Option<Instant>,
+}
+
+impl UsageCollector {
+    /// Constructs a collector sending the reports to the provided exporter
+    ///
+    /// Reports are only sent when calling [`flush`](#method.flush), unless a
+    /// [`flush_interval`](#method.flush_interval) is set.
+    pub fn new<E: UsageExporter + 'static>(exporter: E) -> Self {
+        UsageCollector {
+            exporter: Box::new(exporter),
+            flush_interval: None,
+            stats: Mutex::new(Stats {
+                operations: FnvHashMap::default(),
+                fields: FnvHashMap::default(),
+                last_flush: None,
+            }),
+        }
+    }
+
+    /// Flushes the statistics once an operation finishes at least the provided
+    /// duration after the previous flush, or the first operation
+    ///
+    /// This relies on `std::time::Instant`, which panics on
+    /// `wasm32-unknown-unknown`, so the statistics have to be flushed manually
+    /// there.
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = Some(interval);
+        self
+    }
+
+    /// Sends the statistics collected since the previous flush to the exporter
+    /// and resets them
+    pub fn flush(&self) {
+        let report = self.stats().take_report();
+        self.exporter.export(&report);
+    }
+
+    /// Adds the fields of the schema's objects, so unused ones are reported
+    pub(crate) fn register_schema<S>(&self, schema: &SchemaType<S>) {
+        let mut stats = self.stats();
+        for meta_type in schema.types.values() {
+            if let MetaType::Object(object) = meta_type {
+                if object.name.starts_with("__") {
+                    continue;
+                }
+                for field in &object.fields {
+                    if !field.name.starts_with("__") {
+                        let key = (object.name.to_string(), field.name.clone());
+                        stats.fields.entry(key).or_default();
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn record_field(&self, type_name: &str, field_name: &str, failed: bool) {
+        if type_name.starts_with("__") || field_name.starts_with("__") {
+            return;
+        }
+        let mut stats = self.stats();
+        let key = (type_name.to_owned(), field_name.to_owned());
+        let (count, errors) = stats.fields.entry(key).or_default();
+        *count += 1;
+        if failed {
+            *errors += 1;
+        }
+    }
+
+    /// Counts a finished operation, flushing the statistics if the interval
+    /// elapsed
+    pub(crate) fn record_operation(&self, name: Option<&str>) {
+        let report = {
+            let mut stats = self.stats();
+            *stats
+                .operations
+                .entry(name.unwrap_or_default().to_owned())
+                .or_default() += 1;
+            match self.flush_interval {
+                Some(interval) => {
+                    let last_flush = *stats.last_flush.get_or_insert_with(Instant::now);
+                    if last_flush.elapsed() >= interval {
+                        Some(stats.take_report())
+                    } else {
+                        None
+                    }
+                }
+                None => None,
+            }
+        };
+        if let Some(report) = report {
+            self.exporter.export(&report);
+        }
+    }
+
+    fn stats(&self) -> MutexGuard<'_, Stats> {
+        self.stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Stats {
+    fn take_report(&mut self) -> UsageReport {
+        let mut operations: Vec<_> = self
+            .operations
+            .drain()
+            .map(|(name, count)| OperationUsage { name, count })
+            .collect();
+        operations.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut fields: Vec<_> = self
+            .fields
+            .iter_mut()
+            .map(|((type_name, field_name), stats)| {
+                let (count, errors) = std::mem::take(stats);
+                FieldUsage {
+                    type_name: type_name.clone(),
+                    field_name: field_name.clone(),
+                    count,
+                    errors,
+                }
+            })
+            .collect();
+        fields.sort_by(|a, b| (&a.type_name, &a.field_name).cmp(&(&b.type_name, &b.field_name)));
+
+        if self.last_flush.is_some() {
+            self.last_flush = Some(Instant::now());
+        }
+        UsageReport { operations, fields }
+    }
+}
+
+impl fmt::Debug for UsageCollector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UsageCollector")
+            .field("flush_interval", &self.flush_interval)
+            .finish()
+    }
+}
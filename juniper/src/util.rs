@@ -5,6 +5,9 @@ use std::borrow::Cow;
 /// Note: needs to be public because several macros use it.
 #[doc(hidden)]
 pub fn to_camel_case(s: &'_ str) -> Cow<'_, str> {
+    // raw identifiers, like `r#type`, are named after the keyword
+    let s = if s.starts_with("r#") { &s[2..] } else { s };
+
     let mut dest = Cow::Borrowed(s);
 
     // handle '_' to be more friendly with the
@@ -46,4 +49,6 @@ fn test_to_camel_case() {
     assert_eq!(&to_camel_case("a_b")[..], "aB");
     assert_eq!(&to_camel_case("a")[..], "a");
     assert_eq!(&to_camel_case("")[..], "");
+    assert_eq!(&to_camel_case("r#type")[..], "type");
+    assert_eq!(&to_camel_case("r#type_of")[..], "typeOf");
 }
@@ -0,0 +1,420 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    ast::InputValue,
+    messages::Message,
+    schema::{
+        meta::{EnumMeta, InputObjectMeta, MetaType, ScalarMeta},
+        model::{SchemaType, TypeType},
+    },
+    value::ScalarValue,
+};
+
+/// Where an input value being coerced comes from
+///
+/// Literals and variable values are checked by the same rules, except for the
+/// few places where the spec treats them differently: literals may contain
+/// variables, and only variable values may spell enum values as strings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum InputSource {
+    Literal,
+    Variable,
+}
+
+/// A single reason why an input value can't be coerced into its expected type
+#[derive(Debug, PartialEq)]
+pub(crate) struct CoercionError {
+    /// Location of the offending value inside the coerced one, e.g.
+    /// `filters.ages[1]`, empty if it's the coerced value itself
+    pub path: String,
+
+    /// What's wrong with the offending value
+    pub message: String,
+}
+
+#[derive(Debug)]
+enum Path<'a> {
+    Root,
+    ArrayElement(usize, &'a Path<'a>),
+    ObjectField(&'a str, &'a Path<'a>),
+}
+
+/// Checks whether `value` can be coerced into `meta_type`, returning all the
+/// reasons why it can't.
+pub(crate) fn coercion_errors<S>(
+    schema: &SchemaType<S>,
+    meta_type: &TypeType<S>,
+    value: &InputValue<S>,
+    source: InputSource,
+) -> Vec<CoercionError>
+where
+    S: ScalarValue,
+{
+    let mut errors = vec![];
+    Coercion {
+        schema,
+        source,
+        errors: &mut errors,
+    }
+    .check(meta_type, value, &Path::Root);
+    errors
+}
+
+/// Checks whether the literal `value` can be coerced into `meta_type`
+pub(crate) fn is_valid_literal_value<S>(
+    schema: &SchemaType<S>,
+    meta_type: &TypeType<S>,
+    value: &InputValue<S>,
+) -> bool
+where
+    S: ScalarValue,
+{
+    coercion_errors(schema, meta_type, value, InputSource::Literal).is_empty()
+}
+
+struct Coercion<'e, 's, S> {
+    schema: &'s SchemaType<'s, S>,
+    source: InputSource,
+    errors: &'e mut Vec<CoercionError>,
+}
+
+impl<'e, 's, S> Coercion<'e, 's, S>
+where
+    S: ScalarValue,
+{
+    fn check(&mut self, meta_type: &TypeType<S>, value: &InputValue<S>, path: &Path) {
+        // Variables inside literals are checked against their usage by the
+        // `VariablesInAllowedPosition` rule.
+        if let InputValue::Variable(_) = *value {
+            return;
+        }
+
+        match *meta_type {
+            TypeType::NonNull(ref inner) => {
+                if value.is_null() {
+                    let expected_type = meta_type.to_string();
+                    self.error(
+                        path,
+                        Message::UnexpectedNull {
+                            expected_type: &expected_type,
+                        },
+                    );
+                } else {
+                    self.check(inner, value, path);
+                }
+            }
+            TypeType::List(ref inner) => match *value {
+                InputValue::Null => {}
+                InputValue::List(ref items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(inner, &item.item, &Path::ArrayElement(i, path));
+                    }
+                }
+                // A single value is coerced into a list of one element.
+                _ => self.check(inner, value, path),
+            },
+            TypeType::Concrete(_) if value.is_null() => {}
+            TypeType::Concrete(mt) => match *mt {
+                MetaType::Scalar(ref sm) => self.check_scalar(sm, value, path),
+                MetaType::Enum(ref em) => self.check_enum(em, value, path),
+                MetaType::InputObject(ref iom) => self.check_input_object(iom, value, path),
+                _ => {
+                    let expected_type = meta_type.to_string();
+                    self.error(
+                        path,
+                        Message::NonInputType {
+                            expected_type: &expected_type,
+                        },
+                    )
+                }
+            },
+        }
+    }
+
+    fn check_scalar(&mut self, meta: &ScalarMeta<S>, value: &InputValue<S>, path: &Path) {
+        if let Err(e) = (meta.try_parse_fn)(value) {
+            let e = match e.expected() {
+                Some(_) => e,
+                None => e.with_expected(meta.name.to_string()),
+            };
+            // Scalars word their own parsing errors.
+            self.push_error(path, e.to_string());
+            return;
+        }
+
+        match *value {
+            InputValue::List(_) => self.error(
+                path,
+                Message::UnexpectedList {
+                    expected_type: &meta.name,
+                },
+            ),
+            InputValue::Object(_) => self.error(
+                path,
+                Message::UnexpectedObject {
+                    expected_type: &meta.name,
+                },
+            ),
+            _ => {}
+        }
+    }
+
+    fn check_enum(&mut self, meta: &EnumMeta<S>, value: &InputValue<S>, path: &Path) {
+        let name = match *value {
+            InputValue::Enum(ref name) => Some(name.as_str()),
+            // Even though strings can be parsed into an enum, they are not
+            // valid as enum *literals* in a GraphQL query.
+            InputValue::Scalar(ref scalar) if self.source == InputSource::Variable => {
+                scalar.as_str()
+            }
+            _ => None,
+        };
+
+        match name {
+            Some(name) => {
+                // Enums may accept names not listed in the schema, e.g. aliases
+                // kept for compatibility.
+                if !meta.values.iter().any(|ev| ev.name == name)
+                    && (meta.try_parse_fn)(value).is_err()
+                {
+                    let valid = meta
+                        .values
+                        .iter()
+                        .map(|ev| ev.name.as_str())
+                        .collect::<Vec<_>>();
+                    self.error(
+                        path,
+                        Message::InvalidEnumValue {
+                            enum_type: &meta.name,
+                            values: &valid,
+                        },
+                    );
+                }
+            }
+            None => self.error(
+                path,
+                Message::NotAnEnum {
+                    enum_type: &meta.name,
+                    strings_allowed: self.source == InputSource::Variable,
+                },
+            ),
+        }
+    }
+
+    fn check_input_object(
+        &mut self,
+        meta: &InputObjectMeta<S>,
+        value: &InputValue<S>,
+        path: &Path,
+    ) {
+        let obj = match value.to_object_value() {
+            Some(obj) => obj,
+            None => {
+                self.error(
+                    path,
+                    Message::NotAnObject {
+                        expected_type: &meta.name,
+                    },
+                );
+                return;
+            }
+        };
+
+        let errors_before = self.errors.len();
+        let mut keys = obj.keys().cloned().collect::<HashSet<&str>>();
+
+        for input_field in &meta.input_fields {
+            keys.remove(input_field.name.as_str());
+            let field_path = Path::ObjectField(&input_field.name, path);
+
+            match obj.get(input_field.name.as_str()) {
+                Some(field_value) if !field_value.is_null() => {
+                    let field_type = self.schema.make_type(&input_field.arg_type);
+                    self.check(&field_type, field_value, &field_path);
+                }
+                _ => {
+                    if input_field.arg_type.is_non_null() && input_field.default_value.is_none() {
+                        let expected_type = input_field.arg_type.to_string();
+                        self.error(
+                            &field_path,
+                            Message::UnexpectedNull {
+                                expected_type: &expected_type,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut unknown_keys = keys.into_iter().collect::<Vec<_>>();
+        unknown_keys.sort();
+        for key in unknown_keys {
+            self.error(&Path::ObjectField(key, path), Message::UnknownInputField);
+        }
+
+        // All the fields are fine, see if there is an overall error when parsing
+        // the input value. Literals referencing variables can't be parsed yet.
+        if self.errors.len() == errors_before && value.referenced_variables().is_empty() {
+            if let Err(e) = (meta.try_parse_fn)(value) {
+                let value = value.to_string();
+                self.error(
+                    path,
+                    Message::InvalidInputObject {
+                        expected_type: &meta.name,
+                        value: &value,
+                        reason: e.reason(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn error(&mut self, path: &Path, message: Message) {
+        let message = self.schema.messages.render(&message);
+        self.push_error(path, message);
+    }
+
+    fn push_error(&mut self, path: &Path, message: String) {
+        self.errors.push(CoercionError {
+            path: path.to_string(),
+            message,
+        });
+    }
+}
+
+/// Renders the path the way it would be written in a JavaScript expression
+/// relative to the coerced value, e.g. `filters.ages[1]`.
+impl<'a> fmt::Display for Path<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Path::Root => Ok(()),
+            Path::ArrayElement(idx, prev) => write!(f, "{}[{}]", prev, idx),
+            Path::ObjectField(name, Path::Root) => write!(f, "{}", name),
+            Path::ObjectField(name, prev) => write!(f, "{}.{}", prev, name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{coercion_errors, CoercionError, InputSource};
+
+    use crate::{
+        ast::{InputValue, Type},
+        schema::model::SchemaType,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        validation::test_harness::QueryRoot,
+        value::DefaultScalarValue,
+    };
+
+    fn check(
+        type_name: &str,
+        value: InputValue<DefaultScalarValue>,
+        source: InputSource,
+    ) -> Vec<CoercionError> {
+        let schema = SchemaType::<DefaultScalarValue>::new::<
+            QueryRoot,
+            EmptyMutation<()>,
+            EmptySubscription<()>,
+        >(&(), &(), &());
+        let meta_type = schema.make_type(&Type::NonNullNamed(type_name.into()));
+        coercion_errors(&schema, &meta_type, &value, source)
+    }
+
+    fn error(path: &str, message: &str) -> CoercionError {
+        CoercionError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn literals_and_variables_report_the_same_errors() {
+        for &source in &[InputSource::Literal, InputSource::Variable] {
+            assert_eq!(
+                check("FurColor", InputValue::enum_value("PURPLE"), source),
+                vec![error(
+                    "",
+                    r#"Invalid value for enum "FurColor", expected one of: BROWN, BLACK, TAN, SPOTTED"#,
+                )],
+            );
+            assert_eq!(
+                check("Int", InputValue::scalar("foo"), source),
+                vec![error("", r#"Expected "Int""#)],
+            );
+            assert_eq!(
+                check("Int", InputValue::null(), source),
+                vec![error("", r#"Expected "Int!", found null"#)],
+            );
+        }
+    }
+
+    #[test]
+    fn only_variables_accept_enum_strings() {
+        assert_eq!(
+            check(
+                "FurColor",
+                InputValue::scalar("BROWN"),
+                InputSource::Variable
+            ),
+            vec![],
+        );
+        assert_eq!(
+            check(
+                "FurColor",
+                InputValue::scalar("BROWN"),
+                InputSource::Literal
+            ),
+            vec![error("", r#"Expected "FurColor", found not an enum"#)],
+        );
+    }
+
+    #[test]
+    fn reports_paths_of_nested_values() {
+        let value = InputValue::object(
+            vec![
+                ("requiredField", InputValue::scalar(true)),
+                (
+                    "stringListField",
+                    InputValue::list(vec![InputValue::scalar("a"), InputValue::scalar(1)]),
+                ),
+                ("unknownField", InputValue::scalar("b")),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(
+            check("ComplexInput", value, InputSource::Literal),
+            vec![
+                error("stringListField[1]", r#"Expected "String""#),
+                error("unknownField", "Unknown field"),
+            ],
+        );
+    }
+
+    #[test]
+    fn coerces_single_values_into_lists() {
+        let value = InputValue::object(
+            vec![
+                ("requiredField", InputValue::scalar(true)),
+                ("stringListField", InputValue::scalar("a")),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(check("ComplexInput", value, InputSource::Variable), vec![]);
+    }
+
+    #[test]
+    fn skips_variables_in_literals() {
+        let value = InputValue::object(
+            vec![("requiredField", InputValue::variable("var"))]
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(check("ComplexInput", value, InputSource::Literal), vec![]);
+    }
+}
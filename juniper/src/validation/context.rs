@@ -7,7 +7,7 @@ use crate::ast::{Definition, Document, Type};
 
 use crate::schema::{meta::MetaType, model::SchemaType};
 
-use crate::parser::SourcePosition;
+use crate::{messages::Message, parser::SourcePosition};
 
 /// Query validation error
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -30,9 +30,9 @@ pub struct ValidatorContext<'a, S: Debug + 'a> {
 
 impl RuleError {
     #[doc(hidden)]
-    pub fn new(message: &str, locations: &[SourcePosition]) -> RuleError {
+    pub fn new(message: impl fmt::Display, locations: &[SourcePosition]) -> RuleError {
         RuleError {
-            message: message.to_owned(),
+            message: message.to_string(),
             locations: locations.to_vec(),
         }
     }
@@ -97,6 +97,13 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
         self.errors.push(RuleError::new(message, locations))
     }
 
+    /// Reports the `message`, worded by the message catalog of the schema
+    #[doc(hidden)]
+    pub fn report(&mut self, message: Message, locations: &[SourcePosition]) {
+        let message = self.schema.messages.render(&message);
+        self.report_error(&message, locations)
+    }
+
     #[doc(hidden)]
     pub fn into_errors(mut self) -> Vec<RuleError> {
         self.errors.sort();
@@ -1,16 +1,20 @@
 //! Query validation related methods and data structures
 
+mod coercion;
 mod context;
 mod input_value;
 mod multi_visitor;
+mod nesting_depth;
 mod rules;
+mod suggestion;
 mod traits;
 mod visitor;
 
 #[cfg(test)]
 pub(crate) mod test_harness;
 
-pub(crate) use self::rules::visit_all_rules;
+#[cfg(test)]
+pub(crate) use self::rules::visit_rule;
 pub use self::{
     context::{RuleError, ValidatorContext},
     input_value::validate_input_values,
@@ -18,6 +22,10 @@ pub use self::{
     traits::Visitor,
     visitor::visit,
 };
+pub(crate) use self::{
+    rules::{pagination, visit_all_rules, Pagination},
+    suggestion::did_you_mean,
+};
 
 #[cfg(test)]
 pub use self::test_harness::{
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Definition, Document, Selection},
+    messages::{Message, Messages},
+    validation::RuleError,
+};
+
+/// Nesting information collected from a single selection set, without
+/// following fragment spreads
+struct LocalDepth<'a> {
+    max_depth: usize,
+    spreads: Vec<(&'a str, usize)>,
+}
+
+/// Checks that no operation or fragment definition in `document` nests its
+/// selection sets deeper than `limit`, counting fragment spreads as nesting
+/// into the spread fragment.
+///
+/// The parser already bounds the nesting of every single definition, but a
+/// chain of fragments spreading each other can still describe an arbitrarily
+/// deep selection. This check walks the document without recursion, so it
+/// can run before the (recursive) validation rules and the executor get to see
+/// such a document.
+pub(crate) fn validate_nesting_depth<S>(
+    document: &Document<S>,
+    limit: usize,
+    messages: &Messages,
+) -> Vec<RuleError> {
+    let mut fragments = HashMap::new();
+    for def in document {
+        if let Definition::Fragment(f) = def {
+            fragments
+                .entry(f.item.name.item)
+                .or_insert_with(|| local_depth(&f.item.selection_set));
+        }
+    }
+
+    let mut resolved = HashMap::new();
+    let mut errors = vec![];
+
+    for def in document {
+        let (local, start) = match def {
+            Definition::Operation(op) => (local_depth(&op.item.selection_set), op.start),
+            Definition::Fragment(f) => (local_depth(&f.item.selection_set), f.start),
+        };
+
+        let depth = local
+            .spreads
+            .iter()
+            .map(|&(name, at)| at + fragment_depth(name, &fragments, &mut resolved))
+            .fold(local.max_depth, usize::max);
+
+        if depth > limit {
+            errors.push(RuleError::new(
+                messages.render(&Message::NestingTooDeep { limit }),
+                &[start],
+            ));
+        }
+    }
+
+    errors
+}
+
+fn local_depth<'a, S>(selection_set: &'a [Selection<'a, S>]) -> LocalDepth<'a> {
+    let mut result = LocalDepth {
+        max_depth: 0,
+        spreads: vec![],
+    };
+    let mut stack = vec![(selection_set, 1)];
+
+    while let Some((selection_set, depth)) = stack.pop() {
+        result.max_depth = result.max_depth.max(depth);
+
+        for selection in selection_set {
+            match selection {
+                Selection::Field(f) => {
+                    if let Some(ref selection_set) = f.item.selection_set {
+                        stack.push((selection_set, depth + 1));
+                    }
+                }
+                Selection::InlineFragment(f) => {
+                    stack.push((&f.item.selection_set, depth + 1));
+                }
+                Selection::FragmentSpread(s) => {
+                    result.spreads.push((s.item.name.item, depth));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Computes the full nesting depth of the named fragment, memoizing results in
+/// `resolved`.
+///
+/// Fragments currently being resolved are marked with `None`, so spreads
+/// closing a cycle don't add any depth; cycles are reported by the
+/// `NoFragmentCycles` rule.
+fn fragment_depth<'a>(
+    name: &'a str,
+    fragments: &HashMap<&'a str, LocalDepth<'a>>,
+    resolved: &mut HashMap<&'a str, Option<usize>>,
+) -> usize {
+    if let Some(depth) = resolved.get(name) {
+        return depth.unwrap_or(0);
+    }
+
+    let mut stack: Vec<(&'a str, usize, usize)> = vec![];
+    match fragments.get(name) {
+        Some(local) => {
+            resolved.insert(name, None);
+            stack.push((name, 0, local.max_depth));
+        }
+        None => return 0,
+    }
+
+    while let Some(&(current, next_spread, depth)) = stack.last() {
+        let top = stack.len() - 1;
+
+        if let Some(&(spread, at)) = fragments[current].spreads.get(next_spread) {
+            stack[top].1 += 1;
+
+            match resolved.get(spread) {
+                Some(spread_depth) => {
+                    stack[top].2 = depth.max(at + spread_depth.unwrap_or(0));
+                }
+                None => match fragments.get(spread) {
+                    Some(spread_local) => {
+                        resolved.insert(spread, None);
+                        stack.push((spread, 0, spread_local.max_depth));
+                    }
+                    None => stack[top].2 = depth.max(at),
+                },
+            }
+        } else {
+            stack.pop();
+            resolved.insert(current, Some(depth));
+
+            if let Some(&mut (parent, next_spread, ref mut parent_depth)) = stack.last_mut() {
+                let (_, at) = fragments[parent].spreads[next_spread - 1];
+                *parent_depth = (*parent_depth).max(at + depth);
+            }
+        }
+    }
+
+    resolved[name].unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_nesting_depth;
+
+    use crate::{
+        parser::{parse_document_source, SourcePosition},
+        schema::model::SchemaType,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        validation::{test_harness::QueryRoot, RuleError},
+        value::DefaultScalarValue,
+    };
+
+    fn check(query: &str, limit: usize) -> Vec<RuleError> {
+        let schema = SchemaType::<DefaultScalarValue>::new::<
+            QueryRoot,
+            EmptyMutation<()>,
+            EmptySubscription<()>,
+        >(&(), &(), &());
+        let doc = parse_document_source(query, &schema).expect("Parse error");
+        validate_nesting_depth(&doc, limit, &schema.messages)
+    }
+
+    #[test]
+    fn shallow_selection_passes() {
+        assert_eq!(check("{ dog { name } }", 2), vec![]);
+    }
+
+    #[test]
+    fn deep_selection_fails() {
+        assert_eq!(
+            check("{ dog { name } }", 1),
+            vec![RuleError::new(
+                "Selection exceeds the maximum nesting depth of 1",
+                &[SourcePosition::new(0, 0, 0)],
+            )],
+        );
+    }
+
+    #[test]
+    fn fragment_spreads_add_depth() {
+        let errors = check(
+            r#"
+            { dog { ...A } }
+            fragment A on Dog { ...B }
+            fragment B on Dog { ... on Dog { name } }
+            "#,
+            3,
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].locations(), &[SourcePosition::new(13, 1, 12)]);
+    }
+
+    #[test]
+    fn long_fragment_chains_do_not_overflow() {
+        let mut query = String::from("{ dog { ...F0 } }\n");
+        for i in 0..20_000 {
+            query.push_str(&format!("fragment F{} on Dog {{ ...F{} }}\n", i, i + 1));
+        }
+        query.push_str("fragment F20000 on Dog { name }\n");
+
+        let errors = check(&query, 128);
+
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn fragment_cycles_terminate() {
+        let errors = check(
+            r#"
+            { dog { ...A } }
+            fragment A on Dog { ...B }
+            fragment B on Dog { ...A }
+            "#,
+            128,
+        );
+
+        assert_eq!(errors, vec![]);
+    }
+}
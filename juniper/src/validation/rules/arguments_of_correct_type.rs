@@ -1,9 +1,9 @@
 use crate::{
     ast::{Directive, Field, InputValue},
+    messages::Message,
     parser::Spanning,
     schema::meta::Argument,
-    types::utilities::is_valid_literal_value,
-    validation::{ValidatorContext, Visitor},
+    validation::{coercion::is_valid_literal_value, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -58,8 +58,8 @@ where
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
             if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
-                ctx.report_error(
-                    &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
+                ctx.report(
+                    error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
                     &[arg_value.start],
                 );
             }
@@ -67,11 +67,11 @@ where
     }
 }
 
-fn error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
-        "Invalid value for argument \"{}\", expected type \"{}\"",
-        arg_name, type_name
-    )
+fn error_message<'a>(argument: &'a str, expected_type: &'a str) -> Message<'a> {
+    Message::InvalidArgumentValue {
+        argument,
+        expected_type,
+    }
 }
 
 #[cfg(test)]
@@ -110,7 +110,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("nonNullIntArg", "Int!"),
+                error_message("nonNullIntArg", "Int!"),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -240,7 +240,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                error_message("stringArg", "String"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -258,7 +258,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                error_message("stringArg", "String"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -276,7 +276,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                error_message("stringArg", "String"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -294,7 +294,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                error_message("stringArg", "String"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -312,7 +312,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                error_message("intArg", "Int"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -330,7 +330,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                error_message("intArg", "Int"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -348,7 +348,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                error_message("intArg", "Int"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -366,7 +366,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                error_message("intArg", "Int"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -384,7 +384,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                error_message("floatArg", "Float"),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -402,7 +402,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                error_message("floatArg", "Float"),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -420,7 +420,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                error_message("floatArg", "Float"),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -438,7 +438,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                error_message("booleanArg", "Boolean"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -456,7 +456,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                error_message("booleanArg", "Boolean"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -474,7 +474,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                error_message("booleanArg", "Boolean"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -492,7 +492,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                error_message("booleanArg", "Boolean"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -510,7 +510,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                error_message("idArg", "ID"),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -528,7 +528,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                error_message("idArg", "ID"),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -546,7 +546,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                error_message("idArg", "ID"),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -564,7 +564,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -582,7 +582,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -600,7 +600,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -618,7 +618,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -636,7 +636,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -654,7 +654,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                error_message("dogCommand", "DogCommand"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -714,7 +714,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                error_message("stringListArg", "[String]"),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -732,7 +732,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                error_message("stringListArg", "[String]"),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -891,11 +891,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("req2", "Int!"),
+                    error_message("req2", "Int!"),
                     &[SourcePosition::new(82, 3, 35)],
                 ),
                 RuleError::new(
-                    &error_message("req1", "Int!"),
+                    error_message("req1", "Int!"),
                     &[SourcePosition::new(95, 3, 48)],
                 ),
             ],
@@ -914,7 +914,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("req1", "Int!"),
+                error_message("req1", "Int!"),
                 &[SourcePosition::new(82, 3, 35)],
             )],
         );
@@ -1028,7 +1028,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                error_message("complexArg", "ComplexInput"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1049,7 +1049,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                error_message("complexArg", "ComplexInput"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1070,7 +1070,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                error_message("complexArg", "ComplexInput"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1106,11 +1106,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    error_message("if", "Boolean!"),
                     &[SourcePosition::new(38, 2, 27)],
                 ),
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    error_message("if", "Boolean!"),
                     &[SourcePosition::new(74, 3, 27)],
                 ),
             ],
@@ -1,8 +1,8 @@
 use crate::{
     ast::VariableDefinition,
+    messages::Message,
     parser::Spanning,
-    types::utilities::is_valid_literal_value,
-    validation::{ValidatorContext, Visitor},
+    validation::{coercion::is_valid_literal_value, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -28,16 +28,16 @@ where
         }) = var_def.default_value
         {
             if var_def.var_type.item.is_non_null() {
-                ctx.report_error(
-                    &non_null_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
+                ctx.report(
+                    non_null_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[*start],
                 )
             } else {
                 let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
                 if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
-                    ctx.report_error(
-                        &type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
+                    ctx.report(
+                        type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                         &[*start],
                     );
                 }
@@ -46,18 +46,18 @@ where
     }
 }
 
-fn type_error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
-        "Invalid default value for argument \"{}\", expected type \"{}\"",
-        arg_name, type_name
-    )
+fn type_error_message<'a>(variable: &'a str, expected_type: &'a str) -> Message<'a> {
+    Message::InvalidDefaultValue {
+        variable,
+        expected_type,
+    }
 }
 
-fn non_null_error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
-        "Argument \"{}\" has type \"{}\" and is not nullable, so it't can't have a default value",
-        arg_name, type_name
-    )
+fn non_null_error_message<'a>(variable: &'a str, variable_type: &'a str) -> Message<'a> {
+    Message::NonNullDefaultValue {
+        variable,
+        variable_type,
+    }
 }
 
 #[cfg(test)]
@@ -121,11 +121,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &non_null_error_message("a", "Int!"),
+                    non_null_error_message("a", "Int!"),
                     &[SourcePosition::new(53, 1, 52)],
                 ),
                 RuleError::new(
-                    &non_null_error_message("b", "String!"),
+                    non_null_error_message("b", "String!"),
                     &[SourcePosition::new(70, 1, 69)],
                 ),
             ],
@@ -147,15 +147,15 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &type_error_message("a", "Int"),
+                    type_error_message("a", "Int"),
                     &[SourcePosition::new(61, 2, 22)],
                 ),
                 RuleError::new(
-                    &type_error_message("b", "String"),
+                    type_error_message("b", "String"),
                     &[SourcePosition::new(93, 3, 25)],
                 ),
                 RuleError::new(
-                    &type_error_message("c", "ComplexInput"),
+                    type_error_message("c", "ComplexInput"),
                     &[SourcePosition::new(127, 4, 31)],
                 ),
             ],
@@ -172,7 +172,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &type_error_message("a", "ComplexInput"),
+                type_error_message("a", "ComplexInput"),
                 &[SourcePosition::new(57, 1, 56)],
             )],
         );
@@ -188,7 +188,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &type_error_message("a", "[String]"),
+                type_error_message("a", "[String]"),
                 &[SourcePosition::new(44, 1, 43)],
             )],
         );
@@ -1,8 +1,9 @@
 use crate::{
     ast::Field,
+    messages::Message,
     parser::Spanning,
-    schema::meta::MetaType,
-    validation::{ValidatorContext, Visitor},
+    schema::meta::{InterfaceMeta, MetaType, ObjectMeta},
+    validation::{suggestion::suggestion_list, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -36,8 +37,16 @@ where
                         }
                     }
 
-                    context.report_error(
-                        &error_message(field_name.item, type_name),
+                    let suggestions = match *parent_type {
+                        MetaType::Object(ObjectMeta { ref fields, .. })
+                        | MetaType::Interface(InterfaceMeta { ref fields, .. }) => {
+                            suggestion_list(field_name.item, fields.iter().map(|f| f.name.as_str()))
+                        }
+                        _ => vec![],
+                    };
+
+                    context.report(
+                        error_message(field_name.item, type_name, &suggestions),
                         &[field_name.start],
                     );
                 }
@@ -46,8 +55,16 @@ where
     }
 }
 
-fn error_message(field: &str, type_name: &str) -> String {
-    format!(r#"Unknown field "{}" on type "{}""#, field, type_name)
+fn error_message<'a>(
+    field: &'a str,
+    type_name: &'a str,
+    suggestions: &'a [&'a str],
+) -> Message<'a> {
+    Message::UnknownField {
+        field,
+        type_name,
+        suggestions,
+    }
 }
 
 #[cfg(test)]
@@ -150,11 +167,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("unknown_pet_field", "Pet"),
+                    error_message("unknown_pet_field", "Pet", &[]),
                     &[SourcePosition::new(56, 2, 12)],
                 ),
                 RuleError::new(
-                    &error_message("unknown_cat_field", "Cat"),
+                    error_message("unknown_cat_field", "Cat", &[]),
                     &[SourcePosition::new(119, 4, 16)],
                 ),
             ],
@@ -171,7 +188,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("meowVolume", "Dog"),
+                error_message("meowVolume", "Dog", &["barkVolume"]),
                 &[SourcePosition::new(57, 2, 12)],
             )],
         );
@@ -189,7 +206,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("unknown_field", "Dog"),
+                error_message("unknown_field", "Dog", &[]),
                 &[SourcePosition::new(61, 2, 12)],
             )],
         );
@@ -207,7 +224,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("unknown_field", "Pet"),
+                error_message("unknown_field", "Pet", &[]),
                 &[SourcePosition::new(83, 3, 14)],
             )],
         );
@@ -225,7 +242,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("meowVolume", "Dog"),
+                error_message("meowVolume", "Dog", &["barkVolume"]),
                 &[SourcePosition::new(84, 3, 14)],
             )],
         );
@@ -241,7 +258,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("mooVolume", "Dog"),
+                error_message("mooVolume", "Dog", &["barkVolume"]),
                 &[SourcePosition::new(79, 2, 21)],
             )],
         );
@@ -257,7 +274,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("kawVolume", "Dog"),
+                error_message("kawVolume", "Dog", &["barkVolume"]),
                 &[SourcePosition::new(88, 2, 25)],
             )],
         );
@@ -273,7 +290,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("tailLength", "Pet"),
+                error_message("tailLength", "Pet", &[]),
                 &[SourcePosition::new(63, 2, 12)],
             )],
         );
@@ -289,7 +306,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("nickname", "Pet"),
+                error_message("nickname", "Pet", &["name"]),
                 &[SourcePosition::new(78, 2, 12)],
             )],
         );
@@ -317,7 +334,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("name", "CatOrDog"),
+                error_message("name", "CatOrDog", &[]),
                 &[SourcePosition::new(82, 2, 12)],
             )],
         );
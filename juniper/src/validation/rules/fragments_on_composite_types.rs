@@ -1,5 +1,6 @@
 use crate::{
     ast::{Fragment, InlineFragment},
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -26,8 +27,8 @@ where
                     let type_name = current_type.name().unwrap_or("<unknown>");
                     let type_cond = &f.item.type_condition;
 
-                    context.report_error(
-                        &error_message(Some(f.item.name.item), type_name),
+                    context.report(
+                        error_message(Some(f.item.name.item), type_name),
                         &[type_cond.start],
                     );
                 }
@@ -50,24 +51,17 @@ where
                     .next();
 
                 if let Some(name) = invalid_type_name {
-                    context.report_error(&error_message(None, name), &[type_cond.start]);
+                    context.report(error_message(None, name), &[type_cond.start]);
                 }
             }
         }
     }
 }
 
-fn error_message(fragment_name: Option<&str>, on_type: &str) -> String {
-    if let Some(name) = fragment_name {
-        format!(
-            r#"Fragment "{}" cannot condition non composite type "{}"#,
-            name, on_type
-        )
-    } else {
-        format!(
-            r#"Fragment cannot condition on non composite type "{}""#,
-            on_type
-        )
+fn error_message<'a>(fragment: Option<&'a str>, type_name: &'a str) -> Message<'a> {
+    Message::NonCompositeFragment {
+        fragment,
+        type_name,
     }
 }
 
@@ -155,7 +149,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(Some("scalarFragment"), "Boolean"),
+                error_message(Some("scalarFragment"), "Boolean"),
                 &[SourcePosition::new(38, 1, 37)],
             )],
         );
@@ -171,7 +165,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(Some("scalarFragment"), "FurColor"),
+                error_message(Some("scalarFragment"), "FurColor"),
                 &[SourcePosition::new(38, 1, 37)],
             )],
         );
@@ -187,7 +181,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(Some("inputFragment"), "ComplexInput"),
+                error_message(Some("inputFragment"), "ComplexInput"),
                 &[SourcePosition::new(37, 1, 36)],
             )],
         );
@@ -205,7 +199,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(None, "String"),
+                error_message(None, "String"),
                 &[SourcePosition::new(64, 2, 19)],
             )],
         );
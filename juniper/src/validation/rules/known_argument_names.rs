@@ -1,8 +1,9 @@
 use crate::{
     ast::{Directive, Field, InputValue},
+    messages::Message,
     parser::Spanning,
     schema::meta::Argument,
-    validation::{ValidatorContext, Visitor},
+    validation::{suggestion::suggestion_list, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -75,33 +76,47 @@ where
     ) {
         if let Some((ref pos, args)) = self.current_args {
             if args.iter().find(|a| a.name == arg_name.item).is_none() {
+                let suggestions =
+                    suggestion_list(arg_name.item, args.iter().map(|a| a.name.as_str()));
                 let message = match *pos {
                     ArgumentPosition::Field(field_name, type_name) => {
-                        field_error_message(arg_name.item, field_name, type_name)
+                        field_error_message(arg_name.item, field_name, type_name, &suggestions)
                     }
                     ArgumentPosition::Directive(directive_name) => {
-                        directive_error_message(arg_name.item, directive_name)
+                        directive_error_message(arg_name.item, directive_name, &suggestions)
                     }
                 };
 
-                ctx.report_error(&message, &[arg_name.start]);
+                ctx.report(message, &[arg_name.start]);
             }
         }
     }
 }
 
-fn field_error_message(arg_name: &str, field_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Unknown argument "{}" on field "{}" of type "{}""#,
-        arg_name, field_name, type_name
-    )
+fn field_error_message<'a>(
+    argument: &'a str,
+    field: &'a str,
+    type_name: &'a str,
+    suggestions: &'a [&'a str],
+) -> Message<'a> {
+    Message::UnknownFieldArgument {
+        argument,
+        field,
+        type_name,
+        suggestions,
+    }
 }
 
-fn directive_error_message(arg_name: &str, directive_name: &str) -> String {
-    format!(
-        r#"Unknown argument "{}" on directive "{}""#,
-        arg_name, directive_name
-    )
+fn directive_error_message<'a>(
+    argument: &'a str,
+    directive: &'a str,
+    suggestions: &'a [&'a str],
+) -> Message<'a> {
+    Message::UnknownDirectiveArgument {
+        argument,
+        directive,
+        suggestions,
+    }
 }
 
 #[cfg(test)]
@@ -217,7 +232,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &directive_error_message("unless", "skip"),
+                directive_error_message("unless", "skip", &[]),
                 &[SourcePosition::new(35, 2, 22)],
             )],
         );
@@ -233,12 +248,39 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &field_error_message("unknown", "doesKnowCommand", "Dog"),
+                field_error_message("unknown", "doesKnowCommand", "Dog", &[]),
                 &[SourcePosition::new(72, 2, 28)],
             )],
         );
     }
 
+    #[test]
+    fn misspelled_arg_name_is_suggested() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          fragment misspelledArgName on Dog {
+            doesKnowCommand(dogcommand: SIT)
+            isHousetrained(atOtherHome: true) @include(iff: true)
+          }
+        "#,
+            &[
+                RuleError::new(
+                    field_error_message("dogcommand", "doesKnowCommand", "Dog", &["dogCommand"]),
+                    &[SourcePosition::new(75, 2, 28)],
+                ),
+                RuleError::new(
+                    field_error_message("atOtherHome", "isHousetrained", "Dog", &["atOtherHomes"]),
+                    &[SourcePosition::new(119, 3, 27)],
+                ),
+                RuleError::new(
+                    directive_error_message("iff", "include", &["if"]),
+                    &[SourcePosition::new(147, 3, 55)],
+                ),
+            ],
+        );
+    }
+
     #[test]
     fn unknown_args_amongst_known_args() {
         expect_fails_rule::<_, _, DefaultScalarValue>(
@@ -250,11 +292,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &field_error_message("whoknows", "doesKnowCommand", "Dog"),
+                    field_error_message("whoknows", "doesKnowCommand", "Dog", &[]),
                     &[SourcePosition::new(81, 2, 28)],
                 ),
                 RuleError::new(
-                    &field_error_message("unknown", "doesKnowCommand", "Dog"),
+                    field_error_message("unknown", "doesKnowCommand", "Dog", &[]),
                     &[SourcePosition::new(111, 2, 58)],
                 ),
             ],
@@ -281,11 +323,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &field_error_message("unknown", "doesKnowCommand", "Dog"),
+                    field_error_message("unknown", "doesKnowCommand", "Dog", &[]),
                     &[SourcePosition::new(61, 3, 30)],
                 ),
                 RuleError::new(
-                    &field_error_message("unknown", "doesKnowCommand", "Dog"),
+                    field_error_message("unknown", "doesKnowCommand", "Dog", &[]),
                     &[SourcePosition::new(193, 8, 34)],
                 ),
             ],
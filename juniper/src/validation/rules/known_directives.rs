@@ -1,5 +1,6 @@
 use crate::{
     ast::{Directive, Field, Fragment, FragmentSpread, InlineFragment, Operation, OperationType},
+    messages::Message,
     parser::Spanning,
     schema::model::DirectiveLocation,
     validation::{ValidatorContext, Visitor},
@@ -121,27 +122,27 @@ where
                     .find(|l| l == &current_location)
                     .is_none()
                 {
-                    ctx.report_error(
-                        &misplaced_error_message(directive_name, current_location),
+                    ctx.report(
+                        misplaced_error_message(directive_name, &current_location.to_string()),
                         &[directive.start],
                     );
                 }
             }
         } else {
-            ctx.report_error(&unknown_error_message(directive_name), &[directive.start]);
+            ctx.report(unknown_error_message(directive_name), &[directive.start]);
         }
     }
 }
 
-fn unknown_error_message(directive_name: &str) -> String {
-    format!(r#"Unknown directive "{}""#, directive_name)
+fn unknown_error_message(directive: &str) -> Message<'_> {
+    Message::UnknownDirective { directive }
 }
 
-fn misplaced_error_message(directive_name: &str, location: &DirectiveLocation) -> String {
-    format!(
-        r#"Directive "{}" may not be used on {}"#,
-        directive_name, location
-    )
+fn misplaced_error_message<'a>(directive: &'a str, location: &'a str) -> Message<'a> {
+    Message::MisplacedDirective {
+        directive,
+        location,
+    }
 }
 
 #[cfg(test)]
@@ -150,7 +151,6 @@ mod tests {
 
     use crate::{
         parser::SourcePosition,
-        schema::model::DirectiveLocation,
         validation::{expect_fails_rule, expect_passes_rule, RuleError},
         value::DefaultScalarValue,
     };
@@ -201,7 +201,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &unknown_error_message("unknown"),
+                unknown_error_message("unknown"),
                 &[SourcePosition::new(29, 2, 16)],
             )],
         );
@@ -226,15 +226,15 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &unknown_error_message("unknown"),
+                    unknown_error_message("unknown"),
                     &[SourcePosition::new(29, 2, 16)],
                 ),
                 RuleError::new(
-                    &unknown_error_message("unknown"),
+                    unknown_error_message("unknown"),
                     &[SourcePosition::new(111, 5, 18)],
                 ),
                 RuleError::new(
-                    &unknown_error_message("unknown"),
+                    unknown_error_message("unknown"),
                     &[SourcePosition::new(180, 7, 19)],
                 ),
             ],
@@ -276,19 +276,19 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &misplaced_error_message("include", &DirectiveLocation::Query),
+                    misplaced_error_message("include", "query"),
                     &[SourcePosition::new(21, 1, 20)],
                 ),
                 RuleError::new(
-                    &misplaced_error_message("onQuery", &DirectiveLocation::Field),
+                    misplaced_error_message("onQuery", "field"),
                     &[SourcePosition::new(59, 2, 17)],
                 ),
                 RuleError::new(
-                    &misplaced_error_message("onQuery", &DirectiveLocation::FragmentSpread),
+                    misplaced_error_message("onQuery", "fragment spread"),
                     &[SourcePosition::new(88, 3, 20)],
                 ),
                 RuleError::new(
-                    &misplaced_error_message("onQuery", &DirectiveLocation::Mutation),
+                    misplaced_error_message("onQuery", "mutation"),
                     &[SourcePosition::new(133, 6, 23)],
                 ),
             ],
@@ -1,5 +1,6 @@
 use crate::{
     ast::FragmentSpread,
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -22,13 +23,13 @@ where
     ) {
         let spread_name = &spread.item.name;
         if !context.is_known_fragment(spread_name.item) {
-            context.report_error(&error_message(spread_name.item), &[spread_name.start]);
+            context.report(error_message(spread_name.item), &[spread_name.start]);
         }
     }
 }
 
-fn error_message(frag_name: &str) -> String {
-    format!(r#"Unknown fragment: "{}""#, frag_name)
+fn error_message(fragment: &str) -> Message<'_> {
+    Message::UnknownFragment { fragment }
 }
 
 #[cfg(test)]
@@ -91,15 +92,15 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("UnknownFragment1"),
+                    error_message("UnknownFragment1"),
                     &[SourcePosition::new(57, 3, 17)],
                 ),
                 RuleError::new(
-                    &error_message("UnknownFragment2"),
+                    error_message("UnknownFragment2"),
                     &[SourcePosition::new(122, 5, 19)],
                 ),
                 RuleError::new(
-                    &error_message("UnknownFragment3"),
+                    error_message("UnknownFragment3"),
                     &[SourcePosition::new(255, 11, 15)],
                 ),
             ],
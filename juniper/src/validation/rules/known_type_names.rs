@@ -1,5 +1,6 @@
 use crate::{
     ast::{Fragment, InlineFragment, VariableDefinition},
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -51,12 +52,12 @@ fn validate_type<'a, S: Debug>(
     location: &SourcePosition,
 ) {
     if ctx.schema.type_by_name(type_name).is_none() {
-        ctx.report_error(&error_message(type_name), &[*location]);
+        ctx.report(error_message(type_name), &[*location]);
     }
 }
 
-fn error_message(type_name: &str) -> String {
-    format!(r#"Unknown type "{}""#, type_name)
+fn error_message(type_name: &str) -> Message<'_> {
+    Message::UnknownType { type_name }
 }
 
 #[cfg(test)]
@@ -103,11 +104,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("JumbledUpLetters"),
+                    error_message("JumbledUpLetters"),
                     &[SourcePosition::new(27, 1, 26)],
                 ),
-                RuleError::new(&error_message("Badger"), &[SourcePosition::new(120, 4, 28)]),
-                RuleError::new(&error_message("Peettt"), &[SourcePosition::new(210, 7, 32)]),
+                RuleError::new(error_message("Badger"), &[SourcePosition::new(120, 4, 28)]),
+                RuleError::new(error_message("Peettt"), &[SourcePosition::new(210, 7, 32)]),
             ],
         );
     }
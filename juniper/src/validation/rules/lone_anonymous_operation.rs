@@ -1,5 +1,6 @@
 use crate::{
     ast::{Definition, Document, Operation},
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -37,14 +38,14 @@ where
     ) {
         if let Some(operation_count) = self.operation_count {
             if operation_count > 1 && op.item.name.is_none() {
-                ctx.report_error(error_message(), &[op.start]);
+                ctx.report(error_message(), &[op.start]);
             }
         }
     }
 }
 
-fn error_message() -> &'static str {
-    "This anonymous operation must be the only defined operation"
+fn error_message() -> Message<'static> {
+    Message::AnonymousOperationNotAlone
 }
 
 #[cfg(test)]
@@ -12,6 +12,7 @@ mod no_undefined_variables;
 mod no_unused_fragments;
 mod no_unused_variables;
 mod overlapping_fields_can_be_merged;
+mod paginated_fields;
 mod possible_fragment_spreads;
 mod provided_non_null_arguments;
 mod scalar_leafs;
@@ -25,15 +26,26 @@ mod variables_in_allowed_position;
 
 use crate::{
     ast::Document,
-    validation::{visit, MultiVisitorNil, ValidatorContext},
+    parser::DEFAULT_RECURSION_LIMIT,
+    validation::{nesting_depth::validate_nesting_depth, visit, MultiVisitorNil, ValidatorContext},
     value::ScalarValue,
 };
 use std::fmt::Debug;
 
+pub(crate) use self::paginated_fields::{pagination, Pagination};
+
 pub(crate) fn visit_all_rules<'a, S: Debug>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
 where
     S: ScalarValue,
 {
+    // Rules below follow fragment spreads recursively, so overly deep documents
+    // must be rejected before running them.
+    let depth_errors = validate_nesting_depth(doc, DEFAULT_RECURSION_LIMIT, &ctx.schema.messages);
+    if !depth_errors.is_empty() {
+        ctx.append_errors(depth_errors);
+        return;
+    }
+
     let mut mv = MultiVisitorNil
         .with(self::arguments_of_correct_type::factory())
         .with(self::default_values_of_correct_type::factory())
@@ -49,6 +61,7 @@ where
         .with(self::no_unused_fragments::factory())
         .with(self::no_unused_variables::factory())
         .with(self::overlapping_fields_can_be_merged::factory())
+        .with(self::paginated_fields::factory())
         .with(self::possible_fragment_spreads::factory())
         .with(self::provided_non_null_arguments::factory())
         .with(self::scalar_leafs::factory())
@@ -62,3 +75,54 @@ where
 
     visit(&mut mv, ctx, doc)
 }
+
+/// Visits the document with the rule named `name` only, as named by
+/// [graphql-js](https://github.com/graphql/graphql-js), returning whether the
+/// rule is known
+#[cfg(test)]
+pub(crate) fn visit_rule<'a, S: Debug>(
+    name: &str,
+    ctx: &mut ValidatorContext<'a, S>,
+    doc: &'a Document<S>,
+) -> bool
+where
+    S: ScalarValue,
+{
+    macro_rules! visit_rule {
+        ($($name:literal => $rule:ident,)*) => {
+            match name {
+                $($name => visit(&mut MultiVisitorNil.with(self::$rule::factory()), ctx, doc),)*
+                _ => return false,
+            }
+        };
+    }
+
+    visit_rule! {
+        "ArgumentsOfCorrectType" => arguments_of_correct_type,
+        "DefaultValuesOfCorrectType" => default_values_of_correct_type,
+        "FieldsOnCorrectType" => fields_on_correct_type,
+        "FragmentsOnCompositeTypes" => fragments_on_composite_types,
+        "KnownArgumentNames" => known_argument_names,
+        "KnownDirectives" => known_directives,
+        "KnownFragmentNames" => known_fragment_names,
+        "KnownTypeNames" => known_type_names,
+        "LoneAnonymousOperation" => lone_anonymous_operation,
+        "NoFragmentCycles" => no_fragment_cycles,
+        "NoUndefinedVariables" => no_undefined_variables,
+        "NoUnusedFragments" => no_unused_fragments,
+        "NoUnusedVariables" => no_unused_variables,
+        "OverlappingFieldsCanBeMerged" => overlapping_fields_can_be_merged,
+        "PossibleFragmentSpreads" => possible_fragment_spreads,
+        "ProvidedNonNullArguments" => provided_non_null_arguments,
+        "ScalarLeafs" => scalar_leafs,
+        "UniqueArgumentNames" => unique_argument_names,
+        "UniqueFragmentNames" => unique_fragment_names,
+        "UniqueInputFieldNames" => unique_input_field_names,
+        "UniqueOperationNames" => unique_operation_names,
+        "UniqueVariableNames" => unique_variable_names,
+        "VariablesAreInputTypes" => variables_are_input_types,
+        "VariablesInAllowedPosition" => variables_in_allowed_position,
+    }
+
+    true
+}
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     ast::{Document, Fragment, FragmentSpread},
+    messages::{Message, Messages},
     parser::Spanning,
     validation::{RuleError, ValidatorContext, Visitor},
     value::ScalarValue,
@@ -17,6 +18,7 @@ struct CycleDetector<'a> {
     visited: HashSet<&'a str>,
     spreads: &'a HashMap<&'a str, Vec<Spanning<&'a str>>>,
     path_indices: HashMap<&'a str, usize>,
+    messages: &'a Messages,
     errors: Vec<RuleError>,
 }
 
@@ -39,6 +41,7 @@ where
             visited: HashSet::new(),
             spreads: &self.spreads,
             path_indices: HashMap::new(),
+            messages: &ctx.schema.messages,
             errors: Vec::new(),
         };
 
@@ -112,8 +115,10 @@ impl<'a> CycleDetector<'a> {
                     node
                 };
 
-                self.errors
-                    .push(RuleError::new(&error_message(name), &[err_pos.start]));
+                self.errors.push(RuleError::new(
+                    self.messages.render(&error_message(name)),
+                    &[err_pos.start],
+                ));
             } else if !self.visited.contains(name) {
                 path.push(node);
                 self.detect_from(name, path);
@@ -125,8 +130,8 @@ impl<'a> CycleDetector<'a> {
     }
 }
 
-fn error_message(frag_name: &str) -> String {
-    format!(r#"Cannot spread fragment "{}""#, frag_name)
+fn error_message(fragment: &str) -> Message<'_> {
+    Message::FragmentCycle { fragment }
 }
 
 #[cfg(test)]
@@ -211,7 +216,7 @@ mod tests {
           fragment fragA on Human { relatives { ...fragA } },
         "#,
             &[RuleError::new(
-                &error_message("fragA"),
+                error_message("fragA"),
                 &[SourcePosition::new(49, 1, 48)],
             )],
         );
@@ -225,7 +230,7 @@ mod tests {
           fragment fragA on Dog { ...fragA }
         "#,
             &[RuleError::new(
-                &error_message("fragA"),
+                error_message("fragA"),
                 &[SourcePosition::new(35, 1, 34)],
             )],
         );
@@ -243,7 +248,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("fragA"),
+                error_message("fragA"),
                 &[SourcePosition::new(74, 3, 14)],
             )],
         );
@@ -258,7 +263,7 @@ mod tests {
           fragment fragB on Dog { ...fragA }
         "#,
             &[RuleError::new(
-                &error_message("fragA"),
+                error_message("fragA"),
                 &[SourcePosition::new(35, 1, 34)],
             )],
         );
@@ -273,7 +278,7 @@ mod tests {
           fragment fragA on Dog { ...fragB }
         "#,
             &[RuleError::new(
-                &error_message("fragB"),
+                error_message("fragB"),
                 &[SourcePosition::new(35, 1, 34)],
             )],
         );
@@ -296,7 +301,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("fragA"),
+                error_message("fragA"),
                 &[SourcePosition::new(74, 3, 14)],
             )],
         );
@@ -317,8 +322,8 @@ mod tests {
           fragment fragP on Dog { ...fragA, ...fragX }
         "#,
             &[
-                RuleError::new(&error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
-                RuleError::new(&error_message("fragO"), &[SourcePosition::new(305, 7, 34)]),
+                RuleError::new(error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
+                RuleError::new(error_message("fragO"), &[SourcePosition::new(305, 7, 34)]),
             ],
         );
     }
@@ -333,8 +338,8 @@ mod tests {
           fragment fragC on Dog { ...fragA }
         "#,
             &[
-                RuleError::new(&error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
-                RuleError::new(&error_message("fragA"), &[SourcePosition::new(45, 1, 44)]),
+                RuleError::new(error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
+                RuleError::new(error_message("fragA"), &[SourcePosition::new(45, 1, 44)]),
             ],
         );
     }
@@ -349,8 +354,8 @@ mod tests {
           fragment fragC on Dog { ...fragA, ...fragB }
         "#,
             &[
-                RuleError::new(&error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
-                RuleError::new(&error_message("fragC"), &[SourcePosition::new(135, 3, 44)]),
+                RuleError::new(error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
+                RuleError::new(error_message("fragC"), &[SourcePosition::new(135, 3, 44)]),
             ],
         );
     }
@@ -365,9 +370,9 @@ mod tests {
           fragment fragC on Dog { ...fragA, ...fragB }
         "#,
             &[
-                RuleError::new(&error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
-                RuleError::new(&error_message("fragB"), &[SourcePosition::new(80, 2, 34)]),
-                RuleError::new(&error_message("fragB"), &[SourcePosition::new(90, 2, 44)]),
+                RuleError::new(error_message("fragA"), &[SourcePosition::new(35, 1, 34)]),
+                RuleError::new(error_message("fragB"), &[SourcePosition::new(80, 2, 34)]),
+                RuleError::new(error_message("fragB"), &[SourcePosition::new(90, 2, 44)]),
             ],
         );
     }
@@ -1,7 +1,8 @@
 use crate::{
     ast::{Document, Fragment, FragmentSpread, InputValue, Operation, VariableDefinition},
+    messages::Message,
     parser::{SourcePosition, Spanning},
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::collections::{HashMap, HashSet};
@@ -73,14 +74,9 @@ where
                 &mut visited,
             );
 
-            ctx.append_errors(
-                unused
-                    .into_iter()
-                    .map(|var| {
-                        RuleError::new(&error_message(var.item, *op_name), &[var.start, *pos])
-                    })
-                    .collect(),
-            );
+            for var in unused {
+                ctx.report(error_message(var.item, *op_name), &[var.start, *pos]);
+            }
         }
     }
 
@@ -149,14 +145,10 @@ where
     }
 }
 
-fn error_message(var_name: &str, op_name: Option<&str>) -> String {
-    if let Some(op_name) = op_name {
-        format!(
-            r#"Variable "${}" is not defined by operation "{}""#,
-            var_name, op_name
-        )
-    } else {
-        format!(r#"Variable "${}" is not defined"#, var_name)
+fn error_message<'a>(variable: &'a str, operation: Option<&'a str>) -> Message<'a> {
+    Message::UndefinedVariable {
+        variable,
+        operation,
     }
 }
 
@@ -309,7 +301,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("d", Some("Foo")),
+                error_message("d", Some("Foo")),
                 &[
                     SourcePosition::new(101, 2, 42),
                     SourcePosition::new(11, 1, 10),
@@ -328,7 +320,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("a", None),
+                error_message("a", None),
                 &[
                     SourcePosition::new(34, 2, 21),
                     SourcePosition::new(11, 1, 10),
@@ -348,14 +340,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(56, 2, 21),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Foo")),
+                    error_message("c", Some("Foo")),
                     &[
                         SourcePosition::new(70, 2, 35),
                         SourcePosition::new(11, 1, 10),
@@ -378,7 +370,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("a", None),
+                error_message("a", None),
                 &[
                     SourcePosition::new(102, 5, 21),
                     SourcePosition::new(11, 1, 10),
@@ -410,7 +402,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("c", Some("Foo")),
+                error_message("c", Some("Foo")),
                 &[
                     SourcePosition::new(358, 15, 21),
                     SourcePosition::new(11, 1, 10),
@@ -443,14 +435,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(124, 5, 21),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Foo")),
+                    error_message("c", Some("Foo")),
                     &[
                         SourcePosition::new(346, 15, 21),
                         SourcePosition::new(11, 1, 10),
@@ -477,14 +469,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("b", Some("Foo")),
+                    error_message("b", Some("Foo")),
                     &[
                         SourcePosition::new(201, 8, 28),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("b", Some("Bar")),
+                    error_message("b", Some("Bar")),
                     &[
                         SourcePosition::new(201, 8, 28),
                         SourcePosition::new(79, 4, 10),
@@ -511,14 +503,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(194, 8, 21),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("b", Some("Bar")),
+                    error_message("b", Some("Bar")),
                     &[
                         SourcePosition::new(201, 8, 28),
                         SourcePosition::new(79, 4, 10),
@@ -548,14 +540,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(191, 8, 21),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("b", Some("Bar")),
+                    error_message("b", Some("Bar")),
                     &[
                         SourcePosition::new(263, 11, 21),
                         SourcePosition::new(78, 4, 10),
@@ -587,42 +579,42 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(195, 8, 22),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("b", Some("Bar")),
+                    error_message("b", Some("Bar")),
                     &[
                         SourcePosition::new(202, 8, 29),
                         SourcePosition::new(79, 4, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[
                         SourcePosition::new(249, 10, 22),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("b", Some("Bar")),
+                    error_message("b", Some("Bar")),
                     &[
                         SourcePosition::new(256, 10, 29),
                         SourcePosition::new(79, 4, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Foo")),
+                    error_message("c", Some("Foo")),
                     &[
                         SourcePosition::new(329, 13, 22),
                         SourcePosition::new(11, 1, 10),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Bar")),
+                    error_message("c", Some("Bar")),
                     &[
                         SourcePosition::new(329, 13, 22),
                         SourcePosition::new(79, 4, 10),
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     ast::{Definition, Document, Fragment, FragmentSpread, Operation},
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -65,7 +66,7 @@ where
 
         for fragment in &self.defined_fragments {
             if !reachable.contains(&fragment.item) {
-                ctx.report_error(&error_message(fragment.item), &[fragment.start]);
+                ctx.report(error_message(fragment.item), &[fragment.start]);
             }
         }
     }
@@ -103,8 +104,8 @@ where
     }
 }
 
-fn error_message(frag_name: &str) -> String {
-    format!(r#"Fragment "{}" is never used"#, frag_name)
+fn error_message(fragment: &str) -> Message<'_> {
+    Message::UnusedFragment { fragment }
 }
 
 #[cfg(test)]
@@ -207,11 +208,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("Unused1"),
+                    error_message("Unused1"),
                     &[SourcePosition::new(465, 21, 10)],
                 ),
                 RuleError::new(
-                    &error_message("Unused2"),
+                    error_message("Unused2"),
                     &[SourcePosition::new(532, 24, 10)],
                 ),
             ],
@@ -254,11 +255,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("Unused1"),
+                    error_message("Unused1"),
                     &[SourcePosition::new(465, 21, 10)],
                 ),
                 RuleError::new(
-                    &error_message("Unused2"),
+                    error_message("Unused2"),
                     &[SourcePosition::new(555, 25, 10)],
                 ),
             ],
@@ -280,7 +281,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("foo"),
+                error_message("foo"),
                 &[SourcePosition::new(107, 6, 10)],
             )],
         );
@@ -1,7 +1,8 @@
 use crate::{
     ast::{Document, Fragment, FragmentSpread, InputValue, Operation, VariableDefinition},
+    messages::Message,
     parser::Spanning,
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::collections::{HashMap, HashSet};
@@ -73,13 +74,9 @@ where
                 &mut visited,
             );
 
-            ctx.append_errors(
-                def_vars
-                    .iter()
-                    .filter(|var| !used.contains(var.item))
-                    .map(|var| RuleError::new(&error_message(var.item, *op_name), &[var.start]))
-                    .collect(),
-            );
+            for var in def_vars.iter().filter(|var| !used.contains(var.item)) {
+                ctx.report(error_message(var.item, *op_name), &[var.start]);
+            }
         }
     }
 
@@ -140,14 +137,10 @@ where
     }
 }
 
-fn error_message(var_name: &str, op_name: Option<&str>) -> String {
-    if let Some(op_name) = op_name {
-        format!(
-            r#"Variable "${}" is not used by operation "{}""#,
-            var_name, op_name
-        )
-    } else {
-        format!(r#"Variable "${}" is not used"#, var_name)
+fn error_message<'a>(variable: &'a str, operation: Option<&'a str>) -> Message<'a> {
+    Message::UnusedVariable {
+        variable,
+        operation,
     }
 }
 
@@ -282,7 +275,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("c", None),
+                error_message("c", None),
                 &[SourcePosition::new(42, 1, 41)],
             )],
         );
@@ -299,11 +292,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[SourcePosition::new(21, 1, 20)],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Foo")),
+                    error_message("c", Some("Foo")),
                     &[SourcePosition::new(45, 1, 44)],
                 ),
             ],
@@ -333,7 +326,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("c", Some("Foo")),
+                error_message("c", Some("Foo")),
                 &[SourcePosition::new(45, 1, 44)],
             )],
         );
@@ -363,11 +356,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("a", Some("Foo")),
+                    error_message("a", Some("Foo")),
                     &[SourcePosition::new(21, 1, 20)],
                 ),
                 RuleError::new(
-                    &error_message("c", Some("Foo")),
+                    error_message("c", Some("Foo")),
                     &[SourcePosition::new(45, 1, 44)],
                 ),
             ],
@@ -390,7 +383,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("b", Some("Foo")),
+                error_message("b", Some("Foo")),
                 &[SourcePosition::new(21, 1, 20)],
             )],
         );
@@ -416,11 +409,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("b", Some("Foo")),
+                    error_message("b", Some("Foo")),
                     &[SourcePosition::new(21, 1, 20)],
                 ),
                 RuleError::new(
-                    &error_message("a", Some("Bar")),
+                    error_message("a", Some("Bar")),
                     &[SourcePosition::new(88, 4, 20)],
                 ),
             ],
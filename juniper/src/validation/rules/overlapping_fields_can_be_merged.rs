@@ -1,7 +1,15 @@
-use std::{borrow::Borrow, cell::RefCell, collections::HashMap, fmt::Debug, hash::Hash};
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    ptr,
+};
 
 use crate::{
     ast::{Arguments, Definition, Document, Field, Fragment, FragmentSpread, Selection, Type},
+    messages,
     parser::{SourcePosition, Spanning},
     schema::meta::{Field as FieldType, MetaType},
     validation::{ValidatorContext, Visitor},
@@ -162,9 +170,11 @@ impl<'a, S: Debug> OverlappingFieldsCanBeMerged<'a, S> {
 
         self.collect_conflicts_within(&mut conflicts, &field_map, ctx);
 
+        let mut compared_fragments = HashSet::new();
         for (i, frag_name1) in fragment_names.iter().enumerate() {
             self.collect_conflicts_between_fields_and_fragment(
                 &mut conflicts,
+                &mut compared_fragments,
                 &field_map,
                 frag_name1,
                 false,
@@ -261,16 +271,25 @@ impl<'a, S: Debug> OverlappingFieldsCanBeMerged<'a, S> {
         }
     }
 
+    /// Collects the conflicts between the fields and the ones of the fragment
+    /// and of the fragments it spreads, `compared_fragments` holding the
+    /// fragments already compared to the fields, so each of them is only
+    /// compared once, and recursive fragments don't recurse forever.
     fn collect_conflicts_between_fields_and_fragment(
         &self,
         conflicts: &mut Vec<Conflict>,
+        compared_fragments: &mut HashSet<&'a str>,
         field_map: &AstAndDefCollection<'a, S>,
-        fragment_name: &str,
+        fragment_name: &'a str,
         mutually_exclusive: bool,
         ctx: &ValidatorContext<'a, S>,
     ) where
         S: ScalarValue,
     {
+        if !compared_fragments.insert(fragment_name) {
+            return;
+        }
+
         let fragment = match self.named_fragments.get(fragment_name) {
             Some(f) => f,
             None => return,
@@ -279,11 +298,17 @@ impl<'a, S: Debug> OverlappingFieldsCanBeMerged<'a, S> {
         let (field_map2, fragment_names2) =
             self.get_referenced_fields_and_fragment_names(fragment, ctx);
 
+        // Do not compare the fields of a fragment spreading itself to themselves.
+        if is_same_field_map(field_map, &field_map2) {
+            return;
+        }
+
         self.collect_conflicts_between(conflicts, mutually_exclusive, field_map, &field_map2, ctx);
 
         for fragment_name2 in fragment_names2 {
             self.collect_conflicts_between_fields_and_fragment(
                 conflicts,
+                compared_fragments,
                 field_map,
                 fragment_name2,
                 mutually_exclusive,
@@ -457,9 +482,11 @@ impl<'a, S: Debug> OverlappingFieldsCanBeMerged<'a, S> {
             ctx,
         );
 
+        let mut compared_fragments = HashSet::new();
         for fragment_name in &fragment_names2 {
             self.collect_conflicts_between_fields_and_fragment(
                 &mut conflicts,
+                &mut compared_fragments,
                 &field_map1,
                 fragment_name,
                 mutually_exclusive,
@@ -467,9 +494,11 @@ impl<'a, S: Debug> OverlappingFieldsCanBeMerged<'a, S> {
             );
         }
 
+        let mut compared_fragments = HashSet::new();
         for fragment_name in &fragment_names1 {
             self.collect_conflicts_between_fields_and_fragment(
                 &mut conflicts,
+                &mut compared_fragments,
                 &field_map2,
                 fragment_name,
                 mutually_exclusive,
@@ -702,19 +731,35 @@ where
             self.find_conflicts_within_selection_set(ctx.parent_type(), selection_set, ctx)
         {
             p1.append(&mut p2);
-            ctx.report_error(&error_message(&reason_name, &reason_msg), &p1);
+            let reason = format_reason(&reason_msg);
+            ctx.report(error_message(&reason_name, &reason), &p1);
         }
     }
 }
 
-fn error_message(reason_name: &str, reason: &ConflictReasonMessage) -> String {
-    let suffix = "Use different aliases on the fields to fetch both if this was intentional";
-    format!(
-        r#"Fields "{}" conflict because {}. {}"#,
-        reason_name,
-        format_reason(reason),
-        suffix
-    )
+/// Whether the field maps were collected from the same selection set.
+fn is_same_field_map<S: Debug>(
+    field_map1: &AstAndDefCollection<S>,
+    field_map2: &AstAndDefCollection<S>,
+) -> bool {
+    field_map1.insert_order.len() == field_map2.insert_order.len()
+        && field_map1
+            .iter()
+            .zip(field_map2.iter())
+            .all(|((_, fields1), (_, fields2))| {
+                fields1.len() == fields2.len()
+                    && fields1
+                        .iter()
+                        .zip(fields2)
+                        .all(|(AstAndDef(_, ast1, _), AstAndDef(_, ast2, _))| ptr::eq(*ast1, *ast2))
+            })
+}
+
+fn error_message<'a>(response_name: &'a str, reason: &'a str) -> messages::Message<'a> {
+    messages::Message::FieldsConflict {
+        response_name,
+        reason,
+    }
 }
 
 fn format_reason(reason: &ConflictReasonMessage) -> String {
@@ -736,7 +781,9 @@ fn format_reason(reason: &ConflictReasonMessage) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{error_message, factory, ConflictReason, ConflictReasonMessage::*};
+    use super::{
+        factory, format_reason, ConflictReason, ConflictReasonMessage, ConflictReasonMessage::*,
+    };
 
     use crate::{
         executor::Registry,
@@ -756,6 +803,10 @@ mod tests {
         value::{DefaultScalarValue, ScalarValue},
     };
 
+    fn error_message(reason_name: &str, reason: &ConflictReasonMessage) -> String {
+        super::error_message(reason_name, &format_reason(reason)).to_string()
+    }
+
     #[test]
     fn unique_fields() {
         expect_passes_rule::<_, _, DefaultScalarValue>(
@@ -858,7 +909,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "fido",
                     &Message("name and nickname are different fields".to_owned()),
                 ),
@@ -898,7 +949,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "name",
                     &Message("nickname and name are different fields".to_owned()),
                 ),
@@ -921,7 +972,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "doesKnowCommand",
                     &Message("they have differing arguments".to_owned()),
                 ),
@@ -944,7 +995,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "doesKnowCommand",
                     &Message("they have differing arguments".to_owned()),
                 ),
@@ -967,7 +1018,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "doesKnowCommand",
                     &Message("they have differing arguments".to_owned()),
                 ),
@@ -979,6 +1030,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn allows_different_order_of_args() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            complicatedArgs {
+              multipleReqs(req1: 1, req2: 2)
+              multipleReqs(req2: 2, req1: 1)
+            }
+          }
+        "#,
+        );
+    }
+
     #[test]
     fn allows_different_args_where_no_conflict_is_possible() {
         expect_passes_rule::<_, _, DefaultScalarValue>(
@@ -1013,7 +1079,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "x",
                     &Message("name and barks are different fields".to_owned()),
                 ),
@@ -1054,7 +1120,7 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message(
+                    error_message(
                         "x",
                         &Message("name and barks are different fields".to_owned()),
                     ),
@@ -1064,7 +1130,7 @@ mod tests {
                     ],
                 ),
                 RuleError::new(
-                    &error_message(
+                    error_message(
                         "x",
                         &Message("name and nickname are different fields".to_owned()),
                     ),
@@ -1074,7 +1140,7 @@ mod tests {
                     ],
                 ),
                 RuleError::new(
-                    &error_message(
+                    error_message(
                         "x",
                         &Message("barks and nickname are different fields".to_owned()),
                     ),
@@ -1102,7 +1168,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "dog",
                     &Nested(vec![ConflictReason(
                         "x".to_owned(),
@@ -1136,7 +1202,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "dog",
                     &Nested(vec![
                         ConflictReason(
@@ -1180,7 +1246,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "human",
                     &Nested(vec![ConflictReason(
                         "relatives".to_owned(),
@@ -1224,7 +1290,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "relatives",
                     &Nested(vec![ConflictReason(
                         "x".to_owned(),
@@ -1271,7 +1337,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "relatives",
                     &Nested(vec![ConflictReason(
                         "x".to_owned(),
@@ -1317,7 +1383,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "dog",
                     &Nested(vec![
                         ConflictReason(
@@ -1365,6 +1431,238 @@ mod tests {
         );
     }
 
+    #[test]
+    fn does_not_infinite_loop_on_recursive_fragment() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            ...fragA
+          }
+
+          fragment fragA on Human { name, relatives { name, ...fragA } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn does_not_infinite_loop_on_immediately_recursive_fragment() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            ...fragA
+          }
+
+          fragment fragA on Human { name, ...fragA }
+        "#,
+        );
+    }
+
+    #[test]
+    fn does_not_infinite_loop_on_transitively_recursive_fragment() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            ...fragA
+            fragB
+          }
+
+          fragment fragA on Human { name, ...fragB }
+          fragment fragB on Human { name, ...fragC }
+          fragment fragC on Human { name, ...fragA }
+        "#,
+        );
+    }
+
+    #[test]
+    fn finds_invalid_case_even_with_immediately_recursive_fragment() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          fragment sameAliasesWithDifferentFieldTargets on Dog {
+            ...sameAliasesWithDifferentFieldTargets
+            fido: name
+            fido: nickname
+          }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "fido",
+                    &Message("name and nickname are different fields".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(130, 3, 12),
+                    SourcePosition::new(153, 4, 12),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn finds_invalid_case_even_with_field_named_after_fragment() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            dog {
+              fragA
+              ...fragA
+            }
+          }
+
+          fragment fragA on Dog {
+            fragA: name
+          }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "fragA",
+                    &Message("fragA and name are different fields".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(45, 3, 14),
+                    SourcePosition::new(147, 9, 12),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn encounters_conflict_in_fragments_nested_in_union_members() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            catOrDog {
+              ... on Dog {
+                ...dogName
+              }
+              ... on Dog {
+                ...dogBarks
+              }
+            }
+          }
+
+          fragment dogName on Dog {
+            x: name
+          }
+
+          fragment dogBarks on Dog {
+            x: barks
+          }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "x",
+                    &Message("name and barks are different fields".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(252, 13, 12),
+                    SourcePosition::new(322, 17, 12),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn allows_exclusive_union_members_in_nested_fragments() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            catOrDog {
+              ...catVolume
+              ...dogVolume
+            }
+          }
+
+          fragment catVolume on CatOrDog {
+            ... on Cat {
+              volume: meowVolume
+            }
+          }
+
+          fragment dogVolume on CatOrDog {
+            ... on Dog {
+              volume: barkVolume
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn encounters_conflict_between_interface_and_member_fragments() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            pet {
+              ...petName
+              ...dogName
+            }
+          }
+
+          fragment petName on Pet {
+            name
+          }
+
+          fragment dogName on Dog {
+            name: nickname
+          }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "name",
+                    &Message("name and nickname are different fields".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(156, 9, 12),
+                    SourcePosition::new(222, 13, 12),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn encounters_conflict_between_fields_and_fragments_nested_in_fragments() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            dorOrHuman {
+              ... on Dog {
+                x: name
+              }
+              ...humanOrDog
+            }
+          }
+
+          fragment humanOrDog on DogOrHuman {
+            ... on Dog {
+              ...dogBarks
+            }
+          }
+
+          fragment dogBarks on Dog {
+            x: barks
+          }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "x",
+                    &Message("name and barks are different fields".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(81, 4, 16),
+                    SourcePosition::new(333, 17, 12),
+                ],
+            )],
+        );
+    }
+
     struct SomeBox;
     struct StringBox;
     struct IntBox;
@@ -1427,6 +1725,7 @@ mod tests {
                 registry.field::<Option<String>>("scalar", i),
                 registry.field::<Option<StringBox>>("deepBox", i),
                 registry.field::<Option<String>>("unrelatedField", i),
+                registry.field::<Option<String>>("otherField", i),
                 registry.field::<Option<Vec<Option<StringBox>>>>("listStringBox", i),
                 registry.field::<Option<StringBox>>("stringBox", i),
                 registry.field::<Option<IntBox>>("intBox", i),
@@ -1467,6 +1766,7 @@ mod tests {
                 registry.field::<Option<i32>>("scalar", i),
                 registry.field::<Option<IntBox>>("deepBox", i),
                 registry.field::<Option<String>>("unrelatedField", i),
+                registry.field::<Option<String>>("otherField", i),
                 registry.field::<Option<Vec<Option<StringBox>>>>("listStringBox", i),
                 registry.field::<Option<StringBox>>("stringBox", i),
                 registry.field::<Option<IntBox>>("intBox", i),
@@ -1537,6 +1837,7 @@ mod tests {
                 registry.field::<String>("scalar", i),
                 registry.field::<Option<SomeBox>>("deepBox", i),
                 registry.field::<Option<String>>("unrelatedField", i),
+                registry.field::<Option<String>>("otherField", i),
             ];
 
             registry
@@ -1607,6 +1908,7 @@ mod tests {
                 registry.field::<String>("scalar", i),
                 registry.field::<Option<SomeBox>>("deepBox", i),
                 registry.field::<Option<String>>("unrelatedField", i),
+                registry.field::<Option<String>>("otherField", i),
             ];
 
             registry
@@ -1780,7 +2082,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "scalar",
                     &Message("they return conflicting types Int and String!".to_owned()),
                 ),
@@ -1844,7 +2146,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "scalar",
                     &Message("they return conflicting types Int and String".to_owned()),
                 ),
@@ -1907,7 +2209,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "other",
                     &Nested(vec![ConflictReason(
                         "otherField".to_owned(),
@@ -1943,7 +2245,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "scalar",
                     &Message("they return conflicting types String! and String".to_owned()),
                 ),
@@ -1978,7 +2280,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "box",
                     &Message("they return conflicting types [StringBox] and StringBox".to_owned()),
                 ),
@@ -2010,7 +2312,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "box",
                     &Message("they return conflicting types StringBox and [StringBox]".to_owned()),
                 ),
@@ -2046,7 +2348,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "val",
                     &Message("scalar and unrelatedField are different fields".to_owned()),
                 ),
@@ -2081,7 +2383,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "box",
                     &Nested(vec![ConflictReason(
                         "scalar".to_owned(),
@@ -2212,7 +2514,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message(
+                error_message(
                     "edges",
                     &Nested(vec![ConflictReason(
                         "node".to_owned(),
@@ -2263,6 +2565,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn disallows_differing_return_types_of_union_members_in_nested_fragments() {
+        expect_fails_rule_with_schema::<_, EmptyMutation<()>, _, _, DefaultScalarValue>(
+            QueryRoot,
+            EmptyMutation::new(),
+            factory,
+            r#"
+            {
+              someBox {
+                ...intBoxScalar
+                ...stringBoxScalar
+              }
+            }
+
+            fragment intBoxScalar on SomeBox {
+              ... on IntBox {
+                scalar
+              }
+            }
+
+            fragment stringBoxScalar on SomeBox {
+              ... on StringBox {
+                ...scalar
+              }
+            }
+
+            fragment scalar on StringBox {
+              scalar
+            }
+        "#,
+            &[RuleError::new(
+                error_message(
+                    "scalar",
+                    &Message("they return conflicting types Int and String".to_owned()),
+                ),
+                &[
+                    SourcePosition::new(230, 10, 16),
+                    SourcePosition::new(465, 21, 14),
+                ],
+            )],
+        );
+    }
+
     #[test]
     fn error_message_contains_hint_for_alias_conflict() {
         assert_eq!(
@@ -0,0 +1,104 @@
+use crate::{
+    ast::{Field, InputValue},
+    messages::Message,
+    parser::Spanning,
+    schema::meta::MetaType,
+    validation::{ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+/// How the `first` and `last` arguments of a field paginate it
+pub(crate) enum Pagination {
+    /// One of the arguments is provided within the limit, or by a variable
+    Paginated,
+
+    /// Neither argument is provided
+    Unpaginated,
+
+    /// The argument exceeds the limit
+    AboveLimit(&'static str),
+}
+
+/// Tells how the `first` and `last` arguments, as provided by `value`,
+/// paginate a field
+pub(crate) fn pagination<'v, S, F>(limit: i32, value: F) -> Pagination
+where
+    S: ScalarValue + 'v,
+    F: Fn(&str) -> Option<&'v InputValue<S>>,
+{
+    let mut pagination = Pagination::Unpaginated;
+    for &argument in &["first", "last"] {
+        match value(argument) {
+            None | Some(InputValue::Null) => {}
+            Some(v) => match v.as_int_value() {
+                Some(count) if count > limit => return Pagination::AboveLimit(argument),
+                _ => pagination = Pagination::Paginated,
+            },
+        }
+    }
+    pagination
+}
+
+pub struct PaginatedFields;
+
+pub fn factory() -> PaginatedFields {
+    PaginatedFields
+}
+
+impl<'a, S> Visitor<'a, S> for PaginatedFields
+where
+    S: ScalarValue,
+{
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
+        let field_name = field.item.name.item;
+        let (parent_type, type_name) = match ctx.parent_type() {
+            Some(t) => match t.name() {
+                Some(name) => (t, name),
+                None => return,
+            },
+            None => return,
+        };
+        let limit = match ctx.schema.pagination_limit(type_name, field_name) {
+            Some(limit) => limit,
+            None => return,
+        };
+
+        let message = match pagination(limit, |name| {
+            field
+                .item
+                .arguments
+                .as_ref()
+                .and_then(|args| args.item.get(name))
+                .map(|arg| &arg.item)
+                .or_else(|| default_value(parent_type, field_name, name))
+        }) {
+            Pagination::Paginated => return,
+            Pagination::Unpaginated => Message::UnpaginatedField {
+                field: field_name,
+                type_name,
+            },
+            Pagination::AboveLimit(argument) => Message::PaginationAboveLimit {
+                argument,
+                field: field_name,
+                limit,
+            },
+        };
+        ctx.report(message, &[field.start]);
+    }
+}
+
+/// The default value of the argument `name` of a field
+fn default_value<'a, S>(
+    parent_type: &'a MetaType<'a, S>,
+    field_name: &str,
+    name: &str,
+) -> Option<&'a InputValue<S>> {
+    parent_type
+        .field_by_name(field_name)?
+        .arguments
+        .as_ref()?
+        .iter()
+        .find(|a| a.name == name)?
+        .default_value
+        .as_ref()
+}
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 
 use crate::{
     ast::{Definition, Document, FragmentSpread, InlineFragment},
+    messages::Message,
     parser::Spanning,
     schema::meta::MetaType,
     validation::{ValidatorContext, Visitor},
@@ -46,8 +47,8 @@ where
                 .and_then(|s| ctx.schema.concrete_type_by_name(s.item)),
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
-                ctx.report_error(
-                    &error_message(
+                ctx.report(
+                    error_message(
                         None,
                         parent_type.name().unwrap_or("<unknown>"),
                         frag_type.name().unwrap_or("<unknown>"),
@@ -68,8 +69,8 @@ where
             self.fragment_types.get(spread.item.name.item),
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
-                ctx.report_error(
-                    &error_message(
+                ctx.report(
+                    error_message(
                         Some(spread.item.name.item),
                         parent_type.name().unwrap_or("<unknown>"),
                         frag_type.name().unwrap_or("<unknown>"),
@@ -81,19 +82,15 @@ where
     }
 }
 
-fn error_message(frag_name: Option<&str>, parent_type_name: &str, frag_type: &str) -> String {
-    if let Some(frag_name) = frag_name {
-        format!(
-            "Fragment \"{}\" cannot be spread here as objects of type \
-             \"{}\" can never be of type \"{}\"",
-            frag_name, parent_type_name, frag_type
-        )
-    } else {
-        format!(
-            "Fragment cannot be spread here as objects of type \"{}\" \
-             can never be of type \"{}\"",
-            parent_type_name, frag_type
-        )
+fn error_message<'a>(
+    fragment: Option<&'a str>,
+    parent_type: &'a str,
+    fragment_type: &'a str,
+) -> Message<'a> {
+    Message::ImpossibleFragmentSpread {
+        fragment,
+        parent_type,
+        fragment_type,
     }
 }
 
@@ -235,7 +232,7 @@ mod tests {
           fragment dogFragment on Dog { barkVolume }
         "#,
             &[RuleError::new(
-                &error_message(Some("dogFragment"), "Cat", "Dog"),
+                error_message(Some("dogFragment"), "Cat", "Dog"),
                 &[SourcePosition::new(55, 1, 54)],
             )],
         );
@@ -251,7 +248,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(None, "Cat", "Dog"),
+                error_message(None, "Cat", "Dog"),
                 &[SourcePosition::new(71, 2, 12)],
             )],
         );
@@ -266,7 +263,7 @@ mod tests {
           fragment humanFragment on Human { pets { name } }
         "#,
             &[RuleError::new(
-                &error_message(Some("humanFragment"), "Pet", "Human"),
+                error_message(Some("humanFragment"), "Pet", "Human"),
                 &[SourcePosition::new(58, 1, 57)],
             )],
         );
@@ -281,7 +278,7 @@ mod tests {
           fragment humanFragment on Human { pets { name } }
         "#,
             &[RuleError::new(
-                &error_message(Some("humanFragment"), "CatOrDog", "Human"),
+                error_message(Some("humanFragment"), "CatOrDog", "Human"),
                 &[SourcePosition::new(59, 1, 58)],
             )],
         );
@@ -296,7 +293,7 @@ mod tests {
           fragment catOrDogFragment on CatOrDog { __typename }
         "#,
             &[RuleError::new(
-                &error_message(Some("catOrDogFragment"), "Human", "CatOrDog"),
+                error_message(Some("catOrDogFragment"), "Human", "CatOrDog"),
                 &[SourcePosition::new(56, 1, 55)],
             )],
         );
@@ -311,7 +308,7 @@ mod tests {
           fragment humanOrAlienFragment on HumanOrAlien { __typename }
         "#,
             &[RuleError::new(
-                &error_message(Some("humanOrAlienFragment"), "Pet", "HumanOrAlien"),
+                error_message(Some("humanOrAlienFragment"), "Pet", "HumanOrAlien"),
                 &[SourcePosition::new(57, 1, 56)],
             )],
         );
@@ -326,7 +323,7 @@ mod tests {
           fragment humanOrAlienFragment on HumanOrAlien { __typename }
         "#,
             &[RuleError::new(
-                &error_message(Some("humanOrAlienFragment"), "CatOrDog", "HumanOrAlien"),
+                error_message(Some("humanOrAlienFragment"), "CatOrDog", "HumanOrAlien"),
                 &[SourcePosition::new(58, 1, 57)],
             )],
         );
@@ -341,7 +338,7 @@ mod tests {
           fragment intelligentFragment on Intelligent { iq }
         "#,
             &[RuleError::new(
-                &error_message(Some("intelligentFragment"), "Cat", "Intelligent"),
+                error_message(Some("intelligentFragment"), "Cat", "Intelligent"),
                 &[SourcePosition::new(58, 1, 57)],
             )],
         );
@@ -358,7 +355,7 @@ mod tests {
           fragment intelligentFragment on Intelligent { iq }
         "#,
             &[RuleError::new(
-                &error_message(Some("intelligentFragment"), "Pet", "Intelligent"),
+                error_message(Some("intelligentFragment"), "Pet", "Intelligent"),
                 &[SourcePosition::new(73, 2, 12)],
             )],
         );
@@ -374,7 +371,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message(None, "Pet", "Intelligent"),
+                error_message(None, "Pet", "Intelligent"),
                 &[SourcePosition::new(77, 2, 12)],
             )],
         );
@@ -389,7 +386,7 @@ mod tests {
           fragment petFragment on Pet { name }
         "#,
             &[RuleError::new(
-                &error_message(Some("petFragment"), "HumanOrAlien", "Pet"),
+                error_message(Some("petFragment"), "HumanOrAlien", "Pet"),
                 &[SourcePosition::new(66, 1, 65)],
             )],
         );
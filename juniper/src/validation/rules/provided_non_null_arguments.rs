@@ -1,5 +1,6 @@
 use crate::{
     ast::{Directive, Field},
+    messages::Message,
     parser::Spanning,
     schema::{meta::Field as FieldType, model::DirectiveType},
     validation::{ValidatorContext, Visitor},
@@ -33,8 +34,8 @@ where
                         .and_then(|args| args.item.get(&meta_arg.name))
                         .is_none()
                 {
-                    ctx.report_error(
-                        &field_error_message(
+                    ctx.report(
+                        field_error_message(
                             field_name,
                             &meta_arg.name,
                             &format!("{}", meta_arg.arg_type),
@@ -67,8 +68,8 @@ where
                         .and_then(|args| args.item.get(&meta_arg.name))
                         .is_none()
                 {
-                    ctx.report_error(
-                        &directive_error_message(
+                    ctx.report(
+                        directive_error_message(
                             directive_name,
                             &meta_arg.name,
                             &format!("{}", meta_arg.arg_type),
@@ -81,18 +82,28 @@ where
     }
 }
 
-fn field_error_message(field_name: &str, arg_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Field "{}" argument "{}" of type "{}" is required but not provided"#,
-        field_name, arg_name, type_name
-    )
+fn field_error_message<'a>(
+    field: &'a str,
+    argument: &'a str,
+    argument_type: &'a str,
+) -> Message<'a> {
+    Message::MissingFieldArgument {
+        field,
+        argument,
+        argument_type,
+    }
 }
 
-fn directive_error_message(directive_name: &str, arg_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Directive "@{}" argument "{}" of type "{}" is required but not provided"#,
-        directive_name, arg_name, type_name
-    )
+fn directive_error_message<'a>(
+    directive: &'a str,
+    argument: &'a str,
+    argument_type: &'a str,
+) -> Message<'a> {
+    Message::MissingDirectiveArgument {
+        directive,
+        argument,
+        argument_type,
+    }
 }
 
 #[cfg(test)]
@@ -271,7 +282,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &field_error_message("multipleReqs", "req1", "Int!"),
+                field_error_message("multipleReqs", "req1", "Int!"),
                 &[SourcePosition::new(63, 3, 16)],
             )],
         );
@@ -290,11 +301,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &field_error_message("multipleReqs", "req1", "Int!"),
+                    field_error_message("multipleReqs", "req1", "Int!"),
                     &[SourcePosition::new(63, 3, 16)],
                 ),
                 RuleError::new(
-                    &field_error_message("multipleReqs", "req2", "Int!"),
+                    field_error_message("multipleReqs", "req2", "Int!"),
                     &[SourcePosition::new(63, 3, 16)],
                 ),
             ],
@@ -313,7 +324,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &field_error_message("multipleReqs", "req2", "Int!"),
+                field_error_message("multipleReqs", "req2", "Int!"),
                 &[SourcePosition::new(63, 3, 16)],
             )],
         );
@@ -361,11 +372,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &directive_error_message("include", "if", "Boolean!"),
+                    directive_error_message("include", "if", "Boolean!"),
                     &[SourcePosition::new(33, 2, 18)],
                 ),
                 RuleError::new(
-                    &directive_error_message("skip", "if", "Boolean!"),
+                    directive_error_message("skip", "if", "Boolean!"),
                     &[SourcePosition::new(65, 3, 21)],
                 ),
             ],
@@ -1,7 +1,8 @@
 use crate::{
     ast::Field,
+    messages::Message,
     parser::Spanning,
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -18,42 +19,36 @@ where
     fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         let field_name = &field.item.name.item;
 
-        let error = if let (Some(field_type), Some(field_type_literal)) =
+        if let (Some(field_type), Some(field_type_literal)) =
             (ctx.current_type(), ctx.current_type_literal())
         {
             match (field_type.is_leaf(), &field.item.selection_set) {
-                (true, &Some(_)) => Some(RuleError::new(
-                    &no_allowed_error_message(field_name, &format!("{}", field_type_literal)),
-                    &[field.start],
-                )),
-                (false, &None) => Some(RuleError::new(
-                    &required_error_message(field_name, &format!("{}", field_type_literal)),
-                    &[field.start],
-                )),
-                _ => None,
+                (true, &Some(_)) => {
+                    let type_name = format!("{}", field_type_literal);
+                    ctx.report(
+                        no_allowed_error_message(field_name, &type_name),
+                        &[field.start],
+                    );
+                }
+                (false, &None) => {
+                    let type_name = format!("{}", field_type_literal);
+                    ctx.report(
+                        required_error_message(field_name, &type_name),
+                        &[field.start],
+                    );
+                }
+                _ => {}
             }
-        } else {
-            None
-        };
-
-        if let Some(error) = error {
-            ctx.append_errors(vec![error]);
         }
     }
 }
 
-fn no_allowed_error_message(field_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Field "{}" must not have a selection since type {} has no subfields"#,
-        field_name, type_name
-    )
+fn no_allowed_error_message<'a>(field: &'a str, field_type: &'a str) -> Message<'a> {
+    Message::UnexpectedSelection { field, field_type }
 }
 
-fn required_error_message(field_name: &str, type_name: &str) -> String {
-    format!(
-        r#"Field "{}" of type "{}" must have a selection of subfields. Did you mean "{} {{ ... }}"?"#,
-        field_name, type_name, field_name
-    )
+fn required_error_message<'a>(field: &'a str, field_type: &'a str) -> Message<'a> {
+    Message::MissingSelection { field, field_type }
 }
 
 #[cfg(test)]
@@ -88,7 +83,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &required_error_message("human", "Human"),
+                required_error_message("human", "Human"),
                 &[SourcePosition::new(67, 2, 12)],
             )],
         );
@@ -104,7 +99,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &required_error_message("pets", "[Pet]"),
+                required_error_message("pets", "[Pet]"),
                 &[SourcePosition::new(33, 2, 20)],
             )],
         );
@@ -132,7 +127,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &no_allowed_error_message("barks", "Boolean"),
+                no_allowed_error_message("barks", "Boolean"),
                 &[SourcePosition::new(77, 2, 12)],
             )],
         );
@@ -148,7 +143,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &no_allowed_error_message("furColor", "FurColor"),
+                no_allowed_error_message("furColor", "FurColor"),
                 &[SourcePosition::new(74, 2, 12)],
             )],
         );
@@ -164,7 +159,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &no_allowed_error_message("doesKnowCommand", "Boolean"),
+                no_allowed_error_message("doesKnowCommand", "Boolean"),
                 &[SourcePosition::new(76, 2, 12)],
             )],
         );
@@ -180,7 +175,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &no_allowed_error_message("name", "String"),
+                no_allowed_error_message("name", "String"),
                 &[SourcePosition::new(82, 2, 12)],
             )],
         );
@@ -196,7 +191,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &no_allowed_error_message("doesKnowCommand", "Boolean"),
+                no_allowed_error_message("doesKnowCommand", "Boolean"),
                 &[SourcePosition::new(89, 2, 12)],
             )],
         );
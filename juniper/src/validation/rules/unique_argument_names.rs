@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry, HashMap};
 
 use crate::{
     ast::{Directive, Field, InputValue},
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -36,7 +37,7 @@ where
     ) {
         match self.known_names.entry(arg_name.item) {
             Entry::Occupied(e) => {
-                ctx.report_error(&error_message(arg_name.item), &[*e.get(), arg_name.start]);
+                ctx.report(error_message(arg_name.item), &[*e.get(), arg_name.start]);
             }
             Entry::Vacant(e) => {
                 e.insert(arg_name.start);
@@ -45,8 +46,8 @@ where
     }
 }
 
-fn error_message(arg_name: &str) -> String {
-    format!("There can only be one argument named \"{}\"", arg_name)
+fn error_message(argument: &str) -> Message<'_> {
+    Message::DuplicateArgument { argument }
 }
 
 #[cfg(test)]
@@ -178,7 +179,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("arg1"),
+                error_message("arg1"),
                 &[
                     SourcePosition::new(31, 2, 18),
                     SourcePosition::new(46, 2, 33),
@@ -198,14 +199,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("arg1"),
+                    error_message("arg1"),
                     &[
                         SourcePosition::new(31, 2, 18),
                         SourcePosition::new(46, 2, 33),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("arg1"),
+                    error_message("arg1"),
                     &[
                         SourcePosition::new(31, 2, 18),
                         SourcePosition::new(61, 2, 48),
@@ -225,7 +226,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("arg1"),
+                error_message("arg1"),
                 &[
                     SourcePosition::new(42, 2, 29),
                     SourcePosition::new(57, 2, 44),
@@ -245,14 +246,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("arg1"),
+                    error_message("arg1"),
                     &[
                         SourcePosition::new(42, 2, 29),
                         SourcePosition::new(57, 2, 44),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("arg1"),
+                    error_message("arg1"),
                     &[
                         SourcePosition::new(42, 2, 29),
                         SourcePosition::new(72, 2, 59),
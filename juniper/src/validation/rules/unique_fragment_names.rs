@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry, HashMap};
 
 use crate::{
     ast::Fragment,
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -28,8 +29,8 @@ where
     ) {
         match self.names.entry(f.item.name.item) {
             Entry::Occupied(e) => {
-                context.report_error(
-                    &duplicate_message(f.item.name.item),
+                context.report(
+                    duplicate_message(f.item.name.item),
                     &[*e.get(), f.item.name.start],
                 );
             }
@@ -40,8 +41,8 @@ where
     }
 }
 
-fn duplicate_message(frag_name: &str) -> String {
-    format!("There can only be one fragment named {}", frag_name)
+fn duplicate_message(fragment: &str) -> Message<'_> {
+    Message::DuplicateFragment { fragment }
 }
 
 #[cfg(test)]
@@ -165,7 +166,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &duplicate_message("fragA"),
+                duplicate_message("fragA"),
                 &[
                     SourcePosition::new(99, 6, 19),
                     SourcePosition::new(162, 9, 19),
@@ -187,7 +188,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &duplicate_message("fragA"),
+                duplicate_message("fragA"),
                 &[
                     SourcePosition::new(20, 1, 19),
                     SourcePosition::new(83, 4, 19),
@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry, HashMap};
 
 use crate::{
     ast::InputValue,
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -45,8 +46,8 @@ where
         if let Some(ref mut known_names) = self.known_name_stack.last_mut() {
             match known_names.entry(&field_name.item) {
                 Entry::Occupied(e) => {
-                    ctx.report_error(
-                        &error_message(&field_name.item),
+                    ctx.report(
+                        error_message(&field_name.item),
                         &[*e.get(), field_name.start],
                     );
                 }
@@ -58,8 +59,8 @@ where
     }
 }
 
-fn error_message(field_name: &str) -> String {
-    format!("There can only be one input field named \"{}\"", field_name)
+fn error_message(field: &str) -> Message<'_> {
+    Message::DuplicateInputField { field }
 }
 
 #[cfg(test)]
@@ -138,7 +139,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("f1"),
+                error_message("f1"),
                 &[
                     SourcePosition::new(38, 2, 25),
                     SourcePosition::new(51, 2, 38),
@@ -158,14 +159,14 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("f1"),
+                    error_message("f1"),
                     &[
                         SourcePosition::new(38, 2, 25),
                         SourcePosition::new(51, 2, 38),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("f1"),
+                    error_message("f1"),
                     &[
                         SourcePosition::new(38, 2, 25),
                         SourcePosition::new(64, 2, 51),
@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry, HashMap};
 
 use crate::{
     ast::Operation,
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -29,7 +30,7 @@ where
         if let Some(ref op_name) = op.item.name {
             match self.names.entry(op_name.item) {
                 Entry::Occupied(e) => {
-                    ctx.report_error(&error_message(op_name.item), &[*e.get(), op.start]);
+                    ctx.report(error_message(op_name.item), &[*e.get(), op.start]);
                 }
                 Entry::Vacant(e) => {
                     e.insert(op.start);
@@ -39,8 +40,8 @@ where
     }
 }
 
-fn error_message(op_name: &str) -> String {
-    format!("There can only be one operation named {}", op_name)
+fn error_message(operation: &str) -> Message<'_> {
+    Message::DuplicateOperation { operation }
 }
 
 #[cfg(test)]
@@ -159,7 +160,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("Foo"),
+                error_message("Foo"),
                 &[
                     SourcePosition::new(11, 1, 10),
                     SourcePosition::new(96, 6, 10),
@@ -183,7 +184,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("Foo"),
+                error_message("Foo"),
                 &[
                     SourcePosition::new(11, 1, 10),
                     SourcePosition::new(96, 6, 10),
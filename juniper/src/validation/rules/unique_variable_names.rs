@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry, HashMap};
 
 use crate::{
     ast::{Operation, VariableDefinition},
+    messages::Message,
     parser::{SourcePosition, Spanning},
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -36,7 +37,7 @@ where
     ) {
         match self.names.entry(var_name.item) {
             Entry::Occupied(e) => {
-                ctx.report_error(&error_message(var_name.item), &[*e.get(), var_name.start]);
+                ctx.report(error_message(var_name.item), &[*e.get(), var_name.start]);
             }
             Entry::Vacant(e) => {
                 e.insert(var_name.start);
@@ -45,8 +46,8 @@ where
     }
 }
 
-fn error_message(var_name: &str) -> String {
-    format!("There can only be one variable named {}", var_name)
+fn error_message(variable: &str) -> Message<'_> {
+    Message::DuplicateVariable { variable }
 }
 
 #[cfg(test)]
@@ -81,28 +82,28 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("x"),
+                    error_message("x"),
                     &[
                         SourcePosition::new(19, 1, 18),
                         SourcePosition::new(28, 1, 27),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("x"),
+                    error_message("x"),
                     &[
                         SourcePosition::new(19, 1, 18),
                         SourcePosition::new(37, 1, 36),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("x"),
+                    error_message("x"),
                     &[
                         SourcePosition::new(82, 2, 18),
                         SourcePosition::new(94, 2, 30),
                     ],
                 ),
                 RuleError::new(
-                    &error_message("x"),
+                    error_message("x"),
                     &[
                         SourcePosition::new(136, 3, 18),
                         SourcePosition::new(145, 3, 27),
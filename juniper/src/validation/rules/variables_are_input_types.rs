@@ -1,5 +1,6 @@
 use crate::{
     ast::VariableDefinition,
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -25,8 +26,8 @@ where
             .concrete_type_by_name(var_def.var_type.item.innermost_name())
         {
             if !var_type.is_input() {
-                ctx.report_error(
-                    &error_message(var_name.item, &format!("{}", var_def.var_type.item)),
+                ctx.report(
+                    error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[var_def.var_type.start],
                 );
             }
@@ -34,11 +35,11 @@ where
     }
 }
 
-fn error_message(var_name: &str, type_name: &str) -> String {
-    format!(
-        "Variable \"{}\" cannot be of non-input type \"{}\"",
-        var_name, type_name
-    )
+fn error_message<'a>(variable: &'a str, variable_type: &'a str) -> Message<'a> {
+    Message::NonInputVariableType {
+        variable,
+        variable_type,
+    }
 }
 
 #[cfg(test)]
@@ -73,18 +74,12 @@ mod tests {
           }
         "#,
             &[
+                RuleError::new(error_message("a", "Dog"), &[SourcePosition::new(25, 1, 24)]),
                 RuleError::new(
-                    &error_message("a", "Dog"),
-                    &[SourcePosition::new(25, 1, 24)],
-                ),
-                RuleError::new(
-                    &error_message("b", "[[CatOrDog!]]!"),
+                    error_message("b", "[[CatOrDog!]]!"),
                     &[SourcePosition::new(34, 1, 33)],
                 ),
-                RuleError::new(
-                    &error_message("c", "Pet"),
-                    &[SourcePosition::new(54, 1, 53)],
-                ),
+                RuleError::new(error_message("c", "Pet"), &[SourcePosition::new(54, 1, 53)]),
             ],
         );
     }
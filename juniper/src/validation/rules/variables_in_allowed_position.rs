@@ -6,6 +6,7 @@ use std::{
 
 use crate::{
     ast::{Document, Fragment, FragmentSpread, Operation, Type, VariableDefinition},
+    messages::Message,
     parser::Spanning,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
@@ -62,8 +63,8 @@ impl<'a, S: Debug> VariableInAllowedPosition<'a, S> {
                     };
 
                     if !ctx.schema.is_subtype(&expected_type, var_type) {
-                        ctx.report_error(
-                            &error_message(
+                        ctx.report(
+                            error_message(
                                 var_name.item,
                                 &format!("{}", expected_type),
                                 &format!("{}", var_type),
@@ -154,11 +155,16 @@ where
     }
 }
 
-fn error_message(var_name: &str, type_name: &str, expected_type_name: &str) -> String {
-    format!(
-        "Variable \"{}\" of type \"{}\" used in position expecting type \"{}\"",
-        var_name, type_name, expected_type_name
-    )
+fn error_message<'a>(
+    variable: &'a str,
+    variable_type: &'a str,
+    expected_type: &'a str,
+) -> Message<'a> {
+    Message::VariableInWrongPosition {
+        variable,
+        variable_type,
+        expected_type,
+    }
 }
 
 #[cfg(test)]
@@ -396,7 +402,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int", "Int!"),
+                error_message("intArg", "Int", "Int!"),
                 &[
                     SourcePosition::new(23, 1, 22),
                     SourcePosition::new(117, 3, 48),
@@ -421,7 +427,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int", "Int!"),
+                error_message("intArg", "Int", "Int!"),
                 &[
                     SourcePosition::new(154, 5, 22),
                     SourcePosition::new(110, 2, 46),
@@ -450,7 +456,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int", "Int!"),
+                error_message("intArg", "Int", "Int!"),
                 &[
                     SourcePosition::new(255, 9, 22),
                     SourcePosition::new(211, 6, 46),
@@ -471,7 +477,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("stringVar", "String", "Boolean"),
+                error_message("stringVar", "String", "Boolean"),
                 &[
                     SourcePosition::new(23, 1, 22),
                     SourcePosition::new(117, 3, 42),
@@ -492,7 +498,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("stringVar", "String", "[String]"),
+                error_message("stringVar", "String", "[String]"),
                 &[
                     SourcePosition::new(23, 1, 22),
                     SourcePosition::new(123, 3, 48),
@@ -511,7 +517,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("boolVar", "Boolean", "Boolean!"),
+                error_message("boolVar", "Boolean", "Boolean!"),
                 &[
                     SourcePosition::new(23, 1, 22),
                     SourcePosition::new(73, 2, 29),
@@ -530,7 +536,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &error_message("stringVar", "String", "Boolean!"),
+                error_message("stringVar", "String", "Boolean!"),
                 &[
                     SourcePosition::new(23, 1, 22),
                     SourcePosition::new(74, 2, 29),
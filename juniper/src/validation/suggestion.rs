@@ -0,0 +1,116 @@
+//! "Did you mean" suggestions of the validation errors, picking the names close
+//! to a misspelled one like [graphql-js](https://github.com/graphql/graphql-js)
+//! does
+
+/// Maximum number of names suggested by an error
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The `options` close enough to the `input` to be suggested, closest first
+pub(crate) fn suggestion_list<'a, I>(input: &str, options: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut suggestions: Vec<_> = options
+        .into_iter()
+        .filter_map(|option| {
+            let distance = lexical_distance(input, option);
+            let threshold = (input.len() / 2).max(option.len() / 2).max(1);
+            if distance <= threshold {
+                Some((distance, option))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    suggestions.sort();
+    suggestions.dedup();
+    suggestions.into_iter().map(|(_, option)| option).collect()
+}
+
+/// Sentence appended to an error message to suggest the `suggestions`,
+/// along with the period ending the message, empty if there are none
+pub(crate) fn did_you_mean(suggestions: &[&str]) -> String {
+    let quoted: Vec<_> = suggestions
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|s| format!(r#""{}""#, s))
+        .collect();
+
+    match quoted.as_slice() {
+        [] => String::new(),
+        [only] => format!(". Did you mean {}?", only),
+        [first, second] => format!(". Did you mean {} or {}?", first, second),
+        [init @ .., last] => format!(". Did you mean {}, or {}?", init.join(", "), last),
+    }
+}
+
+/// Damerau-Levenshtein distance between two names, a difference of case only
+/// counting as a single edit
+fn lexical_distance(a: &str, b: &str) -> usize {
+    if a == b {
+        return 0;
+    }
+    let (a, b) = (a.to_lowercase(), b.to_lowercase());
+    if a == b {
+        return 1;
+    }
+
+    let (a, b): (Vec<_>, Vec<_>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + cost);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{did_you_mean, lexical_distance, suggestion_list};
+
+    #[test]
+    fn measures_edits() {
+        assert_eq!(lexical_distance("name", "name"), 0);
+        assert_eq!(lexical_distance("name", "Name"), 1);
+        assert_eq!(lexical_distance("naem", "name"), 1);
+        assert_eq!(lexical_distance("nme", "name"), 1);
+        assert_eq!(lexical_distance("id", "friends"), 5);
+    }
+
+    #[test]
+    fn suggests_close_names_closest_first() {
+        assert_eq!(
+            suggestion_list("nam", vec!["id", "names", "name", "nickname"]),
+            vec!["name", "names"],
+        );
+        assert!(suggestion_list("weight", vec!["id", "name"]).is_empty());
+    }
+
+    #[test]
+    fn lists_the_suggestions() {
+        assert_eq!(did_you_mean(&[]), "");
+        assert_eq!(did_you_mean(&["a"]), r#". Did you mean "a"?"#);
+        assert_eq!(did_you_mean(&["a", "b"]), r#". Did you mean "a" or "b"?"#);
+        assert_eq!(
+            did_you_mean(&["a", "b", "c", "d", "e", "f"]),
+            r#". Did you mean "a", "b", "c", "d", or "e"?"#,
+        );
+    }
+}
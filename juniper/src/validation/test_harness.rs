@@ -812,20 +812,14 @@ where
     }
 }
 
-pub fn validate<'a, Q, M, Sub, V, F, S>(
-    r: Q,
-    m: M,
-    s: Sub,
-    q: &'a str,
-    factory: F,
-) -> Vec<RuleError>
+/// Schema of the `r`, `m` and `s` roots, along with the directives used by the
+/// validation tests
+pub(crate) fn test_root_node<'a, Q, M, Sub, S>(r: Q, m: M, s: Sub) -> RootNode<'a, Q, M, Sub, S>
 where
     S: ScalarValue + 'a,
     Q: GraphQLType<S, TypeInfo = ()>,
     M: GraphQLType<S, TypeInfo = ()>,
     Sub: GraphQLType<S, TypeInfo = ()>,
-    V: Visitor<'a, S> + 'a,
-    F: Fn() -> V,
 {
     let mut root = RootNode::new(r, m, s);
 
@@ -860,6 +854,26 @@ where
         &[],
     ));
 
+    root
+}
+
+pub fn validate<'a, Q, M, Sub, V, F, S>(
+    r: Q,
+    m: M,
+    s: Sub,
+    q: &'a str,
+    factory: F,
+) -> Vec<RuleError>
+where
+    S: ScalarValue + 'a,
+    Q: GraphQLType<S, TypeInfo = ()>,
+    M: GraphQLType<S, TypeInfo = ()>,
+    Sub: GraphQLType<S, TypeInfo = ()>,
+    V: Visitor<'a, S> + 'a,
+    F: Fn() -> V,
+{
+    let root = test_root_node(r, m, s);
+
     let doc =
         parse_document_source(q, &root.schema).expect(&format!("Parse error on input {:#?}", q));
     let mut ctx = ValidatorContext::new(unsafe { ::std::mem::transmute(&root.schema) }, &doc);
@@ -7,6 +7,7 @@ use crate::{
     },
     parser::Spanning,
     schema::meta::Argument,
+    stack::ensure_sufficient_stack,
     validation::{multi_visitor::MultiVisitorCons, ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -203,14 +204,16 @@ fn visit_selection_set<'a, S, V>(
     S: ScalarValue,
     V: Visitor<'a, S>,
 {
-    ctx.with_pushed_parent_type(|ctx| {
-        v.enter_selection_set(ctx, selection_set);
+    ensure_sufficient_stack(|| {
+        ctx.with_pushed_parent_type(|ctx| {
+            v.enter_selection_set(ctx, selection_set);
 
-        for selection in selection_set.iter() {
-            visit_selection(v, ctx, selection);
-        }
+            for selection in selection_set.iter() {
+                visit_selection(v, ctx, selection);
+            }
 
-        v.exit_selection_set(ctx, selection_set);
+            v.exit_selection_set(ctx, selection_set);
+        })
     });
 }
 
@@ -6,9 +6,12 @@ use std::fmt::{self, Display, Formatter};
 mod object;
 mod scalar;
 
-pub use self::object::Object;
+pub use self::object::{DuplicateFieldError, Object, OnDuplicateField};
 
-pub use self::scalar::{DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue};
+pub use self::scalar::{
+    parse_number_literal, parse_token, DefaultScalarValue, IntoScalarValue, OrParser,
+    ParseScalarResult, ParseScalarValue, ScalarConversionError, ScalarTokenParser, ScalarValue,
+};
 
 /// Serializable value returned from query and field execution.
 ///
@@ -76,9 +79,9 @@ where
     /// Construct a scalar value
     pub fn scalar<T>(s: T) -> Self
     where
-        T: Into<S>,
+        T: IntoScalarValue<S>,
     {
-        Value::Scalar(s.into())
+        Value::Scalar(s.into_scalar_value())
     }
 
     // DISCRIMINATORS
@@ -162,6 +165,132 @@ where {
     }
 }
 
+impl<S> Value<S> {
+    // TRANSFORMATIONS
+
+    /// Convert every scalar of this value with the provided function, e.g. to
+    /// change the scalar type of a response.
+    pub fn map_scalars<T, F>(self, mut f: F) -> Value<T>
+    where
+        F: FnMut(S) -> T,
+    {
+        self.map_scalars_with(&mut f)
+    }
+
+    fn map_scalars_with<T, F>(self, f: &mut F) -> Value<T>
+    where
+        F: FnMut(S) -> T,
+    {
+        match self {
+            Value::Null => Value::Null,
+            Value::Scalar(s) => Value::Scalar(f(s)),
+            Value::List(l) => Value::List(l.into_iter().map(|v| v.map_scalars_with(f)).collect()),
+            Value::Object(o) => Value::Object(
+                o.into_iter()
+                    .map(|(k, v)| (k, v.map_scalars_with(f)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Call the provided function on every value of this tree, children
+    /// before their parent, with the path of field names leading to it.
+    ///
+    /// Like the paths of the execution errors, the paths don't contain the
+    /// positions in lists, the items of a list sharing the path of the list.
+    pub fn walk_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[String], &mut Value<S>),
+    {
+        self.walk_mut_with(&mut Vec::new(), &mut f)
+    }
+
+    fn walk_mut_with<F>(&mut self, path: &mut Vec<String>, f: &mut F)
+    where
+        F: FnMut(&[String], &mut Value<S>),
+    {
+        match self {
+            Value::Null | Value::Scalar(_) => {}
+            Value::List(l) => {
+                for v in l {
+                    v.walk_mut_with(path, f);
+                }
+            }
+            Value::Object(o) => {
+                for (k, v) in o.iter_mut() {
+                    path.push(k.clone());
+                    v.walk_mut_with(path, f);
+                    path.pop();
+                }
+            }
+        }
+        f(path, self)
+    }
+
+    /// Remove the null fields of the objects of this tree.
+    ///
+    /// Null items of lists are kept, so the other items keep their position.
+    pub fn prune_nulls(&mut self) {
+        match self {
+            Value::Null | Value::Scalar(_) => {}
+            Value::List(l) => {
+                for v in l {
+                    v.prune_nulls();
+                }
+            }
+            Value::Object(o) => {
+                let fields = std::mem::replace(o, Object::with_capacity(0));
+                *o = fields
+                    .into_iter()
+                    .filter(|(_, v)| !matches!(v, Value::Null))
+                    .map(|(k, mut v)| {
+                        v.prune_nulls();
+                        (k, v)
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    /// Call the provided function on the values found at the given path of
+    /// field names, going through lists as [`walk_mut`](#method.walk_mut)
+    /// does.
+    ///
+    /// Missing fields are skipped, and the empty path designates this value.
+    pub fn map_path<F>(&mut self, path: &[&str], mut f: F)
+    where
+        F: FnMut(&mut Value<S>),
+    {
+        self.map_path_with(path, &mut f)
+    }
+
+    fn map_path_with<F>(&mut self, path: &[&str], f: &mut F)
+    where
+        F: FnMut(&mut Value<S>),
+    {
+        match (self, path.split_first()) {
+            (v, None) => f(v),
+            (Value::List(l), Some(_)) => {
+                for v in l {
+                    v.map_path_with(path, f);
+                }
+            }
+            (Value::Object(o), Some((name, rest))) => {
+                if let Some(v) = o.get_mut_field_value(*name) {
+                    v.map_path_with(rest, f);
+                }
+            }
+            (Value::Null, Some(_)) | (Value::Scalar(_), Some(_)) => {}
+        }
+    }
+
+    /// Replace the values found at the given path of field names with null,
+    /// see [`map_path`](#method.map_path).
+    pub fn redact_path(&mut self, path: &[&str]) {
+        self.map_path(path, |v| *v = Value::Null)
+    }
+}
+
 impl<S: ScalarValue> ToInputValue<S> for Value<S> {
     fn to_input_value(&self) -> InputValue<S> {
         match *self {
@@ -174,7 +303,7 @@ impl<S: ScalarValue> ToInputValue<S> for Value<S> {
             ),
             Value::Object(ref o) => InputValue::Object(
                 o.iter()
-                    .map(|&(ref k, ref v)| {
+                    .map(|(k, v)| {
                         (
                             Spanning::unlocated(k.clone()),
                             Spanning::unlocated(v.to_input_value()),
@@ -473,4 +602,89 @@ mod tests {
         let s = Value::<DefaultScalarValue>::object(Object::with_capacity(0));
         assert_eq!(r#"{}"#, format!("{}", s));
     }
+
+    #[test]
+    fn map_scalars() {
+        let s: Value<DefaultScalarValue> = graphql_value!({
+            "id": 1,
+            "tags": ["a", None],
+        });
+        let mapped = s.map_scalars(|s| s.as_string());
+        let expected: Object<Option<String>> = vec![
+            ("id", Value::Scalar(None)),
+            (
+                "tags",
+                Value::List(vec![Value::Scalar(Some("a".to_owned())), Value::Null]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(mapped, Value::Object(expected));
+    }
+
+    #[test]
+    fn walk_mut() {
+        let mut s: Value<DefaultScalarValue> = graphql_value!({
+            "user": { "name": "Ann", "friends": [{ "name": "Bob" }] },
+        });
+        let mut paths = Vec::new();
+        s.walk_mut(|path, v| {
+            paths.push(path.join("."));
+            if v.as_string_value().is_some() {
+                *v = Value::scalar("***");
+            }
+        });
+        assert_eq!(
+            paths,
+            [
+                "user.name",
+                "user.friends.name",
+                "user.friends",
+                "user.friends",
+                "user",
+                "",
+            ],
+        );
+        assert_eq!(
+            s,
+            graphql_value!({
+                "user": { "name": "***", "friends": [{ "name": "***" }] },
+            }),
+        );
+    }
+
+    #[test]
+    fn prune_nulls() {
+        let mut s: Value<DefaultScalarValue> = graphql_value!({
+            "a": None,
+            "b": [None, { "c": None, "d": 1 }],
+            "e": { "f": None },
+        });
+        s.prune_nulls();
+        let e = Value::object(Object::with_capacity(0));
+        assert_eq!(s, graphql_value!({ "b": [None, { "d": 1 }], "e": e }));
+    }
+
+    #[test]
+    fn redact_path() {
+        let mut s: Value<DefaultScalarValue> = graphql_value!({
+            "users": [
+                { "name": "Ann", "email": "ann@example.com" },
+                { "name": "Bob", "email": "bob@example.com" },
+            ],
+            "email": "root@example.com",
+        });
+        s.redact_path(&["users", "email"]);
+        s.redact_path(&["users", "missing"]);
+        assert_eq!(
+            s,
+            graphql_value!({
+                "users": [
+                    { "name": "Ann", "email": None },
+                    { "name": "Bob", "email": None },
+                ],
+                "email": "root@example.com",
+            }),
+        );
+    }
 }
@@ -1,11 +1,57 @@
-use std::{iter::FromIterator, vec::IntoIter};
+use std::{borrow::Borrow, error::Error, fmt, hash::Hash, iter::FromIterator};
+
+use indexmap::map::{IndexMap, IntoIter};
 
 use super::Value;
 
+/// What [`Object::insert_field`](struct.Object.html#method.insert_field) does
+/// when the object already has the field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnDuplicateField {
+    /// Replace the value, the field keeping its position, as
+    /// [`Object::add_field`](struct.Object.html#method.add_field) does
+    Replace,
+    /// Merge the values like the executor does for a field selected several
+    /// times, following the specification's merging of selection sets
+    ///
+    /// Objects are merged recursively, and lists of objects item by item. For
+    /// other values, the existing value is kept.
+    Merge,
+    /// Keep the object as is and fail
+    Fail,
+}
+
+/// Error of [`Object::insert_field`](struct.Object.html#method.insert_field)
+/// for a field the object already has, with
+/// [`OnDuplicateField::Fail`](enum.OnDuplicateField.html#variant.Fail)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateFieldError {
+    name: String,
+}
+
+impl DuplicateFieldError {
+    /// The name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for DuplicateFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Field \"{}\" is already present", self.name)
+    }
+}
+
+impl Error for DuplicateFieldError {}
+
 /// A Object value
+///
+/// The fields keep the order they were first added in, as the GraphQL
+/// specification requires responses to follow the order of the selections:
+/// adding a field again replaces its value in place.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object<S> {
-    key_value_list: Vec<(String, Value<S>)>,
+    key_value_list: IndexMap<String, Value<S>>,
 }
 
 impl<S> Object<S> {
@@ -13,52 +59,98 @@ impl<S> Object<S> {
     /// preallocated slots for field-value pairs
     pub fn with_capacity(size: usize) -> Self {
         Object {
-            key_value_list: Vec::with_capacity(size),
+            key_value_list: IndexMap::with_capacity(size),
         }
     }
 
     /// Add a new field with a value
     ///
     /// If there is already a field with the same name the old value
-    /// is returned
+    /// is returned, the field keeping its position, see
+    /// [`insert_field`](#method.insert_field) for the other policies
     pub fn add_field<K>(&mut self, k: K, value: Value<S>) -> Option<Value<S>>
     where
         K: Into<String>,
-        for<'a> &'a str: PartialEq<K>,
     {
-        if let Some(item) = self
-            .key_value_list
-            .iter_mut()
-            .find(|&&mut (ref key, _)| (key as &str) == k)
-        {
-            return Some(::std::mem::replace(&mut item.1, value));
+        self.key_value_list.insert(k.into(), value)
+    }
+
+    /// Add a field with a value, handling a field with the same name as the
+    /// provided policy says
+    ///
+    /// Returns the replaced value, if any.
+    pub fn insert_field<K>(
+        &mut self,
+        k: K,
+        value: Value<S>,
+        on_duplicate: OnDuplicateField,
+    ) -> Result<Option<Value<S>>, DuplicateFieldError>
+    where
+        K: AsRef<str> + Into<String>,
+    {
+        match on_duplicate {
+            OnDuplicateField::Replace => Ok(self.add_field(k, value)),
+            OnDuplicateField::Merge => {
+                self.merge_field(k, value);
+                Ok(None)
+            }
+            OnDuplicateField::Fail if self.contains_field(k.as_ref()) => {
+                Err(DuplicateFieldError { name: k.into() })
+            }
+            OnDuplicateField::Fail => Ok(self.add_field(k, value)),
+        }
+    }
+
+    /// Add a field with a value, merging it with the value of a field with the
+    /// same name, see [`OnDuplicateField::Merge`](enum.OnDuplicateField.html#variant.Merge)
+    pub fn merge_field<K>(&mut self, k: K, value: Value<S>)
+    where
+        K: AsRef<str> + Into<String>,
+    {
+        let existing = match self.key_value_list.get_mut(k.as_ref()) {
+            Some(existing) => existing,
+            None => {
+                self.key_value_list.insert(k.into(), value);
+                return;
+            }
+        };
+        match (existing, value) {
+            (Value::Object(dest), Value::Object(src)) => dest.merge(src),
+            (Value::List(dest), Value::List(src)) => {
+                for (dest, src) in dest.iter_mut().zip(src) {
+                    if let (Value::Object(dest), Value::Object(src)) = (dest, src) {
+                        dest.merge(src);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Merge the fields of `other` into this object
+    fn merge(&mut self, other: Object<S>) {
+        for (k, v) in other {
+            self.merge_field(k, v);
         }
-        self.key_value_list.push((k.into(), value));
-        None
     }
 
     /// Check if the object already contains a field with the given name
-    pub fn contains_field<K>(&self, f: K) -> bool
+    pub fn contains_field<Q>(&self, f: &Q) -> bool
     where
-        for<'a> &'a str: PartialEq<K>,
+        String: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
     {
-        self.key_value_list
-            .iter()
-            .any(|&(ref key, _)| (key as &str) == f)
+        self.key_value_list.contains_key(f)
     }
 
     /// Get a iterator over all field value pairs
-    pub fn iter(&self) -> impl Iterator<Item = &(String, Value<S>)> {
-        FieldIter {
-            inner: self.key_value_list.iter(),
-        }
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value<S>)> {
+        self.key_value_list.iter()
     }
 
     /// Get a iterator over all mutable field value pairs
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut (String, Value<S>)> {
-        FieldIterMut {
-            inner: self.key_value_list.iter_mut(),
-        }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut Value<S>)> {
+        self.key_value_list.iter_mut()
     }
 
     /// Get the current number of fields
@@ -67,21 +159,27 @@ impl<S> Object<S> {
     }
 
     /// Get the value for a given field
-    pub fn get_field_value<K>(&self, key: K) -> Option<&Value<S>>
+    pub fn get_field_value<Q>(&self, key: &Q) -> Option<&Value<S>>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.key_value_list.get(key)
+    }
+
+    /// Get the mutable value for a given field
+    pub fn get_mut_field_value<Q>(&mut self, key: &Q) -> Option<&mut Value<S>>
     where
-        for<'a> &'a str: PartialEq<K>,
+        String: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
     {
-        self.key_value_list
-            .iter()
-            .find(|&&(ref k, _)| (k as &str) == key)
-            .map(|&(_, ref value)| value)
+        self.key_value_list.get_mut(key)
     }
 
     /// Recursively sort all keys by field.
     pub fn sort_by_field(&mut self) {
-        self.key_value_list
-            .sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
-        for (_, ref mut value) in &mut self.key_value_list {
+        self.key_value_list.sort_keys();
+        for value in self.key_value_list.values_mut() {
             if let Value::Object(ref mut o) = value {
                 o.sort_by_field();
             }
@@ -91,7 +189,7 @@ impl<S> Object<S> {
 
 impl<S> IntoIterator for Object<S> {
     type Item = (String, Value<S>);
-    type IntoIter = IntoIter<Self::Item>;
+    type IntoIter = IntoIter<String, Value<S>>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.key_value_list.into_iter()
@@ -107,7 +205,6 @@ impl<S> From<Object<S>> for Value<S> {
 impl<K, S> FromIterator<(K, Value<S>)> for Object<S>
 where
     K: Into<String>,
-    for<'a> &'a str: PartialEq<K>,
 {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -115,7 +212,7 @@ where
     {
         let iter = iter.into_iter();
         let mut ret = Self {
-            key_value_list: Vec::with_capacity(iter.size_hint().0),
+            key_value_list: IndexMap::with_capacity(iter.size_hint().0),
         };
         for (k, v) in iter {
             ret.add_field(k, v);
@@ -123,29 +220,3 @@ where
         ret
     }
 }
-
-#[doc(hidden)]
-pub struct FieldIter<'a, S: 'a> {
-    inner: ::std::slice::Iter<'a, (String, Value<S>)>,
-}
-
-impl<'a, S> Iterator for FieldIter<'a, S> {
-    type Item = &'a (String, Value<S>);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
-    }
-}
-
-#[doc(hidden)]
-pub struct FieldIterMut<'a, S: 'a> {
-    inner: ::std::slice::IterMut<'a, (String, Value<S>)>,
-}
-
-impl<'a, S> Iterator for FieldIterMut<'a, S> {
-    type Item = &'a mut (String, Value<S>);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
-    }
-}
@@ -1,9 +1,9 @@
-use std::fmt;
+use std::{error::Error, fmt, sync::Arc};
 
 use serde::{de, ser::Serialize};
 
 use crate::{
-    parser::{ParseError, ScalarToken},
+    parser::{ParseError, ScalarToken, ScalarTokenKind, Token},
     GraphQLScalarValue,
 };
 
@@ -16,6 +16,121 @@ pub trait ParseScalarValue<S = DefaultScalarValue> {
     fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S>;
 }
 
+/// A parser of scalar literals, composable with other parsers
+///
+/// It's implemented for every `fn(ScalarToken) -> ParseScalarResult`, so the
+/// [`ParseScalarValue::from_str`] of existing scalars (see [`parse_token`])
+/// may be combined into a parser of a scalar accepting several literal forms.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use juniper::{
+///     parse_token, InputValue, ParseScalarResult, ScalarTokenParser, ScalarValue, Value,
+/// };
+///
+/// struct Timeout(Duration);
+///
+/// /// Duration in seconds, written either as `300` or as `"5m"`
+/// #[juniper::graphql_scalar]
+/// impl<S> GraphQLScalar for Timeout
+/// where
+///     S: ScalarValue,
+/// {
+///     fn resolve(&self) -> Value {
+///         Value::scalar(self.0.as_secs() as i32)
+///     }
+///
+///     fn from_input_value(v: &InputValue) -> Option<Timeout> {
+///         let v = v.as_scalar()?;
+///         let secs = match v.as_int() {
+///             Some(secs) => secs as u64,
+///             None => {
+///                 let minutes = v.as_str()?.strip_suffix('m')?;
+///                 minutes.parse::<u64>().ok()? * 60
+///             }
+///         };
+///         Some(Timeout(Duration::from_secs(secs)))
+///     }
+///
+///     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+///         parse_token::<i32, S>()
+///             .or(parse_token::<String, S>())
+///             .parse(value)
+///     }
+/// }
+/// ```
+pub trait ScalarTokenParser<S = DefaultScalarValue>: Sized {
+    /// Parses the given literal token into a scalar value
+    fn parse<'a>(&self, token: ScalarToken<'a>) -> ParseScalarResult<'a, S>;
+
+    /// Parses the given literal token into a scalar value, returning along
+    /// with it the kind of the token, i.e. which literal form was matched
+    fn parse_kind<'a>(
+        &self,
+        token: ScalarToken<'a>,
+    ) -> Result<(S, ScalarTokenKind), ParseError<'a>> {
+        self.parse(token).map(|value| (value, token.kind()))
+    }
+
+    /// Combines this parser with the `other` one, which is tried for the
+    /// literals this parser rejects
+    fn or<P: ScalarTokenParser<S>>(self, other: P) -> OrParser<Self, P> {
+        OrParser(self, other)
+    }
+}
+
+impl<S, F> ScalarTokenParser<S> for F
+where
+    F: for<'a> Fn(ScalarToken<'a>) -> ParseScalarResult<'a, S>,
+{
+    fn parse<'a>(&self, token: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        self(token)
+    }
+}
+
+/// Parser falling back to the second parser for the literals the first one
+/// rejects
+///
+/// Created by [`ScalarTokenParser::or`]. If both parsers reject a literal, the
+/// error of the second one is returned.
+#[derive(Clone, Copy, Debug)]
+pub struct OrParser<L, R>(L, R);
+
+impl<S, L, R> ScalarTokenParser<S> for OrParser<L, R>
+where
+    L: ScalarTokenParser<S>,
+    R: ScalarTokenParser<S>,
+{
+    fn parse<'a>(&self, token: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        self.0.parse(token).or_else(|_| self.1.parse(token))
+    }
+}
+
+/// Parses an `Int` or `Float` literal token, preserving its source text with
+/// [`ScalarValue::from_number_literal`]
+///
+/// Fails for the other tokens, and for the scalar value types not supporting
+/// number literals.
+pub fn parse_number_literal<S: ScalarValue>(token: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+    match token {
+        ScalarToken::Int(literal) | ScalarToken::Float(literal) => {
+            S::from_number_literal(literal).ok_or(ParseError::UnexpectedToken(Token::Scalar(token)))
+        }
+        ScalarToken::String(_) | ScalarToken::BlockString(_) => {
+            Err(ParseError::UnexpectedToken(Token::Scalar(token)))
+        }
+    }
+}
+
+/// Returns the parser of the literals of the `T` scalar, to be combined with
+/// other ones via [`ScalarTokenParser::or`]
+pub fn parse_token<T, S>() -> for<'a> fn(ScalarToken<'a>) -> ParseScalarResult<'a, S>
+where
+    T: ParseScalarValue<S> + ?Sized,
+{
+    T::from_str
+}
+
 /// A trait marking a type that could be used as internal representation of
 /// scalar values in juniper
 ///
@@ -24,6 +139,11 @@ pub trait ParseScalarValue<S = DefaultScalarValue> {
 /// needs.
 /// There is a custom derive (`#[derive(juniper::GraphQLScalarValue)]`) available that implements
 /// most of the required traits automatically for a enum representing a scalar value.
+///
+/// Scalar values are constructed with the `from_*` methods, which by default
+/// deserialize the primitive value with the `Visitor`, so they only need to be
+/// implemented for representations the visitor can't construct, or to skip
+/// the deserialization.
 /// This derives needs a additional annotation of the form
 /// `#[juniper(visitor = "VisitorType")]` to specify a type that implements
 /// `serde::de::Visitor` and that is used to deserialize the value.
@@ -167,17 +287,7 @@ pub trait ParseScalarValue<S = DefaultScalarValue> {
 ///
 /// # fn main() {}
 /// ```
-pub trait ScalarValue:
-    fmt::Debug
-    + fmt::Display
-    + PartialEq
-    + Clone
-    + Serialize
-    + From<String>
-    + From<bool>
-    + From<i32>
-    + From<f64>
-{
+pub trait ScalarValue: fmt::Debug + fmt::Display + PartialEq + Clone + Serialize {
     /// Serde visitor used to deserialize this scalar value
     type Visitor: for<'de> de::Visitor<'de, Value = Self> + Default;
 
@@ -232,6 +342,174 @@ pub trait ScalarValue:
     /// This function is used for implementing `GraphQLValue` for `bool` for all
     /// scalar values.
     fn as_boolean(&self) -> Option<bool>;
+
+    /// Convert the given scalar value into an integer value, explaining why it
+    /// can't be
+    ///
+    /// Implementations backed by wider numbers should override it, to tell an
+    /// integer out of the range of `Int` from a value of another type.
+    fn try_to_int(&self) -> Result<i32, ScalarConversionError> {
+        self.as_int()
+            .ok_or_else(|| ScalarConversionError::new("Int", self))
+    }
+
+    /// Convert the given scalar value into a float value, explaining why it
+    /// can't be
+    fn try_to_float(&self) -> Result<f64, ScalarConversionError> {
+        self.as_float()
+            .ok_or_else(|| ScalarConversionError::new("Float", self))
+    }
+
+    /// Convert the given scalar value into a string value, explaining why it
+    /// can't be
+    fn try_to_string(&self) -> Result<String, ScalarConversionError> {
+        self.as_string()
+            .ok_or_else(|| ScalarConversionError::new("String", self))
+    }
+
+    /// Borrow the given scalar value as a string value, explaining why it
+    /// can't be
+    fn try_as_str(&self) -> Result<&str, ScalarConversionError> {
+        self.as_str()
+            .ok_or_else(|| ScalarConversionError::new("String", self))
+    }
+
+    /// Convert the given scalar value into a boolean value, explaining why it
+    /// can't be
+    fn try_to_bool(&self) -> Result<bool, ScalarConversionError> {
+        self.as_boolean()
+            .ok_or_else(|| ScalarConversionError::new("Boolean", self))
+    }
+
+    /// Construct a scalar value from an integer value
+    fn from_int(value: i32) -> Self {
+        visit::<Self, _>(|visitor| de::Visitor::visit_i32(visitor, value))
+    }
+
+    /// Construct a scalar value from a float value
+    fn from_float(value: f64) -> Self {
+        visit::<Self, _>(|visitor| de::Visitor::visit_f64(visitor, value))
+    }
+
+    /// Construct a scalar value from a string value
+    fn from_string(value: String) -> Self {
+        visit::<Self, _>(|visitor| de::Visitor::visit_string(visitor, value))
+    }
+
+    /// Construct a scalar value from a shared string value
+    ///
+    /// This is used for resolving a [`SharedString`](../struct.SharedString.html),
+    /// so implementations storing `Arc<str>` should override it to keep the
+    /// string without copying it.
+    fn from_shared_string(value: Arc<str>) -> Self {
+        Self::from_string(value.as_ref().to_owned())
+    }
+
+    /// Construct a scalar value from a boolean value
+    fn from_bool(value: bool) -> Self {
+        visit::<Self, _>(|visitor| de::Visitor::visit_bool(visitor, value))
+    }
+
+    /// Construct a scalar value preserving the source text of a number
+    /// literal, if this scalar value type can represent it
+    ///
+    /// It's used by [`parse_number_literal`], so custom scalars backed by
+    /// numbers wider or more precise than `i32`/`f64` (e.g. `u64` or
+    /// `BigDecimal`) may be parsed without a lossy roundtrip. Implementations
+    /// having a variant for such literals should override it together with
+    /// [`ScalarValue::as_number_literal`].
+    fn from_number_literal(_literal: &str) -> Option<Self> {
+        None
+    }
+
+    /// Borrow the source text of the number literal preserved by
+    /// [`ScalarValue::from_number_literal`]
+    fn as_number_literal(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Constructs a scalar value with its visitor, which can't fail for the
+/// primitive values
+fn visit<S, F>(f: F) -> S
+where
+    S: ScalarValue,
+    F: FnOnce(S::Visitor) -> Result<S, de::value::Error>,
+{
+    f(S::Visitor::default())
+        .unwrap_or_else(|e| panic!("Scalar value visitor failed to construct a value: {}", e))
+}
+
+/// Error of converting a scalar value into a primitive value
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarConversionError {
+    expected: &'static str,
+    found: String,
+}
+
+impl ScalarConversionError {
+    /// Constructs an error for a scalar value which isn't of the expected
+    /// GraphQL type, like `Int`
+    pub fn new<S: fmt::Display + ?Sized>(expected: &'static str, found: &S) -> Self {
+        ScalarConversionError {
+            expected,
+            found: found.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ScalarConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Expected `{}`, found: {}", self.expected, self.found)
+    }
+}
+
+impl Error for ScalarConversionError {}
+
+/// A primitive value which can be converted into any scalar value
+///
+/// This is what [`Value::scalar`](enum.Value.html#method.scalar) accepts, next
+/// to the scalar value itself. Other values of a custom scalar value have to
+/// be converted into it first, e.g. `Value::scalar(MyScalarValue::from(42_i64))`.
+pub trait IntoScalarValue<S> {
+    /// Performs the conversion
+    fn into_scalar_value(self) -> S;
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for S {
+    fn into_scalar_value(self) -> S {
+        self
+    }
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for i32 {
+    fn into_scalar_value(self) -> S {
+        S::from_int(self)
+    }
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for f64 {
+    fn into_scalar_value(self) -> S {
+        S::from_float(self)
+    }
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for String {
+    fn into_scalar_value(self) -> S {
+        S::from_string(self)
+    }
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for &str {
+    fn into_scalar_value(self) -> S {
+        S::from_string(self.to_owned())
+    }
+}
+
+impl<S: ScalarValue> IntoScalarValue<S> for bool {
+    fn into_scalar_value(self) -> S {
+        S::from_bool(self)
+    }
 }
 
 /// The default scalar value representation in juniper
@@ -284,6 +562,22 @@ impl ScalarValue for DefaultScalarValue {
             _ => None,
         }
     }
+
+    fn from_int(value: i32) -> Self {
+        DefaultScalarValue::Int(value)
+    }
+
+    fn from_float(value: f64) -> Self {
+        DefaultScalarValue::Float(value)
+    }
+
+    fn from_string(value: String) -> Self {
+        DefaultScalarValue::String(value)
+    }
+
+    fn from_bool(value: bool) -> Self {
+        DefaultScalarValue::Boolean(value)
+    }
 }
 
 impl<'a> From<&'a str> for DefaultScalarValue {
@@ -353,3 +647,282 @@ impl<'de> de::Visitor<'de> for DefaultScalarValueVisitor {
         Ok(DefaultScalarValue::String(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, sync::Arc};
+
+    use serde::{de, Serialize, Serializer};
+
+    use super::{parse_number_literal, ParseScalarResult, ScalarConversionError, ScalarValue};
+    use crate::{
+        parser::ScalarToken,
+        value::{DefaultScalarValue, Value},
+        EmptyMutation, EmptySubscription, RootNode, SharedString, Variables,
+    };
+
+    /// Scalar value storing all the numbers as `i64`, the number literals as
+    /// their source text and the strings as `Arc<str>`, without `From` impls
+    #[derive(Clone, Debug, PartialEq)]
+    enum WideScalarValue {
+        Number(i64),
+        Float(f64),
+        Literal(Arc<str>),
+        String(Arc<str>),
+        Boolean(bool),
+    }
+
+    impl Serialize for WideScalarValue {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Self::Number(n) => serializer.serialize_i64(*n),
+                Self::Float(n) => serializer.serialize_f64(*n),
+                Self::Literal(s) | Self::String(s) => serializer.serialize_str(s),
+                Self::Boolean(b) => serializer.serialize_bool(*b),
+            }
+        }
+    }
+
+    impl fmt::Display for WideScalarValue {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Self::Number(n) => write!(f, "{}", n),
+                Self::Float(n) => write!(f, "{}", n),
+                Self::Literal(s) => write!(f, "{}", s),
+                Self::String(s) => write!(f, "\"{}\"", s),
+                Self::Boolean(b) => write!(f, "{}", b),
+            }
+        }
+    }
+
+    impl ScalarValue for WideScalarValue {
+        type Visitor = WideScalarValueVisitor;
+
+        fn as_int(&self) -> Option<i32> {
+            self.try_to_int().ok()
+        }
+
+        fn from_shared_string(value: Arc<str>) -> Self {
+            Self::String(value)
+        }
+
+        fn as_string(&self) -> Option<String> {
+            self.as_str().map(str::to_owned)
+        }
+
+        fn as_str(&self) -> Option<&str> {
+            match self {
+                Self::String(s) => Some(s),
+                _ => None,
+            }
+        }
+
+        fn as_float(&self) -> Option<f64> {
+            match self {
+                Self::Number(n) => Some(*n as f64),
+                Self::Float(n) => Some(*n),
+                _ => None,
+            }
+        }
+
+        fn as_boolean(&self) -> Option<bool> {
+            match self {
+                Self::Boolean(b) => Some(*b),
+                _ => None,
+            }
+        }
+
+        fn try_to_int(&self) -> Result<i32, ScalarConversionError> {
+            match self {
+                Self::Number(n) if *n > i64::from(i32::max_value()) => {
+                    Err(ScalarConversionError::new("Int", "a number too large"))
+                }
+                Self::Number(n) => Ok(*n as i32),
+                _ => Err(ScalarConversionError::new("Int", self)),
+            }
+        }
+
+        fn from_number_literal(literal: &str) -> Option<Self> {
+            Some(Self::Literal(literal.into()))
+        }
+
+        fn as_number_literal(&self) -> Option<&str> {
+            match self {
+                Self::Literal(s) => Some(s),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct WideScalarValueVisitor;
+
+    impl<'de> de::Visitor<'de> for WideScalarValueVisitor {
+        type Value = WideScalarValue;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a valid input value")
+        }
+
+        fn visit_bool<E>(self, value: bool) -> Result<WideScalarValue, E> {
+            Ok(WideScalarValue::Boolean(value))
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<WideScalarValue, E> {
+            Ok(WideScalarValue::Number(value))
+        }
+
+        fn visit_f64<E>(self, value: f64) -> Result<WideScalarValue, E> {
+            Ok(WideScalarValue::Float(value))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<WideScalarValue, E>
+        where
+            E: de::Error,
+        {
+            self.visit_string(value.into())
+        }
+
+        fn visit_string<E>(self, value: String) -> Result<WideScalarValue, E> {
+            Ok(WideScalarValue::String(value.into()))
+        }
+    }
+
+    #[test]
+    fn constructs_values_with_visitor() {
+        assert_eq!(
+            Value::<WideScalarValue>::scalar(42),
+            Value::Scalar(WideScalarValue::Number(42)),
+        );
+        assert_eq!(
+            Value::<WideScalarValue>::scalar("foo"),
+            Value::Scalar(WideScalarValue::String("foo".into())),
+        );
+        assert_eq!(
+            Value::<WideScalarValue>::scalar(true),
+            Value::Scalar(WideScalarValue::Boolean(true)),
+        );
+    }
+
+    #[test]
+    fn explains_failed_conversions() {
+        assert_eq!(
+            WideScalarValue::Number(1 << 40)
+                .try_to_int()
+                .unwrap_err()
+                .to_string(),
+            "Expected `Int`, found: a number too large",
+        );
+        assert_eq!(
+            WideScalarValue::Boolean(true)
+                .try_to_float()
+                .unwrap_err()
+                .to_string(),
+            "Expected `Float`, found: true",
+        );
+        assert_eq!(WideScalarValue::Number(1).try_to_float(), Ok(1.0));
+    }
+
+    struct Query(SharedString);
+
+    #[crate::graphql_object(scalar = WideScalarValue)]
+    impl Query {
+        fn text(&self) -> &SharedString {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn resolves_shared_strings_without_copying() {
+        let text = SharedString::from("A long text");
+        let schema = RootNode::<_, _, _, WideScalarValue>::new(
+            Query(text.clone()),
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors,
+            ..
+        } = crate::execute_sync("{ text }", None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(errors, vec![]);
+        match result
+            .as_object_value()
+            .and_then(|o| o.get_field_value("text"))
+        {
+            Some(Value::Scalar(WideScalarValue::String(s))) => {
+                assert!(Arc::ptr_eq(s, &text.into_inner()))
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    struct Count(u64);
+
+    #[crate::graphql_scalar]
+    impl GraphQLScalar for Count {
+        fn resolve(&self) -> Value {
+            Value::scalar(WideScalarValue::Literal(self.0.to_string().into()))
+        }
+
+        fn from_input_value(v: &InputValue) -> Option<Count> {
+            v.as_number_literal()?.parse().ok().map(Count)
+        }
+
+        fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, WideScalarValue> {
+            parse_number_literal(value)
+        }
+    }
+
+    struct Counter;
+
+    #[crate::graphql_object(scalar = WideScalarValue)]
+    impl Counter {
+        fn next(count: Count) -> Count {
+            Count(count.0 + 1)
+        }
+    }
+
+    #[test]
+    fn preserves_number_literals() {
+        let schema = RootNode::<_, _, _, WideScalarValue>::new(
+            Counter,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let crate::ExecutionOutput {
+            data: result,
+            errors,
+            ..
+        } = crate::execute_sync(
+            "{ next(count: 18446744073709551614) }",
+            None,
+            &schema,
+            &Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_eq!(errors, vec![]);
+        assert_eq!(
+            result
+                .as_object_value()
+                .and_then(|o| o.get_field_value("next")),
+            Some(&Value::Scalar(WideScalarValue::Literal(
+                "18446744073709551615".into(),
+            ))),
+        );
+    }
+
+    #[test]
+    fn rejects_number_literals_unless_supported() {
+        assert!(parse_number_literal::<DefaultScalarValue>(ScalarToken::Int(
+            "18446744073709551615"
+        ))
+        .is_err());
+        assert!(parse_number_literal::<WideScalarValue>(ScalarToken::String("1")).is_err());
+    }
+}
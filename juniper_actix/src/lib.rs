@@ -41,14 +41,18 @@ Check the LICENSE file for details.
 #![doc(html_root_url = "https://docs.rs/juniper_actix/0.1.0")]
 
 use actix_web::{
-    error::{ErrorBadRequest, ErrorMethodNotAllowed, ErrorUnsupportedMediaType},
-    http::{header::CONTENT_TYPE, Method},
+    error::{ErrorBadRequest, ErrorMethodNotAllowed, ErrorUnsupportedMediaType, InternalError},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        Method, StatusCode,
+    },
     web, Error, FromRequest, HttpRequest, HttpResponse,
 };
+use futures::StreamExt as _;
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::graphiql_source, playground::playground_source, ContextFactory,
+        GraphQLBatchRequest, GraphQLRequest, HttpError, LimitError, RequestLimits, RequestParts,
     },
     ScalarValue,
 };
@@ -81,6 +85,34 @@ where
     }
 }
 
+/// Build the GraphQL context of the request with the provided factory
+///
+/// Fails with the status code and message of the factory's `HttpError`.
+pub async fn build_context<F>(context_factory: &F, req: &HttpRequest) -> Result<F::Context, Error>
+where
+    F: ContextFactory,
+{
+    context_factory
+        .build(&request_parts(req))
+        .await
+        .map_err(http_error)
+}
+
+fn request_parts(req: &HttpRequest) -> RequestParts {
+    RequestParts {
+        method: req.method().to_string(),
+        path: req.path().to_owned(),
+        query: Some(req.query_string())
+            .filter(|query| !query.is_empty())
+            .map(ToOwned::to_owned),
+        headers: req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect(),
+    }
+}
+
 /// Actix Web GraphQL Handler for GET and POST requests
 pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
     schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
@@ -130,6 +162,9 @@ where
         true => HttpResponse::Ok(),
         false => HttpResponse::BadRequest(),
     };
+    if let Some(policy) = gql_response.cache_policy() {
+        response.header(CACHE_CONTROL, policy.to_string());
+    }
     Ok(response
         .content_type("application/json")
         .body(body_response))
@@ -177,9 +212,98 @@ where
         true => HttpResponse::Ok(),
         false => HttpResponse::BadRequest(),
     };
+    if let Some(policy) = gql_batch_response.cache_policy() {
+        response.header(CACHE_CONTROL, policy.to_string());
+    }
+    Ok(response.content_type("application/json").body(gql_response))
+}
+
+/// Like [`graphql_handler`](fn.graphql_handler.html), answering the requests
+/// exceeding the limits with `413 Payload Too Large` before they're parsed
+pub async fn graphql_handler_with_limits<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    limits: &RequestLimits,
+    req: HttpRequest,
+    mut payload: actix_web::web::Payload,
+) -> Result<HttpResponse, Error>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let parts = request_parts(&req);
+    limits.check_parts(&parts).map_err(too_large)?;
+    let req = match *req.method() {
+        Method::POST => {
+            let content_type = req
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|hv| hv.to_str().ok());
+            if !matches!(
+                content_type,
+                Some("application/json") | Some("application/graphql")
+            ) {
+                return Err(ErrorUnsupportedMediaType(
+                    "GraphQL requests should have content type `application/json` or `application/graphql`",
+                ));
+            }
+
+            // Stop reading the body as soon as it exceeds the limit.
+            let max = limits.body_limit(&parts);
+            let mut body = web::BytesMut::new();
+            while let Some(chunk) = payload.next().await {
+                body.extend_from_slice(&chunk?);
+                if matches!(max, Some(max) if body.len() > max) {
+                    break;
+                }
+            }
+            limits.check_body(&parts, &body).map_err(too_large)?;
+            let body = String::from_utf8(body.to_vec()).map_err(ErrorBadRequest)?;
+
+            if content_type == Some("application/json") {
+                serde_json::from_str::<GraphQLBatchRequest<S>>(&body).map_err(ErrorBadRequest)?
+            } else {
+                GraphQLBatchRequest::Single(GraphQLRequest::new(body, None, None))
+            }
+        }
+        Method::GET => {
+            let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
+            GraphQLBatchRequest::Single(GraphQLRequest::from(get_req.into_inner()))
+        }
+        _ => {
+            return Err(ErrorMethodNotAllowed(
+                "GraphQL requests can only be sent with GET or POST",
+            ))
+        }
+    };
+    limits.check_request(&req).map_err(too_large)?;
+    let gql_batch_response = req.execute(schema, context).await;
+    let gql_response = serde_json::to_string(&gql_batch_response)?;
+    let mut response = match gql_batch_response.is_ok() {
+        true => HttpResponse::Ok(),
+        false => HttpResponse::BadRequest(),
+    };
+    if let Some(policy) = gql_batch_response.cache_policy() {
+        response.header(CACHE_CONTROL, policy.to_string());
+    }
     Ok(response.content_type("application/json").body(gql_response))
 }
 
+fn too_large(e: LimitError) -> Error {
+    http_error(e.into())
+}
+
+fn http_error(e: HttpError) -> Error {
+    let status = StatusCode::from_u16(e.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    InternalError::new(e.message().to_owned(), status).into()
+}
+
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
 ///
 /// For example:
@@ -634,6 +758,64 @@ mod tests {
         );
     }
 
+    #[actix_web::rt::test]
+    async fn limits_are_checked_before_parsing() {
+        async fn limited_index(
+            req: HttpRequest,
+            payload: actix_web::web::Payload,
+            schema: web::Data<Schema>,
+        ) -> Result<HttpResponse, Error> {
+            let context = Database::new();
+            let limits = RequestLimits::new().max_body_bytes(64).max_query_bytes(24);
+            graphql_handler_with_limits(&schema, &context, &limits, req, payload).await
+        }
+
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let mut app =
+            test::init_service(App::new().data(schema).route("/", web::to(limited_index))).await;
+
+        let req = test::TestRequest::post()
+            .header("content-type", "application/json")
+            .set_payload(r#"{"query": "{hero{name}}"}"#)
+            .uri("/")
+            .to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            take_response_body_string(&mut resp).await,
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#
+        );
+
+        let req = test::TestRequest::post()
+            .header("content-type", "application/json")
+            .set_payload(format!(
+                r#"{{"query": "{{hero{{name}}}}{}"}}"#,
+                " ".repeat(64)
+            ))
+            .uri("/")
+            .to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            take_response_body_string(&mut resp).await,
+            "The body of the request exceeds the limit of 64 bytes",
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/?query=%7Bhero%7Bname%20appearsIn%20id%7D%7D")
+            .to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            take_response_body_string(&mut resp).await,
+            "The query of the request exceeds the limit of 24 bytes",
+        );
+    }
+
     #[actix_web::rt::test]
     async fn graphql_get_works() {
         let schema: Schema = RootNode::new(
@@ -1,15 +1,9 @@
 use juniper::{
-    graphql_object, DefaultScalarValue, EmptyMutation, EmptySubscription, ExecutionError,
-    FieldError, GraphQLEnum, Value, Variables,
+    graphql_object, DefaultScalarValue, EmptyMutation, EmptySubscription, ExecutionOutput,
+    FieldError, GraphQLEnum, Variables,
 };
 
-pub type QueryResult = Result<
-    (
-        Value<DefaultScalarValue>,
-        Vec<ExecutionError<DefaultScalarValue>>,
-    ),
-    String,
->;
+pub type QueryResult = Result<ExecutionOutput<DefaultScalarValue>, String>;
 
 pub struct Context {}
 
@@ -0,0 +1,817 @@
+//! # juniper_cli
+//!
+//! Command line tools for [Juniper](https://github.com/graphql-rust/juniper)
+//! schemas, built on the parser and validator of the library:
+//!
+//! - `sdl` prints the schema in the GraphQL schema language;
+//! - `check` validates the documents of `.graphql` files against the schema;
+//! - `manifest` validates the documents, then prints their persisted query
+//!   manifest, mapping the SHA-256 hash of each document to its source;
+//! - `registry` prints the request registering the schema to the Apollo schema
+//!   registry, from CI without the JavaScript tooling;
+//! - `typescript` prints the TypeScript definitions of the types of the
+//!   schema, with the `typescript` feature;
+//! - `migrate` rewrites the legacy `graphql_object!` invocations of Rust files
+//!   into `impl` blocks with the `#[graphql_object]` attribute, without needing
+//!   a schema.
+//!
+//! ## The `cargo graphql` subcommand
+//!
+//! Installing the crate adds a `cargo graphql` subcommand, reading the schema
+//! from its schema language, or from the JSON result of the introspection query:
+//!
+//! ```sh
+//! cargo install juniper_cli
+//! cargo graphql --schema schema.graphql check queries/*.graphql
+//! cargo graphql --schema introspection.json manifest queries/*.graphql > manifest.json
+//! ```
+//!
+//! Publishing the schema from CI, with the git context read from the
+//! `APOLLO_VCS_BRANCH`, `APOLLO_VCS_COMMIT`, `APOLLO_VCS_COMMITTER`,
+//! `APOLLO_VCS_MESSAGE` and `APOLLO_VCS_REMOTE_URL` environment variables:
+//!
+//! ```sh
+//! cargo graphql --schema schema.graphql registry my-graph@current \
+//!     | curl https://graphql.api.apollographql.com/api/graphql \
+//!         -H "content-type: application/json" -H "x-api-key: $APOLLO_KEY" --data @-
+//! ```
+//!
+//! Migrating the objects of a crate, then reviewing the changes:
+//!
+//! ```sh
+//! cargo graphql migrate $(grep -rl 'graphql_object!' src)
+//! git diff
+//! ```
+//!
+//! ## A binary of the crate defining the schema
+//!
+//! To use the schema built by the crate itself, along with its custom scalars, add
+//! a binary calling [`main`] with it:
+//!
+//! ```no_run
+//! # use juniper::{EmptyMutation, EmptySubscription, RootNode};
+//! # struct Query;
+//! # #[juniper::graphql_object]
+//! # impl Query {
+//! #     fn version() -> i32 {
+//! #         1
+//! #     }
+//! # }
+//! // src/bin/graphql.rs
+//! fn main() {
+//!     let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//!     juniper_cli::main(&schema.schema);
+//! }
+//! ```
+//!
+//! Run with `cargo run --bin graphql -- check queries/*.graphql`.
+
+#![doc(html_root_url = "https://docs.rs/juniper_cli/0.1.0")]
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+mod migrate;
+mod registry;
+mod sdl;
+#[cfg(feature = "typescript")]
+mod typescript;
+
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use juniper::{DefaultScalarValue, GraphQLError, ScalarValue, SchemaType, Value};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "typescript")]
+pub use crate::typescript::typescript_definitions;
+pub use crate::{
+    migrate::{migrate, Migration},
+    registry::{publish_payload, GitContext, GraphRef},
+};
+
+const USAGE: &str = "\
+Usage: cargo graphql [--schema <schema.graphql | introspection.json>] <command>
+
+Commands:
+    sdl                 Print the schema in the GraphQL schema language
+    check <files>...    Validate the documents of the files against the schema
+    manifest <files>... Print the persisted query manifest of the documents
+    registry <graph>    Print the request registering the schema as the
+                        graph@variant of the Apollo schema registry
+    typescript          Print the TypeScript definitions of the types of the
+                        schema, with the typescript feature
+    migrate <files>...  Rewrite the graphql_object! invocations of the Rust files
+                        into #[graphql_object] impl blocks, without a schema";
+
+/// Error of a command
+#[derive(Debug)]
+pub enum Error {
+    /// The command line arguments are invalid
+    Usage(String),
+    /// The schema can't be read
+    Schema(String),
+    /// A file can't be read
+    Read(PathBuf, io::Error),
+    /// The output can't be written
+    Write(io::Error),
+    /// A Rust file can't be tokenized
+    InvalidSource(PathBuf, String),
+    /// Documents are invalid, with a line per error prefixed by the location
+    /// of the error, like `queries/user.graphql:3:5: Unknown field "age"`
+    InvalidDocuments(Vec<String>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            Error::Schema(message) => write!(f, "Invalid schema: {}", message),
+            Error::Read(path, error) => write!(f, "Can't read {}: {}", path.display(), error),
+            Error::Write(error) => write!(f, "Can't write the output: {}", error),
+            Error::InvalidSource(path, message) => write!(f, "{}: {}", path.display(), message),
+            Error::InvalidDocuments(errors) => write!(f, "{}", errors.join("\n")),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Command to run on a schema
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Print the schema in the GraphQL schema language
+    Sdl,
+    /// Validate the documents of the files against the schema
+    Check(Vec<PathBuf>),
+    /// Validate the documents of the files, then print their persisted query
+    /// manifest
+    Manifest(Vec<PathBuf>),
+    /// Print the request registering the schema to the Apollo schema registry
+    Registry(GraphRef),
+    /// Print the TypeScript definitions of the types of the schema
+    #[cfg(feature = "typescript")]
+    TypeScript,
+    /// Rewrite the `graphql_object!` invocations of the Rust files in place,
+    /// ignoring the schema
+    Migrate(Vec<PathBuf>),
+}
+
+/// Parsed command line arguments
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args {
+    /// The file to read the schema from, given with `--schema`
+    pub schema: Option<PathBuf>,
+    /// The command to run
+    pub command: Command,
+}
+
+impl Args {
+    /// Parse the arguments, without the name of the program
+    pub fn parse<I>(args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut args = args.into_iter().map(Into::into).peekable();
+        // Cargo passes the name of the subcommand along.
+        args.next_if_eq("graphql");
+
+        let mut schema = None;
+        let mut rest = vec![];
+        while let Some(arg) = args.next() {
+            if arg == "--schema" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("Missing the path of --schema".to_owned()))?;
+                schema = Some(PathBuf::from(path));
+            } else {
+                rest.push(arg);
+            }
+        }
+
+        let mut rest = rest.into_iter();
+        let command = rest
+            .next()
+            .ok_or_else(|| Error::Usage("Missing the command".to_owned()))?;
+        if command == "registry" {
+            let command = match (rest.next(), rest.next()) {
+                (Some(graph_ref), None) => {
+                    Command::Registry(graph_ref.parse().map_err(Error::Usage)?)
+                }
+                _ => {
+                    return Err(Error::Usage(
+                        "`registry` takes the graph to publish to, like `my-graph@current`"
+                            .to_owned(),
+                    ))
+                }
+            };
+            return Ok(Args { schema, command });
+        }
+        let files = rest.map(PathBuf::from).collect::<Vec<_>>();
+        let command = match command.as_str() {
+            "sdl" if files.is_empty() => Command::Sdl,
+            "sdl" => return Err(Error::Usage("`sdl` takes no files".to_owned())),
+            #[cfg(feature = "typescript")]
+            "typescript" if files.is_empty() => Command::TypeScript,
+            #[cfg(feature = "typescript")]
+            "typescript" => return Err(Error::Usage("`typescript` takes no files".to_owned())),
+            "check" | "manifest" | "migrate" if files.is_empty() => {
+                return Err(Error::Usage(format!(
+                    "`{}` takes the files to read",
+                    command
+                )))
+            }
+            "check" => Command::Check(files),
+            "manifest" => Command::Manifest(files),
+            "migrate" => Command::Migrate(files),
+            _ => return Err(Error::Usage(format!("Unknown command `{}`", command))),
+        };
+        Ok(Args { schema, command })
+    }
+
+    /// Parse the arguments the program was started with
+    pub fn from_env() -> Result<Self, Error> {
+        Self::parse(env::args().skip(1))
+    }
+}
+
+/// Read the schema from a file, either in the GraphQL schema language or, for
+/// `.json` files, the result of the introspection query
+pub fn load_schema(path: &Path) -> Result<SchemaType<'static, DefaultScalarValue>, Error> {
+    let source = fs::read_to_string(path).map_err(|e| Error::Read(path.to_owned(), e))?;
+    let result = if path.extension() == Some("json".as_ref()) {
+        serde_json::from_str::<Value>(&source).map_err(|e| Error::Schema(e.to_string()))?
+    } else {
+        sdl::introspection_result(&source)?
+    };
+    SchemaType::from_introspection(&result).map_err(|e| Error::Schema(e.to_string()))
+}
+
+/// Run the command on the schema, writing its output to `out`
+pub fn run<S, W>(schema: &SchemaType<S>, command: &Command, out: &mut W) -> Result<(), Error>
+where
+    S: ScalarValue,
+    W: Write,
+{
+    match command {
+        Command::Sdl => writeln!(out, "{}", schema.as_schema_language()).map_err(Error::Write),
+        Command::Check(files) => validate(schema, &read_documents(files)?),
+        Command::Manifest(files) => {
+            let documents = read_documents(files)?;
+            validate(schema, &documents)?;
+
+            let manifest = documents
+                .into_iter()
+                .map(|(_, source)| (hash(&source), source))
+                .collect::<BTreeMap<_, _>>();
+            serde_json::to_writer_pretty(&mut *out, &manifest)
+                .map_err(|e| Error::Write(e.into()))?;
+            writeln!(out).map_err(Error::Write)
+        }
+        Command::Registry(graph_ref) => {
+            let payload = publish_payload(
+                &schema.as_schema_language(),
+                graph_ref,
+                &GitContext::from_env(),
+            );
+            serde_json::to_writer_pretty(&mut *out, &payload)
+                .map_err(|e| Error::Write(e.into()))?;
+            writeln!(out).map_err(Error::Write)
+        }
+        #[cfg(feature = "typescript")]
+        Command::TypeScript => {
+            write!(out, "{}", typescript_definitions(schema)).map_err(Error::Write)
+        }
+        Command::Migrate(files) => migrate_files(files, out),
+    }
+}
+
+/// Run the command given on the command line on the schema, then exit
+///
+/// For binaries of the crates defining the schema, as the `--schema` argument
+/// isn't accepted.
+pub fn main<S: ScalarValue>(schema: &SchemaType<S>) -> ! {
+    let result = Args::from_env().and_then(|args| {
+        if args.schema.is_some() {
+            return Err(Error::Usage(
+                "The schema is built by this program, --schema isn't accepted".to_owned(),
+            ));
+        }
+        run(schema, &args.command, &mut io::stdout())
+    });
+    exit(result)
+}
+
+/// Exit with the result of a command, printing its error if any
+pub fn exit(result: Result<(), Error>) -> ! {
+    match result {
+        Ok(()) => process::exit(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(match e {
+                Error::Usage(_) => 2,
+                _ => 1,
+            })
+        }
+    }
+}
+
+/// Migrate the Rust files in place, writing to `out` the number of migrated
+/// invocations of each file and the locations of the skipped ones
+pub fn migrate_files<W: Write>(files: &[PathBuf], out: &mut W) -> Result<(), Error> {
+    for (path, source) in read_documents(files)? {
+        let migration =
+            migrate(&source).map_err(|message| Error::InvalidSource(path.to_owned(), message))?;
+        if migration.migrated > 0 {
+            fs::write(path, &migration.source).map_err(Error::Write)?;
+        }
+        writeln!(
+            out,
+            "{}: {} migrated, {} skipped",
+            path.display(),
+            migration.migrated,
+            migration.skipped.len(),
+        )
+        .map_err(Error::Write)?;
+        for (line, column, reason) in &migration.skipped {
+            writeln!(out, "{}:{}:{}: {}", path.display(), line, column, reason)
+                .map_err(Error::Write)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_documents(files: &[PathBuf]) -> Result<Vec<(&Path, String)>, Error> {
+    files
+        .iter()
+        .map(|path| {
+            fs::read_to_string(path)
+                .map(|source| (path.as_path(), source))
+                .map_err(|e| Error::Read(path.clone(), e))
+        })
+        .collect()
+}
+
+/// Validate all the documents, reporting all their errors at once
+fn validate<S: ScalarValue>(
+    schema: &SchemaType<S>,
+    documents: &[(&Path, String)],
+) -> Result<(), Error> {
+    let mut errors = vec![];
+    for (path, source) in documents {
+        let path = path.display();
+        match juniper::validate_document(source, schema) {
+            Ok(()) => {}
+            Err(GraphQLError::ParseError(error)) => errors.push(format!(
+                "{}:{}:{}: {}",
+                path,
+                error.start.line() + 1,
+                error.start.column() + 1,
+                error.item,
+            )),
+            Err(GraphQLError::ValidationError(rule_errors)) => {
+                for error in rule_errors {
+                    let location = error.locations().first().map_or(String::new(), |pos| {
+                        format!("{}:{}:", pos.line() + 1, pos.column() + 1)
+                    });
+                    errors.push(format!("{}:{} {}", path, location, error.message()));
+                }
+            }
+            Err(error) => errors.push(format!("{}: {}", path, error)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidDocuments(errors))
+    }
+}
+
+/// The SHA-256 hash of the document as sent by clients, in lowercase
+/// hexadecimal, like for Apollo's automatic persisted queries
+fn hash(source: &str) -> String {
+    Sha256::digest(source.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use juniper::{DefaultScalarValue, SchemaType};
+
+    use super::{
+        migrate, migrate_files, publish_payload, run, sdl, Args, Command, Error, GitContext,
+        GraphRef,
+    };
+
+    const SCHEMA: &str = r#"
+        schema {
+            query: Root
+        }
+
+        "A point in time"
+        scalar DateTime
+
+        enum Order {
+            ASC
+            DESC @deprecated(reason: "Use ASC")
+        }
+
+        interface Node {
+            id: ID!
+        }
+
+        type User implements Node {
+            id: ID!
+            name: String
+            posts(order: Order = ASC, first: Int = 10): [Post!]!
+        }
+
+        type Post implements Node {
+            id: ID!
+            title: String! @deprecated
+            publishedAt: DateTime
+        }
+
+        union SearchResult = User | Post
+
+        input UserFilter {
+            name: String
+            since: DateTime
+        }
+
+        type Root {
+            node(id: ID!): Node
+            search(term: String!): [SearchResult!]!
+            users(filter: UserFilter = {name: "a"}): [User!]!
+        }
+
+        directive @cached(ttl: Int = 60) on FIELD
+    "#;
+
+    fn schema() -> SchemaType<'static, DefaultScalarValue> {
+        let result = sdl::introspection_result(SCHEMA).unwrap();
+        SchemaType::from_introspection(&result).unwrap()
+    }
+
+    fn write_documents(name: &str, documents: &[&str]) -> Vec<PathBuf> {
+        let dir = std::env::temp_dir().join(format!("juniper_cli_{}", name));
+        fs::create_dir_all(&dir).unwrap();
+        documents
+            .iter()
+            .enumerate()
+            .map(|(i, document)| {
+                let path = dir.join(format!("{}.graphql", i));
+                fs::write(&path, document).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(
+            Args::parse(vec![
+                "graphql",
+                "--schema",
+                "schema.graphql",
+                "check",
+                "a.graphql"
+            ])
+            .unwrap(),
+            Args {
+                schema: Some("schema.graphql".into()),
+                command: Command::Check(vec!["a.graphql".into()]),
+            },
+        );
+        assert_eq!(
+            Args::parse(vec!["sdl"]).unwrap(),
+            Args {
+                schema: None,
+                command: Command::Sdl,
+            },
+        );
+        assert!(matches!(Args::parse(vec!["check"]), Err(Error::Usage(_))));
+        assert!(matches!(Args::parse(vec!["lint"]), Err(Error::Usage(_))));
+        assert!(matches!(Args::parse(vec!["migrate"]), Err(Error::Usage(_))));
+        assert_eq!(
+            Args::parse(vec!["registry", "my-graph@staging"]).unwrap(),
+            Args {
+                schema: None,
+                command: Command::Registry(GraphRef {
+                    graph: "my-graph".to_owned(),
+                    variant: "staging".to_owned(),
+                }),
+            },
+        );
+        assert!(matches!(
+            Args::parse(vec!["registry"]),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            Args::parse(vec!["registry", "a@b", "c@d"]),
+            Err(Error::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn prints_sdl() {
+        let mut out = vec![];
+        run(&schema(), &Command::Sdl, &mut out).unwrap();
+
+        let sdl = String::from_utf8(out).unwrap();
+        assert!(sdl.contains("schema {\n  query: Root\n}"), "{}", sdl);
+        assert!(sdl.contains("posts(order: Order = ASC, first: Int = 10): [Post!]!"));
+        assert!(
+            sdl.contains(r#"DESC @deprecated(reason: "Use ASC")"#),
+            "{}",
+            sdl
+        );
+        assert!(sdl.contains("union SearchResult = "));
+    }
+
+    #[test]
+    fn checks_documents() {
+        let files = write_documents(
+            "check",
+            &[
+                "{ node(id: 1) { id ... on User { posts(order: DESC) { title } } } }",
+                "query {\n  search(term: \"a\") {\n    ... on User { age }\n  }\n}",
+                "{ users(filter: {since: 1588291200}) @cached(ttl: 5) { name } }",
+                "{ users {",
+            ],
+        );
+
+        let result = run(&schema(), &Command::Check(files.clone()), &mut vec![]);
+
+        match result {
+            Err(Error::InvalidDocuments(errors)) => assert_eq!(
+                errors,
+                vec![
+                    format!(
+                        r#"{}:3:19: Unknown field "age" on type "User". Did you mean "name"?"#,
+                        files[1].display(),
+                    ),
+                    format!("{}:1:10: Unexpected end of input", files[3].display()),
+                ],
+            ),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn prints_manifest() {
+        let files = write_documents(
+            "manifest",
+            &[
+                "{ users { name } }",
+                "{ search(term: \"a\") { __typename } }",
+            ],
+        );
+
+        let mut out = vec![];
+        run(&schema(), &Command::Manifest(files), &mut out).unwrap();
+
+        let manifest: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            manifest,
+            serde_json::json!({
+                "3de70fc68b4b5d028115bafc36966b4da03fd708da6bc2410a36c7a743fcef25": "{ users { name } }",
+                "7150483d26b3bf6d0c64e5682b89707fa6c0f0c195411e5e5c8d00896c2f47a8": "{ search(term: \"a\") { __typename } }",
+            }),
+        );
+    }
+
+    #[test]
+    fn parses_graph_refs() {
+        assert_eq!(
+            "my-graph".parse::<GraphRef>().unwrap().to_string(),
+            "my-graph@current",
+        );
+        assert_eq!("my-graph@prod".parse::<GraphRef>().unwrap().variant, "prod",);
+        assert!("@prod".parse::<GraphRef>().is_err());
+        assert!("my-graph@".parse::<GraphRef>().is_err());
+        assert!("my-graph@a@b".parse::<GraphRef>().is_err());
+    }
+
+    #[test]
+    fn prints_publish_payload() {
+        let sdl = schema().as_schema_language();
+        let git = GitContext {
+            branch: Some("main".to_owned()),
+            commit: Some("0bcb077".to_owned()),
+            ..GitContext::default()
+        };
+        let payload = publish_payload(&sdl, &"my-graph@staging".parse().unwrap(), &git);
+
+        assert_eq!(payload["operationName"], "UploadSchema");
+        assert!(payload["query"]
+            .as_str()
+            .unwrap()
+            .starts_with("mutation UploadSchema("));
+        assert_eq!(
+            payload["variables"],
+            serde_json::json!({
+                "id": "my-graph",
+                "tag": "staging",
+                "schemaDocument": sdl,
+                "gitContext": {
+                    "branch": "main",
+                    "commit": "0bcb077",
+                    "committer": null,
+                    "message": null,
+                    "remoteUrl": null,
+                },
+            }),
+        );
+    }
+
+    #[cfg(feature = "typescript")]
+    #[test]
+    fn prints_typescript_definitions() {
+        let mut out = vec![];
+        run(&schema(), &Command::TypeScript, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"/** A point in time */
+export type DateTime = unknown;
+
+export interface Node {
+  id: string;
+}
+
+export type Order = "ASC" | "DESC";
+
+export interface Post extends Node {
+  __typename?: "Post";
+  id: string;
+  /** @deprecated */
+  title: string;
+  publishedAt: DateTime | null;
+}
+
+export interface Root {
+  __typename?: "Root";
+  node: Node | null;
+  search: Array<SearchResult>;
+  users: Array<User>;
+}
+
+export interface RootNodeArgs {
+  id: string;
+}
+
+export interface RootSearchArgs {
+  term: string;
+}
+
+export interface RootUsersArgs {
+  filter?: UserFilter | null;
+}
+
+export type SearchResult = User | Post;
+
+export interface User extends Node {
+  __typename?: "User";
+  id: string;
+  name: string | null;
+  posts: Array<Post>;
+}
+
+export interface UserPostsArgs {
+  order?: Order | null;
+  first?: number | null;
+}
+
+export interface UserFilter {
+  name?: string | null;
+  since?: DateTime | null;
+}
+"#,
+        );
+    }
+
+    const LEGACY: &str = r#"use juniper::{graphql_object, Executor};
+
+struct Root;
+
+graphql_object!(Root: Database as "Query" |&self| {
+    description: "The root query object of the schema"
+
+    interfaces: [&Node]
+
+    /// The current user
+    field current_user(&executor) -> Option<User> {
+        executor.context().current_user()
+    }
+
+    field deprecated "Use search" users(
+        /// The maximum number of users
+        first = 10: i32,
+        name: Option<String> as "Filter on the name",
+    ) -> Vec<User> as "All the users" {
+        vec![]
+    }
+});
+
+mod nested {
+    juniper::graphql_object!(<'a> Wrapper<'a>: () where Scalar = MyScalar |&self| {
+        field value() -> &str { self.0 }
+    });
+}
+
+graphql_object!(<S> Generic: () where Scalar = <S> |&self| {
+    field value() -> i32 { 1 }
+});
+"#;
+
+    const MIGRATED: &str = r#"use juniper::{graphql_object, Executor};
+
+struct Root;
+
+#[juniper::graphql_object(
+    Context = Database,
+    name = "Query",
+    description = "The root query object of the schema",
+    interfaces = [&Node],
+)]
+impl Root {
+    /// The current user
+    fn current_user(&self, executor: &juniper::Executor) -> Option<User> {
+        executor.context().current_user()
+    }
+
+    #[graphql(
+        description = "All the users",
+        deprecated = "Use search",
+        arguments(
+            first(description = "The maximum number of users", default = 10),
+            name(description = "Filter on the name"),
+        ),
+    )]
+    fn users(&self, first: i32, name: Option<String>) -> Vec<User> {
+        vec![]
+    }
+}
+
+mod nested {
+    #[juniper::graphql_object(Scalar = MyScalar)]
+    impl<'a> Wrapper<'a> {
+        fn value(&self) -> &str { self.0 }
+    }
+}
+
+graphql_object!(<S> Generic: () where Scalar = <S> |&self| {
+    field value() -> i32 { 1 }
+});
+"#;
+
+    #[test]
+    fn migrates_objects() {
+        let migration = migrate(LEGACY).unwrap();
+        assert_eq!(migration.source, MIGRATED);
+        assert_eq!(migration.migrated, 2);
+        assert_eq!(
+            migration.skipped,
+            vec![(
+                30,
+                1,
+                "objects generic over the scalar value have to be migrated by hand".to_owned(),
+            )],
+        );
+
+        let migration = migrate(MIGRATED).unwrap();
+        assert_eq!(migration.source, MIGRATED);
+        assert_eq!(migration.migrated, 0);
+    }
+
+    #[test]
+    fn migrates_files() {
+        let dir = std::env::temp_dir().join("juniper_cli_migrate");
+        fs::create_dir_all(&dir).unwrap();
+        let files = vec![dir.join("schema.rs")];
+        let path = &files[0];
+        fs::write(path, LEGACY).unwrap();
+
+        let mut out = vec![];
+        migrate_files(&files, &mut out).unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), MIGRATED);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "{0}: 2 migrated, 1 skipped\n\
+                 {0}:30:1: objects generic over the scalar value have to be migrated by hand\n",
+                path.display(),
+            ),
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! The `cargo graphql` subcommand, reading the schema from the file given with
+//! `--schema`
+
+use juniper_cli::{exit, load_schema, migrate_files, run, Args, Command, Error};
+
+fn main() {
+    let result = Args::from_env().and_then(|args| {
+        if let Command::Migrate(files) = &args.command {
+            // Migrating doesn't involve the schema.
+            return migrate_files(files, &mut std::io::stdout());
+        }
+        let path = args
+            .schema
+            .ok_or_else(|| Error::Usage("Missing the --schema file".to_owned()))?;
+        let schema = load_schema(&path)?;
+        run(&schema, &args.command, &mut std::io::stdout())
+    });
+    exit(result)
+}
@@ -0,0 +1,548 @@
+//! Rewriting of the legacy `graphql_object!` invocations into `impl` blocks
+//! with the `#[graphql_object]` attribute
+//!
+//! The fields keep their bodies, doc comments and attributes as written, while
+//! the metadata of the legacy syntax moves to the arguments of the attributes:
+//!
+//! ```text
+//! graphql_object!(User: Database as "Person" |&self| {
+//!     description: "A user"
+//!
+//!     field name(&executor, upper = false: bool as "Capitalize") -> String {
+//!         executor.context().name(self.id, upper)
+//!     }
+//! });
+//! ```
+//!
+//! becomes
+//!
+//! ```text
+//! #[juniper::graphql_object(Context = Database, name = "Person", description = "A user")]
+//! impl User {
+//!     #[graphql(arguments(upper(description = "Capitalize", default = false)))]
+//!     fn name(&self, executor: &juniper::Executor, upper: bool) -> String {
+//!         executor.context().name(self.id, upper)
+//!     }
+//! }
+//! ```
+//!
+//! Invocations the attribute can't express, like the ones generic over the
+//! scalar value, are left as is and reported.
+
+use std::ops::Range;
+
+use proc_macro2::{LineColumn, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Block, Expr, Generics, Ident, LitStr, Token, Type,
+};
+
+/// Result of migrating a source file
+#[derive(Debug, PartialEq)]
+pub struct Migration {
+    /// The migrated source
+    pub source: String,
+    /// The number of migrated invocations
+    pub migrated: usize,
+    /// The invocations left as is, with their 1-based line and column, and
+    /// the reason why
+    pub skipped: Vec<(usize, usize, String)>,
+}
+
+/// Migrate all the `graphql_object!` invocations of the Rust `source`
+pub fn migrate(source: &str) -> Result<Migration, String> {
+    let tokens = source
+        .parse::<TokenStream>()
+        .map_err(|e| format!("Invalid Rust source: {}", e))?;
+    let index = SourceIndex::new(source);
+
+    let mut invocations = vec![];
+    find_invocations(tokens, &mut invocations);
+
+    let mut migration = Migration {
+        source: String::with_capacity(source.len()),
+        migrated: 0,
+        skipped: vec![],
+    };
+    let mut copied = 0;
+    for Invocation {
+        path,
+        body,
+        start,
+        end,
+    } in invocations
+    {
+        match syn::parse2::<LegacyObject>(body) {
+            Ok(object) => {
+                migration
+                    .source
+                    .push_str(&source[copied..index.offset(start)]);
+                migration
+                    .source
+                    // The invocation is already indented.
+                    .push_str(object.render(&index, &path, start).trim_start());
+                copied = index.offset(end);
+                migration.migrated += 1;
+            }
+            Err(e) => migration
+                .skipped
+                .push((start.line, start.column + 1, e.to_string())),
+        }
+    }
+    migration.source.push_str(&source[copied..]);
+    Ok(migration)
+}
+
+/// A `graphql_object!(...);` invocation
+struct Invocation {
+    /// The path of the macro, without `graphql_object`
+    path: String,
+    body: TokenStream,
+    start: LineColumn,
+    end: LineColumn,
+}
+
+fn find_invocations(tokens: TokenStream, invocations: &mut Vec<Invocation>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Ident(ident) if ident == "graphql_object" => {
+                if let (Some(TokenTree::Punct(bang)), Some(TokenTree::Group(group))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    if bang.as_char() == '!' {
+                        // Include the path leading to the macro, like `juniper::`.
+                        let mut first = i;
+                        while first >= 3 {
+                            match (&tokens[first - 3], &tokens[first - 2], &tokens[first - 1]) {
+                                (TokenTree::Ident(_), TokenTree::Punct(a), TokenTree::Punct(b))
+                                    if a.as_char() == ':' && b.as_char() == ':' =>
+                                {
+                                    first -= 3
+                                }
+                                _ => break,
+                            }
+                        }
+                        let path = tokens[first..i]
+                            .iter()
+                            .map(|t| t.to_string())
+                            .collect::<String>();
+                        let mut end = group.span().end();
+                        match tokens.get(i + 3) {
+                            Some(TokenTree::Punct(semi)) if semi.as_char() == ';' => {
+                                end = semi.span().end();
+                                i += 1;
+                            }
+                            _ => {}
+                        }
+                        invocations.push(Invocation {
+                            path,
+                            body: group.stream(),
+                            start: tokens[first].span().start(),
+                            end,
+                        });
+                        i += 3;
+                        continue;
+                    }
+                }
+            }
+            TokenTree::Group(group) => find_invocations(group.stream(), invocations),
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Byte offsets of the lines of a source, to slice it with the spans of its
+/// tokens
+struct SourceIndex<'a> {
+    source: &'a str,
+    lines: Vec<usize>,
+}
+
+impl<'a> SourceIndex<'a> {
+    fn new(source: &'a str) -> Self {
+        let lines = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceIndex { source, lines }
+    }
+
+    fn offset(&self, position: LineColumn) -> usize {
+        let line = self.lines[position.line - 1];
+        line + self.source[line..]
+            .char_indices()
+            .nth(position.column)
+            .map_or(self.source.len() - line, |(i, _)| i)
+    }
+
+    /// The source of the tokens, as written
+    fn text<T: ToTokens>(&self, tokens: &T) -> &'a str {
+        let range = self.range(tokens);
+        &self.source[range]
+    }
+
+    fn range<T: ToTokens>(&self, tokens: &T) -> Range<usize> {
+        let tokens = tokens.to_token_stream().into_iter().collect::<Vec<_>>();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => {
+                self.offset(first.span().start())..self.offset(last.span().end())
+            }
+            _ => 0..0,
+        }
+    }
+}
+
+/// The arguments of `graphql_object!`
+struct LegacyObject {
+    generics: Generics,
+    ty: Type,
+    context: Option<Type>,
+    name: Option<LitStr>,
+    scalar: Option<Type>,
+    description: Option<LitStr>,
+    interfaces: Vec<Type>,
+    fields: Vec<LegacyField>,
+}
+
+struct LegacyField {
+    attrs: Vec<Attribute>,
+    deprecated: Option<LitStr>,
+    name: Ident,
+    executor: Option<Ident>,
+    args: Vec<LegacyArg>,
+    return_ty: Type,
+    description: Option<LitStr>,
+    body: Block,
+}
+
+struct LegacyArg {
+    docs: Vec<String>,
+    name: Ident,
+    default: Option<TokenTree>,
+    ty: Type,
+    description: Option<Expr>,
+}
+
+impl Parse for LegacyObject {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let generics = if input.peek(Token![<]) {
+            input.parse()?
+        } else {
+            Generics::default()
+        };
+        let ty = input.parse()?;
+        let context = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let name = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let scalar = if input.peek(Token![where]) {
+            input.parse::<Token![where]>()?;
+            let ident = input.parse::<Ident>()?;
+            if ident != "Scalar" {
+                return Err(syn::Error::new(ident.span(), "expected `Scalar`"));
+            }
+            input.parse::<Token![=]>()?;
+            if input.peek(Token![<]) {
+                return Err(input
+                    .error("objects generic over the scalar value have to be migrated by hand"));
+            }
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            input.parse::<Token![&]>()?;
+            input.parse::<Token![self]>().map_err(|e| {
+                syn::Error::new(
+                    e.span(),
+                    "only `|&self|` can be migrated, rename the receiver by hand",
+                )
+            })?;
+            input.parse::<Token![|]>()?;
+        }
+
+        let content;
+        syn::braced!(content in input);
+        let mut object = LegacyObject {
+            generics,
+            ty,
+            context,
+            name,
+            scalar,
+            description: None,
+            interfaces: vec![],
+            fields: vec![],
+        };
+        while !content.is_empty() {
+            let attrs = content.call(Attribute::parse_outer)?;
+            let keyword = content.parse::<Ident>()?;
+            if keyword == "description" && attrs.is_empty() {
+                content.parse::<Token![:]>()?;
+                object.description = Some(content.parse()?);
+            } else if keyword == "interfaces" && attrs.is_empty() {
+                content.parse::<Token![:]>()?;
+                let interfaces;
+                bracketed!(interfaces in content);
+                object.interfaces = Punctuated::<Type, Token![,]>::parse_terminated(&interfaces)?
+                    .into_iter()
+                    .collect();
+            } else if keyword == "field" {
+                object.fields.push(LegacyField::parse(attrs, &content)?);
+            } else {
+                return Err(syn::Error::new(
+                    keyword.span(),
+                    format!("unexpected `{}`", keyword),
+                ));
+            }
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        Ok(object)
+    }
+}
+
+impl LegacyField {
+    fn parse(attrs: Vec<Attribute>, input: ParseStream) -> syn::Result<Self> {
+        let deprecated = if input.peek(Ident) && input.peek2(LitStr) {
+            let keyword = input.parse::<Ident>()?;
+            if keyword != "deprecated" {
+                return Err(syn::Error::new(keyword.span(), "expected `deprecated`"));
+            }
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let name = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+        let executor = if content.peek(Token![&]) {
+            content.parse::<Token![&]>()?;
+            let executor = content.parse()?;
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+            Some(executor)
+        } else {
+            None
+        };
+        let args = Punctuated::<LegacyArg, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        input.parse::<Token![->]>()?;
+        let return_ty = input.parse()?;
+        let description = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let body = input.parse()?;
+        Ok(LegacyField {
+            attrs,
+            deprecated,
+            name,
+            executor,
+            args,
+            return_ty,
+            description,
+            body,
+        })
+    }
+}
+
+impl Parse for LegacyArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut docs = vec![];
+        for attr in input.call(Attribute::parse_outer)? {
+            match attr.parse_meta()? {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    lit: syn::Lit::Str(ref doc),
+                    ..
+                }) if path.is_ident("doc") => docs.push(doc.value().trim().to_owned()),
+                _ => {
+                    return Err(syn::Error::new(
+                        attr.bracket_token.span,
+                        "only doc comments are expected on arguments",
+                    ))
+                }
+            }
+        }
+        let name = input.parse()?;
+        let default = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let description = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(LegacyArg {
+            docs,
+            name,
+            default,
+            ty,
+            description,
+        })
+    }
+}
+
+impl LegacyObject {
+    fn render(&self, index: &SourceIndex, path: &str, start: LineColumn) -> String {
+        let indent = " ".repeat(start.column);
+        let mut attr_args = vec![];
+        if let Some(context) = &self.context {
+            match context {
+                Type::Tuple(tuple) if tuple.elems.is_empty() => {}
+                _ => attr_args.push(format!("Context = {}", index.text(context))),
+            }
+        }
+        if let Some(name) = &self.name {
+            attr_args.push(format!("name = {}", index.text(name)));
+        }
+        if let Some(scalar) = &self.scalar {
+            attr_args.push(format!("Scalar = {}", index.text(scalar)));
+        }
+        if let Some(description) = &self.description {
+            attr_args.push(format!("description = {}", index.text(description)));
+        }
+        if !self.interfaces.is_empty() {
+            let interfaces = self
+                .interfaces
+                .iter()
+                .map(|i| index.text(i))
+                .collect::<Vec<_>>();
+            attr_args.push(format!("interfaces = [{}]", interfaces.join(", ")));
+        }
+        let path = if path.is_empty() { "juniper::" } else { path };
+
+        let mut out = attribute(&indent, &format!("{}graphql_object", path), &attr_args);
+        out.push_str(&format!(
+            "{}impl{} {} {{\n",
+            indent,
+            index.text(&self.generics),
+            index.text(&self.ty),
+        ));
+        let field_indent = format!("{}    ", indent);
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&field.render(index, path, &field_indent));
+        }
+        out.push_str(&indent);
+        out.push('}');
+        out
+    }
+}
+
+impl LegacyField {
+    fn render(&self, index: &SourceIndex, path: &str, indent: &str) -> String {
+        let mut out = String::new();
+        for attr in &self.attrs {
+            out.push_str(&format!("{}{}\n", indent, index.text(attr)));
+        }
+
+        let mut graphql_args = vec![];
+        if let Some(description) = &self.description {
+            graphql_args.push(format!("description = {}", index.text(description)));
+        }
+        if let Some(reason) = &self.deprecated {
+            graphql_args.push(format!("deprecated = {}", index.text(reason)));
+        }
+        let arguments = self
+            .args
+            .iter()
+            .filter_map(|arg| {
+                let mut meta = vec![];
+                if let Some(description) = &arg.description {
+                    meta.push(format!("description = {}", index.text(description)));
+                } else if !arg.docs.is_empty() {
+                    meta.push(format!("description = {:?}", arg.docs.join("\n")));
+                }
+                if let Some(default) = &arg.default {
+                    meta.push(format!("default = {}", index.text(default)));
+                }
+                if meta.is_empty() {
+                    None
+                } else {
+                    Some(format!("{}({})", arg.name, meta.join(", ")))
+                }
+            })
+            .collect::<Vec<_>>();
+        if !arguments.is_empty() {
+            let line = format!("arguments({})", arguments.join(", "));
+            graphql_args.push(if indent.len() + line.len() + 6 <= 100 {
+                line
+            } else {
+                let mut out = "arguments(\n".to_owned();
+                for argument in &arguments {
+                    out.push_str(&format!("{}        {},\n", indent, argument));
+                }
+                out.push_str(&format!("{}    )", indent));
+                out
+            });
+        }
+        if !graphql_args.is_empty() {
+            out.push_str(&attribute(indent, "graphql", &graphql_args));
+        }
+
+        let mut params = vec!["&self".to_owned()];
+        if let Some(executor) = &self.executor {
+            params.push(format!("{}: &{}Executor", executor, path));
+        }
+        for arg in &self.args {
+            params.push(format!("{}: {}", arg.name, index.text(&arg.ty)));
+        }
+        out.push_str(&format!(
+            "{}fn {}({}) -> {} {}\n",
+            indent,
+            self.name,
+            params.join(", "),
+            index.text(&self.return_ty),
+            index.text(&self.body),
+        ));
+        out
+    }
+}
+
+/// Renders the `#[name(args)]` attribute on a line, or on a line per argument
+/// when too long
+fn attribute(indent: &str, name: &str, args: &[String]) -> String {
+    if args.is_empty() {
+        return format!("{}#[{}]\n", indent, name);
+    }
+    let line = format!("{}#[{}({})]\n", indent, name, args.join(", "));
+    if line.len() <= 101 && !args.iter().any(|arg| arg.contains('\n')) {
+        return line;
+    }
+    let mut out = format!("{}#[{}(\n", indent, name);
+    for arg in args {
+        out.push_str(&format!("{}    {},\n", indent, arg));
+    }
+    out.push_str(&format!("{})]\n", indent));
+    out
+}
@@ -0,0 +1,119 @@
+//! Payloads publishing a schema to a schema registry
+
+use std::{env, fmt, str::FromStr};
+
+use serde_json::json;
+
+/// The operation of the Apollo Platform API registering a schema
+const UPLOAD_SCHEMA: &str = "\
+mutation UploadSchema($id: ID!, $schemaDocument: String!, $tag: String!, $gitContext: GitContextInput) {
+  service(id: $id) {
+    uploadSchema(schemaDocument: $schemaDocument, tag: $tag, gitContext: $gitContext) {
+      code
+      message
+      success
+      tag {
+        variant {
+          name
+        }
+        schema {
+          hash
+        }
+      }
+    }
+  }
+}";
+
+/// Reference to a variant of a graph of a registry, written `graph@variant`,
+/// the variant defaulting to `current`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphRef {
+    /// The id of the graph
+    pub graph: String,
+    /// The variant of the graph, like `current` or `staging`
+    pub variant: String,
+}
+
+impl FromStr for GraphRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (graph, variant) = match s.find('@') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, "current"),
+        };
+        if graph.is_empty() || variant.is_empty() || variant.contains('@') {
+            return Err(format!(
+                "Invalid graph reference `{}`, expected `graph@variant`",
+                s
+            ));
+        }
+        Ok(GraphRef {
+            graph: graph.to_owned(),
+            variant: variant.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for GraphRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.graph, self.variant)
+    }
+}
+
+/// The version control context a schema is published from, shown by the
+/// registry along with the schema
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GitContext {
+    /// The branch, like `main`
+    pub branch: Option<String>,
+    /// The hash of the commit
+    pub commit: Option<String>,
+    /// The author of the commit
+    pub committer: Option<String>,
+    /// The message of the commit
+    pub message: Option<String>,
+    /// The URL of the repository
+    pub remote_url: Option<String>,
+}
+
+impl GitContext {
+    /// Read the context from the `APOLLO_VCS_BRANCH`, `APOLLO_VCS_COMMIT`,
+    /// `APOLLO_VCS_COMMITTER`, `APOLLO_VCS_MESSAGE` and
+    /// `APOLLO_VCS_REMOTE_URL` environment variables, as set by the CI
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok().filter(|v| !v.is_empty());
+        GitContext {
+            branch: var("APOLLO_VCS_BRANCH"),
+            commit: var("APOLLO_VCS_COMMIT"),
+            committer: var("APOLLO_VCS_COMMITTER"),
+            message: var("APOLLO_VCS_MESSAGE"),
+            remote_url: var("APOLLO_VCS_REMOTE_URL"),
+        }
+    }
+}
+
+/// The request body registering the schema, given in the schema language,
+/// as the `graph_ref` variant of the registry
+///
+/// The body is the one of the `UploadSchema` operation of the Apollo Platform
+/// API, to `POST` to `https://graphql.api.apollographql.com/api/graphql` with
+/// the API key of the graph in the `x-api-key` header.
+pub fn publish_payload(sdl: &str, graph_ref: &GraphRef, git: &GitContext) -> serde_json::Value {
+    json!({
+        "operationName": "UploadSchema",
+        "query": UPLOAD_SCHEMA,
+        "variables": {
+            "id": graph_ref.graph,
+            "tag": graph_ref.variant,
+            "schemaDocument": sdl,
+            "gitContext": {
+                "branch": git.branch,
+                "commit": git.commit,
+                "committer": git.committer,
+                "message": git.message,
+                "remoteUrl": git.remote_url,
+            },
+        },
+    })
+}
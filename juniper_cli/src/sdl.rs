@@ -0,0 +1,261 @@
+//! Reading schemas from the GraphQL schema language, by describing them as the
+//! result of the introspection query
+
+use std::collections::HashMap;
+
+use graphql_parser::schema::{
+    parse_schema, Definition, Directive, Document, EnumValue, Field, InputValue, Type,
+    TypeDefinition,
+};
+use juniper::{DefaultScalarValue, Object, Value};
+
+use crate::Error;
+
+const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// Describe the schema defined in the schema language `source` as the result
+/// of the introspection query
+pub(crate) fn introspection_result(source: &str) -> Result<Value<DefaultScalarValue>, Error> {
+    let document: Document<String> =
+        parse_schema(source).map_err(|e| Error::Schema(e.to_string()))?;
+
+    let mut kinds = BUILTIN_SCALARS
+        .iter()
+        .map(|&name| (name.to_owned(), "SCALAR"))
+        .collect::<HashMap<_, _>>();
+    let mut implementations = HashMap::<&str, Vec<&str>>::new();
+    let (mut query, mut mutation, mut subscription) = (None, None, None);
+    for definition in &document.definitions {
+        match definition {
+            Definition::SchemaDefinition(schema) => {
+                query = schema.query.as_deref();
+                mutation = schema.mutation.as_deref();
+                subscription = schema.subscription.as_deref();
+            }
+            Definition::TypeDefinition(definition) => {
+                let (name, kind) = match definition {
+                    TypeDefinition::Scalar(t) => (&t.name, "SCALAR"),
+                    TypeDefinition::Object(t) => {
+                        for interface in &t.implements_interfaces {
+                            implementations
+                                .entry(interface.as_str())
+                                .or_default()
+                                .push(t.name.as_str());
+                        }
+                        (&t.name, "OBJECT")
+                    }
+                    TypeDefinition::Interface(t) => (&t.name, "INTERFACE"),
+                    TypeDefinition::Union(t) => (&t.name, "UNION"),
+                    TypeDefinition::Enum(t) => (&t.name, "ENUM"),
+                    TypeDefinition::InputObject(t) => (&t.name, "INPUT_OBJECT"),
+                };
+                kinds.insert(name.clone(), kind);
+            }
+            Definition::TypeExtension(_) => {
+                return Err(Error::Schema("type extensions aren't supported".to_owned()))
+            }
+            Definition::DirectiveDefinition(_) => {}
+        }
+    }
+    let describer = Describer { kinds };
+
+    let mut types = BUILTIN_SCALARS
+        .iter()
+        .map(|&name| object(vec![("kind", string("SCALAR")), ("name", string(name))]))
+        .collect::<Vec<_>>();
+    let mut directives = vec![];
+    for definition in &document.definitions {
+        match definition {
+            Definition::TypeDefinition(TypeDefinition::Scalar(t))
+                if !BUILTIN_SCALARS.contains(&t.name.as_str()) =>
+            {
+                types.push(object(vec![
+                    ("kind", string("SCALAR")),
+                    ("name", string(&t.name)),
+                    ("description", opt_string(&t.description)),
+                ]))
+            }
+            Definition::TypeDefinition(TypeDefinition::Object(t)) => types.push(object(vec![
+                ("kind", string("OBJECT")),
+                ("name", string(&t.name)),
+                ("description", opt_string(&t.description)),
+                ("fields", describer.fields(&t.fields)),
+                ("interfaces", describer.type_refs(&t.implements_interfaces)),
+            ])),
+            Definition::TypeDefinition(TypeDefinition::Interface(t)) => {
+                let implementations = implementations.remove(t.name.as_str()).unwrap_or_default();
+                types.push(object(vec![
+                    ("kind", string("INTERFACE")),
+                    ("name", string(&t.name)),
+                    ("description", opt_string(&t.description)),
+                    ("fields", describer.fields(&t.fields)),
+                    ("possibleTypes", describer.type_refs(&implementations)),
+                ]))
+            }
+            Definition::TypeDefinition(TypeDefinition::Union(t)) => types.push(object(vec![
+                ("kind", string("UNION")),
+                ("name", string(&t.name)),
+                ("description", opt_string(&t.description)),
+                ("possibleTypes", describer.type_refs(&t.types)),
+            ])),
+            Definition::TypeDefinition(TypeDefinition::Enum(t)) => types.push(object(vec![
+                ("kind", string("ENUM")),
+                ("name", string(&t.name)),
+                ("description", opt_string(&t.description)),
+                (
+                    "enumValues",
+                    Value::list(t.values.iter().map(enum_value).collect()),
+                ),
+            ])),
+            Definition::TypeDefinition(TypeDefinition::InputObject(t)) => types.push(object(vec![
+                ("kind", string("INPUT_OBJECT")),
+                ("name", string(&t.name)),
+                ("description", opt_string(&t.description)),
+                ("inputFields", describer.input_values(&t.fields)),
+            ])),
+            Definition::DirectiveDefinition(d) => directives.push(object(vec![
+                ("name", string(&d.name)),
+                ("description", opt_string(&d.description)),
+                (
+                    "locations",
+                    Value::list(d.locations.iter().map(|l| string(l.as_str())).collect()),
+                ),
+                ("args", describer.input_values(&d.arguments)),
+            ])),
+            _ => {}
+        }
+    }
+
+    // Without a schema definition, the root types are the ones named after
+    // their operation.
+    let root_type = |name: Option<&str>, default: &str| {
+        let name = name.or_else(|| Some(default).filter(|d| describer.kinds.contains_key(*d)));
+        name.map_or(Value::null(), |name| object(vec![("name", string(name))]))
+    };
+    Ok(object(vec![(
+        "__schema",
+        object(vec![
+            ("queryType", root_type(query, "Query")),
+            ("mutationType", root_type(mutation, "Mutation")),
+            ("subscriptionType", root_type(subscription, "Subscription")),
+            ("types", Value::list(types)),
+            ("directives", Value::list(directives)),
+        ]),
+    )]))
+}
+
+/// Describes the members of types, knowing the kinds of the named types
+struct Describer {
+    kinds: HashMap<String, &'static str>,
+}
+
+impl Describer {
+    fn fields(&self, fields: &[Field<String>]) -> Value<DefaultScalarValue> {
+        Value::list(
+            fields
+                .iter()
+                .map(|f| {
+                    let mut field = vec![
+                        ("name", string(&f.name)),
+                        ("description", opt_string(&f.description)),
+                        ("args", self.input_values(&f.arguments)),
+                        ("type", self.type_ref(&f.field_type)),
+                    ];
+                    field.extend(deprecation(&f.directives));
+                    object(field)
+                })
+                .collect(),
+        )
+    }
+
+    fn input_values(&self, values: &[InputValue<String>]) -> Value<DefaultScalarValue> {
+        Value::list(
+            values
+                .iter()
+                .map(|v| {
+                    object(vec![
+                        ("name", string(&v.name)),
+                        ("description", opt_string(&v.description)),
+                        ("type", self.type_ref(&v.value_type)),
+                        (
+                            "defaultValue",
+                            v.default_value
+                                .as_ref()
+                                .map_or(Value::null(), |d| string(&d.to_string())),
+                        ),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    fn type_ref(&self, of_type: &Type<String>) -> Value<DefaultScalarValue> {
+        match of_type {
+            Type::NamedType(name) => self.named_type_ref(name),
+            Type::ListType(inner) => object(vec![
+                ("kind", string("LIST")),
+                ("ofType", self.type_ref(inner)),
+            ]),
+            Type::NonNullType(inner) => object(vec![
+                ("kind", string("NON_NULL")),
+                ("ofType", self.type_ref(inner)),
+            ]),
+        }
+    }
+
+    fn type_refs<N: AsRef<str>>(&self, names: &[N]) -> Value<DefaultScalarValue> {
+        Value::list(
+            names
+                .iter()
+                .map(|name| self.named_type_ref(name.as_ref()))
+                .collect(),
+        )
+    }
+
+    fn named_type_ref(&self, name: &str) -> Value<DefaultScalarValue> {
+        // Undefined types are reported once the result is imported.
+        let kind = self.kinds.get(name).copied().unwrap_or("OBJECT");
+        object(vec![("kind", string(kind)), ("name", string(name))])
+    }
+}
+
+fn enum_value(value: &EnumValue<String>) -> Value<DefaultScalarValue> {
+    let mut enum_value = vec![
+        ("name", string(&value.name)),
+        ("description", opt_string(&value.description)),
+    ];
+    enum_value.extend(deprecation(&value.directives));
+    object(enum_value)
+}
+
+/// The `isDeprecated` and `deprecationReason` members, from the
+/// `@deprecated` directive
+fn deprecation(directives: &[Directive<String>]) -> Vec<(&'static str, Value<DefaultScalarValue>)> {
+    let deprecated = directives.iter().find(|d| d.name == "deprecated");
+    let reason = deprecated
+        .and_then(|d| d.arguments.iter().find(|(name, _)| name == "reason"))
+        .and_then(|(_, reason)| match reason {
+            graphql_parser::query::Value::String(reason) => Some(string(reason)),
+            _ => None,
+        });
+    vec![
+        ("isDeprecated", Value::scalar(deprecated.is_some())),
+        ("deprecationReason", reason.unwrap_or_else(Value::null)),
+    ]
+}
+
+fn object(fields: Vec<(&str, Value<DefaultScalarValue>)>) -> Value<DefaultScalarValue> {
+    let mut object = Object::with_capacity(fields.len());
+    for (name, value) in fields {
+        object.add_field(name, value);
+    }
+    Value::object(object)
+}
+
+fn string(s: &str) -> Value<DefaultScalarValue> {
+    Value::scalar(s.to_owned())
+}
+
+fn opt_string(s: &Option<String>) -> Value<DefaultScalarValue> {
+    s.as_deref().map_or(Value::null(), string)
+}
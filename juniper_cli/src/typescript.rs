@@ -0,0 +1,181 @@
+//! TypeScript definitions of the types of a schema
+
+use std::fmt::Write;
+
+use juniper::{
+    meta::{Argument, Field, MetaType},
+    ScalarValue, SchemaType, Type,
+};
+
+/// The TypeScript definitions of the named types of the schema, as the
+/// content of a `.d.ts` file
+///
+/// - Objects and interfaces become interfaces, objects extending the ones of
+///   their interfaces, with an interface for the arguments of each of their
+///   fields taking some, named like `UserPostsArgs`;
+/// - Input objects become interfaces whose nullable fields are optional;
+/// - Enums and unions become unions of their values and types;
+/// - Custom scalars become `unknown`, as their serialization is up to them.
+///
+/// Nullable types are unions with `null`, and lists are `Array`s. The
+/// descriptions and deprecations of the schema become doc comments.
+pub fn typescript_definitions<S: ScalarValue>(schema: &SchemaType<S>) -> String {
+    let mut types = schema
+        .concrete_type_list()
+        .into_iter()
+        .filter(|t| !t.is_builtin())
+        .filter_map(|t| t.name().map(|name| (name, t)))
+        .collect::<Vec<_>>();
+    types.sort_by_key(|(name, _)| *name);
+
+    let mut out = String::new();
+    for (i, (name, meta)) in types.into_iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        doc_comment(&mut out, "", meta.description().map(String::as_str), None);
+        match meta {
+            MetaType::Scalar(_) => writeln!(out, "export type {} = unknown;", name).unwrap(),
+            MetaType::Enum(_) => {
+                let values = meta
+                    .enum_value_list()
+                    .iter()
+                    .map(|v| format!("{:?}", v.name))
+                    .collect::<Vec<_>>();
+                writeln!(out, "export type {} = {};", name, values.join(" | ")).unwrap()
+            }
+            MetaType::Union(_) => {
+                let types = meta.possible_type_names().unwrap_or(&[]);
+                writeln!(out, "export type {} = {};", name, types.join(" | ")).unwrap()
+            }
+            MetaType::Object(_) | MetaType::Interface(_) => output_type(&mut out, name, meta),
+            MetaType::InputObject(_) => {
+                writeln!(out, "export interface {} {{", name).unwrap();
+                for field in meta.input_field_list() {
+                    input_field(&mut out, field);
+                }
+                out.push_str("}\n");
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn output_type<S>(out: &mut String, name: &str, meta: &MetaType<S>) {
+    let fields = meta
+        .field_list()
+        .iter()
+        .filter(|f| !f.is_builtin())
+        .collect::<Vec<_>>();
+
+    let interfaces = meta.interface_names();
+    if interfaces.is_empty() {
+        writeln!(out, "export interface {} {{", name).unwrap();
+    } else {
+        let extends = interfaces.join(", ");
+        writeln!(out, "export interface {} extends {} {{", name, extends).unwrap();
+    }
+    if let MetaType::Object(_) = meta {
+        writeln!(out, "  __typename?: {:?};", name).unwrap();
+    }
+    for field in &fields {
+        output_field(out, field);
+    }
+    out.push_str("}\n");
+
+    for field in fields.iter().filter(|f| !f.argument_list().is_empty()) {
+        writeln!(
+            out,
+            "\nexport interface {}{}Args {{",
+            name,
+            pascal_case(&field.name)
+        )
+        .unwrap();
+        for argument in field.argument_list() {
+            input_field(out, argument);
+        }
+        out.push_str("}\n");
+    }
+}
+
+fn output_field<S>(out: &mut String, field: &Field<S>) {
+    doc_comment(
+        out,
+        "  ",
+        field.description.as_deref(),
+        field
+            .is_deprecated()
+            .then(|| field.deprecation_reason().unwrap_or("")),
+    );
+    writeln!(out, "  {}: {};", field.name, ts_type(&field.field_type)).unwrap();
+}
+
+fn input_field<S>(out: &mut String, field: &Argument<S>) {
+    doc_comment(out, "  ", field.description.as_deref(), None);
+    writeln!(
+        out,
+        "  {}{}: {};",
+        field.name,
+        if field.is_required() { "" } else { "?" },
+        ts_type(&field.arg_type),
+    )
+    .unwrap();
+}
+
+fn ts_type(t: &Type) -> String {
+    match t {
+        Type::Named(name) => format!("{} | null", ts_name(name)),
+        Type::NonNullNamed(name) => ts_name(name).to_owned(),
+        Type::List(inner) => format!("Array<{}> | null", ts_type(inner)),
+        Type::NonNullList(inner) => format!("Array<{}>", ts_type(inner)),
+    }
+}
+
+fn ts_name(name: &str) -> &str {
+    match name {
+        "Int" | "Float" => "number",
+        "String" | "ID" => "string",
+        "Boolean" => "boolean",
+        _ => name,
+    }
+}
+
+fn doc_comment(
+    out: &mut String,
+    indent: &str,
+    description: Option<&str>,
+    deprecated: Option<&str>,
+) {
+    let mut lines = description
+        // Keep the comment from being closed by the description.
+        .map(|d| {
+            d.replace("*/", "*\\/")
+                .lines()
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if let Some(reason) = deprecated {
+        lines.push(format!("@deprecated {}", reason).trim_end().to_owned());
+    }
+    match lines.as_slice() {
+        [] => {}
+        [line] => writeln!(out, "{}/** {} */", indent, line).unwrap(),
+        _ => {
+            writeln!(out, "{}/**", indent).unwrap();
+            for line in &lines {
+                writeln!(out, "{} * {}", indent, line).unwrap();
+            }
+            writeln!(out, "{} */", indent).unwrap();
+        }
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
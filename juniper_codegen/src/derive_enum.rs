@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned, Data, Fields};
 
 use crate::{
@@ -28,6 +28,9 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let rename = attrs.rename.unwrap_or(util::RenameRule::ScreamingSnakeCase);
+    let mut has_ignored_variants = false;
+
     let fields = variants
         .into_iter()
         .filter_map(|field| {
@@ -48,23 +51,19 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 .name
                 .clone()
                 .map(SpanContainer::into_inner)
-                .unwrap_or_else(|| util::to_upper_snake_case(&field_name.unraw().to_string()));
-
-            let resolver_code = quote!( #ident::#field_name );
-
-            let _type = match field.fields {
-                Fields::Unit => syn::parse_str(&field_name.to_string()).unwrap(),
-                _ => {
-                    error.emit_custom(
-                        field.fields.span(),
-                        "all fields of the enum must be unnamed, e.g., None",
-                    );
-                    return None;
-                }
-            };
+                .unwrap_or_else(|| {
+                    let variant_name = field_name.unraw().to_string();
+                    match rename {
+                        util::RenameRule::None => variant_name,
+                        // Rename rules expect `snake_case` input, as used for fields.
+                        rule => {
+                            rule.apply(&util::to_upper_snake_case(&variant_name).to_lowercase())
+                        }
+                    }
+                });
 
-            if let Some(skip) = field_attrs.skip {
-                error.unsupported_attribute(skip.span(), UnsupportedAttribute::Skip);
+            if field_attrs.skip.is_some() {
+                has_ignored_variants = true;
                 return None;
             }
 
@@ -76,13 +75,90 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 });
             }
 
-            if let Some(default) = field_attrs.default {
+            // Variants carrying data are matched regardless of their data on
+            // output, and need an explicit `default` to be constructed on input.
+            if let Some(ref cache) = field_attrs.cache {
+                error.unsupported_attribute_within(cache.span_ident(), UnsupportedAttribute::Cache);
+            }
+
+            if let Some(ref cache_control) = field_attrs.cache_control {
+                error.unsupported_attribute_within(
+                    cache_control.span_ident(),
+                    UnsupportedAttribute::CacheControl,
+                );
+            }
+
+            if let Some(ref serial) = field_attrs.serial {
+                error.unsupported_attribute_within(
+                    serial.span_ident(),
+                    UnsupportedAttribute::Serial,
+                );
+            }
+
+            if let Some(ref timeout) = field_attrs.timeout {
+                error.unsupported_attribute_within(
+                    timeout.span_ident(),
+                    UnsupportedAttribute::Timeout,
+                );
+            }
+
+            if let Some(ref retries) = field_attrs.retries {
                 error.unsupported_attribute_within(
-                    default.span_ident(),
-                    UnsupportedAttribute::Default,
+                    retries.span_ident(),
+                    UnsupportedAttribute::Retries,
                 );
             }
 
+            if let Some(ref require_auth) = field_attrs.require_auth {
+                error.unsupported_attribute_within(
+                    require_auth.span_ident(),
+                    UnsupportedAttribute::RequireAuth,
+                );
+            }
+
+            let (resolver_code, default) = match field.fields {
+                Fields::Unit => {
+                    if let Some(default) = field_attrs.default {
+                        error.unsupported_attribute_within(
+                            default.span_ident(),
+                            UnsupportedAttribute::Default,
+                        );
+                    }
+                    (quote!( #ident::#field_name ), None)
+                }
+                ref fields => {
+                    let pattern = match fields {
+                        Fields::Named(_) => quote!( #ident::#field_name { .. } ),
+                        _ => quote!( #ident::#field_name(..) ),
+                    };
+                    let constructor = match field_attrs.default.map(SpanContainer::into_inner) {
+                        Some(Some(expr)) => expr.into_token_stream(),
+                        Some(None) => match fields {
+                            Fields::Named(named) => {
+                                let idents = named.named.iter().map(|f| &f.ident);
+                                quote!( #ident::#field_name { #( #idents: Default::default(), )* } )
+                            }
+                            _ => {
+                                let defaults = fields.iter().map(|_| quote!(Default::default()));
+                                quote!( #ident::#field_name( #( #defaults ),* ) )
+                            }
+                        },
+                        None => {
+                            error.emit_custom(
+                                fields.span(),
+                                "variants carrying data must either be ignored with \
+                                 `#[graphql(ignore)]`, or specify how to construct them from \
+                                 input with `#[graphql(default)]` or \
+                                 `#[graphql(default = \"<expr>\")]`",
+                            );
+                            return None;
+                        }
+                    };
+                    (pattern, Some(constructor))
+                }
+            };
+            let _type = syn::parse_str(&field_name.to_string()).unwrap();
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
@@ -92,7 +168,18 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 resolver_code,
                 is_type_inferred: true,
                 is_async: false,
-                default: None,
+                default,
+                aliases: field_attrs
+                    .aliases
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
+                cache_ttl_millis: None,
+                cache_hint: None,
+                serial: false,
+                timeout_millis: None,
+                retries: 0,
+                required_scopes: None,
                 span,
             })
         })
@@ -108,6 +195,16 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
     {
         error.duplicate(duplicates.iter())
     }
+    for field in &fields {
+        for alias in &field.aliases {
+            if fields.iter().any(|f| &f.name == alias) {
+                error.emit_custom(
+                    field.span,
+                    format!("alias `{}` clashes with the name of a variant", alias),
+                );
+            }
+        }
+    }
 
     if !attrs.interfaces.is_empty() {
         attrs.interfaces.iter().for_each(|elm| {
@@ -119,6 +216,17 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         error.unsupported_attribute(scalar.span_ident(), UnsupportedAttribute::Scalar);
     }
 
+    if let Some(cache_control) = attrs.cache_control {
+        error.unsupported_attribute(
+            cache_control.span_ident(),
+            UnsupportedAttribute::CacheControl,
+        );
+    }
+
+    if let Some(require_auth) = attrs.require_auth {
+        error.unsupported_attribute(require_auth.span_ident(), UnsupportedAttribute::RequireAuth);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = attrs.name {
             name.span_ident()
@@ -142,7 +250,10 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        local: false,
+        cache_hint: None,
+        required_scopes: None,
     };
 
-    Ok(definition.into_enum_tokens())
+    Ok(definition.into_enum_tokens(has_ignored_variants))
 }
@@ -4,7 +4,7 @@ use crate::{
     util::{self, span_container::SpanContainer},
 };
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::quote;
 use syn::{self, ext::IdentExt, spanned::Spanned, Data, Fields};
 
 pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
@@ -30,7 +30,10 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         .name
         .clone()
         .map(SpanContainer::into_inner)
-        .unwrap_or_else(|| ident.to_string());
+        .unwrap_or_else(|| ident.unraw().to_string());
+
+    // Fields hidden from GraphQL, along with the expressions populating them.
+    let mut skipped_fields = vec![];
 
     let fields = fields
         .into_iter()
@@ -50,11 +53,19 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
             let field_ident = field.ident.as_ref().unwrap();
             let name = match field_attrs.name {
                 Some(ref name) => name.to_string(),
-                None => attrs.rename.apply(&field_ident.unraw().to_string()),
+                None => attrs
+                    .rename
+                    .unwrap_or_default()
+                    .apply(&field_ident.unraw().to_string()),
             };
 
-            if let Some(span) = field_attrs.skip {
-                error.unsupported_attribute_within(span.span(), UnsupportedAttribute::Skip)
+            if field_attrs.skip.is_some() {
+                let value = match field_attrs.default.map(SpanContainer::into_inner) {
+                    Some(Some(expr)) => util::default_value_tokens(&expr),
+                    _ => quote! { Default::default() },
+                };
+                skipped_fields.push(quote! { #field_ident: #value, });
+                return None;
             }
 
             if let Some(span) = field_attrs.deprecation {
@@ -72,12 +83,55 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 });
             }
 
+            if let Some(alias) = field_attrs.aliases.first() {
+                error.unsupported_attribute_within(alias.span_ident(), UnsupportedAttribute::Alias);
+            }
+
+            if let Some(cache) = field_attrs.cache {
+                error.unsupported_attribute_within(cache.span_ident(), UnsupportedAttribute::Cache);
+            }
+
+            if let Some(cache_control) = field_attrs.cache_control {
+                error.unsupported_attribute_within(
+                    cache_control.span_ident(),
+                    UnsupportedAttribute::CacheControl,
+                );
+            }
+
+            if let Some(serial) = field_attrs.serial {
+                error.unsupported_attribute_within(
+                    serial.span_ident(),
+                    UnsupportedAttribute::Serial,
+                );
+            }
+
+            if let Some(ref timeout) = field_attrs.timeout {
+                error.unsupported_attribute_within(
+                    timeout.span_ident(),
+                    UnsupportedAttribute::Timeout,
+                );
+            }
+
+            if let Some(ref retries) = field_attrs.retries {
+                error.unsupported_attribute_within(
+                    retries.span_ident(),
+                    UnsupportedAttribute::Retries,
+                );
+            }
+
+            if let Some(ref require_auth) = field_attrs.require_auth {
+                error.unsupported_attribute_within(
+                    require_auth.span_ident(),
+                    UnsupportedAttribute::RequireAuth,
+                );
+            }
+
             let resolver_code = quote!(#field_ident);
 
             let default = field_attrs
                 .default
                 .map(|default| match default.into_inner() {
-                    Some(expr) => expr.into_token_stream(),
+                    Some(expr) => util::default_value_tokens(&expr),
                     None => quote! { Default::default() },
                 });
 
@@ -91,6 +145,13 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                aliases: Vec::new(),
+                cache_ttl_millis: None,
+                cache_hint: None,
+                serial: false,
+                timeout_millis: None,
+                retries: 0,
+                required_scopes: None,
                 span,
             })
         })
@@ -120,6 +181,17 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         error.duplicate(duplicates.iter());
     }
 
+    if let Some(cache_control) = attrs.cache_control {
+        error.unsupported_attribute(
+            cache_control.span_ident(),
+            UnsupportedAttribute::CacheControl,
+        );
+    }
+
+    if let Some(require_auth) = attrs.require_auth {
+        error.unsupported_attribute(require_auth.span_ident(), UnsupportedAttribute::RequireAuth);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = attrs.name {
             name.span_ident()
@@ -142,7 +214,10 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        local: false,
+        cache_hint: None,
+        required_scopes: None,
     };
 
-    Ok(definition.into_input_object_tokens())
+    Ok(definition.into_input_object_tokens(&skipped_fields))
 }
@@ -4,11 +4,11 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{self, ext::IdentExt, spanned::Spanned, Data, Fields};
+use syn::{self, ext::IdentExt, parse_quote, spanned::Spanned, Data, Fields};
 
 pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
     let ast_span = ast.span();
-    let struct_fields = match ast.data {
+    let struct_fields = match ast.data.clone() {
         Data::Struct(data) => match data.fields {
             Fields::Named(fields) => fields.named,
             _ => return Err(error.custom_error(ast_span, "only named fields are allowed")),
@@ -26,6 +26,20 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let object_name = &name;
+    let vis = &ast.vis;
+    let nested_attrs = {
+        let context = attrs.context.as_ref().map(|c| quote!( , context = #c ));
+        let scalar = attrs.scalar.as_ref().map(|s| quote!( , scalar = #s ));
+        quote!( #context #scalar )
+    };
+
+    // Fields of the companion input object, if requested.
+    let mut input_fields = vec![];
+    // Objects exposing map fields as lists of key/value entries, and tuple
+    // fields as objects.
+    let mut nested_objects = vec![];
+
     let fields = struct_fields
         .into_iter()
         .filter_map(|field| {
@@ -41,16 +55,41 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 }
             };
 
-            if field_attrs.skip.is_some() {
-                return None;
-            }
-
             let field_name = &field.ident.unwrap();
             let name = field_attrs
                 .name
                 .clone()
                 .map(SpanContainer::into_inner)
-                .unwrap_or_else(|| attrs.rename.apply(&field_name.unraw().to_string()));
+                .unwrap_or_else(|| {
+                    attrs
+                        .rename
+                        .unwrap_or_default()
+                        .apply(&field_name.unraw().to_string())
+                });
+
+            if attrs.input_object.is_some() && field_attrs.skip_input.is_none() {
+                let ty = &field.ty;
+                let description = field_attrs
+                    .description
+                    .as_ref()
+                    .map(|d| d.as_ref().as_str())
+                    .map(|d| quote!( , description = #d ));
+                input_fields.push(quote!(
+                    #[graphql(name = #name #description)]
+                    #field_name: #ty,
+                ));
+            } else if let Some(skip_input) = field_attrs.skip_input.as_ref() {
+                if attrs.input_object.is_none() {
+                    error.emit_custom(
+                        skip_input.span(),
+                        "`skip_input` requires an `input_object` on the object",
+                    );
+                }
+            }
+
+            if field_attrs.skip.is_some() {
+                return None;
+            }
 
             if name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = field_attrs.name {
@@ -67,13 +106,94 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 );
             }
 
-            let resolver_code = quote!(
-                &self . #field_name
-            );
+            if let Some(alias) = field_attrs.aliases.first() {
+                error.unsupported_attribute_within(alias.span_ident(), UnsupportedAttribute::Alias);
+            }
+
+            if let Some(ref timeout) = field_attrs.timeout {
+                error.unsupported_attribute_within(
+                    timeout.span_ident(),
+                    UnsupportedAttribute::Timeout,
+                );
+            }
+
+            if let Some(ref retries) = field_attrs.retries {
+                error.unsupported_attribute_within(
+                    retries.span_ident(),
+                    UnsupportedAttribute::Retries,
+                );
+            }
+
+            let map_types = util::map_key_value_types(&field.ty);
+            if (map_types.is_some() || field_attrs.tuple.is_some())
+                && !ast.generics.params.is_empty()
+            {
+                error.emit_custom(
+                    field.ty.span(),
+                    "map and tuple fields are not supported on objects with generics or lifetimes",
+                );
+                return None;
+            }
+
+            let (_type, resolver_code) = match (field_attrs.tuple.as_ref(), map_types) {
+                (Some(tuple), _) => {
+                    let tuple = match util::TupleObject::new(
+                        tuple,
+                        &field.ty,
+                        true,
+                        nested_attrs.clone(),
+                    ) {
+                        Ok(tuple) => tuple,
+                        Err(err) => {
+                            proc_macro_error::emit_error!(err);
+                            return None;
+                        }
+                    };
+                    let tuple_ident = &tuple.ident;
+                    let resolver_code = tuple.constructor(quote!( &self . #field_name ));
+                    nested_objects.push(tuple.definition);
+
+                    (parse_quote!( #tuple_ident<'_> ), resolver_code)
+                }
+                (None, Some((key_ty, value_ty, unordered))) => {
+                    let entry_name = format!("{}{}Entry", object_name, to_pascal_case(&name));
+                    let entry_ident = syn::Ident::new(&entry_name, field_name.span());
+                    nested_objects.push(quote!(
+                        #[derive(::juniper::GraphQLObject)]
+                        #[graphql(name = #entry_name #nested_attrs)]
+                        #[doc(hidden)]
+                        #vis struct #entry_ident<'__entry> {
+                            key: &'__entry #key_ty,
+                            value: &'__entry #value_ty,
+                        }
+                    ));
+
+                    let entries = quote!(
+                        self . #field_name
+                            .iter()
+                            .map(|(key, value)| #entry_ident { key, value })
+                            .collect::<Vec<_>>()
+                    );
+                    // Entries of a `HashMap` come in an arbitrary order, while
+                    // those of a `BTreeMap` are already sorted by key.
+                    let resolver_code = if unordered {
+                        quote!({
+                            let mut entries = #entries;
+                            entries.sort_by(|a, b| ::std::cmp::Ord::cmp(a.key, b.key));
+                            entries
+                        })
+                    } else {
+                        entries
+                    };
+
+                    (parse_quote!( Vec<#entry_ident<'_>> ), resolver_code)
+                }
+                (None, None) => (field.ty, quote!( &self . #field_name )),
+            };
 
             Some(util::GraphQLTypeDefinitionField {
                 name,
-                _type: field.ty,
+                _type,
                 args: Vec::new(),
                 description: field_attrs.description.map(SpanContainer::into_inner),
                 deprecation: field_attrs.deprecation.map(SpanContainer::into_inner),
@@ -81,6 +201,13 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 default: None,
                 is_type_inferred: true,
                 is_async: false,
+                aliases: Vec::new(),
+                cache_ttl_millis: field_attrs.cache.map(|cache| cache.ttl_millis),
+                cache_hint: field_attrs.cache_control.map(|attr| attr.hint_tokens()),
+                serial: field_attrs.serial.is_some(),
+                timeout_millis: None,
+                retries: 0,
+                required_scopes: field_attrs.require_auth.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -128,7 +255,46 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        local: attrs.local.is_some(),
+        cache_hint: attrs.cache_control.map(|attr| attr.hint_tokens()),
+        required_scopes: attrs.require_auth.map(SpanContainer::into_inner),
     };
 
-    Ok(definition.into_tokens())
+    let input_object = attrs.input_object.map(|input_name| {
+        let input_ident = syn::Ident::new(&input_name, input_name.span_ident());
+        let scalar = definition
+            .scalar
+            .as_ref()
+            .map(|scalar| quote!( #[graphql(scalar = #scalar)] ));
+        let description = definition
+            .description
+            .as_ref()
+            .map(|description| quote!( #[graphql(description = #description)] ));
+
+        quote!(
+            #[derive(::juniper::GraphQLInputObject)]
+            #scalar
+            #description
+            #vis struct #input_ident {
+                #( #input_fields )*
+            }
+        )
+    });
+
+    let output_object = definition.into_tokens();
+    Ok(quote!(
+        #output_object
+        #input_object
+        #( #nested_objects )*
+    ))
+}
+
+/// Converts a GraphQL field name into a type name part, e.g. `userTags` into
+/// `UserTags`.
+fn to_pascal_case(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
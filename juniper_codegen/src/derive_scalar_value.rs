@@ -4,7 +4,7 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{self, spanned::Spanned, Data, Fields, Ident, Variant};
+use syn::{self, ext::IdentExt, spanned::Spanned, Data, Fields, Ident, Variant};
 
 #[derive(Debug, Default)]
 struct TransparentAttributes {
@@ -93,7 +93,7 @@ fn impl_scalar_struct(
     let ident = &ast.ident;
     let attrs = TransparentAttributes::from_attrs(&ast.attrs)?;
     let inner_ty = &field.ty;
-    let name = attrs.name.unwrap_or_else(|| ident.to_string());
+    let name = attrs.name.unwrap_or_else(|| ident.unraw().to_string());
 
     let description = match attrs.description {
         Some(val) => quote!( .description( #val ) ),
@@ -119,6 +119,22 @@ fn impl_scalar_struct(
                 Box::pin(future::ready(v))
             }
         }
+
+        impl<__S> ::juniper::GraphQLValueAsyncLocal<__S> for #ident
+        where
+            __S: ::juniper::ScalarValue,
+        {
+            fn resolve_local<'a>(
+                &'a self,
+                info: &'a Self::TypeInfo,
+                selection_set: Option<&'a [::juniper::Selection<__S>]>,
+                executor: &'a ::juniper::Executor<Self::Context, __S>,
+            ) -> ::juniper::LocalBoxFuture<'a, ::juniper::ExecutionResult<__S>> {
+                use ::juniper::futures::future;
+                let v = ::juniper::GraphQLValue::resolve(self, info, selection_set, executor);
+                Box::pin(future::ready(v))
+            }
+        }
     );
 
     let content = quote!(
@@ -286,7 +302,34 @@ fn derive_from_variant(
 
     let variant = &variant.ident;
 
+    // Primitive types are already converted into any scalar value by `juniper`.
+    let is_primitive = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .map(|seg| {
+                ["i32", "f64", "String", "bool"]
+                    .iter()
+                    .any(|p| seg.ident == p)
+            })
+            .unwrap_or(false),
+        _ => false,
+    };
+    let into_scalar_value = if is_primitive {
+        None
+    } else {
+        Some(quote! {
+            impl ::juniper::IntoScalarValue<#ident> for #ty {
+                fn into_scalar_value(self) -> #ident {
+                    #ident::#variant(self)
+                }
+            }
+        })
+    };
+
     Ok(quote! {
+        #into_scalar_value
+
         impl ::std::convert::From<#ty> for #ident {
             fn from(t: #ty) -> Self {
                 #ident::#variant(t)
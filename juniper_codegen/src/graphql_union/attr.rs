@@ -98,6 +98,8 @@ pub fn expand(attr_args: TokenStream, body: TokenStream) -> syn::Result<TokenStr
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics.clone(),
         variants,
+        type_resolver: meta.type_resolver.map(SpanContainer::into_inner),
+        is_local: meta.local.is_some(),
         span: trait_span,
     };
 
@@ -85,6 +85,8 @@ fn expand_enum(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
         variants,
+        type_resolver: meta.type_resolver.map(SpanContainer::into_inner),
+        is_local: meta.local.is_some(),
         span: enum_span,
     })
 }
@@ -214,6 +216,8 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
         variants,
+        type_resolver: meta.type_resolver.map(SpanContainer::into_inner),
+        is_local: meta.local.is_some(),
         span: struct_span,
     })
 }
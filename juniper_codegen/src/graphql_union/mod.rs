@@ -121,6 +121,22 @@ struct UnionMeta {
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     pub external_resolvers: UnionMetaResolvers,
 
+    /// Explicitly specified function returning the name of the [GraphQL union][1] variant to
+    /// resolve into.
+    ///
+    /// If absent, then the variant is found by calling each variant resolver until one returns
+    /// [`Some`], which requires them to return references.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub type_resolver: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Indicator whether `juniper::GraphQLValueAsyncLocal` should be implemented for this
+    /// [GraphQL union][1] instead of `juniper::GraphQLValueAsync`, so it can be resolved by
+    /// single-threaded executors from non-`Send` variants.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub local: Option<SpanContainer<()>>,
+
     /// Indicator whether the generated code is intended to be used only inside the `juniper`
     /// library.
     pub is_internal: bool,
@@ -184,6 +200,18 @@ impl Parse for UnionMeta {
                         .insert(ty, rslvr_spanned)
                         .none_or_else(|_| dup_attr_err(rslvr_span))?
                 }
+                "resolve_type" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let rslvr = input.parse::<syn::ExprPath>()?;
+                    output
+                        .type_resolver
+                        .replace(SpanContainer::new(ident.span(), Some(rslvr.span()), rslvr))
+                        .none_or_else(|_| dup_attr_err(ident.span()))?
+                }
+                "local" => output
+                    .local
+                    .replace(SpanContainer::new(ident.span(), None, ()))
+                    .none_or_else(|_| dup_attr_err(ident.span()))?,
                 "internal" => {
                     output.is_internal = true;
                 }
@@ -209,6 +237,8 @@ impl UnionMeta {
             context: try_merge_opt!(context: self, another),
             scalar: try_merge_opt!(scalar: self, another),
             external_resolvers: try_merge_hashmap!(external_resolvers: self, another => span_joined),
+            type_resolver: try_merge_opt!(type_resolver: self, another),
+            local: try_merge_opt!(local: self, another),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -391,6 +421,20 @@ struct UnionDefinition {
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     pub variants: Vec<UnionVariantDefinition>,
 
+    /// Function returning the name of the variant this [GraphQL union][1] resolves into, called
+    /// with `&self` and the `juniper::Context`.
+    ///
+    /// If [`None`] then variants are checked in order with their resolver checks.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub type_resolver: Option<syn::ExprPath>,
+
+    /// Indicator whether `juniper::GraphQLValueAsyncLocal` is implemented for this
+    /// [GraphQL union][1] instead of `juniper::GraphQLValueAsync`.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub is_local: bool,
+
     /// [`Span`] that points to the Rust source code which defines this [GraphQL union][1].
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
@@ -427,16 +471,45 @@ impl ToTokens for UnionDefinition {
             None
         };
 
-        let match_names = self.variants.iter().map(|var| {
-            let var_ty = &var.ty;
-            let var_check = &var.resolver_check;
-            quote! {
-                if #var_check {
-                    return <#var_ty as ::juniper::GraphQLType<#scalar>>::name(&())
-                        .unwrap().to_string();
-                }
-            }
-        });
+        // Fragments on the other variants are resolved into `null`, as the variant resolvers
+        // aren't expected to fail when called for some other variant.
+        let (concrete_type_name, match_type, match_type_async) =
+            if let Some(rslvr) = &self.type_resolver {
+                let name = quote! { #rslvr(self, ::juniper::FromContext::from(context)) };
+                (
+                    quote! { ::std::string::ToString::to_string(&#name) },
+                    Some(quote! {
+                        if type_name != #name {
+                            return Ok(::juniper::Value::null());
+                        }
+                    }),
+                    Some(quote! {
+                        if type_name != #name {
+                            return Box::pin(async { Ok(::juniper::Value::null()) });
+                        }
+                    }),
+                )
+            } else {
+                let match_names = self.variants.iter().map(|var| {
+                    let var_ty = &var.ty;
+                    let var_check = &var.resolver_check;
+                    quote! {
+                        if #var_check {
+                            return <#var_ty as ::juniper::GraphQLType<#scalar>>::name(&())
+                                .unwrap().to_string();
+                        }
+                    }
+                });
+                let concrete_type_name = quote! {
+                    #( #match_names )*
+                    panic!(
+                        "GraphQL union {} cannot be resolved into any of its variants in its \
+                         current state",
+                        #name,
+                    );
+                };
+                (concrete_type_name, None, None)
+            };
 
         let match_resolves: Vec<_> = self.variants.iter().map(|var| &var.resolver_code).collect();
         let resolve_into_type = self.variants.iter().zip(match_resolves.iter()).map(|(var, expr)| {
@@ -456,6 +529,11 @@ impl ToTokens for UnionDefinition {
                 }
             }
         });
+        let resolve_with_ctx_async = if self.is_local {
+            quote! { resolve_with_ctx_local }
+        } else {
+            quote! { resolve_with_ctx_async }
+        };
         let resolve_into_type_async =
             self.variants
                 .iter()
@@ -476,7 +554,7 @@ impl ToTokens for UnionDefinition {
                                 match res? {
                                     Some((ctx, r)) => {
                                         let subexec = executor.replaced_context(ctx);
-                                        subexec.resolve_with_ctx_async(&(), &r).await
+                                        subexec.#resolve_with_ctx_async(&(), &r).await
                                     },
                                     None => Ok(::juniper::Value::null()),
                                 }
@@ -513,7 +591,11 @@ impl ToTokens for UnionDefinition {
 
         let mut ty_full = quote! { #ty#ty_generics };
         if self.is_trait_object {
-            ty_full = quote! { dyn #ty_full + '__obj + Send + Sync };
+            ty_full = if self.is_local {
+                quote! { dyn #ty_full + '__obj }
+            } else {
+                quote! { dyn #ty_full + '__obj + Send + Sync }
+            };
         }
 
         let type_impl = quote! {
@@ -558,12 +640,7 @@ impl ToTokens for UnionDefinition {
                     context: &Self::Context,
                     _: &Self::TypeInfo,
                 ) -> String {
-                    #( #match_names )*
-                    panic!(
-                        "GraphQL union {} cannot be resolved into any of its variants in its \
-                         current state",
-                        #name,
-                    );
+                    #concrete_type_name
                 }
 
                 fn resolve_into_type(
@@ -574,6 +651,7 @@ impl ToTokens for UnionDefinition {
                     executor: &::juniper::Executor<Self::Context, #scalar>,
                 ) -> ::juniper::ExecutionResult<#scalar> {
                     let context = executor.context();
+                    #match_type
                     #( #resolve_into_type )*
                     panic!(
                         "Concrete type {} is not handled by instance resolvers on GraphQL union {}",
@@ -583,24 +661,50 @@ impl ToTokens for UnionDefinition {
             }
         };
 
-        let value_async_impl = quote! {
-            #[automatically_derived]
-            impl#ext_impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ty_full
-                #where_async
-            {
-                fn resolve_into_type_async<'b>(
-                    &'b self,
-                    _: &'b Self::TypeInfo,
-                    type_name: &str,
-                    _: Option<&'b [::juniper::Selection<'b, #scalar>]>,
-                    executor: &'b ::juniper::Executor<'b, 'b, Self::Context, #scalar>
-                ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
-                    let context = executor.context();
-                    #( #resolve_into_type_async )*
-                    panic!(
-                        "Concrete type {} is not handled by instance resolvers on GraphQL union {}",
-                        type_name, #name,
-                    );
+        let value_async_impl = if self.is_local {
+            quote! {
+                #[automatically_derived]
+                impl#ext_impl_generics ::juniper::GraphQLValueAsyncLocal<#scalar> for #ty_full
+                    #where_clause
+                {
+                    fn resolve_into_type_local<'b>(
+                        &'b self,
+                        _: &'b Self::TypeInfo,
+                        type_name: &str,
+                        _: Option<&'b [::juniper::Selection<'b, #scalar>]>,
+                        executor: &'b ::juniper::Executor<'b, 'b, Self::Context, #scalar>
+                    ) -> ::juniper::LocalBoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                        let context = executor.context();
+                        #match_type_async
+                        #( #resolve_into_type_async )*
+                        panic!(
+                            "Concrete type {} is not handled by instance resolvers on GraphQL union {}",
+                            type_name, #name,
+                        );
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #[automatically_derived]
+                impl#ext_impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ty_full
+                    #where_async
+                {
+                    fn resolve_into_type_async<'b>(
+                        &'b self,
+                        _: &'b Self::TypeInfo,
+                        type_name: &str,
+                        _: Option<&'b [::juniper::Selection<'b, #scalar>]>,
+                        executor: &'b ::juniper::Executor<'b, 'b, Self::Context, #scalar>
+                    ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                        let context = executor.context();
+                        #match_type_async
+                        #( #resolve_into_type_async )*
+                        panic!(
+                            "Concrete type {} is not handled by instance resolvers on GraphQL union {}",
+                            type_name, #name,
+                        );
+                    }
                 }
             }
         };
@@ -6,15 +6,19 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{ext::IdentExt, spanned::Spanned};
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned};
 
 /// Generate code for the juniper::graphql_object macro.
 pub fn build_object(args: TokenStream, body: TokenStream, error: GraphQLScope) -> TokenStream {
-    let definition = match create(args, body, error) {
-        Ok(definition) => definition,
+    let (definition, tuple_objects) = match create(args, body, &error, false) {
+        Ok(created) => created,
         Err(err) => return err.to_compile_error(),
     };
-    definition.into_tokens()
+    let object = definition.into_tokens();
+    quote!(
+        #object
+        #( #tuple_objects )*
+    )
 }
 
 /// Generate code for the juniper::graphql_subscription macro.
@@ -23,18 +27,39 @@ pub fn build_subscription(
     body: TokenStream,
     error: GraphQLScope,
 ) -> TokenStream {
-    let definition = match create(args, body, error) {
-        Ok(definition) => definition,
+    let (definition, tuple_objects) = match create(args, body, &error, true) {
+        Ok(created) => created,
         Err(err) => return err.to_compile_error(),
     };
-    definition.into_subscription_tokens()
+    for field in &definition.fields {
+        if field.cache_ttl_millis.is_some() || field.cache_hint.is_some() {
+            error.unsupported_attribute_within(field.span, UnsupportedAttribute::Cache);
+        }
+        if field.serial {
+            error.unsupported_attribute_within(field.span, UnsupportedAttribute::Serial);
+        }
+        if field.timeout_millis.is_some() {
+            error.unsupported_attribute_within(field.span, UnsupportedAttribute::Timeout);
+        }
+        if field.retries > 0 {
+            error.unsupported_attribute_within(field.span, UnsupportedAttribute::Retries);
+        }
+    }
+    proc_macro_error::abort_if_dirty();
+
+    let subscription = definition.into_subscription_tokens();
+    quote!(
+        #subscription
+        #( #tuple_objects )*
+    )
 }
 
 fn create(
     args: TokenStream,
     body: TokenStream,
-    error: GraphQLScope,
-) -> syn::Result<util::GraphQLTypeDefiniton> {
+    error: &GraphQLScope,
+    is_subscription: bool,
+) -> syn::Result<(util::GraphQLTypeDefiniton, Vec<TokenStream>)> {
     let body_span = body.span();
     let _impl = util::parse_impl::ImplBlock::parse(args, body)?;
     let name = _impl
@@ -46,6 +71,17 @@ fn create(
 
     let top_attrs = &_impl.attrs;
 
+    // Objects exposing the elements of tuple-typed fields.
+    let mut tuple_objects = vec![];
+    let tuple_attrs = {
+        let context = top_attrs.context.as_ref().map(|c| quote!( , context = #c ));
+        let scalar = match top_attrs.scalar {
+            Some(ref scalar) => quote!( , scalar = #scalar ),
+            None => quote!( , scalar = ::juniper::DefaultScalarValue ),
+        };
+        quote!( #context #scalar )
+    };
+
     let fields = _impl
         .methods
         .iter()
@@ -80,7 +116,7 @@ fn create(
                     let final_name = attrs
                         .argument(&arg_name)
                         .and_then(|attrs| attrs.rename.clone().map(|ident| ident.value()))
-                        .unwrap_or_else(|| top_attrs.rename.apply(&arg_name));
+                        .unwrap_or_else(|| top_attrs.rename.unwrap_or_default().apply(&arg_name));
 
                     let expect_text = format!(
                         "Internal error: missing argument {} - validation must have failed",
@@ -129,6 +165,83 @@ fn create(
             };
 
             let body = &method.block;
+            let mut is_type_inferred = false;
+            let (_type, body) = match attrs.tuple {
+                Some(ref tuple) => {
+                    let tuple =
+                        match util::TupleObject::new(tuple, &_type, false, tuple_attrs.clone()) {
+                            Ok(tuple) => tuple,
+                            Err(err) => {
+                                proc_macro_error::emit_error!(err);
+                                return None;
+                            }
+                        };
+                    // Keep `return`s in the body working.
+                    let value = if is_async {
+                        quote!( async move #body.await )
+                    } else {
+                        quote!( (|| #body)() )
+                    };
+                    let constructor = tuple.constructor(value);
+                    let tuple_ident = &tuple.ident;
+                    tuple_objects.push(tuple.definition);
+
+                    (parse_quote!( #tuple_ident ), quote!( { #constructor } ))
+                }
+                None => match util::sequence_item_type(&_type) {
+                    // Subscriptions resolve into the stream itself, so only its item type has to
+                    // be named, for registering the field.
+                    Some((util::SequenceKind::Stream, item))
+                        if is_subscription && matches!(_type, syn::Type::ImplTrait(_)) =>
+                    {
+                        is_type_inferred = true;
+                        (
+                            parse_quote!(
+                                ::std::pin::Pin<Box<
+                                    dyn ::juniper::futures::Stream<Item = #item> + Send
+                                >>
+                            ),
+                            quote!( #body ),
+                        )
+                    }
+                    // Sequences returned by other fields are collected into lists.
+                    Some((kind, item)) if !is_subscription => {
+                        // Keep `return`s in the body working, coercing them to boxed trait
+                        // objects where needed.
+                        let value = if is_async {
+                            quote!( async move #body.await )
+                        } else if let syn::Type::ImplTrait(_) = _type {
+                            quote!( (|| #body)() )
+                        } else {
+                            quote!( (|| -> #_type #body)() )
+                        };
+                        let collected = match kind {
+                            util::SequenceKind::Iterator => quote!(
+                                ::std::iter::Iterator::collect::<::std::vec::Vec<_>>(
+                                    ::std::iter::IntoIterator::into_iter(#value),
+                                )
+                            ),
+                            util::SequenceKind::Stream if is_async => quote!(
+                                ::juniper::futures::StreamExt::collect::<::std::vec::Vec<_>>(
+                                    #value,
+                                ).await
+                            ),
+                            util::SequenceKind::Stream => {
+                                error.emit_custom(
+                                    method.sig.span(),
+                                    "fields returning a stream must be `async`",
+                                );
+                                return None;
+                            }
+                        };
+                        (
+                            parse_quote!( ::std::vec::Vec<#item> ),
+                            quote!( { #collected } ),
+                        )
+                    }
+                    _ => (_type, quote!( #body )),
+                },
+            };
             let resolver_code = quote!(
                 #( #resolve_parts )*
                 #body
@@ -139,7 +252,12 @@ fn create(
                 .name
                 .clone()
                 .map(SpanContainer::into_inner)
-                .unwrap_or_else(|| top_attrs.rename.apply(&ident.unraw().to_string()));
+                .unwrap_or_else(|| {
+                    top_attrs
+                        .rename
+                        .unwrap_or_default()
+                        .apply(&ident.unraw().to_string())
+                });
 
             if name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = attrs.name {
@@ -156,6 +274,19 @@ fn create(
                 );
             }
 
+            if let Some(alias) = attrs.aliases.first() {
+                error.unsupported_attribute_within(alias.span_ident(), UnsupportedAttribute::Alias);
+            }
+
+            if !is_async {
+                if let Some(timeout) = attrs.timeout.as_ref() {
+                    error.emit_custom(timeout.span_ident(), "field with a `timeout` must be async");
+                }
+                if let Some(retries) = attrs.retries.as_ref() {
+                    error.emit_custom(retries.span_ident(), "field with `retries` must be async");
+                }
+            }
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
@@ -163,9 +294,16 @@ fn create(
                 description: attrs.description.map(SpanContainer::into_inner),
                 deprecation: attrs.deprecation.map(SpanContainer::into_inner),
                 resolver_code,
-                is_type_inferred: false,
+                is_type_inferred,
                 is_async,
                 default: None,
+                aliases: Vec::new(),
+                cache_ttl_millis: attrs.cache.map(|cache| cache.ttl_millis),
+                cache_hint: attrs.cache_control.map(|attr| attr.hint_tokens()),
+                serial: attrs.serial.is_some(),
+                timeout_millis: attrs.timeout.map(SpanContainer::into_inner),
+                retries: attrs.retries.map_or(0, SpanContainer::into_inner),
+                required_scopes: attrs.require_auth.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -218,7 +356,10 @@ fn create(
         include_type_generics: false,
         generic_scalar: false,
         no_async: _impl.attrs.no_async.is_some(),
+        local: _impl.attrs.local.is_some(),
+        cache_hint: _impl.attrs.cache_control.map(|attr| attr.hint_tokens()),
+        required_scopes: _impl.attrs.require_auth.map(SpanContainer::into_inner),
     };
 
-    Ok(definition)
+    Ok((definition, tuple_objects))
 }
@@ -4,9 +4,9 @@ use crate::{
     result::GraphQLScope,
     util::{self, span_container::SpanContainer},
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Group, TokenStream, TokenTree};
 use quote::quote;
-use syn::spanned::Spanned;
+use syn::{ext::IdentExt, spanned::Spanned};
 
 #[derive(Debug)]
 struct ScalarCodegenInput {
@@ -17,6 +17,8 @@ struct ScalarCodegenInput {
     from_input_value_arg: Option<syn::Ident>,
     from_input_value_body: Option<syn::Block>,
     from_input_value_result: Option<syn::Type>,
+    try_from_input_value_arg: Option<syn::Ident>,
+    try_from_input_value_body: Option<syn::Block>,
     from_str_arg: Option<syn::Ident>,
     from_str_body: Option<syn::Block>,
     from_str_result: Option<syn::Type>,
@@ -105,6 +107,8 @@ impl syn::parse::Parse for ScalarCodegenInput {
         let mut from_input_value_arg: Option<syn::Ident> = None;
         let mut from_input_value_body: Option<syn::Block> = None;
         let mut from_input_value_result: Option<syn::Type> = None;
+        let mut try_from_input_value_arg: Option<syn::Ident> = None;
+        let mut try_from_input_value_body: Option<syn::Block> = None;
         let mut from_str_arg: Option<syn::Ident> = None;
         let mut from_str_body: Option<syn::Block> = None;
         let mut from_str_result: Option<syn::Type> = None;
@@ -135,6 +139,10 @@ impl syn::parse::Parse for ScalarCodegenInput {
                         from_input_value_result = get_method_return_type(method.sig.output);
                         from_input_value_body = Some(method.block);
                     }
+                    "try_from_input_value" => {
+                        try_from_input_value_arg = get_first_method_arg(method.sig.inputs);
+                        try_from_input_value_body = Some(method.block);
+                    }
                     "from_str" => {
                         from_str_arg = get_first_method_arg(method.sig.inputs);
                         from_str_result = get_method_return_type(method.sig.output);
@@ -165,6 +173,8 @@ impl syn::parse::Parse for ScalarCodegenInput {
             from_input_value_arg,
             from_input_value_body,
             from_input_value_result,
+            try_from_input_value_arg,
+            try_from_input_value_body,
             from_str_arg,
             from_str_body,
             from_str_result,
@@ -180,7 +190,11 @@ pub fn build_scalar(
 ) -> syn::Result<TokenStream> {
     let body_span = body.span();
 
-    let attrs = syn::parse2::<util::FieldAttributes>(attributes)?;
+    let mut attrs = syn::parse2::<util::FieldAttributes>(attributes)?;
+    if let Some(instances) = attrs.instances.take() {
+        return build_scalar_instances(attrs, instances, body, error);
+    }
+
     let input = syn::parse2::<ScalarCodegenInput>(body)?;
 
     let impl_for_type = input.impl_for_type.ok_or_else(|| {
@@ -195,24 +209,6 @@ pub fn build_scalar(
     let resolve_body = input
         .resolve_body
         .ok_or_else(|| error.custom_error(body_span, "unable to find body of `resolve` method"))?;
-    let from_input_value_arg = input.from_input_value_arg.ok_or_else(|| {
-        error.custom_error(
-            body_span,
-            "unable to find argument for `from_input_value` method",
-        )
-    })?;
-    let from_input_value_body = input.from_input_value_body.ok_or_else(|| {
-        error.custom_error(
-            body_span,
-            "unable to find body of `from_input_value` method",
-        )
-    })?;
-    let from_input_value_result = input.from_input_value_result.ok_or_else(|| {
-        error.custom_error(
-            body_span,
-            "unable to find return type of `from_input_value` method",
-        )
-    })?;
     let from_str_arg = input.from_str_arg.ok_or_else(|| {
         error.custom_error(body_span, "unable to find argument for `from_str` method")
     })?;
@@ -226,7 +222,7 @@ pub fn build_scalar(
     let name = attrs
         .name
         .map(SpanContainer::into_inner)
-        .unwrap_or_else(|| impl_for_type.ident.to_string());
+        .unwrap_or_else(|| impl_for_type.ident.unraw().to_string());
     let description = match attrs.description {
         Some(val) => quote!(.description(#val)),
         None => quote!(),
@@ -252,6 +248,64 @@ pub fn build_scalar(
         _ => quote!(),
     };
 
+    let try_from_input_value = match (
+        input.try_from_input_value_arg,
+        input.try_from_input_value_body,
+    ) {
+        (Some(arg), Some(body)) => Some(quote!(
+            fn try_from_input_value(
+                #arg: &::juniper::InputValue<#generic_type>,
+            ) -> Result<Self, ::juniper::FromInputError> {
+                #body
+            }
+        )),
+        (None, Some(_)) => {
+            return Err(error.custom_error(
+                body_span,
+                "unable to find argument for `try_from_input_value` method",
+            ))
+        }
+        _ => None,
+    };
+    let from_input_value =
+        if input.from_input_value_body.is_none() && try_from_input_value.is_some() {
+            // Only the fallible conversion is provided, so derive the plain one from it.
+            quote!(
+                fn from_input_value(
+                    v: &::juniper::InputValue<#generic_type>,
+                ) -> Option<Self> {
+                    <Self as ::juniper::FromInputValue<#generic_type>>::try_from_input_value(v)
+                        .ok()
+                }
+            )
+        } else {
+            let from_input_value_arg = input.from_input_value_arg.ok_or_else(|| {
+                error.custom_error(
+                    body_span,
+                    "unable to find argument for `from_input_value` method",
+                )
+            })?;
+            let from_input_value_body = input.from_input_value_body.ok_or_else(|| {
+                error.custom_error(
+                    body_span,
+                    "unable to find body of `from_input_value` method",
+                )
+            })?;
+            let from_input_value_result = input.from_input_value_result.ok_or_else(|| {
+                error.custom_error(
+                    body_span,
+                    "unable to find return type of `from_input_value` method",
+                )
+            })?;
+            quote!(
+                fn from_input_value(
+                    #from_input_value_arg: &::juniper::InputValue<#generic_type>,
+                ) -> #from_input_value_result {
+                    #from_input_value_body
+                }
+            )
+        };
+
     let _async = quote!(
         impl#async_generic_type_decl ::juniper::GraphQLValueAsync<#async_generic_type> for #impl_for_type
         where
@@ -271,6 +325,22 @@ pub fn build_scalar(
                 Box::pin(future::ready(v))
             }
         }
+
+        impl#async_generic_type_decl ::juniper::GraphQLValueAsyncLocal<#async_generic_type> for #impl_for_type
+        where
+            #async_generic_type: ::juniper::ScalarValue,
+        {
+            fn resolve_local<'a>(
+                &'a self,
+                info: &'a Self::TypeInfo,
+                selection_set: Option<&'a [::juniper::Selection<#async_generic_type>]>,
+                executor: &'a ::juniper::Executor<Self::Context, #async_generic_type>,
+            ) -> ::juniper::LocalBoxFuture<'a, ::juniper::ExecutionResult<#async_generic_type>> {
+                use ::juniper::futures::future;
+                let v = ::juniper::GraphQLValue::resolve(self, info, selection_set, executor);
+                Box::pin(future::ready(v))
+            }
+        }
     );
 
     let content = quote!(
@@ -334,9 +404,9 @@ pub fn build_scalar(
         impl#generic_type_decl ::juniper::FromInputValue<#generic_type> for #impl_for_type
         #generic_type_bound
         {
-            fn from_input_value(#from_input_value_arg: &::juniper::InputValue<#generic_type>) -> #from_input_value_result {
-                #from_input_value_body
-            }
+            #from_input_value
+
+            #try_from_input_value
         }
 
         impl#generic_type_decl ::juniper::ParseScalarValue<#generic_type> for #impl_for_type
@@ -352,3 +422,147 @@ pub fn build_scalar(
 
     Ok(content)
 }
+
+/// Generates a separate GraphQL scalar for each of the given instantiations of a scalar generic
+/// over a type parameter (besides the `S` one of `ScalarValue`).
+fn build_scalar_instances(
+    attrs: util::FieldAttributes,
+    instances: SpanContainer<util::ScalarInstancesAttr>,
+    body: TokenStream,
+    error: GraphQLScope,
+) -> syn::Result<TokenStream> {
+    if let Some(name) = attrs.name {
+        return Err(error.custom_error(
+            name.span_ident(),
+            "generic scalars are named by the `template` of `instances`",
+        ));
+    }
+
+    let mut item = syn::parse2::<syn::ItemImpl>(body)?;
+    let mut type_params = item
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .filter(|ident| ident != "S");
+    let param =
+        match (type_params.next(), type_params.next()) {
+            (Some(param), None) => param,
+            _ => return Err(error.custom_error(
+                item.generics.span(),
+                "instantiating a generic scalar requires exactly one type parameter besides `S`",
+            )),
+        };
+
+    // Bounds of the type parameter are asserted for each instance separately, as the generated
+    // implementations are for the concrete types.
+    let mut bounds = item
+        .generics
+        .type_params()
+        .filter(|ty| ty.ident == param)
+        .flat_map(|ty| ty.bounds.iter().cloned())
+        .collect::<Vec<_>>();
+    if let Some(where_clause) = &item.generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(predicate) = predicate {
+                if let syn::Type::Path(ty) = &predicate.bounded_ty {
+                    if ty.qself.is_none() && ty.path.is_ident(&param) {
+                        bounds.extend(predicate.bounds.iter().cloned());
+                    }
+                }
+            }
+        }
+    }
+    item.generics.params = item
+        .generics
+        .params
+        .into_iter()
+        .filter(|p| !matches!(p, syn::GenericParam::Type(ty) if ty.ident == param))
+        .collect();
+    item.generics.where_clause = None;
+
+    let template = match &instances.template {
+        Some(template) => template.value(),
+        None => {
+            let ident = util::name_of_type(&item.self_ty).ok_or_else(|| {
+                error.custom_error(
+                    item.self_ty.span(),
+                    "could not determine a name for the impl type",
+                )
+            })?;
+            format!("{{}}{}", ident.unraw())
+        }
+    };
+    let description = attrs.description.map(|desc| {
+        let desc = desc.into_inner();
+        quote!( description = #desc )
+    });
+
+    let item = quote!( #item );
+    let mut names = Vec::new();
+    let mut output = TokenStream::new();
+    for (ty, name) in &instances.instances {
+        let name = match name {
+            Some(name) => name.value(),
+            None => {
+                let ty_name = util::name_of_type(ty).ok_or_else(|| {
+                    error.custom_error(ty.span(), "could not determine a name for the type")
+                })?;
+                template.replace("{}", &ty_name.unraw().to_string())
+            }
+        };
+        if !util::is_valid_name(&name) {
+            return Err(error.custom_error(
+                ty.span(),
+                format!(
+                    "name `{}` consists of not allowed characters. \
+                     (must match /^[_a-zA-Z][_a-zA-Z0-9]*$/)",
+                    name,
+                ),
+            ));
+        }
+        if names.contains(&name) {
+            return Err(error.custom_error(
+                ty.span(),
+                format!("duplicate name `{}`, instances need distinct names", name),
+            ));
+        }
+
+        output.extend(quote!(
+            const _: fn() = || {
+                fn assert_bounds<#param: #( #bounds + )* ?Sized>() {}
+                assert_bounds::<#ty>();
+            };
+        ));
+        let instance = replace_ident(item.clone(), &param, &quote!( #ty ));
+        output.extend(build_scalar(
+            quote!( name = #name, #description ),
+            instance,
+            GraphQLScope::ImplScalar,
+        )?);
+        names.push(name);
+    }
+    Ok(output)
+}
+
+/// Replaces all occurrences of the `ident` in the `tokens` with the given `replacement`.
+fn replace_ident(
+    tokens: TokenStream,
+    ident: &syn::Ident,
+    replacement: &TokenStream,
+) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ref i) if i == ident => replacement.clone(),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(
+                    group.delimiter(),
+                    replace_ident(group.stream(), ident, replacement),
+                );
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            tt => tt.into(),
+        })
+        .collect()
+}
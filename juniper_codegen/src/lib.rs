@@ -47,6 +47,12 @@ pub fn derive_input_object(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derives `GraphQLType` for a struct, exposing its fields as GraphQL fields.
+///
+/// `HashMap` and `BTreeMap` fields are exposed as lists of generated
+/// `<Object><Field>Entry` objects with `key` and `value` fields, sorted by key.
+/// The entries of a `HashMap` are sorted when resolving the field, so its key
+/// must implement `Ord`.
 #[proc_macro_error]
 #[proc_macro_derive(GraphQLObject, attributes(graphql))]
 pub fn derive_object(input: TokenStream) -> TokenStream {
@@ -208,6 +214,12 @@ The Context can be injected into your resolvers by just
 specifying an argument with the same type as the context
 (but as a reference).
 
+Context and executor arguments may be placed at any position,
+and may be wrapped into an `Option` (always being `Some`).
+Arguments of other types, like type aliases of the context,
+are injected when marked with `#[graphql(context)]` or
+`#[graphql(executor)]`, regardless of their name.
+
 ```
 
 # #[derive(juniper::GraphQLObject)] struct User { id: i32 }
@@ -241,6 +253,11 @@ impl Query {
         // ...
         true
     }
+
+    // Arguments may also be marked explicitly.
+    fn with_marked_context(id: i32, #[graphql(context)] db: &Context) -> Option<User> {
+        db.db.user(id)
+    }
 }
 
 ```
@@ -306,6 +323,27 @@ impl InternalQuery {
 }
 ```
 
+## Iterators and streams
+
+Fields may return an `impl Iterator<Item = T>` or an `impl Stream<Item = T>`
+(as well as boxed ones), which are collected into a list of `T`. Streams can
+only be returned from `async` fields.
+
+```
+struct Numbers(Vec<i32>);
+
+#[juniper::graphql_object]
+impl Numbers {
+    fn evens(&self) -> impl Iterator<Item = i32> + '_ {
+        self.0.iter().copied().filter(|n| n % 2 == 0)
+    }
+
+    async fn squares(&self) -> impl futures::Stream<Item = i32> + '_ {
+        futures::stream::iter(self.0.iter().map(|n| n * n))
+    }
+}
+```
+
 ## Lifetimes, Generics and custom Scalars
 
 Lifetimes work just like you'd expect.
@@ -365,6 +403,113 @@ impl User {
 }
 ```
 
+## Serial fields
+
+The asynchronous execution resolves the fields of a selection set
+concurrently. A field marked with `#[graphql(serial)]` is resolved apart: the
+fields selected before it are resolved first, and the fields selected after it
+wait for it, e.g. for fields sharing a connection held by the context.
+
+```
+# struct Connection;
+# impl Connection { async fn next_id(&self) -> i32 { 1 } }
+struct Context {
+    connection: Connection,
+}
+
+impl juniper::Context for Context {}
+
+struct Query;
+
+#[juniper::graphql_object(Context = Context)]
+impl Query {
+    #[graphql(serial)]
+    async fn next_id(context: &Context) -> i32 {
+        context.connection.next_id().await
+    }
+}
+```
+
+## Timeouts and retries
+
+An async field marked with `#[graphql(timeout = "2s")]` fails once resolving it
+takes longer than the duration, given in `ms`, `s`, `m`, `h` or `d`. With
+`#[graphql(retries = 2)]`, a failing field is resolved up to twice more before
+giving up. The error of the last attempt is reported, with the number of
+attempts in the `attempts` extension.
+
+Timeouts are completed by the timer of the runtime executing the queries, set
+with `RootNode::with_sleep`, like `tokio::time::delay_for`. Without one, the
+fields with a timeout fail.
+
+```
+# use juniper::FieldResult;
+# async fn fetch_price() -> FieldResult<f64> { Ok(1.0) }
+struct Query;
+
+#[juniper::graphql_object]
+impl Query {
+    #[graphql(timeout = "2s", retries = 2)]
+    async fn price() -> FieldResult<f64> {
+        fetch_price().await
+    }
+}
+```
+
+## Authorization
+
+Objects and fields marked with `#[graphql(require_auth("scope", ...))]` may
+only be accessed by the operations whose context has the scopes, as decided by
+the `PolicyEvaluator` of the schema, see the `juniper::auth` module. Without
+scopes, `#[graphql(require_auth)]` only requires an authenticated context.
+
+```
+# struct Context;
+# impl juniper::Context for Context {}
+struct Audit;
+
+#[juniper::graphql_object(Context = Context, require_auth("admin"))]
+impl Audit {
+    fn entries() -> i32 {
+        3
+    }
+}
+
+struct Query;
+
+#[juniper::graphql_object(Context = Context)]
+impl Query {
+    #[graphql(require_auth)]
+    fn audit() -> Option<Audit> {
+        Some(Audit)
+    }
+}
+```
+
+## Single-threaded execution
+
+With the `local` argument, resolvers may return non-`Send` futures, and the
+context doesn't need to be `Sync`. Such objects implement
+`juniper::GraphQLValueAsyncLocal` instead of `juniper::GraphQLValueAsync`, and
+are executed with `juniper::execute_local`.
+
+```
+use std::rc::Rc;
+
+struct User {
+    name: Rc<str>,
+}
+
+#[juniper::graphql_object(local)]
+impl User {
+    async fn name(&self) -> String {
+        let name = Rc::clone(&self.name);
+        futures::future::ready(()).await;
+        name.to_string()
+    }
+}
+```
+
 */
 #[proc_macro_error]
 #[proc_macro_attribute]
@@ -427,6 +572,43 @@ pub fn graphql_object(args: TokenStream, input: TokenStream) -> TokenStream {
 /// In addition to implementing `GraphQLType` for the type in question,
 /// `FromInputValue` and `ToInputValue` is also implemented. This makes the type
 /// usable as arguments and default values.
+///
+/// ## Generic scalars
+///
+/// A scalar generic over a type can't be registered as a single GraphQL type,
+/// so it is instantiated once per type listed in `instances`. Each instance is
+/// named by the `template`, with `{}` replaced by the last segment of the
+/// type's path, unless a name is given explicitly.
+///
+/// ```rust
+/// use std::{fmt::Display, str::FromStr};
+///
+/// struct Id<T>(T);
+///
+/// #[juniper::graphql_scalar(instances(template = "{}Id", i32, u64 = "BigId"))]
+/// impl<S, T> GraphQLScalar for Id<T>
+/// where
+///     S: juniper::ScalarValue,
+///     T: Display + FromStr,
+/// {
+///     fn resolve(&self) -> juniper::Value {
+///         juniper::Value::scalar(self.0.to_string())
+///     }
+///
+///     fn from_input_value(value: &juniper::InputValue) -> Option<Id<T>> {
+///         value.as_string_value().and_then(|s| s.parse().ok()).map(Id)
+///     }
+///
+///     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> juniper::ParseScalarResult<'a, S> {
+///         <String as juniper::ParseScalarValue<S>>::from_str(value)
+///     }
+/// }
+///
+/// // Registers the `i32Id` and `BigId` scalars.
+/// # fn main() { }
+/// ```
+///
+/// Every listed type must satisfy the bounds of the type parameter.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_scalar(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -748,6 +930,68 @@ pub fn graphql_subscription(args: TokenStream, input: TokenStream) -> TokenStrea
 /// }
 /// ```
 ///
+/// # Resolving by type name
+///
+/// Instead of trying each external resolver function in turn, the [GraphQL union][1] variant
+/// may be decided by a function specified with `resolve_type`. It returns the name of the
+/// variant to resolve into, e.g. from a field of a JSON document or a database row. Only the
+/// resolver function of that variant is called then, and it may return the variant by value, as
+/// the checking of the other variants doesn't rely on them returning `None`.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use juniper::{GraphQLObject, GraphQLUnion};
+/// #
+/// #[derive(GraphQLObject)]
+/// struct Human {
+///     id: String,
+///     home_planet: String,
+/// }
+///
+/// #[derive(GraphQLObject)]
+/// struct Droid {
+///     id: String,
+///     primary_function: String,
+/// }
+///
+/// #[derive(GraphQLUnion)]
+/// #[graphql(
+///     resolve_type = Character::kind,
+///     on Human = Character::to_human,
+///     on Droid = Character::to_droid,
+/// )]
+/// struct Character {
+///     row: HashMap<String, String>,
+/// }
+///
+/// impl Character {
+///     fn kind(&self, _: &()) -> &str {
+///         &self.row["kind"]
+///     }
+///
+///     fn to_human(&self, _: &()) -> Human {
+///         Human {
+///             id: self.row["id"].clone(),
+///             home_planet: self.row["home_planet"].clone(),
+///         }
+///     }
+///
+///     fn to_droid(&self, _: &()) -> Droid {
+///         Droid {
+///             id: self.row["id"].clone(),
+///             primary_function: self.row["primary_function"].clone(),
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Single-threaded execution
+///
+/// With `#[graphql(local)]`, `juniper::GraphQLValueAsyncLocal` is implemented instead of
+/// `juniper::GraphQLValueAsync`, so the [GraphQL union][1] may be resolved by
+/// `juniper::execute_local` from variants holding non-`Send` state. All of its variants have to
+/// be `local` objects then.
+///
 /// [1]: https://spec.graphql.org/June2018/#sec-Unions
 /// [4]: https://doc.rust-lang.org/stable/std/primitive.unit.html
 #[proc_macro_error]
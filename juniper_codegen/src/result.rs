@@ -49,11 +49,17 @@ impl fmt::Display for GraphQLScope {
 #[allow(unused_variables)]
 #[derive(Debug)]
 pub enum UnsupportedAttribute {
-    Skip,
     Interface,
     Scalar,
     Deprecation,
     Default,
+    Alias,
+    Cache,
+    CacheControl,
+    Serial,
+    Timeout,
+    Retries,
+    RequireAuth,
 }
 
 impl GraphQLScope {
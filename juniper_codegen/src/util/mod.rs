@@ -8,14 +8,14 @@ pub mod span_container;
 use std::ops::Deref as _;
 use std::str::FromStr;
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Ident, Span, TokenStream, TokenTree};
 use proc_macro_error::abort;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use span_container::SpanContainer;
 use std::collections::HashMap;
 use syn::{
-    parse, parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute, Lit, Meta, MetaList,
-    MetaNameValue, NestedMeta, Token,
+    ext::IdentExt as _, parse, parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute,
+    Lit, Meta, MetaList, MetaNameValue, NestedMeta, Token,
 };
 
 pub use self::option_ext::OptionExt;
@@ -84,6 +84,150 @@ pub fn unparenthesize(ty: &syn::Type) -> &syn::Type {
     }
 }
 
+/// Returns the trait bounds of the type if it's a reference to a trait object,
+/// like `&dyn Trait`.
+pub fn trait_object_bounds(ty: &syn::Type) -> Option<Vec<&syn::TraitBound>> {
+    let reference = match unparenthesize(ty) {
+        syn::Type::Reference(reference) => reference,
+        _ => return None,
+    };
+    match unparenthesize(&reference.elem) {
+        syn::Type::TraitObject(object) => Some(
+            object
+                .bounds
+                .iter()
+                .filter_map(|bound| match bound {
+                    syn::TypeParamBound::Trait(bound) => Some(bound),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the key and value types if the type is a `HashMap` or a `BTreeMap`,
+/// and whether its entries are unordered, as they are in a `HashMap`.
+pub fn map_key_value_types(ty: &syn::Type) -> Option<(&syn::Type, &syn::Type, bool)> {
+    let segment = match unparenthesize(ty) {
+        syn::Type::Path(ref type_path) => type_path.path.segments.iter().last()?,
+        _ => return None,
+    };
+    if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+        return None;
+    }
+    let args = match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) => &args.args,
+        _ => return None,
+    };
+    let mut types = args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    Some((types.next()?, types.next()?, segment.ident == "HashMap"))
+}
+
+/// Returns the inner type if the type is an `Option`.
+pub fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match unparenthesize(ty) {
+        syn::Type::Path(ref type_path) => type_path.path.segments.iter().last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    let args = match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) => &args.args,
+        _ => return None,
+    };
+    match args.iter().next()? {
+        syn::GenericArgument::Type(ty) if args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
+
+/// Replaces the named lifetimes of the tokens of a type with `'_`, to use the
+/// type outside of the generics declaring them.
+fn elide_lifetimes(tokens: TokenStream) -> TokenStream {
+    let mut lifetime = false;
+    tokens
+        .into_iter()
+        .map(|token| {
+            let token = match token {
+                TokenTree::Group(group) => {
+                    let mut elided = Group::new(group.delimiter(), elide_lifetimes(group.stream()));
+                    elided.set_span(group.span());
+                    TokenTree::Group(elided)
+                }
+                TokenTree::Ident(ident) if lifetime && ident != "static" => {
+                    TokenTree::Ident(Ident::new("_", ident.span()))
+                }
+                token => token,
+            };
+            lifetime = matches!(&token, TokenTree::Punct(p) if p.as_char() == '\'');
+            token
+        })
+        .collect()
+}
+
+/// Kind of a sequence of values returned by a field resolver.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SequenceKind {
+    /// [`Iterator`] or [`IntoIterator`].
+    Iterator,
+    /// `futures::Stream`.
+    Stream,
+}
+
+/// Returns the sequence kind and item type if the type is an `impl Trait` or a boxed trait object
+/// of an iterator or a stream, e.g. `impl Iterator<Item = T>` or `Pin<Box<dyn Stream<Item = T>>>`.
+pub fn sequence_item_type(ty: &syn::Type) -> Option<(SequenceKind, &syn::Type)> {
+    let bounds = match unparenthesize(ty) {
+        syn::Type::ImplTrait(ty) => &ty.bounds,
+        syn::Type::TraitObject(ty) => &ty.bounds,
+        syn::Type::Path(type_path) => {
+            let segment = type_path.path.segments.iter().last()?;
+            let args = match segment.arguments {
+                syn::PathArguments::AngleBracketed(ref args) => &args.args,
+                _ => return None,
+            };
+            let mut types = args.iter().filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            });
+            return match segment.ident.to_string().as_str() {
+                "Box" | "Pin" => sequence_item_type(types.next()?),
+                "BoxStream" | "LocalBoxStream" => Some((SequenceKind::Stream, types.next()?)),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+    bounds.iter().find_map(|bound| {
+        let segment = match bound {
+            syn::TypeParamBound::Trait(bound) => bound.path.segments.iter().last()?,
+            _ => return None,
+        };
+        let kind = match segment.ident.to_string().as_str() {
+            "Iterator" | "IntoIterator" | "ExactSizeIterator" | "DoubleEndedIterator" => {
+                SequenceKind::Iterator
+            }
+            "Stream" => SequenceKind::Stream,
+            _ => return None,
+        };
+        let args = match segment.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => &args.args,
+            _ => return None,
+        };
+        args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Binding(binding) if binding.ident == "Item" => {
+                Some((kind, &binding.ty))
+            }
+            _ => None,
+        })
+    })
+}
+
 #[derive(Debug)]
 pub struct DeprecationAttr {
     pub reason: Option<String>,
@@ -283,6 +427,56 @@ pub(crate) fn to_upper_snake_case(s: &str) -> String {
     upper
 }
 
+/// Turns a default value given in a `#[graphql(...)]` attribute into an
+/// expression of the argument or field type.
+///
+/// Besides plain Rust expressions, this accepts GraphQL-like literals: lists
+/// may be written as arrays (`[1, 2]`), and string literals are converted into
+/// the expected string type, also inside structs and `Some(...)` (e.g.
+/// `Some(Filter { tags: ["a"] })`). The result is type-checked by the compiler
+/// against the declared type.
+pub fn default_value_tokens(expr: &syn::Expr) -> TokenStream {
+    match expr {
+        syn::Expr::Array(array) => {
+            let items = array.elems.iter().map(default_value_tokens);
+            quote!(::std::vec![ #( #items ),* ])
+        }
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(_),
+            ..
+        }) => quote!( ::std::convert::Into::into(#expr) ),
+        syn::Expr::Struct(strukt) => {
+            let path = &strukt.path;
+            let fields = strukt.fields.iter().map(|field| {
+                let member = &field.member;
+                let value = default_value_tokens(&field.expr);
+                quote!( #member: #value )
+            });
+            let rest = strukt.rest.as_ref().map(|rest| quote!( .. #rest ));
+            quote!( #path { #( #fields, )* #rest } )
+        }
+        // Only `Some(...)` is looked into, as other functions may be generic over
+        // their arguments.
+        syn::Expr::Call(call) if is_some_constructor(&call.func) => {
+            let func = &call.func;
+            let args = call.args.iter().map(default_value_tokens);
+            quote!( #func( #( #args ),* ) )
+        }
+        syn::Expr::Paren(paren) => default_value_tokens(&paren.expr),
+        _ => quote!( #expr ),
+    }
+}
+
+fn is_some_constructor(func: &syn::Expr) -> bool {
+    match func {
+        syn::Expr::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment.ident == "Some",
+            None => false,
+        },
+        _ => false,
+    }
+}
+
 #[doc(hidden)]
 pub fn is_valid_name(field_name: &str) -> bool {
     let mut chars = field_name.chars();
@@ -304,6 +498,8 @@ pub enum RenameRule {
     None,
     /// Rename direct children to "camelCase" style.
     CamelCase,
+    /// Rename direct children to "SCREAMING_SNAKE_CASE" style.
+    ScreamingSnakeCase,
 }
 
 impl Default for RenameRule {
@@ -317,6 +513,7 @@ impl RenameRule {
         match self {
             Self::None => field.to_owned(),
             Self::CamelCase => to_camel_case(field),
+            Self::ScreamingSnakeCase => to_upper_snake_case(field),
         }
     }
 }
@@ -328,6 +525,7 @@ impl FromStr for RenameRule {
         match rule {
             "none" => Ok(Self::None),
             "camelCase" => Ok(Self::CamelCase),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnakeCase),
             _ => Err(()),
         }
     }
@@ -341,8 +539,20 @@ pub struct ObjectAttributes {
     pub scalar: Option<SpanContainer<syn::Type>>,
     pub interfaces: Vec<SpanContainer<syn::Type>>,
     pub no_async: Option<SpanContainer<()>>,
+    /// Only relevant for objects: implement `GraphQLValueAsyncLocal` instead
+    /// of `GraphQLValueAsync`, so resolvers may return non-`Send` futures.
+    pub local: Option<SpanContainer<()>>,
     pub is_internal: bool,
-    pub rename: RenameRule,
+    /// Only relevant for GraphQLObject derive: the name of a companion input
+    /// object to generate from the same fields.
+    pub input_object: Option<SpanContainer<String>>,
+    /// Defaults to [`RenameRule::CamelCase`] for fields, and to
+    /// [`RenameRule::ScreamingSnakeCase`] for enum variants.
+    pub rename: Option<RenameRule>,
+    /// Only relevant for objects.
+    pub cache_control: Option<SpanContainer<CacheControlAttr>>,
+    /// Only relevant for objects: the scopes required to access the object.
+    pub require_auth: Option<SpanContainer<Vec<String>>>,
 }
 
 impl syn::parse::Parse for ObjectAttributes {
@@ -405,18 +615,40 @@ impl syn::parse::Parse for ObjectAttributes {
                 "noasync" => {
                     output.no_async = Some(SpanContainer::new(ident.span(), None, ()));
                 }
+                "local" => {
+                    output.local = Some(SpanContainer::new(ident.span(), None, ()));
+                }
                 "internal" => {
                     output.is_internal = true;
                 }
-                "rename" => {
+                "input_object" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let val = input.parse::<syn::LitStr>()?;
+                    output.input_object = Some(SpanContainer::new(
+                        ident.span(),
+                        Some(val.span()),
+                        val.value(),
+                    ));
+                }
+                "rename" | "rename_all" => {
                     input.parse::<syn::Token![=]>()?;
                     let val = input.parse::<syn::LitStr>()?;
                     if let Ok(rename) = RenameRule::from_str(&val.value()) {
-                        output.rename = rename;
+                        output.rename = Some(rename);
                     } else {
                         return Err(syn::Error::new(val.span(), "unknown rename rule"));
                     }
                 }
+                "cache_control" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let attr = content.parse::<CacheControlAttr>()?;
+                    output.cache_control = Some(SpanContainer::new(ident.span(), None, attr));
+                }
+                "require_auth" => {
+                    let scopes = parse_required_scopes(input)?;
+                    output.require_auth = Some(SpanContainer::new(ident.span(), None, scopes));
+                }
                 _ => {
                     return Err(syn::Error::new(ident.span(), "unknown attribute"));
                 }
@@ -460,7 +692,8 @@ pub struct FieldAttributeArgument {
 
 impl parse::Parse for FieldAttributeArgument {
     fn parse(input: parse::ParseStream) -> parse::Result<Self> {
-        let name = input.parse()?;
+        // Keyword-named arguments may be referred to without the `r#` prefix.
+        let name = input.call(syn::Ident::parse_any)?;
 
         let mut arg = Self {
             name,
@@ -508,8 +741,18 @@ enum FieldAttribute {
     Description(SpanContainer<syn::LitStr>),
     Deprecation(SpanContainer<DeprecationAttr>),
     Skip(SpanContainer<syn::Ident>),
+    SkipInput(SpanContainer<syn::Ident>),
+    Alias(SpanContainer<syn::LitStr>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(SpanContainer<Option<syn::Expr>>),
+    Tuple(SpanContainer<TupleAttr>),
+    Cache(SpanContainer<CacheAttr>),
+    CacheControl(SpanContainer<CacheControlAttr>),
+    Serial(SpanContainer<syn::Ident>),
+    Timeout(SpanContainer<u64>),
+    Retries(SpanContainer<u32>),
+    RequireAuth(SpanContainer<Vec<String>>),
+    Instances(SpanContainer<ScalarInstancesAttr>),
 }
 
 impl parse::Parse for FieldAttribute {
@@ -555,11 +798,54 @@ impl parse::Parse for FieldAttribute {
                     },
                 )))
             }
-            "skip" => Ok(FieldAttribute::Skip(SpanContainer::new(
+            "skip" | "ignore" => Ok(FieldAttribute::Skip(SpanContainer::new(
                 ident.span(),
                 None,
                 ident,
             ))),
+            "skip_input" => Ok(FieldAttribute::SkipInput(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "serial" => Ok(FieldAttribute::Serial(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "timeout" => {
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                match parse_duration_millis(&lit.value()) {
+                    Some(millis) if millis > 0 => Ok(FieldAttribute::Timeout(SpanContainer::new(
+                        ident.span(),
+                        Some(lit.span()),
+                        millis,
+                    ))),
+                    _ => Err(syn::Error::new(
+                        lit.span(),
+                        "invalid `timeout`, expected a positive duration like \"500ms\", \"30s\", \"5m\", \"1h\" or \"1d\"",
+                    )),
+                }
+            }
+            "retries" => {
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<syn::LitInt>()?;
+                Ok(FieldAttribute::Retries(SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    lit.base10_parse()?,
+                )))
+            }
+            "alias" => {
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                Ok(FieldAttribute::Alias(SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    lit,
+                )))
+            }
             "arguments" => {
                 let arg_content;
                 syn::parenthesized!(arg_content in input);
@@ -568,7 +854,7 @@ impl parse::Parse for FieldAttribute {
                 )?;
                 let map = args
                     .into_iter()
-                    .map(|arg| (arg.name.to_string(), arg))
+                    .map(|arg| (arg.name.unraw().to_string(), arg))
                     .collect();
                 Ok(FieldAttribute::Arguments(map))
             }
@@ -584,22 +870,430 @@ impl parse::Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(default_expr))
             }
+            "instances" => {
+                let content;
+                let parens = syn::parenthesized!(content in input);
+                Ok(FieldAttribute::Instances(SpanContainer::new(
+                    ident.span(),
+                    Some(parens.span),
+                    content.parse()?,
+                )))
+            }
+            "tuple" => {
+                let attr = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    TupleAttr {
+                        name: lit,
+                        fields: None,
+                    }
+                } else {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    content.parse()?
+                };
+                Ok(FieldAttribute::Tuple(SpanContainer::new(
+                    ident.span(),
+                    Some(attr.name.span()),
+                    attr,
+                )))
+            }
+            "cache" => {
+                let content;
+                syn::parenthesized!(content in input);
+                let attr = content.parse::<CacheAttr>()?;
+                Ok(FieldAttribute::Cache(SpanContainer::new(
+                    ident.span(),
+                    Some(attr.ttl.span()),
+                    attr,
+                )))
+            }
+            "cache_control" => {
+                let content;
+                syn::parenthesized!(content in input);
+                let attr = content.parse::<CacheControlAttr>()?;
+                Ok(FieldAttribute::CacheControl(SpanContainer::new(
+                    ident.span(),
+                    None,
+                    attr,
+                )))
+            }
+            "require_auth" => Ok(FieldAttribute::RequireAuth(SpanContainer::new(
+                ident.span(),
+                None,
+                parse_required_scopes(input)?,
+            ))),
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
 }
 
+/// `#[graphql(tuple = "Name")]` or `#[graphql(tuple(name = "Name", fields("a", "b")))]`
+#[derive(Debug)]
+pub struct TupleAttr {
+    pub name: syn::LitStr,
+    pub fields: Option<Vec<syn::LitStr>>,
+}
+
+impl parse::Parse for TupleAttr {
+    fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+        let mut name = None;
+        let mut fields = None;
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "name" => {
+                    input.parse::<Token![=]>()?;
+                    name = Some(input.parse::<syn::LitStr>()?);
+                }
+                "fields" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let names = Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&content)?;
+                    for lit in &names {
+                        if !is_valid_name(&lit.value()) {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "name consists of not allowed characters. (must match /^[_a-zA-Z][_a-zA-Z0-9]*$/)",
+                            ));
+                        }
+                    }
+                    fields = Some(names.into_iter().collect());
+                }
+                _ => return Err(syn::Error::new(ident.span(), "unknown attribute")),
+            }
+
+            // Discard trailing comma.
+            input.parse::<Token![,]>().ok();
+        }
+
+        match name {
+            Some(name) => Ok(Self { name, fields }),
+            None => Err(input.error("missing `name` of the tuple object")),
+        }
+    }
+}
+
+/// `#[graphql_scalar(instances(template = "{}Id", User, Post = "PostKey"))]`
+#[derive(Debug)]
+pub struct ScalarInstancesAttr {
+    /// Name of the instances, with `{}` being replaced by the name of the type argument.
+    pub template: Option<syn::LitStr>,
+    /// Type arguments to instantiate the scalar with, along with their explicit names.
+    pub instances: Vec<(syn::Type, Option<syn::LitStr>)>,
+}
+
+impl parse::Parse for ScalarInstancesAttr {
+    fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+        let mut template = None;
+        let mut instances = vec![];
+
+        while !input.is_empty() {
+            let is_template = input.peek(syn::Ident)
+                && input.peek2(Token![=])
+                && input.fork().parse::<syn::Ident>()? == "template";
+            if is_template {
+                input.parse::<syn::Ident>()?;
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                if !lit.value().contains("{}") {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "template has to contain a `{}` placeholder for the type argument",
+                    ));
+                }
+                template = Some(lit);
+            } else {
+                let ty = input.parse::<syn::Type>()?;
+                let name = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse::<syn::LitStr>()?)
+                } else {
+                    None
+                };
+                instances.push((ty, name));
+            }
+
+            // Discard trailing comma.
+            input.parse::<Token![,]>().ok();
+        }
+
+        if instances.is_empty() {
+            return Err(input.error("at least one type argument has to be instantiated"));
+        }
+        Ok(Self {
+            template,
+            instances,
+        })
+    }
+}
+
+/// `#[graphql(cache(ttl = "30s"))]`
+#[derive(Debug)]
+pub struct CacheAttr {
+    pub ttl: syn::LitStr,
+    /// The parsed `ttl`.
+    pub ttl_millis: u64,
+}
+
+impl parse::Parse for CacheAttr {
+    fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+        let mut ttl = None;
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "ttl" => {
+                    input.parse::<Token![=]>()?;
+                    ttl = Some(input.parse::<syn::LitStr>()?);
+                }
+                _ => return Err(syn::Error::new(ident.span(), "unknown attribute")),
+            }
+
+            // Discard trailing comma.
+            input.parse::<Token![,]>().ok();
+        }
+
+        let ttl = match ttl {
+            Some(ttl) => ttl,
+            None => return Err(input.error("missing `ttl` of the cached field")),
+        };
+        match parse_duration_millis(&ttl.value()) {
+            Some(ttl_millis) if ttl_millis > 0 => Ok(Self { ttl, ttl_millis }),
+            _ => Err(syn::Error::new(
+                ttl.span(),
+                "invalid `ttl`, expected a positive duration like \"500ms\", \"30s\", \"5m\", \"1h\" or \"1d\"",
+            )),
+        }
+    }
+}
+
+/// `#[graphql(cache_control(max_age = 60, scope = "private"))]`
+#[derive(Debug)]
+pub struct CacheControlAttr {
+    pub max_age: Option<u32>,
+    /// Whether `scope = "private"`.
+    pub private: Option<bool>,
+}
+
+impl parse::Parse for CacheControlAttr {
+    fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+        let mut attr = Self {
+            max_age: None,
+            private: None,
+        };
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "max_age" => {
+                    input.parse::<Token![=]>()?;
+                    let lit = input.parse::<syn::LitInt>()?;
+                    attr.max_age = Some(lit.base10_parse()?);
+                }
+                "scope" => {
+                    input.parse::<Token![=]>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    attr.private = match lit.value().as_str() {
+                        "public" => Some(false),
+                        "private" => Some(true),
+                        _ => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "expected `scope = \"public\"` or `scope = \"private\"`",
+                            ))
+                        }
+                    };
+                }
+                _ => return Err(syn::Error::new(ident.span(), "unknown attribute")),
+            }
+
+            // Discard trailing comma.
+            input.parse::<Token![,]>().ok();
+        }
+
+        Ok(attr)
+    }
+}
+
+impl CacheControlAttr {
+    /// Constructs the `CacheHint`.
+    pub fn hint_tokens(&self) -> TokenStream {
+        let max_age = match self.max_age {
+            Some(max_age) => quote!( Some(#max_age) ),
+            None => quote!(None),
+        };
+        let scope = match self.private {
+            Some(true) => quote!(Some(::juniper::cache::CacheScope::Private)),
+            Some(false) => quote!(Some(::juniper::cache::CacheScope::Public)),
+            None => quote!(None),
+        };
+        quote!(
+            ::juniper::cache::CacheHint {
+                max_age: #max_age,
+                scope: #scope,
+            }
+        )
+    }
+}
+
+/// Parses the optional scopes of `require_auth`, e.g. `require_auth("a", "b")`.
+fn parse_required_scopes(input: syn::parse::ParseStream) -> syn::parse::Result<Vec<String>> {
+    if !input.peek(syn::token::Paren) {
+        return Ok(vec![]);
+    }
+    let content;
+    syn::parenthesized!(content in input);
+    let scopes = Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(scopes.iter().map(syn::LitStr::value).collect())
+}
+
+/// Tokens setting the scopes required by a type or a field, if any.
+fn require_auth_tokens(required_scopes: Option<&Vec<String>>) -> Option<TokenStream> {
+    required_scopes.map(|scopes| quote!( .require_auth(&[ #( #scopes ),* ]) ))
+}
+
+/// Parses a duration made of a number and a unit, e.g. `"30s"`.
+fn parse_duration_millis(duration: &str) -> Option<u64> {
+    let duration = duration.trim();
+    let unit_start = duration.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = duration.split_at(unit_start);
+    let millis = match unit.trim() {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return None,
+    };
+    amount.parse::<u64>().ok()?.checked_mul(millis)
+}
+
+/// Hidden object type exposing the elements of a tuple-typed field, see
+/// [`TupleAttr`].
+pub struct TupleObject {
+    pub ident: syn::Ident,
+    /// Number of elements in the tuple.
+    pub len: usize,
+    pub definition: TokenStream,
+}
+
+impl TupleObject {
+    /// Fields referencing the elements of the tuple are generated if
+    /// `borrowed`, owning them otherwise. `object_attrs` are passed through to
+    /// the object derive, e.g. `context = Ctx`.
+    pub fn new(
+        attr: &SpanContainer<TupleAttr>,
+        ty: &syn::Type,
+        borrowed: bool,
+        object_attrs: TokenStream,
+    ) -> syn::Result<Self> {
+        let elems = match unparenthesize(ty) {
+            syn::Type::Tuple(tuple) if !tuple.elems.is_empty() => &tuple.elems,
+            _ => {
+                return Err(syn::Error::new(
+                    ty.span(),
+                    "`tuple` requires a non-empty tuple type, e.g. `(i32, String)`",
+                ))
+            }
+        };
+
+        let names = match attr.fields {
+            Some(ref fields) if fields.len() != elems.len() => {
+                return Err(syn::Error::new(
+                    attr.span_ident(),
+                    format!(
+                        "expected {} field names, one per tuple element",
+                        elems.len()
+                    ),
+                ))
+            }
+            Some(ref fields) => fields.iter().map(syn::LitStr::value).collect(),
+            None => (0..elems.len())
+                .map(|i| format!("_{}", i))
+                .collect::<Vec<_>>(),
+        };
+
+        let name = &attr.name;
+        let ident = syn::Ident::new(&name.value(), name.span());
+        let fields = elems.iter().zip(names).enumerate().map(|(i, (ty, name))| {
+            let field_ident = quote::format_ident!("_{}", i);
+            let ty = if borrowed {
+                quote!( &'__tuple #ty )
+            } else {
+                quote!( #ty )
+            };
+            quote!(
+                #[graphql(name = #name)]
+                #field_ident: #ty,
+            )
+        });
+        let lifetime = if borrowed {
+            Some(quote!( <'__tuple> ))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            ident: ident.clone(),
+            len: elems.len(),
+            definition: quote!(
+                #[derive(::juniper::GraphQLObject)]
+                #[graphql(name = #name #object_attrs)]
+                #[doc(hidden)]
+                struct #ident #lifetime {
+                    #( #fields )*
+                }
+            ),
+        })
+    }
+
+    /// Constructs the object out of an expression of the tuple type, or of a
+    /// reference to it if `borrowed`.
+    pub fn constructor(&self, tuple: TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        let idents = (0..self.len)
+            .map(|i| quote::format_ident!("_{}", i))
+            .collect::<Vec<_>>();
+        quote!({
+            let ( #( #idents, )* ) = #tuple;
+            #ident { #( #idents, )* }
+        })
+    }
+}
+
 #[derive(Default)]
 pub struct FieldAttributes {
     pub name: Option<SpanContainer<String>>,
     pub description: Option<SpanContainer<String>>,
     pub deprecation: Option<SpanContainer<DeprecationAttr>>,
-    // Only relevant for GraphQLObject derive.
+    // Only relevant for GraphQLObject and GraphQLEnum derive.
     pub skip: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for GraphQLObject derive with an `input_object`.
+    pub skip_input: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for GraphQLEnum derive.
+    pub aliases: Vec<SpanContainer<String>>,
     /// Only relevant for object macro.
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Only relevant for objects.
+    pub tuple: Option<SpanContainer<TupleAttr>>,
+    /// Only relevant for objects.
+    pub cache: Option<SpanContainer<CacheAttr>>,
+    /// Only relevant for objects.
+    pub cache_control: Option<SpanContainer<CacheControlAttr>>,
+    /// Only relevant for objects.
+    pub serial: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for objects, in milliseconds.
+    pub timeout: Option<SpanContainer<u64>>,
+    /// Only relevant for objects.
+    pub retries: Option<SpanContainer<u32>>,
+    /// Only relevant for objects: the scopes required to access the field.
+    pub require_auth: Option<SpanContainer<Vec<String>>>,
+    /// Only relevant for scalars.
+    pub instances: Option<SpanContainer<ScalarInstancesAttr>>,
 }
 
 impl parse::Parse for FieldAttributes {
@@ -622,12 +1316,42 @@ impl parse::Parse for FieldAttributes {
                 FieldAttribute::Skip(ident) => {
                     output.skip = Some(ident);
                 }
+                FieldAttribute::SkipInput(ident) => {
+                    output.skip_input = Some(ident);
+                }
+                FieldAttribute::Alias(alias) => {
+                    output.aliases.push(alias.map(|val| val.value()));
+                }
                 FieldAttribute::Arguments(args) => {
                     output.arguments = args;
                 }
                 FieldAttribute::Default(expr) => {
                     output.default = Some(expr);
                 }
+                FieldAttribute::Tuple(tuple) => {
+                    output.tuple = Some(tuple);
+                }
+                FieldAttribute::Cache(cache) => {
+                    output.cache = Some(cache);
+                }
+                FieldAttribute::CacheControl(cache_control) => {
+                    output.cache_control = Some(cache_control);
+                }
+                FieldAttribute::Serial(ident) => {
+                    output.serial = Some(ident);
+                }
+                FieldAttribute::Timeout(timeout) => {
+                    output.timeout = Some(timeout);
+                }
+                FieldAttribute::Retries(retries) => {
+                    output.retries = Some(retries);
+                }
+                FieldAttribute::RequireAuth(scopes) => {
+                    output.require_auth = Some(scopes);
+                }
+                FieldAttribute::Instances(instances) => {
+                    output.instances = Some(instances);
+                }
             }
         }
 
@@ -688,7 +1412,27 @@ pub struct GraphQLTypeDefinitionField {
     pub resolver_code: TokenStream,
     pub is_type_inferred: bool,
     pub is_async: bool,
+    /// For input objects the default value of the field, for enums the
+    /// expression constructing a variant carrying data.
     pub default: Option<TokenStream>,
+    /// Only relevant for enums: other names accepted on input.
+    pub aliases: Vec<String>,
+    /// Only relevant for objects: how long results are cached, in
+    /// milliseconds.
+    pub cache_ttl_millis: Option<u64>,
+    /// Only relevant for objects: the `CacheHint` of the field.
+    pub cache_hint: Option<TokenStream>,
+    /// Only relevant for objects: whether the field is resolved apart from
+    /// its siblings.
+    pub serial: bool,
+    /// Only relevant for async fields of objects: the timeout of each attempt
+    /// to resolve the field, in milliseconds.
+    pub timeout_millis: Option<u64>,
+    /// Only relevant for async fields of objects: how often the field is
+    /// resolved again after failing.
+    pub retries: u32,
+    /// Only relevant for objects: the scopes required to access the field.
+    pub required_scopes: Option<Vec<String>>,
     pub span: Span,
 }
 
@@ -731,6 +1475,13 @@ pub struct GraphQLTypeDefiniton {
     pub generic_scalar: bool,
     // FIXME: make this redundant.
     pub no_async: bool,
+    /// Only relevant for objects: whether to implement
+    /// `GraphQLValueAsyncLocal` instead of `GraphQLValueAsync`.
+    pub local: bool,
+    /// Only relevant for objects: the `CacheHint` of the type.
+    pub cache_hint: Option<TokenStream>,
+    /// Only relevant for objects: the scopes required to access the type.
+    pub required_scopes: Option<Vec<String>>,
 }
 
 impl GraphQLTypeDefiniton {
@@ -741,6 +1492,7 @@ impl GraphQLTypeDefiniton {
 
     pub fn into_tokens(self) -> TokenStream {
         let name = &self.name;
+        let type_name = name;
         let ty = &self._type;
         let context = self
             .context
@@ -759,7 +1511,7 @@ impl GraphQLTypeDefiniton {
                 };
 
                 // Code.
-                match arg.default.as_ref() {
+                match arg.default.as_ref().map(default_value_tokens) {
                     Some(value) => quote!(
                         .argument(
                             registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
@@ -793,6 +1545,19 @@ impl GraphQLTypeDefiniton {
 
             let field_name = &field.name;
 
+            let cache_hint = field
+                .cache_hint
+                .as_ref()
+                .map(|hint| quote!( .cache_hint(#hint) ));
+
+            let serial = if field.serial {
+                quote!( .serial() )
+            } else {
+                quote!()
+            };
+
+            let require_auth = require_auth_tokens(field.required_scopes.as_ref());
+
             let _type = &field._type;
             quote! {
                 registry
@@ -800,6 +1565,9 @@ impl GraphQLTypeDefiniton {
                     #(#args)*
                     #description
                     #deprecation
+                    #cache_hint
+                    #serial
+                    #require_auth
             }
         });
 
@@ -837,21 +1605,33 @@ impl GraphQLTypeDefiniton {
                     let _type = &field._type;
                     quote!(: #_type)
                 };
-                quote!(
-                    #name => {
-                        let res #_type = (|| { #code })();
-                        ::juniper::IntoResolvable::into(
-                            res,
-                            executor.context()
-                        )
-                            .and_then(|res| {
-                                match res {
-                                    Some((ctx, r)) => executor.replaced_context(ctx).resolve_with_ctx(&(), &r),
-                                    None => Ok(::juniper::Value::null()),
-                                }
-                            })
-                    },
-                )
+                let resolve = quote!(
+                    let res #_type = (|| { #code })();
+                    ::juniper::IntoResolvable::into(
+                        res,
+                        executor.context()
+                    )
+                        .and_then(|res| {
+                            match res {
+                                Some((ctx, r)) => executor.replaced_context(ctx).resolve_with_ctx(&(), &r),
+                                None => Ok(::juniper::Value::null()),
+                            }
+                        })
+                );
+                match field.cache_ttl_millis {
+                    Some(ttl) => quote!(
+                        #name => {
+                            let key = executor.field_cache_key(#type_name, #name, self);
+                            let ttl = ::std::time::Duration::from_millis(#ttl);
+                            executor.resolve_cached(key, ttl, || { #resolve })
+                        },
+                    ),
+                    None => quote!(
+                        #name => {
+                            #resolve
+                        },
+                    ),
+                }
             }
         });
 
@@ -868,6 +1648,136 @@ impl GraphQLTypeDefiniton {
             )
         });
 
+        // Interfaces defined by `graphql_interface!` list their fields, which
+        // are checked against the ones of the object in a constant, failing
+        // the build at the interface. Objects generic over types are skipped,
+        // as their interfaces may depend on them.
+        let interface_checks = if self.generics.type_params().next().is_none() {
+            let object_fields = self.fields.iter().map(|field| {
+                let field_name = &field.name;
+                let args = field.args.iter().map(|arg| {
+                    let arg_name = &arg.name;
+                    let nullable = match unparenthesize(&arg._type) {
+                        syn::Type::Path(type_path) => matches!(
+                            type_path.path.segments.last(),
+                            Some(s) if s.ident == "Option" || s.ident == "Nullable"
+                        ),
+                        _ => false,
+                    };
+                    let optional = arg.default.is_some() || nullable;
+                    let required = if optional {
+                        quote!(None)
+                    } else {
+                        let error = format!(
+                            "Argument `{}` of field `{}` must be optional, as the interface \
+                             doesn't define it",
+                            arg_name, field_name,
+                        );
+                        quote!(Some(#error))
+                    };
+                    quote!(
+                        ::juniper::interface_helpers::ObjectArgument {
+                            name: #arg_name,
+                            required: #required,
+                        }
+                    )
+                });
+                quote!(
+                    ::juniper::interface_helpers::ObjectField {
+                        name: #field_name,
+                        arguments: &[ #( #args ),* ],
+                    }
+                )
+            });
+            let object_fields = quote!(&[ #( #object_fields ),* ]);
+
+            self.interfaces
+                .iter()
+                .flatten()
+                .map(|iface| {
+                    let iface_ty = elide_lifetimes(quote!(#iface));
+                    let span = name_of_type(iface).map_or_else(|| iface.span(), |name| name.span());
+                    quote_spanned!(span=>
+                        const _: () = ::juniper::interface_helpers::check_interface_fields(
+                            <#iface_ty as ::juniper::GraphQLType>::INTERFACE_FIELDS,
+                            #object_fields,
+                        );
+                    )
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
+        // The fields an interface implements by default are resolved by
+        // turning `self` into the interface, which is only possible for trait
+        // object interfaces whose trait the object implements. Whether it does
+        // is probed with autoref specialization, as the object isn't required
+        // to.
+        let default_impls = self
+            .interfaces
+            .iter()
+            .flatten()
+            .filter_map(|iface| Some((iface, trait_object_bounds(iface)?)))
+            .map(|(iface, bounds)| {
+                quote!(
+                    if executor.schema().is_default_impl(
+                        <#iface as ::juniper::GraphQLType<#scalar>>::name(&()).unwrap(),
+                        field,
+                    ) {
+                        struct Probe<'p, T: ?Sized>(&'p T);
+                        trait Implemented<'p> {
+                            fn interface(&self) -> Option<&'p (dyn #( #bounds )+* + 'p)>;
+                        }
+                        impl<'p, T: #( #bounds )+* + 'p> Implemented<'p> for Probe<'p, T> {
+                            fn interface(&self) -> Option<&'p (dyn #( #bounds )+* + 'p)> {
+                                Some(self.0)
+                            }
+                        }
+                        trait NotImplemented<'p> {
+                            fn interface(&self) -> Option<&'p (dyn #( #bounds )+* + 'p)>;
+                        }
+                        impl<'p, T: ?Sized> NotImplemented<'p> for &Probe<'p, T> {
+                            fn interface(&self) -> Option<&'p (dyn #( #bounds )+* + 'p)> {
+                                None
+                            }
+                        }
+
+                        if let Some(interface) = (&Probe(self)).interface() {
+                            let interface: #iface = interface;
+                            return Some(::juniper::GraphQLValue::<#scalar>::resolve_field(
+                                &interface,
+                                &(),
+                                field,
+                                args,
+                                executor,
+                            ));
+                        }
+                    }
+                )
+            })
+            .collect::<Vec<_>>();
+        let default_impl = |res: TokenStream| {
+            if default_impls.is_empty() {
+                return quote!();
+            }
+            quote!(
+                if let Some(res) = (|| {
+                    #( #default_impls )*
+                    None
+                })() {
+                    return #res;
+                }
+            )
+        };
+        let default_impl_sync = default_impl(quote!(res));
+
+        let cache_hint = self
+            .cache_hint
+            .as_ref()
+            .map(|hint| quote!( .cache_hint(#hint) ));
+        let require_auth = require_auth_tokens(self.required_scopes.as_ref());
+
         // Preserve the original type_generics before modification,
         // since alteration makes them invalid if self.generic_scalar
         // is specified.
@@ -892,6 +1802,14 @@ impl GraphQLTypeDefiniton {
         };
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
+        // With the `local` switch, the non-`Send` counterparts of the async
+        // traits and `Executor` methods are used instead.
+        let (resolve_with_ctx_async, boxed) = if self.local {
+            (quote!(resolve_with_ctx_local), quote!(boxed_local))
+        } else {
+            (quote!(resolve_with_ctx_async), quote!(boxed))
+        };
+
         let resolve_field_async = {
             let resolve_matches_async = self.fields.iter().map(|field| {
                 let name = &field.name;
@@ -903,27 +1821,91 @@ impl GraphQLTypeDefiniton {
                     quote!(: #_type)
                 };
 
+                let cache = field.cache_ttl_millis.map(|ttl| quote!(
+                    let key = executor.field_cache_key(#type_name, #name, self);
+                    let ttl = ::std::time::Duration::from_millis(#ttl);
+                    let f = executor.resolve_cached_async(key, ttl, f);
+                ));
+
                 if field.is_async {
+                    let inner_res = if field.timeout_millis.is_some() || field.retries > 0 {
+                        let timeout = match field.timeout_millis {
+                            Some(millis) => quote!(Some(::std::time::Duration::from_millis(#millis))),
+                            None => quote!(None),
+                        };
+                        let retries = field.retries;
+                        quote!(
+                            let policy = ::juniper::FieldPolicy {
+                                timeout: #timeout,
+                                retries: #retries,
+                            };
+                            let inner_res = policy.resolve(executor.sleep(), move || async move {
+                                let res #_type = async move { #code }.await;
+                                ::juniper::IntoResolvable::into(res, executor.context())
+                            }).await;
+                        )
+                    } else {
+                        quote!(
+                            let res #_type = async move { #code }.await;
+
+                            let inner_res = ::juniper::IntoResolvable::into(
+                                res,
+                                executor.context()
+                            );
+                        )
+                    };
                     quote!(
                         #name => {
                             let f = async move {
-                                let res #_type = async move { #code }.await;
-
-                                let inner_res = ::juniper::IntoResolvable::into(
-                                    res,
-                                    executor.context()
-                                );
+                                #inner_res
                                 match inner_res {
                                     Ok(Some((ctx, r))) => {
                                         let subexec = executor
                                             .replaced_context(ctx);
-                                        subexec.resolve_with_ctx_async(&(), &r)
+                                        subexec.#resolve_with_ctx_async(&(), &r)
                                             .await
                                     },
                                     Ok(None) => Ok(::juniper::Value::null()),
                                     Err(e) => Err(e),
                                 }
                             };
+                            #cache
+                            Box::pin(f)
+                        },
+                    )
+                } else if cache.is_some() {
+                    // Only resolve the field once the cache is missed.
+                    let resolve = if !self.no_async {
+                        quote!(
+                            match res2 {
+                                Ok(Some((ctx, r))) => {
+                                    let sub = executor.replaced_context(ctx);
+                                    sub.#resolve_with_ctx_async(&(), &r).await
+                                },
+                                Ok(None) => Ok(::juniper::Value::null()),
+                                Err(e) => Err(e),
+                            }
+                        )
+                    } else {
+                        quote!(
+                            match res2 {
+                                Ok(Some((ctx, r))) => executor.replaced_context(ctx).resolve_with_ctx(&(), &r),
+                                Ok(None) => Ok(::juniper::Value::null()),
+                                Err(e) => Err(e),
+                            }
+                        )
+                    };
+                    quote!(
+                        #name => {
+                            let f = async move {
+                                let res #_type = (||{ #code })();
+                                let res2 = ::juniper::IntoResolvable::into(
+                                    res,
+                                    executor.context()
+                                );
+                                #resolve
+                            };
+                            #cache
                             Box::pin(f)
                         },
                     )
@@ -934,14 +1916,14 @@ impl GraphQLTypeDefiniton {
                                 match res2 {
                                     Ok(Some((ctx, r))) => {
                                         let sub = executor.replaced_context(ctx);
-                                        sub.resolve_with_ctx_async(&(), &r).await
+                                        sub.#resolve_with_ctx_async(&(), &r).await
                                     },
                                     Ok(None) => Ok(::juniper::Value::null()),
                                     Err(e) => Err(e),
                                 }
                             };
                             use ::juniper::futures::future;
-                            future::FutureExt::boxed(f)
+                            future::FutureExt::#boxed(f)
                         )
                     } else {
                         quote!(
@@ -968,44 +1950,74 @@ impl GraphQLTypeDefiniton {
                 }
             });
 
-            let mut where_async = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+            let default_impl_async = default_impl(quote!(Box::pin(future::ready(res))));
+            let resolve_field_async = quote!(
+                use ::juniper::futures::future;
+                use ::juniper::GraphQLType;
+                match field {
+                    #( #resolve_matches_async )*
+                    _ => {
+                        #default_impl_async
+                        panic!("Field {} not found on type {:?}",
+                            field,
+                            <Self as ::juniper::GraphQLType<#scalar>>::name(info)
+                        );
+                    }
+                }
+            );
 
-            where_async
-                .predicates
-                .push(parse_quote!( #scalar: Send + Sync ));
-            where_async.predicates.push(parse_quote!(Self: Sync));
+            if self.local {
+                quote!(
+                    impl#impl_generics ::juniper::GraphQLValueAsyncLocal<#scalar> for #ty #type_generics_tokens
+                        #where_clause
+                    {
+                        fn resolve_field_local<'b>(
+                            &'b self,
+                            info: &'b Self::TypeInfo,
+                            field: &'b str,
+                            args: &'b ::juniper::Arguments<#scalar>,
+                            executor: &'b ::juniper::Executor<Self::Context, #scalar>,
+                        ) -> ::juniper::LocalBoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                            #resolve_field_async
+                        }
+                    }
+                )
+            } else {
+                let mut where_async = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+
+                where_async
+                    .predicates
+                    .push(parse_quote!( #scalar: Send + Sync ));
+                // Structs generic over lifetimes only are `Sync` for any of them, while bounding
+                // `Self` would shadow that with a bound for specific lifetimes, which doesn't
+                // satisfy the `Send` check of futures borrowing `self`.
+                if self.generics.type_params().next().is_some()
+                    || !matches!(unparenthesize(ty), syn::Type::Path(_))
+                {
+                    where_async.predicates.push(parse_quote!(Self: Sync));
+                }
 
-            // FIXME: add where clause for interfaces.
+                // FIXME: add where clause for interfaces.
 
-            quote!(
-                impl#impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ty #type_generics_tokens
-                    #where_async
-                {
-                    fn resolve_field_async<'b>(
-                        &'b self,
-                        info: &'b Self::TypeInfo,
-                        field: &'b str,
-                        args: &'b ::juniper::Arguments<#scalar>,
-                        executor: &'b ::juniper::Executor<Self::Context, #scalar>,
-                    ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>>
-                        where #scalar: Send + Sync,
+                quote!(
+                    impl#impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ty #type_generics_tokens
+                        #where_async
                     {
-                        use ::juniper::futures::future;
-                        use ::juniper::GraphQLType;
-                        match field {
-                            #( #resolve_matches_async )*
-                            _ => {
-                                panic!("Field {} not found on type {:?}",
-                                    field,
-                                    <Self as ::juniper::GraphQLType<#scalar>>::name(info)
-                                );
-                            }
+                        fn resolve_field_async<'b>(
+                            &'b self,
+                            info: &'b Self::TypeInfo,
+                            field: &'b str,
+                            args: &'b ::juniper::Arguments<#scalar>,
+                            executor: &'b ::juniper::Executor<Self::Context, #scalar>,
+                        ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>>
+                            where #scalar: Send + Sync,
+                        {
+                            #resolve_field_async
                         }
                     }
-                }
-            )
+                )
+            }
         };
-
         let marks = self.fields.iter().map(|field| {
             let field_marks = field.args.iter().map(|arg| {
                 let arg_ty = &arg._type;
@@ -1026,6 +2038,8 @@ impl GraphQLTypeDefiniton {
         });
 
         let output = quote!(
+            #( #interface_checks )*
+
             impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ty #type_generics_tokens #where_clause {
                 fn mark() {
                     #( #marks )*
@@ -1053,7 +2067,9 @@ impl GraphQLTypeDefiniton {
                     ];
                     let meta = registry.build_object_type::<#ty>(info, &fields)
                         #description
-                        #interfaces;
+                        #interfaces
+                        #cache_hint
+                        #require_auth;
                     meta.into_meta()
                 }
         }
@@ -1080,6 +2096,7 @@ impl GraphQLTypeDefiniton {
                     match field {
                         #( #resolve_matches )*
                         _ => {
+                            #default_impl_sync
                             panic!("Field {} not found on type {:?}",
                                 field,
                                 <Self as ::juniper::GraphQLType<#scalar>>::name(_info)
@@ -1133,7 +2150,7 @@ impl GraphQLTypeDefiniton {
                     None => quote!(),
                 };
 
-                match arg.default.as_ref() {
+                match arg.default.as_ref().map(default_value_tokens) {
                     Some(value) => quote!(
                         .argument(
                             registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
@@ -1177,15 +2194,20 @@ impl GraphQLTypeDefiniton {
                 panic!("Synchronous resolvers are not supported. Specify that this function is async: 'async fn foo()'")
             }
 
+            let require_auth = require_auth_tokens(field.required_scopes.as_ref());
+
             quote! {
                 registry
                     .field_convert::<#_type, _, Self::Context>(#field_name, info)
                     #(#args)*
                     #description
                     #deprecation
+                    #require_auth
             }
         });
 
+        let require_auth = require_auth_tokens(self.required_scopes.as_ref());
+
         let description = self
             .description
             .as_ref()
@@ -1322,7 +2344,8 @@ impl GraphQLTypeDefiniton {
                         ];
                         let meta = registry.build_object_type::<#ty>(info, &fields)
                             #description
-                            #interfaces;
+                            #interfaces
+                            #require_auth;
                         meta.into_meta()
                     }
             }
@@ -1403,7 +2426,10 @@ impl GraphQLTypeDefiniton {
         )
     }
 
-    pub fn into_enum_tokens(self) -> TokenStream {
+    /// `has_ignored_variants` signals that not all variants of the enum are
+    /// listed in `fields`, so the remaining ones fail to resolve, and the enum
+    /// can't be converted into an input value, e.g. for argument defaults.
+    pub fn into_enum_tokens(self, has_ignored_variants: bool) -> TokenStream {
         let name = &self.name;
         let ty = &self._type;
         let context = self
@@ -1469,13 +2495,32 @@ impl GraphQLTypeDefiniton {
 
         let from_inputs = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
-            let resolver_code = &variant.resolver_code;
+            let aliases = &variant.aliases;
+            let constructor = variant.default.as_ref().unwrap_or(&variant.resolver_code);
 
             quote!(
-                Some(#variant_name) => Some(#resolver_code),
+                Some(#variant_name) #( | Some(#aliases) )* => Some(#constructor),
             )
         });
 
+        let ignored_variants_error = format!("Cannot resolve an ignored variant of enum {}", name);
+        let ignored_resolve = if has_ignored_variants {
+            quote!(
+                _ => return Err(::juniper::FieldError::from(#ignored_variants_error)),
+            )
+        } else {
+            quote!()
+        };
+
+        let invalid_value_reason = format!(
+            "expected one of: {}",
+            self.fields
+                .iter()
+                .map(|variant| variant.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
         let to_inputs = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
             let resolver_code = &variant.resolver_code;
@@ -1524,6 +2569,21 @@ impl GraphQLTypeDefiniton {
                     Box::pin(future::ready(v))
                 }
             }
+
+            impl#impl_generics ::juniper::GraphQLValueAsyncLocal<#scalar> for #ty
+                #where_clause
+            {
+                fn resolve_local<'a>(
+                    &'a self,
+                    info: &'a Self::TypeInfo,
+                    selection_set: Option<&'a [::juniper::Selection<#scalar>]>,
+                    executor: &'a ::juniper::Executor<Self::Context, #scalar>,
+                ) -> ::juniper::LocalBoxFuture<'a, ::juniper::ExecutionResult<#scalar>> {
+                    use ::juniper::futures::future;
+                    let v = ::juniper::GraphQLValue::resolve(self, info, selection_set, executor);
+                    Box::pin(future::ready(v))
+                }
+            }
         );
 
         let mut body = quote!(
@@ -1572,6 +2632,7 @@ impl GraphQLTypeDefiniton {
                 ) -> ::juniper::ExecutionResult<#scalar> {
                     let v = match self {
                         #( #resolves )*
+                        #ignored_resolve
                     };
                     Ok(v)
                 }
@@ -1589,19 +2650,32 @@ impl GraphQLTypeDefiniton {
                         _ => None,
                     }
                 }
-            }
 
-            impl#impl_generics ::juniper::ToInputValue<#scalar> for #ty
-                #where_clause
-            {
-                fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
-                    match self {
-                        #( #to_inputs )*
-                    }
+                fn try_from_input_value(
+                    v: &::juniper::InputValue<#scalar>,
+                ) -> Result<#ty, ::juniper::FromInputError> {
+                    <Self as ::juniper::FromInputValue<#scalar>>::from_input_value(v).ok_or_else(|| {
+                        ::juniper::FromInputError::new(#invalid_value_reason).with_expected(#name)
+                    })
                 }
             }
         );
 
+        // Ignored variants have no input value to be converted into.
+        if !has_ignored_variants {
+            body.extend(quote!(
+                impl#impl_generics ::juniper::ToInputValue<#scalar> for #ty
+                    #where_clause
+                {
+                    fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
+                        match self {
+                            #( #to_inputs )*
+                        }
+                    }
+                }
+            ));
+        }
+
         if !self.no_async {
             body.extend(_async)
         }
@@ -1609,7 +2683,9 @@ impl GraphQLTypeDefiniton {
         body
     }
 
-    pub fn into_input_object_tokens(self) -> TokenStream {
+    /// `skipped_fields` initialize the struct fields not exposed to GraphQL,
+    /// e.g. `field: Default::default(),`.
+    pub fn into_input_object_tokens(self, skipped_fields: &[TokenStream]) -> TokenStream {
         let name = &self.name;
         let ty = &self._type;
         let context = self
@@ -1679,34 +2755,38 @@ impl GraphQLTypeDefiniton {
             })
             .collect::<Vec<_>>();
 
-        let from_inputs = self.fields.iter().map(|field| {
-            let field_ident = &field.resolver_code;
-            let field_name = &field.name;
+        let from_inputs = self
+            .fields
+            .iter()
+            .map(|field| {
+                let field_ident = &field.resolver_code;
+                let field_name = &field.name;
 
-            // Build from_input clause.
-            let from_input_default = match field.default {
-                Some(ref def) => {
-                    quote! {
-                        Some(&&::juniper::InputValue::Null) | None if true => #def,
+                // Build from_input clause.
+                let from_input_default = match field.default {
+                    Some(ref def) => {
+                        quote! {
+                            Some(&&::juniper::InputValue::Null) | None if true => #def,
+                        }
                     }
-                }
-                None => quote! {},
-            };
+                    None => quote! {},
+                };
 
-            quote!(
-                #field_ident: {
-                    // TODO: investigate the unwraps here, they seem dangerous!
-                    match obj.get(#field_name) {
-                        #from_input_default
-                        Some(ref v) => ::juniper::FromInputValue::from_input_value(v).unwrap(),
-                        None => {
-                            ::juniper::FromInputValue::from_input_value(&::juniper::InputValue::<#scalar>::null())
-                            .unwrap()
-                        },
-                    }
-                },
-            )
-        }).collect::<Vec<_>>();
+                quote!(
+                    #field_ident: {
+                        // TODO: investigate the unwraps here, they seem dangerous!
+                        match obj.get(#field_name) {
+                            #from_input_default
+                            Some(ref v) => ::juniper::FromInputValue::from_input_value(v).unwrap(),
+                            None => {
+                                <_ as ::juniper::FromInputValue<#scalar>>::from_implicit_null()
+                                    .unwrap()
+                            },
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
 
         let to_inputs = self
             .fields
@@ -1821,6 +2901,7 @@ impl GraphQLTypeDefiniton {
                     if let Some(obj) = value.to_object_value() {
                         let item = #ty {
                             #( #from_inputs )*
+                            #( #skipped_fields )*
                         };
                         Some(item)
                     }
@@ -1997,4 +3078,17 @@ mod test {
         assert_eq!(is_valid_name("aTest"), true);
         assert_eq!(is_valid_name("__Atest90"), true);
     }
+
+    #[test]
+    fn test_parse_duration_millis() {
+        assert_eq!(parse_duration_millis("250ms"), Some(250));
+        assert_eq!(parse_duration_millis("30s"), Some(30_000));
+        assert_eq!(parse_duration_millis("5m"), Some(300_000));
+        assert_eq!(parse_duration_millis("2h"), Some(7_200_000));
+        assert_eq!(parse_duration_millis("1d"), Some(86_400_000));
+        assert_eq!(parse_duration_millis("30"), None);
+        assert_eq!(parse_duration_millis("s"), None);
+        assert_eq!(parse_duration_millis("1.5s"), None);
+        assert_eq!(parse_duration_millis("3w"), None);
+    }
 }
@@ -81,36 +81,61 @@ impl ImplBlock {
                         }
                     };
                     let context_type = self.attrs.context.as_ref();
+                    let injected = parse_injection_attr(&captured.attrs)?;
 
+                    // Executor and context may be wrapped into an `Option`, which is always
+                    // `Some`.
+                    let (ty, is_optional) = match util::option_inner_type(&captured.ty) {
+                        Some(inner) => (inner, true),
+                        None => (&*captured.ty, false),
+                    };
+                    let wrap = |value: TokenStream| {
+                        if is_optional {
+                            quote!( ::std::option::Option::Some(#value) )
+                        } else {
+                            value
+                        }
+                    };
+
+                    // Make sure an explicitly injected argument is specified as a reference.
+                    if injected.is_some() && !matches!(ty, syn::Type::Reference(_)) {
+                        return Err(syn::Error::new(
+                            captured.ty.span(),
+                            format!(
+                                "injected arguments need to be specified as a reference.\nDid you mean &{}?",
+                                quote!(#ty),
+                            ),
+                        ));
+                    }
                     // Check for executor arguments.
-                    if util::type_is_identifier_ref(&captured.ty, "Executor") {
-                        resolve_parts.push(quote!(let #arg_ident = executor;));
+                    else if injected == Some(Injection::Executor)
+                        || util::type_is_identifier_ref(ty, "Executor")
+                    {
+                        let value = wrap(quote!(executor));
+                        resolve_parts.push(quote!( let #arg_ident = #value; ));
                     }
                     // Make sure executor is specified as a reference.
-                    else if util::type_is_identifier(&captured.ty, "Executor") {
+                    else if util::type_is_identifier(ty, "Executor") {
                         return Err(syn::Error::new(
                             captured.ty.span(),
                             "to access the Executor, you need to specify the type as a reference.\nDid you mean &Executor?"
                         ));
                     }
                     // Check for context arg.
-                    else if context_type
-                        .clone()
-                        .map(|ctx| util::type_is_ref_of(&captured.ty, ctx))
-                        .unwrap_or(false)
+                    else if injected == Some(Injection::Context)
+                        || context_type
+                            .map(|ctx| util::type_is_ref_of(ty, ctx))
+                            .unwrap_or(false)
                     {
-                        resolve_parts.push(quote!( let #arg_ident = executor.context(); ));
+                        let value = wrap(quote!(executor.context()));
+                        resolve_parts.push(quote!( let #arg_ident = #value; ));
                     }
                     // Make sure the user does not specify the Context
                     //  without a reference. (&Context)
-                    else if context_type
-                        .clone()
-                        .map(|ctx| ctx.inner() == &*captured.ty)
-                        .unwrap_or(false)
-                    {
+                    else if context_type.map(|ctx| ctx.inner() == ty).unwrap_or(false) {
                         return Err(syn::Error::new(
                             captured.ty.span(),
-                            format!("to access the context, you need to specify the type as a reference.\nDid you mean &{}?", quote!(captured.ty)),
+                            format!("to access the context, you need to specify the type as a reference.\nDid you mean &{}?", quote!(#ty)),
                         ));
                     } else {
                         let (tokens, ty) = f(captured, arg_ident, is_mut)?;
@@ -184,3 +209,43 @@ impl ImplBlock {
         })
     }
 }
+
+/// Value injected into a resolver argument instead of being taken from the GraphQL arguments.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Injection {
+    /// `#[graphql(context)]`
+    Context,
+    /// `#[graphql(executor)]`
+    Executor,
+}
+
+/// Parses the `#[graphql(context)]` or `#[graphql(executor)]` attribute of a resolver argument,
+/// marking it explicitly as the injected value, regardless of its name and type.
+fn parse_injection_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<Injection>> {
+    let mut injection = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("graphql")) {
+        let ident = attr.parse_args::<syn::Ident>().map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "expected `#[graphql(context)]` or `#[graphql(executor)]`",
+            )
+        })?;
+        let parsed = match ident.to_string().as_str() {
+            "context" | "Context" => Injection::Context,
+            "executor" | "Executor" => Injection::Executor,
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "unknown argument attribute, expected `context` or `executor`",
+                ))
+            }
+        };
+        if injection.replace(parsed).is_some() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "argument can't be injected more than once",
+            ));
+        }
+    }
+    Ok(injection)
+}
@@ -0,0 +1,148 @@
+use crate::{ConnectionConfig, ConnectionErrorPayload, Reaction, Schema, ServerMessage};
+use juniper::futures::{
+    channel::oneshot,
+    future::{self, Either},
+    stream::{self, BoxStream, StreamExt},
+    task::{Poll, Waker},
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// OverflowPolicy decides what happens to the events of an operation when its buffer is full
+/// because the client is reading them too slowly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The oldest buffered event is dropped to make room for the new one.
+    DropOldest,
+    /// The new event is dropped.
+    DropNewest,
+    /// The buffered events are dropped and the connection is closed with a connection error.
+    Disconnect,
+}
+
+/// SubscriptionLag describes an operation whose events are produced faster than the client reads
+/// them. It is passed to the callback given to `ConnectionConfig::with_lag_callback` whenever the
+/// operation's buffer overflows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubscriptionLag {
+    /// The id of the operation.
+    pub id: String,
+    /// The number of events that were waiting to be read by the client when the buffer
+    /// overflowed.
+    pub buffered: usize,
+    /// The total number of events of the operation dropped so far.
+    pub dropped: u64,
+    /// The policy that was applied to the overflowing event.
+    pub policy: OverflowPolicy,
+}
+
+/// Buffer holds the reactions produced by an operation until the client reads them.
+struct Buffer<S: Schema> {
+    reactions: VecDeque<Reaction<S>>,
+    dropped: u64,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+/// Drives the operation's `stream` in the background, buffering up to `capacity` reactions
+/// according to the configured `OverflowPolicy`, and returns the stream of buffered reactions.
+/// The operation stops being driven once the returned stream is dropped.
+pub(crate) fn buffered<S: Schema>(
+    id: String,
+    config: Arc<ConnectionConfig<S::Context, S::ScalarValue>>,
+    stream: BoxStream<'static, Reaction<S>>,
+) -> BoxStream<'static, Reaction<S>> {
+    let buffer = Arc::new(Mutex::new(Buffer {
+        reactions: VecDeque::new(),
+        dropped: 0,
+        done: false,
+        waker: None,
+    }));
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+
+    tokio::spawn({
+        let buffer = buffer.clone();
+        async move {
+            let (capacity, policy) = config.subscription_buffer;
+            let mut stream = stream;
+            let mut stop_rx = stop_rx;
+            loop {
+                let reaction = match future::select(stop_rx, stream.next()).await {
+                    Either::Left(_) => return,
+                    Either::Right((reaction, rx)) => {
+                        stop_rx = rx;
+                        reaction
+                    }
+                };
+
+                let mut buffer = buffer.lock().unwrap();
+                match reaction {
+                    Some(reaction) if buffer.reactions.len() < capacity => {
+                        buffer.reactions.push_back(reaction);
+                    }
+                    Some(reaction) => {
+                        let buffered = buffer.reactions.len();
+                        buffer.dropped += 1;
+                        match policy {
+                            OverflowPolicy::DropOldest => {
+                                buffer.reactions.pop_front();
+                                buffer.reactions.push_back(reaction);
+                            }
+                            OverflowPolicy::DropNewest => {}
+                            OverflowPolicy::Disconnect => {
+                                buffer.dropped += buffer.reactions.len() as u64;
+                                buffer.reactions.clear();
+                                buffer.reactions.extend(vec![
+                                    Reaction::ServerMessage(ServerMessage::ConnectionError {
+                                        payload: ConnectionErrorPayload {
+                                            message: "Subscription lagged too far behind."
+                                                .to_string(),
+                                        },
+                                    }),
+                                    Reaction::EndStream,
+                                ]);
+                                buffer.done = true;
+                            }
+                        }
+                        if let Some(callback) = &config.lag_callback {
+                            callback(
+                                &config.context,
+                                &SubscriptionLag {
+                                    id: id.clone(),
+                                    buffered,
+                                    dropped: buffer.dropped,
+                                    policy,
+                                },
+                            );
+                        }
+                    }
+                    None => buffer.done = true,
+                }
+                if let Some(waker) = buffer.waker.take() {
+                    waker.wake();
+                }
+                if buffer.done {
+                    return;
+                }
+            }
+        }
+    });
+
+    stream::poll_fn(move |cx| {
+        // Keep the sender alive for as long as the client may read the operation.
+        let _ = &stop_tx;
+
+        let mut buffer = buffer.lock().unwrap();
+        match buffer.reactions.pop_front() {
+            Some(reaction) => Poll::Ready(Some(reaction)),
+            None if buffer.done => Poll::Ready(None),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    })
+    .boxed()
+}
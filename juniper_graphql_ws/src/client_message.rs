@@ -16,6 +16,10 @@ pub struct StartPayload<S: ScalarValue> {
 
     /// The optional operation name (required if the document contains multiple operations).
     pub operation_name: Option<String>,
+
+    /// The optional id of the last subscription event received by the client. If the connection
+    /// has a `ReplayBuffer`, the events that followed it are sent before the new ones.
+    pub last_event_id: Option<String>,
 }
 
 /// ClientMessage defines the message types that clients can send.
@@ -86,6 +90,7 @@ mod test {
                         .cloned()
                         .collect(),
                     operation_name: Some("MyQuery".to_string()),
+                    last_event_id: Some("1".to_string()),
                 },
             },
             serde_json::from_str(
@@ -94,7 +99,8 @@ mod test {
                 "variables": {
                     "foo": "bar"
                 },
-                "operationName": "MyQuery"
+                "operationName": "MyQuery",
+                "lastEventId": "1"
             }}"##
             )
             .unwrap(),
@@ -107,6 +113,7 @@ mod test {
                     query: "query MyQuery { __typename }".to_string(),
                     variables: Variables::default(),
                     operation_name: None,
+                    last_event_id: None,
                 },
             },
             serde_json::from_str(
@@ -139,6 +146,7 @@ mod test {
             query: "query".into(),
             variables: Variables::default(),
             operation_name: None,
+            last_event_id: None,
         };
 
         assert_eq!(expected, payload);
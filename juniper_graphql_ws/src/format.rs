@@ -0,0 +1,315 @@
+use crate::{ClientMessage, ServerMessage};
+use juniper::ScalarValue;
+use std::{error::Error, fmt};
+
+/// Frame is a WebSocket message carrying an encoded protocol message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+    /// A text frame, used by textual formats such as JSON.
+    Text(String),
+    /// A binary frame, used by binary formats such as MessagePack and CBOR.
+    Binary(Vec<u8>),
+}
+
+/// MessageFormat defines how protocol messages are encoded into WebSocket frames. Integrations
+/// use it to encode the `ServerMessage`s of a `Connection` and to decode the frames sent by the
+/// client into `ClientMessage`s. Implementations are provided by `Format`, but any serialization
+/// can be plugged in.
+pub trait MessageFormat<S: ScalarValue> {
+    /// The error that is returned when a message cannot be encoded or decoded.
+    type Error: Error;
+
+    /// Encodes a message sent by the server.
+    fn encode(&self, msg: &ServerMessage<S>) -> Result<Frame, Self::Error>;
+
+    /// Decodes a message sent by the client.
+    fn decode(&self, frame: &Frame) -> Result<ClientMessage<S>, Self::Error>;
+}
+
+/// Format is a serialization of the protocol messages, negotiated with the client through the
+/// WebSocket subprotocol. JSON is always available, while binary formats are enabled by the
+/// `msgpack` and `cbor` features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// JSON text frames, negotiated with the `graphql-ws` subprotocol.
+    Json,
+    /// MessagePack binary frames, negotiated with the `graphql-ws+msgpack` subprotocol.
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    /// CBOR binary frames, negotiated with the `graphql-ws+cbor` subprotocol.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+impl Format {
+    /// All the formats available, in order of preference.
+    pub const ALL: &'static [Format] = &[
+        #[cfg(feature = "msgpack")]
+        Self::MessagePack,
+        #[cfg(feature = "cbor")]
+        Self::Cbor,
+        Self::Json,
+    ];
+
+    /// Returns the WebSocket subprotocol that negotiates this format.
+    pub fn subprotocol(self) -> &'static str {
+        match self {
+            Self::Json => "graphql-ws",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "graphql-ws+msgpack",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "graphql-ws+cbor",
+        }
+    }
+
+    /// Returns the format negotiated by a WebSocket subprotocol, if it is available.
+    pub fn from_subprotocol(subprotocol: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|f| f.subprotocol() == subprotocol.trim())
+    }
+
+    /// Picks the preferred format among the subprotocols requested by the client, typically the
+    /// values of its `Sec-WebSocket-Protocol` header. The subprotocol of the returned format
+    /// should be sent back to the client to complete the negotiation.
+    pub fn negotiate<'a, I>(subprotocols: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let requested: Vec<_> = subprotocols
+            .into_iter()
+            .filter_map(Self::from_subprotocol)
+            .collect();
+        Self::ALL.iter().copied().find(|f| requested.contains(f))
+    }
+}
+
+impl<S: ScalarValue> MessageFormat<S> for Format {
+    type Error = FormatError;
+
+    fn encode(&self, msg: &ServerMessage<S>) -> Result<Frame, FormatError> {
+        match self {
+            Self::Json => Ok(Frame::Text(serde_json::to_string(msg)?)),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => Ok(Frame::Binary(rmp_serde::to_vec_named(msg)?)),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => Ok(Frame::Binary(serde_cbor::to_vec(msg)?)),
+        }
+    }
+
+    fn decode(&self, frame: &Frame) -> Result<ClientMessage<S>, FormatError> {
+        match (self, frame) {
+            (Self::Json, Frame::Text(text)) => Ok(serde_json::from_str(text)?),
+            (Self::Json, Frame::Binary(bytes)) => Ok(serde_json::from_slice(bytes)?),
+            #[cfg(feature = "msgpack")]
+            (Self::MessagePack, Frame::Binary(bytes)) => Ok(rmp_serde::from_slice(bytes)?),
+            #[cfg(feature = "cbor")]
+            (Self::Cbor, Frame::Binary(bytes)) => Ok(serde_cbor::from_slice(bytes)?),
+            #[allow(unreachable_patterns)]
+            (_, Frame::Text(_)) => Err(FormatError::UnexpectedText),
+        }
+    }
+}
+
+/// FormatError is the error that occurs when a `Format` fails to encode or decode a message.
+#[derive(Debug)]
+pub enum FormatError {
+    /// A JSON error.
+    Json(serde_json::Error),
+    /// A MessagePack encoding error.
+    #[cfg(feature = "msgpack")]
+    MessagePackEncode(rmp_serde::encode::Error),
+    /// A MessagePack decoding error.
+    #[cfg(feature = "msgpack")]
+    MessagePackDecode(rmp_serde::decode::Error),
+    /// A CBOR error.
+    #[cfg(feature = "cbor")]
+    Cbor(serde_cbor::Error),
+    /// A text frame was received by a binary format.
+    UnexpectedText,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "json error: {}", e),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePackEncode(e) => write!(f, "msgpack error: {}", e),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePackDecode(e) => write!(f, "msgpack error: {}", e),
+            #[cfg(feature = "cbor")]
+            Self::Cbor(e) => write!(f, "cbor error: {}", e),
+            Self::UnexpectedText => write!(f, "unexpected text frame for a binary format"),
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+impl From<serde_json::Error> for FormatError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::encode::Error> for FormatError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        Self::MessagePackEncode(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::decode::Error> for FormatError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        Self::MessagePackDecode(err)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl From<serde_cbor::Error> for FormatError {
+    fn from(err: serde_cbor::Error) -> Self {
+        Self::Cbor(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StartPayload;
+    use juniper::{DefaultScalarValue, InputValue, Variables};
+
+    type ClientMessage = super::ClientMessage<DefaultScalarValue>;
+    type ServerMessage = super::ServerMessage<DefaultScalarValue>;
+
+    fn start_message() -> ClientMessage {
+        ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "query MyQuery { __typename }".to_string(),
+                variables: [("foo".to_string(), InputValue::scalar("bar"))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+                operation_name: Some("MyQuery".to_string()),
+                last_event_id: None,
+            },
+        }
+    }
+
+    fn start_json() -> serde_json::Value {
+        serde_json::json!({
+            "type": "start",
+            "id": "foo",
+            "payload": {
+                "query": "query MyQuery { __typename }",
+                "variables": {"foo": "bar"},
+                "operationName": "MyQuery",
+            },
+        })
+    }
+
+    #[test]
+    fn test_negotiation() {
+        assert_eq!(Format::from_subprotocol("graphql-ws"), Some(Format::Json));
+        assert_eq!(Format::from_subprotocol("graphql-transport-ws"), None);
+        assert_eq!(
+            Format::negotiate(vec!["foo", "graphql-ws"]),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::negotiate(vec!["foo"]), None);
+        assert_eq!(Format::Json.subprotocol(), "graphql-ws");
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            Format::Json.encode(&ServerMessage::ConnectionAck).unwrap(),
+            Frame::Text(r##"{"type":"connection_ack"}"##.to_string()),
+        );
+        assert_eq!(
+            Format::Json
+                .decode(&Frame::Text(start_json().to_string()))
+                .unwrap(),
+            start_message(),
+        );
+        assert_eq!(
+            Format::Json
+                .decode(&Frame::Binary(start_json().to_string().into_bytes()))
+                .unwrap(),
+            start_message(),
+        );
+        assert_eq!(
+            Format::Json
+                .decode(&Frame::Text(r##"{"type": "connection_init"}"##.to_string()))
+                .unwrap(),
+            ClientMessage::ConnectionInit {
+                payload: Variables::default(),
+            },
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack() {
+        assert_eq!(
+            Format::negotiate(vec!["graphql-ws", "graphql-ws+msgpack"]),
+            Some(Format::MessagePack)
+        );
+
+        let frame = Format::MessagePack
+            .encode(&ServerMessage::ConnectionAck)
+            .unwrap();
+        match frame {
+            Frame::Binary(bytes) => assert_eq!(
+                rmp_serde::from_slice::<serde_json::Value>(&bytes).unwrap(),
+                serde_json::json!({"type": "connection_ack"}),
+            ),
+            f => panic!("expected binary frame, got: {:?}", f),
+        }
+
+        let bytes = rmp_serde::to_vec_named(&start_json()).unwrap();
+        assert_eq!(
+            Format::MessagePack.decode(&Frame::Binary(bytes)).unwrap(),
+            start_message(),
+        );
+
+        let decoded: Result<ClientMessage, _> =
+            Format::MessagePack.decode(&Frame::Text(start_json().to_string()));
+        match decoded {
+            Err(FormatError::UnexpectedText) => {}
+            r => panic!("expected unexpected text error, got: {:?}", r),
+        }
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_cbor() {
+        assert_eq!(
+            Format::from_subprotocol("graphql-ws+cbor"),
+            Some(Format::Cbor)
+        );
+
+        let frame = Format::Cbor.encode(&ServerMessage::ConnectionAck).unwrap();
+        match frame {
+            Frame::Binary(bytes) => assert_eq!(
+                serde_cbor::from_slice::<serde_json::Value>(&bytes).unwrap(),
+                serde_json::json!({"type": "connection_ack"}),
+            ),
+            f => panic!("expected binary frame, got: {:?}", f),
+        }
+
+        let bytes = serde_cbor::to_vec(&start_json()).unwrap();
+        assert_eq!(
+            Format::Cbor.decode(&Frame::Binary(bytes)).unwrap(),
+            start_message(),
+        );
+    }
+}
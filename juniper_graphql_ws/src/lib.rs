@@ -18,9 +18,22 @@ pub use client_message::*;
 mod server_message;
 pub use server_message::*;
 
+mod format;
+pub use format::*;
+
 mod schema;
 pub use schema::*;
 
+mod limits;
+pub use limits::LimitExceeded;
+use limits::{MessageRate, SubscriptionGuard};
+
+mod replay;
+pub use replay::ReplayBuffer;
+
+mod buffer;
+pub use buffer::{OverflowPolicy, SubscriptionLag};
+
 mod utils;
 
 use juniper::{
@@ -31,39 +44,54 @@ use juniper::{
         task::{Context, Poll, Waker},
         Sink, Stream,
     },
-    GraphQLError, RuleError, ScalarValue, Variables,
+    DefaultScalarValue, GraphQLError, RuleError, ScalarValue, Variables,
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     convert::{Infallible, TryInto},
     error::Error,
     marker::PhantomPinned,
     pin::Pin,
-    sync::Arc,
+    sync::{atomic::AtomicUsize, Arc},
     time::Duration,
 };
 
 struct ExecutionParams<S: Schema> {
     start_payload: StartPayload<S::ScalarValue>,
-    config: Arc<ConnectionConfig<S::Context>>,
+    config: Arc<ConnectionConfig<S::Context, S::ScalarValue>>,
+    subscriptions: Arc<AtomicUsize>,
     schema: S,
 }
 
 /// ConnectionConfig is used to configure the connection once the client sends the ConnectionInit
 /// message.
-pub struct ConnectionConfig<CtxT> {
+pub struct ConnectionConfig<CtxT, S: ScalarValue = DefaultScalarValue> {
     context: CtxT,
     max_in_flight_operations: usize,
+    max_subscriptions: usize,
+    max_messages_per_second: u32,
+    max_payload_size: usize,
     keep_alive_interval: Duration,
+    limit_callback: Option<Box<dyn Fn(&CtxT, &LimitExceeded) + Send + Sync>>,
+    replay_buffer: Option<Arc<dyn ReplayBuffer<S>>>,
+    subscription_buffer: (usize, OverflowPolicy),
+    lag_callback: Option<Box<dyn Fn(&CtxT, &SubscriptionLag) + Send + Sync>>,
 }
 
-impl<CtxT> ConnectionConfig<CtxT> {
+impl<CtxT, S: ScalarValue> ConnectionConfig<CtxT, S> {
     /// Constructs the configuration required for a connection to be accepted.
     pub fn new(context: CtxT) -> Self {
         Self {
             context,
             max_in_flight_operations: 0,
+            max_subscriptions: 0,
+            max_messages_per_second: 0,
+            max_payload_size: 0,
             keep_alive_interval: Duration::from_secs(15),
+            limit_callback: None,
+            replay_buffer: None,
+            subscription_buffer: (0, OverflowPolicy::DropOldest),
+            lag_callback: None,
         }
     }
 
@@ -75,6 +103,70 @@ impl<CtxT> ConnectionConfig<CtxT> {
         self
     }
 
+    /// Specifies the maximum number of subscriptions that a connection can have at once. If this
+    /// number is exceeded, attempting to start more will result in an error. Unlike
+    /// `with_max_in_flight_operations`, queries and mutations are not counted. By default, there
+    /// is no limit to subscriptions.
+    pub fn with_max_subscriptions(mut self, max: usize) -> Self {
+        self.max_subscriptions = max;
+        self
+    }
+
+    /// Specifies the maximum number of messages that a client can send per second. Start messages
+    /// exceeding this rate will result in an error, while other messages are still handled. By
+    /// default, there is no limit to the message rate.
+    pub fn with_max_messages_per_second(mut self, max: u32) -> Self {
+        self.max_messages_per_second = max;
+        self
+    }
+
+    /// Specifies the maximum size in bytes of the query and variables of a start message. The size
+    /// of the variables is approximated from their values. If this size is exceeded, the operation
+    /// will not be started and will result in an error. By default, there is no limit to the
+    /// payload size.
+    pub fn with_max_payload_size(mut self, max: usize) -> Self {
+        self.max_payload_size = max;
+        self
+    }
+
+    /// Specifies a callback to invoke with the connection's context whenever one of the limits of
+    /// this configuration is exceeded. This can be used to log abusive clients or to keep track of
+    /// them.
+    pub fn with_limit_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CtxT, &LimitExceeded) + Send + Sync + 'static,
+    {
+        self.limit_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Specifies the buffer in which to store subscription events, enabling clients to resume
+    /// subscriptions by sending the `lastEventId` they received. By default, subscriptions are
+    /// not resumable and events are sent without ids.
+    pub fn with_replay_buffer(mut self, buffer: Arc<dyn ReplayBuffer<S>>) -> Self {
+        self.replay_buffer = Some(buffer);
+        self
+    }
+
+    /// Specifies how many events of each operation can be buffered while the client is reading
+    /// them, and what to do with the events that don't fit. With a buffer, operations are driven
+    /// in the background regardless of how fast the client reads. By default, there is no buffer
+    /// and operations only produce events as fast as the client reads them.
+    pub fn with_subscription_buffer(mut self, capacity: usize, policy: OverflowPolicy) -> Self {
+        self.subscription_buffer = (capacity, policy);
+        self
+    }
+
+    /// Specifies a callback to invoke with the connection's context whenever the buffer of an
+    /// operation overflows. This can be used to report lagging clients to metrics or logs.
+    pub fn with_lag_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CtxT, &SubscriptionLag) + Send + Sync + 'static,
+    {
+        self.lag_callback = Some(Box::new(callback));
+        self
+    }
+
     /// Specifies the interval at which to send keep-alives. Specifying a zero duration will
     /// disable keep-alives. By default, keep-alives are sent every 15 seconds.
     pub fn with_keep_alive_interval(mut self, interval: Duration) -> Self {
@@ -83,7 +175,25 @@ impl<CtxT> ConnectionConfig<CtxT> {
     }
 }
 
-impl<S: ScalarValue, CtxT: Unpin + Send + 'static> Init<S, CtxT> for ConnectionConfig<CtxT> {
+impl<CtxT, S: ScalarValue> ConnectionConfig<CtxT, S> {
+    /// Notifies the limit callback, if any, and returns the error rejecting the operation.
+    fn reject<T>(&self, id: String, message: &str, limit: LimitExceeded) -> Reaction<T>
+    where
+        T: Schema<ScalarValue = S>,
+    {
+        if let Some(callback) = &self.limit_callback {
+            callback(&self.context, &limit);
+        }
+        Reaction::ServerMessage(ServerMessage::Error {
+            id,
+            payload: GraphQLError::ValidationError(vec![RuleError::new(message, &[])]).into(),
+        })
+    }
+}
+
+impl<S: ScalarValue + 'static, CtxT: Unpin + Send + 'static> Init<S, CtxT>
+    for ConnectionConfig<CtxT, S>
+{
     type Error = Infallible;
     type Future = future::Ready<Result<Self, Self::Error>>;
 
@@ -113,7 +223,7 @@ pub trait Init<S: ScalarValue, CtxT>: Unpin + 'static {
     type Error: Error;
 
     /// The future configuration type.
-    type Future: Future<Output = Result<ConnectionConfig<CtxT>, Self::Error>> + Send + 'static;
+    type Future: Future<Output = Result<ConnectionConfig<CtxT, S>, Self::Error>> + Send + 'static;
 
     /// Returns a future for the configuration to use.
     fn init(self, params: Variables<S>) -> Self::Future;
@@ -123,7 +233,7 @@ impl<F, S, CtxT, Fut, E> Init<S, CtxT> for F
 where
     S: ScalarValue,
     F: FnOnce(Variables<S>) -> Fut + Unpin + 'static,
-    Fut: Future<Output = Result<ConnectionConfig<CtxT>, E>> + Send + 'static,
+    Fut: Future<Output = Result<ConnectionConfig<CtxT, S>, E>> + Send + 'static,
     E: Error,
 {
     type Error = E;
@@ -139,8 +249,10 @@ enum ConnectionState<S: Schema, I: Init<S::ScalarValue, S::Context>> {
     PreInit { init: I, schema: S },
     /// Active is the state after a ConnectionInit message has been accepted.
     Active {
-        config: Arc<ConnectionConfig<S::Context>>,
+        config: Arc<ConnectionConfig<S::Context, S::ScalarValue>>,
         stoppers: HashMap<String, oneshot::Sender<()>>,
+        subscriptions: Arc<AtomicUsize>,
+        rate: MessageRate,
         schema: S,
     },
     /// Terminated is the state after a ConnectionInit message has been rejected.
@@ -191,6 +303,8 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                             Self::Active {
                                 config: Arc::new(config),
                                 stoppers: HashMap::new(),
+                                subscriptions: Arc::new(AtomicUsize::new(0)),
+                                rate: MessageRate::new(),
                                 schema,
                             },
                             s,
@@ -214,14 +328,53 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
             Self::Active {
                 config,
                 mut stoppers,
+                subscriptions,
+                mut rate,
                 schema,
             } => {
+                let within_rate = config.max_messages_per_second == 0
+                    || rate.record(config.max_messages_per_second);
+
                 let reactions = match msg {
                     ClientMessage::Start { id, payload } => {
+                        let payload_size = if config.max_payload_size > 0 {
+                            limits::payload_size(&payload.query, &payload.variables)
+                        } else {
+                            0
+                        };
+
                         if stoppers.contains_key(&id) {
                             // We already have an operation with this id, so we can't start a new
                             // one.
                             stream::empty().boxed()
+                        } else if !within_rate {
+                            // Too many messages. Just send back a validation error.
+                            stream::iter(vec![
+                                config.reject(
+                                    id.clone(),
+                                    "Too many messages.",
+                                    LimitExceeded::MessageRate {
+                                        max: config.max_messages_per_second,
+                                    },
+                                ),
+                                Reaction::ServerMessage(ServerMessage::Complete { id }),
+                            ])
+                            .boxed()
+                        } else if payload_size > config.max_payload_size {
+                            // The payload is too large. Just send back a validation error.
+                            stream::iter(vec![
+                                config.reject(
+                                    id.clone(),
+                                    "Payload too large.",
+                                    LimitExceeded::PayloadSize {
+                                        id: id.clone(),
+                                        size: payload_size,
+                                        max: config.max_payload_size,
+                                    },
+                                ),
+                                Reaction::ServerMessage(ServerMessage::Complete { id }),
+                            ])
+                            .boxed()
                         } else {
                             // Go ahead and prune canceled stoppers before adding a new one.
                             stoppers.retain(|_, tx| !tx.is_canceled());
@@ -231,13 +384,14 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                             {
                                 // Too many in-flight operations. Just send back a validation error.
                                 stream::iter(vec![
-                                    Reaction::ServerMessage(ServerMessage::Error {
-                                        id: id.clone(),
-                                        payload: GraphQLError::ValidationError(vec![
-                                            RuleError::new("Too many in-flight operations.", &[]),
-                                        ])
-                                        .into(),
-                                    }),
+                                    config.reject(
+                                        id.clone(),
+                                        "Too many in-flight operations.",
+                                        LimitExceeded::InFlightOperations {
+                                            id: id.clone(),
+                                            max: config.max_in_flight_operations,
+                                        },
+                                    ),
                                     Reaction::ServerMessage(ServerMessage::Complete { id }),
                                 ])
                                 .boxed()
@@ -253,15 +407,24 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                                     ExecutionParams {
                                         start_payload: payload,
                                         config: config.clone(),
+                                        subscriptions: subscriptions.clone(),
                                         schema: schema.clone(),
                                     },
                                 )
                                 .into_stream()
-                                .flatten();
+                                .flatten()
+                                .boxed();
+
+                                // Drive the operation in the background if it is buffered.
+                                let s = if config.subscription_buffer.0 > 0 {
+                                    buffer::buffered(id.clone(), config.clone(), s)
+                                } else {
+                                    s
+                                };
 
                                 // Combine this with our oneshot channel so that the stream ends if the
                                 // oneshot is ever fired.
-                                let s = stream::unfold((rx, s.boxed()), |(rx, mut s)| async move {
+                                let s = stream::unfold((rx, s), |(rx, mut s)| async move {
                                     let next = match future::select(rx, s.next()).await {
                                         Either::Left(_) => None,
                                         Either::Right((r, rx)) => r.map(|r| (r, rx)),
@@ -289,6 +452,8 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                     Self::Active {
                         config,
                         stoppers,
+                        subscriptions,
+                        rate,
                         schema,
                     },
                     reactions,
@@ -319,10 +484,14 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
         )
         .await
         {
-            Ok((data, errors)) => {
+            Ok(output) => {
                 return Reaction::ServerMessage(ServerMessage::Data {
                     id: id.clone(),
-                    payload: DataPayload { data, errors },
+                    payload: DataPayload {
+                        data: output.data,
+                        errors: output.errors,
+                        event_id: None,
+                    },
                 })
                 .to_stream();
             }
@@ -337,8 +506,21 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
             }
         }
 
-        // Try to execute as a subscription.
-        SubscriptionStart::new(id, params.clone()).boxed()
+        // Try to execute as a subscription, if the connection has room for one more.
+        match SubscriptionGuard::acquire(&params.subscriptions, params.config.max_subscriptions) {
+            Some(guard) => SubscriptionStart::new(id, params.clone(), guard).boxed(),
+            None => params
+                .config
+                .reject(
+                    id.clone(),
+                    "Too many subscriptions.",
+                    LimitExceeded::Subscriptions {
+                        id,
+                        max: params.config.max_subscriptions,
+                    },
+                )
+                .to_stream(),
+        }
     }
 }
 
@@ -376,9 +558,11 @@ enum SubscriptionStartState<S: Schema> {
         >,
     },
     /// Streaming is the state after we've successfully obtained the event stream for the
-    /// subscription. In this state, we're just forwarding events back to the client.
+    /// subscription. In this state, we're replaying the events the client missed, if any, then
+    /// forwarding events back to the client.
     Streaming {
         id: String,
+        replayed: VecDeque<DataPayload<S::ScalarValue>>,
         stream: juniper_subscriptions::Connection<'static, S::ScalarValue>,
     },
     /// Terminated is the state once we're all done.
@@ -389,14 +573,20 @@ enum SubscriptionStartState<S: Schema> {
 struct SubscriptionStart<S: Schema> {
     params: Arc<ExecutionParams<S>>,
     state: SubscriptionStartState<S>,
+    _guard: SubscriptionGuard,
     _marker: PhantomPinned,
 }
 
 impl<S: Schema> SubscriptionStart<S> {
-    fn new(id: String, params: Arc<ExecutionParams<S>>) -> Pin<Box<Self>> {
+    fn new(
+        id: String,
+        params: Arc<ExecutionParams<S>>,
+        guard: SubscriptionGuard,
+    ) -> Pin<Box<Self>> {
         Box::pin(Self {
             params,
             state: SubscriptionStartState::Init { id },
+            _guard: guard,
             _marker: PhantomPinned,
         })
     }
@@ -435,9 +625,7 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                                 &(*params).config.context,
                             )
                         }
-                        .map_ok(|(stream, errors)| {
-                            juniper_subscriptions::Connection::from_stream(stream, errors)
-                        })
+                        .map_ok(juniper_subscriptions::Connection::from_output)
                         .boxed(),
                     };
                 }
@@ -447,8 +635,24 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                 } => match future.as_mut().poll(cx) {
                     Poll::Ready(r) => match r {
                         Ok(stream) => {
+                            let start_payload = &params.start_payload;
+                            let replayed = match (
+                                &params.config.replay_buffer,
+                                &start_payload.last_event_id,
+                            ) {
+                                (Some(buffer), Some(last_event_id)) => buffer
+                                    .replay(start_payload, last_event_id)
+                                    .into_iter()
+                                    .map(|(event_id, event)| DataPayload {
+                                        event_id: Some(event_id),
+                                        ..event
+                                    })
+                                    .collect(),
+                                _ => VecDeque::new(),
+                            };
                             *state = SubscriptionStartState::Streaming {
                                 id: id.clone(),
+                                replayed,
                                 stream,
                             }
                         }
@@ -468,23 +672,40 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                 },
                 SubscriptionStartState::Streaming {
                     ref id,
+                    ref mut replayed,
                     ref mut stream,
-                } => match Pin::new(stream).poll_next(cx) {
-                    Poll::Ready(Some(output)) => {
+                } => {
+                    if let Some(payload) = replayed.pop_front() {
                         return Poll::Ready(Some(Reaction::ServerMessage(ServerMessage::Data {
                             id: id.clone(),
-                            payload: DataPayload {
-                                data: output.data,
-                                errors: output.errors,
-                            },
+                            payload,
                         })));
                     }
-                    Poll::Ready(None) => {
-                        *state = SubscriptionStartState::Terminated;
-                        return Poll::Ready(None);
+                    match Pin::new(stream).poll_next(cx) {
+                        Poll::Ready(Some(output)) => {
+                            let mut payload = DataPayload {
+                                data: output.data,
+                                errors: output.errors,
+                                event_id: None,
+                            };
+                            if let Some(buffer) = &params.config.replay_buffer {
+                                payload.event_id =
+                                    Some(buffer.store(&params.start_payload, &payload));
+                            }
+                            return Poll::Ready(Some(Reaction::ServerMessage(
+                                ServerMessage::Data {
+                                    id: id.clone(),
+                                    payload,
+                                },
+                            )));
+                        }
+                        Poll::Ready(None) => {
+                            *state = SubscriptionStartState::Terminated;
+                            return Poll::Ready(None);
+                        }
+                        Poll::Pending => return Poll::Pending,
                     }
-                    Poll::Pending => return Poll::Pending,
-                },
+                }
                 SubscriptionStartState::Terminated => return Poll::Ready(None),
             }
         }
@@ -648,7 +869,7 @@ mod test {
         parser::{ParseError, Spanning, Token},
         DefaultScalarValue, EmptyMutation, FieldError, FieldResult, InputValue, RootNode, Value,
     };
-    use std::{convert::Infallible, io};
+    use std::{convert::Infallible, io, sync::Mutex};
 
     struct Context(i32);
 
@@ -685,6 +906,17 @@ mod test {
                 .boxed()
         }
 
+        /// numbers emits the numbers from 1 to 5 at once, then never emits anything else.
+        async fn numbers(context: &Context) -> BoxStream<'static, FieldResult<i32>> {
+            stream::iter((1..=5).map(Ok))
+                .chain(
+                    tokio::time::delay_for(Duration::from_secs(10000))
+                        .map(|_| unreachable!())
+                        .into_stream(),
+                )
+                .boxed()
+        }
+
         /// error emits an error once, then never emits anything else.
         async fn error(context: &Context) -> BoxStream<'static, FieldResult<i32>> {
             stream::once(future::ready(Err(FieldError::new(
@@ -728,6 +960,7 @@ mod test {
                 query: "{context}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -744,6 +977,7 @@ mod test {
                             .collect()
                     ),
                     errors: vec![],
+                    event_id: None,
                 },
             },
             conn.next().await.unwrap()
@@ -778,6 +1012,7 @@ mod test {
                 query: "subscription Foo {context}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -789,6 +1024,7 @@ mod test {
                 payload: DataPayload {
                     data: Value::Object([("context", Value::scalar(1))].iter().cloned().collect()),
                     errors: vec![],
+                    event_id: None,
                 },
             },
             conn.next().await.unwrap()
@@ -800,6 +1036,7 @@ mod test {
                 query: "subscription Bar {context}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -811,6 +1048,7 @@ mod test {
                 payload: DataPayload {
                     data: Value::Object([("context", Value::scalar(1))].iter().cloned().collect()),
                     errors: vec![],
+                    event_id: None,
                 },
             },
             conn.next().await.unwrap()
@@ -898,6 +1136,64 @@ mod test {
                 query: "subscription Foo {never}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        conn.send(ClientMessage::Start {
+            id: "bar".to_string(),
+            payload: StartPayload {
+                query: "subscription Bar {never}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Error { id, .. } => {
+                assert_eq!(id, "bar");
+            }
+            msg @ _ => panic!("expected error, got: {:?}", msg),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_subscriptions() {
+        let exceeded = Arc::new(Mutex::new(vec![]));
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_max_subscriptions(1)
+                .with_limit_callback({
+                    let exceeded = exceeded.clone();
+                    move |ctx: &Context, limit: &LimitExceeded| {
+                        assert_eq!(ctx.0, 1);
+                        exceeded.lock().unwrap().push(limit.clone());
+                    }
+                }),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription Foo {never}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -909,6 +1205,7 @@ mod test {
                 query: "subscription Bar {never}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -920,6 +1217,459 @@ mod test {
             }
             msg @ _ => panic!("expected error, got: {:?}", msg),
         }
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "bar".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+
+        // Queries are not counted as subscriptions.
+        conn.send(ClientMessage::Start {
+            id: "baz".to_string(),
+            payload: StartPayload {
+                query: "{context}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Data { id, .. } => {
+                assert_eq!(id, "baz");
+            }
+            msg @ _ => panic!("expected data, got: {:?}", msg),
+        }
+
+        // Stopping a subscription makes room for another one.
+        conn.send(ClientMessage::Stop {
+            id: "foo".to_string(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "baz".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "foo".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+
+        conn.send(ClientMessage::Start {
+            id: "qux".to_string(),
+            payload: StartPayload {
+                query: "subscription Qux {context}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Data { id, .. } => {
+                assert_eq!(id, "qux");
+            }
+            msg @ _ => panic!("expected data, got: {:?}", msg),
+        }
+
+        assert_eq!(
+            *exceeded.lock().unwrap(),
+            vec![LimitExceeded::Subscriptions {
+                id: "bar".to_string(),
+                max: 1,
+            }],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_messages_per_second() {
+        let exceeded = Arc::new(Mutex::new(vec![]));
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_max_messages_per_second(2)
+                .with_limit_callback({
+                    let exceeded = exceeded.clone();
+                    move |_: &Context, limit: &LimitExceeded| {
+                        exceeded.lock().unwrap().push(limit.clone());
+                    }
+                }),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        for id in &["foo", "bar", "baz"] {
+            conn.send(ClientMessage::Start {
+                id: id.to_string(),
+                payload: StartPayload {
+                    query: "subscription {never}".to_string(),
+                    variables: Variables::default(),
+                    operation_name: None,
+                    last_event_id: None,
+                },
+            })
+            .await
+            .unwrap();
+        }
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Error { id, .. } => {
+                assert_eq!(id, "baz");
+            }
+            msg @ _ => panic!("expected error, got: {:?}", msg),
+        }
+
+        assert_eq!(
+            *exceeded.lock().unwrap(),
+            vec![LimitExceeded::MessageRate { max: 2 }],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_payload_size() {
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_max_payload_size(16),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "{context}".to_string(),
+                variables: [("foo".to_string(), InputValue::scalar("a long value"))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Error { id, payload } => {
+                assert_eq!(id, "foo");
+                match payload.graphql_error() {
+                    GraphQLError::ValidationError(errors) => {
+                        assert_eq!(errors[0].message(), "Payload too large.");
+                    }
+                    p @ _ => panic!("expected validation error, got: {:?}", p),
+                }
+            }
+            msg @ _ => panic!("expected error, got: {:?}", msg),
+        }
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "foo".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+
+        conn.send(ClientMessage::Start {
+            id: "bar".to_string(),
+            payload: StartPayload {
+                query: "{context}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Data { id, .. } => {
+                assert_eq!(id, "bar");
+            }
+            msg @ _ => panic!("expected data, got: {:?}", msg),
+        }
+    }
+
+    /// MemoryReplayBuffer stores the events of all subscriptions together, using their positions
+    /// as ids.
+    #[derive(Default)]
+    struct MemoryReplayBuffer {
+        events: Mutex<Vec<super::DataPayload<DefaultScalarValue>>>,
+    }
+
+    impl ReplayBuffer<DefaultScalarValue> for MemoryReplayBuffer {
+        fn store(
+            &self,
+            _payload: &super::StartPayload<DefaultScalarValue>,
+            event: &super::DataPayload<DefaultScalarValue>,
+        ) -> String {
+            let mut events = self.events.lock().unwrap();
+            events.push(event.clone());
+            events.len().to_string()
+        }
+
+        fn replay(
+            &self,
+            _payload: &super::StartPayload<DefaultScalarValue>,
+            last_event_id: &str,
+        ) -> Vec<(String, super::DataPayload<DefaultScalarValue>)> {
+            let last: usize = last_event_id.parse().unwrap();
+            self.events.lock().unwrap()[last..]
+                .iter()
+                .enumerate()
+                .map(|(i, event)| ((last + i + 1).to_string(), event.clone()))
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_subscription() {
+        let buffer = Arc::new(MemoryReplayBuffer::default());
+
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_replay_buffer(buffer.clone()),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription Foo {context}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ServerMessage::Data {
+                id: "foo".to_string(),
+                payload: DataPayload {
+                    data: Value::Object([("context", Value::scalar(1))].iter().cloned().collect()),
+                    errors: vec![],
+                    event_id: Some("1".to_string()),
+                },
+            },
+            conn.next().await.unwrap()
+        );
+
+        // An event is emitted while the client is disconnected.
+        let missed = DataPayload {
+            data: Value::Object([("context", Value::scalar(2))].iter().cloned().collect()),
+            errors: vec![],
+            event_id: None,
+        };
+        assert_eq!(
+            buffer.store(
+                &StartPayload {
+                    query: "subscription Foo {context}".to_string(),
+                    variables: Variables::default(),
+                    operation_name: None,
+                    last_event_id: None,
+                },
+                &missed,
+            ),
+            "2",
+        );
+
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(3))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_replay_buffer(buffer.clone()),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription Foo {context}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: Some("1".to_string()),
+            },
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ServerMessage::Data {
+                id: "foo".to_string(),
+                payload: DataPayload {
+                    event_id: Some("2".to_string()),
+                    ..missed
+                },
+            },
+            conn.next().await.unwrap()
+        );
+
+        assert_eq!(
+            ServerMessage::Data {
+                id: "foo".to_string(),
+                payload: DataPayload {
+                    data: Value::Object([("context", Value::scalar(3))].iter().cloned().collect()),
+                    errors: vec![],
+                    event_id: Some("3".to_string()),
+                },
+            },
+            conn.next().await.unwrap()
+        );
+    }
+
+    /// Starts the numbers subscription on a connection with a buffer of 2 events, letting it
+    /// overflow before returning the connection and the lags reported.
+    async fn overflow_numbers(
+        policy: OverflowPolicy,
+    ) -> (
+        Connection<
+            Arc<RootNode<'static, Query, EmptyMutation<Context>, Subscription>>,
+            ConnectionConfig<Context>,
+        >,
+        Arc<Mutex<Vec<SubscriptionLag>>>,
+    ) {
+        let lags = Arc::new(Mutex::new(vec![]));
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1))
+                .with_keep_alive_interval(Duration::from_secs(0))
+                .with_subscription_buffer(2, policy)
+                .with_lag_callback({
+                    let lags = lags.clone();
+                    move |_: &Context, lag: &SubscriptionLag| {
+                        lags.lock().unwrap().push(lag.clone());
+                    }
+                }),
+        );
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: Variables::default(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription {numbers}".to_string(),
+                variables: Variables::default(),
+                operation_name: None,
+                last_event_id: None,
+            },
+        })
+        .await
+        .unwrap();
+
+        // The client doesn't read while the events are produced.
+        tokio::time::delay_for(Duration::from_millis(50)).await;
+
+        (conn, lags)
+    }
+
+    fn number_data(n: i32) -> ServerMessage {
+        ServerMessage::Data {
+            id: "foo".to_string(),
+            payload: DataPayload {
+                data: Value::Object([("numbers", Value::scalar(n))].iter().cloned().collect()),
+                errors: vec![],
+                event_id: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffer_drop_oldest() {
+        let (mut conn, lags) = overflow_numbers(OverflowPolicy::DropOldest).await;
+
+        assert_eq!(number_data(4), conn.next().await.unwrap());
+        assert_eq!(number_data(5), conn.next().await.unwrap());
+
+        let lags = lags.lock().unwrap();
+        assert_eq!(lags.len(), 3);
+        assert_eq!(
+            lags[2],
+            SubscriptionLag {
+                id: "foo".to_string(),
+                buffered: 2,
+                dropped: 3,
+                policy: OverflowPolicy::DropOldest,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_buffer_drop_newest() {
+        let (mut conn, lags) = overflow_numbers(OverflowPolicy::DropNewest).await;
+
+        assert_eq!(number_data(1), conn.next().await.unwrap());
+        assert_eq!(number_data(2), conn.next().await.unwrap());
+
+        assert_eq!(lags.lock().unwrap().last().unwrap().dropped, 3);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_disconnect() {
+        let (mut conn, lags) = overflow_numbers(OverflowPolicy::Disconnect).await;
+
+        match conn.next().await.unwrap() {
+            ServerMessage::ConnectionError { .. } => {}
+            msg @ _ => panic!("expected connection error, got: {:?}", msg),
+        }
+        assert_eq!(conn.next().await, None);
+
+        assert_eq!(
+            *lags.lock().unwrap(),
+            vec![SubscriptionLag {
+                id: "foo".to_string(),
+                buffered: 2,
+                dropped: 3,
+                policy: OverflowPolicy::Disconnect,
+            }],
+        );
     }
 
     #[tokio::test]
@@ -943,6 +1693,7 @@ mod test {
                 query: "asd".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -1006,6 +1757,7 @@ mod test {
                 query: "{context}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -1024,6 +1776,7 @@ mod test {
                             .collect()
                     ),
                     errors: vec![],
+                    event_id: None,
                 },
             },
             conn.next().await.unwrap()
@@ -1051,6 +1804,7 @@ mod test {
                 query: "subscription Foo {error}".to_string(),
                 variables: Variables::default(),
                 operation_name: None,
+                last_event_id: None,
             },
         })
         .await
@@ -1059,7 +1813,7 @@ mod test {
         match conn.next().await.unwrap() {
             ServerMessage::Data {
                 id,
-                payload: DataPayload { data, errors },
+                payload: DataPayload { data, errors, .. },
             } => {
                 assert_eq!(id, "foo");
                 assert_eq!(
@@ -0,0 +1,121 @@
+use juniper::{InputValue, ScalarValue, Variables};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// LimitExceeded describes a limit of a `ConnectionConfig` that a client has hit. It is passed to
+/// the callback given to `ConnectionConfig::with_limit_callback`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LimitExceeded {
+    /// The client attempted to start more in-flight operations than allowed.
+    InFlightOperations {
+        /// The id of the rejected operation.
+        id: String,
+        /// The maximum number of in-flight operations.
+        max: usize,
+    },
+    /// The client attempted to start more subscriptions than allowed.
+    Subscriptions {
+        /// The id of the rejected subscription.
+        id: String,
+        /// The maximum number of subscriptions.
+        max: usize,
+    },
+    /// The client sent more messages within a second than allowed.
+    MessageRate {
+        /// The maximum number of messages per second.
+        max: u32,
+    },
+    /// The client sent a start message with a payload larger than allowed.
+    PayloadSize {
+        /// The id of the rejected operation.
+        id: String,
+        /// The approximate size of the payload in bytes.
+        size: usize,
+        /// The maximum payload size in bytes.
+        max: usize,
+    },
+}
+
+/// MessageRate counts the messages received within the current one-second window.
+pub(crate) struct MessageRate {
+    window_start: Instant,
+    count: u32,
+}
+
+impl MessageRate {
+    pub(crate) fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Records a message and returns whether it stays within `max` messages per second.
+    pub(crate) fn record(&mut self, max: u32) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count = self.count.saturating_add(1);
+        self.count <= max
+    }
+}
+
+/// SubscriptionGuard holds one of the subscription slots of a connection, releasing it when
+/// dropped.
+pub(crate) struct SubscriptionGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl SubscriptionGuard {
+    /// Takes a slot from `count`, unless `max` slots are already taken. A `max` of zero means
+    /// there is no limit.
+    pub(crate) fn acquire(count: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                if max > 0 && n >= max {
+                    None
+                } else {
+                    Some(n + 1)
+                }
+            })
+            .ok()
+            .map(|_| Self {
+                count: count.clone(),
+            })
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Returns the approximate size in bytes of a start message's query and variables.
+pub(crate) fn payload_size<S: ScalarValue>(query: &str, variables: &Variables<S>) -> usize {
+    query.len()
+        + variables
+            .iter()
+            .map(|(name, value)| name.len() + input_value_size(value))
+            .sum::<usize>()
+}
+
+fn input_value_size<S: ScalarValue>(value: &InputValue<S>) -> usize {
+    match value {
+        InputValue::Null => 4,
+        InputValue::Scalar(s) => s.to_string().len(),
+        InputValue::Enum(s) | InputValue::Variable(s) => s.len(),
+        InputValue::List(items) => items.iter().map(|i| input_value_size(&i.item)).sum(),
+        InputValue::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| k.item.len() + input_value_size(&v.item))
+            .sum(),
+    }
+}
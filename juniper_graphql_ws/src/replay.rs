@@ -0,0 +1,26 @@
+use crate::{DataPayload, StartPayload};
+use juniper::ScalarValue;
+
+/// ReplayBuffer stores the events of subscriptions so that they can be resumed. It is given to
+/// `ConnectionConfig::with_replay_buffer`, which enables the resumable mode of the connection:
+///
+///   * Every subscription event is stored, and is sent with the returned id as its `eventId`.
+///   * A client that starts a subscription with a `lastEventId`, typically after reconnecting,
+///     first receives the events that followed that one, then the new ones.
+///
+/// The storage is up to the implementation, and is typically shared by all the connections.
+/// Subscriptions are identified by their start payloads: the same query, operation name and
+/// variables designate the same stream of events.
+pub trait ReplayBuffer<S: ScalarValue>: Send + Sync {
+    /// Stores an event of the subscription started with `payload`, returning the id to send it
+    /// with.
+    fn store(&self, payload: &StartPayload<S>, event: &DataPayload<S>) -> String;
+
+    /// Returns the events of the subscription started with `payload` that were stored after the
+    /// one with id `last_event_id`, oldest first, along with their ids.
+    fn replay(
+        &self,
+        payload: &StartPayload<S>,
+        last_event_id: &str,
+    ) -> Vec<(String, DataPayload<S>)>;
+}
@@ -12,7 +12,7 @@ pub struct ConnectionErrorPayload {
 
 /// Sent after execution of an operation. For queries and mutations, this is sent to the client
 /// once. For subscriptions, this is sent for every event in the event stream.
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(bound(serialize = "S: ScalarValue"))]
 #[serde(rename_all = "camelCase")]
 pub struct DataPayload<S> {
@@ -23,6 +23,12 @@ pub struct DataPayload<S> {
     /// not included here. They are sent via Error messages.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<ExecutionError<S>>,
+
+    /// The id of the subscription event, if the connection has a `ReplayBuffer`. A client can
+    /// resume the subscription from this event by sending it back as the `lastEventId` of a start
+    /// message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
 }
 
 /// A payload for errors that can happen before execution. Errors that happen during execution are
@@ -161,12 +167,26 @@ mod test {
                 payload: DataPayload {
                     data: Value::null(),
                     errors: vec![],
+                    event_id: None,
                 },
             })
             .unwrap(),
             r##"{"type":"data","id":"foo","payload":{"data":null}}"##,
         );
 
+        assert_eq!(
+            serde_json::to_string(&ServerMessage::Data {
+                id: "foo".to_string(),
+                payload: DataPayload {
+                    data: Value::null(),
+                    errors: vec![],
+                    event_id: Some("1".to_string()),
+                },
+            })
+            .unwrap(),
+            r##"{"type":"data","id":"foo","payload":{"data":null,"eventId":"1"}}"##,
+        );
+
         assert_eq!(
             serde_json::to_string(&ServerMessage::Error {
                 id: "foo".to_string(),
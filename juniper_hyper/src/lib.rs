@@ -4,11 +4,15 @@
 extern crate reqwest;
 
 use hyper::{
+    body::HttpBody as _,
     header::{self, HeaderValue},
     Body, Method, Request, Response, StatusCode,
 };
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
+    http::{
+        ContextFactory, GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest,
+        GraphQLRequest, HttpError, LimitError, RequestLimits, RequestParts,
+    },
     GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
 };
 use serde_json::error::Error as SerdeError;
@@ -30,7 +34,7 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    Ok(match parse_req(req).await {
+    Ok(match parse_req(req, &RequestLimits::new()).await {
         Ok(req) => execute_request_sync(root_node, context, req).await,
         Err(resp) => resp,
     })
@@ -51,15 +55,83 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    Ok(match parse_req(req).await {
+    Ok(match parse_req(req, &RequestLimits::new()).await {
         Ok(req) => execute_request(root_node, context, req).await,
         Err(resp) => resp,
     })
 }
 
+/// Like [`graphql`](fn.graphql.html), answering the requests exceeding the
+/// limits with `413 Payload Too Large` before they're parsed
+pub async fn graphql_with_limits<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
+    limits: RequestLimits,
+    req: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    Ok(match parse_req(req, &limits).await {
+        Ok(req) => execute_request(root_node, context, req).await,
+        Err(resp) => resp,
+    })
+}
+
+/// Like [`graphql`](fn.graphql.html), building the context of each request
+/// with the provided factory
+pub async fn graphql_with_context_factory<F, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context_factory: Arc<F>,
+    req: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    F: ContextFactory,
+    F::Context: Sync,
+    QueryT: GraphQLTypeAsync<S, Context = F::Context>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = F::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = F::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let parts = request_parts(&req);
+    let context = match context_factory.build(&parts).await {
+        Ok(context) => Arc::new(context),
+        Err(e) => return Ok(render_http_error(&e)),
+    };
+    graphql(root_node, context, req).await
+}
+
+fn request_parts(req: &Request<Body>) -> RequestParts {
+    RequestParts {
+        method: req.method().to_string(),
+        path: req.uri().path().to_owned(),
+        query: req.uri().query().map(ToOwned::to_owned),
+        headers: req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect(),
+    }
+}
+
 async fn parse_req<S: ScalarValue>(
     req: Request<Body>,
+    limits: &RequestLimits,
 ) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
+    let parts = request_parts(&req);
+    limits
+        .check_parts(&parts)
+        .map_err(|e| render_error(GraphQLRequestError::TooLarge(e)))?;
     match *req.method() {
         Method::GET => parse_get_req(req),
         Method::POST => {
@@ -68,16 +140,46 @@ async fn parse_req<S: ScalarValue>(
                 .get(header::CONTENT_TYPE)
                 .map(HeaderValue::to_str);
             match content_type {
-                Some(Ok("application/json")) => parse_post_json_req(req.into_body()).await,
-                Some(Ok("application/graphql")) => parse_post_graphql_req(req.into_body()).await,
+                Some(Ok("application/json")) => read_body(req.into_body(), &parts, limits)
+                    .await
+                    .and_then(parse_post_json_req),
+                Some(Ok("application/graphql")) => read_body(req.into_body(), &parts, limits)
+                    .await
+                    .and_then(parse_post_graphql_req),
                 _ => return Err(new_response(StatusCode::BAD_REQUEST)),
             }
         }
         _ => return Err(new_response(StatusCode::METHOD_NOT_ALLOWED)),
     }
+    .and_then(|req| {
+        limits
+            .check_request(&req)
+            .map_err(GraphQLRequestError::TooLarge)?;
+        Ok(req)
+    })
     .map_err(|e| render_error(e))
 }
 
+/// Reads the body, stopping as soon as it exceeds the body limit
+async fn read_body(
+    mut body: Body,
+    parts: &RequestParts,
+    limits: &RequestLimits,
+) -> Result<Vec<u8>, GraphQLRequestError> {
+    let max = limits.body_limit(parts);
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk.map_err(GraphQLRequestError::BodyHyper)?);
+        if matches!(max, Some(max) if bytes.len() > max) {
+            break;
+        }
+    }
+    limits
+        .check_body(parts, &bytes)
+        .map_err(GraphQLRequestError::TooLarge)?;
+    Ok(bytes)
+}
+
 fn parse_get_req<S: ScalarValue>(
     req: Request<Body>,
 ) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
@@ -91,29 +193,19 @@ fn parse_get_req<S: ScalarValue>(
         })
 }
 
-async fn parse_post_json_req<S: ScalarValue>(
-    body: Body,
+fn parse_post_json_req<S: ScalarValue>(
+    body: Vec<u8>,
 ) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let input = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
+    let input = String::from_utf8(body).map_err(GraphQLRequestError::BodyUtf8)?;
 
     serde_json::from_str::<GraphQLBatchRequest<S>>(&input)
         .map_err(GraphQLRequestError::BodyJSONError)
 }
 
-async fn parse_post_graphql_req<S: ScalarValue>(
-    body: Body,
+fn parse_post_graphql_req<S: ScalarValue>(
+    body: Vec<u8>,
 ) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let query = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
+    let query = String::from_utf8(body).map_err(GraphQLRequestError::BodyUtf8)?;
 
     Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
         query, None, None,
@@ -146,12 +238,27 @@ pub async fn playground(
 }
 
 fn render_error(err: GraphQLRequestError) -> Response<Body> {
+    if let GraphQLRequestError::TooLarge(err) = err {
+        return render_http_error(&err.into());
+    }
     let message = format!("{}", err);
     let mut resp = new_response(StatusCode::BAD_REQUEST);
     *resp.body_mut() = Body::from(message);
     resp
 }
 
+fn render_http_error(err: &HttpError) -> Response<Body> {
+    let mut resp = new_response(
+        StatusCode::from_u16(err.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+    );
+    if let Some(allow) = err.allow() {
+        resp.headers_mut()
+            .insert(header::ALLOW, HeaderValue::from_static(allow));
+    }
+    *resp.body_mut() = Body::from(err.message().to_owned());
+    resp
+}
+
 async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
@@ -179,6 +286,12 @@ where
         header::CONTENT_TYPE,
         HeaderValue::from_static("application/json"),
     );
+    if let Some(policy) = res.cache_policy() {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&policy.to_string()).unwrap(),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
@@ -210,6 +323,12 @@ where
         header::CONTENT_TYPE,
         HeaderValue::from_static("application/json"),
     );
+    if let Some(policy) = res.cache_policy() {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&policy.to_string()).unwrap(),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
@@ -285,6 +404,7 @@ enum GraphQLRequestError {
     BodyJSONError(SerdeError),
     Variables(SerdeError),
     Invalid(String),
+    TooLarge(LimitError),
 }
 
 impl fmt::Display for GraphQLRequestError {
@@ -295,6 +415,7 @@ impl fmt::Display for GraphQLRequestError {
             GraphQLRequestError::BodyJSONError(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLRequestError::Variables(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLRequestError::Invalid(ref err) => fmt::Display::fmt(err, &mut f),
+            GraphQLRequestError::TooLarge(ref err) => fmt::Display::fmt(err, &mut f),
         }
     }
 }
@@ -307,6 +428,7 @@ impl Error for GraphQLRequestError {
             GraphQLRequestError::BodyJSONError(ref err) => Some(err),
             GraphQLRequestError::Variables(ref err) => Some(err),
             GraphQLRequestError::Invalid(_) => None,
+            GraphQLRequestError::TooLarge(ref err) => Some(err),
         }
     }
 }
@@ -315,10 +437,10 @@ impl Error for GraphQLRequestError {
 mod tests {
     use hyper::{
         service::{make_service_fn, service_fn},
-        Body, Method, Response, Server, StatusCode,
+        Body, Method, Request, Response, Server, StatusCode,
     };
     use juniper::{
-        http::tests as http_tests,
+        http::{tests as http_tests, HttpError, RequestLimits, RequestParts},
         tests::fixtures::starwars::{model::Database, schema::Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
@@ -453,4 +575,145 @@ mod tests {
     async fn test_sync_hyper_integration() {
         run_hyper_integration(true).await
     }
+
+    #[tokio::test]
+    async fn test_context_factory() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let factory = Arc::new(|parts: RequestParts| async move {
+            match parts.header("Authorization") {
+                Some("Bearer secret") => Ok(Database::new()),
+                _ => Err(HttpError::unauthorized("Invalid credentials")),
+            }
+        });
+        let request = |authorization: &str| {
+            Request::get("/graphql?query=%7Bhero%7Bname%7D%7D")
+                .header("authorization", authorization)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = super::graphql_with_context_factory(
+            root_node.clone(),
+            factory.clone(),
+            request("Bearer secret"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            &body[..],
+            br#"{
+  "data": {
+    "hero": {
+      "name": "R2-D2"
+    }
+  }
+}"#
+        );
+
+        let resp = super::graphql_with_context_factory(root_node, factory, request("Bearer wrong"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"Invalid credentials");
+    }
+
+    #[tokio::test]
+    async fn test_cache_control() {
+        struct CachedQuery;
+
+        #[juniper::graphql_object]
+        impl CachedQuery {
+            #[graphql(cache_control(max_age = 60))]
+            fn greeting() -> &str {
+                "Hello"
+            }
+
+            fn uncached() -> i32 {
+                1
+            }
+        }
+
+        let root_node = Arc::new(RootNode::new(
+            CachedQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        ));
+        let request = |query: &str| {
+            Request::get(format!("/graphql?query={}", query))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = super::graphql(root_node.clone(), Arc::new(()), request("%7Bgreeting%7D"))
+            .await
+            .unwrap();
+        assert_eq!(resp.headers()["cache-control"], "max-age=60, public");
+
+        let resp = super::graphql(root_node, Arc::new(()), request("%7Buncached%7D"))
+            .await
+            .unwrap();
+        assert!(resp.headers().get("cache-control").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let context = Arc::new(Database::new());
+        let limits = RequestLimits::new().max_body_bytes(64).max_query_bytes(24);
+        let execute =
+            |req| super::graphql_with_limits(root_node.clone(), context.clone(), limits, req);
+
+        let resp = execute(
+            Request::post("/graphql")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"query": "{hero{name}}"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = execute(
+            Request::post("/graphql")
+                .header("content-type", "application/json")
+                .body(Body::from(format!(
+                    r#"{{"query": "{{hero{{name}}}}{}"}}"#,
+                    " ".repeat(64)
+                )))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            &body[..],
+            &b"The body of the request exceeds the limit of 64 bytes"[..],
+        );
+
+        let resp = execute(
+            Request::get("/graphql?query=%7Bhero%7Bname%20appearsIn%20id%7D%7D")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            &body[..],
+            &b"The query of the request exceeds the limit of 24 bytes"[..],
+        );
+    }
 }
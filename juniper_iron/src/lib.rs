@@ -129,8 +129,9 @@ use std::{error::Error, fmt, io::Read, ops::Deref as _};
 use serde_json::error::Error as SerdeError;
 
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
-    ScalarValue,
+    http,
+    http::{GraphQLBatchRequest, HttpError, LimitError, RequestLimits, RequestParts},
+    DefaultScalarValue, GraphQLType, InputValue, RootNode, ScalarValue,
 };
 
 /// Handler that executes `GraphQL` queries in the given schema
@@ -161,6 +162,7 @@ pub struct GraphQLHandler<
 {
     context_factory: CtxFactory,
     root_node: RootNode<'a, Query, Mutation, Subscription, S>,
+    limits: RequestLimits,
 }
 
 /// Handler that renders `GraphiQL` - a graphical query editor interface
@@ -231,9 +233,17 @@ where
         GraphQLHandler {
             context_factory,
             root_node: RootNode::new(query, mutation, subscription),
+            limits: RequestLimits::new(),
         }
     }
 
+    /// Answer the requests exceeding the limits with `413 Payload Too Large`
+    /// before they're parsed
+    pub fn limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     fn handle_get(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
         let url_query = req
             .get_mut::<UrlEncodedQuery>()
@@ -251,9 +261,28 @@ where
         )))
     }
 
-    fn handle_post_json(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
+    fn read_body(&self, req: &mut Request, parts: &RequestParts) -> IronResult<String> {
+        let mut payload = Vec::new();
+        match self.limits.body_limit(parts) {
+            // Stop reading the body as soon as it exceeds the limit.
+            Some(max) => itry!((&mut req.body)
+                .take(max as u64 + 1)
+                .read_to_end(&mut payload)),
+            None => itry!(req.body.read_to_end(&mut payload)),
+        };
+        self.limits
+            .check_body(parts, &payload)
+            .map_err(GraphQLIronError::TooLarge)?;
+
+        Ok(itry!(String::from_utf8(payload)))
+    }
+
+    fn handle_post_json(
+        &self,
+        req: &mut Request,
+        parts: &RequestParts,
+    ) -> IronResult<GraphQLBatchRequest<S>> {
+        let payload = self.read_body(req, parts)?;
 
         Ok(
             serde_json::from_str::<GraphQLBatchRequest<S>>(payload.as_str())
@@ -261,9 +290,12 @@ where
         )
     }
 
-    fn handle_post_graphql(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
+    fn handle_post_graphql(
+        &self,
+        req: &mut Request,
+        parts: &RequestParts,
+    ) -> IronResult<GraphQLBatchRequest<S>> {
+        let payload = self.read_body(req, parts)?;
 
         Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
             payload, None, None,
@@ -283,7 +315,12 @@ where
         } else {
             status::BadRequest
         };
-        Ok(Response::with((content_type, status, json)))
+        let mut resp = Response::with((content_type, status, json));
+        if let Some(policy) = response.cache_policy() {
+            resp.headers
+                .set_raw("cache-control", vec![policy.to_string().into_bytes()]);
+        }
+        Ok(resp)
     }
 }
 
@@ -325,25 +362,46 @@ where
     'a: 'static,
 {
     fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
+        let parts = request_parts(req);
+        self.limits
+            .check_parts(&parts)
+            .map_err(GraphQLIronError::TooLarge)?;
+
         let context = (self.context_factory)(req)?;
 
         let graphql_request = match req.method {
             method::Get => self.handle_get(&mut req)?,
             method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
                 Some(Mime(TopLevel::Application, sub_lvl, _)) => match sub_lvl.as_str() {
-                    "json" => self.handle_post_json(&mut req)?,
-                    "graphql" => self.handle_post_graphql(&mut req)?,
+                    "json" => self.handle_post_json(&mut req, &parts)?,
+                    "graphql" => self.handle_post_graphql(&mut req, &parts)?,
                     _ => return Ok(Response::with(status::BadRequest)),
                 },
                 _ => return Ok(Response::with(status::BadRequest)),
             },
             _ => return Ok(Response::with(status::MethodNotAllowed)),
         };
+        self.limits
+            .check_request(&graphql_request)
+            .map_err(GraphQLIronError::TooLarge)?;
 
         self.execute_sync(&context, graphql_request)
     }
 }
 
+fn request_parts(req: &Request) -> RequestParts {
+    RequestParts {
+        method: req.method.to_string(),
+        path: format!("/{}", req.url.path().join("/")),
+        query: req.url.query().map(ToOwned::to_owned),
+        headers: req
+            .headers
+            .iter()
+            .map(|header| (header.name().to_owned(), header.value_string()))
+            .collect(),
+    }
+}
+
 impl Handler for GraphiQLHandler {
     fn handle(&self, _: &mut Request) -> IronResult<Response> {
         let content_type = "text/html; charset=utf-8".parse::<Mime>().unwrap();
@@ -379,6 +437,7 @@ enum GraphQLIronError {
     Serde(SerdeError),
     Url(UrlDecodingError),
     InvalidData(&'static str),
+    TooLarge(LimitError),
 }
 
 impl fmt::Display for GraphQLIronError {
@@ -387,6 +446,7 @@ impl fmt::Display for GraphQLIronError {
             GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLIronError::InvalidData(err) => fmt::Display::fmt(err, &mut f),
+            GraphQLIronError::TooLarge(ref err) => fmt::Display::fmt(err, &mut f),
         }
     }
 }
@@ -397,6 +457,7 @@ impl Error for GraphQLIronError {
             GraphQLIronError::Serde(ref err) => Some(err),
             GraphQLIronError::Url(ref err) => Some(err),
             GraphQLIronError::InvalidData(_) => None,
+            GraphQLIronError::TooLarge(ref err) => Some(err),
         }
     }
 }
@@ -404,7 +465,13 @@ impl Error for GraphQLIronError {
 impl From<GraphQLIronError> for IronError {
     fn from(err: GraphQLIronError) -> IronError {
         let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
+        let status = match err {
+            GraphQLIronError::TooLarge(ref err) => {
+                status::Status::from_u16(HttpError::from(err.clone()).status())
+            }
+            _ => status::BadRequest,
+        };
+        IronError::new(err, (status, message))
     }
 }
 
@@ -520,6 +587,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_limits() {
+        let handler = GraphQLHandler::new(
+            context_factory,
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        )
+        .limits(RequestLimits::new().max_body_bytes(64).max_query_bytes(24));
+        let mut headers = Headers::new();
+        headers.set(ContentType::json());
+
+        let response = request::post(
+            "http://localhost:3000/",
+            headers.clone(),
+            r#"{"query": "{hero{name}}"}"#,
+            &handler,
+        )
+        .unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+
+        let error = request::post(
+            "http://localhost:3000/",
+            headers,
+            &format!(r#"{{"query": "{{hero{{name}}}}{}"}}"#, " ".repeat(64)),
+            &handler,
+        )
+        .unwrap_err();
+        assert_eq!(error.response.status, Some(status::PayloadTooLarge));
+        assert_eq!(
+            response::extract_body_to_string(error.response),
+            "The body of the request exceeds the limit of 64 bytes",
+        );
+
+        let error = request::get(
+            "http://localhost:3000/?query=%7Bhero%7Bname%20appearsIn%20id%7D%7D",
+            Headers::new(),
+            &handler,
+        )
+        .unwrap_err();
+        assert_eq!(error.response.status, Some(status::PayloadTooLarge));
+        assert_eq!(
+            response::extract_body_to_string(error.response),
+            "The query of the request exceeds the limit of 24 bytes",
+        );
+    }
+
     fn make_handler() -> Box<dyn Handler> {
         Box::new(GraphQLHandler::new(
             context_factory,
@@ -42,7 +42,7 @@ Check the LICENSE file for details.
 use std::io::{Cursor, Read};
 
 use juniper::{
-    http::{self, GraphQLBatchRequest},
+    http::{self, GraphQLBatchRequest, HttpError, LimitError, RequestLimits, RequestParts},
     DefaultScalarValue, FieldError, GraphQLType, InputValue, RootNode, ScalarValue,
 };
 use rocket::{
@@ -52,7 +52,7 @@ use rocket::{
     response::{content, Responder, Response},
     Data,
     Outcome::{Forward, Success},
-    Request,
+    Request, State,
 };
 
 /// Simple wrapper around an incoming GraphQL request
@@ -60,6 +60,10 @@ use rocket::{
 /// See the `http` module for more information. This type can be constructed
 /// automatically from both GET and POST routes by implementing the `FromForm`
 /// and `FromData` traits.
+///
+/// POST requests exceeding the `juniper::http::RequestLimits` managed by the
+/// application, if any, are answered with `413 Payload Too Large` before
+/// they're parsed.
 #[derive(Debug, PartialEq)]
 pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>)
 where
@@ -279,19 +283,54 @@ where
             _ => return Forward(data),
         };
 
-        let mut body = String::new();
-        data.open()
-            .read_to_string(&mut body)
-            .map_err(|e| Err((Status::InternalServerError, format!("{:?}", e))))?;
+        let limits = match req.guard::<State<RequestLimits>>().succeeded() {
+            Some(limits) => *limits,
+            None => RequestLimits::new(),
+        };
+        let parts = request_parts(req);
+        limits.check_parts(&parts).map_err(too_large)?;
+
+        let mut body = Vec::new();
+        let read = match limits.body_limit(&parts) {
+            // Stop reading the body as soon as it exceeds the limit.
+            Some(max) => data.open().take(max as u64 + 1).read_to_end(&mut body),
+            None => data.open().read_to_end(&mut body),
+        };
+        read.map_err(|e| Err((Status::InternalServerError, format!("{:?}", e))))?;
+        limits.check_body(&parts, &body).map_err(too_large)?;
+        let body =
+            String::from_utf8(body).map_err(|e| Err((Status::BadRequest, format!("{}", e))))?;
 
-        Success(GraphQLRequest(if is_json {
+        let request = if is_json {
             serde_json::from_str(&body).map_err(|e| Err((Status::BadRequest, format!("{}", e))))?
         } else {
             GraphQLBatchRequest::Single(http::GraphQLRequest::new(body, None, None))
-        }))
+        };
+        limits.check_request(&request).map_err(too_large)?;
+
+        Success(GraphQLRequest(request))
     }
 }
 
+fn request_parts(req: &Request) -> RequestParts {
+    RequestParts {
+        method: req.method().as_str().to_owned(),
+        path: req.uri().path().to_owned(),
+        query: req.uri().query().map(ToOwned::to_owned),
+        headers: req
+            .headers()
+            .iter()
+            .map(|header| (header.name().to_owned(), header.value().to_owned()))
+            .collect(),
+    }
+}
+
+fn too_large(e: LimitError) -> Result<(Status, String), Data> {
+    let e = HttpError::from(e);
+    let status = Status::from_code(e.status()).unwrap_or(Status::PayloadTooLarge);
+    Err((status, e.message().to_owned()))
+}
+
 impl<'r> Responder<'r> for GraphQLResponse {
     fn respond_to(self, _: &Request) -> Result<Response<'r>, Status> {
         let GraphQLResponse(status, body) = self;
@@ -421,13 +460,13 @@ mod fromform_tests {
 #[cfg(test)]
 mod tests {
     use juniper::{
-        http::tests as http_tests,
+        http::{tests as http_tests, RequestLimits},
         tests::fixtures::starwars::{model::Database, schema::Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
     use rocket::{
         self, get,
-        http::ContentType,
+        http::{ContentType, Status},
         local::{Client, LocalRequest},
         post,
         request::Form,
@@ -513,6 +552,33 @@ mod tests {
         assert_eq!(resp.status_code, 200);
     }
 
+    #[test]
+    fn test_limits() {
+        let rocket =
+            make_rocket().manage(RequestLimits::new().max_body_bytes(64).max_query_bytes(24));
+        let client = Client::new(rocket).expect("valid rocket");
+
+        let req = client
+            .post("/")
+            .header(ContentType::JSON)
+            .body(r#"{"query": "{hero{name}}"}"#);
+        assert_eq!(make_test_response(&req).status_code, 200);
+
+        let req = client.post("/").header(ContentType::JSON).body(format!(
+            r#"{{"query": "{{hero{{name}}}}{}"}}"#,
+            " ".repeat(64)
+        ));
+        let resp = req.dispatch();
+        assert_eq!(resp.status(), Status::PayloadTooLarge);
+
+        let req = client
+            .post("/")
+            .header(ContentType::new("application", "graphql"))
+            .body("{hero{name appearsIn id}}");
+        let resp = req.dispatch();
+        assert_eq!(resp.status(), Status::PayloadTooLarge);
+    }
+
     fn make_rocket() -> Rocket {
         make_rocket_without_routes().mount("/", routes![post_graphql_handler, get_graphql_handler])
     }
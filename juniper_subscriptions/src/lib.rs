@@ -81,7 +81,7 @@ where
         context: &'a CtxT,
     ) -> BoxFuture<'a, Result<Self::Connection, Self::Error>> {
         juniper::http::resolve_into_stream(req, &self.root_node, context)
-            .map_ok(|(stream, errors)| Connection::from_stream(stream, errors))
+            .map_ok(Connection::from_output)
             .boxed()
     }
 }
@@ -111,6 +111,33 @@ where
             stream: whole_responses_stream(stream, errors),
         }
     }
+
+    /// Creates new [`Connection`] from the output of
+    /// [`juniper::resolve_into_stream`], its extensions being sent with the
+    /// first response
+    pub fn from_output(output: ExecutionOutput<S, Value<ValuesStream<'a, S>>>) -> Self {
+        let ExecutionOutput {
+            data,
+            errors,
+            extensions,
+        } = output;
+        let stream = whole_responses_stream(data, errors);
+        if extensions.field_count() == 0 {
+            return Self { stream };
+        }
+
+        let mut extensions = Some(extensions);
+        Self {
+            stream: stream
+                .map(move |mut output| {
+                    if let Some(extensions) = extensions.take() {
+                        output.extensions = extensions;
+                    }
+                    output
+                })
+                .boxed(),
+        }
+    }
 }
 
 impl<'a, S> SubscriptionConnection<S> for Connection<'a, S> where S: ScalarValue + Send + Sync + 'a {}
@@ -146,8 +173,8 @@ where
 {
     if !errors.is_empty() {
         return stream::once(future::ready(ExecutionOutput {
-            data: Value::null(),
             errors,
+            ..ExecutionOutput::from_data(Value::null())
         }))
         .boxed();
     }
@@ -159,8 +186,8 @@ where
         Value::Scalar(s) => Box::pin(s.map(|res| match res {
             Ok(val) => ExecutionOutput::from_data(val),
             Err(err) => ExecutionOutput {
-                data: Value::null(),
                 errors: vec![err],
+                ..ExecutionOutput::from_data(Value::null())
             },
         })),
         Value::List(list) => {
@@ -238,8 +265,8 @@ where
                     });
                     let obj = Object::from_iter(ready_vec_iterator);
                     Poll::Ready(Some(ExecutionOutput {
-                        data: Value::Object(obj),
                         errors,
+                        ..ExecutionOutput::from_data(Value::Object(obj))
                     }))
                 } else {
                     Poll::Pending
@@ -261,11 +288,11 @@ mod whole_responses_stream {
     #[tokio::test]
     async fn with_error() {
         let expected = vec![ExecutionOutput {
-            data: Value::<DefaultScalarValue>::Null,
             errors: vec![ExecutionError::at_origin(FieldError::new(
                 "field error",
                 Value::Null,
             ))],
+            ..ExecutionOutput::from_data(Value::<DefaultScalarValue>::Null)
         }];
         let expected = serde_json::to_string(&expected).unwrap();
 
@@ -413,4 +440,31 @@ mod whole_responses_stream {
 
         assert_eq!(result, expected);
     }
+
+    #[tokio::test]
+    async fn output_extensions() {
+        let extensions: Object<DefaultScalarValue> = vec![("traceId", Value::scalar("abc"))]
+            .into_iter()
+            .collect();
+        let expected = vec![
+            ExecutionOutput {
+                extensions: extensions.clone(),
+                ..ExecutionOutput::from_data(Value::scalar(1))
+            },
+            ExecutionOutput::from_data(Value::scalar(2)),
+        ];
+        let expected = serde_json::to_string(&expected).unwrap();
+
+        let values: Vec<PollResult> = vec![Ok(Value::scalar(1)), Ok(Value::scalar(2))];
+        let output = ExecutionOutput {
+            data: Value::Scalar(Box::pin(stream::iter(values)) as ValuesStream<'_, _>),
+            errors: vec![],
+            extensions,
+        };
+
+        let result = Connection::from_output(output).collect::<Vec<_>>().await;
+        let result = serde_json::to_string(&result).unwrap();
+
+        assert_eq!(result, expected);
+    }
 }
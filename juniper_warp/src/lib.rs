@@ -42,9 +42,13 @@ Check the LICENSE file for details.
 
 use anyhow::anyhow;
 use bytes::Bytes;
-use futures::{FutureExt as _, TryFutureExt};
+use futures::TryFutureExt;
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
+    cache::CachePolicy,
+    http::{
+        ContextFactory, GraphQLBatchRequest, GraphQLRequest, HttpError, LimitError, RequestLimits,
+        RequestParts,
+    },
     ScalarValue,
 };
 use std::{collections::HashMap, str, sync::Arc};
@@ -115,6 +119,30 @@ pub fn make_graphql_filter<Query, Mutation, Subscription, CtxT, S>(
     schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context_extractor: BoxedFilter<(CtxT,)>,
 ) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    make_graphql_filter_with_limits(schema, context_extractor, RequestLimits::new())
+}
+
+/// Make a filter for graphql queries/mutations, like
+/// [`make_graphql_filter`](fn.make_graphql_filter.html), rejecting the
+/// requests exceeding the `limits` before they're parsed.
+///
+/// The requests are rejected with an [`HttpRejection`](struct.HttpRejection.html),
+/// to turn into a `413 Payload Too Large` response with `Filter::recover`.
+pub fn make_graphql_filter_with_limits<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    limits: RequestLimits,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
 where
     Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
     Query::TypeInfo: Send + Sync,
@@ -129,61 +157,91 @@ where
     let post_json_schema = schema.clone();
     let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
+    let parts_filter = request_parts_filter()
+        .and_then(move |parts: RequestParts| async move {
+            limits.check_parts(&parts).map_err(too_large)?;
+            Ok::<_, warp::Rejection>(parts)
+        })
+        .boxed();
+
+    let handle_post_json_request = move |context: CtxT, parts: RequestParts, body: Bytes| {
         let schema = post_json_schema.clone();
         async move {
+            limits.check_body(&parts, &body).map_err(too_large)?;
+            let req = match serde_json::from_slice::<GraphQLBatchRequest<S>>(&body) {
+                Ok(req) => req,
+                Err(e) => return Ok(bad_request(e)),
+            };
+            limits.check_request(&req).map_err(too_large)?;
+
             let resp = req.execute(&schema, &context).await;
 
             Ok::<_, warp::Rejection>(build_response(
                 serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
+                    .map(|json| (json, resp.is_ok(), resp.cache_policy()))
                     .map_err(Into::into),
             ))
         }
     };
     let post_json_filter = warp::post()
+        .and(json_content_type())
         .and(context_extractor.clone())
-        .and(body::json())
+        .and(parts_filter.clone())
+        .and(body::bytes())
         .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
+    let handle_post_graphql_request = move |context: CtxT, parts: RequestParts, body: Bytes| {
         let schema = post_graphql_schema.clone();
         async move {
-            let query = str::from_utf8(body.as_ref())
-                .map_err(|e| anyhow!("Request body query is not a valid UTF-8 string: {}", e))?;
-            let req = GraphQLRequest::new(query.into(), None, None);
+            limits.check_body(&parts, &body).map_err(too_large)?;
+            let query = match str::from_utf8(body.as_ref()) {
+                Ok(query) => query,
+                Err(e) => {
+                    return Ok(bad_request(format!(
+                        "Request body query is not a valid UTF-8 string: {}",
+                        e,
+                    )))
+                }
+            };
+            let req = GraphQLBatchRequest::Single(GraphQLRequest::new(query.into(), None, None));
+            limits.check_request(&req).map_err(too_large)?;
 
             let resp = req.execute(&schema, &context).await;
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok::<_, warp::Rejection>(build_response(
+                serde_json::to_vec(&resp)
+                    .map(|json| (json, resp.is_ok(), resp.cache_policy()))
+                    .map_err(Into::into),
+            ))
         }
-        .then(|res| async { Ok::<_, warp::Rejection>(build_response(res)) })
     };
     let post_graphql_filter = warp::post()
         .and(context_extractor.clone())
+        .and(parts_filter.clone())
         .and(body::bytes())
         .and_then(handle_post_graphql_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+    let handle_get_request = move |context: CtxT, _: RequestParts, qry: HashMap<String, String>| {
         let schema = schema.clone();
         async move {
-            let req = GraphQLRequest::new(
-                qry.remove("query")
-                    .ok_or_else(|| anyhow!("Missing GraphQL query string in query parameters"))?,
-                qry.remove("operation_name"),
-                qry.remove("variables")
-                    .map(|vs| serde_json::from_str(&vs))
-                    .transpose()?,
-            );
+            let req = match get_request_from_query(qry) {
+                Ok(req) => GraphQLBatchRequest::Single(req),
+                Err(e) => return Ok(build_response(Err(e))),
+            };
+            limits.check_request(&req).map_err(too_large)?;
 
             let resp = req.execute(&schema, &context).await;
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok::<_, warp::Rejection>(build_response(
+                serde_json::to_vec(&resp)
+                    .map(|json| (json, resp.is_ok(), resp.cache_policy()))
+                    .map_err(Into::into),
+            ))
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
     };
     let get_filter = warp::get()
         .and(context_extractor)
+        .and(parts_filter)
         .and(query::query())
         .and_then(handle_get_request);
 
@@ -195,6 +253,120 @@ where
         .boxed()
 }
 
+fn get_request_from_query<S: ScalarValue>(
+    mut qry: HashMap<String, String>,
+) -> anyhow::Result<GraphQLRequest<S>> {
+    Ok(GraphQLRequest::new(
+        qry.remove("query")
+            .ok_or_else(|| anyhow!("Missing GraphQL query string in query parameters"))?,
+        qry.remove("operation_name"),
+        qry.remove("variables")
+            .map(|vs| serde_json::from_str(&vs))
+            .transpose()?,
+    ))
+}
+
+/// Rejection of requests exceeding the limits of
+/// [`make_graphql_filter_with_limits`](fn.make_graphql_filter_with_limits.html),
+/// or whose GraphQL context couldn't be built by the factory of
+/// [`make_context_filter`](fn.make_context_filter.html).
+#[derive(Debug)]
+pub struct HttpRejection(HttpError);
+
+impl HttpRejection {
+    /// The reason the request was rejected, holding the status code and
+    /// message to respond with.
+    pub fn error(&self) -> &HttpError {
+        &self.0
+    }
+}
+
+impl warp::reject::Reject for HttpRejection {}
+
+fn too_large(e: LimitError) -> warp::Rejection {
+    warp::reject::custom(HttpRejection(e.into()))
+}
+
+fn bad_request<E: ToString>(e: E) -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(http::StatusCode::BAD_REQUEST)
+        .body(e.to_string().into_bytes())
+        .expect("status code is valid")
+}
+
+/// Rejects the requests with a content type other than JSON, like
+/// `warp::body::json`, so that the other filters can accept them.
+fn json_content_type() -> BoxedFilter<()> {
+    warp::header::optional::<String>("content-type")
+        .and_then(|content_type: Option<String>| async move {
+            match content_type {
+                Some(content_type)
+                    if !content_type
+                        .split(';')
+                        .next()
+                        .unwrap_or("")
+                        .trim()
+                        .eq_ignore_ascii_case("application/json") =>
+                {
+                    Err(warp::reject())
+                }
+                _ => Ok(()),
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
+
+/// Extracts the parts of the request shared by all the integrations
+fn request_parts_filter() -> BoxedFilter<(RequestParts,)> {
+    warp::method()
+        .and(warp::path::full())
+        .and(query::raw().map(Some).or(warp::any().map(|| None)).unify())
+        .and(warp::header::headers_cloned())
+        .map(
+            |method: http::Method,
+             path: warp::path::FullPath,
+             query: Option<String>,
+             headers: http::HeaderMap| RequestParts {
+                method: method.to_string(),
+                path: path.as_str().to_owned(),
+                query,
+                headers: headers
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                    })
+                    .collect(),
+            },
+        )
+        .boxed()
+}
+
+/// Make a filter building the GraphQL context of each request with the
+/// provided factory, to pass to [`make_graphql_filter`](fn.make_graphql_filter.html).
+///
+/// Requests whose context can't be built are rejected with a
+/// [`HttpRejection`](struct.HttpRejection.html), to turn into a
+/// response with `Filter::recover`.
+pub fn make_context_filter<F>(context_factory: F) -> BoxedFilter<(F::Context,)>
+where
+    F: ContextFactory + 'static,
+    F::Context: Send + 'static,
+{
+    let context_factory = Arc::new(context_factory);
+    request_parts_filter()
+        .and_then(move |parts: RequestParts| {
+            let context_factory = context_factory.clone();
+            async move {
+                context_factory
+                    .build(&parts)
+                    .await
+                    .map_err(|e| warp::reject::custom(HttpRejection(e)))
+            }
+        })
+        .boxed()
+}
+
 /// Make a synchronous filter for graphql endpoint.
 pub fn make_graphql_filter_sync<Query, Mutation, Subscription, CtxT, S>(
     schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
@@ -216,7 +388,11 @@ where
         async move {
             let res = task::spawn_blocking(move || {
                 let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+                Ok((
+                    serde_json::to_vec(&resp)?,
+                    resp.is_ok(),
+                    resp.cache_policy(),
+                ))
             })
             .await?;
 
@@ -238,7 +414,11 @@ where
                 let req = GraphQLRequest::new(query.into(), None, None);
 
                 let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+                Ok((
+                    serde_json::to_vec(&resp)?,
+                    resp.is_ok(),
+                    resp.cache_policy(),
+                ))
             })
             .await?;
 
@@ -266,7 +446,11 @@ where
                 );
 
                 let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+                Ok((
+                    serde_json::to_vec(&resp)?,
+                    resp.is_ok(),
+                    resp.cache_policy(),
+                ))
             })
             .await?;
 
@@ -295,13 +479,19 @@ pub struct JoinError(task::JoinError);
 
 impl warp::reject::Reject for JoinError {}
 
-fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Response<Vec<u8>> {
+fn build_response(
+    response: Result<(Vec<u8>, bool, Option<CachePolicy>), anyhow::Error>,
+) -> http::Response<Vec<u8>> {
     match response {
-        Ok((body, is_ok)) => http::Response::builder()
-            .status(if is_ok { 200 } else { 400 })
-            .header("content-type", "application/json")
-            .body(body)
-            .expect("response is valid"),
+        Ok((body, is_ok, cache_policy)) => {
+            let mut builder = http::Response::builder()
+                .status(if is_ok { 200 } else { 400 })
+                .header("content-type", "application/json");
+            if let Some(policy) = cache_policy {
+                builder = builder.header("cache-control", policy.to_string());
+            }
+            builder.body(body).expect("response is valid")
+        }
         Err(_) => http::Response::builder()
             .status(http::StatusCode::INTERNAL_SERVER_ERROR)
             .body(Vec::new())
@@ -682,6 +872,81 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn limits_are_checked_before_parsing() {
+        use juniper::{
+            tests::fixtures::starwars::{model::Database, schema::Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        type Schema =
+            juniper::RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let state = warp::any().map(Database::new);
+        let limits = RequestLimits::new().max_body_bytes(64).max_query_bytes(24);
+        let filter = warp::path("graphql2")
+            .and(make_graphql_filter_with_limits(
+                schema,
+                state.boxed(),
+                limits,
+            ))
+            .recover(|rejection: warp::Rejection| async move {
+                match rejection.find::<HttpRejection>() {
+                    Some(HttpRejection(e)) => Ok(http::Response::builder()
+                        .status(e.status())
+                        .body(e.to_string().into_bytes())
+                        .unwrap()),
+                    None => Err(rejection),
+                }
+            });
+
+        let response = request()
+            .method("POST")
+            .path("/graphql2")
+            .header("content-type", "application/json")
+            .body(r#"{"query": "{hero{name}}"}"#)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            String::from_utf8(response.body().to_vec()).unwrap(),
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#
+        );
+
+        let response = request()
+            .method("POST")
+            .path("/graphql2")
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"query": "{{hero{{name}}}}{}"}}"#,
+                " ".repeat(64)
+            ))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            String::from_utf8(response.body().to_vec()).unwrap(),
+            "The body of the request exceeds the limit of 64 bytes",
+        );
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7Bhero%7Bname%20appearsIn%20id%7D%7D")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            String::from_utf8(response.body().to_vec()).unwrap(),
+            "The query of the request exceeds the limit of 24 bytes",
+        );
+    }
+
     #[test]
     fn batch_request_deserialization_can_fail() {
         let json = r#"blah"#;